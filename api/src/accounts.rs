@@ -316,7 +316,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             }
             AcceptType::Bcs => {
                 // Put resources in a BTreeMap to ensure they're ordered the same every time
@@ -326,7 +326,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             }
         }
     }
@@ -388,7 +388,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             }
             AcceptType::Bcs => {
                 // Sort modules by name
@@ -401,7 +401,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             }
         }
     }
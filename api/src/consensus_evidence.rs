@@ -0,0 +1,26 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::context::Context;
+use consensus_types::evidence::Evidence;
+use poem::{
+    handler,
+    web::{Data, Json},
+};
+
+#[handler]
+pub fn get_consensus_evidence_poem(
+    context: Data<&std::sync::Arc<Context>>,
+) -> poem::Result<Json<Vec<Evidence>>> {
+    if context.consensus_evidence_enabled() {
+        let evidence = context
+            .evidence_reader()
+            .get_evidence()
+            .map_err(poem::Error::from)?;
+        Ok(Json(evidence))
+    } else {
+        Err(poem::Error::from(anyhow::anyhow!(
+            "Consensus evidence is not enabled at a config level"
+        )))
+    }
+}
@@ -15,7 +15,9 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::error;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    MempoolClientRequest, MempoolClientSender, MinAcceptedGasPrice, SubmissionStatus,
+};
 use aptos_state_view::StateView;
 use aptos_types::access_path::{AccessPath, Path};
 use aptos_types::account_config::NewBlockEvent;
@@ -33,15 +35,28 @@ use aptos_types::{
     transaction::{SignedTransaction, TransactionWithProof, Version},
 };
 use aptos_vm::data_cache::{IntoMoveResolver, StorageAdapter, StorageAdapterOwned};
-use futures::{channel::oneshot, SinkExt};
+use consensus_types::evidence::EvidenceReader;
+use event_notifications::{EventNotification, EventNotificationListener};
+use futures::{channel::oneshot, SinkExt, StreamExt};
 use itertools::Itertools;
-use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use std::sync::RwLock;
 use std::{collections::HashMap, sync::Arc};
 use storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order, MAX_REQUEST_LIMIT,
 };
+use tokio::{
+    runtime::Handle,
+    sync::{broadcast, OwnedSemaphorePermit, Semaphore},
+};
+
+// Maximum number of event notifications buffered for each event stream. Streams that fall
+// further behind catch up from the DB.
+const EVENT_STREAM_CHANNEL_SIZE: usize = 100;
 
 // Context holds application scope context
 #[derive(Clone)]
@@ -52,6 +67,9 @@ pub struct Context {
     pub node_config: NodeConfig,
     gas_estimation: Arc<RwLock<GasEstimationCache>>,
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
+    event_notifications: Option<broadcast::Sender<Arc<EventNotification>>>,
+    event_stream_permits: Arc<Semaphore>,
+    evidence_reader: EvidenceReader,
 }
 
 impl std::fmt::Debug for Context {
@@ -67,6 +85,7 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let event_stream_permits = Arc::new(Semaphore::new(node_config.api.max_event_streams));
         Self {
             chain_id,
             db,
@@ -83,9 +102,57 @@ impl Context {
                 last_updated_epoch: None,
                 gas_schedule_params: None,
             })),
+            event_notifications: None,
+            event_stream_permits,
+            evidence_reader: EvidenceReader::new(),
         }
     }
 
+    /// Sets the reader of the evidence of misbehaving validators recorded by consensus
+    pub fn set_evidence_reader(&mut self, evidence_reader: EvidenceReader) {
+        self.evidence_reader = evidence_reader;
+    }
+
+    pub fn evidence_reader(&self) -> &EvidenceReader {
+        &self.evidence_reader
+    }
+
+    /// Forwards the notifications of committed events to the event streaming APIs
+    pub fn enable_event_streaming(
+        &mut self,
+        runtime_handle: &Handle,
+        mut listener: EventNotificationListener,
+    ) {
+        let (sender, _) = broadcast::channel(EVENT_STREAM_CHANNEL_SIZE);
+        let event_sender = sender.clone();
+        runtime_handle.spawn(async move {
+            while let Some(notification) = listener.next().await {
+                // Sending only fails if no client is currently streaming events
+                let _ = event_sender.send(Arc::new(notification));
+            }
+        });
+        self.event_notifications = Some(sender);
+    }
+
+    /// Returns a receiver of committed event notifications, if event streaming is enabled
+    pub fn subscribe_to_event_notifications(
+        &self,
+    ) -> Option<broadcast::Receiver<Arc<EventNotification>>> {
+        self.event_notifications
+            .as_ref()
+            .map(|sender| sender.subscribe())
+    }
+
+    /// Reserves one of the event streams that may be served at once, if any is left. The
+    /// stream is released when the permit is dropped.
+    pub fn try_acquire_event_stream_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.event_stream_permits.clone().try_acquire_owned().ok()
+    }
+
+    pub fn max_event_stream_lookback_versions(&self) -> u64 {
+        self.node_config.api.max_event_stream_lookback_versions
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
         self.node_config.api.max_transactions_page_size
     }
@@ -137,6 +204,10 @@ impl Context {
         self.node_config.api.failpoints_enabled
     }
 
+    pub fn consensus_evidence_enabled(&self) -> bool {
+        self.node_config.api.consensus_evidence_enabled
+    }
+
     pub fn max_submit_transaction_batch_size(&self) -> usize {
         self.node_config.api.max_submit_transaction_batch_size
     }
@@ -654,6 +725,36 @@ impl Context {
             .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
     }
 
+    pub fn get_transactions_by_function<E: InternalError>(
+        &self,
+        module_id: &ModuleId,
+        function: &IdentStr,
+        success: Option<bool>,
+        sender: Option<AccountAddress>,
+        start_version: u64,
+        limit: u16,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Vec<TransactionOnChainData>, E> {
+        let ledger_version = ledger_info.version();
+        self.db
+            .get_transaction_versions_by_function(
+                module_id,
+                function,
+                success,
+                sender,
+                start_version,
+                limit as u64,
+                ledger_version,
+            )
+            .context("Failed to retrieve transactions by function")
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))?
+            .into_iter()
+            .map(|version| self.get_transaction_by_version(version, ledger_version))
+            .collect::<Result<Vec<_>>>()
+            .context("Failed to parse transactions by function")
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
+    }
+
     pub fn get_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -680,6 +781,19 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    /// Returns the minimum gas unit price mempool currently accepts.
+    pub async fn get_mempool_min_gas_price(&self) -> Result<MinAcceptedGasPrice> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetMinAcceptedGasPrice(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -740,6 +854,23 @@ impl Context {
         }
     }
 
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: u64,
+        start_index: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        self.db.get_events_by_type(
+            type_tag,
+            start_version,
+            start_index,
+            limit as u64,
+            ledger_version,
+        )
+    }
+
     pub fn estimate_gas_price<E: InternalError>(
         &self,
         ledger_info: &LedgerInfo,
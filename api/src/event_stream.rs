@@ -0,0 +1,264 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Streams of committed events for the event streaming APIs.
+//!
+//! Event notifications from state sync are only used to wake streams up: events are always
+//! read from the DB, so a stream that falls behind the notifications, or resumes from a cursor,
+//! never misses events. A stream that fails to read events sends an error message and ends.
+
+use crate::context::Context;
+use anyhow::Result;
+use aptos_api_types::{
+    AptosError, AptosErrorCode, AsConverter, EventStreamMessage, VersionedEvent,
+};
+use aptos_logger::warn;
+use aptos_types::{
+    contract_event::{ContractEvent, EventWithVersion},
+    event::EventKey,
+    transaction::Version,
+};
+use event_notifications::EventNotification;
+use futures::stream::{self, BoxStream, StreamExt};
+use move_core_types::language_storage::TypeTag;
+use std::{collections::VecDeque, sync::Arc};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    OwnedSemaphorePermit,
+};
+
+type EventNotificationReceiver = broadcast::Receiver<Arc<EventNotification>>;
+
+/// A subscription to committed event notifications, holding one of the event streams that may
+/// be served at once until the stream ends.
+pub struct EventStreamSubscription {
+    pub receiver: EventNotificationReceiver,
+    pub permit: OwnedSemaphorePermit,
+}
+
+/// Streams the events of `event_key` in sequence number order, starting at sequence number
+/// `start`, or at the next emitted event if `start` is not given.
+pub fn stream_events_by_key(
+    context: Arc<Context>,
+    subscription: EventStreamSubscription,
+    event_key: EventKey,
+    start: Option<u64>,
+) -> Result<BoxStream<'static, EventStreamMessage>> {
+    let next_sequence_number = match start {
+        Some(start) => start,
+        None => {
+            let ledger_version = context.get_latest_ledger_info_wrapped()?.version();
+            context
+                .get_events(&event_key, None, 1, ledger_version)?
+                .last()
+                .map_or(0, |event| event.event.sequence_number() + 1)
+        }
+    };
+    let event_stream = EventKeyStream {
+        context,
+        subscription,
+        event_key,
+        next_sequence_number,
+        pending: VecDeque::new(),
+        ended: false,
+    };
+    Ok(stream::unfold(event_stream, |mut event_stream| async move {
+        event_stream
+            .next()
+            .await
+            .map(|message| (message, event_stream))
+    })
+    .boxed())
+}
+
+/// Streams the events of type `type_tag` in version order, starting at version `start_version`,
+/// or after the latest committed version if `start_version` is not given. Events are read
+/// through the internal indexer.
+pub fn stream_events_by_type(
+    context: Arc<Context>,
+    subscription: EventStreamSubscription,
+    type_tag: TypeTag,
+    start_version: Option<Version>,
+) -> Result<BoxStream<'static, EventStreamMessage>> {
+    let next_version = match start_version {
+        Some(start_version) => start_version,
+        None => context.get_latest_ledger_info_wrapped()?.version() + 1,
+    };
+    let event_stream = EventTypeStream {
+        context,
+        subscription,
+        type_tag,
+        next_version,
+        next_index: 0,
+        pending: VecDeque::new(),
+        ended: false,
+    };
+    Ok(stream::unfold(event_stream, |mut event_stream| async move {
+        event_stream
+            .next()
+            .await
+            .map(|message| (message, event_stream))
+    })
+    .boxed())
+}
+
+struct EventKeyStream {
+    context: Arc<Context>,
+    subscription: EventStreamSubscription,
+    event_key: EventKey,
+    next_sequence_number: u64,
+    pending: VecDeque<VersionedEvent>,
+    ended: bool,
+}
+
+impl EventKeyStream {
+    async fn next(&mut self) -> Option<EventStreamMessage> {
+        loop {
+            if self.ended {
+                return None;
+            }
+            if let Some(event) = self.pending.pop_front() {
+                return Some(EventStreamMessage::Event(event));
+            }
+            match self.fetch_committed_events() {
+                Ok(true) => continue,
+                Ok(false) => (),
+                Err(err) => {
+                    warn!("Failed to stream events of {}: {:?}", self.event_key, err);
+                    self.ended = true;
+                    return Some(error_message(err));
+                }
+            }
+            let event_key = self.event_key;
+            if !wait_for_events(&mut self.subscription.receiver, |event| {
+                *event.key() == event_key
+            })
+            .await
+            {
+                return None;
+            }
+        }
+    }
+
+    /// Reads the next page of committed events into the pending events, returning whether
+    /// there were any.
+    fn fetch_committed_events(&mut self) -> Result<bool> {
+        let ledger_version = self.context.get_latest_ledger_info_wrapped()?.version();
+        let events = self.context.get_events(
+            &self.event_key,
+            Some(self.next_sequence_number),
+            self.context.max_events_page_size(),
+            ledger_version,
+        )?;
+        if let Some(last_event) = events.last() {
+            self.next_sequence_number = last_event.event.sequence_number() + 1;
+        }
+        self.pending.extend(convert_events(&self.context, &events)?);
+        Ok(!events.is_empty())
+    }
+}
+
+struct EventTypeStream {
+    context: Arc<Context>,
+    subscription: EventStreamSubscription,
+    type_tag: TypeTag,
+    // Position of the next event to stream, as its version and index among the events of the
+    // transaction, like the cursor of `/events/by_type`.
+    next_version: Version,
+    next_index: u64,
+    pending: VecDeque<VersionedEvent>,
+    ended: bool,
+}
+
+impl EventTypeStream {
+    async fn next(&mut self) -> Option<EventStreamMessage> {
+        loop {
+            if self.ended {
+                return None;
+            }
+            if let Some(event) = self.pending.pop_front() {
+                return Some(EventStreamMessage::Event(event));
+            }
+            match self.fetch_committed_events() {
+                Ok(true) => continue,
+                Ok(false) => (),
+                Err(err) => {
+                    warn!(
+                        "Failed to stream events of type {}: {:?}",
+                        self.type_tag, err
+                    );
+                    self.ended = true;
+                    return Some(error_message(err));
+                }
+            }
+            let type_tag = self.type_tag.clone();
+            if !wait_for_events(&mut self.subscription.receiver, |event| {
+                *event.type_tag() == type_tag
+            })
+            .await
+            {
+                return None;
+            }
+        }
+    }
+
+    /// Reads the next page of committed events of the streamed type from the index into the
+    /// pending events, returning whether there were any.
+    fn fetch_committed_events(&mut self) -> Result<bool> {
+        let ledger_version = self.context.get_latest_ledger_info_wrapped()?.version();
+        if self.next_version > ledger_version {
+            return Ok(false);
+        }
+        let events = self.context.get_events_by_type(
+            &self.type_tag,
+            self.next_version,
+            self.next_index,
+            self.context.max_events_page_size(),
+            ledger_version,
+        )?;
+        if let Some((index, last_event)) = events.last() {
+            self.next_version = last_event.transaction_version;
+            self.next_index = index + 1;
+        }
+        let events: Vec<_> = events.into_iter().map(|(_index, event)| event).collect();
+        self.pending.extend(convert_events(&self.context, &events)?);
+        Ok(!events.is_empty())
+    }
+}
+
+/// Waits until a notification contains a matching event, returning false once no more
+/// notifications will be sent.
+async fn wait_for_events(
+    receiver: &mut EventNotificationReceiver,
+    matches: impl Fn(&ContractEvent) -> bool,
+) -> bool {
+    loop {
+        match receiver.recv().await {
+            Ok(notification) => {
+                if notification.subscribed_events.iter().any(&matches) {
+                    return true;
+                }
+            }
+            // Notifications were dropped, so check the DB for any missed events
+            Err(RecvError::Lagged(_)) => return true,
+            Err(RecvError::Closed) => return false,
+        }
+    }
+}
+
+fn convert_events(context: &Context, events: &[EventWithVersion]) -> Result<Vec<VersionedEvent>> {
+    if events.is_empty() {
+        return Ok(vec![]);
+    }
+    context
+        .move_resolver()?
+        .as_converter(context.db.clone())
+        .try_into_versioned_events(events)
+}
+
+fn error_message(err: anyhow::Error) -> EventStreamMessage {
+    EventStreamMessage::Error(AptosError::new_with_error_code(
+        err,
+        AptosErrorCode::InternalError,
+    ))
+}
@@ -4,21 +4,27 @@
 use crate::accept_type::AcceptType;
 use crate::accounts::Account;
 use crate::context::Context;
+use crate::event_stream::{stream_events_by_key, stream_events_by_type, EventStreamSubscription};
 use crate::failpoint::fail_point_poem;
 use crate::page::Page;
 use crate::response::BadRequestError;
 use crate::response::{
-    BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404, InternalError,
+    api_disabled, version_pruned, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+    BasicResultWith404, InternalError, ServiceUnavailableError,
 };
 use crate::ApiTags;
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    verify_field_identifier, Address, AptosErrorCode, AsConverter, EventCursor, EventStreamMessage,
+    IdentifierWrapper, LedgerInfo, MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
-use aptos_types::event::EventKey;
+use aptos_types::{contract_event::EventWithVersion, event::EventKey};
+use futures::stream::BoxStream;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::param::Query;
+use poem_openapi::payload::EventStream;
 use poem_openapi::{param::Path, OpenApi};
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 pub struct EventsApi {
@@ -135,9 +141,309 @@ impl EventsApi {
         let key = account.find_event_key(event_handle.0, field_name.0.into())?;
         self.list(account.latest_ledger_info, accept_type, page, key)
     }
+
+    /// Get events by event type
+    ///
+    /// This API returns events of the given `event_type` in ledger version order,
+    /// regardless of the event handle they were emitted to. It requires the node's
+    /// internal indexer, and only covers the versions committed since the indexer
+    /// started indexing events by type. To get the next page, pass the cursor
+    /// returned in the X-Aptos-Cursor header as `start`.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_event_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_event_type(
+        &self,
+        accept_type: AcceptType,
+        /// Name of the event struct e.g. `0x1::coin::DepositEvent`
+        event_type: Path<MoveStructTag>,
+        /// Starting ledger version of events, or the cursor returned in the
+        /// X-Aptos-Cursor header of the previous page.
+        ///
+        /// If unspecified, by default will retrieve events from the oldest ledger
+        /// version available
+        start: Query<Option<EventCursor>>,
+        /// Max number of events to retrieve.
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_events_by_event_type")?;
+        self.context
+            .check_api_output_enabled("Get events by event type", &accept_type)?;
+        if !self.context.db.indexer_enabled() {
+            return Err(api_disabled("Get events by event type"));
+        }
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let struct_tag = StructTag::try_from(event_type.0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &latest_ledger_info,
+                )
+            })?;
+        let page = Page::new(None, limit.0, self.context.max_events_page_size());
+        let limit = page.limit(&latest_ledger_info)?;
+        let start = start.0.unwrap_or(EventCursor {
+            version: latest_ledger_info.oldest_ledger_version.0,
+            index: 0,
+        });
+        if start.version < latest_ledger_info.oldest_ledger_version.0 {
+            return Err(version_pruned(start.version, &latest_ledger_info));
+        }
+
+        let events = self
+            .context
+            .get_events_by_type(
+                &TypeTag::Struct(Box::new(struct_tag)),
+                start.version,
+                start.index,
+                limit,
+                latest_ledger_info.version(),
+            )
+            .context("Failed to find events by type")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+        // A full page may be followed by more events.
+        let next_cursor = if events.len() == limit as usize {
+            events.last().map(|(index, event)| EventCursor {
+                version: event.transaction_version,
+                index: index + 1,
+            })
+        } else {
+            None
+        };
+        let events = events.into_iter().map(|(_index, event)| event).collect();
+        self.render(latest_ledger_info, accept_type, events)
+            .map(|v| v.with_cursor(next_cursor))
+    }
+
+    /// Stream events by creation number
+    ///
+    /// Streams the events of the event type identified by the account `address`
+    /// and `creation_number` as server-sent events, pushing new events as they
+    /// are committed. Streams can be resumed by passing the sequence number
+    /// following the last received event as `start`.
+    #[oai(
+        path = "/accounts/:address/event_streams/:creation_number",
+        method = "get",
+        operation_id = "stream_events_by_creation_number",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events_by_creation_number(
+        &self,
+        /// Hex-encoded 32 byte Aptos account, with or without a `0x` prefix, for
+        /// which events are streamed. This refers to the account that events were
+        /// emitted to, not the account hosting the move module that emits that
+        /// event type.
+        address: Path<Address>,
+        /// Creation number corresponding to the event stream originating
+        /// from the given account.
+        creation_number: Path<U64>,
+        /// Starting sequence number of events.
+        ///
+        /// If unspecified, by default will only stream new events
+        start: Query<Option<U64>>,
+    ) -> Result<EventStream<BoxStream<'static, EventStreamMessage>>, BasicErrorWith404> {
+        fail_point_poem("endpoint_stream_events_by_creation_number")?;
+        let account = Account::new(self.context.clone(), address.0, None, None, None)?;
+        account.get_account_resource()?;
+        self.stream_by_key(
+            account.latest_ledger_info,
+            EventKey::new(creation_number.0 .0, address.0.into()),
+            start.0.map(|v| v.0),
+        )
+    }
+
+    /// Stream events by event handle
+    ///
+    /// Streams the events emitted to the event handle found in the given account
+    /// `address`, `event_handle` and `field_name` as server-sent events, pushing
+    /// new events as they are committed. Streams can be resumed by passing the
+    /// sequence number following the last received event as `start`.
+    #[oai(
+        path = "/accounts/:address/event_streams/:event_handle/:field_name",
+        method = "get",
+        operation_id = "stream_events_by_event_handle",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events_by_event_handle(
+        &self,
+        /// Hex-encoded 32 byte Aptos account, with or without a `0x` prefix, for
+        /// which events are streamed. This refers to the account that events were
+        /// emitted to, not the account hosting the move module that emits that
+        /// event type.
+        address: Path<Address>,
+        /// Name of struct to lookup event handle e.g. `0x1::account::Account`
+        event_handle: Path<MoveStructTag>,
+        /// Name of field to lookup event handle e.g. `withdraw_events`
+        field_name: Path<IdentifierWrapper>,
+        /// Starting sequence number of events.
+        ///
+        /// If unspecified, by default will only stream new events
+        start: Query<Option<U64>>,
+    ) -> Result<EventStream<BoxStream<'static, EventStreamMessage>>, BasicErrorWith404> {
+        event_handle
+            .0
+            .verify(0)
+            .context("'event_handle' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        verify_field_identifier(field_name.as_str())
+            .context("'field_name' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_stream_events_by_event_handle")?;
+        let account = Account::new(self.context.clone(), address.0, None, None, None)?;
+        let key = account.find_event_key(event_handle.0, field_name.0.into())?;
+        self.stream_by_key(account.latest_ledger_info, key, start.0.map(|v| v.0))
+    }
+
+    /// Stream events by event type
+    ///
+    /// Streams all events of the given `event_type`, regardless of the event
+    /// handle they are emitted to, as server-sent events, pushing new events as
+    /// they are committed. Streams can be resumed by passing the version
+    /// following the last received event as `start_version`. It requires the
+    /// node's internal indexer, and `start_version` can only go back a limited
+    /// number of versions from the latest version.
+    #[oai(
+        path = "/event_streams/:event_type",
+        method = "get",
+        operation_id = "stream_events_by_event_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn stream_events_by_event_type(
+        &self,
+        /// Name of the event struct e.g. `0x1::coin::DepositEvent`
+        event_type: Path<MoveStructTag>,
+        /// Starting ledger version of events.
+        ///
+        /// If unspecified, by default will only stream new events
+        start_version: Query<Option<U64>>,
+    ) -> Result<EventStream<BoxStream<'static, EventStreamMessage>>, BasicErrorWith404> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_stream_events_by_event_type")?;
+        if !self.context.db.indexer_enabled() {
+            return Err(api_disabled("Stream events by event type"));
+        }
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let struct_tag = StructTag::try_from(event_type.0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &latest_ledger_info,
+                )
+            })?;
+        if let Some(start_version) = start_version.0 {
+            let start_version = start_version.0;
+            if start_version < latest_ledger_info.oldest_ledger_version.0 {
+                return Err(version_pruned(start_version, &latest_ledger_info));
+            }
+            let lookback_versions = self.context.max_event_stream_lookback_versions();
+            if start_version.saturating_add(lookback_versions) < latest_ledger_info.version() {
+                return Err(BasicErrorWith404::bad_request_with_code(
+                    format!(
+                        "'start_version' {} is more than {} versions behind the latest version",
+                        start_version, lookback_versions
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    &latest_ledger_info,
+                ));
+            }
+        }
+        let subscription = self.subscribe_to_event_notifications()?;
+        let stream = stream_events_by_type(
+            self.context.clone(),
+            subscription,
+            TypeTag::Struct(Box::new(struct_tag)),
+            start_version.0.map(|v| v.0),
+        )
+        .context("Failed to stream events by type")
+        .map_err(|err| {
+            BasicErrorWith404::internal_with_code(
+                err,
+                AptosErrorCode::InternalError,
+                &latest_ledger_info,
+            )
+        })?;
+        Ok(EventStream::new(stream))
+    }
 }
 
 impl EventsApi {
+    fn subscribe_to_event_notifications(
+        &self,
+    ) -> Result<EventStreamSubscription, BasicErrorWith404> {
+        if !self.context.node_config.api.event_stream_enabled {
+            return Err(api_disabled("Event stream"));
+        }
+        let receiver = self
+            .context
+            .subscribe_to_event_notifications()
+            .ok_or_else(|| api_disabled("Event stream"))?;
+        let permit = self
+            .context
+            .try_acquire_event_stream_permit()
+            .ok_or_else(|| {
+                BasicErrorWith404::service_unavailable_with_code_no_info(
+                    format!(
+                        "Too many event streams, at most {} are served at once",
+                        self.context.node_config.api.max_event_streams
+                    ),
+                    AptosErrorCode::TooManyEventStreams,
+                )
+            })?;
+        Ok(EventStreamSubscription { receiver, permit })
+    }
+
+    /// Stream events from an [`EventKey`]
+    fn stream_by_key(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        event_key: EventKey,
+        start: Option<u64>,
+    ) -> Result<EventStream<BoxStream<'static, EventStreamMessage>>, BasicErrorWith404> {
+        let subscription = self.subscribe_to_event_notifications()?;
+        let stream = stream_events_by_key(self.context.clone(), subscription, event_key, start)
+            .context(format!("Failed to stream events by key {}", event_key))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+        Ok(EventStream::new(stream))
+    }
+
     /// List events from an [`EventKey`]
     fn list(
         &self,
@@ -163,7 +469,16 @@ impl EventsApi {
                     &latest_ledger_info,
                 )
             })?;
+        self.render(latest_ledger_info, accept_type, events)
+    }
 
+    /// Render events in the requested output format
+    fn render(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        accept_type: AcceptType,
+        events: Vec<EventWithVersion>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
         match accept_type {
             AcceptType::Json => {
                 let resolver = self.context.move_resolver_poem(&latest_ledger_info)?;
@@ -9,8 +9,10 @@ mod basic;
 mod bcs_payload;
 mod blocks;
 mod check_size;
+mod consensus_evidence;
 pub mod context;
 mod error_converter;
+mod event_stream;
 mod events;
 mod failpoint;
 mod index;
@@ -24,6 +26,7 @@ mod state;
 #[cfg(test)]
 pub mod tests;
 mod transactions;
+mod view_function;
 
 /// API categories for the OpenAPI spec
 #[derive(Tags)]
@@ -44,6 +47,9 @@ pub enum ApiTags {
 
     /// Access to transactions
     Transactions,
+
+    /// View functions
+    View,
 }
 
 // Note: Many of these exports are just for the test-context crate, which is
@@ -462,11 +462,11 @@ macro_rules! generate_success_response {
                )))
             }
 
-            pub fn with_cursor(mut self, new_cursor: Option<aptos_types::state_store::state_key::StateKey>) -> Self {
+            pub fn with_cursor<C: std::string::ToString>(mut self, new_cursor: Option<C>) -> Self {
                 match self {
                     $(
                     [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
-                        *cursor = new_cursor.map(|c| aptos_api_types::StateKeyWrapper::from(c).to_string());
+                        *cursor = new_cursor.map(|c| c.to_string());
                     }
                     )*
                 }
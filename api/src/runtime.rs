@@ -5,14 +5,17 @@ use std::{net::SocketAddr, sync::Arc};
 
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
+    consensus_evidence, context::Context, error_converter::convert_error, events::EventsApi,
+    index::IndexApi, log::middleware_log, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::NodeConfig;
 use aptos_logger::info;
 use aptos_mempool::MempoolClientSender;
 use aptos_types::chain_id::ChainId;
+use consensus_types::evidence::EvidenceReader;
+use event_notifications::EventNotificationListener;
 use poem::{
     http::{header, Method},
     listener::{Listener, RustlsCertificate, RustlsConfig, TcpListener},
@@ -32,6 +35,8 @@ pub fn bootstrap(
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    event_notification_listener: Option<EventNotificationListener>,
+    evidence_reader: EvidenceReader,
 ) -> anyhow::Result<Runtime> {
     let runtime = Builder::new_multi_thread()
         .thread_name_fn(|| {
@@ -44,7 +49,11 @@ pub fn bootstrap(
         .build()
         .context("[api] failed to create runtime")?;
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone());
+    let mut context = Context::new(chain_id, db, mp_sender, config.clone());
+    if let Some(listener) = event_notification_listener {
+        context.enable_event_streaming(runtime.handle(), listener);
+    }
+    context.set_evidence_reader(evidence_reader);
 
     attach_poem_to_runtime(runtime.handle(), context, config, false)
         .context("Failed to attach poem to runtime")?;
@@ -70,6 +79,7 @@ pub fn get_api_service(
         IndexApi,
         StateApi,
         TransactionsApi,
+        ViewFunctionApi,
     ),
     (),
 > {
@@ -93,7 +103,10 @@ pub fn get_api_service(
         StateApi {
             context: context.clone(),
         },
-        TransactionsApi { context },
+        TransactionsApi {
+            context: context.clone(),
+        },
+        ViewFunctionApi { context },
     );
 
     let version = VERSION.to_string();
@@ -188,6 +201,11 @@ pub fn attach_poem_to_runtime(
                     .at(
                         "/set_failpoint",
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
+                    )
+                    .at(
+                        "/consensus_evidence",
+                        poem::get(consensus_evidence::get_consensus_evidence_poem)
+                            .data(context.clone()),
                     ),
             )
             .with(cors)
@@ -213,6 +231,7 @@ mod tests {
     use aptos_api_test_context::{new_test_context, TestContext};
     use aptos_config::config::NodeConfig;
     use aptos_types::chain_id::ChainId;
+    use consensus_types::evidence::EvidenceReader;
 
     use super::bootstrap;
 
@@ -237,6 +256,8 @@ mod tests {
             ChainId::test(),
             context.db.clone(),
             context.mempool.ac_client.clone(),
+            None,
+            EvidenceReader::new(),
         );
         assert!(ret.is_ok());
 
@@ -1,9 +1,24 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_indexer};
+use crate::{
+    context::Context,
+    event_stream::{stream_events_by_type, EventStreamSubscription},
+};
 use aptos_api_test_context::current_function_name;
+use aptos_api_types::{AptosErrorCode, EventCursor, EventStreamMessage};
+use aptos_config::config::NodeConfig;
+use aptos_infallible::RwLock;
+use aptos_types::chain_id::ChainId;
+use event_notifications::{EventNotificationSender, EventSubscriptionService};
+use futures::StreamExt;
+use move_core_types::language_storage::TypeTag;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde_json::Value;
+use std::{str::FromStr, sync::Arc, time::Duration};
+use storage_interface::DbReaderWriter;
+use tokio::{runtime::Handle, time::timeout};
 
 static ACCOUNT_ADDRESS: &str = "0xa550c18";
 static CREATION_NUMBER: &str = "0";
@@ -154,3 +169,192 @@ async fn test_get_events_by_struct_type_has_generic_type_parameter() {
     let resp = context.expect_status_code(404).get(path.as_str()).await;
     context.check_golden_output(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_event_type() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let start_version = context.get_latest_ledger_info().version() + 1;
+    let account = context.gen_account();
+    let txn = context.mint_user_account(&account);
+    context.commit_block(&[txn]).await;
+
+    let resp = context
+        .get(
+            format!(
+                "/events/by_type/0x1::coin::DepositEvent?start={}",
+                start_version
+            )
+            .as_str(),
+        )
+        .await;
+    let events = resp.as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["type"], "0x1::coin::DepositEvent");
+    assert_eq!(
+        events[0]["guid"]["account_address"],
+        account.address().to_hex_literal()
+    );
+
+    // Later pages start after the last returned version.
+    let next_version: u64 = events[0]["version"].as_str().unwrap().parse().unwrap();
+    let resp = context
+        .get(
+            format!(
+                "/events/by_type/0x1::coin::DepositEvent?start={}",
+                next_version + 1
+            )
+            .as_str(),
+        )
+        .await;
+    assert_eq!(resp.as_array().unwrap().len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_event_type_with_cursor() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.mint_user_account(&account);
+    context.commit_block(&[txn]).await;
+    let all_events = context
+        .get("/events/by_type/0x1::coin::DepositEvent?start=0&limit=100")
+        .await;
+
+    // Page through the events one at a time, so that events of the same transaction end up on
+    // different pages.
+    let mut events = vec![];
+    let mut start = "0".to_string();
+    loop {
+        let req = warp::test::request().method("GET").path(&format!(
+            "/v1/events/by_type/0x1::coin::DepositEvent?start={}&limit=1",
+            start
+        ));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let page: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+        assert!(page.len() <= 1);
+        events.extend(page);
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(cursor) => {
+                start = EventCursor::from_str(cursor.to_str().unwrap())
+                    .unwrap()
+                    .to_string()
+            }
+            None => break,
+        }
+    }
+    assert_eq!(Value::Array(events), all_events);
+}
+
+fn subscribe(api_context: &Context) -> EventStreamSubscription {
+    EventStreamSubscription {
+        receiver: api_context.subscribe_to_event_notifications().unwrap(),
+        permit: api_context.try_acquire_event_stream_permit().unwrap(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_by_event_type() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let mut event_subscription_service = EventSubscriptionService::new(
+        &[],
+        Arc::new(RwLock::new(DbReaderWriter::from_arc(context.db.clone()))),
+    );
+    let mut api_context = context.context.clone();
+    api_context.enable_event_streaming(
+        &Handle::current(),
+        event_subscription_service
+            .subscribe_to_all_events()
+            .unwrap(),
+    );
+    let api_context = Arc::new(api_context);
+    let deposit_event = TypeTag::from_str("0x1::coin::DepositEvent").unwrap();
+    let mut stream = stream_events_by_type(
+        api_context.clone(),
+        subscribe(&api_context),
+        deposit_event,
+        None,
+    )
+    .unwrap();
+
+    // The stream only starts after the latest committed version, so it waits for new events.
+    let next_event = tokio::spawn(async move { stream.next().await });
+
+    let start_version = context.get_latest_ledger_info().version() + 1;
+    let account = context.gen_account();
+    let txn = context.mint_user_account(&account);
+    context.commit_block(&[txn]).await;
+    let version = context.get_latest_ledger_info().version();
+    let events = context
+        .get_transactions(start_version, (version - start_version + 1) as u16)
+        .into_iter()
+        .flat_map(|txn| txn.events)
+        .collect();
+    event_subscription_service
+        .notify_events(version, events)
+        .unwrap();
+
+    let message = timeout(Duration::from_secs(10), next_event)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    let event = match message {
+        EventStreamMessage::Event(event) => event,
+        EventStreamMessage::Error(err) => panic!("Unexpected error: {}", err),
+    };
+    assert_eq!(event.typ.to_string(), "0x1::coin::DepositEvent".to_string());
+    assert!((start_version..=version).contains(&event.version.0));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_events_by_event_type_ends_with_error() {
+    // Without the indexer, events by type can't be read.
+    let context = new_test_context(current_function_name!());
+    let mut event_subscription_service = EventSubscriptionService::new(
+        &[],
+        Arc::new(RwLock::new(DbReaderWriter::from_arc(context.db.clone()))),
+    );
+    let mut api_context = context.context.clone();
+    api_context.enable_event_streaming(
+        &Handle::current(),
+        event_subscription_service
+            .subscribe_to_all_events()
+            .unwrap(),
+    );
+    let api_context = Arc::new(api_context);
+    let deposit_event = TypeTag::from_str("0x1::coin::DepositEvent").unwrap();
+    let mut stream = stream_events_by_type(
+        api_context.clone(),
+        subscribe(&api_context),
+        deposit_event,
+        Some(0),
+    )
+    .unwrap();
+
+    match stream.next().await.unwrap() {
+        EventStreamMessage::Error(err) => {
+            assert!(matches!(err.error_code, AptosErrorCode::InternalError))
+        }
+        EventStreamMessage::Event(event) => panic!("Unexpected event: {:?}", event),
+    }
+    assert!(stream.next().await.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_event_streams_are_limited() {
+    let context = new_test_context(current_function_name!());
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_event_streams = 1;
+    let api_context = Context::new(
+        ChainId::test(),
+        context.db.clone(),
+        context.mempool.ac_client.clone(),
+        node_config,
+    );
+
+    let permit = api_context.try_acquire_event_stream_permit().unwrap();
+    assert!(api_context.try_acquire_event_stream_permit().is_none());
+    // Ending a stream releases its permit.
+    drop(permit);
+    assert!(api_context.try_acquire_event_stream_permit().is_some());
+}
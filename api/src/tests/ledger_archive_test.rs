@@ -0,0 +1,57 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_api_test_context::{
+    assert_json, current_function_name, new_test_context_with_ledger_archive,
+};
+use storage_interface::DbReader;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_read_archived_ledger_history() {
+    let mut context = new_test_context_with_ledger_archive(current_function_name!());
+    let root_address = context.root_account().address().to_hex_literal();
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+    let txn = context.account_transfer(&mut context.root_account(), &account, 1);
+    context.commit_block(&vec![txn]).await;
+
+    let root_txns = context
+        .get(&format!("/accounts/{}/transactions", root_address))
+        .await;
+    let user_txn_version = root_txns[0]["version"].as_str().unwrap().to_string();
+    let paths = vec![
+        "/".to_string(),
+        "/transactions?start=0&limit=100".to_string(),
+        format!("/transactions/by_version/{}", user_txn_version),
+        format!("/accounts/{}/transactions", root_address),
+        format!("/accounts/{}/events/0", root_address),
+        format!(
+            "/blocks/by_version/{}?with_transactions=true",
+            user_txn_version
+        ),
+    ];
+    let mut expected = vec![];
+    for path in &paths {
+        expected.push(context.get(path).await);
+    }
+    let user_txn_hash = expected[2]["hash"].as_str().unwrap().to_string();
+    let expected_by_hash = context
+        .get(&format!("/transactions/by_hash/{}", user_txn_hash))
+        .await;
+
+    // Everything but the latest version moves to the archive.
+    let latest_version = context.get_latest_ledger_info().version();
+    context.db.prune_ledger_for_test(latest_version).unwrap();
+    assert_eq!(context.db.get_first_txn_version().unwrap(), Some(0));
+
+    for (path, expected) in paths.iter().zip(expected) {
+        assert_json(context.get(path).await, expected);
+    }
+    assert_json(
+        context
+            .get(&format!("/transactions/by_hash/{}", user_txn_hash))
+            .await,
+        expected_by_hash,
+    );
+}
@@ -7,13 +7,19 @@ mod converter_test;
 mod events_test;
 mod index_test;
 mod invalid_post_request_test;
+mod ledger_archive_test;
 mod state_test;
 mod string_resource_test;
 mod transaction_vector_test;
 mod transactions_test;
+mod view_function_test;
 
 use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
 
 fn new_test_context(test_name: String) -> TestContext {
     super_new_test_context(test_name, false)
 }
+
+fn new_test_context_with_indexer(test_name: String) -> TestContext {
+    super_new_test_context(test_name, true)
+}
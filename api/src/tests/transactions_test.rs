@@ -1,8 +1,9 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_indexer};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::mime_types;
 
 use aptos_crypto::{
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
@@ -211,6 +212,20 @@ async fn test_post_invalid_signature_transaction() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_gas_profile_as_bcs() {
+    let mut context = new_test_context(current_function_name!());
+    let txn = context.create_invalid_signature_transaction();
+    let req = warp::test::request()
+        .method("POST")
+        .path("/v1/transactions/simulate?profile_gas=true")
+        .header("Content-Type", mime_types::BCS_SIGNED_TRANSACTION)
+        .header("Accept", mime_types::BCS)
+        .body(bcs::to_bytes(&txn).unwrap());
+    let resp = context.expect_status_code(400).execute(req).await;
+    assert_eq!(resp["error_code"], "bcs_not_supported");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_post_transaction_rejected_by_mempool() {
     let mut context = new_test_context(current_function_name!());
@@ -296,6 +311,70 @@ async fn test_multi_agent_signed_transaction() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_fee_payer_signed_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let fee_payer = context.gen_account();
+    let factory = context.transaction_factory();
+    let mut root_account = context.root_account();
+
+    // Create and fund the fee payer account
+    let fund_txn = root_account
+        .sign_with_transaction_builder(factory.account_transfer(fee_payer.address(), 10_000_000));
+    context.commit_block(&[fund_txn]).await;
+
+    // Create a new account with gas paid by the fee payer
+    let txn = root_account.sign_fee_payer_with_transaction_builder(
+        vec![],
+        &fee_payer,
+        factory.create_user_account(account.public_key()),
+    );
+
+    let body = bcs::to_bytes(&txn).unwrap();
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let (sender, fee_payer_signer) = match txn.authenticator() {
+        TransactionAuthenticator::FeePayer {
+            sender,
+            fee_payer_signer,
+            ..
+        } => (sender, fee_payer_signer),
+        _ => panic!(
+            "expecting TransactionAuthenticator::FeePayer, but got: {:?}",
+            txn.authenticator()
+        ),
+    };
+    assert_json(
+        resp["signature"].clone(),
+        json!({
+            "type": "fee_payer_signature",
+            "sender": {
+                "type": "ed25519_signature",
+                "public_key": format!("0x{}", hex::encode(sender.public_key_bytes())),
+                "signature": format!("0x{}", hex::encode(sender.signature_bytes())),
+            },
+            "secondary_signer_addresses": [],
+            "secondary_signers": [],
+            "fee_payer_address": fee_payer.address().to_hex_literal(),
+            "fee_payer_signer": {
+                "type": "ed25519_signature",
+                "public_key": format!("0x{}", hex::encode(fee_payer_signer.public_key_bytes())),
+                "signature": format!("0x{}", hex::encode(fee_payer_signer.signature_bytes())),
+            },
+        }),
+    );
+
+    // ensure fee payer txns can be submitted into mempool by JSON format
+    context
+        .expect_status_code(202)
+        .post("/transactions", resp)
+        .await;
+}
+
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_ed25519_signed_transaction() {
@@ -590,6 +669,53 @@ async fn test_get_account_transactions() {
     assert_json(txns, expected_txns);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_by_function() {
+    let mut context = new_test_context_with_indexer(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.mint_user_account(&account);
+    context.commit_block(&vec![txn]).await;
+
+    let txns = context
+        .get("/transactions/by_function/0x1::aptos_account::transfer")
+        .await;
+    assert_eq!(1, txns.as_array().unwrap().len());
+    let expected_txns = context.get("/transactions?start=2&limit=1").await;
+    assert_json(txns, expected_txns);
+
+    let txns = context
+        .get(
+            format!(
+                "/transactions/by_function/0x1::aptos_account::transfer?sender={}",
+                context.root_account().address()
+            )
+            .as_str(),
+        )
+        .await;
+    assert_eq!(1, txns.as_array().unwrap().len());
+
+    let txns = context
+        .get(
+            format!(
+                "/transactions/by_function/0x1::aptos_account::transfer?sender={}",
+                account.address()
+            )
+            .as_str(),
+        )
+        .await;
+    assert_json(txns, json!([]));
+
+    let txns = context
+        .get("/transactions/by_function/0x1::aptos_account::transfer?success=false")
+        .await;
+    assert_json(txns, json!([]));
+
+    let txns = context
+        .get("/transactions/by_function/0x1::aptos_account::transfer?start=3")
+        .await;
+    assert_json(txns, json!([]));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_transactions_filter_transactions_by_start_sequence_number() {
     let mut context = new_test_context(current_function_name!());
@@ -0,0 +1,67 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::current_function_name;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_coin_balance() {
+    let context = new_test_context(current_function_name!());
+    let root = context.root_account();
+    let coin_store = context
+        .api_get_account_resource(
+            &root,
+            "0x1",
+            "coin",
+            "CoinStore<0x1::aptos_coin::AptosCoin>",
+        )
+        .await;
+
+    let resp = context
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::coin::balance",
+                "type_arguments": ["0x1::aptos_coin::AptosCoin"],
+                "arguments": [root.address().to_hex_literal()],
+            }),
+        )
+        .await;
+    assert_eq!(resp, json!([coin_store["data"]["coin"]["value"]]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_function_not_found() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::coin::does_not_exist",
+                "type_arguments": [],
+                "arguments": [],
+            }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], json!("invalid_input"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_function_wrong_type_arguments() {
+    let context = new_test_context(current_function_name!());
+    let root = context.root_account();
+    let resp = context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::coin::balance",
+                "type_arguments": [],
+                "arguments": [root.address().to_hex_literal()],
+            }),
+        )
+        .await;
+    assert_eq!(resp["error_code"], json!("invalid_input"));
+}
@@ -14,28 +14,32 @@ use crate::{
     page::Page,
     response::{
         api_disabled, transaction_not_found_by_hash, transaction_not_found_by_version,
-        BadRequestError, BasicError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
-        BasicResult, BasicResultWith404, InsufficientStorageError, InternalError,
+        version_pruned, BadRequestError, BasicError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResult, BasicResultWith404, InsufficientStorageError,
+        InternalError, ServiceUnavailableError,
     },
     ApiTags,
 };
 use anyhow::{anyhow, Context as AnyhowContext};
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
-    AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
-    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+    AsConverter, EncodeSubmissionRequest, EntryFunctionId, GasEstimation, GasEstimationBcs,
+    HashValue, HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
+    Transaction, TransactionData, TransactionGasProfile, TransactionOnChainData,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_gas::GasProfile;
+use aptos_logger::warn;
+use aptos_mempool::MinAcceptedGasPrice;
 use aptos_types::{
     account_config::CoinStoreResource,
     account_view::AccountView,
     mempool_status::MempoolStatusCode,
     transaction::{
-        ExecutionStatus, RawTransaction, RawTransactionWithData, SignedTransaction,
-        TransactionPayload, TransactionStatus,
+        EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
+        RawTransactionWithData, SignedTransaction, TransactionPayload, TransactionStatus,
     },
     vm_status::StatusCode,
 };
@@ -268,6 +272,61 @@ impl TransactionsApi {
         self.list_by_account(&accept_type, page, address.0)
     }
 
+    /// Get transactions by entry function
+    ///
+    /// Retrieves on-chain committed user transactions calling the given entry
+    /// `function`, in ledger version order. Transactions can be filtered by
+    /// whether they succeeded and by their sender. This requires the node's
+    /// internal indexer.
+    ///
+    /// If no start version is given, it will start at the oldest ledger version
+    /// available. To get the next page, pass the version following the last
+    /// returned transaction as `start`.
+    #[oai(
+        path = "/transactions/by_function/:function",
+        method = "get",
+        operation_id = "get_transactions_by_function",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_transactions_by_function(
+        &self,
+        accept_type: AcceptType,
+        /// Entry function called by the transactions e.g. `0x1::coin::transfer`
+        function: Path<EntryFunctionId>,
+        /// If given, only retrieve successful (`true`) or failed (`false`) transactions
+        success: Query<Option<bool>>,
+        /// If given, only retrieve transactions sent by this account
+        sender: Query<Option<Address>>,
+        /// Ledger version to start list of transactions
+        ///
+        /// If not provided, defaults to the oldest ledger version available
+        start: Query<Option<U64>>,
+        /// Max number of transactions to retrieve.
+        ///
+        /// If not provided, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        function
+            .0
+            .verify()
+            .context("'function' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_transactions_by_function")?;
+        self.context
+            .check_api_output_enabled("Get transactions by function", &accept_type)?;
+        if !self.context.db.indexer_enabled() {
+            return Err(api_disabled("Get transactions by function"));
+        }
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_transactions_page_size(),
+        );
+        self.list_by_function(&accept_type, page, function.0, success.0, sender.0)
+    }
+
     /// Submit transaction
     ///
     /// This endpoint accepts transaction submissions in two formats.
@@ -413,6 +472,9 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
+        /// If set to true, the response will include a breakdown of where the gas was spent.
+        /// Only supported for JSON responses, requesting BCS as well results in a 400.
+        profile_gas: Query<Option<bool>>,
         data: SubmitTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
@@ -522,8 +584,13 @@ impl TransactionsApi {
             );
         }
 
-        self.simulate(&accept_type, ledger_info, signed_transaction)
-            .await
+        self.simulate(
+            &accept_type,
+            ledger_info,
+            signed_transaction,
+            profile_gas.0.unwrap_or_default(),
+        )
+        .await
     }
 
     /// Encode submission
@@ -580,6 +647,11 @@ impl TransactionsApi {
     /// gas price moves with the fee market, and should only increase when demand outweighs supply.
     ///
     /// If there have been no transactions in the last 100,000 transactions, the price will be 1.
+    ///
+    /// If mempool is full, the estimates are raised to at least the minimum gas unit price
+    /// mempool currently accepts, since cheaper transactions would be rejected. If mempool is
+    /// full and none of its transactions can be evicted, a 503 with the `mempool_is_full` error
+    /// code is returned, since no gas price would be accepted.
     #[oai(
         path = "/estimate_gas_price",
         method = "get",
@@ -591,7 +663,37 @@ impl TransactionsApi {
         self.context
             .check_api_output_enabled("Estimate gas price", &accept_type)?;
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
-        let gas_estimation = self.context.estimate_gas_price(&latest_ledger_info)?;
+        let mut gas_estimation = self.context.estimate_gas_price(&latest_ledger_info)?;
+        // The on-chain estimates are still useful if mempool can't be queried, so don't fail
+        // the request, just skip the mempool floor.
+        let mempool_min_gas_price = self
+            .context
+            .get_mempool_min_gas_price()
+            .await
+            .unwrap_or_else(|err| {
+                warn!("Failed to get minimum gas price from mempool: {:?}", err);
+                MinAcceptedGasPrice::Any
+            });
+        match mempool_min_gas_price {
+            MinAcceptedGasPrice::Any => {}
+            MinAcceptedGasPrice::AtLeast(min_gas_price) => {
+                gas_estimation.deprioritized_gas_estimate = gas_estimation
+                    .deprioritized_gas_estimate
+                    .map(|estimate| std::cmp::max(estimate, min_gas_price));
+                gas_estimation.gas_estimate =
+                    std::cmp::max(gas_estimation.gas_estimate, min_gas_price);
+                gas_estimation.prioritized_gas_estimate = gas_estimation
+                    .prioritized_gas_estimate
+                    .map(|estimate| std::cmp::max(estimate, min_gas_price));
+            }
+            MinAcceptedGasPrice::NotAccepting => {
+                return Err(BasicError::service_unavailable_with_code(
+                    "Mempool is full and not accepting transactions at any gas price",
+                    AptosErrorCode::MempoolIsFull,
+                    &latest_ledger_info,
+                ));
+            }
+        }
 
         match accept_type {
             AcceptType::Json => BasicResponse::try_from_json((
@@ -824,6 +926,84 @@ impl TransactionsApi {
         }
     }
 
+    /// List transactions calling an entry function
+    fn list_by_function(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        function: EntryFunctionId,
+        success: Option<bool>,
+        sender: Option<Address>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let start_version = page
+            .start_option()
+            .unwrap_or_else(|| latest_ledger_info.oldest_ledger_version.0);
+        if start_version < latest_ledger_info.oldest_ledger_version.0 {
+            return Err(version_pruned(start_version, &latest_ledger_info));
+        }
+
+        let data = self.context.get_transactions_by_function(
+            &function.module.into(),
+            &function.name.0,
+            success,
+            sender.map(Into::into),
+            start_version,
+            page.limit(&latest_ledger_info)?,
+            &latest_ledger_info,
+        )?;
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                self.context
+                    .render_transactions_non_sequential(&latest_ledger_info, data)?,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
+            }
+        }
+    }
+
+    /// Checks that the identifiers and type arguments of an entry function are well formed
+    fn validate_entry_function_payload_format(
+        ledger_info: &LedgerInfo,
+        entry_function: &EntryFunction,
+    ) -> Result<(), SubmitTransactionError> {
+        verify_module_identifier(entry_function.module().name().as_str())
+            .context("Transaction entry function module invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                )
+            })?;
+
+        verify_function_identifier(entry_function.function().as_str())
+            .context("Transaction entry function name invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                )
+            })?;
+        for arg in entry_function.ty_args() {
+            let arg: MoveType = arg.into();
+            arg.verify(0)
+                .context("Transaction entry function type arg invalid")
+                .map_err(|err| {
+                    SubmitTransactionError::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        ledger_info,
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
     /// Parses a single signed transaction
     fn get_signed_transaction(
         &self,
@@ -845,36 +1025,16 @@ impl TransactionsApi {
                 // Verify the signed transaction
                 match signed_transaction.payload() {
                     TransactionPayload::EntryFunction(entry_function) => {
-                        verify_module_identifier(entry_function.module().name().as_str())
-                            .context("Transaction entry function module invalid")
-                            .map_err(|err| {
-                                SubmitTransactionError::bad_request_with_code(
-                                    err,
-                                    AptosErrorCode::InvalidInput,
-                                    ledger_info,
-                                )
-                            })?;
-
-                        verify_function_identifier(entry_function.function().as_str())
-                            .context("Transaction entry function name invalid")
-                            .map_err(|err| {
-                                SubmitTransactionError::bad_request_with_code(
-                                    err,
-                                    AptosErrorCode::InvalidInput,
-                                    ledger_info,
-                                )
-                            })?;
-                        for arg in entry_function.ty_args() {
-                            let arg: MoveType = arg.into();
-                            arg.verify(0)
-                                .context("Transaction entry function type arg invalid")
-                                .map_err(|err| {
-                                    SubmitTransactionError::bad_request_with_code(
-                                        err,
-                                        AptosErrorCode::InvalidInput,
-                                        ledger_info,
-                                    )
-                                })?;
+                        Self::validate_entry_function_payload_format(ledger_info, entry_function)?;
+                    }
+                    TransactionPayload::Multisig(multisig) => {
+                        if let Some(MultisigTransactionPayload::EntryFunction(entry_function)) =
+                            &multisig.transaction_payload
+                        {
+                            Self::validate_entry_function_payload_format(
+                                ledger_info,
+                                entry_function,
+                            )?;
                         }
                     }
                     TransactionPayload::Script(script) => {
@@ -1132,7 +1292,17 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
         txn: SignedTransaction,
+        profile_gas: bool,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
+        // The gas profile is only rendered in JSON, rather than silently dropping it reject it
+        if profile_gas && accept_type == &AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Gas profiling is not supported for BCS responses",
+                AptosErrorCode::BcsNotSupported,
+                &ledger_info,
+            ));
+        }
+
         // Transactions shouldn't have a valid signature or this could be used to attack
         if txn.signature_is_valid() {
             return Err(SubmitTransactionError::bad_request_with_code(
@@ -1144,7 +1314,14 @@ impl TransactionsApi {
 
         // Simulate transaction
         let move_resolver = self.context.move_resolver_poem(&ledger_info)?;
-        let (_, output_ext) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
+        let (output_ext, gas_profile) = if profile_gas {
+            let (_, output_ext, gas_profile) =
+                AptosVM::simulate_signed_transaction_with_gas_profile(&txn, &move_resolver);
+            (output_ext, gas_profile)
+        } else {
+            let (_, output_ext) = AptosVM::simulate_signed_transaction(&txn, &move_resolver);
+            (output_ext, None)
+        };
         let version = ledger_info.version();
 
         // Apply transaction outputs to build up a transaction
@@ -1192,7 +1369,10 @@ impl TransactionsApi {
                 let mut user_transactions = Vec::new();
                 for transaction in transactions.into_iter() {
                     match transaction {
-                        Transaction::UserTransaction(user_txn) => user_transactions.push(*user_txn),
+                        Transaction::UserTransaction(mut user_txn) => {
+                            user_txn.gas_profile = gas_profile.as_ref().map(render_gas_profile);
+                            user_transactions.push(*user_txn)
+                        }
                         _ => {
                             return Err(SubmitTransactionError::internal_with_code(
                                 "Simulation transaction resulted in a non-UserTransaction",
@@ -1238,8 +1418,23 @@ impl TransactionsApi {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?;
 
-        let raw_message = match request.secondary_signers {
-            Some(secondary_signer_addresses) => signing_message(
+        let raw_message = match (request.secondary_signers, request.fee_payer_address) {
+            (secondary_signers, Some(fee_payer_address)) => signing_message(
+                &RawTransactionWithData::new_fee_payer(
+                    raw_txn,
+                    secondary_signers
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|v| v.into())
+                        .collect(),
+                    fee_payer_address.into(),
+                ),
+            )
+            .context("Invalid transaction to generate signing message")
+            .map_err(|err| {
+                BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
+            })?,
+            (Some(secondary_signer_addresses), None) => signing_message(
                 &RawTransactionWithData::new_multi_agent(
                     raw_txn,
                     secondary_signer_addresses
@@ -1252,7 +1447,7 @@ impl TransactionsApi {
             .map_err(|err| {
                 BasicError::bad_request_with_code(err, AptosErrorCode::InvalidInput, &ledger_info)
             })?,
-            None => raw_txn
+            (None, None) => raw_txn
                 .signing_message()
                 .context("Invalid transaction to generate signing message")
                 .map_err(|err| {
@@ -1292,3 +1487,18 @@ fn override_gas_parameters(
     // TODO: Check that signature is null, this would just be helpful for downstream use
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
+
+/// Converts a gas profile into its API representation, in internal gas units
+fn render_gas_profile(profile: &GasProfile) -> TransactionGasProfile {
+    TransactionGasProfile {
+        intrinsic: u64::from(profile.intrinsic).into(),
+        execution: u64::from(profile.execution.total()).into(),
+        storage_reads: u64::from(profile.execution.total_storage_reads()).into(),
+        storage_writes: u64::from(profile.total_storage_writes()).into(),
+        folded_stacks: profile
+            .to_folded_stacks()
+            .lines()
+            .map(String::from)
+            .collect(),
+    }
+}
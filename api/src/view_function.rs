@@ -0,0 +1,203 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    bcs_payload::Bcs,
+    context::Context,
+    failpoint::fail_point_poem,
+    response::{
+        api_disabled, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResultWith404, InternalError,
+    },
+    ApiTags,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    AptosErrorCode, AsConverter, ExplainVMStatus, MoveValue, VerifyInput, ViewRequest,
+    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+};
+use aptos_types::transaction::{EntryFunction, TransactionStatus};
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use poem_openapi::{param::Query, payload::Json, ApiRequest, OpenApi};
+use std::sync::Arc;
+
+// We need a custom type here because we use different types for each of the
+// content types possible for the POST data.
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionRequest {
+    #[oai(content_type = "application/json")]
+    Json(Json<ViewRequest>),
+
+    // The BCS payload is an `EntryFunction` with already BCS encoded arguments.
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
+impl VerifyInput for ViewFunctionRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            ViewFunctionRequest::Json(inner) => inner.0.verify(),
+            ViewFunctionRequest::Bcs(_) => Ok(()),
+        }
+    }
+}
+
+/// API for executing read-only Move functions
+pub struct ViewFunctionApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl ViewFunctionApi {
+    /// Execute view function of a module
+    ///
+    /// Execute the Move function with the given parameters and return its execution result.
+    /// The function must be public, must not take a `signer`, and any writes it performs are
+    /// discarded.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/view",
+        method = "post",
+        operation_id = "view",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function(
+        &self,
+        accept_type: AcceptType,
+        /// View function request with type and position arguments
+        request: ViewFunctionRequest,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<MoveValue>> {
+        request
+            .verify()
+            .context("'request' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_view_function")?;
+        self.context
+            .check_api_output_enabled("View function", &accept_type)?;
+        if !self.context.node_config.api.view_function_enabled {
+            return Err(api_disabled("View function"));
+        }
+        self.view(&accept_type, request, ledger_version.0.map(|inner| inner.0))
+    }
+}
+
+impl ViewFunctionApi {
+    /// Executes the function in the VM against the state at the requested ledger version
+    ///
+    /// JSON: Convert the return values to `MoveValue`s using the function's return types
+    /// BCS: Return the BCS encoded return values as is
+    fn view(
+        &self,
+        accept_type: &AcceptType,
+        request: ViewFunctionRequest,
+        ledger_version: Option<u64>,
+    ) -> BasicResultWith404<Vec<MoveValue>> {
+        let (ledger_info, requested_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(ledger_version)?;
+        let state_view = self
+            .context
+            .state_view_at_version(requested_version)
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let resolver = state_view.as_move_resolver();
+
+        let entry_function = match request {
+            ViewFunctionRequest::Json(data) => resolver
+                .as_converter(self.context.db.clone())
+                .try_into_view_function(data.0)
+                .context("Failed to convert view function request")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?,
+            ViewFunctionRequest::Bcs(data) => bcs::from_bytes_with_limit::<EntryFunction>(
+                &data.0,
+                MAX_RECURSIVE_TYPES_ALLOWED as usize,
+            )
+            .context("Failed to deserialize input into EntryFunction")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?,
+        };
+
+        let (module, function, ty_args, args) = entry_function.clone().into_inner();
+        let return_values = AptosVM::execute_view_function(
+            &state_view,
+            module,
+            function,
+            ty_args,
+            args,
+            self.context.node_config.api.max_gas_view_function,
+        )
+        .map_err(|status| {
+            let message = match TransactionStatus::from(status.clone()) {
+                TransactionStatus::Keep(exec_status) => resolver
+                    .as_converter(self.context.db.clone())
+                    .explain_vm_status(&exec_status),
+                _ => format!("{:?}", status),
+            };
+            BasicErrorWith404::bad_request_with_vm_status(
+                message,
+                AptosErrorCode::VmError,
+                status.status_code(),
+                &ledger_info,
+            )
+        })?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let converter = resolver.as_converter(self.context.db.clone());
+                let return_types = converter
+                    .function_return_types(&entry_function)
+                    .context("Failed to resolve view function return types")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+                let move_values = return_values
+                    .iter()
+                    .zip(return_types.iter())
+                    .map(|(bytes, typ)| converter.try_into_move_value(typ, bytes))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to deserialize view function return values")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((move_values, &ledger_info, BasicResponseStatus::Ok))
+            }
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((return_values, &ledger_info, BasicResponseStatus::Ok))
+            }
+        }
+    }
+}
@@ -85,6 +85,36 @@ impl ApiSpecificConfig {
 }
 
 pub fn new_test_context(test_name: String, use_db_with_indexer: bool) -> TestContext {
+    new_test_context_with_db(test_name, |tmp_dir| {
+        if use_db_with_indexer {
+            AptosDB::new_for_test_with_indexer(tmp_dir)
+        } else {
+            AptosDB::open(
+                tmp_dir,
+                false,                       /* readonly */
+                NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+                RocksdbConfigs::default(),
+                false, /* indexer */
+                BUFFERED_STATE_TARGET_ITEMS,
+                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            )
+            .unwrap()
+        }
+    })
+}
+
+/// Creates a test context whose ledger pruner archives the pruned history, see
+/// `AptosDB::prune_ledger_for_test`.
+pub fn new_test_context_with_ledger_archive(test_name: String) -> TestContext {
+    new_test_context_with_db(test_name, |tmp_dir| {
+        AptosDB::new_for_test_with_ledger_archive(tmp_dir)
+    })
+}
+
+fn new_test_context_with_db(
+    test_name: String,
+    open_db: impl FnOnce(&TempPath) -> AptosDB,
+) -> TestContext {
     let tmp_dir = TempPath::new();
     tmp_dir.create_as_dir().unwrap();
 
@@ -103,22 +133,7 @@ pub fn new_test_context(test_name: String, use_db_with_indexer: bool) -> TestCon
     let (validator_identity, _, _, _) = validators[0].get_key_objects(None).unwrap();
     let validator_owner = validator_identity.account_address.unwrap();
 
-    let (db, db_rw) = if use_db_with_indexer {
-        DbReaderWriter::wrap(AptosDB::new_for_test_with_indexer(&tmp_dir))
-    } else {
-        DbReaderWriter::wrap(
-            AptosDB::open(
-                &tmp_dir,
-                false,                       /* readonly */
-                NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
-                RocksdbConfigs::default(),
-                false, /* indexer */
-                BUFFERED_STATE_TARGET_ITEMS,
-                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
-            )
-            .unwrap(),
-        )
-    };
+    let (db, db_rw) = DbReaderWriter::wrap(open_db(&tmp_dir));
     let ret =
         db_bootstrapper::maybe_bootstrap::<AptosVM>(&db_rw, &genesis, genesis_waypoint).unwrap();
     assert!(ret);
@@ -140,7 +155,7 @@ pub fn new_test_context(test_name: String, use_db_with_indexer: bool) -> TestCon
         .expect("Failed to attach poem to runtime");
     let api_specific_config = ApiSpecificConfig::V1(poem_address);
 
-    TestContext::new(
+    let mut test_context = TestContext::new(
         context,
         rng,
         root_key,
@@ -150,7 +165,10 @@ pub fn new_test_context(test_name: String, use_db_with_indexer: bool) -> TestCon
         db,
         test_name,
         api_specific_config,
-    )
+    );
+    // The db keeps writing to the directory (e.g. ledger archive segments).
+    test_context._db_dir = Some(Arc::new(tmp_dir));
+    test_context
 }
 
 #[derive(Clone)]
@@ -167,6 +185,7 @@ pub struct TestContext {
     golden_output: Option<GoldenOutputs>,
     fake_time_usecs: u64,
     pub api_specific_config: ApiSpecificConfig,
+    _db_dir: Option<Arc<TempPath>>,
 }
 
 impl TestContext {
@@ -194,6 +213,7 @@ impl TestContext {
             golden_output: None,
             fake_time_usecs: 0,
             api_specific_config,
+            _db_dir: None,
         }
     }
 
@@ -8,10 +8,11 @@ use crate::{
         WriteResource, WriteTableItem,
     },
     Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event, HexEncodedBytes,
-    MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveValue,
-    PendingTransaction, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction,
-    TransactionInfo, TransactionOnChainData, TransactionPayload, UserTransactionRequest,
-    VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload,
+    MoveFunction, MoveFunctionVisibility, MoveModuleBytecode, MoveResource, MoveScriptBytecode,
+    MoveType, MoveValue, MultisigPayload, MultisigTransactionPayload, PendingTransaction,
+    ScriptPayload, ScriptWriteSet, SubmitTransactionRequest, Transaction, TransactionInfo,
+    TransactionOnChainData, TransactionPayload, UserTransactionRequest, VersionedEvent,
+    ViewRequest, WriteSet, WriteSetChange, WriteSetPayload,
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -166,34 +167,52 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
                     .collect::<Result<Vec<_>>>()?,
             }),
             EntryFunction(fun) => {
-                let (module, function, ty_args, args) = fun.into_inner();
-                let func_args = self
-                    .inner
-                    .view_function_arguments(&module, &function, &args);
-                let json_args = match func_args {
-                    Ok(values) => values
-                        .into_iter()
-                        .map(|v| MoveValue::try_from(v)?.json())
-                        .collect::<Result<_>>()?,
-                    Err(_e) => args
-                        .into_iter()
-                        .map(|arg| HexEncodedBytes::from(arg).json())
-                        .collect::<Result<_>>()?,
+                TransactionPayload::EntryFunctionPayload(self.try_into_entry_function_payload(fun)?)
+            }
+            Multisig(multisig) => {
+                let transaction_payload = match multisig.transaction_payload {
+                    Some(aptos_types::transaction::MultisigTransactionPayload::EntryFunction(
+                        fun,
+                    )) => Some(MultisigTransactionPayload::EntryFunctionPayload(
+                        self.try_into_entry_function_payload(fun)?,
+                    )),
+                    None => None,
                 };
-
-                TransactionPayload::EntryFunctionPayload(EntryFunctionPayload {
-                    arguments: json_args,
-                    function: EntryFunctionId {
-                        module: module.into(),
-                        name: function.into(),
-                    },
-                    type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+                TransactionPayload::MultisigPayload(MultisigPayload {
+                    multisig_address: multisig.multisig_address.into(),
+                    transaction_payload,
                 })
             }
         };
         Ok(ret)
     }
 
+    fn try_into_entry_function_payload(&self, fun: EntryFunction) -> Result<EntryFunctionPayload> {
+        let (module, function, ty_args, args) = fun.into_inner();
+        let func_args = self
+            .inner
+            .view_function_arguments(&module, &function, &args);
+        let json_args = match func_args {
+            Ok(values) => values
+                .into_iter()
+                .map(|v| MoveValue::try_from(v)?.json())
+                .collect::<Result<_>>()?,
+            Err(_e) => args
+                .into_iter()
+                .map(|arg| HexEncodedBytes::from(arg).json())
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(EntryFunctionPayload {
+            arguments: json_args,
+            function: EntryFunctionId {
+                module: module.into(),
+                name: function.into(),
+            },
+            type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+        })
+    }
+
     pub fn try_into_write_set_payload(
         &self,
         payload: aptos_types::transaction::WriteSetPayload,
@@ -465,39 +484,23 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
 
         let ret = match payload {
             TransactionPayload::EntryFunctionPayload(entry_func_payload) => {
-                let EntryFunctionPayload {
-                    function,
-                    type_arguments,
-                    arguments,
-                } = entry_func_payload;
-
-                let module = function.module.clone();
-                let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
-                let func = code
-                    .find_entry_function(function.name.0.as_ident_str())
-                    .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
-                ensure!(
-                    func.generic_type_params.len() == type_arguments.len(),
-                    "expect {} type arguments for entry function {}, but got {}",
-                    func.generic_type_params.len(),
-                    function,
-                    type_arguments.len()
-                );
-                let args = self
-                    .try_into_vm_values(func, arguments)?
-                    .iter()
-                    .map(bcs::to_bytes)
-                    .collect::<Result<_, bcs::Error>>()?;
-
-                Target::EntryFunction(EntryFunction::new(
-                    module.into(),
-                    function.name.into(),
-                    type_arguments
-                        .into_iter()
-                        .map(|v| v.try_into())
-                        .collect::<Result<_>>()?,
-                    args,
-                ))
+                Target::EntryFunction(self.try_into_entry_function(entry_func_payload)?)
+            }
+            TransactionPayload::MultisigPayload(multisig) => {
+                let transaction_payload = match multisig.transaction_payload {
+                    Some(MultisigTransactionPayload::EntryFunctionPayload(entry_func_payload)) => {
+                        Some(
+                            aptos_types::transaction::MultisigTransactionPayload::EntryFunction(
+                                self.try_into_entry_function(entry_func_payload)?,
+                            ),
+                        )
+                    }
+                    None => None,
+                };
+                Target::Multisig(aptos_types::transaction::Multisig {
+                    multisig_address: multisig.multisig_address.into(),
+                    transaction_payload,
+                })
             }
             TransactionPayload::ModuleBundlePayload(payload) => {
                 Target::ModuleBundle(ModuleBundle::new(
@@ -537,6 +540,98 @@ impl<'a, R: MoveResolverExt + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    fn try_into_entry_function(&self, payload: EntryFunctionPayload) -> Result<EntryFunction> {
+        let EntryFunctionPayload {
+            function,
+            type_arguments,
+            arguments,
+        } = payload;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_entry_function(function.name.0.as_ident_str())
+            .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for entry function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let args = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(EntryFunction::new(
+            module.into(),
+            function.name.into(),
+            type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            args,
+        ))
+    }
+
+    /// Converts a JSON [`ViewRequest`] into an [`EntryFunction`], which carries the BCS encoded
+    /// arguments needed to execute the view function in the VM
+    pub fn try_into_view_function(&self, request: ViewRequest) -> Result<EntryFunction> {
+        let ViewRequest {
+            function,
+            type_arguments,
+            arguments,
+        } = request;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_function(function.name.0.as_ident_str())
+            .ok_or_else(|| format_err!("could not find view function by {}", function))?;
+        ensure!(
+            func.visibility == MoveFunctionVisibility::Public,
+            "view function {} must be public",
+            function
+        );
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for view function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let args = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(EntryFunction::new(
+            module.into(),
+            function.name.into(),
+            type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            args,
+        ))
+    }
+
+    /// Resolves the concrete return types of a view function for its type arguments
+    pub fn function_return_types(&self, function: &EntryFunction) -> Result<Vec<TypeTag>> {
+        let code = self.inner.get_module(function.module())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_function(function.function())
+            .ok_or_else(|| format_err!("could not find function by {}", function.function()))?;
+        let type_args: Vec<MoveType> = function.ty_args().iter().map(MoveType::from).collect();
+        func.return_
+            .iter()
+            .map(|ret| ret.substitute(&type_args)?.try_into())
+            .collect()
+    }
+
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
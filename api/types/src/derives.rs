@@ -23,8 +23,8 @@ use serde_json::json;
 
 use crate::{
     move_types::{MoveAbility, MoveStructValue},
-    Address, EntryFunctionId, HashValue, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
-    MoveStructTag, MoveType, StateKeyWrapper, U128, U64,
+    Address, EntryFunctionId, EventCursor, HashValue, HexEncodedBytes, IdentifierWrapper,
+    MoveModuleId, MoveStructTag, MoveType, StateKeyWrapper, U128, U64,
 };
 use indoc::indoc;
 
@@ -64,6 +64,19 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    EventCursor,
+    "string",
+    (
+        example = Some(serde_json::Value::String("32425224034:1".to_string())),
+        description = Some(indoc! {"
+          Position of an event as `<version>:<index>`, the version of the transaction that emitted
+          it and its index among the events of the transaction. A plain `<version>` refers to the
+          first event of the transaction. This is used for cursor based pagination.
+        "})
+    )
+);
+
 impl_poem_type!(HashValue, "string", ());
 
 impl_poem_type!(
@@ -274,6 +287,8 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    EntryFunctionId,
+    EventCursor,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
@@ -98,6 +98,8 @@ pub enum AptosErrorCode {
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The maximum number of concurrent event streams is reached, no new stream can be opened.
+    TooManyEventStreams = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -16,6 +16,7 @@ pub mod mime_types;
 mod move_types;
 mod table;
 mod transaction;
+mod view;
 mod wrappers;
 
 pub use account::AccountData;
@@ -41,15 +42,18 @@ pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction, ModuleBundlePayload,
-    MultiAgentSignature, MultiEd25519Signature, PendingTransaction, ScriptPayload, ScriptWriteSet,
-    SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
-    TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
-    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
-    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
-    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
+    EventStreamMessage, FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload,
+    GenesisTransaction, ModuleBundlePayload, MultiAgentSignature, MultiEd25519Signature,
+    MultisigPayload, MultisigTransactionPayload, PendingTransaction, ScriptPayload, ScriptWriteSet,
+    SubmitTransactionRequest, Transaction, TransactionData, TransactionGasProfile, TransactionId,
+    TransactionInfo, TransactionOnChainData, TransactionPayload, TransactionSignature,
+    TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
+    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
+    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
+    WriteSetPayload, WriteTableItem,
 };
-pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
+pub use view::ViewRequest;
+pub use wrappers::{EventCursor, EventGuid, IdentifierWrapper, StateKeyWrapper};
 
 pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -4,6 +4,9 @@
 /// MIME type to submit BCS transactions
 pub const BCS_SIGNED_TRANSACTION: &str = "application/x.aptos.signed_transaction+bcs";
 
+/// MIME type to submit BCS view function requests
+pub const BCS_VIEW_FUNCTION: &str = "application/x.aptos.view_function+bcs";
+
 /// MIME type to submit JSON transactions and get JSON output
 pub const JSON: &str = "application/json";
 
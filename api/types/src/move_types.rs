@@ -679,6 +679,33 @@ impl MoveType {
             _ => false,
         }
     }
+
+    /// Replaces any generic type params with the given type arguments, e.g. when resolving
+    /// the concrete return types of a function instantiation
+    pub fn substitute(&self, type_args: &[MoveType]) -> anyhow::Result<MoveType> {
+        Ok(match self {
+            MoveType::GenericTypeParam { index } => type_args
+                .get(*index as usize)
+                .cloned()
+                .ok_or_else(|| format_err!("Missing type argument for type param T{}", index))?,
+            MoveType::Vector { items } => MoveType::Vector {
+                items: Box::new(items.substitute(type_args)?),
+            },
+            MoveType::Struct(struct_tag) => MoveType::Struct(MoveStructTag {
+                generic_type_params: struct_tag
+                    .generic_type_params
+                    .iter()
+                    .map(|param| param.substitute(type_args))
+                    .collect::<anyhow::Result<_>>()?,
+                ..struct_tag.clone()
+            }),
+            MoveType::Reference { mutable, to } => MoveType::Reference {
+                mutable: *mutable,
+                to: Box::new(to.substitute(type_args)?),
+            },
+            _ => self.clone(),
+        })
+    }
 }
 
 impl From<TypeTag> for MoveType {
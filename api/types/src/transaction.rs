@@ -266,6 +266,7 @@ impl
             request: (txn, payload).into(),
             events,
             timestamp: timestamp.into(),
+            gas_profile: None,
         }))
     }
 }
@@ -366,6 +367,25 @@ pub struct UserTransaction {
     /// Events generated by the transaction
     pub events: Vec<Event>,
     pub timestamp: U64,
+    /// Breakdown of the gas used, only present on simulations that asked for it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub gas_profile: Option<TransactionGasProfile>,
+}
+
+/// Where the gas of a simulated transaction was spent, in internal gas units
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionGasProfile {
+    /// Gas charged for the size of the transaction
+    pub intrinsic: U64,
+    /// Gas charged for executing Move code, including storage reads
+    pub execution: U64,
+    /// Gas charged for loading resources from storage
+    pub storage_reads: U64,
+    /// Gas charged for the writes made by the transaction
+    pub storage_writes: U64,
+    /// The gas profile in the folded stacks format, which can be rendered as a flamegraph
+    pub folded_stacks: Vec<String>,
 }
 
 /// A state checkpoint transaction
@@ -471,6 +491,9 @@ pub struct EncodeSubmissionRequest {
     /// Secondary signer accounts of the request for Multi-agent
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secondary_signers: Option<Vec<Address>>,
+    /// Account paying for gas, for a fee payer transaction
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer_address: Option<Address>,
 }
 
 impl VerifyInput for EncodeSubmissionRequest {
@@ -570,6 +593,18 @@ impl From<(&EventWithVersion, serde_json::Value)> for VersionedEvent {
     }
 }
 
+/// A message of an event stream
+///
+/// Streams send an event per committed event, and end with an error if they can't
+/// continue
+#[derive(Clone, Debug, Serialize, Deserialize, Union)]
+#[serde(tag = "message_type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "message_type", rename_all = "snake_case")]
+pub enum EventStreamMessage {
+    Event(VersionedEvent),
+    Error(AptosError),
+}
+
 /// The writeset payload of the Genesis transaction
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -586,6 +621,7 @@ pub enum TransactionPayload {
     EntryFunctionPayload(EntryFunctionPayload),
     ScriptPayload(ScriptPayload),
     ModuleBundlePayload(ModuleBundlePayload),
+    MultisigPayload(MultisigPayload),
 }
 
 impl VerifyInput for TransactionPayload {
@@ -594,6 +630,7 @@ impl VerifyInput for TransactionPayload {
             TransactionPayload::EntryFunctionPayload(inner) => inner.verify(),
             TransactionPayload::ScriptPayload(inner) => inner.verify(),
             TransactionPayload::ModuleBundlePayload(inner) => inner.verify(),
+            TransactionPayload::MultisigPayload(inner) => inner.verify(),
         }
     }
 }
@@ -633,6 +670,40 @@ impl VerifyInput for EntryFunctionPayload {
     }
 }
 
+/// A multisig transaction that allows an owner of a multisig account to execute a pre-approved
+/// transaction as the multisig account.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MultisigPayload {
+    pub multisig_address: Address,
+    /// The approved payload to execute, if not omitted in favor of the one stored on chain
+    pub transaction_payload: Option<MultisigTransactionPayload>,
+}
+
+impl VerifyInput for MultisigPayload {
+    fn verify(&self) -> anyhow::Result<()> {
+        match &self.transaction_payload {
+            Some(payload) => payload.verify(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// An enum of the possible payloads a multisig account can execute
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum MultisigTransactionPayload {
+    EntryFunctionPayload(EntryFunctionPayload),
+}
+
+impl VerifyInput for MultisigTransactionPayload {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            MultisigTransactionPayload::EntryFunctionPayload(inner) => inner.verify(),
+        }
+    }
+}
+
 /// Payload which runs a script that can run multiple functions
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct ScriptPayload {
@@ -812,6 +883,7 @@ pub enum TransactionSignature {
     Ed25519Signature(Ed25519Signature),
     MultiEd25519Signature(MultiEd25519Signature),
     MultiAgentSignature(MultiAgentSignature),
+    FeePayerSignature(FeePayerSignature),
 }
 
 impl VerifyInput for TransactionSignature {
@@ -820,6 +892,7 @@ impl VerifyInput for TransactionSignature {
             TransactionSignature::Ed25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiEd25519Signature(inner) => inner.verify(),
             TransactionSignature::MultiAgentSignature(inner) => inner.verify(),
+            TransactionSignature::FeePayerSignature(inner) => inner.verify(),
         }
     }
 }
@@ -831,6 +904,7 @@ impl TryFrom<TransactionSignature> for TransactionAuthenticator {
             TransactionSignature::Ed25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiEd25519Signature(sig) => sig.try_into()?,
             TransactionSignature::MultiAgentSignature(sig) => sig.try_into()?,
+            TransactionSignature::FeePayerSignature(sig) => sig.try_into()?,
         })
     }
 }
@@ -1115,6 +1189,65 @@ impl TryFrom<MultiAgentSignature> for TransactionAuthenticator {
     }
 }
 
+/// Fee payer signature for fee payer transactions
+///
+/// This allows a separate account to pay the gas of a (possibly multi agent) transaction
+/// without taking part in it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct FeePayerSignature {
+    pub sender: AccountSignature,
+    /// The other involved parties' addresses
+    pub secondary_signer_addresses: Vec<Address>,
+    /// The associated signatures, in the same order as the secondary addresses
+    pub secondary_signers: Vec<AccountSignature>,
+    /// The address of the account paying for gas
+    pub fee_payer_address: Address,
+    /// The signature of the fee payer
+    pub fee_payer_signer: AccountSignature,
+}
+
+impl VerifyInput for FeePayerSignature {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.sender.verify()?;
+
+        if self.secondary_signers.len() != self.secondary_signer_addresses.len() {
+            bail!("FeePayer signatures don't match addresses length")
+        }
+
+        for signer in self.secondary_signers.iter() {
+            signer.verify()?;
+        }
+        self.fee_payer_signer.verify()
+    }
+}
+
+impl TryFrom<FeePayerSignature> for TransactionAuthenticator {
+    type Error = anyhow::Error;
+
+    fn try_from(value: FeePayerSignature) -> Result<Self, Self::Error> {
+        let FeePayerSignature {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        } = value;
+        Ok(TransactionAuthenticator::fee_payer(
+            sender.try_into()?,
+            secondary_signer_addresses
+                .into_iter()
+                .map(|a| a.into())
+                .collect(),
+            secondary_signers
+                .into_iter()
+                .map(|s| s.try_into())
+                .collect::<anyhow::Result<_>>()?,
+            fee_payer_address.into(),
+            fee_payer_signer.try_into()?,
+        ))
+    }
+}
+
 impl From<(&Ed25519PublicKey, &ed25519::Ed25519Signature)> for Ed25519Signature {
     fn from((pk, sig): (&Ed25519PublicKey, &ed25519::Ed25519Signature)) -> Self {
         Self {
@@ -1210,6 +1343,22 @@ impl From<TransactionAuthenticator> for TransactionSignature {
             } => Self::MultiAgentSignature(
                 (sender, secondary_signer_addresses, secondary_signers).into(),
             ),
+            FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => Self::FeePayerSignature(FeePayerSignature {
+                sender: sender.into(),
+                secondary_signer_addresses: secondary_signer_addresses
+                    .iter()
+                    .map(|address| (*address).into())
+                    .collect(),
+                secondary_signers: secondary_signers.iter().map(|s| s.into()).collect(),
+                fee_payer_address: (*fee_payer_address).into(),
+                fee_payer_signer: fee_payer_signer.into(),
+            }),
         }
     }
 }
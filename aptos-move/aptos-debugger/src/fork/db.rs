@@ -0,0 +1,465 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fork::{AptosFork, ForkedTransaction};
+use anyhow::{ensure, format_err, Result};
+use aptos_crypto::HashValue;
+use aptos_state_view::account_with_state_view::AsAccountWithStateView;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::{NewBlockEvent, CORE_CODE_ADDRESS},
+    account_view::AccountView,
+    aggregate_signature::AggregateSignature,
+    block_info::BlockInfo,
+    chain_id::ChainId,
+    contract_event::EventWithVersion,
+    event::EventKey,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{
+        AccumulatorProof, TransactionAccumulatorRangeProof, TransactionInfoListWithProof,
+        TransactionInfoWithProof,
+    },
+    state_store::{
+        state_key::{StateKey, StateKeyTag},
+        state_key_prefix::StateKeyPrefix,
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+    },
+    timestamp::TimestampResource,
+    transaction::{
+        AccountTransactionsWithProof, Transaction, TransactionListWithProof,
+        TransactionOutputListWithProof, TransactionWithProof, Version,
+    },
+};
+use std::sync::Arc;
+use storage_interface::{DbReader, Order};
+
+/// Serves a fork as a `DbReader`, for the REST API.
+///
+/// The ledger starts at the fork version, whose transaction is fetched from the remote, and every
+/// version is a block of its own. Ledger infos are not signed, and proofs and accumulator root
+/// hashes are empty, since a fork only holds part of the state. Events and account transactions
+/// are only served for transactions on the fork.
+pub struct ForkDb {
+    fork: Arc<AptosFork>,
+    fork_transaction: ForkedTransaction,
+    chain_id: ChainId,
+    epoch: u64,
+    timestamp_usecs: u64,
+}
+
+impl ForkDb {
+    pub async fn new(fork: Arc<AptosFork>) -> Result<Self> {
+        let fork_transaction = fork.get_fork_transaction().await?;
+        let state_view = fork.state_view();
+        let account = state_view.as_account_with_state_view(&CORE_CODE_ADDRESS);
+        let chain_id = account
+            .get_chain_id_resource()?
+            .ok_or_else(|| format_err!("Chain id not found on the fork"))?
+            .chain_id();
+        let epoch = account
+            .get_configuration_resource()?
+            .ok_or_else(|| format_err!("Configuration not found on the fork"))?
+            .epoch();
+        // Transactions on the fork are not in blocks, so the time does not move.
+        let timestamp_usecs = account
+            .get_resource::<TimestampResource>()?
+            .ok_or_else(|| format_err!("Timestamp not found on the fork"))?
+            .timestamp
+            .microseconds;
+        Ok(Self {
+            fork,
+            fork_transaction,
+            chain_id,
+            epoch,
+            timestamp_usecs,
+        })
+    }
+
+    pub fn chain_id(&self) -> ChainId {
+        self.chain_id
+    }
+
+    fn ensure_on_fork(&self, version: Version) -> Result<()> {
+        ensure!(
+            version >= self.fork.version() && version <= self.fork.latest_version(),
+            "Version {} is not on the fork, which is at versions {} to {}.",
+            version,
+            self.fork.version(),
+            self.fork.latest_version()
+        );
+        Ok(())
+    }
+
+    fn block_event(&self, version: Version) -> NewBlockEvent {
+        let height = version - self.fork.version();
+        NewBlockEvent::new(
+            AccountAddress::ZERO,
+            self.epoch,
+            height,
+            height,
+            vec![],
+            AccountAddress::ZERO,
+            vec![],
+            self.timestamp_usecs,
+        )
+    }
+
+    fn transactions(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<ForkedTransaction>> {
+        ensure!(
+            start_version >= self.fork.version(),
+            "Version {} is before the fork at version {}.",
+            start_version,
+            self.fork.version()
+        );
+        let limit = std::cmp::min(limit, (ledger_version + 1).saturating_sub(start_version));
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+        if start_version == self.fork.version() {
+            let mut txns = vec![self.fork_transaction.clone()];
+            txns.extend(self.fork.get_transactions(start_version + 1, limit - 1));
+            Ok(txns)
+        } else {
+            Ok(self.fork.get_transactions(start_version, limit))
+        }
+    }
+
+    fn info_list_with_proof(txns: &[ForkedTransaction]) -> TransactionInfoListWithProof {
+        TransactionInfoListWithProof::new(
+            TransactionAccumulatorRangeProof::new_empty(),
+            txns.iter().map(|txn| txn.info.clone()).collect(),
+        )
+    }
+
+    fn with_proof(txn: ForkedTransaction, fetch_events: bool) -> TransactionWithProof {
+        TransactionWithProof::new(
+            txn.version,
+            txn.transaction,
+            fetch_events.then(|| txn.output.events().to_vec()),
+            TransactionInfoWithProof::new(AccumulatorProof::new(vec![]), txn.info),
+        )
+    }
+}
+
+impl DbReader for ForkDb {
+    fn get_transactions(
+        &self,
+        start_version: Version,
+        batch_size: u64,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<TransactionListWithProof> {
+        let txns = self.transactions(start_version, batch_size, ledger_version)?;
+        let proof = Self::info_list_with_proof(&txns);
+        let events = fetch_events.then(|| {
+            txns.iter()
+                .map(|txn| txn.output.events().to_vec())
+                .collect()
+        });
+        Ok(TransactionListWithProof::new(
+            txns.into_iter().map(|txn| txn.transaction).collect(),
+            events,
+            (!proof.transaction_infos.is_empty()).then_some(start_version),
+            proof,
+        ))
+    }
+
+    fn get_gas_prices(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<u64>> {
+        let start_version = std::cmp::max(start_version, self.fork.version());
+        Ok(self
+            .transactions(start_version, limit, ledger_version)?
+            .into_iter()
+            .filter_map(|txn| match txn.transaction {
+                Transaction::UserTransaction(txn) => Some(txn.gas_unit_price()),
+                _ => None,
+            })
+            .collect())
+    }
+
+    fn get_transaction_by_hash(
+        &self,
+        hash: HashValue,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<Option<TransactionWithProof>> {
+        Ok(self
+            .transactions(self.fork.version(), u64::MAX, ledger_version)?
+            .into_iter()
+            .find(|txn| txn.info.transaction_hash() == hash)
+            .map(|txn| Self::with_proof(txn, fetch_events)))
+    }
+
+    fn get_transaction_by_version(
+        &self,
+        version: Version,
+        ledger_version: Version,
+        fetch_events: bool,
+    ) -> Result<TransactionWithProof> {
+        self.transactions(version, 1, ledger_version)?
+            .pop()
+            .map(|txn| Self::with_proof(txn, fetch_events))
+            .ok_or_else(|| format_err!("Transaction {} not found", version))
+    }
+
+    fn get_first_viable_txn_version(&self) -> Result<Version> {
+        Ok(self.fork.version())
+    }
+
+    fn get_transaction_outputs(
+        &self,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<TransactionOutputListWithProof> {
+        let txns = self.transactions(start_version, limit, ledger_version)?;
+        let proof = Self::info_list_with_proof(&txns);
+        Ok(TransactionOutputListWithProof::new(
+            txns.into_iter()
+                .map(|txn| (txn.transaction, txn.output))
+                .collect(),
+            (!proof.transaction_infos.is_empty()).then_some(start_version),
+            proof,
+        ))
+    }
+
+    fn get_events(
+        &self,
+        event_key: &EventKey,
+        start: u64,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        let events = self
+            .transactions(self.fork.version(), u64::MAX, ledger_version)?
+            .into_iter()
+            .flat_map(|txn| {
+                let version = txn.version;
+                txn.output
+                    .events()
+                    .iter()
+                    .filter(|event| event.key() == event_key)
+                    .map(|event| EventWithVersion::new(version, event.clone()))
+                    .collect::<Vec<_>>()
+            });
+        Ok(match order {
+            Order::Ascending => events
+                .filter(|event| event.event.sequence_number() >= start)
+                .take(limit as usize)
+                .collect(),
+            Order::Descending => events
+                .filter(|event| event.event.sequence_number() <= start)
+                .collect::<Vec<_>>()
+                .into_iter()
+                .rev()
+                .take(limit as usize)
+                .collect(),
+        })
+    }
+
+    fn get_block_timestamp(&self, version: Version) -> Result<u64> {
+        self.ensure_on_fork(version)?;
+        Ok(self.timestamp_usecs)
+    }
+
+    fn get_next_block_event(&self, version: Version) -> Result<(Version, NewBlockEvent)> {
+        self.ensure_on_fork(version)?;
+        Ok((version, self.block_event(version)))
+    }
+
+    fn get_block_info_by_version(
+        &self,
+        version: Version,
+    ) -> Result<(Version, Version, NewBlockEvent)> {
+        self.ensure_on_fork(version)?;
+        Ok((version, version, self.block_event(version)))
+    }
+
+    fn get_block_info_by_height(&self, height: u64) -> Result<(Version, Version, NewBlockEvent)> {
+        let version = self.fork.version().saturating_add(height);
+        self.get_block_info_by_version(version)
+    }
+
+    fn get_prefixed_state_value_iterator(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        cursor: Option<&StateKey>,
+        version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>> {
+        // Only account prefixes are supported, which are all the REST API uses.
+        let prefix = key_prefix.encode()?;
+        ensure!(
+            prefix.len() == 1 + AccountAddress::LENGTH
+                && prefix[0] == StateKeyTag::AccessPath as u8,
+            "Only account prefixes are supported on a fork."
+        );
+        let address = AccountAddress::from_bytes(&prefix[1..])?;
+        let cursor = cursor.cloned();
+        Ok(Box::new(
+            self.fork
+                .state_view()
+                .get_account_state_values(address, version)?
+                .into_iter()
+                .filter(move |(state_key, _)| cursor.as_ref().map_or(true, |c| state_key >= c))
+                .map(|(state_key, value)| Ok((state_key, StateValue::new(value)))),
+        ))
+    }
+
+    fn get_latest_ledger_info_option(&self) -> Result<Option<LedgerInfoWithSignatures>> {
+        let block_info = BlockInfo::new(
+            self.epoch,
+            0,
+            HashValue::zero(),
+            HashValue::zero(),
+            self.fork.latest_version(),
+            self.timestamp_usecs,
+            None,
+        );
+        Ok(Some(LedgerInfoWithSignatures::new(
+            LedgerInfo::new(block_info, HashValue::zero()),
+            AggregateSignature::empty(),
+        )))
+    }
+
+    fn get_latest_state_checkpoint_version(&self) -> Result<Option<Version>> {
+        Ok(Some(self.fork.latest_version()))
+    }
+
+    fn get_account_transactions(
+        &self,
+        address: AccountAddress,
+        seq_num: u64,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<AccountTransactionsWithProof> {
+        Ok(AccountTransactionsWithProof::new(
+            self.transactions(self.fork.version(), u64::MAX, ledger_version)?
+                .into_iter()
+                .filter(|txn| match &txn.transaction {
+                    Transaction::UserTransaction(txn) => {
+                        txn.sender() == address && txn.sequence_number() >= seq_num
+                    }
+                    _ => false,
+                })
+                .take(limit as usize)
+                .map(|txn| Self::with_proof(txn, include_events))
+                .collect(),
+        ))
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        Ok(self
+            .fork
+            .state_view()
+            .get_state_value_by_version(state_key, version)?
+            .map(StateValue::new))
+    }
+
+    fn get_accumulator_root_hash(&self, _version: Version) -> Result<HashValue> {
+        Ok(HashValue::zero())
+    }
+
+    fn indexer_enabled(&self) -> bool {
+        false
+    }
+
+    fn get_state_storage_usage(&self, _version: Option<Version>) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::transaction::{
+        ExecutionStatus, TransactionOutput, TransactionStatus, WriteSetPayload,
+    };
+    use aptos_validator_interface::InMemoryDebuggerInterface;
+    use language_e2e_tests::{
+        account::Account, common_transactions::create_account_txn,
+        data_store::GENESIS_CHANGE_SET_HEAD,
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fork_db_serves_transactions_on_the_fork() {
+        let mut remote = InMemoryDebuggerInterface::default();
+        remote.commit_transaction(
+            0,
+            Transaction::GenesisTransaction(WriteSetPayload::Direct(
+                GENESIS_CHANGE_SET_HEAD.clone(),
+            )),
+            TransactionOutput::new(
+                GENESIS_CHANGE_SET_HEAD.write_set().clone(),
+                GENESIS_CHANGE_SET_HEAD.events().to_vec(),
+                0,
+                TransactionStatus::Keep(ExecutionStatus::Success),
+            ),
+        );
+        let fork = Arc::new(AptosFork::new(Arc::new(remote), 0));
+        let db = ForkDb::new(fork.clone()).await.unwrap();
+        assert_eq!(db.get_latest_version().unwrap(), 0);
+
+        let sender = Account::new_aptos_root();
+        let new_account = Account::new();
+        let txn = create_account_txn(&sender, &new_account, 0);
+        fork.execute_transaction(txn.clone()).unwrap();
+
+        assert_eq!(db.get_latest_version().unwrap(), 1);
+        assert_eq!(db.get_block_info_by_height(1).unwrap().0, 1);
+        let txns = db.get_transactions(0, 10, 1, false).unwrap();
+        assert_eq!(txns.first_transaction_version, Some(0));
+        assert_eq!(txns.transactions.len(), 2);
+        let txn_with_proof = db.get_transaction_by_version(1, 1, true).unwrap();
+        assert_eq!(
+            txn_with_proof.transaction,
+            Transaction::UserTransaction(txn.clone())
+        );
+        assert_eq!(
+            db.get_transaction_by_hash(
+                txn_with_proof.proof.transaction_info.transaction_hash(),
+                1,
+                false
+            )
+            .unwrap()
+            .map(|txn| txn.version),
+            Some(1)
+        );
+        assert_eq!(
+            db.get_account_transactions(*sender.address(), 0, 10, false, 1)
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // The new account's resources are only listed from the version it was created at.
+        let account_key = StateKey::AccessPath(new_account.make_account_access_path());
+        let list = |version| {
+            db.get_prefixed_state_value_iterator(
+                &StateKeyPrefix::from(*new_account.address()),
+                None,
+                version,
+            )
+            .unwrap()
+            .map(|item| item.unwrap().0)
+            .collect::<Vec<_>>()
+        };
+        assert!(list(0).is_empty());
+        assert!(list(1).contains(&account_key));
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local execution on top of the state of a remote chain.
+//!
+//! A fork reads the state of the remote at a pinned version lazily and keeps its own writes
+//! locally, so transactions can be tested against real network state without syncing it. Forks
+//! are used by tests and tools through `AptosDebugger::fork_at_version`, and served through the
+//! REST API by [`start_forked_node`], which is what `aptos node run-local-testnet --fork-url`
+//! runs.
+
+mod db;
+mod node;
+
+pub use crate::fork::{db::ForkDb, node::start_forked_node};
+
+use anyhow::{ensure, format_err, Result};
+use aptos_crypto::hash::{CryptoHash, EventAccumulatorHasher};
+use aptos_infallible::{Mutex, RwLock};
+use aptos_state_view::StateView;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    proof::accumulator::InMemoryAccumulator,
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::{
+        SignedTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionStatus,
+        Version,
+    },
+    write_set::{WriteOp, WriteSet},
+};
+use aptos_validator_interface::AptosValidatorInterface;
+use aptos_vm::{AptosVM, VMExecutor};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    sync::Arc,
+};
+use tokio::runtime::Handle;
+
+/// A `StateView` over the state of a remote chain at a pinned version with local modifications
+/// on top. Values missing locally are fetched from the remote lazily and cached, so each state
+/// key is fetched at most once.
+pub struct ForkedStateView {
+    remote: Arc<dyn AptosValidatorInterface + Send>,
+    // The runtime the remote is queried on, readers block on the queries from their own thread.
+    runtime: Handle,
+    version: Version,
+    // The values of the state keys read or written by the fork by the version they were written
+    // at, None for values that do not exist. Values fetched from the remote are at the fork
+    // version.
+    state: RwLock<HashMap<StateKey, BTreeMap<Version, Option<Vec<u8>>>>>,
+}
+
+impl ForkedStateView {
+    /// Forks the state right after the transaction at `version` was committed. Must be called
+    /// within a Tokio runtime, which the remote is then queried on.
+    pub fn new(remote: Arc<dyn AptosValidatorInterface + Send>, version: Version) -> Self {
+        Self {
+            remote,
+            runtime: Handle::current(),
+            version,
+            state: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Applies the write set of the transaction at `version` locally, without affecting the
+    /// remote.
+    pub fn apply_write_set(&self, version: Version, write_set: &WriteSet) {
+        let mut state = self.state.write();
+        for (state_key, write_op) in write_set.iter() {
+            let value = match write_op {
+                WriteOp::Creation(blob) | WriteOp::Modification(blob) => Some(blob.clone()),
+                WriteOp::Deletion => None,
+            };
+            state
+                .entry(state_key.clone())
+                .or_default()
+                .insert(version, value);
+        }
+    }
+
+    /// Returns the value of `state_key` right after the transaction at `version` was committed,
+    /// which must not be before the fork.
+    pub fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<Vec<u8>>> {
+        self.ensure_forked(version)?;
+        if let Some(values) = self.state.read().get(state_key) {
+            if let Some((_, value)) = values.range(..=version).next_back() {
+                return Ok(value.clone());
+            }
+        }
+
+        let remote = self.remote.clone();
+        let remote_key = state_key.clone();
+        let fork_version = self.version;
+        let value = self
+            .block_on(async move {
+                remote
+                    .get_state_value_by_version(&remote_key, fork_version)
+                    .await
+            })?
+            .map(StateValue::into_bytes);
+        // Another reader may have fetched the value in the meantime, local writes are all after
+        // the fork version.
+        Ok(self
+            .state
+            .write()
+            .entry(state_key.clone())
+            .or_default()
+            .entry(fork_version)
+            .or_insert(value)
+            .clone())
+    }
+
+    /// Returns the values of the state keys of `address` right after the transaction at
+    /// `version` was committed, which must not be before the fork. The remote only lists
+    /// resources, so modules are only included once the fork has read or written them.
+    pub fn get_account_state_values(
+        &self,
+        address: AccountAddress,
+        version: Version,
+    ) -> Result<BTreeMap<StateKey, Vec<u8>>> {
+        self.ensure_forked(version)?;
+        let remote = self.remote.clone();
+        let fork_version = self.version;
+        let account_state = self.block_on(async move {
+            remote
+                .get_account_state_by_version(address, fork_version)
+                .await
+        })?;
+
+        let mut state = self.state.write();
+        for (path, value) in account_state.iter().flat_map(|state| state.iter()) {
+            state
+                .entry(StateKey::AccessPath(AccessPath::new(address, path.clone())))
+                .or_default()
+                .entry(fork_version)
+                .or_insert_with(|| Some(value.clone()));
+        }
+        Ok(state
+            .iter()
+            .filter(|(state_key, _)| {
+                matches!(state_key, StateKey::AccessPath(path) if path.address == address)
+            })
+            .filter_map(|(state_key, values)| {
+                let (_, value) = values.range(..=version).next_back()?;
+                Some((state_key.clone(), value.clone()?))
+            })
+            .collect())
+    }
+
+    fn ensure_forked(&self, version: Version) -> Result<()> {
+        ensure!(
+            version >= self.version,
+            "Version {} is before the fork at version {}.",
+            version,
+            self.version
+        );
+        Ok(())
+    }
+
+    fn block_on<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = Result<T>> + Send + 'static,
+    ) -> Result<T> {
+        futures::executor::block_on(self.runtime.spawn(future))?
+    }
+}
+
+impl StateView for ForkedStateView {
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<Vec<u8>>> {
+        self.get_state_value_by_version(state_key, Version::MAX)
+    }
+
+    fn is_genesis(&self) -> bool {
+        false
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        Ok(StateStorageUsage::new_untracked())
+    }
+}
+
+/// A transaction committed on a fork, or the one at the fork version.
+#[derive(Clone, Debug)]
+pub struct ForkedTransaction {
+    pub version: Version,
+    pub transaction: Transaction,
+    pub output: TransactionOutput,
+    pub info: TransactionInfo,
+}
+
+impl ForkedTransaction {
+    /// Returns `None` if the transaction was not kept.
+    fn new(version: Version, transaction: Transaction, output: TransactionOutput) -> Option<Self> {
+        let status = match output.status() {
+            TransactionStatus::Keep(status) => status.clone(),
+            TransactionStatus::Discard(_) | TransactionStatus::Retry => return None,
+        };
+        let event_hashes: Vec<_> = output.events().iter().map(CryptoHash::hash).collect();
+        let info = TransactionInfo::new(
+            transaction.hash(),
+            CryptoHash::hash(output.write_set()),
+            InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash(),
+            None,
+            output.gas_used(),
+            status,
+        );
+        Some(Self {
+            version,
+            transaction,
+            output,
+            info,
+        })
+    }
+}
+
+/// Executes new transactions on top of the state of a remote chain at a pinned version, e.g. to
+/// test contracts against real network state locally. Outputs of kept transactions are applied
+/// to the local fork only.
+pub struct AptosFork {
+    remote: Arc<dyn AptosValidatorInterface + Send>,
+    state_view: ForkedStateView,
+    version: Version,
+    // Transactions kept by the fork, the first one at `version + 1`.
+    transactions: RwLock<Vec<ForkedTransaction>>,
+    // Serializes execution, so that each block is executed on top of the previous one.
+    execution: Mutex<()>,
+}
+
+impl AptosFork {
+    /// Must be called within a Tokio runtime, see `ForkedStateView::new`.
+    pub fn new(remote: Arc<dyn AptosValidatorInterface + Send>, version: Version) -> Self {
+        Self {
+            remote: remote.clone(),
+            state_view: ForkedStateView::new(remote, version),
+            version,
+            transactions: RwLock::new(Vec::new()),
+            execution: Mutex::new(()),
+        }
+    }
+
+    /// The remote version the fork is pinned to.
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The version of the latest transaction kept by the fork, the fork version if there is
+    /// none.
+    pub fn latest_version(&self) -> Version {
+        self.version + self.transactions.read().len() as u64
+    }
+
+    pub fn state_view(&self) -> &ForkedStateView {
+        &self.state_view
+    }
+
+    /// Fetches the transaction at the fork version from the remote.
+    pub async fn get_fork_transaction(&self) -> Result<ForkedTransaction> {
+        let (transaction, output) = self
+            .remote
+            .get_committed_transactions_with_outputs(self.version, 1)
+            .await?
+            .pop()
+            .ok_or_else(|| format_err!("Transaction {} not found on the remote", self.version))?;
+        ForkedTransaction::new(self.version, transaction, output)
+            .ok_or_else(|| format_err!("Transaction {} was not kept", self.version))
+    }
+
+    /// Returns up to `limit` transactions kept by the fork, starting at `start_version`.
+    pub fn get_transactions(&self, start_version: Version, limit: u64) -> Vec<ForkedTransaction> {
+        let start = start_version.saturating_sub(self.version + 1) as usize;
+        self.transactions
+            .read()
+            .iter()
+            .skip(start)
+            .take(limit as usize)
+            .cloned()
+            .collect()
+    }
+
+    /// Executes a block of transactions against the fork and commits the kept ones.
+    pub fn execute_transactions(&self, txns: Vec<Transaction>) -> Result<Vec<TransactionOutput>> {
+        let _execution = self.execution.lock();
+        let outputs = AptosVM::execute_block(txns.clone(), &self.state_view)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        let mut version = self.latest_version();
+        let mut committed = vec![];
+        for (txn, output) in txns.into_iter().zip(outputs.iter()) {
+            if let Some(txn) = ForkedTransaction::new(version + 1, txn, output.clone()) {
+                version += 1;
+                self.state_view
+                    .apply_write_set(version, txn.output.write_set());
+                committed.push(txn);
+            }
+        }
+        // The state is written before the transactions become visible, readers only read at
+        // committed versions.
+        self.transactions.write().extend(committed);
+        Ok(outputs)
+    }
+
+    pub fn execute_transaction(&self, txn: SignedTransaction) -> Result<TransactionOutput> {
+        self.execute_transactions(vec![Transaction::UserTransaction(txn)])?
+            .pop()
+            .ok_or_else(|| format_err!("No output for transaction"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::transaction::ExecutionStatus;
+    use aptos_validator_interface::InMemoryDebuggerInterface;
+    use language_e2e_tests::{
+        account::Account, common_transactions::create_account_txn,
+        data_store::GENESIS_CHANGE_SET_HEAD,
+    };
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fork_executes_on_top_of_remote_state() {
+        let remote = Arc::new(InMemoryDebuggerInterface::from_genesis(
+            GENESIS_CHANGE_SET_HEAD.write_set(),
+        ));
+        let fork = AptosFork::new(remote.clone(), 0);
+
+        let sender = Account::new_aptos_root();
+        let new_account = Account::new();
+        let output = fork
+            .execute_transaction(create_account_txn(&sender, &new_account, 0))
+            .unwrap();
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+        assert_eq!(fork.latest_version(), 1);
+
+        // The new account only exists on the fork, from the version it was created at.
+        let account_key = StateKey::AccessPath(new_account.make_account_access_path());
+        assert!(fork
+            .state_view()
+            .get_state_value(&account_key)
+            .unwrap()
+            .is_some());
+        assert!(fork
+            .state_view()
+            .get_state_value_by_version(&account_key, 0)
+            .unwrap()
+            .is_none());
+        assert!(remote
+            .get_state_value_by_version(&account_key, 0)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Values already read by the fork are served from its cache.
+        let num_state_queries = remote.num_state_queries();
+        let output = fork
+            .execute_transaction(create_account_txn(&sender, &Account::new(), 1))
+            .unwrap();
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+        assert!(remote.num_state_queries() - num_state_queries < num_state_queries);
+        assert_eq!(fork.get_transactions(2, 10).len(), 1);
+    }
+}
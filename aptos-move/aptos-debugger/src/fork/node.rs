@@ -0,0 +1,83 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::fork::{AptosFork, ForkDb};
+use anyhow::Result;
+use aptos_config::config::NodeConfig;
+use aptos_logger::info;
+use aptos_mempool::{
+    MempoolClientRequest, MempoolEventsReceiver, MinAcceptedGasPrice, SubmissionStatus,
+};
+use aptos_types::{
+    mempool_status::{MempoolStatus, MempoolStatusCode},
+    transaction::{SignedTransaction, TransactionStatus},
+};
+use consensus_types::evidence::EvidenceReader;
+use futures::{channel::mpsc, StreamExt};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const MEMPOOL_CLIENT_CHANNEL_SIZE: usize = 1_024;
+
+/// Starts the REST API of a node on top of a fork, at the address in `config.api`. Transactions
+/// submitted through it are executed on the fork right away, each in a block of its own, instead
+/// of going through mempool and consensus, so sequence numbers must be used in order.
+///
+/// The node runs as long as the returned runtime is kept.
+pub async fn start_forked_node(config: &NodeConfig, fork: Arc<AptosFork>) -> Result<Runtime> {
+    let db = ForkDb::new(fork.clone()).await?;
+    info!(
+        "Serving the fork at version {} of chain {}",
+        fork.version(),
+        db.chain_id()
+    );
+    let (mempool_client_sender, mempool_client_events) = mpsc::channel(MEMPOOL_CLIENT_CHANNEL_SIZE);
+    let runtime = aptos_api::bootstrap(
+        config,
+        db.chain_id(),
+        Arc::new(db),
+        mempool_client_sender,
+        None,
+        EvidenceReader::new(),
+    )?;
+    runtime.spawn(handle_mempool_requests(fork, mempool_client_events));
+    Ok(runtime)
+}
+
+async fn handle_mempool_requests(fork: Arc<AptosFork>, mut requests: MempoolEventsReceiver) {
+    while let Some(request) = requests.next().await {
+        match request {
+            MempoolClientRequest::SubmitTransaction(txn, callback) => {
+                let fork = fork.clone();
+                let status = tokio::task::spawn_blocking(move || submit_transaction(&fork, txn))
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|status| status);
+                let _ = callback.send(status);
+            }
+            // Transactions are never pending on a fork.
+            MempoolClientRequest::GetTransactionByHash(_, callback) => {
+                let _ = callback.send(None);
+            }
+            MempoolClientRequest::GetMinAcceptedGasPrice(callback) => {
+                let _ = callback.send(MinAcceptedGasPrice::Any);
+            }
+        }
+    }
+}
+
+fn submit_transaction(fork: &AptosFork, txn: SignedTransaction) -> Result<SubmissionStatus> {
+    let output = fork.execute_transaction(txn)?;
+    Ok(match output.status() {
+        TransactionStatus::Keep(_) => (MempoolStatus::new(MempoolStatusCode::Accepted), None),
+        TransactionStatus::Discard(status) => (
+            MempoolStatus::new(MempoolStatusCode::VmError),
+            Some(*status),
+        ),
+        TransactionStatus::Retry => (
+            MempoolStatus::new(MempoolStatusCode::UnknownStatus)
+                .with_message("Transaction was not executed".to_string()),
+            None,
+        ),
+    })
+}
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{format_err, Result};
-use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
+use aptos_gas::{
+    AbstractValueSizeGasParameters, GasProfile, NativeGasParameters, LATEST_GAS_FEATURE_VERSION,
+};
 use aptos_resource_viewer::{AnnotatedAccountStateBlob, AptosValueAnnotator};
 use aptos_rest_client::Client;
 use aptos_types::{
@@ -14,13 +16,19 @@ use aptos_validator_interface::{
     AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
 };
 use aptos_vm::{
-    data_cache::StorageAdapter,
+    data_cache::{StateViewCache, StorageAdapter},
     move_vm_ext::{MoveVmExt, SessionExt, SessionId},
     AptosVM, VMExecutor,
 };
 use move_binary_format::errors::VMResult;
 use std::{path::Path, sync::Arc};
 
+mod fork;
+mod replay;
+
+pub use crate::fork::{start_forked_node, AptosFork, ForkDb, ForkedStateView, ForkedTransaction};
+pub use crate::replay::{EventSummary, ReplayOverrides, ReplayReport, ValueDiff, WriteSetDiff};
+
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
 }
@@ -76,6 +84,71 @@ impl AptosDebugger {
         Ok(ret)
     }
 
+    /// Replays past transactions one at a time, profiling the gas usage of user transactions.
+    /// Each transaction is executed against the state right before its version, so this is
+    /// slower than `execute_past_transactions` but does not need to care about epoch changes.
+    pub async fn execute_past_transactions_with_gas_profile(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<Vec<(TransactionOutput, Option<GasProfile>)>> {
+        let txns = self
+            .debugger
+            .get_committed_transactions(begin, limit)
+            .await?;
+        let mut ret = vec![];
+        for (version, txn) in (begin..).zip(txns.into_iter()) {
+            match txn {
+                Transaction::UserTransaction(signed_txn) => {
+                    let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+                    let (_, output, gas_profile) =
+                        AptosVM::execute_user_transaction_with_gas_profile(&state_view, signed_txn);
+                    ret.push((output.into_transaction_output(&state_view), gas_profile));
+                }
+                txn => {
+                    let output = self
+                        .execute_transactions_at_version(version, vec![txn])?
+                        .pop()
+                        .ok_or_else(|| format_err!("No output for transaction {}", version))?;
+                    ret.push((output, None));
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Replays the transaction committed at `version` against the state right before it, with
+    /// `overrides` applied on top, and diffs the result against the committed output.
+    pub async fn replay_transaction_with_overrides(
+        &self,
+        version: Version,
+        overrides: &ReplayOverrides,
+    ) -> Result<ReplayReport> {
+        let (txn, committed_output) = self
+            .debugger
+            .get_committed_transactions_with_outputs(version, 1)
+            .await?
+            .pop()
+            .ok_or_else(|| format_err!("Transaction {} is not committed", version))?;
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let overridden_state_view = StateViewCache::from_map_ref(&state_view, overrides.writes());
+        let replayed_output = AptosVM::execute_block(vec![txn], &overridden_state_view)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?
+            .pop()
+            .ok_or_else(|| format_err!("No output for transaction {}", version))?;
+        Ok(ReplayReport::new(
+            version,
+            &committed_output,
+            &replayed_output,
+        ))
+    }
+
+    /// Forks the state right after the transaction at `version` was committed, so new
+    /// transactions can be executed on top of it locally.
+    pub fn fork_at_version(&self, version: Version) -> AptosFork {
+        AptosFork::new(self.debugger.clone(), version)
+    }
+
     pub async fn execute_transactions_by_epoch(
         &self,
         begin: Version,
@@ -179,3 +252,102 @@ fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
         .iter()
         .any(|event| *event.key() == new_epoch_event_key)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_gas::gen::current_gas_schedule;
+    use aptos_types::transaction::{ExecutionStatus, TransactionStatus};
+    use aptos_validator_interface::InMemoryDebuggerInterface;
+    use language_e2e_tests::{
+        account::Account,
+        common_transactions::create_account_txn,
+        data_store::{FakeDataStore, GENESIS_CHANGE_SET_HEAD},
+    };
+    use package_builder::PackageBuilder;
+
+    /// Returns a debugger over a chain whose only transaction after genesis, at version 1,
+    /// creates an account.
+    fn debugger_with_committed_transaction() -> AptosDebugger {
+        let genesis_write_set = GENESIS_CHANGE_SET_HEAD.write_set();
+        let mut remote = InMemoryDebuggerInterface::from_genesis(genesis_write_set);
+        let mut data_store = FakeDataStore::default();
+        data_store.add_write_set(genesis_write_set);
+
+        let txn = Transaction::UserTransaction(create_account_txn(
+            &Account::new_aptos_root(),
+            &Account::new(),
+            0,
+        ));
+        let output = AptosVM::execute_block(vec![txn.clone()], &data_store)
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+        remote.commit_transaction(1, txn, output);
+        AptosDebugger::new(Arc::new(remote))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replay_without_overrides() {
+        let debugger = debugger_with_committed_transaction();
+        let report = debugger
+            .replay_transaction_with_overrides(1, &ReplayOverrides::default())
+            .await
+            .unwrap();
+        assert!(report.is_identical());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replay_with_module_override() {
+        let debugger = debugger_with_committed_transaction();
+        let mut builder = PackageBuilder::new("AptosAccountOverride");
+        builder.add_source(
+            "aptos_account",
+            r#"
+            module 0x1::aptos_account {
+                public entry fun create_account(_auth_key: address) { abort 42 }
+            }
+            "#,
+        );
+        let package_dir = builder.write_to_temp().unwrap();
+        let overrides =
+            ReplayOverrides::from_files(Some(package_dir.path().to_path_buf()), None).unwrap();
+
+        let report = debugger
+            .replay_transaction_with_overrides(1, &overrides)
+            .await
+            .unwrap();
+        assert!(report.status.is_changed());
+        assert!(report.status.replayed.contains("MoveAbort"));
+        assert!(!report.write_set.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_replay_with_gas_schedule_override() {
+        let debugger = debugger_with_committed_transaction();
+        let mut gas_schedule = current_gas_schedule();
+        for (name, value) in gas_schedule.entries.iter_mut() {
+            if name == "txn.min_transaction_gas_units" {
+                *value *= 2;
+            }
+        }
+        let gas_schedule_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            gas_schedule_file.path(),
+            serde_json::to_vec(&gas_schedule).unwrap(),
+        )
+        .unwrap();
+        let overrides = ReplayOverrides::from_files(None, Some(gas_schedule_file.path())).unwrap();
+
+        let report = debugger
+            .replay_transaction_with_overrides(1, &overrides)
+            .await
+            .unwrap();
+        assert!(!report.status.is_changed());
+        assert!(report.gas_used.replayed > report.gas_used.committed);
+    }
+}
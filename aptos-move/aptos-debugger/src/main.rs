@@ -0,0 +1,58 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_debugger::{AptosDebugger, ReplayOverrides};
+use aptos_rest_client::Client;
+use aptos_types::transaction::Version;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use url::Url;
+
+#[derive(Subcommand)]
+pub enum Target {
+    /// Use full node's rest api as query endpoint.
+    Rest { endpoint: String },
+    /// Use a local db instance to serve as query endpoint.
+    Db { path: PathBuf },
+}
+
+#[derive(Parser)]
+pub struct Argument {
+    #[clap(subcommand)]
+    target: Target,
+
+    /// Version of the committed transaction to replay.
+    #[clap(long)]
+    version: Version,
+
+    /// Path to a Move package (e.g. a modified framework) whose modules replace the on-chain
+    /// code during the replay.
+    #[clap(long, parse(from_os_str))]
+    framework_path: Option<PathBuf>,
+
+    /// Path to a JSON encoded `GasScheduleV2` that replaces the on-chain gas schedule during the
+    /// replay.
+    #[clap(long, parse(from_os_str))]
+    gas_schedule: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Argument::parse();
+
+    let debugger = match args.target {
+        Target::Rest { endpoint } => {
+            AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?
+        }
+        Target::Db { path } => AptosDebugger::db(path)?,
+    };
+
+    let overrides = ReplayOverrides::from_files(args.framework_path, args.gas_schedule.as_deref())?;
+
+    let report = debugger
+        .replay_transaction_with_overrides(args.version, &overrides)
+        .await?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
@@ -0,0 +1,403 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use aptos_types::{
+    access_path::AccessPath,
+    contract_event::ContractEvent,
+    on_chain_config::{access_path_for_config, GasScheduleV2, OnChainConfig},
+    state_store::state_key::StateKey,
+    transaction::{TransactionOutput, Version},
+    write_set::WriteOp,
+};
+use framework::{BuildOptions, BuiltPackage};
+use move_binary_format::file_format::CompiledModule;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// Modifications applied on top of the on-chain state before replaying a transaction, e.g. a
+/// locally compiled framework or a different gas schedule.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayOverrides {
+    writes: BTreeMap<StateKey, WriteOp>,
+}
+
+impl ReplayOverrides {
+    /// Loads the overrides given on the command line: the modules of the Move package at
+    /// `package_path` and the JSON encoded `GasScheduleV2` at `gas_schedule_path`.
+    pub fn from_files(
+        package_path: Option<PathBuf>,
+        gas_schedule_path: Option<&Path>,
+    ) -> Result<Self> {
+        let mut overrides = Self::default();
+        if let Some(package_path) = package_path {
+            overrides = overrides.with_package(package_path)?;
+        }
+        if let Some(gas_schedule_path) = gas_schedule_path {
+            overrides = overrides.with_gas_schedule_file(gas_schedule_path)?;
+        }
+        Ok(overrides)
+    }
+
+    /// Replaces the on-chain code of the modules of the Move package at `package_path`, e.g. a
+    /// modified framework.
+    pub fn with_package(self, package_path: PathBuf) -> Result<Self> {
+        let package = BuiltPackage::build(package_path, BuildOptions::default())?;
+        self.with_modules(package.extract_code())
+    }
+
+    /// Replaces the on-chain code of the given (serialized) modules.
+    pub fn with_modules(mut self, modules: impl IntoIterator<Item = Vec<u8>>) -> Result<Self> {
+        for module_bytes in modules {
+            let module = CompiledModule::deserialize(&module_bytes)
+                .map_err(|e| format_err!("Failure deserializing module: {:?}", e))?;
+            self.writes.insert(
+                StateKey::AccessPath(AccessPath::code_access_path(module.self_id())),
+                WriteOp::Modification(module_bytes),
+            );
+        }
+        Ok(self)
+    }
+
+    /// Replaces the on-chain gas schedule.
+    pub fn with_gas_schedule(mut self, gas_schedule: &GasScheduleV2) -> Result<Self> {
+        self.writes.insert(
+            StateKey::AccessPath(access_path_for_config(GasScheduleV2::CONFIG_ID)),
+            WriteOp::Modification(bcs::to_bytes(gas_schedule)?),
+        );
+        Ok(self)
+    }
+
+    /// Replaces the on-chain gas schedule with the JSON encoded `GasScheduleV2` at `path`.
+    pub fn with_gas_schedule_file(self, path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| format_err!("Failure reading {}: {}", path.display(), e))?;
+        let gas_schedule: GasScheduleV2 = serde_json::from_slice(&bytes)
+            .map_err(|e| format_err!("Failure parsing gas schedule {}: {}", path.display(), e))?;
+        self.with_gas_schedule(&gas_schedule)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    pub(crate) fn writes(&self) -> &BTreeMap<StateKey, WriteOp> {
+        &self.writes
+    }
+}
+
+/// A value as committed on chain and as observed when replaying the transaction.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ValueDiff<T> {
+    pub committed: T,
+    pub replayed: T,
+}
+
+impl<T: PartialEq> ValueDiff<T> {
+    pub fn is_changed(&self) -> bool {
+        self.committed != self.replayed
+    }
+}
+
+/// The difference between the committed and the replayed write to a single state key. Values are
+/// hex encoded, `None` stands for a deletion.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WriteSetDiff {
+    /// Written only by the replayed transaction.
+    Added {
+        state_key: String,
+        replayed: Option<String>,
+    },
+    /// Written only by the committed transaction.
+    Removed {
+        state_key: String,
+        committed: Option<String>,
+    },
+    /// Written by both, with different values.
+    Modified {
+        state_key: String,
+        committed: Option<String>,
+        replayed: Option<String>,
+    },
+}
+
+/// A summary of an event emitted by only one of the committed and replayed transactions.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EventSummary {
+    pub key: String,
+    pub sequence_number: u64,
+    pub type_tag: String,
+    pub data: String,
+}
+
+impl From<&ContractEvent> for EventSummary {
+    fn from(event: &ContractEvent) -> Self {
+        Self {
+            key: event.key().to_string(),
+            sequence_number: event.sequence_number(),
+            type_tag: event.type_tag().to_string(),
+            data: hex::encode(event.event_data()),
+        }
+    }
+}
+
+/// Structured report of how replaying a committed transaction differs from what was committed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ReplayReport {
+    pub version: Version,
+    pub status: ValueDiff<String>,
+    pub gas_used: ValueDiff<u64>,
+    pub write_set: Vec<WriteSetDiff>,
+    /// Events emitted only by the replayed transaction.
+    pub added_events: Vec<EventSummary>,
+    /// Events emitted only by the committed transaction.
+    pub removed_events: Vec<EventSummary>,
+}
+
+impl ReplayReport {
+    pub fn new(
+        version: Version,
+        committed: &TransactionOutput,
+        replayed: &TransactionOutput,
+    ) -> Self {
+        let status = ValueDiff {
+            committed: format!("{:?}", committed.status()),
+            replayed: format!("{:?}", replayed.status()),
+        };
+        let gas_used = ValueDiff {
+            committed: committed.gas_used(),
+            replayed: replayed.gas_used(),
+        };
+
+        let committed_writes: BTreeMap<_, _> = committed.write_set().iter().collect();
+        let replayed_writes: BTreeMap<_, _> = replayed.write_set().iter().collect();
+        let keys: BTreeSet<_> = committed_writes
+            .keys()
+            .chain(replayed_writes.keys())
+            .collect();
+        let write_set = keys
+            .into_iter()
+            .filter_map(|key| {
+                let state_key = display_state_key(key);
+                match (committed_writes.get(key), replayed_writes.get(key)) {
+                    (None, Some(op)) => Some(WriteSetDiff::Added {
+                        state_key,
+                        replayed: display_write_op(op),
+                    }),
+                    (Some(op), None) => Some(WriteSetDiff::Removed {
+                        state_key,
+                        committed: display_write_op(op),
+                    }),
+                    (Some(committed_op), Some(replayed_op)) if committed_op != replayed_op => {
+                        Some(WriteSetDiff::Modified {
+                            state_key,
+                            committed: display_write_op(committed_op),
+                            replayed: display_write_op(replayed_op),
+                        })
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let added_events = replayed
+            .events()
+            .iter()
+            .filter(|event| !committed.events().contains(event))
+            .map(EventSummary::from)
+            .collect();
+        let removed_events = committed
+            .events()
+            .iter()
+            .filter(|event| !replayed.events().contains(event))
+            .map(EventSummary::from)
+            .collect();
+
+        Self {
+            version,
+            status,
+            gas_used,
+            write_set,
+            added_events,
+            removed_events,
+        }
+    }
+
+    /// Whether the replayed transaction produced exactly the committed output.
+    pub fn is_identical(&self) -> bool {
+        !self.status.is_changed()
+            && !self.gas_used.is_changed()
+            && self.write_set.is_empty()
+            && self.added_events.is_empty()
+            && self.removed_events.is_empty()
+    }
+}
+
+fn display_state_key(state_key: &StateKey) -> String {
+    match state_key {
+        StateKey::AccessPath(path) => path.to_string(),
+        StateKey::TableItem { handle, key } => {
+            format!(
+                "TableItem {{ handle: {:x}, key: {} }}",
+                handle.0,
+                hex::encode(key)
+            )
+        }
+        StateKey::Raw(key) => format!("Raw {{ key: {} }}", hex::encode(key)),
+    }
+}
+
+fn display_write_op(op: &WriteOp) -> Option<String> {
+    match op {
+        WriteOp::Creation(blob) | WriteOp::Modification(blob) => Some(hex::encode(blob)),
+        WriteOp::Deletion => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_types::{
+        account_address::AccountAddress,
+        event::EventKey,
+        transaction::{ExecutionStatus, TransactionStatus},
+        write_set::WriteSetMut,
+    };
+    use move_core_types::language_storage::TypeTag;
+
+    fn state_key(name: &str) -> StateKey {
+        StateKey::Raw(name.as_bytes().to_vec())
+    }
+
+    fn event(sequence_number: u64, data: Vec<u8>) -> ContractEvent {
+        ContractEvent::new(
+            EventKey::new(0, AccountAddress::ONE),
+            sequence_number,
+            TypeTag::U64,
+            data,
+        )
+    }
+
+    fn output(
+        writes: Vec<(StateKey, WriteOp)>,
+        events: Vec<ContractEvent>,
+        gas_used: u64,
+        status: ExecutionStatus,
+    ) -> TransactionOutput {
+        TransactionOutput::new(
+            WriteSetMut::new(writes).freeze().unwrap(),
+            events,
+            gas_used,
+            TransactionStatus::Keep(status),
+        )
+    }
+
+    #[test]
+    fn test_identical_outputs() {
+        let committed = output(
+            vec![(state_key("a"), WriteOp::Modification(vec![1]))],
+            vec![event(0, vec![1])],
+            10,
+            ExecutionStatus::Success,
+        );
+        let report = ReplayReport::new(1, &committed, &committed);
+        assert!(report.is_identical());
+        assert!(report.write_set.is_empty());
+        assert_eq!(
+            report.gas_used,
+            ValueDiff {
+                committed: 10,
+                replayed: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_write_set_diff() {
+        let committed = output(
+            vec![
+                (state_key("modified"), WriteOp::Modification(vec![1])),
+                (state_key("removed"), WriteOp::Deletion),
+                (state_key("unchanged"), WriteOp::Creation(vec![2])),
+            ],
+            vec![],
+            10,
+            ExecutionStatus::Success,
+        );
+        let replayed = output(
+            vec![
+                (state_key("added"), WriteOp::Creation(vec![3])),
+                (state_key("modified"), WriteOp::Deletion),
+                (state_key("unchanged"), WriteOp::Creation(vec![2])),
+            ],
+            vec![],
+            10,
+            ExecutionStatus::Success,
+        );
+        let report = ReplayReport::new(1, &committed, &replayed);
+        assert!(!report.is_identical());
+        assert_eq!(
+            report.write_set,
+            vec![
+                WriteSetDiff::Added {
+                    state_key: display_state_key(&state_key("added")),
+                    replayed: Some("03".to_string()),
+                },
+                WriteSetDiff::Modified {
+                    state_key: display_state_key(&state_key("modified")),
+                    committed: Some("01".to_string()),
+                    replayed: None,
+                },
+                WriteSetDiff::Removed {
+                    state_key: display_state_key(&state_key("removed")),
+                    committed: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_gas_and_status_diff() {
+        let unchanged_event = event(0, vec![1]);
+        let removed_event = event(1, vec![2]);
+        let added_event = event(1, vec![3]);
+        let committed = output(
+            vec![],
+            vec![unchanged_event.clone(), removed_event.clone()],
+            10,
+            ExecutionStatus::Success,
+        );
+        let replayed = output(
+            vec![],
+            vec![unchanged_event, added_event.clone()],
+            20,
+            ExecutionStatus::OutOfGas,
+        );
+        let report = ReplayReport::new(1, &committed, &replayed);
+        assert!(!report.is_identical());
+        assert!(report.write_set.is_empty());
+        assert_eq!(report.added_events, vec![EventSummary::from(&added_event)]);
+        assert_eq!(
+            report.removed_events,
+            vec![EventSummary::from(&removed_event)]
+        );
+        assert_eq!(
+            report.gas_used,
+            ValueDiff {
+                committed: 10,
+                replayed: 20
+            }
+        );
+        assert_eq!(
+            report.status,
+            ValueDiff {
+                committed: format!("{:?}", TransactionStatus::Keep(ExecutionStatus::Success)),
+                replayed: format!("{:?}", TransactionStatus::Keep(ExecutionStatus::OutOfGas)),
+            }
+        );
+    }
+}
@@ -211,6 +211,11 @@ impl AptosGasMeter {
             .to_unit_round_down_with_params(&self.gas_params.txn)
     }
 
+    /// Remaining balance in internal gas units, without the rounding `balance()` does.
+    pub fn balance_internal(&self) -> InternalGas {
+        self.balance
+    }
+
     #[inline]
     fn charge(&mut self, amount: InternalGas) -> PartialVMResult<()> {
         match self.balance.checked_sub(amount) {
@@ -747,6 +752,50 @@ impl GasMeter for AptosGasMeter {
     }
 }
 
+/// The gas meter interface used by the Aptos VM to run a transaction, which on top of what the
+/// Move VM needs covers the charges made outside of Move execution.
+///
+/// Implemented by the official `AptosGasMeter`, as well as wrappers around it like `GasProfiler`.
+pub trait TransactionGasMeter: GasMeter {
+    fn feature_version(&self) -> u64;
+
+    fn balance(&self) -> Gas;
+
+    fn balance_internal(&self) -> InternalGas;
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+
+    fn charge_write_set_gas<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
+    ) -> VMResult<()>;
+}
+
+impl TransactionGasMeter for AptosGasMeter {
+    fn feature_version(&self) -> u64 {
+        AptosGasMeter::feature_version(self)
+    }
+
+    fn balance(&self) -> Gas {
+        AptosGasMeter::balance(self)
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        AptosGasMeter::balance_internal(self)
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+        AptosGasMeter::charge_intrinsic_gas_for_transaction(self, txn_size)
+    }
+
+    fn charge_write_set_gas<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
+    ) -> VMResult<()> {
+        AptosGasMeter::charge_write_set_gas(self, ops)
+    }
+}
+
 impl AptosGasMeter {
     pub fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
         let cost = self.gas_params.txn.calculate_intrinsic_gas(txn_size);
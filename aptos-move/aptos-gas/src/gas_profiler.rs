@@ -0,0 +1,587 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module contains a gas meter wrapper recording where the gas of a transaction goes, so
+//! developers can tell which Move functions, instructions, natives and storage accesses are
+//! expensive when simulating or replaying transactions.
+
+use crate::{algebra::Gas, gas_meter::TransactionGasMeter};
+use aptos_types::{access_path::Path, state_store::state_key::StateKey, write_set::WriteOp};
+use move_binary_format::errors::{PartialVMResult, VMResult};
+use move_core_types::{
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
+    identifier::IdentStr,
+    language_storage::ModuleId,
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use std::collections::BTreeMap;
+
+/// Gas spent in a Move function, including the functions it called, aggregated over all the
+/// calls made to it from the same caller. All amounts are in internal gas units.
+#[derive(Clone, Debug)]
+pub struct CallFrame {
+    pub num_calls: u64,
+    /// Gas charged for executing instructions, by instruction class.
+    pub instructions: BTreeMap<String, InternalGas>,
+    /// Gas charged by the native functions called, by function name.
+    pub natives: BTreeMap<String, InternalGas>,
+    /// Gas charged for loading resources from storage.
+    pub storage_reads: InternalGas,
+    /// The Move functions called, by function name.
+    pub callees: BTreeMap<String, CallFrame>,
+}
+
+impl CallFrame {
+    fn new() -> Self {
+        Self {
+            num_calls: 1,
+            instructions: BTreeMap::new(),
+            natives: BTreeMap::new(),
+            storage_reads: InternalGas::zero(),
+            callees: BTreeMap::new(),
+        }
+    }
+
+    /// Total gas spent in this function, including the functions it called.
+    pub fn total(&self) -> InternalGas {
+        let own = self
+            .instructions
+            .values()
+            .chain(self.natives.values())
+            .fold(self.storage_reads, |acc, cost| acc + *cost);
+        self.callees
+            .values()
+            .fold(own, |acc, callee| acc + callee.total())
+    }
+
+    /// Gas spent loading resources in this function, including the functions it called.
+    pub fn total_storage_reads(&self) -> InternalGas {
+        self.callees
+            .values()
+            .fold(self.storage_reads, |acc, callee| {
+                acc + callee.total_storage_reads()
+            })
+    }
+
+    fn merge(&mut self, other: CallFrame) {
+        self.num_calls += other.num_calls;
+        for (name, cost) in other.instructions {
+            *self
+                .instructions
+                .entry(name)
+                .or_insert_with(InternalGas::zero) += cost;
+        }
+        for (name, cost) in other.natives {
+            *self.natives.entry(name).or_insert_with(InternalGas::zero) += cost;
+        }
+        self.storage_reads += other.storage_reads;
+        for (name, callee) in other.callees {
+            add_callee(&mut self.callees, name, callee);
+        }
+    }
+
+    fn write_folded_stacks(&self, stack: &str, out: &mut String) {
+        for (name, cost) in &self.instructions {
+            write_folded_line(out, &format!("{};{}", stack, name), *cost);
+        }
+        for (name, cost) in &self.natives {
+            write_folded_line(out, &format!("{};{} [native]", stack, name), *cost);
+        }
+        write_folded_line(out, &format!("{};storage_read", stack), self.storage_reads);
+        for (name, callee) in &self.callees {
+            callee.write_folded_stacks(&format!("{};{}", stack, name), out);
+        }
+    }
+}
+
+/// Where the gas of a transaction went. All amounts are in internal gas units, see
+/// `gas_unit_scaling_factor` for their ratio to gas units.
+#[derive(Clone, Debug)]
+pub struct GasProfile {
+    /// What the transaction ran, like the entry function called, or "script".
+    pub entry_point: String,
+    /// Gas charged for the transaction itself, based on its size.
+    pub intrinsic: InternalGas,
+    /// Gas charged for executing Move code, including reading from storage.
+    pub execution: CallFrame,
+    /// Gas charged for writing to storage, by the state written.
+    pub storage_writes: BTreeMap<String, InternalGas>,
+}
+
+impl GasProfile {
+    pub fn total(&self) -> InternalGas {
+        self.intrinsic + self.execution.total() + self.total_storage_writes()
+    }
+
+    pub fn total_storage_writes(&self) -> InternalGas {
+        self.storage_writes
+            .values()
+            .fold(InternalGas::zero(), |acc, cost| acc + *cost)
+    }
+
+    /// Renders the profile in the folded stack format consumed by flamegraph tools like
+    /// `inferno-flamegraph` or `flamegraph.pl`: one line per stack, frames separated by ";",
+    /// followed by a space and the gas spent.
+    pub fn to_folded_stacks(&self) -> String {
+        let mut out = String::new();
+        write_folded_line(
+            &mut out,
+            &format!("{};intrinsic", self.entry_point),
+            self.intrinsic,
+        );
+        self.execution
+            .write_folded_stacks(&self.entry_point, &mut out);
+        for (key, cost) in &self.storage_writes {
+            write_folded_line(
+                &mut out,
+                &format!("{};storage_write;{}", self.entry_point, key),
+                *cost,
+            );
+        }
+        out
+    }
+}
+
+fn write_folded_line(out: &mut String, stack: &str, cost: InternalGas) {
+    if cost > InternalGas::zero() {
+        out.push_str(&format!("{} {}\n", stack, u64::from(cost)));
+    }
+}
+
+fn add_callee(callees: &mut BTreeMap<String, CallFrame>, name: String, callee: CallFrame) {
+    match callees.get_mut(&name) {
+        Some(existing) => existing.merge(callee),
+        None => {
+            callees.insert(name, callee);
+        }
+    }
+}
+
+fn describe_state_key(key: &StateKey) -> String {
+    match key {
+        StateKey::AccessPath(access_path) => match bcs::from_bytes::<Path>(&access_path.path) {
+            Ok(Path::Resource(struct_tag)) => format!(
+                "{} @ {}",
+                struct_tag,
+                access_path.address.short_str_lossless()
+            ),
+            Ok(Path::Code(module_id)) => format!("{} [code]", module_id.short_str_lossless()),
+            Err(_) => format!("{:?}", access_path),
+        },
+        StateKey::TableItem { handle, .. } => {
+            format!("table_item @ {}", handle.0.short_str_lossless())
+        }
+        StateKey::Raw(_) => "raw".to_string(),
+    }
+}
+
+/// A gas meter wrapping another one, recording what each charge was for into a `GasProfile`.
+///
+/// The Move VM only notifies the gas meter of calls, not of which functions are native, so a frame
+/// is opened for every call and turned into a native entry of the caller when a native function
+/// charges for its execution. Frames are closed when the VM drops them on return.
+pub struct GasProfiler<G> {
+    base: G,
+    entry_point: String,
+    intrinsic: InternalGas,
+    frames: Vec<(String, CallFrame)>,
+    storage_writes: BTreeMap<String, InternalGas>,
+}
+
+impl<G: TransactionGasMeter> GasProfiler<G> {
+    pub fn new(base: G, entry_point: String) -> Self {
+        Self {
+            base,
+            entry_point: entry_point.clone(),
+            intrinsic: InternalGas::zero(),
+            frames: vec![(entry_point, CallFrame::new())],
+            storage_writes: BTreeMap::new(),
+        }
+    }
+
+    pub fn new_function(base: G, module_id: &ModuleId, func_name: &IdentStr) -> Self {
+        Self::new(base, Self::function_name(module_id, func_name))
+    }
+
+    /// Stops profiling, returning the profile recorded. Frames left open by an aborted execution
+    /// are closed, so the gas they spent is still accounted for.
+    pub fn finish(mut self) -> GasProfile {
+        while self.frames.len() > 1 {
+            self.close_frame();
+        }
+        let (_, execution) = self.frames.pop().expect("Root frame must exist.");
+
+        GasProfile {
+            entry_point: self.entry_point,
+            intrinsic: self.intrinsic,
+            execution,
+            storage_writes: self.storage_writes,
+        }
+    }
+
+    fn function_name(module_id: &ModuleId, func_name: &str) -> String {
+        format!("{}::{}", module_id.short_str_lossless(), func_name)
+    }
+
+    fn current_frame(&mut self) -> &mut CallFrame {
+        &mut self.frames.last_mut().expect("Root frame must exist.").1
+    }
+
+    fn close_frame(&mut self) {
+        let (name, frame) = self.frames.pop().expect("Frame must exist.");
+        add_callee(&mut self.current_frame().callees, name, frame);
+    }
+
+    /// Runs a charge on the wrapped gas meter, returning how much it charged along with its
+    /// result.
+    fn measure<T>(&mut self, charge: impl FnOnce(&mut G) -> T) -> (InternalGas, T) {
+        let before = self.base.balance_internal();
+        let res = charge(&mut self.base);
+        let cost = before
+            .checked_sub(self.base.balance_internal())
+            .unwrap_or_else(InternalGas::zero);
+        (cost, res)
+    }
+
+    fn record_instruction(
+        &mut self,
+        name: &str,
+        charge: impl FnOnce(&mut G) -> PartialVMResult<()>,
+    ) -> PartialVMResult<()> {
+        let (cost, res) = self.measure(charge);
+        let instructions = &mut self.current_frame().instructions;
+        match instructions.get_mut(name) {
+            Some(total) => *total += cost,
+            None => {
+                instructions.insert(name.to_string(), cost);
+            }
+        }
+        res
+    }
+}
+
+impl<G: TransactionGasMeter> GasMeter for GasProfiler<G> {
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        self.record_instruction(&format!("{:?}", instr), |base| {
+            base.charge_simple_instr(instr)
+        })
+    }
+
+    fn charge_native_function_before_execution(
+        &mut self,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.base
+            .charge_native_function_before_execution(ty_args, args)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView>>,
+    ) -> PartialVMResult<()> {
+        let (cost, res) = self.measure(|base| base.charge_native_function(amount, ret_vals));
+        // The frame opened by the call turns out to be for a native function, which never gets
+        // dropped, so close it here.
+        let name = match self.frames.len() {
+            1 => "unknown".to_string(),
+            _ => self.frames.pop().expect("Frame must exist.").0,
+        };
+        *self
+            .current_frame()
+            .natives
+            .entry(name)
+            .or_insert_with(InternalGas::zero) += cost;
+        res
+    }
+
+    fn charge_load_resource(
+        &mut self,
+        loaded: Option<(NumBytes, impl ValueView)>,
+    ) -> PartialVMResult<()> {
+        let (cost, res) = self.measure(|base| base.charge_load_resource(loaded));
+        self.current_frame().storage_reads += cost;
+        res
+    }
+
+    fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("Pop", |base| base.charge_pop(popped_val))
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let res = self.record_instruction("Call", |base| {
+            base.charge_call(module_id, func_name, args, num_locals)
+        });
+        if res.is_ok() {
+            self.frames
+                .push((Self::function_name(module_id, func_name), CallFrame::new()));
+        }
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView>,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let res = self.record_instruction("CallGeneric", |base| {
+            base.charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+        });
+        if res.is_ok() {
+            self.frames
+                .push((Self::function_name(module_id, func_name), CallFrame::new()));
+        }
+        res
+    }
+
+    fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()> {
+        self.record_instruction("LdConst", |base| base.charge_ld_const(size))
+    }
+
+    fn charge_ld_const_after_deserialization(
+        &mut self,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("LdConst", |base| {
+            base.charge_ld_const_after_deserialization(val)
+        })
+    }
+
+    fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("CopyLoc", |base| base.charge_copy_loc(val))
+    }
+
+    fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("MoveLoc", |base| base.charge_move_loc(val))
+    }
+
+    fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("StLoc", |base| base.charge_store_loc(val))
+    }
+
+    fn charge_pack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let name = if is_generic { "PackGeneric" } else { "Pack" };
+        self.record_instruction(name, |base| base.charge_pack(is_generic, args))
+    }
+
+    fn charge_unpack(
+        &mut self,
+        is_generic: bool,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let name = if is_generic {
+            "UnpackGeneric"
+        } else {
+            "Unpack"
+        };
+        self.record_instruction(name, |base| base.charge_unpack(is_generic, args))
+    }
+
+    fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("ReadRef", |base| base.charge_read_ref(val))
+    }
+
+    fn charge_write_ref(
+        &mut self,
+        new_val: impl ValueView,
+        old_val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("WriteRef", |base| base.charge_write_ref(new_val, old_val))
+    }
+
+    fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("Eq", |base| base.charge_eq(lhs, rhs))
+    }
+
+    fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()> {
+        self.record_instruction("Neq", |base| base.charge_neq(lhs, rhs))
+    }
+
+    fn charge_borrow_global(
+        &mut self,
+        is_mut: bool,
+        is_generic: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let name = match (is_mut, is_generic) {
+            (false, false) => "ImmBorrowGlobal",
+            (false, true) => "ImmBorrowGlobalGeneric",
+            (true, false) => "MutBorrowGlobal",
+            (true, true) => "MutBorrowGlobalGeneric",
+        };
+        self.record_instruction(name, |base| {
+            base.charge_borrow_global(is_mut, is_generic, ty, is_success)
+        })
+    }
+
+    fn charge_exists(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        exists: bool,
+    ) -> PartialVMResult<()> {
+        let name = if is_generic {
+            "ExistsGeneric"
+        } else {
+            "Exists"
+        };
+        self.record_instruction(name, |base| base.charge_exists(is_generic, ty, exists))
+    }
+
+    fn charge_move_from(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let name = if is_generic {
+            "MoveFromGeneric"
+        } else {
+            "MoveFrom"
+        };
+        self.record_instruction(name, |base| base.charge_move_from(is_generic, ty, val))
+    }
+
+    fn charge_move_to(
+        &mut self,
+        is_generic: bool,
+        ty: impl TypeView,
+        val: impl ValueView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let name = if is_generic {
+            "MoveToGeneric"
+        } else {
+            "MoveTo"
+        };
+        self.record_instruction(name, |base| {
+            base.charge_move_to(is_generic, ty, val, is_success)
+        })
+    }
+
+    fn charge_vec_pack<'a>(
+        &mut self,
+        ty: impl TypeView + 'a,
+        args: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("VecPack", |base| base.charge_vec_pack(ty, args))
+    }
+
+    fn charge_vec_unpack(
+        &mut self,
+        ty: impl TypeView,
+        expect_num_elements: NumArgs,
+        elems: impl ExactSizeIterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("VecUnpack", |base| {
+            base.charge_vec_unpack(ty, expect_num_elements, elems)
+        })
+    }
+
+    fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.record_instruction("VecLen", |base| base.charge_vec_len(ty))
+    }
+
+    fn charge_vec_borrow(
+        &mut self,
+        is_mut: bool,
+        ty: impl TypeView,
+        is_success: bool,
+    ) -> PartialVMResult<()> {
+        let name = if is_mut {
+            "VecMutBorrow"
+        } else {
+            "VecImmBorrow"
+        };
+        self.record_instruction(name, |base| base.charge_vec_borrow(is_mut, ty, is_success))
+    }
+
+    fn charge_vec_push_back(
+        &mut self,
+        ty: impl TypeView,
+        val: impl ValueView,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("VecPushBack", |base| base.charge_vec_push_back(ty, val))
+    }
+
+    fn charge_vec_pop_back(
+        &mut self,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+    ) -> PartialVMResult<()> {
+        self.record_instruction("VecPopBack", |base| base.charge_vec_pop_back(ty, val))
+    }
+
+    fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()> {
+        self.record_instruction("VecSwap", |base| base.charge_vec_swap(ty))
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView>,
+    ) -> PartialVMResult<()> {
+        let res = self.base.charge_drop_frame(locals);
+        // The root frame stands for the entry point, which is dropped when it returns but stays
+        // open to collect anything charged afterwards.
+        if res.is_ok() && self.frames.len() > 1 {
+            self.close_frame();
+        }
+        res
+    }
+}
+
+impl<G: TransactionGasMeter> TransactionGasMeter for GasProfiler<G> {
+    fn feature_version(&self) -> u64 {
+        self.base.feature_version()
+    }
+
+    fn balance(&self) -> Gas {
+        self.base.balance()
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
+        let (cost, res) = self.measure(|base| base.charge_intrinsic_gas_for_transaction(txn_size));
+        self.intrinsic += cost;
+        res
+    }
+
+    fn charge_write_set_gas<'a>(
+        &mut self,
+        ops: impl IntoIterator<Item = (&'a StateKey, &'a WriteOp)>,
+    ) -> VMResult<()> {
+        // The write set gas is the sum of that of the individual writes, charge them one by one to
+        // tell them apart.
+        for (key, op) in ops {
+            let (cost, res) =
+                self.measure(|base| base.charge_write_set_gas(std::iter::once((key, op))));
+            *self
+                .storage_writes
+                .entry(describe_state_key(key))
+                .or_insert_with(InternalGas::zero) += cost;
+            res?;
+        }
+        Ok(())
+    }
+}
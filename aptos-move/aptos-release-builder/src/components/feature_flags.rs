@@ -19,6 +19,8 @@ pub struct Features {
 pub enum FeatureFlag {
     CodeDependencyCheck,
     TreatFriendAsPrivate,
+    MultisigAccounts,
+    FeePayer,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -85,6 +87,8 @@ impl From<FeatureFlag> for AFeatureFlag {
         match f {
             FeatureFlag::CodeDependencyCheck => AFeatureFlag::CODE_DEPENDENCY_CHECK,
             FeatureFlag::TreatFriendAsPrivate => AFeatureFlag::TREAT_FRIEND_AS_PRIVATE,
+            FeatureFlag::MultisigAccounts => AFeatureFlag::MULTISIG_ACCOUNTS,
+            FeatureFlag::FeePayer => AFeatureFlag::FEE_PAYER,
         }
     }
 }
@@ -95,6 +99,8 @@ impl From<AFeatureFlag> for FeatureFlag {
         match f {
             AFeatureFlag::CODE_DEPENDENCY_CHECK => FeatureFlag::CodeDependencyCheck,
             AFeatureFlag::TREAT_FRIEND_AS_PRIVATE => FeatureFlag::TreatFriendAsPrivate,
+            AFeatureFlag::MULTISIG_ACCOUNTS => FeatureFlag::MultisigAccounts,
+            AFeatureFlag::FEE_PAYER => FeatureFlag::FeePayer,
         }
     }
 }
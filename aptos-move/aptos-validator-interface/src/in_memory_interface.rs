@@ -0,0 +1,155 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosValidatorInterface;
+use anyhow::{anyhow, Result};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, TransactionOutput, Version},
+    write_set::{WriteOp, WriteSet},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// An in-process `AptosValidatorInterface` backed by versioned in-memory state, used as a
+/// stand-in for a remote endpoint in offline tests.
+#[derive(Default)]
+pub struct InMemoryDebuggerInterface {
+    // state_key -> version -> value written at that version (None for deletions).
+    state: BTreeMap<StateKey, BTreeMap<Version, Option<StateValue>>>,
+    transactions: BTreeMap<Version, (Transaction, TransactionOutput)>,
+    latest_version: Version,
+    num_state_queries: AtomicUsize,
+}
+
+impl InMemoryDebuggerInterface {
+    /// Creates an interface whose state at version 0 is the one written by the genesis write set.
+    pub fn from_genesis(write_set: &WriteSet) -> Self {
+        let mut interface = Self::default();
+        interface.apply_write_set(0, write_set);
+        interface
+    }
+
+    /// Records the effects of a write set committed at the given version.
+    pub fn apply_write_set(&mut self, version: Version, write_set: &WriteSet) {
+        for (state_key, write_op) in write_set.iter() {
+            let value = match write_op {
+                WriteOp::Creation(blob) | WriteOp::Modification(blob) => {
+                    Some(StateValue::new(blob.clone()))
+                }
+                WriteOp::Deletion => None,
+            };
+            self.state
+                .entry(state_key.clone())
+                .or_default()
+                .insert(version, value);
+        }
+        self.latest_version = self.latest_version.max(version);
+    }
+
+    /// Records a committed transaction and applies its write set.
+    pub fn commit_transaction(
+        &mut self,
+        version: Version,
+        txn: Transaction,
+        output: TransactionOutput,
+    ) {
+        self.apply_write_set(version, output.write_set());
+        self.transactions.insert(version, (txn, output));
+    }
+
+    /// Number of state values requested so far, which lets tests check that callers cache reads.
+    pub fn num_state_queries(&self) -> usize {
+        self.num_state_queries.load(Ordering::Relaxed)
+    }
+
+    fn get_value(&self, state_key: &StateKey, version: Version) -> Option<StateValue> {
+        self.state
+            .get(state_key)
+            .and_then(|versions| versions.range(..=version).next_back())
+            .and_then(|(_, value)| value.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl AptosValidatorInterface for InMemoryDebuggerInterface {
+    async fn get_account_state_by_version(
+        &self,
+        account: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountState>> {
+        let kvs = self
+            .state
+            .keys()
+            .filter(|key| matches!(key, StateKey::AccessPath(path) if path.address == account))
+            .filter_map(|key| {
+                self.get_value(key, version)
+                    .map(|value| (key.clone(), value))
+            })
+            .collect::<HashMap<_, _>>();
+        AccountState::from_access_paths_and_values(account, &kvs)
+    }
+
+    async fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        self.num_state_queries.fetch_add(1, Ordering::Relaxed);
+        Ok(self.get_value(state_key, version))
+    }
+
+    async fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<Transaction>> {
+        Ok(self
+            .get_committed_transactions_with_outputs(start, limit)
+            .await?
+            .into_iter()
+            .map(|(txn, _)| txn)
+            .collect())
+    }
+
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(Transaction, TransactionOutput)>> {
+        (start..start.saturating_add(limit))
+            .take_while(|version| *version <= self.latest_version)
+            .map(|version| {
+                self.transactions
+                    .get(&version)
+                    .cloned()
+                    .ok_or_else(|| anyhow!("Transaction {} is not available", version))
+            })
+            .collect()
+    }
+
+    async fn get_latest_version(&self) -> Result<Version> {
+        Ok(self.latest_version)
+    }
+
+    async fn get_version_by_account_sequence(
+        &self,
+        account: AccountAddress,
+        seq: u64,
+    ) -> Result<Option<Version>> {
+        Ok(self
+            .transactions
+            .iter()
+            .find(|(_, (txn, _))| match txn {
+                Transaction::UserTransaction(signed_txn) => {
+                    signed_txn.sender() == account && signed_txn.sequence_number() == seq
+                }
+                _ => false,
+            })
+            .map(|(version, _)| *version))
+    }
+}
@@ -1,9 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+mod in_memory_interface;
 mod rest_interface;
 mod storage_interface;
 
+pub use crate::in_memory_interface::InMemoryDebuggerInterface;
 pub use crate::rest_interface::RestDebuggerInterface;
 pub use crate::storage_interface::DBDebuggerInterface;
 
@@ -17,7 +19,7 @@ use aptos_types::{
     account_view::AccountView,
     on_chain_config::ValidatorSet,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionOutput, Version},
 };
 use move_binary_format::file_format::CompiledModule;
 use std::sync::{
@@ -48,6 +50,14 @@ pub trait AptosValidatorInterface: Sync {
         limit: u64,
     ) -> Result<Vec<Transaction>>;
 
+    /// Returns committed transactions along with the outputs they produced on chain, i.e. the
+    /// write sets, events, gas used and execution statuses recorded in the ledger.
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(Transaction, TransactionOutput)>>;
+
     async fn get_latest_version(&self) -> Result<Version>;
 
     async fn get_version_by_account_sequence(
@@ -3,14 +3,14 @@
 
 use crate::AptosValidatorInterface;
 use anyhow::{anyhow, bail, Result};
-use aptos_api_types::MoveStructTag;
-use aptos_rest_client::Client;
+use aptos_api_types::{AptosErrorCode, MoveStructTag};
+use aptos_rest_client::{error::RestError, Client};
 use aptos_types::{
     access_path::Path,
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionOutput, TransactionStatus, Version},
 };
 use std::collections::BTreeMap;
 
@@ -22,6 +22,25 @@ impl RestDebuggerInterface {
     }
 }
 
+/// Maps values the REST API doesn't find to `None`, e.g. modules about to be published.
+fn not_found_to_none<T>(result: std::result::Result<T, RestError>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(RestError::Api(err))
+            if matches!(
+                err.error.error_code,
+                AptosErrorCode::AccountNotFound
+                    | AptosErrorCode::ResourceNotFound
+                    | AptosErrorCode::ModuleNotFound
+                    | AptosErrorCode::TableItemNotFound
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(anyhow!("Failed to get account states: {:?}", err)),
+    }
+}
+
 #[async_trait::async_trait]
 impl AptosValidatorInterface for RestDebuggerInterface {
     async fn get_account_state_by_version(
@@ -29,12 +48,15 @@ impl AptosValidatorInterface for RestDebuggerInterface {
         account: AccountAddress,
         version: Version,
     ) -> Result<Option<AccountState>> {
-        let resource = self
-            .0
-            .get_account_resources_at_version_bcs(account, version)
-            .await
-            .map_err(|err| anyhow!("Failed to get account states: {:?}", err))?
-            .into_inner()
+        let resource = match not_found_to_none(
+            self.0
+                .get_account_resources_at_version_bcs(account, version)
+                .await,
+        )? {
+            Some(resource) => resource.into_inner(),
+            None => return Ok(None),
+        };
+        let resource = resource
             .into_iter()
             .map(|(key, value)| (key.access_vector(), value))
             .collect::<BTreeMap<_, _>>();
@@ -49,14 +71,12 @@ impl AptosValidatorInterface for RestDebuggerInterface {
     ) -> Result<Option<StateValue>> {
         match state_key {
             StateKey::AccessPath(path) => match path.get_path() {
-                Path::Code(module_id) => Ok(Some(StateValue::new(
+                Path::Code(module_id) => Ok(not_found_to_none(
                     self.0
                         .get_account_module_bcs(*module_id.address(), module_id.name().as_str())
-                        .await
-                        .map_err(|err| anyhow!("Failed to get account states: {:?}", err))?
-                        .into_inner()
-                        .to_vec(),
-                ))),
+                        .await,
+                )?
+                .map(|inner| StateValue::new(inner.into_inner().to_vec()))),
                 Path::Resource(tag) => Ok(self
                     .0
                     .get_account_resource_at_version_bytes(
@@ -68,13 +88,10 @@ impl AptosValidatorInterface for RestDebuggerInterface {
                     .ok()
                     .map(|inner| StateValue::new(inner.into_inner()))),
             },
-            StateKey::TableItem { handle, key } => Ok(Some(StateValue::new(
-                self.0
-                    .get_raw_table_item(handle.0, key, version)
-                    .await
-                    .map_err(|err| anyhow!("Failed to get account states: {:?}", err))?
-                    .into_inner(),
-            ))),
+            StateKey::TableItem { handle, key } => Ok(not_found_to_none(
+                self.0.get_raw_table_item(handle.0, key, version).await,
+            )?
+            .map(|inner| StateValue::new(inner.into_inner()))),
             StateKey::Raw(_) => bail!("Unexpected key type"),
         }
     }
@@ -94,6 +111,29 @@ impl AptosValidatorInterface for RestDebuggerInterface {
             .collect())
     }
 
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(Transaction, TransactionOutput)>> {
+        Ok(self
+            .0
+            .get_transactions_bcs(Some(start), Some(limit as u16))
+            .await?
+            .into_inner()
+            .into_iter()
+            .map(|txn| {
+                let output = TransactionOutput::new(
+                    txn.changes,
+                    txn.events,
+                    txn.info.gas_used(),
+                    TransactionStatus::Keep(txn.info.status().clone()),
+                );
+                (txn.transaction, output)
+            })
+            .collect())
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         Ok(self.0.get_ledger_information().await?.into_inner().version)
     }
@@ -11,7 +11,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
-    transaction::{Transaction, Version},
+    transaction::{Transaction, TransactionOutput, Version},
 };
 use aptosdb::AptosDB;
 use std::{path::Path, sync::Arc};
@@ -79,6 +79,17 @@ impl AptosValidatorInterface for DBDebuggerInterface {
             .transactions)
     }
 
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<(Transaction, TransactionOutput)>> {
+        Ok(self
+            .0
+            .get_transaction_outputs(start, limit, self.get_latest_version().await?)?
+            .transactions_and_outputs)
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         let (version, _) = self
             .0
@@ -25,7 +25,7 @@ use aptos_aggregator::{
     transaction::{ChangeSetExt, TransactionOutputExt},
 };
 use aptos_crypto::HashValue;
-use aptos_gas::AptosGasMeter;
+use aptos_gas::{AptosGasMeter, GasProfile, GasProfiler, TransactionGasMeter};
 use aptos_logger::prelude::*;
 use aptos_module_verifier::module_init::verify_module_init_function;
 use aptos_state_view::StateView;
@@ -33,11 +33,11 @@ use aptos_types::{
     account_config,
     account_config::new_block_event_key,
     block_metadata::BlockMetadata,
-    on_chain_config::new_epoch_event_key,
+    on_chain_config::{new_epoch_event_key, FeatureFlag},
     transaction::{
-        ChangeSet, ExecutionStatus, ModuleBundle, SignatureCheckedTransaction, SignedTransaction,
-        Transaction, TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
-        WriteSetPayload,
+        ChangeSet, ExecutionStatus, ModuleBundle, Multisig, MultisigTransactionPayload,
+        SignatureCheckedTransaction, SignedTransaction, Transaction, TransactionOutput,
+        TransactionPayload, TransactionStatus, VMValidatorResult, WriteSetPayload,
     },
     vm_status::{AbortLocation, DiscardedVMStatus, StatusCode, VMStatus},
     write_set::WriteSet,
@@ -47,12 +47,14 @@ use framework::natives::code::PublishRequest;
 use move_binary_format::{
     access::ModuleAccess,
     errors::{verification_error, Location, PartialVMError, VMError, VMResult},
+    file_format::Visibility,
     CompiledModule, IndexKind,
 };
 use move_core_types::{
     account_address::AccountAddress,
     ident_str,
-    language_storage::ModuleId,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
     transaction_argument::convert_txn_args,
     value::{serialize_values, MoveValue},
 };
@@ -187,7 +189,7 @@ impl AptosVM {
     pub fn failed_transaction_cleanup<S: MoveResolverExt>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -205,7 +207,7 @@ impl AptosVM {
     fn failed_transaction_cleanup_and_keep_vm_status<S: MoveResolverExt>(
         &self,
         error_code: VMStatus,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         storage: &S,
         log_context: &AdapterLogSchema,
@@ -266,7 +268,7 @@ impl AptosVM {
         &self,
         storage: &S,
         user_txn_change_set_ext: ChangeSetExt,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
@@ -331,7 +333,7 @@ impl AptosVM {
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         payload: &TransactionPayload,
         log_context: &AdapterLogSchema,
@@ -391,7 +393,7 @@ impl AptosVM {
                         gas_meter,
                     )
                 }
-                TransactionPayload::ModuleBundle(_) => {
+                TransactionPayload::ModuleBundle(_) | TransactionPayload::Multisig(_) => {
                     return Err(VMStatus::Error(StatusCode::UNREACHABLE));
                 }
             }
@@ -417,6 +419,106 @@ impl AptosVM {
         }
     }
 
+    /// Executes the next approved transaction of a multisig account on behalf of one of its owners.
+    ///
+    /// The payload is fetched (and its approvals are checked) by the `multisig_account` module,
+    /// then executed with the multisig account as the only signer. If the payload fails, the whole
+    /// transaction fails and the multisig transaction stays pending.
+    fn execute_multisig_transaction<S: MoveResolverExt + StateView>(
+        &self,
+        storage: &S,
+        mut session: SessionExt<S>,
+        gas_meter: &mut impl TransactionGasMeter,
+        txn_data: &TransactionMetadata,
+        txn_payload: &Multisig,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, TransactionOutputExt), VMStatus> {
+        fail_point!("move_adapter::execute_multisig_transaction", |_| {
+            Err(VMStatus::Error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            ))
+        });
+
+        gas_meter
+            .charge_intrinsic_gas_for_transaction(txn_data.transaction_size())
+            .map_err(|e| e.into_vm_status())?;
+
+        // An empty payload means the payload stored on chain should be executed.
+        let provided_payload = match &txn_payload.transaction_payload {
+            Some(payload) => bcs::to_bytes(payload)
+                .map_err(|_| VMStatus::Error(StatusCode::VALUE_SERIALIZATION_ERROR))?,
+            None => vec![],
+        };
+        let return_values = session
+            .execute_function_bypass_visibility(
+                &MULTISIG_ACCOUNT_MODULE,
+                GET_NEXT_TRANSACTION_PAYLOAD,
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Address(txn_data.sender()),
+                    MoveValue::Address(txn_payload.multisig_address),
+                    MoveValue::vector_u8(provided_payload),
+                ]),
+                gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?
+            .return_values;
+        let payload = return_values
+            .first()
+            .and_then(|(bytes, _layout)| bcs::from_bytes::<Vec<u8>>(bytes).ok())
+            .and_then(|payload| bcs::from_bytes::<MultisigTransactionPayload>(&payload).ok())
+            .ok_or(VMStatus::Error(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT))?;
+
+        match payload {
+            MultisigTransactionPayload::EntryFunction(entry_function) => {
+                let function = session.load_function(
+                    entry_function.module(),
+                    entry_function.function(),
+                    entry_function.ty_args(),
+                )?;
+                let args = transaction_arg_validation::validate_combine_signer_and_txn_args(
+                    &session,
+                    vec![txn_payload.multisig_address],
+                    entry_function.args().to_vec(),
+                    &function,
+                )?;
+                session
+                    .execute_entry_function(
+                        entry_function.module(),
+                        entry_function.function(),
+                        entry_function.ty_args().to_vec(),
+                        args,
+                        gas_meter,
+                    )
+                    .map_err(|e| e.into_vm_status())?;
+            }
+        }
+
+        self.resolve_pending_code_publish(&mut session, gas_meter)?;
+
+        session
+            .execute_function_bypass_visibility(
+                &MULTISIG_ACCOUNT_MODULE,
+                SUCCESSFUL_TRANSACTION_EXECUTION_CLEANUP,
+                vec![],
+                serialize_values(&vec![
+                    MoveValue::Address(txn_data.sender()),
+                    MoveValue::Address(txn_payload.multisig_address),
+                ]),
+                gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?;
+
+        let session_output = session.finish().map_err(|e| e.into_vm_status())?;
+        let change_set_ext =
+            session_output.into_change_set(&mut (), gas_meter.feature_version())?;
+
+        // Charge gas for write set
+        gas_meter.charge_write_set_gas(change_set_ext.write_set().iter())?;
+
+        self.success_transaction_cleanup(storage, change_set_ext, gas_meter, txn_data, log_context)
+    }
+
     fn verify_module_bundle<S: MoveResolverExt>(
         session: &mut SessionExt<S>,
         module_bundle: &ModuleBundle,
@@ -448,7 +550,7 @@ impl AptosVM {
     fn execute_module_initialization<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         modules: &[CompiledModule],
         exists: BTreeSet<ModuleId>,
         senders: &[AccountAddress],
@@ -508,7 +610,7 @@ impl AptosVM {
         &self,
         storage: &S,
         mut session: SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
         txn_data: &TransactionMetadata,
         modules: &ModuleBundle,
         log_context: &AdapterLogSchema,
@@ -555,7 +657,7 @@ impl AptosVM {
     fn resolve_pending_code_publish<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
-        gas_meter: &mut AptosGasMeter,
+        gas_meter: &mut impl TransactionGasMeter,
     ) -> VMResult<()> {
         if let Some(PublishRequest {
             destination,
@@ -658,11 +760,33 @@ impl AptosVM {
         txn: &SignatureCheckedTransaction,
         log_context: &AdapterLogSchema,
     ) -> (VMStatus, TransactionOutputExt) {
+        let (vm_status, output, _gas_meter) =
+            self.execute_user_transaction_impl(storage, txn, log_context, |gas_meter| gas_meter);
+        (vm_status, output)
+    }
+
+    /// Executes a user transaction with the gas meter `new_gas_meter` makes out of the standard
+    /// one, returning it as well unless the transaction got discarded before it was created.
+    fn execute_user_transaction_impl<S, G, F>(
+        &self,
+        storage: &S,
+        txn: &SignatureCheckedTransaction,
+        log_context: &AdapterLogSchema,
+        new_gas_meter: F,
+    ) -> (VMStatus, TransactionOutputExt, Option<G>)
+    where
+        S: MoveResolverExt + StateView,
+        G: TransactionGasMeter,
+        F: FnOnce(AptosGasMeter) -> G,
+    {
         macro_rules! unwrap_or_discard {
             ($res: expr) => {
                 match $res {
                     Ok(s) => s,
-                    Err(e) => return discard_error_vm_status(e),
+                    Err(e) => {
+                        let (vm_status, output) = discard_error_vm_status(e);
+                        return (vm_status, output, None);
+                    }
                 }
             };
         }
@@ -677,7 +801,8 @@ impl AptosVM {
             false,
             log_context,
         ) {
-            return discard_error_vm_status(err);
+            let (vm_status, output) = discard_error_vm_status(err);
+            return (vm_status, output, None);
         };
 
         if self.0.get_gas_feature_version() >= 1 {
@@ -692,12 +817,12 @@ impl AptosVM {
         let gas_params = unwrap_or_discard!(self.0.get_gas_parameters(log_context));
         let storage_gas_params = unwrap_or_discard!(self.0.get_storage_gas_parameters(log_context));
         let txn_data = TransactionMetadata::new(txn);
-        let mut gas_meter = AptosGasMeter::new(
+        let mut gas_meter = new_gas_meter(AptosGasMeter::new(
             self.0.get_gas_feature_version(),
             gas_params.clone(),
             storage_gas_params.cloned(),
             txn_data.max_gas_amount(),
-        );
+        ));
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
@@ -713,6 +838,14 @@ impl AptosVM {
             TransactionPayload::ModuleBundle(m) => {
                 self.execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
+            TransactionPayload::Multisig(payload) => self.execute_multisig_transaction(
+                storage,
+                session,
+                &mut gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+            ),
         };
 
         let gas_usage = txn_data
@@ -721,7 +854,7 @@ impl AptosVM {
             .expect("Balance should always be less than or equal to max gas amount set");
         TXN_GAS_USAGE.observe(u64::from(gas_usage) as f64);
 
-        match result {
+        let (vm_status, output) = match result {
             Ok(output) => output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
@@ -737,7 +870,8 @@ impl AptosVM {
                     )
                 }
             }
-        }
+        };
+        (vm_status, output, Some(gas_meter))
     }
 
     fn execute_writeset<S: MoveResolverExt>(
@@ -922,6 +1056,107 @@ impl AptosVM {
         simulation_vm.simulate_signed_transaction(&state_view.as_move_resolver(), txn, &log_context)
     }
 
+    /// Same as `simulate_signed_transaction`, but also returns a breakdown of where the gas was
+    /// spent, unless the transaction got discarded before execution started.
+    pub fn simulate_signed_transaction_with_gas_profile(
+        txn: &SignedTransaction,
+        state_view: &impl StateView,
+    ) -> (VMStatus, TransactionOutputExt, Option<GasProfile>) {
+        let vm = AptosVM::new(state_view);
+        let simulation_vm = AptosSimulationVM(vm);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let (vm_status, output, gas_profiler) = simulation_vm.simulate_signed_transaction_impl(
+            &state_view.as_move_resolver(),
+            txn,
+            &log_context,
+            |gas_meter| GasProfiler::new(gas_meter, payload_entry_point(txn.payload())),
+        );
+        (vm_status, output, gas_profiler.map(GasProfiler::finish))
+    }
+
+    /// Executes a single user transaction against `state_view` the way block execution would,
+    /// returning a breakdown of where the gas was spent along with the output.
+    pub fn execute_user_transaction_with_gas_profile(
+        state_view: &impl StateView,
+        txn: SignedTransaction,
+    ) -> (VMStatus, TransactionOutputExt, Option<GasProfile>) {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let txn = match txn.check_signature() {
+            Ok(txn) => txn,
+            Err(_) => {
+                let (vm_status, output) =
+                    discard_error_vm_status(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+                return (vm_status, output, None);
+            }
+        };
+        let entry_point = payload_entry_point(txn.payload());
+        let (vm_status, output, gas_profiler) = vm.execute_user_transaction_impl(
+            &state_view.as_move_resolver(),
+            &txn,
+            &log_context,
+            |gas_meter| GasProfiler::new(gas_meter, entry_point),
+        );
+        (vm_status, output, gas_profiler.map(GasProfiler::finish))
+    }
+
+    /// Executes a public Move function against `state_view` in a throwaway session and returns
+    /// its BCS-serialized return values. Any writes performed by the function are discarded.
+    ///
+    /// Execution is metered with the on-chain gas schedule and aborts with `OUT_OF_GAS` once
+    /// `gas_budget` gas units have been spent.
+    pub fn execute_view_function(
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> Result<Vec<Vec<u8>>, VMStatus> {
+        let vm = AptosVM::new(state_view);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let gas_params = vm.0.get_gas_parameters(&log_context)?;
+        let storage_gas_params = vm.0.get_storage_gas_parameters(&log_context)?;
+        let mut gas_meter = AptosGasMeter::new(
+            vm.0.get_gas_feature_version(),
+            gas_params.clone(),
+            storage_gas_params.cloned(),
+            gas_budget,
+        );
+
+        let resolver = state_view.as_move_resolver();
+        let module = vm
+            .load_module(&module_id, &resolver)
+            .map_err(|e| e.into_vm_status())?;
+        let is_public = module.function_defs().iter().any(|def| {
+            let handle = module.function_handle_at(def.function);
+            module.identifier_at(handle.name) == func_name.as_ident_str()
+                && def.visibility == Visibility::Public
+        });
+        if !is_public {
+            return Err(VMStatus::Error(StatusCode::FUNCTION_RESOLUTION_FAILURE));
+        }
+
+        let mut session = vm.0.new_session(&resolver, SessionId::void());
+        let function = session.load_function(&module_id, &func_name, &type_args)?;
+        transaction_arg_validation::validate_view_function_args(&session, &arguments, &function)?;
+
+        let return_values = session
+            .execute_function_bypass_visibility(
+                &module_id,
+                &func_name,
+                type_args,
+                arguments,
+                &mut gas_meter,
+            )
+            .map_err(|e| e.into_vm_status())?;
+        Ok(return_values
+            .return_values
+            .into_iter()
+            .map(|(bytes, _layout)| bytes)
+            .collect())
+    }
+
     fn run_prologue_with_payload<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -930,6 +1165,11 @@ impl AptosVM {
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        if txn_data.fee_payer().is_some()
+            && !self.0.get_features().is_enabled(FeatureFlag::FEE_PAYER)
+        {
+            return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+        }
         match payload {
             TransactionPayload::Script(_) => {
                 self.0.check_gas(storage, txn_data, log_context)?;
@@ -940,10 +1180,26 @@ impl AptosVM {
                 self.0.check_gas(storage, txn_data, log_context)?;
                 self.0.run_script_prologue(session, txn_data, log_context)
             }
+            TransactionPayload::Multisig(_) => {
+                if !self
+                    .0
+                    .get_features()
+                    .is_enabled(FeatureFlag::MULTISIG_ACCOUNTS)
+                {
+                    return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+                }
+                // NOTE: Multisig shares the same prologue as Script and EntryFunction
+                self.0.check_gas(storage, txn_data, log_context)?;
+                self.0.run_script_prologue(session, txn_data, log_context)
+            }
             TransactionPayload::ModuleBundle(_module) => {
                 if MODULE_BUNDLE_DISALLOWED.load(Ordering::Relaxed) {
                     return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
                 }
+                // The module prologue always charges the sender, so a fee payer can't be honored
+                if txn_data.fee_payer().is_some() {
+                    return Err(VMStatus::Error(StatusCode::FEATURE_UNDER_GATING));
+                }
                 self.0.check_gas(storage, txn_data, log_context)?;
                 self.0.run_module_prologue(session, txn_data, log_context)
             }
@@ -1127,6 +1383,20 @@ impl VMAdapter for AptosVM {
     }
 }
 
+/// Names the root of a gas profile after what the transaction executes.
+fn payload_entry_point(payload: &TransactionPayload) -> String {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => format!(
+            "{}::{}",
+            entry_function.module().short_str_lossless(),
+            entry_function.function()
+        ),
+        TransactionPayload::Script(_) => "script".to_string(),
+        TransactionPayload::ModuleBundle(_) => "module_bundle".to_string(),
+        TransactionPayload::Multisig(_) => "multisig".to_string(),
+    }
+}
+
 impl AsRef<AptosVMImpl> for AptosVM {
     fn as_ref(&self) -> &AptosVMImpl {
         &self.0
@@ -1167,10 +1437,34 @@ impl AptosSimulationVM {
         txn: &SignedTransaction,
         log_context: &AdapterLogSchema,
     ) -> (VMStatus, TransactionOutputExt) {
+        let (vm_status, output, _gas_meter) =
+            self.simulate_signed_transaction_impl(storage, txn, log_context, |gas_meter| gas_meter);
+        (vm_status, output)
+    }
+
+    fn simulate_signed_transaction_impl<S, G, F>(
+        &self,
+        storage: &S,
+        txn: &SignedTransaction,
+        log_context: &AdapterLogSchema,
+        new_gas_meter: F,
+    ) -> (VMStatus, TransactionOutputExt, Option<G>)
+    where
+        S: MoveResolverExt + StateView,
+        G: TransactionGasMeter,
+        F: FnOnce(AptosGasMeter) -> G,
+    {
+        macro_rules! discard {
+            ($err: expr) => {{
+                let (vm_status, output) = discard_error_vm_status($err);
+                return (vm_status, output, None);
+            }};
+        }
+
         // simulation transactions should not carry valid signatures, otherwise malicious fullnodes
         // may execute them without user's explicit permission.
         if txn.signature_is_valid() {
-            return discard_error_vm_status(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
+            discard!(VMStatus::Error(StatusCode::INVALID_SIGNATURE));
         }
 
         // Revalidate the transaction.
@@ -1183,24 +1477,24 @@ impl AptosSimulationVM {
             &txn_data,
             log_context,
         ) {
-            return discard_error_vm_status(err);
+            discard!(err);
         };
 
         let gas_params = match self.0 .0.get_gas_parameters(log_context) {
-            Err(err) => return discard_error_vm_status(err),
+            Err(err) => discard!(err),
             Ok(s) => s,
         };
         let storage_gas_params = match self.0 .0.get_storage_gas_parameters(log_context) {
-            Err(err) => return discard_error_vm_status(err),
+            Err(err) => discard!(err),
             Ok(s) => s,
         };
 
-        let mut gas_meter = AptosGasMeter::new(
+        let mut gas_meter = new_gas_meter(AptosGasMeter::new(
             self.0 .0.get_gas_feature_version(),
             gas_params.clone(),
             storage_gas_params.cloned(),
             txn_data.max_gas_amount(),
-        );
+        ));
 
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
@@ -1218,9 +1512,17 @@ impl AptosSimulationVM {
                 self.0
                     .execute_modules(storage, session, &mut gas_meter, &txn_data, m, log_context)
             }
+            TransactionPayload::Multisig(payload) => self.0.execute_multisig_transaction(
+                storage,
+                session,
+                &mut gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+            ),
         };
 
-        match result {
+        let (vm_status, output) = match result {
             Ok(output) => output,
             Err(err) => {
                 let txn_status = TransactionStatus::from(err.clone());
@@ -1237,6 +1539,7 @@ impl AptosSimulationVM {
                     (vm_status, output)
                 }
             }
-        }
+        };
+        (vm_status, output, Some(gas_meter))
     }
 }
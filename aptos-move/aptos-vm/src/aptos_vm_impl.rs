@@ -8,6 +8,7 @@ use crate::{
     errors::{convert_epilogue_error, convert_prologue_error, expect_only_successful_execution},
     logging::AdapterLogSchema,
     move_vm_ext::{MoveResolverExt, MoveVmExt, SessionExt, SessionId},
+    system_module_names::{FEE_PAYER_SCRIPT_PROLOGUE_NAME, USER_EPILOGUE_GAS_PAYER_NAME},
     transaction_metadata::TransactionMetadata,
 };
 use aptos_aggregator::transaction::TransactionOutputExt;
@@ -52,6 +53,7 @@ pub struct AptosVMImpl {
     gas_feature_version: u64,
     gas_params: Option<AptosGasParameters>,
     storage_gas_params: Option<StorageGasParameters>,
+    features: Features,
     version: Option<Version>,
     transaction_validation: Option<TransactionValidation>,
     metadata_cache: DashMap<ModuleId, Option<RuntimeModuleMetadata>>,
@@ -131,6 +133,7 @@ impl AptosVMImpl {
             gas_feature_version,
             gas_params,
             storage_gas_params,
+            features,
             version: None,
             transaction_validation: None,
             metadata_cache: Default::default(),
@@ -196,6 +199,10 @@ impl AptosVMImpl {
         }
     }
 
+    pub fn get_features(&self) -> &Features {
+        &self.features
+    }
+
     pub fn get_gas_feature_version(&self) -> u64 {
         self.gas_feature_version
     }
@@ -318,8 +325,9 @@ impl AptosVMImpl {
         Ok(())
     }
 
-    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function
-    /// or `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function stored in the `ACCOUNT_MODULE` on chain.
+    /// Run the prologue of a transaction by calling into either `SCRIPT_PROLOGUE_NAME` function,
+    /// `MULTI_AGENT_SCRIPT_PROLOGUE_NAME` function or `FEE_PAYER_SCRIPT_PROLOGUE_NAME` function
+    /// stored in the `ACCOUNT_MODULE` on chain.
     pub(crate) fn run_script_prologue<S: MoveResolverExt>(
         &self,
         session: &mut SessionExt<S>,
@@ -339,7 +347,25 @@ impl AptosVMImpl {
             .iter()
             .map(|auth_key| MoveValue::vector_u8(auth_key.to_vec()))
             .collect();
-        let args = if txn_data.is_multi_agent() {
+        let args = if let Some(fee_payer) = txn_data.fee_payer() {
+            let fee_payer_auth_key = txn_data
+                .fee_payer_authentication_key
+                .clone()
+                .unwrap_or_default();
+            vec![
+                MoveValue::Signer(txn_data.sender),
+                MoveValue::U64(txn_sequence_number),
+                MoveValue::vector_u8(txn_authentication_key),
+                MoveValue::vector_address(txn_data.secondary_signers()),
+                MoveValue::Vector(secondary_auth_keys),
+                MoveValue::Address(fee_payer),
+                MoveValue::vector_u8(fee_payer_auth_key),
+                MoveValue::U64(txn_gas_price.into()),
+                MoveValue::U64(txn_max_gas_units.into()),
+                MoveValue::U64(txn_expiration_timestamp_secs),
+                MoveValue::U8(chain_id.id()),
+            ]
+        } else if txn_data.is_multi_agent() {
             vec![
                 MoveValue::Signer(txn_data.sender),
                 MoveValue::U64(txn_sequence_number),
@@ -363,10 +389,14 @@ impl AptosVMImpl {
                 MoveValue::vector_u8(txn_data.script_hash.clone()),
             ]
         };
-        let prologue_function_name = if txn_data.is_multi_agent() {
-            &transaction_validation.multi_agent_prologue_name
+        let prologue_function_name = if txn_data.fee_payer().is_some() {
+            FEE_PAYER_SCRIPT_PROLOGUE_NAME
+        } else if txn_data.is_multi_agent() {
+            transaction_validation
+                .multi_agent_prologue_name
+                .as_ident_str()
         } else {
-            &transaction_validation.script_prologue_name
+            transaction_validation.script_prologue_name.as_ident_str()
         };
         session
             .execute_function_bypass_visibility(
@@ -437,26 +467,7 @@ impl AptosVMImpl {
         });
 
         let transaction_validation = self.transaction_validation();
-        let txn_sequence_number = txn_data.sequence_number();
-        let txn_gas_price = txn_data.gas_unit_price();
-        let txn_max_gas_units = txn_data.max_gas_amount();
-        session
-            .execute_function_bypass_visibility(
-                &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
-                // TODO: Deprecate this once we remove gas currency on the Move side.
-                vec![],
-                serialize_values(&vec![
-                    MoveValue::Signer(txn_data.sender),
-                    MoveValue::U64(txn_sequence_number),
-                    MoveValue::U64(txn_gas_price.into()),
-                    MoveValue::U64(txn_max_gas_units.into()),
-                    MoveValue::U64(gas_remaining.into()),
-                ]),
-                &mut UnmeteredGasMeter,
-            )
-            .map(|_return_vals| ())
-            .map_err(expect_no_verification_errors)
+        self.run_epilogue(session, gas_remaining, txn_data)
             .or_else(|err| convert_epilogue_error(transaction_validation, err, log_context))
     }
 
@@ -469,34 +480,63 @@ impl AptosVMImpl {
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        let transaction_validation = self.transaction_validation();
+        self.run_epilogue(session, gas_remaining, txn_data)
+            .or_else(|e| {
+                expect_only_successful_execution(
+                    e,
+                    transaction_validation.user_epilogue_name.as_str(),
+                    log_context,
+                )
+            })
+    }
+
+    /// Calls `USER_EPILOGUE_NAME`, or `USER_EPILOGUE_GAS_PAYER_NAME` if the gas is paid by a fee
+    /// payer, to charge gas and bump the sender's sequence number.
+    fn run_epilogue<S: MoveResolverExt>(
+        &self,
+        session: &mut SessionExt<S>,
+        gas_remaining: Gas,
+        txn_data: &TransactionMetadata,
+    ) -> VMResult<()> {
         let transaction_validation = self.transaction_validation();
         let txn_sequence_number = txn_data.sequence_number();
         let txn_gas_price = txn_data.gas_unit_price();
         let txn_max_gas_units = txn_data.max_gas_amount();
-        session
-            .execute_function_bypass_visibility(
-                &transaction_validation.module_id(),
-                &transaction_validation.user_epilogue_name,
-                // TODO: Deprecate this once we remove gas currency on the Move side.
-                vec![],
-                serialize_values(&vec![
+        let (epilogue_function_name, args) = match txn_data.fee_payer() {
+            Some(fee_payer) => (
+                USER_EPILOGUE_GAS_PAYER_NAME,
+                vec![
                     MoveValue::Signer(txn_data.sender),
+                    MoveValue::Address(fee_payer),
                     MoveValue::U64(txn_sequence_number),
                     MoveValue::U64(txn_gas_price.into()),
                     MoveValue::U64(txn_max_gas_units.into()),
                     MoveValue::U64(gas_remaining.into()),
-                ]),
+                ],
+            ),
+            None => (
+                transaction_validation.user_epilogue_name.as_ident_str(),
+                vec![
+                    MoveValue::Signer(txn_data.sender),
+                    MoveValue::U64(txn_sequence_number),
+                    MoveValue::U64(txn_gas_price.into()),
+                    MoveValue::U64(txn_max_gas_units.into()),
+                    MoveValue::U64(gas_remaining.into()),
+                ],
+            ),
+        };
+        session
+            .execute_function_bypass_visibility(
+                &transaction_validation.module_id(),
+                epilogue_function_name,
+                // TODO: Deprecate this once we remove gas currency on the Move side.
+                vec![],
+                serialize_values(&args),
                 &mut UnmeteredGasMeter,
             )
             .map(|_return_vals| ())
             .map_err(expect_no_verification_errors)
-            .or_else(|e| {
-                expect_only_successful_execution(
-                    e,
-                    transaction_validation.user_epilogue_name.as_str(),
-                    log_context,
-                )
-            })
     }
 
     pub(crate) fn extract_abort_info(
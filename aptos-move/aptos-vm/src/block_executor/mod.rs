@@ -19,7 +19,6 @@ use aptos_block_executor::{
         TransactionOutput as BlockExecutorTransactionOutput,
     },
 };
-use aptos_logger::debug;
 use aptos_state_view::StateView;
 use aptos_types::{
     state_store::state_key::StateKey,
@@ -147,7 +146,7 @@ impl BlockAptosVM {
         let executor =
             BlockExecutor::<PreprocessedTransaction, AptosExecutorTask<S>>::new(concurrency_level);
 
-        let mut ret = if concurrency_level > 1 {
+        let ret = if concurrency_level > 1 {
             executor
                 .execute_transactions_parallel(state_view, &signature_verified_block)
                 .map(|(results, delta_resolver)| {
@@ -159,17 +158,7 @@ impl BlockAptosVM {
                 .map(Self::process_sequential_block_output)
         };
 
-        if ret == Err(Error::ModulePathReadWrite) {
-            debug!("[Execution]: Module read & written, sequential fallback");
-
-            ret = executor
-                .execute_transactions_sequential(state_view, &signature_verified_block)
-                .map(Self::process_sequential_block_output);
-        }
-
-        // Explicit async drop. Happens here because we can't currently move to
-        // BlockExecutor due to the Module publishing fallback. TODO: fix after
-        // module publishing fallback is removed.
+        // Explicit async drop.
         RAYON_EXEC_POOL.spawn(move || {
             // Explicit async drops.
             drop(signature_verified_block);
@@ -177,9 +166,6 @@ impl BlockAptosVM {
 
         match ret {
             Ok(outputs) => Ok(outputs),
-            Err(Error::ModulePathReadWrite) => {
-                unreachable!("[Execution]: Must be handled by sequential fallback")
-            }
             Err(Error::UserError(err)) => Err(err),
         }
     }
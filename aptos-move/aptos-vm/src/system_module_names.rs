@@ -22,3 +22,20 @@ pub const MULTI_AGENT_SCRIPT_PROLOGUE_NAME: &IdentStr = ident_str!("multi_agent_
 pub const MODULE_PROLOGUE_NAME: &IdentStr = ident_str!("module_prologue");
 pub const USER_EPILOGUE_NAME: &IdentStr = ident_str!("epilogue");
 pub const BLOCK_PROLOGUE: &IdentStr = ident_str!("block_prologue");
+
+// The fee payer prologue and epilogue aren't part of the on-chain `TransactionValidation` config,
+// so they are resolved by name in the same module as the other prologues.
+pub const FEE_PAYER_SCRIPT_PROLOGUE_NAME: &IdentStr = ident_str!("fee_payer_script_prologue");
+pub const USER_EPILOGUE_GAS_PAYER_NAME: &IdentStr = ident_str!("epilogue_gas_payer");
+
+/// The ModuleId for the multisig account module
+pub static MULTISIG_ACCOUNT_MODULE: Lazy<ModuleId> = Lazy::new(|| {
+    ModuleId::new(
+        account_config::CORE_CODE_ADDRESS,
+        ident_str!("multisig_account").to_owned(),
+    )
+});
+
+pub const GET_NEXT_TRANSACTION_PAYLOAD: &IdentStr = ident_str!("get_next_transaction_payload");
+pub const SUCCESSFUL_TRANSACTION_EXECUTION_CLEANUP: &IdentStr =
+    ident_str!("successful_transaction_execution_cleanup");
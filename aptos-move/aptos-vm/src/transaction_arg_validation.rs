@@ -100,6 +100,39 @@ pub(crate) fn validate_combine_signer_and_txn_args<S: MoveResolverExt>(
     Ok(combined_args)
 }
 
+/// Validate args for a view function
+/// validation includes:
+/// 1. no parameter is a signer
+/// 2. number of args is same as the number of parameters
+/// 3. check arg types are allowed
+///
+/// view functions are not executed in the context of a transaction, so unlike entry
+/// functions there are no senders to combine with the args
+pub(crate) fn validate_view_function_args<S: MoveResolverExt>(
+    session: &SessionExt<S>,
+    args: &[Vec<u8>],
+    func: &LoadedFunctionInstantiation,
+) -> Result<(), VMStatus> {
+    let mut needs_validation = vec![];
+    for (idx, ty) in func.parameters.iter().enumerate() {
+        let (valid, validation) = is_valid_txn_arg(session, ty);
+        if !valid {
+            return Err(VMStatus::Error(StatusCode::INVALID_MAIN_FUNCTION_SIGNATURE));
+        }
+        if validation {
+            needs_validation.push(idx);
+        }
+    }
+
+    if args.len() != func.parameters.len() {
+        return Err(VMStatus::Error(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH));
+    }
+    if !needs_validation.is_empty() {
+        validate_args(session, &needs_validation, args, func)?;
+    }
+    Ok(())
+}
+
 // Return whether the argument is valid/allowed and whether it needs validation.
 // Validation is only needed for String arguments at the moment and vectors of them.
 fn is_valid_txn_arg<S: MoveResolverExt>(session: &SessionExt<S>, typ: &Type) -> (bool, bool) {
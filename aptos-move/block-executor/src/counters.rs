@@ -4,11 +4,12 @@
 use aptos_metrics_core::{register_int_counter, IntCounter};
 use once_cell::sync::Lazy;
 
-/// Count of times the module publishing fallback was triggered in parallel execution.
+/// Count of times the module publishing fallback (splitting the executed segment of the block at
+/// module publishing transactions) was triggered in parallel execution.
 pub static MODULE_PUBLISHING_FALLBACK_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
         "aptos_execution_module_publishing_fallback_count",
-        "Count times module was read and written in parallel execution (sequential fallback)"
+        "Count times module was read and written in parallel execution (segment split)"
     )
     .unwrap()
 });
@@ -3,12 +3,6 @@
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Error<E> {
-    /// The same module access path for module was both read & written during speculative executions.
-    /// This may trigger a race due to the Move-VM loader cache implementation, and mitigation requires
-    /// aborting the parallel execution pipeline and falling back to the sequential execution.
-    /// TODO: (short-med term) relax the limitation, and (mid-long term) provide proper multi-versioning
-    /// for code (like data) for the cache.
-    ModulePathReadWrite,
     /// Execution of a thread yields a non-recoverable error, such error will be propagated back to
     /// the caller.
     UserError(E),
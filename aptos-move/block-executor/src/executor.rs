@@ -15,7 +15,12 @@ use aptos_types::write_set::TransactionWrite;
 use mvhashmap::{MVHashMap, MVHashMapError, MVHashMapOutput};
 use num_cpus;
 use once_cell::sync::Lazy;
-use std::{collections::btree_map::BTreeMap, hash::Hash, marker::PhantomData, sync::Arc};
+use std::{
+    collections::{btree_map::BTreeMap, VecDeque},
+    hash::Hash,
+    marker::PhantomData,
+    sync::Arc,
+};
 
 pub static RAYON_EXEC_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
     rayon::ThreadPoolBuilder::new()
@@ -356,6 +361,51 @@ where
         }
     }
 
+    /// Executes the transactions of the block with indices in [begin, end) in parallel, the
+    /// transactions before begin having already been committed.
+    fn execute_segment(
+        &self,
+        begin: TxnIndex,
+        end: TxnIndex,
+        executor_arguments: &E::Argument,
+        block: &[T],
+        last_input_output: &TxnLastInputOutput<
+            <T as Transaction>::Key,
+            <E as ExecutorTask>::Output,
+            <E as ExecutorTask>::Error,
+        >,
+        versioned_data_cache: &MVHashMap<<T as Transaction>::Key, <T as Transaction>::Value>,
+    ) {
+        last_input_output.reset_module_accesses();
+        let scheduler = Scheduler::new_from(begin, end);
+
+        if end - begin == 1 {
+            // No point in involving other threads.
+            self.work_task_with_scope(
+                executor_arguments,
+                block,
+                last_input_output,
+                versioned_data_cache,
+                &scheduler,
+            );
+            return;
+        }
+
+        RAYON_EXEC_POOL.scope(|s| {
+            for _ in 0..self.concurrency_level {
+                s.spawn(|_| {
+                    self.work_task_with_scope(
+                        executor_arguments,
+                        block,
+                        last_input_output,
+                        versioned_data_cache,
+                        &scheduler,
+                    );
+                });
+            }
+        });
+    }
+
     pub fn execute_transactions_parallel(
         &self,
         executor_initial_arguments: E::Argument,
@@ -377,51 +427,91 @@ where
 
         let num_txns = signature_verified_block.len();
         let last_input_output = TxnLastInputOutput::new(num_txns);
-        let scheduler = Scheduler::new(num_txns);
 
-        RAYON_EXEC_POOL.scope(|s| {
-            for _ in 0..self.concurrency_level {
-                s.spawn(|_| {
-                    self.work_task_with_scope(
-                        &executor_initial_arguments,
-                        signature_verified_block,
-                        &last_input_output,
-                        &versioned_data_cache,
-                        &scheduler,
-                    );
-                });
+        // The block is executed in segments of consecutive transactions, initially a single one.
+        // Each segment gets its own scheduler and executors, while sharing the multi-version
+        // data-structure, so later segments read the writes of the earlier (committed) ones.
+        let mut segments = VecDeque::from([(0, num_txns)]);
+        while let Some((begin, end)) = segments.pop_front() {
+            self.execute_segment(
+                begin,
+                end,
+                &executor_initial_arguments,
+                signature_verified_block,
+                &last_input_output,
+                &versioned_data_cache,
+            );
+
+            // A transaction executed on its own can not race with the Move-VM loader cache,
+            // as the cache does not outlive the segment.
+            if end - begin > 1 && last_input_output.module_publishing_may_race() {
+                counters::MODULE_PUBLISHING_FALLBACK_COUNT.inc();
+                let module_writers: Vec<TxnIndex> = (begin..end)
+                    .filter(|idx| last_input_output.writes_module(*idx))
+                    .collect();
+
+                // Discard the executions of the segment so it can be re-executed from scratch.
+                for idx in begin..end {
+                    for k in last_input_output.discard(idx) {
+                        versioned_data_cache.delete(&k, idx);
+                    }
+                }
+
+                // Re-execute module publishing transactions on their own, and the transactions
+                // in between them in parallel. The module writes of the final executions of the
+                // segment are a good predictor, but should they change upon re-execution, the
+                // race is detected again and the affected (smaller) segment is split further.
+                let mut split = Vec::new();
+                if module_writers.is_empty() {
+                    let mid = begin + (end - begin) / 2;
+                    split.push((begin, mid));
+                    split.push((mid, end));
+                } else {
+                    let mut segment_begin = begin;
+                    for idx in module_writers {
+                        if segment_begin < idx {
+                            split.push((segment_begin, idx));
+                        }
+                        split.push((idx, idx + 1));
+                        segment_begin = idx + 1;
+                    }
+                    if segment_begin < end {
+                        split.push((segment_begin, end));
+                    }
+                }
+                for segment in split.into_iter().rev() {
+                    segments.push_front(segment);
+                }
+                continue;
             }
-        });
+
+            if (begin..end).any(|idx| last_input_output.ends_block(idx)) {
+                // Transactions after the one ending the block are not committed.
+                break;
+            }
+        }
 
         // TODO: for large block sizes and many cores, extract outputs in parallel.
-        let num_txns = scheduler.num_txn_to_execute();
         let mut final_results = Vec::with_capacity(num_txns);
 
-        let maybe_err = if last_input_output.module_publishing_may_race() {
-            counters::MODULE_PUBLISHING_FALLBACK_COUNT.inc();
-            Some(Error::ModulePathReadWrite)
-        } else {
-            let mut ret = None;
-            for idx in 0..num_txns {
-                match last_input_output.take_output(idx) {
-                    ExecutionStatus::Success(t) => final_results.push(t),
-                    ExecutionStatus::SkipRest(t) => {
-                        final_results.push(t);
-                        break;
-                    }
-                    ExecutionStatus::Abort(err) => {
-                        ret = Some(err);
-                        break;
-                    }
-                };
-            }
-            ret
-        };
+        let mut maybe_err = None;
+        for idx in 0..num_txns {
+            match last_input_output.take_output(idx) {
+                ExecutionStatus::Success(t) => final_results.push(t),
+                ExecutionStatus::SkipRest(t) => {
+                    final_results.push(t);
+                    break;
+                }
+                ExecutionStatus::Abort(err) => {
+                    maybe_err = Some(err);
+                    break;
+                }
+            };
+        }
 
         RAYON_EXEC_POOL.spawn(move || {
             // Explicit async drops.
             drop(last_input_output);
-            drop(scheduler);
         });
 
         match maybe_err {
@@ -2,7 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    errors::Error,
     executor::BlockExecutor,
     proptest_types::types::{
         ExpectedOutput, KeyType, Task, Transaction, TransactionGen, TransactionGenParams, ValueType,
@@ -18,7 +17,7 @@ use proptest::{
     strategy::{Strategy, ValueTree},
     test_runner::TestRunner,
 };
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::HashSet, fmt::Debug, hash::Hash};
 
 fn run_transactions<K, V>(
     key_universe: &[K],
@@ -53,17 +52,45 @@ fn run_transactions<K, V>(
         .execute_transactions_parallel((), &transactions)
         .map(|(res, _)| res);
 
-        if module_access.0 && module_access.1 {
-            assert_eq!(output.unwrap_err(), Error::ModulePathReadWrite);
-            continue;
-        }
-
         let baseline = ExpectedOutput::generate_baseline(&transactions, None);
 
         baseline.assert_output(&output, None);
     }
 }
 
+fn run_transactions_with_publishing<K, V>(
+    key_universe: &[K],
+    transaction_gens: Vec<TransactionGen<V>>,
+    publishing_transactions: Vec<Index>,
+) where
+    K: Hash + Clone + Debug + Eq + Send + Sync + PartialOrd + Ord + 'static,
+    V: Clone + Eq + Send + Sync + Arbitrary + 'static,
+    Vec<u8>: From<V>,
+{
+    let length = transaction_gens.len();
+    let publishing: HashSet<usize> = publishing_transactions
+        .into_iter()
+        .map(|i| i.index(length))
+        .collect();
+
+    // All transactions read modules, the publishing ones also write them.
+    let transactions: Vec<_> = transaction_gens
+        .into_iter()
+        .enumerate()
+        .map(|(idx, txn_gen)| txn_gen.materialize(key_universe, (publishing.contains(&idx), true)))
+        .collect();
+
+    let output = BlockExecutor::<
+        Transaction<KeyType<K>, ValueType<V>>,
+        Task<KeyType<K>, ValueType<V>>,
+    >::new(num_cpus::get())
+    .execute_transactions_parallel((), &transactions)
+    .map(|(res, _)| res);
+
+    let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+    baseline.assert_output(&output, None);
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(32))]
     #[test]
@@ -115,6 +142,15 @@ proptest! {
     ) {
         run_transactions(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false));
     }
+
+    #[test]
+    fn module_publishing_mixed(
+        universe in vec(any::<[u8; 32]>(), 100),
+        transaction_gen in vec(any_with::<TransactionGen<[u8;32]>>(TransactionGenParams::new_dynamic()), 1000).no_shrink(),
+        publishing_transactions in vec(any::<Index>(), 5),
+    ) {
+        run_transactions_with_publishing(&universe, transaction_gen, publishing_transactions);
+    }
 }
 
 #[test]
@@ -389,7 +425,8 @@ fn publishing_fixed_params() {
         .execute_transactions_parallel((), &transactions)
         .map(|(res, _)| res);
 
-        assert_eq!(output.unwrap_err(), Error::ModulePathReadWrite);
+        let baseline = ExpectedOutput::generate_baseline(&transactions, None);
+        baseline.assert_output(&output, None);
     }
 }
 
@@ -46,8 +46,8 @@ pub struct KeyType<K: Hash + Clone + Debug + PartialOrd + Ord + Eq>(
     pub K,
     /// The bool field determines for testing purposes, whether the key will be interpreted
     /// as a module access path. In this case, if a module path is both read and written
-    /// during parallel execution, Error::ModulePathReadWrite must be returned and the
-    /// block execution must fall back to the sequential execution.
+    /// during parallel execution, the block must be re-executed in segments that separate
+    /// the module writing transactions from the others, with the same final outputs.
     pub bool,
 );
 
@@ -140,10 +140,17 @@ pub struct Scheduler {
 /// Public Interfaces for the Scheduler
 impl Scheduler {
     pub fn new(num_txns: usize) -> Self {
+        Self::new_from(0, num_txns)
+    }
+
+    /// Creates a scheduler for the transactions of a block with indices in
+    /// [first_txn_idx, num_txns), the transactions before first_txn_idx having already been
+    /// executed and committed.
+    pub fn new_from(first_txn_idx: TxnIndex, num_txns: usize) -> Self {
         Self {
             num_txns,
-            execution_idx: AtomicUsize::new(0),
-            validation_idx: AtomicUsize::new(0),
+            execution_idx: AtomicUsize::new(first_txn_idx),
+            validation_idx: AtomicUsize::new(first_txn_idx),
             decrease_cnt: AtomicUsize::new(0),
             num_active_tasks: AtomicUsize::new(0),
             done_marker: AtomicBool::new(false),
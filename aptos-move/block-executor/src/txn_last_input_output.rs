@@ -171,10 +171,11 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
     /// possible that Move-VM loader cache loads a module and incorrectly uses it for another
     /// transaction (e.g. a smaller transaction, or if the speculative execution of the publishing
     /// transaction later aborts). The intersection is guaranteed to be found because we first
-    /// record the paths then check the other set (flags principle), and in this case we return an
-    /// error that ensures a fallback to a correct sequential execution.
+    /// record the paths then check the other set (flags principle), and in this case the executor
+    /// re-executes the transactions in segments separating the module publishing transactions from
+    /// the others, each segment with fresh executors (and thus, fresh loader caches).
     /// When the sets do not have an intersection, it is impossible for the race to occur as any
-    /// module in the loader cache may not be published by a transaction in the ongoing block.
+    /// module in the loader cache may not be published by a transaction in the ongoing segment.
     pub fn record(
         &self,
         txn_idx: TxnIndex,
@@ -210,6 +211,49 @@ impl<K: ModulePath, T: TransactionOutput, E: Send + Clone> TxnLastInputOutput<K,
         self.module_read_write_intersection.load(Ordering::Acquire)
     }
 
+    /// Forgets the module paths read and written so far. Must be called before executing a
+    /// new segment of the block with fresh executors (and thus, fresh Move-VM loader caches),
+    /// since modules accessed by earlier segments can no longer race.
+    pub fn reset_module_accesses(&self) {
+        self.module_reads.clear();
+        self.module_writes.clear();
+        self.module_read_write_intersection
+            .store(false, Ordering::Release);
+    }
+
+    /// Whether the last recorded execution of txn_idx wrote to a module path.
+    pub fn writes_module(&self, txn_idx: TxnIndex) -> bool {
+        match &self.outputs[txn_idx].load_full() {
+            None => false,
+            Some(txn_output) => match txn_output.as_ref() {
+                ExecutionStatus::Success(t) | ExecutionStatus::SkipRest(t) => t
+                    .get_writes()
+                    .into_iter()
+                    .any(|(k, _)| k.module_path().is_some()),
+                ExecutionStatus::Abort(_) => false,
+            },
+        }
+    }
+
+    /// Whether the last recorded execution of txn_idx ends the block, i.e. no transactions
+    /// after it should be committed.
+    pub fn ends_block(&self, txn_idx: TxnIndex) -> bool {
+        match &self.outputs[txn_idx].load_full() {
+            None => false,
+            Some(txn_output) => !matches!(txn_output.as_ref(), ExecutionStatus::Success(_)),
+        }
+    }
+
+    /// Forgets the last recorded execution of txn_idx, so the transaction can be executed from
+    /// scratch. Returns the keys it modified, which the caller must remove from the
+    /// multi-version data-structure.
+    pub fn discard(&self, txn_idx: TxnIndex) -> KeySet<T> {
+        let modified_keys = self.modified_keys(txn_idx);
+        self.inputs[txn_idx].store(None);
+        self.outputs[txn_idx].store(None);
+        modified_keys
+    }
+
     pub fn read_set(&self, txn_idx: TxnIndex) -> Option<Arc<Vec<ReadDescriptor<K>>>> {
         self.inputs[txn_idx].load_full()
     }
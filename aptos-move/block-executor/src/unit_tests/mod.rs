@@ -598,3 +598,33 @@ fn scheduler_drain_idx() {
 
     assert!(matches!(s.next_task(), SchedulerTask::Done));
 }
+
+#[test]
+fn scheduler_from_idx() {
+    // Transactions 0 and 1 are committed already.
+    let s = Scheduler::new_from(2, 4);
+    let fake_counter = AtomicUsize::new(0);
+
+    for i in 2..4 {
+        assert!(matches!(
+            s.next_task(),
+            SchedulerTask::ExecutionTask((j, 0), None, _) if j == i
+        ));
+    }
+
+    // Validation index is higher, so the validation task is returned to the caller.
+    assert!(matches!(
+        s.finish_execution(2, 0, false, TaskGuard::new(&fake_counter)),
+        SchedulerTask::ValidationTask((2, 0), _)
+    ));
+    assert!(matches!(
+        s.finish_execution(3, 0, false, TaskGuard::new(&fake_counter)),
+        SchedulerTask::NoTask
+    ));
+    assert!(matches!(
+        s.next_task(),
+        SchedulerTask::ValidationTask((3, 0), _)
+    ));
+
+    assert!(matches!(s.next_task(), SchedulerTask::Done));
+}
@@ -204,6 +204,26 @@ impl MoveHarness {
             .sign()
     }
 
+    /// Creates a transaction from `account` whose gas is paid by `fee_payer`.
+    pub fn create_fee_payer_transaction_payload(
+        &mut self,
+        account: &Account,
+        fee_payer: &Account,
+        payload: TransactionPayload,
+    ) -> SignedTransaction {
+        let on_chain_seq_no = self.sequence_number(account.address());
+        let seq_no_ref = self.txn_seq_no.get_mut(account.address()).unwrap();
+        let seq_no = std::cmp::max(on_chain_seq_no, *seq_no_ref);
+        *seq_no_ref = seq_no + 1;
+        account
+            .transaction()
+            .sequence_number(seq_no)
+            .max_gas_amount(2_000_000)
+            .gas_unit_price(1)
+            .payload(payload)
+            .sign_fee_payer(fee_payer)
+    }
+
     /// Runs a transaction, based on provided payload. If the transaction succeeds, any generated
     /// writeset will be applied to storage.
     pub fn run_transaction_payload(
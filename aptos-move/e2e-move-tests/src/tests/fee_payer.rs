@@ -0,0 +1,86 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_types::{
+    account_address::AccountAddress, account_config::CoinStoreResource,
+    on_chain_config::FeatureFlag, transaction::TransactionStatus, vm_status::StatusCode,
+};
+use cached_packages::aptos_stdlib;
+
+fn read_coin(h: &MoveHarness, account: &AccountAddress) -> u64 {
+    h.read_resource::<CoinStoreResource>(account, CoinStoreResource::struct_tag())
+        .unwrap()
+        .coin()
+}
+
+#[test]
+fn fee_payer_pays_gas_on_success() {
+    let mut h = MoveHarness::new();
+    // The sender could not afford the gas of this transaction on its own.
+    let sender = h.new_account_with_balance_and_sequence_number(1_000, 10);
+    let fee_payer = h.new_account_with_key_pair();
+    let recipient = h.new_account_with_key_pair();
+
+    let fee_payer_start = read_coin(&h, fee_payer.address());
+    let recipient_start = read_coin(&h, recipient.address());
+    let txn = h.create_fee_payer_transaction_payload(
+        &sender,
+        &fee_payer,
+        aptos_stdlib::aptos_account_transfer(*recipient.address(), 100),
+    );
+    let output = h.run_raw(txn);
+    assert_success!(output.status().to_owned());
+
+    assert_eq!(read_coin(&h, sender.address()), 900);
+    assert_eq!(read_coin(&h, recipient.address()), recipient_start + 100);
+    assert_eq!(
+        read_coin(&h, fee_payer.address()),
+        fee_payer_start - output.gas_used()
+    );
+    assert_eq!(h.sequence_number(sender.address()), 11);
+}
+
+#[test]
+fn fee_payer_pays_gas_on_failure() {
+    let mut h = MoveHarness::new();
+    let sender = h.new_account_with_balance_and_sequence_number(10, 10);
+    let fee_payer = h.new_account_with_key_pair();
+    let recipient = h.new_account_with_key_pair();
+
+    // The transfer aborts in `coin`, so only the failure epilogue charges for gas.
+    let fee_payer_start = read_coin(&h, fee_payer.address());
+    let txn = h.create_fee_payer_transaction_payload(
+        &sender,
+        &fee_payer,
+        aptos_stdlib::aptos_account_transfer(*recipient.address(), 100),
+    );
+    let output = h.run_raw(txn);
+    assert_abort!(output.status().to_owned(), 0x10006);
+
+    assert_eq!(read_coin(&h, sender.address()), 10);
+    assert_eq!(
+        read_coin(&h, fee_payer.address()),
+        fee_payer_start - output.gas_used()
+    );
+    assert_eq!(h.sequence_number(sender.address()), 11);
+}
+
+#[test]
+fn fee_payer_requires_feature() {
+    let mut h = MoveHarness::new();
+    let sender = h.new_account_with_key_pair();
+    let fee_payer = h.new_account_with_key_pair();
+    let recipient = h.new_account_with_key_pair();
+
+    h.enable_features(vec![], vec![FeatureFlag::FEE_PAYER]);
+    let txn = h.create_fee_payer_transaction_payload(
+        &sender,
+        &fee_payer,
+        aptos_stdlib::aptos_account_transfer(*recipient.address(), 100),
+    );
+    assert_eq!(
+        h.run(txn),
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
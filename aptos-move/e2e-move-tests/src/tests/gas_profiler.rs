@@ -0,0 +1,45 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_types::account_address::AccountAddress;
+use aptos_vm::AptosVM;
+use cached_packages::aptos_stdlib;
+
+#[test]
+fn test_gas_profiler_transfer() {
+    let mut harness = MoveHarness::new();
+    let account_1 = harness.new_account_at(AccountAddress::from_hex_literal("0x121").unwrap());
+    let account_2 = harness.new_account_at(AccountAddress::from_hex_literal("0x122").unwrap());
+
+    let txn = harness.create_transaction_payload(
+        &account_1,
+        aptos_stdlib::aptos_account_transfer(*account_2.address(), 1000),
+    );
+    let (_, output, gas_profile) = AptosVM::execute_user_transaction_with_gas_profile(
+        harness.executor.get_state_view(),
+        txn.clone(),
+    );
+    let output = output.into_transaction_output(harness.executor.get_state_view());
+    let gas_profile = gas_profile.unwrap();
+
+    // Profiling must not change what gets charged.
+    assert_eq!(output.gas_used(), harness.run_raw(txn).gas_used());
+
+    assert_eq!(gas_profile.entry_point, "0x1::aptos_account::transfer");
+    assert!(gas_profile.intrinsic > 0.into());
+    assert!(gas_profile.execution.total_storage_reads() > 0.into());
+    assert!(gas_profile.total_storage_writes() > 0.into());
+    assert!(gas_profile
+        .execution
+        .callees
+        .contains_key("0x1::coin::transfer"));
+
+    let folded_stacks = gas_profile.to_folded_stacks();
+    assert!(folded_stacks
+        .lines()
+        .any(|line| line.starts_with("0x1::aptos_account::transfer;0x1::coin::transfer;")));
+    assert!(folded_stacks
+        .lines()
+        .any(|line| line.starts_with("0x1::aptos_account::transfer;storage_write;")));
+}
@@ -6,8 +6,10 @@ mod chain_id;
 mod code_publishing;
 mod common;
 mod error_map;
+mod fee_payer;
 mod framework_compatibility;
 mod gas;
+mod gas_profiler;
 mod generate_upgrade_script;
 mod governance_updates;
 mod infinite_loop;
@@ -16,6 +18,7 @@ mod lazy_natives;
 mod max_loop_depth;
 mod memory_quota;
 mod mint_nft;
+mod multisig;
 mod offer_signer_capability;
 mod rotate_auth_key;
 mod scripts;
@@ -0,0 +1,231 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, MoveHarness};
+use aptos_types::{
+    account_address::{create_resource_address, AccountAddress},
+    account_config::CoinStoreResource,
+    on_chain_config::FeatureFlag,
+    transaction::{Multisig, MultisigTransactionPayload, TransactionPayload, TransactionStatus},
+    vm_status::StatusCode,
+};
+use cached_packages::aptos_stdlib;
+use language_e2e_tests::account::Account;
+
+const SEED: &[u8] = b"seed";
+
+/// Creates a multisig account owned by `owners` which requires `num_signatures_required`
+/// approvals, and funds it with `balance` coins.
+fn create_multisig_account(
+    h: &mut MoveHarness,
+    owners: &[Account],
+    num_signatures_required: u64,
+    balance: u64,
+) -> AccountAddress {
+    let additional_owners = owners[1..].iter().map(|owner| *owner.address()).collect();
+    assert_success!(h.run_transaction_payload(
+        &owners[0],
+        aptos_stdlib::multisig_account_create(
+            additional_owners,
+            num_signatures_required,
+            SEED.to_vec()
+        ),
+    ));
+    let multisig_address = create_resource_address(*owners[0].address(), SEED);
+    assert_success!(h.run_transaction_payload(
+        &owners[0],
+        aptos_stdlib::aptos_account_transfer(multisig_address, balance),
+    ));
+    multisig_address
+}
+
+/// Proposes a transfer of `amount` coins from the multisig account to `to`.
+fn propose_transfer(
+    h: &mut MoveHarness,
+    owner: &Account,
+    multisig_address: AccountAddress,
+    to: AccountAddress,
+    amount: u64,
+) {
+    let entry_function = match aptos_stdlib::aptos_account_transfer(to, amount) {
+        TransactionPayload::EntryFunction(entry_function) => entry_function,
+        _ => unreachable!(),
+    };
+    let payload = MultisigTransactionPayload::EntryFunction(entry_function);
+    assert_success!(h.run_transaction_payload(
+        owner,
+        aptos_stdlib::multisig_account_create_transaction(
+            multisig_address,
+            bcs::to_bytes(&payload).unwrap(),
+        ),
+    ));
+}
+
+fn execute(
+    h: &mut MoveHarness,
+    owner: &Account,
+    multisig_address: AccountAddress,
+) -> (TransactionStatus, u64) {
+    let txn = h.create_transaction_payload(
+        owner,
+        TransactionPayload::Multisig(Multisig {
+            multisig_address,
+            transaction_payload: None,
+        }),
+    );
+    let output = h.run_raw(txn);
+    (output.status().to_owned(), output.gas_used())
+}
+
+fn read_coin(h: &MoveHarness, account: &AccountAddress) -> u64 {
+    h.read_resource::<CoinStoreResource>(account, CoinStoreResource::struct_tag())
+        .unwrap()
+        .coin()
+}
+
+#[test]
+fn multisig_execute_approved_transaction() {
+    let mut h = MoveHarness::new();
+    let owners = vec![
+        h.new_account_with_key_pair(),
+        h.new_account_with_key_pair(),
+        h.new_account_with_key_pair(),
+    ];
+    let recipient = h.new_account_with_key_pair();
+    let multisig_address = create_multisig_account(&mut h, &owners, 2, 1_000);
+
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        100,
+    );
+    assert_success!(h.run_transaction_payload(
+        &owners[1],
+        aptos_stdlib::multisig_account_approve_transaction(multisig_address, 1),
+    ));
+
+    let executor_start = read_coin(&h, owners[2].address());
+    let recipient_start = read_coin(&h, recipient.address());
+    let (status, gas_used) = execute(&mut h, &owners[2], multisig_address);
+    assert_success!(status);
+
+    // The transfer is signed by the multisig account, while the executing owner pays for gas.
+    assert_eq!(read_coin(&h, &multisig_address), 900);
+    assert_eq!(read_coin(&h, recipient.address()), recipient_start + 100);
+    assert_eq!(
+        read_coin(&h, owners[2].address()),
+        executor_start - gas_used
+    );
+
+    // The transaction was consumed, so there is nothing left to execute.
+    let (status, _) = execute(&mut h, &owners[2], multisig_address);
+    assert_abort!(status, 0x60006);
+}
+
+#[test]
+fn multisig_execute_without_enough_approvals() {
+    let mut h = MoveHarness::new();
+    let owners = vec![h.new_account_with_key_pair(), h.new_account_with_key_pair()];
+    let recipient = h.new_account_with_key_pair();
+    let multisig_address = create_multisig_account(&mut h, &owners, 2, 1_000);
+
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        100,
+    );
+    let (status, _) = execute(&mut h, &owners[0], multisig_address);
+    assert_abort!(status, 0x30008);
+    assert_eq!(read_coin(&h, &multisig_address), 1_000);
+}
+
+#[test]
+fn multisig_execute_aborting_payload_stays_pending() {
+    let mut h = MoveHarness::new();
+    let owners = vec![h.new_account_with_key_pair()];
+    let recipient = h.new_account_with_key_pair();
+    let multisig_address = create_multisig_account(&mut h, &owners, 1, 10);
+
+    // The multisig account cannot afford the transfer, so the payload aborts in `coin`.
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        100,
+    );
+    let (status, _) = execute(&mut h, &owners[0], multisig_address);
+    assert_abort!(status, 0x10006);
+    assert_eq!(read_coin(&h, &multisig_address), 10);
+
+    // The transaction is still pending and succeeds once the account is funded.
+    assert_success!(h.run_transaction_payload(
+        &owners[0],
+        aptos_stdlib::aptos_account_transfer(multisig_address, 90),
+    ));
+    let (status, _) = execute(&mut h, &owners[0], multisig_address);
+    assert_success!(status);
+    assert_eq!(read_coin(&h, &multisig_address), 0);
+}
+
+#[test]
+fn multisig_remove_rejected_transaction() {
+    let mut h = MoveHarness::new();
+    let owners = vec![h.new_account_with_key_pair(), h.new_account_with_key_pair()];
+    let recipient = h.new_account_with_key_pair();
+    let multisig_address = create_multisig_account(&mut h, &owners, 1, 1_000);
+
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        100,
+    );
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        200,
+    );
+    assert_success!(h.run_transaction_payload(
+        &owners[1],
+        aptos_stdlib::multisig_account_reject_transaction(multisig_address, 1),
+    ));
+    assert_success!(h.run_transaction_payload(
+        &owners[1],
+        aptos_stdlib::multisig_account_remove_rejected_transaction(multisig_address),
+    ));
+
+    // The second transaction is executed, the rejected one never is.
+    let (status, _) = execute(&mut h, &owners[1], multisig_address);
+    assert_success!(status);
+    assert_eq!(read_coin(&h, &multisig_address), 800);
+}
+
+#[test]
+fn multisig_payload_requires_feature() {
+    let mut h = MoveHarness::new();
+    let owners = vec![h.new_account_with_key_pair()];
+    let recipient = h.new_account_with_key_pair();
+    let multisig_address = create_multisig_account(&mut h, &owners, 1, 1_000);
+    propose_transfer(
+        &mut h,
+        &owners[0],
+        multisig_address,
+        *recipient.address(),
+        100,
+    );
+
+    h.enable_features(vec![], vec![FeatureFlag::MULTISIG_ACCOUNTS]);
+    let (status, _) = execute(&mut h, &owners[0], multisig_address);
+    assert_eq!(
+        status,
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
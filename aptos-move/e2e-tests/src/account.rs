@@ -290,6 +290,37 @@ impl TransactionBuilder {
         .unwrap()
         .into_inner()
     }
+
+    pub fn sign_fee_payer(self, fee_payer: &Account) -> SignedTransaction {
+        let secondary_signer_addresses: Vec<AccountAddress> = self
+            .secondary_signers
+            .iter()
+            .map(|signer| *signer.address())
+            .collect();
+        let secondary_private_keys = self
+            .secondary_signers
+            .iter()
+            .map(|signer| &signer.privkey)
+            .collect();
+        RawTransaction::new(
+            *self.sender.address(),
+            self.sequence_number.expect("sequence number not set"),
+            self.program.expect("transaction payload not set"),
+            self.max_gas_amount.unwrap_or(gas_costs::TXN_RESERVED),
+            self.gas_unit_price.unwrap_or(0),
+            self.ttl.unwrap_or(DEFAULT_EXPIRATION_TIME),
+            ChainId::test(),
+        )
+        .sign_fee_payer(
+            &self.sender.privkey,
+            secondary_signer_addresses,
+            secondary_private_keys,
+            *fee_payer.address(),
+            &fee_payer.privkey,
+        )
+        .unwrap()
+        .into_inner()
+    }
 }
 
 //---------------------------------------------------------------------------
@@ -0,0 +1,569 @@
+/// A multisig account is an on-chain account controlled by a set of owners rather than by a private key.
+///
+/// Owners coordinate asynchronously instead of running an offline signing ceremony:
+/// 1. Any owner proposes a transaction with `create_transaction`, passing the BCS encoded
+/// `MultisigTransactionPayload` that the multisig account should execute. The proposer implicitly approves it.
+/// 2. The other owners approve it with `approve_transaction` or reject it with `reject_transaction`.
+/// 3. Once `num_signatures_required` owners have approved, any owner can execute it by sending a transaction with a
+/// `Multisig` payload. The VM runs the approved payload with the multisig account as the signer and gas is paid by
+/// the executing owner.
+///
+/// Transactions are executed in the order they were proposed. If the approved payload fails to execute, the whole
+/// transaction is aborted and the multisig transaction stays pending so it can be executed again later. Once
+/// `num_signatures_required` owners have rejected the next transaction, any owner can remove it with
+/// `remove_rejected_transaction` so that the transactions after it can be executed.
+///
+/// Multisig accounts can only be created once the `MULTISIG_ACCOUNTS` feature is enabled, and the VM refuses
+/// `Multisig` payloads until then.
+module aptos_framework::multisig_account {
+    use std::error;
+    use std::features;
+    use std::signer;
+    use std::vector;
+    use aptos_framework::account::{Self, new_event_handle};
+    use aptos_framework::aptos_coin::AptosCoin;
+    use aptos_framework::coin;
+    use aptos_framework::event::{EventHandle, emit_event};
+    use aptos_framework::timestamp;
+    use aptos_std::table::{Self, Table};
+
+    /// Owner list cannot contain the same address more than once.
+    const EDUPLICATE_OWNER: u64 = 1;
+    /// Number of signatures required must be greater than zero and at most the number of owners.
+    const EINVALID_SIGNATURES_REQUIRED: u64 = 2;
+    /// Specified account is not a multisig account.
+    const EACCOUNT_NOT_MULTISIG: u64 = 3;
+    /// Account executing this operation is not an owner of the multisig account.
+    const ENOT_OWNER: u64 = 4;
+    /// Transaction payload cannot be empty.
+    const EPAYLOAD_CANNOT_BE_EMPTY: u64 = 5;
+    /// Transaction with the specified sequence number does not exist on the multisig account.
+    const ETRANSACTION_NOT_FOUND: u64 = 6;
+    /// Owner has already approved the transaction.
+    const EALREADY_APPROVED: u64 = 7;
+    /// Transaction has not received enough approvals to be executed.
+    const ENOT_ENOUGH_APPROVALS: u64 = 8;
+    /// Provided payload does not match the payload of the approved transaction.
+    const EPAYLOAD_DOES_NOT_MATCH: u64 = 9;
+    /// Owner has already rejected the transaction.
+    const EALREADY_REJECTED: u64 = 10;
+    /// Transaction has not received enough rejections to be removed.
+    const ENOT_ENOUGH_REJECTIONS: u64 = 11;
+    /// Multisig accounts are not enabled yet.
+    const EMULTISIG_ACCOUNTS_NOT_ENABLED: u64 = 12;
+
+    /// Represents a multisig account's configuration and pending transactions.
+    struct MultisigAccount has key {
+        // The list of all owner addresses.
+        owners: vector<address>,
+        // The number of approvals required to execute a transaction.
+        num_signatures_required: u64,
+        // Pending transactions, keyed by their sequence number.
+        transactions: Table<u64, MultisigTransaction>,
+        // The sequence number of the last executed transaction. Transactions are executed in order.
+        last_executed_sequence_number: u64,
+        // The sequence number assigned to the next proposed transaction.
+        next_sequence_number: u64,
+        create_transaction_events: EventHandle<CreateTransactionEvent>,
+        approve_transaction_events: EventHandle<ApproveTransactionEvent>,
+        reject_transaction_events: EventHandle<RejectTransactionEvent>,
+        execute_transaction_events: EventHandle<ExecuteTransactionEvent>,
+        remove_transaction_events: EventHandle<RemoveTransactionEvent>,
+    }
+
+    /// A transaction proposed by one of the owners, waiting for enough approvals to be executed.
+    struct MultisigTransaction has copy, drop, store {
+        // BCS encoded `MultisigTransactionPayload`.
+        payload: vector<u8>,
+        // Owners who have approved the transaction.
+        approvals: vector<address>,
+        // Owners who have rejected the transaction. An owner either approves or rejects a transaction, not both.
+        rejections: vector<address>,
+        // The owner who proposed the transaction.
+        creator: address,
+        creation_time_secs: u64,
+    }
+
+    struct CreateTransactionEvent has drop, store {
+        creator: address,
+        sequence_number: u64,
+        payload: vector<u8>,
+    }
+
+    struct ApproveTransactionEvent has drop, store {
+        owner: address,
+        sequence_number: u64,
+        num_approvals: u64,
+    }
+
+    struct RejectTransactionEvent has drop, store {
+        owner: address,
+        sequence_number: u64,
+        num_rejections: u64,
+    }
+
+    struct ExecuteTransactionEvent has drop, store {
+        executor: address,
+        sequence_number: u64,
+        payload: vector<u8>,
+    }
+
+    struct RemoveTransactionEvent has drop, store {
+        executor: address,
+        sequence_number: u64,
+        num_rejections: u64,
+    }
+
+    /// Creates a new multisig account owned by `owner` and `additional_owners`. The account is a resource account
+    /// derived from `owner` and `seed`, so nobody can sign for it with a private key.
+    public entry fun create(
+        owner: &signer,
+        additional_owners: vector<address>,
+        num_signatures_required: u64,
+        seed: vector<u8>,
+    ) {
+        assert!(features::multisig_accounts_enabled(), error::invalid_state(EMULTISIG_ACCOUNTS_NOT_ENABLED));
+        let owners = additional_owners;
+        vector::push_back(&mut owners, signer::address_of(owner));
+        validate_owners(&owners);
+        assert!(
+            num_signatures_required > 0 && num_signatures_required <= vector::length(&owners),
+            error::invalid_argument(EINVALID_SIGNATURES_REQUIRED),
+        );
+
+        let (multisig_account, _) = account::create_resource_account(owner, seed);
+        coin::register<AptosCoin>(&multisig_account);
+        move_to(&multisig_account, MultisigAccount {
+            owners,
+            num_signatures_required,
+            transactions: table::new<u64, MultisigTransaction>(),
+            last_executed_sequence_number: 0,
+            next_sequence_number: 1,
+            create_transaction_events: new_event_handle<CreateTransactionEvent>(&multisig_account),
+            approve_transaction_events: new_event_handle<ApproveTransactionEvent>(&multisig_account),
+            reject_transaction_events: new_event_handle<RejectTransactionEvent>(&multisig_account),
+            execute_transaction_events: new_event_handle<ExecuteTransactionEvent>(&multisig_account),
+            remove_transaction_events: new_event_handle<RemoveTransactionEvent>(&multisig_account),
+        });
+    }
+
+    /// Proposes a new transaction for the multisig account to execute. The proposer implicitly approves it.
+    public entry fun create_transaction(
+        owner: &signer,
+        multisig_account: address,
+        payload: vector<u8>,
+    ) acquires MultisigAccount {
+        assert!(!vector::is_empty(&payload), error::invalid_argument(EPAYLOAD_CANNOT_BE_EMPTY));
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let creator = signer::address_of(owner);
+        assert_is_owner(creator, multisig_account_resource);
+
+        let sequence_number = multisig_account_resource.next_sequence_number;
+        multisig_account_resource.next_sequence_number = sequence_number + 1;
+        table::add(&mut multisig_account_resource.transactions, sequence_number, MultisigTransaction {
+            payload,
+            approvals: vector::singleton(creator),
+            rejections: vector[],
+            creator,
+            creation_time_secs: timestamp::now_seconds(),
+        });
+
+        emit_event(
+            &mut multisig_account_resource.create_transaction_events,
+            CreateTransactionEvent { creator, sequence_number, payload },
+        );
+    }
+
+    /// Approves a pending transaction of the multisig account. Withdraws the owner's rejection if there is one.
+    public entry fun approve_transaction(
+        owner: &signer,
+        multisig_account: address,
+        sequence_number: u64,
+    ) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let owner_addr = signer::address_of(owner);
+        assert_is_owner(owner_addr, multisig_account_resource);
+        let transaction = borrow_pending_transaction_mut(multisig_account_resource, sequence_number);
+        assert!(!vector::contains(&transaction.approvals, &owner_addr), error::invalid_state(EALREADY_APPROVED));
+        remove_owner(&mut transaction.rejections, owner_addr);
+        vector::push_back(&mut transaction.approvals, owner_addr);
+        let num_approvals = vector::length(&transaction.approvals);
+
+        emit_event(
+            &mut multisig_account_resource.approve_transaction_events,
+            ApproveTransactionEvent { owner: owner_addr, sequence_number, num_approvals },
+        );
+    }
+
+    /// Rejects a pending transaction of the multisig account. Withdraws the owner's approval if there is one.
+    public entry fun reject_transaction(
+        owner: &signer,
+        multisig_account: address,
+        sequence_number: u64,
+    ) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let owner_addr = signer::address_of(owner);
+        assert_is_owner(owner_addr, multisig_account_resource);
+        let transaction = borrow_pending_transaction_mut(multisig_account_resource, sequence_number);
+        assert!(!vector::contains(&transaction.rejections, &owner_addr), error::invalid_state(EALREADY_REJECTED));
+        remove_owner(&mut transaction.approvals, owner_addr);
+        vector::push_back(&mut transaction.rejections, owner_addr);
+        let num_rejections = vector::length(&transaction.rejections);
+
+        emit_event(
+            &mut multisig_account_resource.reject_transaction_events,
+            RejectTransactionEvent { owner: owner_addr, sequence_number, num_rejections },
+        );
+    }
+
+    /// Removes the next pending transaction of the multisig account without executing it, once enough owners have
+    /// rejected it. The transaction after it becomes the next one to execute.
+    public entry fun remove_rejected_transaction(
+        owner: &signer,
+        multisig_account: address,
+    ) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let owner_addr = signer::address_of(owner);
+        assert_is_owner(owner_addr, multisig_account_resource);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        let num_rejections = vector::length(
+            &borrow_pending_transaction_mut(multisig_account_resource, sequence_number).rejections
+        );
+        assert!(
+            num_rejections >= multisig_account_resource.num_signatures_required,
+            error::invalid_state(ENOT_ENOUGH_REJECTIONS),
+        );
+
+        table::remove(&mut multisig_account_resource.transactions, sequence_number);
+        multisig_account_resource.last_executed_sequence_number = sequence_number;
+
+        emit_event(
+            &mut multisig_account_resource.remove_transaction_events,
+            RemoveTransactionEvent { executor: owner_addr, sequence_number, num_rejections },
+        );
+    }
+
+    /// Returns true if `multisig_account` is a multisig account.
+    public fun is_multisig_account(multisig_account: address): bool {
+        exists<MultisigAccount>(multisig_account)
+    }
+
+    /// Returns the owners of the multisig account.
+    public fun owners(multisig_account: address): vector<address> acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).owners
+    }
+
+    /// Returns the number of approvals required to execute a transaction of the multisig account.
+    public fun num_signatures_required(multisig_account: address): u64 acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).num_signatures_required
+    }
+
+    /// Returns the sequence number of the last executed transaction of the multisig account.
+    public fun last_executed_sequence_number(multisig_account: address): u64 acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        borrow_global<MultisigAccount>(multisig_account).last_executed_sequence_number
+    }
+
+    /// Returns the BCS encoded payload, the approvals and the rejections of a pending transaction of the multisig
+    /// account.
+    public fun get_transaction(
+        multisig_account: address,
+        sequence_number: u64,
+    ): (vector<u8>, vector<address>, vector<address>) acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let transactions = &borrow_global<MultisigAccount>(multisig_account).transactions;
+        assert!(table::contains(transactions, sequence_number), error::not_found(ETRANSACTION_NOT_FOUND));
+        let transaction = table::borrow(transactions, sequence_number);
+        (transaction.payload, transaction.approvals, transaction.rejections)
+    }
+
+    /// Returns true if the next transaction of the multisig account has enough approvals to be executed.
+    public fun can_execute_next_transaction(multisig_account: address): bool acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        table::contains(&multisig_account_resource.transactions, sequence_number) &&
+            vector::length(&table::borrow(&multisig_account_resource.transactions, sequence_number).approvals) >=
+                multisig_account_resource.num_signatures_required
+    }
+
+    /// Called by the VM before executing a `Multisig` transaction payload. Verifies that `owner` can execute the next
+    /// transaction of the multisig account and returns its payload. `provided_payload` is empty if the executing
+    /// owner relies on the payload stored on chain, otherwise it has to match it.
+    fun get_next_transaction_payload(
+        owner: address,
+        multisig_account: address,
+        provided_payload: vector<u8>,
+    ): vector<u8> acquires MultisigAccount {
+        assert_multisig_account_exists(multisig_account);
+        let multisig_account_resource = borrow_global<MultisigAccount>(multisig_account);
+        assert_is_owner(owner, multisig_account_resource);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        assert!(
+            table::contains(&multisig_account_resource.transactions, sequence_number),
+            error::not_found(ETRANSACTION_NOT_FOUND),
+        );
+
+        let transaction = table::borrow(&multisig_account_resource.transactions, sequence_number);
+        assert!(
+            vector::length(&transaction.approvals) >= multisig_account_resource.num_signatures_required,
+            error::invalid_state(ENOT_ENOUGH_APPROVALS),
+        );
+        assert!(
+            vector::is_empty(&provided_payload) || provided_payload == transaction.payload,
+            error::invalid_argument(EPAYLOAD_DOES_NOT_MATCH),
+        );
+        transaction.payload
+    }
+
+    /// Called by the VM after the payload of the next transaction was executed successfully.
+    fun successful_transaction_execution_cleanup(
+        executor: address,
+        multisig_account: address,
+    ) acquires MultisigAccount {
+        let multisig_account_resource = borrow_global_mut<MultisigAccount>(multisig_account);
+        let sequence_number = multisig_account_resource.last_executed_sequence_number + 1;
+        let transaction = table::remove(&mut multisig_account_resource.transactions, sequence_number);
+        multisig_account_resource.last_executed_sequence_number = sequence_number;
+
+        emit_event(
+            &mut multisig_account_resource.execute_transaction_events,
+            ExecuteTransactionEvent { executor, sequence_number, payload: transaction.payload },
+        );
+    }
+
+    fun validate_owners(owners: &vector<address>) {
+        let len = vector::length(owners);
+        let i = 0;
+        while (i < len) {
+            let owner = vector::borrow(owners, i);
+            let j = i + 1;
+            while (j < len) {
+                assert!(owner != vector::borrow(owners, j), error::invalid_argument(EDUPLICATE_OWNER));
+                j = j + 1;
+            };
+            i = i + 1;
+        };
+    }
+
+    fun borrow_pending_transaction_mut(
+        multisig_account_resource: &mut MultisigAccount,
+        sequence_number: u64,
+    ): &mut MultisigTransaction {
+        assert!(
+            table::contains(&multisig_account_resource.transactions, sequence_number),
+            error::not_found(ETRANSACTION_NOT_FOUND),
+        );
+        table::borrow_mut(&mut multisig_account_resource.transactions, sequence_number)
+    }
+
+    fun remove_owner(owners: &mut vector<address>, owner: address) {
+        let (found, index) = vector::index_of(owners, &owner);
+        if (found) {
+            vector::remove(owners, index);
+        };
+    }
+
+    fun assert_multisig_account_exists(multisig_account: address) {
+        assert!(exists<MultisigAccount>(multisig_account), error::not_found(EACCOUNT_NOT_MULTISIG));
+    }
+
+    fun assert_is_owner(owner: address, multisig_account_resource: &MultisigAccount) {
+        assert!(
+            vector::contains(&multisig_account_resource.owners, &owner),
+            error::permission_denied(ENOT_OWNER),
+        );
+    }
+
+    #[test_only]
+    const PAYLOAD: vector<u8> = x"010203";
+
+    #[test_only]
+    fun setup(aptos_framework: &signer, owners: vector<address>) {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        features::change_feature_flags(aptos_framework, vector[features::get_multisig_accounts_feature()], vector[]);
+        let len = vector::length(&owners);
+        let i = 0;
+        while (i < len) {
+            account::create_account_for_test(*vector::borrow(&owners, i));
+            i = i + 1;
+        };
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124, owner_3 = @0x125)]
+    public entry fun test_create_approve_and_execute(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+        owner_3: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        let owner_2_addr = signer::address_of(owner_2);
+        let owner_3_addr = signer::address_of(owner_3);
+        setup(aptos_framework, vector[owner_1_addr, owner_2_addr, owner_3_addr]);
+
+        create(owner_1, vector[owner_2_addr, owner_3_addr], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+        assert!(is_multisig_account(multisig_account), 0);
+        assert!(num_signatures_required(multisig_account) == 2, 1);
+
+        create_transaction(owner_2, multisig_account, PAYLOAD);
+        assert!(!can_execute_next_transaction(multisig_account), 2);
+        approve_transaction(owner_3, multisig_account, 1);
+        assert!(can_execute_next_transaction(multisig_account), 3);
+
+        assert!(get_next_transaction_payload(owner_1_addr, multisig_account, vector[]) == PAYLOAD, 4);
+        successful_transaction_execution_cleanup(owner_1_addr, multisig_account);
+        assert!(last_executed_sequence_number(multisig_account) == 1, 5);
+        assert!(!can_execute_next_transaction(multisig_account), 6);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x10002)]
+    public entry fun test_create_with_too_many_signatures_required(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+    ) {
+        setup(aptos_framework, vector[signer::address_of(owner_1), signer::address_of(owner_2)]);
+        create(owner_1, vector[signer::address_of(owner_2)], 3, b"seed");
+    }
+
+    #[test(aptos_framework = @0x1, owner = @0x123, non_owner = @0x124)]
+    #[expected_failure(abort_code = 0x50004)]
+    public entry fun test_non_owner_cannot_create_transaction(
+        aptos_framework: &signer,
+        owner: &signer,
+        non_owner: &signer,
+    ) acquires MultisigAccount {
+        setup(aptos_framework, vector[signer::address_of(owner), signer::address_of(non_owner)]);
+        create(owner, vector[], 1, b"seed");
+        let multisig_account = account::create_resource_address(&signer::address_of(owner), b"seed");
+        create_transaction(non_owner, multisig_account, PAYLOAD);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x30008)]
+    public entry fun test_cannot_execute_without_enough_approvals(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        setup(aptos_framework, vector[owner_1_addr, signer::address_of(owner_2)]);
+        create(owner_1, vector[signer::address_of(owner_2)], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+        create_transaction(owner_1, multisig_account, PAYLOAD);
+        get_next_transaction_payload(owner_1_addr, multisig_account, vector[]);
+    }
+
+    #[test(aptos_framework = @0x1, owner = @0x123)]
+    #[expected_failure(abort_code = 0x10009)]
+    public entry fun test_provided_payload_must_match(
+        aptos_framework: &signer,
+        owner: &signer,
+    ) acquires MultisigAccount {
+        let owner_addr = signer::address_of(owner);
+        setup(aptos_framework, vector[owner_addr]);
+        create(owner, vector[], 1, b"seed");
+        let multisig_account = account::create_resource_address(&owner_addr, b"seed");
+        create_transaction(owner, multisig_account, PAYLOAD);
+        get_next_transaction_payload(owner_addr, multisig_account, vector[4, 5, 6]);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124, owner_3 = @0x125)]
+    public entry fun test_reject_and_remove(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+        owner_3: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        let owner_2_addr = signer::address_of(owner_2);
+        let owner_3_addr = signer::address_of(owner_3);
+        setup(aptos_framework, vector[owner_1_addr, owner_2_addr, owner_3_addr]);
+        create(owner_1, vector[owner_2_addr, owner_3_addr], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+
+        create_transaction(owner_1, multisig_account, PAYLOAD);
+        create_transaction(owner_1, multisig_account, x"040506");
+        reject_transaction(owner_2, multisig_account, 1);
+        // Rejecting withdraws the proposer's implicit approval.
+        reject_transaction(owner_1, multisig_account, 1);
+        let (_, approvals, rejections) = get_transaction(multisig_account, 1);
+        assert!(vector::is_empty(&approvals), 0);
+        assert!(rejections == vector[owner_2_addr, owner_1_addr], 1);
+
+        remove_rejected_transaction(owner_3, multisig_account);
+        assert!(last_executed_sequence_number(multisig_account) == 1, 2);
+        approve_transaction(owner_2, multisig_account, 2);
+        assert!(get_next_transaction_payload(owner_3_addr, multisig_account, vector[]) == x"040506", 3);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124)]
+    public entry fun test_approve_withdraws_rejection(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        let owner_2_addr = signer::address_of(owner_2);
+        setup(aptos_framework, vector[owner_1_addr, owner_2_addr]);
+        create(owner_1, vector[owner_2_addr], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+
+        create_transaction(owner_1, multisig_account, PAYLOAD);
+        reject_transaction(owner_2, multisig_account, 1);
+        approve_transaction(owner_2, multisig_account, 1);
+        let (_, approvals, rejections) = get_transaction(multisig_account, 1);
+        assert!(approvals == vector[owner_1_addr, owner_2_addr], 0);
+        assert!(vector::is_empty(&rejections), 1);
+        assert!(can_execute_next_transaction(multisig_account), 2);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x3000A)]
+    public entry fun test_cannot_reject_twice(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        setup(aptos_framework, vector[owner_1_addr, signer::address_of(owner_2)]);
+        create(owner_1, vector[signer::address_of(owner_2)], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+        create_transaction(owner_1, multisig_account, PAYLOAD);
+        reject_transaction(owner_2, multisig_account, 1);
+        reject_transaction(owner_2, multisig_account, 1);
+    }
+
+    #[test(aptos_framework = @0x1, owner_1 = @0x123, owner_2 = @0x124)]
+    #[expected_failure(abort_code = 0x3000B)]
+    public entry fun test_cannot_remove_without_enough_rejections(
+        aptos_framework: &signer,
+        owner_1: &signer,
+        owner_2: &signer,
+    ) acquires MultisigAccount {
+        let owner_1_addr = signer::address_of(owner_1);
+        setup(aptos_framework, vector[owner_1_addr, signer::address_of(owner_2)]);
+        create(owner_1, vector[signer::address_of(owner_2)], 2, b"seed");
+        let multisig_account = account::create_resource_address(&owner_1_addr, b"seed");
+        create_transaction(owner_1, multisig_account, PAYLOAD);
+        reject_transaction(owner_2, multisig_account, 1);
+        remove_rejected_transaction(owner_1, multisig_account);
+    }
+
+    #[test(aptos_framework = @0x1, owner = @0x123)]
+    #[expected_failure(abort_code = 0x3000C)]
+    public entry fun test_create_requires_feature(aptos_framework: &signer, owner: &signer) {
+        timestamp::set_time_has_started_for_testing(aptos_framework);
+        account::create_account_for_test(signer::address_of(owner));
+        create(owner, vector[], 1, b"seed");
+    }
+}
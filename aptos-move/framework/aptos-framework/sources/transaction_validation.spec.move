@@ -27,6 +27,7 @@ spec aptos_framework::transaction_validation {
         use aptos_framework::account::{Account};
         use aptos_framework::coin::{CoinStore};
         sender: signer;
+        gas_payer: address;
         txn_sequence_number: u64;
         txn_authentication_key: vector<u8>;
         txn_gas_price: u64;
@@ -48,12 +49,13 @@ spec aptos_framework::transaction_validation {
         let max_transaction_fee = txn_gas_price * txn_max_gas_units;
         aborts_if max_transaction_fee > MAX_U64;
         aborts_if !(txn_sequence_number == global<Account>(transaction_sender).sequence_number);
-        aborts_if !exists<CoinStore<AptosCoin>>(transaction_sender);
-        aborts_if !(global<CoinStore<AptosCoin>>(transaction_sender).coin.value >= max_transaction_fee);
+        aborts_if !exists<CoinStore<AptosCoin>>(gas_payer);
+        aborts_if !(global<CoinStore<AptosCoin>>(gas_payer).coin.value >= max_transaction_fee);
     }
 
     spec prologue_common(
         sender: signer,
+        gas_payer: address,
         txn_sequence_number: u64,
         txn_authentication_key: vector<u8>,
         txn_gas_price: u64,
@@ -74,6 +76,7 @@ spec aptos_framework::transaction_validation {
         chain_id: u8,
     ) {
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
             txn_authentication_key: txn_public_key
         };
     }
@@ -89,6 +92,7 @@ spec aptos_framework::transaction_validation {
         _script_hash: vector<u8>,
     ) {
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
             txn_authentication_key: txn_public_key
         };
     }
@@ -110,6 +114,46 @@ spec aptos_framework::transaction_validation {
         pragma aborts_if_is_partial;
 
         include PrologueCommonAbortsIf {
+            gas_payer: signer::address_of(sender),
+            txn_authentication_key: txn_sender_public_key
+        };
+        let num_secondary_signers = len(secondary_signer_addresses);
+        aborts_if !(len(secondary_signer_public_key_hashes) == num_secondary_signers);
+    }
+
+    spec multi_agent_common_prologue(
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+    ) {
+        /// TODO: complex while loop condition.
+        pragma aborts_if_is_partial;
+
+        let num_secondary_signers = len(secondary_signer_addresses);
+        aborts_if !(len(secondary_signer_public_key_hashes) == num_secondary_signers);
+    }
+
+    /// Aborts if the fee payer account doesn't exist or its authentication key doesn't match.
+    spec fee_payer_script_prologue(
+        sender: signer,
+        txn_sequence_number: u64,
+        txn_sender_public_key: vector<u8>,
+        secondary_signer_addresses: vector<address>,
+        secondary_signer_public_key_hashes: vector<vector<u8>>,
+        fee_payer_address: address,
+        fee_payer_public_key_hash: vector<u8>,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        txn_expiration_time: u64,
+        chain_id: u8,
+    ) {
+        use aptos_framework::account::{Account};
+        /// TODO: complex while loop condition.
+        pragma aborts_if_is_partial;
+
+        aborts_if !account::exists_at(fee_payer_address);
+        aborts_if !(fee_payer_public_key_hash == global<Account>(fee_payer_address).authentication_key);
+        include PrologueCommonAbortsIf {
+            gas_payer: fee_payer_address,
             txn_authentication_key: txn_sender_public_key
         };
         let num_secondary_signers = len(secondary_signer_addresses);
@@ -121,16 +165,41 @@ spec aptos_framework::transaction_validation {
     /// Skip transaction_fee::burn_fee verification.
     spec epilogue(
         account: signer,
+        txn_sequence_number: u64,
+        txn_gas_price: u64,
+        txn_max_gas_units: u64,
+        gas_units_remaining: u64
+    ) {
+        // TODO: call burn_fee, complex aborts conditions.
+        pragma aborts_if_is_partial;
+
+        include EpilogueGasPayerAbortsIf { gas_payer: signer::address_of(account) };
+    }
+
+    /// Same as `epilogue`, except the fee is charged to `gas_payer`.
+    spec epilogue_gas_payer(
+        account: signer,
+        gas_payer: address,
         _txn_sequence_number: u64,
         txn_gas_price: u64,
         txn_max_gas_units: u64,
         gas_units_remaining: u64
     ) {
+        // TODO: call burn_fee, complex aborts conditions.
+        pragma aborts_if_is_partial;
+
+        include EpilogueGasPayerAbortsIf;
+    }
+
+    spec schema EpilogueGasPayerAbortsIf {
         use aptos_framework::coin::{CoinStore};
         use aptos_framework::account::{Account};
         use aptos_framework::aptos_coin::{AptosCoin};
-        // TODO: call burn_fee, complex aborts conditions.
-        pragma aborts_if_is_partial;
+        account: signer;
+        gas_payer: address;
+        txn_gas_price: u64;
+        txn_max_gas_units: u64;
+        gas_units_remaining: u64;
 
         aborts_if !(txn_max_gas_units >= gas_units_remaining);
         let gas_used = txn_max_gas_units - gas_units_remaining;
@@ -138,15 +207,15 @@ spec aptos_framework::transaction_validation {
         aborts_if !(txn_gas_price * gas_used <= MAX_U64);
         let transaction_fee_amount = txn_gas_price * gas_used;
 
-        let addr = signer::address_of(account);
-        aborts_if !exists<CoinStore<AptosCoin>>(addr);
-        aborts_if !(global<CoinStore<AptosCoin>>(addr).coin.value >= transaction_fee_amount);
+        aborts_if !exists<CoinStore<AptosCoin>>(gas_payer);
+        aborts_if !(global<CoinStore<AptosCoin>>(gas_payer).coin.value >= transaction_fee_amount);
 
+        let addr = signer::address_of(account);
         aborts_if !exists<Account>(addr);
         aborts_if !(global<Account>(addr).sequence_number < MAX_U64);
 
-        let pre_balance = global<coin::CoinStore<AptosCoin>>(addr).coin.value;
-        let post balance = global<coin::CoinStore<AptosCoin>>(addr).coin.value;
+        let pre_balance = global<coin::CoinStore<AptosCoin>>(gas_payer).coin.value;
+        let post balance = global<coin::CoinStore<AptosCoin>>(gas_payer).coin.value;
         let pre_account = global<account::Account>(addr);
         let post account = global<account::Account>(addr);
         ensures balance == pre_balance - transaction_fee_amount;
@@ -173,6 +173,38 @@ pub enum EntryFunctionCall {
         coin_type: TypeTag,
     },
 
+    /// Approves a pending transaction of the multisig account. Withdraws the owner's rejection if there is one.
+    MultisigAccountApproveTransaction {
+        multisig_account: AccountAddress,
+        sequence_number: u64,
+    },
+
+    /// Creates a new multisig account owned by `owner` and `additional_owners`. The account is a resource account
+    /// derived from `owner` and `seed`, so nobody can sign for it with a private key.
+    MultisigAccountCreate {
+        additional_owners: Vec<AccountAddress>,
+        num_signatures_required: u64,
+        seed: Vec<u8>,
+    },
+
+    /// Proposes a new transaction for the multisig account to execute. The proposer implicitly approves it.
+    MultisigAccountCreateTransaction {
+        multisig_account: AccountAddress,
+        payload: Vec<u8>,
+    },
+
+    /// Rejects a pending transaction of the multisig account. Withdraws the owner's approval if there is one.
+    MultisigAccountRejectTransaction {
+        multisig_account: AccountAddress,
+        sequence_number: u64,
+    },
+
+    /// Removes the next pending transaction of the multisig account without executing it, once enough owners have
+    /// rejected it. The transaction after it becomes the next one to execute.
+    MultisigAccountRemoveRejectedTransaction {
+        multisig_account: AccountAddress,
+    },
+
     /// Creates a new resource account and rotates the authentication key to either
     /// the optional auth key if it is non-empty (though auth keys are 32-bytes)
     /// or the source accounts current auth key.
@@ -544,6 +576,26 @@ impl EntryFunctionCall {
                 amount,
             } => managed_coin_mint(coin_type, dst_addr, amount),
             ManagedCoinRegister { coin_type } => managed_coin_register(coin_type),
+            MultisigAccountApproveTransaction {
+                multisig_account,
+                sequence_number,
+            } => multisig_account_approve_transaction(multisig_account, sequence_number),
+            MultisigAccountCreate {
+                additional_owners,
+                num_signatures_required,
+                seed,
+            } => multisig_account_create(additional_owners, num_signatures_required, seed),
+            MultisigAccountCreateTransaction {
+                multisig_account,
+                payload,
+            } => multisig_account_create_transaction(multisig_account, payload),
+            MultisigAccountRejectTransaction {
+                multisig_account,
+                sequence_number,
+            } => multisig_account_reject_transaction(multisig_account, sequence_number),
+            MultisigAccountRemoveRejectedTransaction { multisig_account } => {
+                multisig_account_remove_rejected_transaction(multisig_account)
+            }
             ResourceAccountCreateResourceAccount {
                 seed,
                 optional_auth_key,
@@ -1126,6 +1178,116 @@ pub fn managed_coin_register(coin_type: TypeTag) -> TransactionPayload {
     ))
 }
 
+/// Approves a pending transaction of the multisig account. Withdraws the owner's rejection if there is one.
+pub fn multisig_account_approve_transaction(
+    multisig_account: AccountAddress,
+    sequence_number: u64,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("multisig_account").to_owned(),
+        ),
+        ident_str!("approve_transaction").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&multisig_account).unwrap(),
+            bcs::to_bytes(&sequence_number).unwrap(),
+        ],
+    ))
+}
+
+/// Creates a new multisig account owned by `owner` and `additional_owners`. The account is a resource account
+/// derived from `owner` and `seed`, so nobody can sign for it with a private key.
+pub fn multisig_account_create(
+    additional_owners: Vec<AccountAddress>,
+    num_signatures_required: u64,
+    seed: Vec<u8>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("multisig_account").to_owned(),
+        ),
+        ident_str!("create").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&additional_owners).unwrap(),
+            bcs::to_bytes(&num_signatures_required).unwrap(),
+            bcs::to_bytes(&seed).unwrap(),
+        ],
+    ))
+}
+
+/// Proposes a new transaction for the multisig account to execute. The proposer implicitly approves it.
+pub fn multisig_account_create_transaction(
+    multisig_account: AccountAddress,
+    payload: Vec<u8>,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("multisig_account").to_owned(),
+        ),
+        ident_str!("create_transaction").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&multisig_account).unwrap(),
+            bcs::to_bytes(&payload).unwrap(),
+        ],
+    ))
+}
+
+/// Rejects a pending transaction of the multisig account. Withdraws the owner's approval if there is one.
+pub fn multisig_account_reject_transaction(
+    multisig_account: AccountAddress,
+    sequence_number: u64,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("multisig_account").to_owned(),
+        ),
+        ident_str!("reject_transaction").to_owned(),
+        vec![],
+        vec![
+            bcs::to_bytes(&multisig_account).unwrap(),
+            bcs::to_bytes(&sequence_number).unwrap(),
+        ],
+    ))
+}
+
+/// Removes the next pending transaction of the multisig account without executing it, once enough owners have
+/// rejected it. The transaction after it becomes the next one to execute.
+pub fn multisig_account_remove_rejected_transaction(
+    multisig_account: AccountAddress,
+) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::new([
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 1,
+            ]),
+            ident_str!("multisig_account").to_owned(),
+        ),
+        ident_str!("remove_rejected_transaction").to_owned(),
+        vec![],
+        vec![bcs::to_bytes(&multisig_account).unwrap()],
+    ))
+}
+
 /// Creates a new resource account and rotates the authentication key to either
 /// the optional auth key if it is non-empty (though auth keys are 32-bytes)
 /// or the source accounts current auth key.
@@ -2312,6 +2474,71 @@ mod decoder {
         }
     }
 
+    pub fn multisig_account_approve_transaction(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::MultisigAccountApproveTransaction {
+                multisig_account: bcs::from_bytes(script.args().get(0)?).ok()?,
+                sequence_number: bcs::from_bytes(script.args().get(1)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn multisig_account_create(payload: &TransactionPayload) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::MultisigAccountCreate {
+                additional_owners: bcs::from_bytes(script.args().get(0)?).ok()?,
+                num_signatures_required: bcs::from_bytes(script.args().get(1)?).ok()?,
+                seed: bcs::from_bytes(script.args().get(2)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn multisig_account_create_transaction(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::MultisigAccountCreateTransaction {
+                multisig_account: bcs::from_bytes(script.args().get(0)?).ok()?,
+                payload: bcs::from_bytes(script.args().get(1)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn multisig_account_reject_transaction(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(EntryFunctionCall::MultisigAccountRejectTransaction {
+                multisig_account: bcs::from_bytes(script.args().get(0)?).ok()?,
+                sequence_number: bcs::from_bytes(script.args().get(1)?).ok()?,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn multisig_account_remove_rejected_transaction(
+        payload: &TransactionPayload,
+    ) -> Option<EntryFunctionCall> {
+        if let TransactionPayload::EntryFunction(script) = payload {
+            Some(
+                EntryFunctionCall::MultisigAccountRemoveRejectedTransaction {
+                    multisig_account: bcs::from_bytes(script.args().get(0)?).ok()?,
+                },
+            )
+        } else {
+            None
+        }
+    }
+
     pub fn resource_account_create_resource_account(
         payload: &TransactionPayload,
     ) -> Option<EntryFunctionCall> {
@@ -2965,6 +3192,26 @@ static SCRIPT_FUNCTION_DECODER_MAP: once_cell::sync::Lazy<EntryFunctionDecoderMa
             "managed_coin_register".to_string(),
             Box::new(decoder::managed_coin_register),
         );
+        map.insert(
+            "multisig_account_approve_transaction".to_string(),
+            Box::new(decoder::multisig_account_approve_transaction),
+        );
+        map.insert(
+            "multisig_account_create".to_string(),
+            Box::new(decoder::multisig_account_create),
+        );
+        map.insert(
+            "multisig_account_create_transaction".to_string(),
+            Box::new(decoder::multisig_account_create_transaction),
+        );
+        map.insert(
+            "multisig_account_reject_transaction".to_string(),
+            Box::new(decoder::multisig_account_reject_transaction),
+        );
+        map.insert(
+            "multisig_account_remove_rejected_transaction".to_string(),
+            Box::new(decoder::multisig_account_remove_rejected_transaction),
+        );
         map.insert(
             "resource_account_create_resource_account".to_string(),
             Box::new(decoder::resource_account_create_resource_account),
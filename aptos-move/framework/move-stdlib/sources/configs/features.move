@@ -69,6 +69,26 @@ module std::features {
         is_enabled(APTOS_STD_CHAIN_ID_NATIVES)
     }
 
+    /// Whether multisig accounts can be created and `Multisig` transaction payloads are accepted by the VM.
+    /// Lifetime: transient
+    const MULTISIG_ACCOUNTS: u64 = 5;
+
+    public fun get_multisig_accounts_feature(): u64 { MULTISIG_ACCOUNTS }
+
+    public fun multisig_accounts_enabled(): bool acquires Features {
+        is_enabled(MULTISIG_ACCOUNTS)
+    }
+
+    /// Whether a transaction's gas may be paid by a fee payer which signs it besides the sender.
+    /// Lifetime: transient
+    const FEE_PAYER: u64 = 6;
+
+    public fun get_fee_payer_feature(): u64 { FEE_PAYER }
+
+    public fun fee_payer_enabled(): bool acquires Features {
+        is_enabled(FEE_PAYER)
+    }
+
     // ============================================================================================
     // Feature Flag Implementation
 
@@ -11,13 +11,13 @@ use aptos_build_info::build_information;
 use aptos_config::config::StateSyncConfig;
 use aptos_config::{
     config::{
-        AptosDataClientConfig, BaseConfig, NetworkConfig, NodeConfig, PersistableConfig,
-        StorageServiceConfig,
+        AptosDataClientConfig, BaseConfig, NetworkConfig, NodeConfig, PeerMonitoringServiceConfig,
+        PersistableConfig, StorageServiceConfig,
     },
     network_id::NetworkId,
     utils::get_genesis_txn,
 };
-use aptos_data_client::aptosnet::AptosNetDataClient;
+use aptos_data_client::aptosnet::{AptosNetDataClient, LatencyMonitor};
 use aptos_fh_stream::runtime::bootstrap as bootstrap_fh_stream;
 use aptos_infallible::RwLock;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
@@ -28,11 +28,12 @@ use aptos_types::{
     on_chain_config::ON_CHAIN_CONFIG_REGISTRY, waypoint::Waypoint,
 };
 use aptos_vm::AptosVM;
-use aptosdb::AptosDB;
+use aptosdb::{db_verifier::BackgroundVerifier, AptosDB};
 use backup_service::start_backup_service;
 use clap::Parser;
-use consensus::consensus_provider::start_consensus;
+use consensus::consensus_provider::{start_consensus, start_consensus_observer};
 use consensus_notifications::ConsensusNotificationListener;
+use consensus_types::evidence::EvidenceReader;
 use data_streaming_service::{
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
@@ -46,6 +47,13 @@ use log_build_information::log_build_information;
 use mempool_notifications::MempoolNotificationSender;
 use network::application::storage::PeerMetadataStorage;
 use network_builder::builder::NetworkBuilder;
+use peer_monitoring_service_client::{
+    PeerMonitoringServiceClient, PeerMonitoringServiceMultiSender,
+    PeerMonitoringServiceNetworkSender,
+};
+use peer_monitoring_service_server::{
+    network::PeerMonitoringServiceNetworkEvents, PeerMonitoringServiceServer,
+};
 use rand::{rngs::StdRng, SeedableRng};
 use state_sync_driver::{
     driver_factory::{DriverFactory, StateSyncRuntimes},
@@ -61,7 +69,7 @@ use std::{
         Arc,
     },
     thread,
-    time::Instant,
+    time::{Duration, Instant},
 };
 use storage_interface::{state_view::LatestDbStateCheckpointView, DbReader, DbReaderWriter};
 use storage_service_client::{StorageServiceClient, StorageServiceMultiSender};
@@ -113,6 +121,12 @@ pub struct AptosNodeArgs {
     /// only commit a block when there is user transaction in mempool.
     #[clap(long, requires("test"))]
     lazy: bool,
+
+    /// Run an indexer alongside the node, backed by a SQLite database in the test directory
+    ///
+    /// Requires the node to be built with the `indexer` feature.
+    #[clap(long, requires("test"))]
+    with_indexer: bool,
 }
 
 impl AptosNodeArgs {
@@ -133,6 +147,7 @@ impl AptosNodeArgs {
                 self.test_dir,
                 self.random_ports,
                 self.lazy,
+                self.with_indexer,
                 &genesis_framework,
                 rng,
             )
@@ -168,8 +183,10 @@ pub struct AptosHandle {
     _api: Option<Runtime>,
     _backup: Runtime,
     _consensus_runtime: Option<Runtime>,
+    _db_verifier: Option<BackgroundVerifier>,
     _mempool: Runtime,
     _network_runtimes: Vec<Runtime>,
+    _peer_monitoring_service_runtime: Runtime,
     _fh_stream: Option<Runtime>,
     _index_runtime: Option<Runtime>,
     _state_sync_runtimes: StateSyncRuntimes,
@@ -248,12 +265,19 @@ pub fn load_test_environment<R>(
     test_dir: Option<PathBuf>,
     random_ports: bool,
     lazy: bool,
+    with_indexer: bool,
     framework: &ReleaseBundle,
     rng: R,
 ) -> anyhow::Result<()>
 where
     R: ::rand::RngCore + ::rand::CryptoRng,
 {
+    if with_indexer && !cfg!(feature = "indexer") {
+        return Err(anyhow!(
+            "Running an indexer requires aptos-node to be built with the `indexer` feature"
+        ));
+    }
+
     // If there wasn't a testnet directory given, create a temp one
     let test_dir = if let Some(test_dir) = test_dir {
         test_dir
@@ -311,6 +335,12 @@ where
         if lazy {
             template.consensus.quorum_store_poll_count = u64::MAX;
         }
+        if with_indexer {
+            template.storage.enable_indexer = true;
+            template.indexer.enabled = true;
+            template.indexer.sqlite_path =
+                Some(test_dir.join("indexer.sqlite").display().to_string());
+        }
 
         // Build genesis and validator node
         let builder = aptos_genesis::builder::Builder::new(&test_dir, framework.clone())?
@@ -338,7 +368,13 @@ where
             genesis_waypoint.to_string().as_bytes(),
         )?;
 
-        validators[0].config.clone()
+        if with_indexer {
+            // Reload the config from disk, so the indexer config gets its defaults filled in
+            NodeConfig::load(&validator_config_path)
+                .map_err(|err| anyhow!("Unable to load config: {}", err))?
+        } else {
+            validators[0].config.clone()
+        }
     };
 
     // Prepare log file since we cannot automatically route logs to stderr
@@ -359,6 +395,9 @@ where
         "\tAptosnet Fullnode network endpoint: {}",
         &config.full_node_networks[0].listen_address
     );
+    if let Some(sqlite_path) = &config.indexer.sqlite_path {
+        println!("\tIndexer database: {}", sqlite_path);
+    }
     if lazy {
         println!("\tLazy mode is enabled");
     }
@@ -389,12 +428,17 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
         NetworkId,
         storage_service_client::StorageServiceNetworkSender,
     >,
+    peer_monitoring_service_client_network_handles: HashMap<
+        NetworkId,
+        PeerMonitoringServiceNetworkSender,
+    >,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
     mempool_notifier: M,
     consensus_listener: ConsensusNotificationListener,
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
+    consensus_observer_executing: Option<Arc<AtomicBool>>,
 ) -> anyhow::Result<StateSyncRuntimes> {
     // Start the state sync storage service
     let storage_service_runtime = setup_state_sync_storage_service(
@@ -409,6 +453,7 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
         node_config.state_sync.aptos_data_client,
         node_config.base.clone(),
         storage_service_client_network_handles,
+        peer_monitoring_service_client_network_handles,
         peer_metadata_storage,
     )?;
 
@@ -433,6 +478,7 @@ fn create_state_sync_runtimes<M: MempoolNotificationSender + 'static>(
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
+        consensus_observer_executing,
     );
 
     // Create and return the new state sync handle
@@ -479,11 +525,18 @@ fn setup_aptos_data_client(
     aptos_data_client_config: AptosDataClientConfig,
     base_config: BaseConfig,
     network_handles: HashMap<NetworkId, storage_service_client::StorageServiceNetworkSender>,
+    peer_monitoring_network_handles: HashMap<NetworkId, PeerMonitoringServiceNetworkSender>,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
 ) -> anyhow::Result<(AptosNetDataClient, Runtime)> {
     // Combine all storage service client handles
     let network_client = StorageServiceClient::new(
         StorageServiceMultiSender::new(network_handles),
+        peer_metadata_storage.clone(),
+    );
+
+    // Combine all peer monitoring service client handles
+    let peer_monitoring_client = PeerMonitoringServiceClient::new(
+        PeerMonitoringServiceMultiSender::new(peer_monitoring_network_handles),
         peer_metadata_storage,
     );
 
@@ -510,9 +563,50 @@ fn setup_aptos_data_client(
     );
     aptos_data_client_runtime.spawn(data_summary_poller.start_poller());
 
+    // Spawn the latency monitor (if latency aware peer selection is enabled)
+    if aptos_data_client_config.latency_aware_peer_selection {
+        let latency_monitor = LatencyMonitor::new(
+            aptos_data_client.clone(),
+            peer_monitoring_client,
+            Some(aptos_data_client_runtime.handle().clone()),
+        );
+        aptos_data_client_runtime.spawn(latency_monitor.start_monitor());
+    }
+
     Ok((aptos_data_client, aptos_data_client_runtime))
 }
 
+fn setup_peer_monitoring_service(
+    config: PeerMonitoringServiceConfig,
+    network_handles: Vec<PeerMonitoringServiceNetworkEvents>,
+    peer_metadata_storage: Arc<PeerMetadataStorage>,
+) -> anyhow::Result<Runtime> {
+    // Create a new peer monitoring service runtime
+    let peer_monitoring_service_runtime = Builder::new_multi_thread()
+        .thread_name_fn(|| {
+            static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
+            let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
+            format!("peer-mon-{}", id)
+        })
+        .disable_lifo_slot()
+        .enable_all()
+        .build()
+        .map_err(|err| anyhow!("Failed to start peer monitoring service {}", err))?;
+
+    // Spawn all peer monitoring service servers on the same runtime
+    for events in network_handles {
+        let service = PeerMonitoringServiceServer::new(
+            config.clone(),
+            peer_monitoring_service_runtime.handle().clone(),
+            events,
+            peer_metadata_storage.clone(),
+        );
+        peer_monitoring_service_runtime.spawn(service.start());
+    }
+
+    Ok(peer_monitoring_service_runtime)
+}
+
 fn setup_state_sync_storage_service(
     config: StorageServiceConfig,
     network_handles: Vec<StorageServiceNetworkEvents>,
@@ -576,10 +670,16 @@ pub fn setup_environment(
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
     logger_filter_update_job: Option<LoggerFilterUpdater>,
 ) -> anyhow::Result<AptosHandle> {
-    // Start the node inspection service
+    // Start the node inspection service. The evidence recorded by consensus is exposed once
+    // consensus starts.
+    let evidence_reader = EvidenceReader::new();
     let node_config_clone = node_config.clone();
+    let evidence_reader_clone = evidence_reader.clone();
     thread::spawn(move || {
-        inspection_service::inspection_service::start_inspection_service(node_config_clone)
+        inspection_service::inspection_service::start_inspection_service(
+            node_config_clone,
+            evidence_reader_clone,
+        )
     });
 
     // Open the database
@@ -600,6 +700,13 @@ pub fn setup_environment(
         node_config.storage.backup_service_address,
         Arc::clone(&aptos_db),
     );
+    let db_verifier = node_config.storage.enable_background_verifier.then(|| {
+        aptos_db
+            .get_db_verifier()
+            .spawn_background_verifier(Duration::from_millis(
+                node_config.storage.background_verifier_interval_ms,
+            ))
+    });
 
     let genesis_waypoint = node_config.base.waypoint.genesis_waypoint();
     // if there's genesis txn and waypoint, commit it if the result matches.
@@ -632,8 +739,11 @@ pub fn setup_environment(
     let mut network_runtimes = vec![];
     let mut mempool_network_handles = vec![];
     let mut consensus_network_handles = None;
+    let mut consensus_observer_network_handles = None;
     let mut storage_service_server_network_handles = vec![];
     let mut storage_service_client_network_handles = HashMap::new();
+    let mut peer_monitoring_service_server_network_handles = vec![];
+    let mut peer_monitoring_service_client_network_handles = HashMap::new();
 
     // Create an event subscription service so that components can be notified of events and reconfigs
     let mut event_subscription_service = EventSubscriptionService::new(
@@ -650,6 +760,35 @@ pub fn setup_environment(
         None
     };
 
+    // Consensus observers are fed by the validators publishing their ordered blocks to the VFNs,
+    // so an observer only runs on a full node connected to the VFN network.
+    let consensus_observer_config = &node_config.consensus.observer;
+    let consensus_publisher_enabled =
+        node_config.base.role.is_validator() && consensus_observer_config.publisher_enabled;
+    let consensus_observer_enabled = !node_config.base.role.is_validator()
+        && consensus_observer_config.observer_enabled
+        && node_config
+            .full_node_networks
+            .iter()
+            .any(|network_config| network_config.network_id == NetworkId::Vfn);
+    // Shared by the observer and state sync, which continuously syncs while the observer
+    // doesn't execute the blocks (e.g. it has no publisher to follow).
+    let consensus_observer_executing =
+        consensus_observer_enabled.then(|| Arc::new(AtomicBool::new(false)));
+    let consensus_observer_reconfig_subscription = if consensus_observer_enabled {
+        Some(event_subscription_service.subscribe_to_reconfigurations()?)
+    } else {
+        None
+    };
+
+    // Create an API subscription for all events, so that clients can stream them.
+    let api_event_subscription = if node_config.api.enabled && node_config.api.event_stream_enabled
+    {
+        Some(event_subscription_service.subscribe_to_all_events()?)
+    } else {
+        None
+    };
+
     // Gather all network configs into a single vector.
     let mut network_configs: Vec<&NetworkConfig> = node_config.full_node_networks.iter().collect();
     if let Some(network_config) = node_config.validator_network.as_ref() {
@@ -745,6 +884,18 @@ pub fn setup_environment(
             network_builder.add_client(&storage_service_client::network_endpoint_config());
         storage_service_client_network_handles.insert(network_id, storage_service_sender);
 
+        // Register the peer monitoring service (both client and server) with Network
+        let peer_monitoring_service_events = network_builder.add_service(
+            &peer_monitoring_service_server::network::network_endpoint_config(
+                node_config.peer_monitoring_service.clone(),
+            ),
+        );
+        peer_monitoring_service_server_network_handles.push(peer_monitoring_service_events);
+        let peer_monitoring_service_sender =
+            network_builder.add_client(&peer_monitoring_service_client::network_endpoint_config());
+        peer_monitoring_service_client_network_handles
+            .insert(network_id, peer_monitoring_service_sender);
+
         // Create the endpoints to connect the Network to mempool.
         let (mempool_sender, mempool_events) = network_builder.add_p2p_service(
             &aptos_mempool::network::network_endpoint_config(MEMPOOL_NETWORK_CHANNEL_BUFFER_SIZE),
@@ -765,6 +916,17 @@ pub fn setup_environment(
             );
         }
 
+        // Register the consensus observer service on the network between validators and VFNs.
+        if network_id == NetworkId::Vfn
+            && (consensus_publisher_enabled || consensus_observer_enabled)
+        {
+            consensus_observer_network_handles = Some(network_builder.add_p2p_service(
+                &consensus::consensus_observer::network::network_endpoint_config(
+                    consensus_observer_config.max_network_channel_size,
+                ),
+            ));
+        }
+
         let network_context = network_builder.network_context();
         network_builder.build(runtime.handle().clone());
         network_builder.start();
@@ -786,17 +948,26 @@ pub fn setup_environment(
                 .commit_notification_timeout_ms,
         );
 
+    // Start the peer monitoring service
+    let peer_monitoring_service_runtime = setup_peer_monitoring_service(
+        node_config.peer_monitoring_service.clone(),
+        peer_monitoring_service_server_network_handles,
+        peer_metadata_storage.clone(),
+    )?;
+
     // Create the state sync runtimes
     let state_sync_runtimes = create_state_sync_runtimes(
         &node_config,
         storage_service_server_network_handles,
         storage_service_client_network_handles,
+        peer_monitoring_service_client_network_handles,
         peer_metadata_storage.clone(),
         mempool_notifier,
         consensus_listener,
         genesis_waypoint,
         event_subscription_service,
         db_rw.clone(),
+        consensus_observer_executing.clone(),
     )?;
 
     let (mp_client_sender, mp_client_events) = mpsc::channel(AC_SMP_CHANNEL_BUFFER_SIZE);
@@ -807,6 +978,8 @@ pub fn setup_environment(
             chain_id,
             aptos_db.clone(),
             mp_client_sender.clone(),
+            api_event_subscription,
+            evidence_reader.clone(),
         )?)
     } else {
         None
@@ -840,10 +1013,6 @@ pub fn setup_environment(
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
-    assert!(
-        !node_config.consensus.use_quorum_store,
-        "QuorumStore is not yet implemented"
-    );
     assert_ne!(
         node_config.consensus.use_quorum_store,
         node_config.mempool.shared_mempool_validator_broadcast,
@@ -874,16 +1043,45 @@ pub fn setup_environment(
             consensus_reconfig_subscription
                 .expect("Consensus requires a reconfiguration subscription!"),
             peer_metadata_storage,
+            consensus_observer_network_handles,
+            evidence_reader,
         ));
         debug!("Consensus started in {} ms", instant.elapsed().as_millis());
+    } else if let Some((observer_network_sender, observer_network_events)) =
+        consensus_observer_network_handles
+    {
+        // The observer starts from the state synced by state sync, as consensus does.
+        debug!("Wait until state sync is initialized");
+        state_sync_runtimes.block_until_initialized();
+        debug!("State sync initialization complete.");
+
+        instant = Instant::now();
+        consensus_runtime = Some(start_consensus_observer(
+            &node_config,
+            observer_network_sender,
+            observer_network_events,
+            Arc::new(consensus_notifier),
+            consensus_to_mempool_sender,
+            db_rw,
+            consensus_observer_reconfig_subscription
+                .expect("Consensus observer requires a reconfiguration subscription!"),
+            consensus_observer_executing
+                .expect("Consensus observer requires an executing flag shared with state sync!"),
+        ));
+        debug!(
+            "Consensus observer started in {} ms",
+            instant.elapsed().as_millis()
+        );
     }
 
     Ok(AptosHandle {
         _api: api_runtime,
         _backup: backup_service,
         _consensus_runtime: consensus_runtime,
+        _db_verifier: db_verifier,
         _mempool: mempool,
         _network_runtimes: network_runtimes,
+        _peer_monitoring_service_runtime: peer_monitoring_service_runtime,
         _index_runtime: index_runtime,
         _fh_stream: sf_runtime,
         _state_sync_runtimes: state_sync_runtimes,
@@ -20,6 +20,8 @@ pub struct ApiConfig {
     pub content_length_limit: Option<u64>,
     #[serde(default = "default_disabled")]
     pub failpoints_enabled: bool,
+    #[serde(default = "default_disabled")]
+    pub consensus_evidence_enabled: bool,
     #[serde(default = "default_enabled")]
     pub json_output_enabled: bool,
     #[serde(default = "default_enabled")]
@@ -30,6 +32,10 @@ pub struct ApiConfig {
     pub transaction_submission_enabled: bool,
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    #[serde(default = "default_enabled")]
+    pub view_function_enabled: bool,
+    #[serde(default = "default_enabled")]
+    pub event_stream_enabled: bool,
 
     pub max_submit_transaction_batch_size: usize,
 
@@ -38,6 +44,14 @@ pub struct ApiConfig {
     pub max_events_page_size: u16,
     pub max_account_resources_page_size: u16,
     pub max_account_modules_page_size: u16,
+
+    /// Maximum gas units a single view function call may consume
+    pub max_gas_view_function: u64,
+
+    /// Maximum number of event streams served at once
+    pub max_event_streams: usize,
+    /// Maximum number of versions behind the latest version an event stream by type may start at
+    pub max_event_stream_lookback_versions: u64,
 }
 
 pub const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -47,6 +61,9 @@ pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 10;
 pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 pub const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 pub const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
+pub const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000;
+pub const DEFAULT_MAX_EVENT_STREAMS: usize = 100;
+pub const DEFAULT_MAX_EVENT_STREAM_LOOKBACK_VERSIONS: u64 = 100_000;
 
 fn default_enabled() -> bool {
     true
@@ -67,16 +84,22 @@ impl Default for ApiConfig {
             tls_key_path: None,
             content_length_limit: None,
             failpoints_enabled: default_disabled(),
+            consensus_evidence_enabled: default_disabled(),
             bcs_output_enabled: default_enabled(),
             json_output_enabled: default_enabled(),
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            view_function_enabled: default_enabled(),
+            event_stream_enabled: default_enabled(),
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
+            max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            max_event_streams: DEFAULT_MAX_EVENT_STREAMS,
+            max_event_stream_lookback_versions: DEFAULT_MAX_EVENT_STREAM_LOOKBACK_VERSIONS,
         }
     }
 }
@@ -32,6 +32,8 @@ pub struct ConsensusConfig {
     // the period = (poll_count - 1) * 30ms
     pub quorum_store_poll_count: u64,
     pub intra_consensus_channel_buffer_size: usize,
+    pub quorum_store: QuorumStoreConfig,
+    pub observer: ConsensusObserverConfig,
 
     // Used to decide if backoff is needed.
     // must match one of the CHAIN_HEALTH_WINDOW_SIZES values.
@@ -47,6 +49,75 @@ pub struct ChainHealthBackoffValues {
     pub max_sending_block_bytes_override: u64,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuorumStoreConfig {
+    // How often a new batch is pulled from mempool and broadcast (in milliseconds)
+    pub batch_generation_poll_interval_ms: u64,
+    pub max_batch_txns: u64,
+    pub max_batch_bytes: u64,
+    // Number of rounds after the last committed round that a new batch remains valid for
+    pub batch_expiry_round_gap: u64,
+    // Timeout for fetching a missing batch from a single peer (in milliseconds)
+    pub batch_request_timeout_ms: u64,
+    // Max number of batches and bytes of a single author stored at once, so that a peer can't
+    // fill the storage of the others with batches
+    pub max_batches_per_author: u64,
+    pub max_bytes_per_author: u64,
+    pub channel_size: usize,
+}
+
+impl Default for QuorumStoreConfig {
+    fn default() -> QuorumStoreConfig {
+        QuorumStoreConfig {
+            batch_generation_poll_interval_ms: 25,
+            max_batch_txns: 250,
+            max_batch_bytes: 100 * 1024, // 100 KB
+            batch_expiry_round_gap: 100,
+            batch_request_timeout_ms: 1000,
+            max_batches_per_author: 2000,
+            max_bytes_per_author: 200 * 1024 * 1024, // 200 MB
+            channel_size: 100,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusObserverConfig {
+    // Whether a validator pushes its ordered blocks and commit decisions to subscribed full nodes
+    pub publisher_enabled: bool,
+    // Whether a full node subscribes to a validator and executes the blocks it pushes, instead
+    // of continuously syncing with state sync
+    pub observer_enabled: bool,
+    pub max_subscribers: usize,
+    // Max number of ordered blocks an observer holds while waiting for their commit decision
+    pub max_pending_blocks: usize,
+    pub max_network_channel_size: usize,
+    // Timeout for the subscription request of an observer (in milliseconds)
+    pub network_request_timeout_ms: u64,
+    // The interval at which an observer checks the health of its subscription (in milliseconds)
+    pub progress_check_interval_ms: u64,
+    // An observer subscribes to another validator if it hasn't heard from the current one for
+    // this long (in milliseconds)
+    pub subscription_timeout_ms: u64,
+}
+
+impl Default for ConsensusObserverConfig {
+    fn default() -> ConsensusObserverConfig {
+        ConsensusObserverConfig {
+            publisher_enabled: false,
+            observer_enabled: false,
+            max_subscribers: 10,
+            max_pending_blocks: 100,
+            max_network_channel_size: 1000,
+            network_request_timeout_ms: 1000,
+            progress_check_interval_ms: 1000,
+            subscription_timeout_ms: 10_000,
+        }
+    }
+}
+
 impl Default for ConsensusConfig {
     fn default() -> ConsensusConfig {
         ConsensusConfig {
@@ -72,6 +143,8 @@ impl Default for ConsensusConfig {
             quorum_store_pull_timeout_ms: 1000,
             quorum_store_poll_count: 10,
             intra_consensus_channel_buffer_size: 10,
+            quorum_store: QuorumStoreConfig::default(),
+            observer: ConsensusObserverConfig::default(),
 
             window_for_chain_health: 100,
             chain_health_backoff: vec![
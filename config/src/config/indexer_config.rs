@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 
 pub const DEFAULT_BATCH_SIZE: u16 = 500;
 pub const DEFAULT_FETCH_TASKS: u8 = 5;
@@ -21,6 +22,12 @@ pub struct IndexerConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub postgres_uri: Option<String>,
 
+    /// Path of a SQLite database file to use instead of Postgres, ex: "/opt/aptos/indexer.sqlite"
+    /// The file is created if it doesn't exist. Takes precedence over `postgres_uri`.
+    /// Alternatively can set the `INDEXER_SQLITE_PATH` env var
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sqlite_path: Option<String>,
+
     /// The specific processor that it will run, ex: "token_processor"
     /// Alternatively can set the `PROCESSOR_NAME` env var
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -69,6 +76,10 @@ pub struct IndexerConfig {
     /// Which address does the ans contract live at. Only available for token_processor. If null, disable ANS indexing
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ans_contract_address: Option<String>,
+
+    /// If set, serves the indexed data over HTTP at this address, ex: "127.0.0.1:8090"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_server_address: Option<SocketAddr>,
 }
 
 pub fn env_or_default<T: std::str::FromStr>(
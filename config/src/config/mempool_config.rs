@@ -27,6 +27,8 @@ pub struct MempoolConfig {
     pub system_transaction_gc_interval_ms: u64,
     pub shared_mempool_validator_broadcast: bool,
     pub broadcast_buckets: Vec<u64>,
+    // persist accepted transactions to disk so they survive a node restart
+    pub journal_enabled: bool,
 }
 
 impl Default for MempoolConfig {
@@ -48,6 +50,7 @@ impl Default for MempoolConfig {
             system_transaction_gc_interval_ms: 60_000,
             shared_mempool_validator_broadcast: true,
             broadcast_buckets: DEFAULT_BROADCAST_BUCKETS.to_vec(),
+            journal_enabled: false,
         }
     }
 }
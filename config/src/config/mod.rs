@@ -320,11 +320,18 @@ impl NodeConfig {
             return Ok(self);
         }
 
-        self.indexer.postgres_uri = env_or_default(
-            "INDEXER_DATABASE_URL",
-            self.indexer.postgres_uri,
-            must_be_set("postgres_uri", "INDEXER_DATABASE_URL"),
-        );
+        self.indexer.sqlite_path = std::env::var("INDEXER_SQLITE_PATH")
+            .ok()
+            .or(self.indexer.sqlite_path);
+
+        // A Postgres uri is only required if the indexer doesn't use SQLite
+        if self.indexer.sqlite_path.is_none() {
+            self.indexer.postgres_uri = env_or_default(
+                "INDEXER_DATABASE_URL",
+                self.indexer.postgres_uri,
+                must_be_set("postgres_uri", "INDEXER_DATABASE_URL"),
+            );
+        }
 
         self.indexer.processor = env_or_default(
             "PROCESSOR_NAME",
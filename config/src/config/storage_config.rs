@@ -88,10 +88,18 @@ pub struct StorageConfig {
     pub max_num_nodes_per_lru_cache_shard: usize,
     /// Rocksdb-specific configurations
     pub rocksdb_configs: RocksdbConfigs,
-    /// Try to enable the internal indexer. The indexer expects to have seen all transactions
-    /// since genesis. To recover operation after data loss, or to bootstrap a node in fast sync
-    /// mode, the indexer db needs to be copied in from another node.
+    /// Try to enable the internal indexer, which indexes table info, events by type and
+    /// transactions by entry function. The indexer expects to have seen all transactions since
+    /// genesis. To recover operation after data loss, or to bootstrap a node in fast sync mode, the
+    /// indexer db needs to be copied in from another node.
     pub enable_indexer: bool,
+    /// Keep verifying newly committed ledger data in place in the background (the same checks as
+    /// `db-tool verify`, except for the state merkle tree). Inconsistencies are logged and counted
+    /// by the `aptos_storage_verifier_inconsistencies` metric.
+    pub enable_background_verifier: bool,
+    /// How long the background verifier waits before checking for newly committed versions once
+    /// it has caught up, or before retrying after failing to read the DB.
+    pub background_verifier_interval_ms: u64,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -100,6 +108,7 @@ pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
         prune_window: 0,
         batch_size: 0,
         user_pruning_window_offset: 0,
+        enable_archive: false,
     },
     state_merkle_pruner_config: StateMerklePrunerConfig {
         enable: false,
@@ -129,6 +138,12 @@ pub struct LedgerPrunerConfig {
     pub batch_size: usize,
     /// The offset for user pruning window to adjust
     pub user_pruning_window_offset: u64,
+    /// Instead of deleting pruned ledger data, move it into compressed, immutable segment files
+    /// under `<storage dir>/ledger_archive`, from which it's still served to readers. Each pruning
+    /// batch becomes one segment, so a larger `batch_size` is advisable when archiving. The
+    /// directory can be mounted on cheaper disk. The lookup indices (by hash, account and event
+    /// key) stay in the ledger db. State values are not archived.
+    pub enable_archive: bool,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -185,6 +200,7 @@ impl Default for LedgerPrunerConfig {
             prune_window: 150_000_000,
             batch_size: 500,
             user_pruning_window_offset: 200_000,
+            enable_archive: false,
         }
     }
 }
@@ -239,6 +255,8 @@ impl Default for StorageConfig {
             data_dir: PathBuf::from("/opt/aptos/data"),
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
+            enable_background_verifier: false,
+            background_verifier_interval_ms: 10_000,
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         }
@@ -20,6 +20,7 @@ use aptos_types::{
     block_info::BlockInfo,
     ledger_info::{generate_ledger_info_with_sig, LedgerInfo},
     test_helpers::transaction_test_helpers::get_test_signed_txn,
+    transaction::SignedTransaction,
     validator_signer::{proptests, ValidatorSigner},
 };
 use proptest::prelude::*;
@@ -256,13 +257,15 @@ pub fn certificate_for_genesis() -> QuorumCert {
     )
 }
 
-pub fn random_payload(count: usize) -> Payload {
+pub fn random_txns(count: usize) -> Vec<SignedTransaction> {
     let address = AccountAddress::random();
     let private_key = Ed25519PrivateKey::generate_for_testing();
     let public_key = private_key.public_key();
-    Payload::DirectMempool(
-        (0..count)
-            .map(|i| get_test_signed_txn(address, i as u64, &private_key, public_key.clone(), None))
-            .collect(),
-    )
+    (0..count)
+        .map(|i| get_test_signed_txn(address, i as u64, &private_key, public_key.clone(), None))
+        .collect()
+}
+
+pub fn random_payload(count: usize) -> Payload {
+    Payload::DirectMempool(random_txns(count))
 }
@@ -1,11 +1,16 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::proof_of_store::ProofOfStore;
+use anyhow::ensure;
 use aptos_crypto::HashValue;
-use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use aptos_types::{
+    account_address::AccountAddress, transaction::SignedTransaction,
+    validator_verifier::ValidatorVerifier,
+};
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::{fmt, fmt::Write};
+use std::{collections::HashSet, fmt, fmt::Write};
 
 /// The round of a block is a consensus-internal counter, which starts with 0 and increases
 /// monotonically. It is used for the protocol safety and liveness (please see the detailed
@@ -37,6 +42,9 @@ pub struct RejectedTransactionSummary {
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub enum Payload {
     DirectMempool(Vec<SignedTransaction>),
+    /// Digests of batches disseminated by the quorum store, each with a proof that a quorum of
+    /// validators stores the batch.
+    InQuorumStore(Vec<ProofOfStore>),
 }
 
 impl Payload {
@@ -47,12 +55,17 @@ impl Payload {
     pub fn len(&self) -> usize {
         match self {
             Payload::DirectMempool(txns) => txns.len(),
+            Payload::InQuorumStore(proofs) => proofs
+                .iter()
+                .map(|proof| proof.info().num_txns as usize)
+                .sum(),
         }
     }
 
     pub fn is_empty(&self) -> bool {
         match self {
             Payload::DirectMempool(txns) => txns.is_empty(),
+            Payload::InQuorumStore(proofs) => proofs.is_empty(),
         }
     }
 
@@ -64,18 +77,30 @@ impl Payload {
                 .with_min_len(100)
                 .map(|txn| txn.raw_txn_bytes_len())
                 .sum(),
+            Payload::InQuorumStore(proofs) => proofs
+                .iter()
+                .map(|proof| proof.info().num_bytes as usize)
+                .sum(),
         }
     }
-}
-
-// TODO: What I really want is an iterator that isn't necessarily a vector (e.g., read lazily from RocksDB). This doesn't seem like the way.
-impl IntoIterator for Payload {
-    type Item = SignedTransaction;
-    type IntoIter = std::vec::IntoIter<Self::Item>;
 
-    fn into_iter(self) -> Self::IntoIter {
+    /// Verifies the proofs of store carried by the payload, transactions carried directly are
+    /// verified at execution.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
         match self {
-            Payload::DirectMempool(txns) => txns.into_iter(),
+            Payload::DirectMempool(_) => Ok(()),
+            Payload::InQuorumStore(proofs) => {
+                let mut digests = HashSet::new();
+                for proof in proofs {
+                    ensure!(
+                        digests.insert(*proof.digest()),
+                        "Duplicate batch {} in payload",
+                        proof.digest()
+                    );
+                    proof.verify(validator)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -86,6 +111,14 @@ impl fmt::Display for Payload {
             Payload::DirectMempool(txns) => {
                 write!(f, "InMemory txns: {}", txns.len())
             }
+            Payload::InQuorumStore(proofs) => {
+                write!(
+                    f,
+                    "InQuorumStore batches: {}, txns: {}",
+                    proofs.len(),
+                    self.len()
+                )
+            }
         }
     }
 }
@@ -94,27 +127,39 @@ impl fmt::Display for Payload {
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
 pub enum PayloadFilter {
     DirectMempool(Vec<TransactionSummary>),
+    InQuorumStore(HashSet<HashValue>),
+    Empty,
 }
 
 impl From<&Vec<&Payload>> for PayloadFilter {
     fn from(exclude_payloads: &Vec<&Payload>) -> Self {
-        if exclude_payloads.is_empty() {
-            return PayloadFilter::DirectMempool(vec![]);
-        }
-        match exclude_payloads.first().unwrap() {
-            Payload::DirectMempool(_) => {
+        // Empty payloads carry nothing to exclude, and are also what reconfiguration suffixes
+        // carry regardless of the quorum store in use.
+        match exclude_payloads.iter().find(|payload| !payload.is_empty()) {
+            None => PayloadFilter::Empty,
+            Some(Payload::DirectMempool(_)) => {
                 let mut exclude_txns = vec![];
                 for payload in exclude_payloads {
-                    let Payload::DirectMempool(txns) = payload;
-                    for txn in txns {
-                        exclude_txns.push(TransactionSummary {
-                            sender: txn.sender(),
-                            sequence_number: txn.sequence_number(),
-                        });
+                    if let Payload::DirectMempool(txns) = payload {
+                        for txn in txns {
+                            exclude_txns.push(TransactionSummary {
+                                sender: txn.sender(),
+                                sequence_number: txn.sequence_number(),
+                            });
+                        }
                     }
                 }
                 PayloadFilter::DirectMempool(exclude_txns)
             }
+            Some(Payload::InQuorumStore(_)) => {
+                let mut exclude_digests = HashSet::new();
+                for payload in exclude_payloads {
+                    if let Payload::InQuorumStore(proofs) = payload {
+                        exclude_digests.extend(proofs.iter().map(|proof| *proof.digest()));
+                    }
+                }
+                PayloadFilter::InQuorumStore(exclude_digests)
+            }
         }
     }
 }
@@ -129,6 +174,16 @@ impl fmt::Display for PayloadFilter {
                 }
                 write!(f, "{}", txns_str)
             }
+            PayloadFilter::InQuorumStore(excluded_digests) => {
+                let mut digests_str = "".to_string();
+                for digest in excluded_digests.iter() {
+                    write!(digests_str, "{} ", digest)?;
+                }
+                write!(f, "{}", digests_str)
+            }
+            PayloadFilter::Empty => {
+                write!(f, "Empty filter")
+            }
         }
     }
 }
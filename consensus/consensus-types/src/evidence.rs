@@ -0,0 +1,282 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    block::Block,
+    common::{Author, Round},
+    vote::Vote,
+};
+use anyhow::{bail, ensure, Context};
+use aptos_crypto::hash::CryptoHash;
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_infallible::RwLock;
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Display, Formatter},
+    sync::Arc,
+};
+
+/// Proof of a validator signing two conflicting messages for the same round, which an honest
+/// validator never does. Both messages carry the signature of the validator, so the evidence can
+/// be checked by anyone knowing the validator set of the epoch.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
+pub enum Evidence {
+    /// Two votes of the same author for different ledger infos in the same round.
+    DoubleVote { first: Vote, second: Vote },
+    /// Two different blocks proposed by the same author in the same round.
+    DoubleProposal { first: Block, second: Block },
+}
+
+impl Display for Evidence {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self.author() {
+            Some(author) => write!(f, "[{} by {}", self.name(), author)?,
+            None => write!(f, "[{} without author", self.name())?,
+        }
+        write!(f, " at epoch {} round {}]", self.epoch(), self.round())
+    }
+}
+
+impl Evidence {
+    /// Evidence type in string
+    pub fn name(&self) -> &'static str {
+        match self {
+            Evidence::DoubleVote { .. } => "DoubleVote",
+            Evidence::DoubleProposal { .. } => "DoubleProposal",
+        }
+    }
+
+    /// The validator that signed the conflicting messages. Evidence read back from storage or
+    /// received from others isn't verified, so blocks without an author are possible.
+    pub fn author(&self) -> Option<Author> {
+        match self {
+            Evidence::DoubleVote { first, .. } => Some(first.author()),
+            Evidence::DoubleProposal { first, .. } => first.author(),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Evidence::DoubleVote { first, .. } => first.epoch(),
+            Evidence::DoubleProposal { first, .. } => first.epoch(),
+        }
+    }
+
+    pub fn round(&self) -> Round {
+        match self {
+            Evidence::DoubleVote { first, .. } => first.vote_data().proposed().round(),
+            Evidence::DoubleProposal { first, .. } => first.round(),
+        }
+    }
+
+    /// Verifies that the messages conflict and are both signed by the same validator of the
+    /// given validator set, i.e. the validator set of the epoch of the messages.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        match self {
+            Evidence::DoubleVote { first, second } => {
+                ensure!(
+                    first.author() == second.author(),
+                    "Votes of different authors {} and {}",
+                    first.author(),
+                    second.author()
+                );
+                ensure!(
+                    (first.epoch(), first.vote_data().proposed().round())
+                        == (second.epoch(), second.vote_data().proposed().round()),
+                    "Votes for different rounds: {} and {}",
+                    first,
+                    second
+                );
+                ensure!(
+                    first.ledger_info().hash() != second.ledger_info().hash(),
+                    "Votes for the same ledger info"
+                );
+                first.verify(validator).context("Invalid first vote")?;
+                second.verify(validator).context("Invalid second vote")?;
+            }
+            Evidence::DoubleProposal { first, second } => {
+                let (first_author, second_author) = match (first.author(), second.author()) {
+                    (Some(first_author), Some(second_author)) => (first_author, second_author),
+                    _ => bail!("Blocks without author aren't proposals"),
+                };
+                ensure!(
+                    first_author == second_author,
+                    "Proposals of different authors {} and {}",
+                    first_author,
+                    second_author
+                );
+                ensure!(
+                    (first.epoch(), first.round()) == (second.epoch(), second.round()),
+                    "Proposals for different rounds: {} and {}",
+                    first,
+                    second
+                );
+                ensure!(first.id() != second.id(), "Same proposal {}", first);
+                first
+                    .validate_signature(validator)
+                    .context("Invalid first proposal")?;
+                second
+                    .validate_signature(validator)
+                    .context("Invalid second proposal")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Storage of the evidence recorded by consensus.
+pub trait EvidenceStore: Send + Sync {
+    /// All the evidence recorded so far.
+    fn get_evidence(&self) -> anyhow::Result<Vec<Evidence>>;
+}
+
+/// Read access to the evidence recorded by consensus, shared with the services exposing it.
+/// These services start before consensus, so the store is set once consensus opens it.
+#[derive(Clone)]
+pub struct EvidenceReader {
+    store: Arc<RwLock<Option<Arc<dyn EvidenceStore>>>>,
+}
+
+impl EvidenceReader {
+    pub fn new() -> Self {
+        Self {
+            store: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub fn set_store(&self, store: Arc<dyn EvidenceStore>) {
+        *self.store.write() = Some(store);
+    }
+
+    /// The evidence recorded so far, none if consensus isn't running on this node.
+    pub fn get_evidence(&self) -> anyhow::Result<Vec<Evidence>> {
+        match self.store.read().as_ref() {
+            Some(store) => store.get_evidence(),
+            None => Ok(vec![]),
+        }
+    }
+}
+
+impl Default for EvidenceReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block::block_test_utils::certificate_for_genesis, common::Payload, vote_data::VoteData,
+    };
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        block_info::BlockInfo, ledger_info::LedgerInfo, validator_signer::ValidatorSigner,
+        validator_verifier::random_validator_verifier,
+    };
+
+    fn create_vote(signer: &ValidatorSigner, round: Round) -> Vote {
+        let proposed = BlockInfo::new(1, round, HashValue::random(), HashValue::zero(), 0, 0, None);
+        Vote::new(
+            VoteData::new(proposed, BlockInfo::random(0)),
+            signer.author(),
+            LedgerInfo::new(BlockInfo::empty(), HashValue::zero()),
+            signer,
+        )
+        .unwrap()
+    }
+
+    fn create_proposal(signer: &ValidatorSigner, round: Round, timestamp_usecs: u64) -> Block {
+        Block::new_proposal(
+            Payload::empty(),
+            round,
+            timestamp_usecs,
+            certificate_for_genesis(),
+            signer,
+            vec![],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_double_vote_verify() {
+        let (signers, validators) = random_validator_verifier(4, None, false);
+        let vote = create_vote(&signers[0], 1);
+
+        let evidence = Evidence::DoubleVote {
+            first: vote.clone(),
+            second: create_vote(&signers[0], 1),
+        };
+        assert!(evidence.verify(&validators).is_ok());
+        assert_eq!(evidence.author(), Some(signers[0].author()));
+        assert_eq!((evidence.epoch(), evidence.round()), (1, 1));
+
+        // the votes must be different votes of the same author in the same round
+        for second in [
+            vote.clone(),
+            create_vote(&signers[1], 1),
+            create_vote(&signers[0], 2),
+        ] {
+            let evidence = Evidence::DoubleVote {
+                first: vote.clone(),
+                second,
+            };
+            assert!(evidence.verify(&validators).is_err());
+        }
+
+        // the votes must be signed by the author
+        let vote_data = create_vote(&signers[1], 1).vote_data().clone();
+        let ledger_info = LedgerInfo::new(BlockInfo::empty(), vote_data.hash());
+        let signature = signers[1].sign(&ledger_info).unwrap();
+        let forged_vote =
+            Vote::new_with_signature(vote_data, signers[0].author(), ledger_info, signature);
+        let evidence = Evidence::DoubleVote {
+            first: vote,
+            second: forged_vote,
+        };
+        assert!(evidence.verify(&validators).is_err());
+    }
+
+    #[test]
+    fn test_double_proposal_verify() {
+        let (signers, validators) = random_validator_verifier(4, None, false);
+        let proposal = create_proposal(&signers[0], 1, 1);
+
+        let evidence = Evidence::DoubleProposal {
+            first: proposal.clone(),
+            second: create_proposal(&signers[0], 1, 2),
+        };
+        assert!(evidence.verify(&validators).is_ok());
+        assert_eq!(evidence.author(), Some(signers[0].author()));
+
+        for second in [
+            proposal.clone(),
+            create_proposal(&signers[1], 1, 2),
+            create_proposal(&signers[0], 2, 2),
+        ] {
+            let evidence = Evidence::DoubleProposal {
+                first: proposal.clone(),
+                second,
+            };
+            assert!(evidence.verify(&validators).is_err());
+        }
+
+        // blocks without author aren't proposals
+        let evidence = Evidence::DoubleProposal {
+            first: Block::make_genesis_block(),
+            second: Block::make_genesis_block(),
+        };
+        assert_eq!(evidence.author(), None);
+        assert!(evidence.to_string().contains("without author"));
+        assert!(evidence.verify(&validators).is_err());
+
+        // the proposals must be signed by validators of the epoch
+        let (other_signers, _) = random_validator_verifier(4, None, true);
+        let evidence = Evidence::DoubleProposal {
+            first: create_proposal(&other_signers[0], 1, 1),
+            second: create_proposal(&other_signers[0], 1, 2),
+        };
+        assert!(evidence.verify(&validators).is_err());
+    }
+}
@@ -8,8 +8,10 @@ pub mod block_data;
 pub mod block_retrieval;
 pub mod common;
 pub mod epoch_retrieval;
+pub mod evidence;
 pub mod executed_block;
 pub mod experimental;
+pub mod proof_of_store;
 pub mod proposal_msg;
 pub mod quorum_cert;
 pub mod request_response;
@@ -0,0 +1,349 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{Author, Round};
+use anyhow::{ensure, Context};
+use aptos_crypto::hash::CryptoHash;
+use aptos_crypto::{bls12381, CryptoMaterialError, HashValue};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::{
+    aggregate_signature::AggregateSignature, transaction::SignedTransaction,
+    validator_signer::ValidatorSigner, validator_verifier::ValidatorVerifier,
+};
+use serde::{Deserialize, Serialize};
+use short_hex_str::AsShortHexStr;
+use std::fmt::{Display, Formatter};
+
+/// The logical time of consensus, batches expire once a block of a later logical time is
+/// committed.
+#[derive(
+    Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default,
+)]
+pub struct LogicalTime {
+    epoch: u64,
+    round: Round,
+}
+
+impl LogicalTime {
+    pub fn new(epoch: u64, round: Round) -> Self {
+        Self { epoch, round }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+}
+
+impl Display for LogicalTime {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "[epoch: {}, round: {}]", self.epoch, self.round)
+    }
+}
+
+/// The information about a batch that validators sign to attest that they store it.
+#[derive(
+    Deserialize, Serialize, Clone, Debug, PartialEq, Eq, Hash, CryptoHasher, BCSCryptoHash,
+)]
+pub struct SignedDigestInfo {
+    pub digest: HashValue,
+    pub expiration: LogicalTime,
+    pub num_txns: u64,
+    pub num_bytes: u64,
+}
+
+impl SignedDigestInfo {
+    pub fn new(digest: HashValue, expiration: LogicalTime, num_txns: u64, num_bytes: u64) -> Self {
+        Self {
+            digest,
+            expiration,
+            num_txns,
+            num_bytes,
+        }
+    }
+}
+
+/// A validator's signature on a batch it persisted, sent back to the author of the batch.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedDigest {
+    epoch: u64,
+    signer: Author,
+    info: SignedDigestInfo,
+    signature: bls12381::Signature,
+}
+
+impl Display for SignedDigest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "SignedDigest: [signer: {}, digest: {}, expiration: {}]",
+            self.signer.short_str(),
+            self.info.digest,
+            self.info.expiration
+        )
+    }
+}
+
+impl SignedDigest {
+    pub fn new(
+        epoch: u64,
+        info: SignedDigestInfo,
+        validator_signer: &ValidatorSigner,
+    ) -> Result<Self, CryptoMaterialError> {
+        let signature = validator_signer.sign(&info)?;
+        Ok(Self {
+            epoch,
+            signer: validator_signer.author(),
+            info,
+            signature,
+        })
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn signer(&self) -> Author {
+        self.signer
+    }
+
+    pub fn info(&self) -> &SignedDigestInfo {
+        &self.info
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.info.digest
+    }
+
+    pub fn signature(&self) -> &bls12381::Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        validator
+            .verify(self.signer, &self.info, &self.signature)
+            .context("Failed to verify SignedDigest")
+    }
+}
+
+/// Proof of availability of a batch: signatures of a quorum of validators on the same
+/// `SignedDigestInfo`, so that at least one honest validator can serve the batch until it expires.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProofOfStore {
+    info: SignedDigestInfo,
+    multi_signature: AggregateSignature,
+}
+
+impl Display for ProofOfStore {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "ProofOfStore: [digest: {}, expiration: {}, num_txns: {}]",
+            self.info.digest, self.info.expiration, self.info.num_txns
+        )
+    }
+}
+
+impl ProofOfStore {
+    pub fn new(info: SignedDigestInfo, multi_signature: AggregateSignature) -> Self {
+        Self {
+            info,
+            multi_signature,
+        }
+    }
+
+    pub fn info(&self) -> &SignedDigestInfo {
+        &self.info
+    }
+
+    pub fn digest(&self) -> &HashValue {
+        &self.info.digest
+    }
+
+    pub fn expiration(&self) -> LogicalTime {
+        self.info.expiration
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.info.expiration.epoch()
+    }
+
+    pub fn multi_signature(&self) -> &AggregateSignature {
+        &self.multi_signature
+    }
+
+    /// The validators that signed the proof, any of them should be able to serve the batch.
+    pub fn signers(&self, validator: &ValidatorVerifier) -> Vec<Author> {
+        self.multi_signature.get_voter_addresses(
+            &validator
+                .get_ordered_account_addresses_iter()
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(
+            self.info.num_txns > 0,
+            "ProofOfStore {} certifies an empty batch",
+            self
+        );
+        validator
+            .verify_multi_signatures(&self.info, &self.multi_signature)
+            .context("Failed to verify ProofOfStore")
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq, CryptoHasher, BCSCryptoHash)]
+pub struct BatchPayload {
+    txns: Vec<SignedTransaction>,
+}
+
+impl BatchPayload {
+    pub fn new(txns: Vec<SignedTransaction>) -> Self {
+        Self { txns }
+    }
+
+    pub fn txns(&self) -> &[SignedTransaction] {
+        &self.txns
+    }
+
+    pub fn into_txns(self) -> Vec<SignedTransaction> {
+        self.txns
+    }
+}
+
+/// A batch of transactions broadcast by its author ahead of being referenced, by digest, in a
+/// proposal.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Batch {
+    epoch: u64,
+    author: Author,
+    expiration: LogicalTime,
+    payload: BatchPayload,
+}
+
+impl Display for Batch {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Batch: [author: {}, expiration: {}, num_txns: {}]",
+            self.author.short_str(),
+            self.expiration,
+            self.num_txns()
+        )
+    }
+}
+
+impl Batch {
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        expiration: LogicalTime,
+        txns: Vec<SignedTransaction>,
+    ) -> Self {
+        Self {
+            epoch,
+            author,
+            expiration,
+            payload: BatchPayload::new(txns),
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn expiration(&self) -> LogicalTime {
+        self.expiration
+    }
+
+    /// The digest is the hash of the transactions, it doesn't depend on who authored the batch.
+    pub fn digest(&self) -> HashValue {
+        self.payload.hash()
+    }
+
+    pub fn txns(&self) -> &[SignedTransaction] {
+        &self.payload.txns
+    }
+
+    pub fn into_txns(self) -> Vec<SignedTransaction> {
+        self.payload.txns
+    }
+
+    pub fn num_txns(&self) -> u64 {
+        self.payload.txns.len() as u64
+    }
+
+    pub fn num_bytes(&self) -> u64 {
+        self.payload
+            .txns
+            .iter()
+            .map(|txn| txn.raw_txn_bytes_len() as u64)
+            .sum()
+    }
+
+    pub fn info(&self) -> SignedDigestInfo {
+        SignedDigestInfo::new(
+            self.digest(),
+            self.expiration,
+            self.num_txns(),
+            self.num_bytes(),
+        )
+    }
+
+    /// Makes sure the batch was sent by its author and is well formed.
+    pub fn verify(&self, peer_id: Author) -> anyhow::Result<()> {
+        ensure!(
+            self.author == peer_id,
+            "Batch authored by {} was sent by {}",
+            self.author,
+            peer_id
+        );
+        ensure!(
+            self.expiration.epoch() == self.epoch,
+            "Batch of epoch {} expires in epoch {}",
+            self.epoch,
+            self.expiration.epoch()
+        );
+        ensure!(!self.payload.txns.is_empty(), "Batch is empty");
+        Ok(())
+    }
+}
+
+/// RPC to fetch a batch that a proof of store refers to but isn't available locally.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct BatchRequest {
+    epoch: u64,
+    digest: HashValue,
+}
+
+impl Display for BatchRequest {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[BatchRequest epoch: {}, digest: {}]",
+            self.epoch, self.digest
+        )
+    }
+}
+
+impl BatchRequest {
+    pub fn new(epoch: u64, digest: HashValue) -> Self {
+        Self { epoch, digest }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn digest(&self) -> HashValue {
+        self.digest
+    }
+}
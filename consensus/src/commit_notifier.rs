@@ -4,6 +4,7 @@
 use crate::error::QuorumStoreError;
 use crate::monitor;
 use anyhow::{format_err, Result};
+use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use consensus_types::{common::Round, request_response::ConsensusRequest};
 use futures::channel::{mpsc, mpsc::Sender, oneshot};
@@ -13,8 +14,13 @@ use tokio::time::timeout;
 /// Notification of execution committed logical time for QuorumStore to clean.
 #[async_trait::async_trait]
 pub trait CommitNotifier: Send + Sync {
-    /// Notification of committed logical time
-    async fn notify_commit(&self, epoch: u64, round: Round) -> Result<(), QuorumStoreError>;
+    /// Notification of committed logical time and of the quorum store batches committed
+    async fn notify_commit(
+        &self,
+        epoch: u64,
+        round: Round,
+        batches: Vec<HashValue>,
+    ) -> Result<(), QuorumStoreError>;
 
     fn new_epoch(&self, quorum_store_commit_sender: mpsc::Sender<ConsensusRequest>);
 }
@@ -39,9 +45,14 @@ impl QuorumStoreCommitNotifier {
 
 #[async_trait::async_trait]
 impl CommitNotifier for QuorumStoreCommitNotifier {
-    async fn notify_commit(&self, epoch: u64, round: Round) -> Result<(), QuorumStoreError> {
+    async fn notify_commit(
+        &self,
+        epoch: u64,
+        round: Round,
+        batches: Vec<HashValue>,
+    ) -> Result<(), QuorumStoreError> {
         let (callback, callback_rcv) = oneshot::channel();
-        let req = ConsensusRequest::CleanRequest(epoch, round, callback);
+        let req = ConsensusRequest::CleanRequest(epoch, round, batches, callback);
 
         self.quorum_store_commit_sender
             .lock()
@@ -71,3 +82,20 @@ impl CommitNotifier for QuorumStoreCommitNotifier {
         *self.quorum_store_commit_sender.lock() = quorum_store_commit_sender;
     }
 }
+
+/// Commit notifier of the nodes without a quorum store to clean, e.g. consensus observers.
+pub struct EmptyCommitNotifier;
+
+#[async_trait::async_trait]
+impl CommitNotifier for EmptyCommitNotifier {
+    async fn notify_commit(
+        &self,
+        _epoch: u64,
+        _round: Round,
+        _batches: Vec<HashValue>,
+    ) -> Result<(), QuorumStoreError> {
+        Ok(())
+    }
+
+    fn new_epoch(&self, _quorum_store_commit_sender: Sender<ConsensusRequest>) {}
+}
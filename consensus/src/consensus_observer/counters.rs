@@ -0,0 +1,92 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
+};
+use once_cell::sync::Lazy;
+
+/// Counters(queued,dequeued,dropped) related to pending network notifications to the consensus
+/// observer and publisher.
+pub static PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_pending_network_events",
+        "Counters(queued,dequeued,dropped) related to pending network notifications to the consensus observer",
+        &["state"]
+    )
+    .unwrap()
+});
+
+/// Number of messages published to the subscribers, by message type.
+pub static PUBLISHED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_published_messages",
+        "Number of messages published to the subscribers, by message type",
+        &["type"]
+    )
+    .unwrap()
+});
+
+/// Number of full nodes subscribed to this validator.
+pub static NUM_SUBSCRIBERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_num_subscribers",
+        "Number of full nodes subscribed to this validator"
+    )
+    .unwrap()
+});
+
+/// Number of messages received by the observer, by message type.
+pub static RECEIVED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_received_messages",
+        "Number of messages received by the observer, by message type",
+        &["type"]
+    )
+    .unwrap()
+});
+
+/// Number of ordered blocks executed by the observer and waiting for their commit decision.
+pub static NUM_PENDING_BLOCKS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_num_pending_blocks",
+        "Number of ordered blocks executed by the observer and waiting for their commit decision"
+    )
+    .unwrap()
+});
+
+/// Round of the last block committed by the observer.
+pub static LAST_COMMITTED_ROUND: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_last_committed_round",
+        "Round of the last block committed by the observer"
+    )
+    .unwrap()
+});
+
+/// Number of times the observer synced to a commit decision to catch up to it.
+pub static STATE_SYNC_TARGETS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_observer_state_sync_targets",
+        "Number of times the observer synced to a commit decision to catch up to it"
+    )
+    .unwrap()
+});
+
+/// Number of times the observer handed the node back to state sync.
+pub static STATE_SYNC_FALLBACKS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_observer_state_sync_fallbacks",
+        "Number of times the observer handed the node back to state sync"
+    )
+    .unwrap()
+});
+
+/// Whether the observer executes the blocks (1) or state sync continuously syncs the node (0).
+pub static EXECUTING: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_executing",
+        "Whether the observer executes the blocks (1) or state sync continuously syncs the node (0)"
+    )
+    .unwrap()
+});
@@ -0,0 +1,19 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Consensus observer: validators push the blocks they order and the commit decisions to the
+//! subscribed full nodes, which execute and commit the blocks without waiting for state sync.
+
+/// Interface between the consensus observer and Network layers.
+pub mod network;
+
+/// Executes and commits the blocks pushed by the validator a full node is subscribed to.
+pub(crate) mod observer;
+/// Resolves the transactions of the blocks executed by the observer.
+pub(crate) mod payload_manager;
+/// Pushes the blocks ordered by a validator to its subscribers.
+pub(crate) mod publisher;
+
+mod counters;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,233 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::consensus_observer::counters;
+use anyhow::ensure;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures, validator_verifier::ValidatorVerifier, PeerId,
+};
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_types::{block::Block, common::Payload, proof_of_store::BatchPayload};
+use network::{
+    error::NetworkError,
+    peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
+    protocols::{
+        network::{AppConfig, NetworkEvents, NetworkSender, NewNetworkSender},
+        rpc::error::RpcError,
+    },
+    ProtocolId,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
+
+/// Network type for the consensus observer
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ObserverMessage {
+    /// RPC from an observer to a validator to start receiving its ordered blocks and commit
+    /// decisions.
+    SubscribeRequest,
+    /// Carries the latest commit decision of the validator, the observer catches up to it before
+    /// following the blocks pushed by the validator.
+    SubscribeResponse(Box<LedgerInfoWithSignatures>),
+    /// RPC from an observer to a validator to stop receiving its messages.
+    UnsubscribeRequest,
+    /// Acknowledges the UnsubscribeRequest.
+    UnsubscribeResponse,
+    /// Chain of blocks ordered by consensus, pushed to the subscribers as soon as it's ordered.
+    OrderedBlock(Box<OrderedBlock>),
+    /// Ledger info certifying the execution result of the ordered blocks up to its block, pushed
+    /// to the subscribers once the blocks are committed.
+    CommitDecision(Box<LedgerInfoWithSignatures>),
+}
+
+impl ObserverMessage {
+    /// ObserverMessage type in string
+    pub fn name(&self) -> &str {
+        match self {
+            ObserverMessage::SubscribeRequest => "SubscribeRequest",
+            ObserverMessage::SubscribeResponse(_) => "SubscribeResponse",
+            ObserverMessage::UnsubscribeRequest => "UnsubscribeRequest",
+            ObserverMessage::UnsubscribeResponse => "UnsubscribeResponse",
+            ObserverMessage::OrderedBlock(_) => "OrderedBlock",
+            ObserverMessage::CommitDecision(_) => "CommitDecision",
+        }
+    }
+}
+
+/// The blocks committed together by consensus, with the proof of their order. The transactions
+/// of the quorum store batches the blocks refer to are pushed along, observers can't fetch the
+/// batches from the validators.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OrderedBlock {
+    blocks: Vec<Block>,
+    // The payloads of the batches of the quorum store proofs in the blocks, in order
+    batch_payloads: Vec<BatchPayload>,
+    ordered_proof: LedgerInfoWithSignatures,
+}
+
+impl fmt::Display for OrderedBlock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "[OrderedBlock: {} blocks, ordered proof: {}]",
+            self.blocks.len(),
+            self.ordered_proof.commit_info()
+        )
+    }
+}
+
+impl OrderedBlock {
+    /// Creates the message of blocks ordered by the given proof.
+    pub fn new(
+        blocks: Vec<Block>,
+        batch_payloads: Vec<BatchPayload>,
+        ordered_proof: LedgerInfoWithSignatures,
+    ) -> Self {
+        Self {
+            blocks,
+            batch_payloads,
+            ordered_proof,
+        }
+    }
+
+    /// The ordered blocks, the last one is the block of the ordered proof.
+    pub fn blocks(&self) -> &[Block] {
+        &self.blocks
+    }
+
+    /// The ledger info ordering the blocks, signed by a quorum of the validators of the epoch.
+    pub fn ordered_proof(&self) -> &LedgerInfoWithSignatures {
+        &self.ordered_proof
+    }
+
+    /// The last ordered block, i.e. the block of the ordered proof.
+    pub fn last_block(&self) -> &Block {
+        self.blocks.last().expect("Ordered blocks can't be empty")
+    }
+
+    /// Consumes the message, returning the blocks and the batch payloads they refer to.
+    pub fn into_parts(self) -> (Vec<Block>, Vec<BatchPayload>) {
+        (self.blocks, self.batch_payloads)
+    }
+
+    /// Makes sure the blocks form a chain ending at the block of the ordered proof, that the
+    /// proof is signed by the validators of the epoch and that the batch payloads are the ones
+    /// of the proofs of store in the blocks.
+    pub fn verify(&self, validator: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(!self.blocks.is_empty(), "No ordered blocks");
+        for (parent, block) in self.blocks.iter().zip(self.blocks.iter().skip(1)) {
+            ensure!(
+                block.parent_id() == parent.id(),
+                "Block {} doesn't extend block {}",
+                block,
+                parent
+            );
+        }
+        let commit_info = self.ordered_proof.commit_info();
+        ensure!(
+            self.last_block().id() == commit_info.id()
+                && self.last_block().round() == commit_info.round()
+                && self.last_block().epoch() == commit_info.epoch(),
+            "Last ordered block {} doesn't match the ordered proof {}",
+            self.last_block(),
+            commit_info
+        );
+
+        let digests: Vec<HashValue> = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block.payload() {
+                Some(Payload::InQuorumStore(proofs)) => Some(proofs),
+                _ => None,
+            })
+            .flatten()
+            .map(|proof| *proof.digest())
+            .collect();
+        ensure!(
+            digests.len() == self.batch_payloads.len(),
+            "Ordered blocks refer to {} batches but carry {}",
+            digests.len(),
+            self.batch_payloads.len()
+        );
+        for (digest, batch_payload) in digests.iter().zip(self.batch_payloads.iter()) {
+            ensure!(
+                *digest == batch_payload.hash(),
+                "Batch payload doesn't match the digest {}",
+                digest
+            );
+        }
+
+        self.ordered_proof.verify_signatures(validator)?;
+        Ok(())
+    }
+}
+
+/// The interface from Network to the consensus observer and publisher.
+///
+/// `ObserverNetworkEvents` is a `Stream` of `PeerManagerNotification` where the
+/// raw `Bytes` direct-send and rpc messages are deserialized into
+/// `ObserverMessage` types.
+pub type ObserverNetworkEvents = NetworkEvents<ObserverMessage>;
+
+/// The interface from the consensus observer and publisher to Networking layer.
+///
+/// This is a thin wrapper around a `NetworkSender<ObserverMessage>`, the direct-send
+/// messages and the rpcs each use a single protocol.
+#[derive(Clone)]
+pub struct ObserverNetworkSender {
+    inner: NetworkSender<ObserverMessage>,
+}
+
+/// Configuration for the network endpoints to support the consensus observer.
+pub fn network_endpoint_config(max_network_channel_size: usize) -> AppConfig {
+    AppConfig::p2p(
+        [
+            ProtocolId::ConsensusObserverDirectSend,
+            ProtocolId::ConsensusObserverRpc,
+        ],
+        aptos_channel::Config::new(max_network_channel_size)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&counters::PENDING_CONSENSUS_OBSERVER_NETWORK_EVENTS),
+    )
+}
+
+impl NewNetworkSender for ObserverNetworkSender {
+    fn new(
+        peer_mgr_reqs_tx: PeerManagerRequestSender,
+        connection_reqs_tx: ConnectionRequestSender,
+    ) -> Self {
+        Self {
+            inner: NetworkSender::new(peer_mgr_reqs_tx, connection_reqs_tx),
+        }
+    }
+}
+
+impl ObserverNetworkSender {
+    /// Send a message to all the given recipients.
+    pub fn send_to_many(
+        &self,
+        recipients: impl Iterator<Item = PeerId>,
+        message: ObserverMessage,
+    ) -> Result<(), NetworkError> {
+        self.inner
+            .send_to_many(recipients, ProtocolId::ConsensusObserverDirectSend, message)
+    }
+
+    /// Send a RPC to the destination peer and wait for its response.
+    pub async fn send_rpc(
+        &self,
+        recipient: PeerId,
+        message: ObserverMessage,
+        timeout: Duration,
+    ) -> Result<ObserverMessage, RpcError> {
+        self.inner
+            .send_rpc(
+                recipient,
+                ProtocolId::ConsensusObserverRpc,
+                message,
+                timeout,
+            )
+            .await
+    }
+}
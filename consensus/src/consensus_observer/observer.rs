@@ -0,0 +1,486 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        counters,
+        network::{ObserverMessage, ObserverNetworkEvents, ObserverNetworkSender, OrderedBlock},
+        payload_manager::ObserverPayloadManager,
+    },
+    state_replication::StateComputer,
+};
+use anyhow::{bail, ensure};
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    epoch_state::EpochState,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::ValidatorSet,
+    PeerId,
+};
+use consensus_types::{block::Block, common::Round, executed_block::ExecutedBlock};
+use event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use futures::{FutureExt, StreamExt};
+use network::{protocols::network::Event, ProtocolId};
+use rand::{seq::SliceRandom, thread_rng};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+use storage_interface::DbReader;
+
+/// The validator the observer follows, and the last time it heard from it.
+struct Subscription {
+    peer_id: PeerId,
+    last_message_time: Instant,
+}
+
+/// The id of the block the blocks following the given committed ledger info extend. The blocks
+/// of a new epoch extend its genesis block.
+fn committed_block_id(ledger_info: &LedgerInfo) -> HashValue {
+    if ledger_info.ends_epoch() {
+        Block::make_genesis_block_from_ledger_info(ledger_info).id()
+    } else {
+        ledger_info.consensus_block_id()
+    }
+}
+
+/// Consensus observer of a full node: subscribes to a validator, executes the blocks it orders
+/// and commits them once their commit decision is received, without taking part in consensus.
+/// Whenever the ordered blocks can't be executed, e.g. some of them were missed, the observer
+/// falls back to state sync to catch up to the next commit decision.
+///
+/// Only the commit decisions verified against the validators of the current epoch are used as
+/// sync targets. Without a publisher to follow, or when the publisher is in a future epoch, the
+/// observer hands the node back to state sync, which continuously syncs (verifying the epoch
+/// changes on the way) until the observer receives a verifiable commit decision again.
+pub struct ConsensusObserver {
+    config: ConsensusObserverConfig,
+    network_sender: ObserverNetworkSender,
+    reconfig_events: ReconfigNotificationListener,
+    db_reader: Arc<dyn DbReader>,
+    execution_client: Arc<dyn StateComputer>,
+    payload_manager: Arc<ObserverPayloadManager>,
+    epoch_state: Option<Arc<EpochState>>,
+    // the latest ledger info committed by this node
+    root: LedgerInfoWithSignatures,
+    // the blocks ordered after the root and executed, waiting for their commit decision
+    pending_blocks: BTreeMap<Round, Arc<ExecutedBlock>>,
+    // the connected peers publishing their blocks
+    publishers: HashSet<PeerId>,
+    subscription: Option<Subscription>,
+    // shared with state sync: whether the observer executes the blocks of this node, or state
+    // sync continuously syncs it
+    executing: Arc<AtomicBool>,
+}
+
+impl ConsensusObserver {
+    pub fn new(
+        config: ConsensusObserverConfig,
+        network_sender: ObserverNetworkSender,
+        reconfig_events: ReconfigNotificationListener,
+        db_reader: Arc<dyn DbReader>,
+        execution_client: Arc<dyn StateComputer>,
+        executing: Arc<AtomicBool>,
+    ) -> Self {
+        let root = db_reader
+            .get_latest_ledger_info()
+            .expect("Failed to read the latest ledger info");
+        Self {
+            config,
+            network_sender,
+            reconfig_events,
+            db_reader,
+            execution_client,
+            payload_manager: Arc::new(ObserverPayloadManager::new()),
+            epoch_state: None,
+            root,
+            pending_blocks: BTreeMap::new(),
+            publishers: HashSet::new(),
+            subscription: None,
+            executing,
+        }
+    }
+
+    /// Returns true iff the observer executes the blocks, i.e. state sync doesn't.
+    fn is_executing(&self) -> bool {
+        self.executing.load(Ordering::Acquire)
+    }
+
+    fn set_executing(&self, executing: bool) {
+        self.executing.store(executing, Ordering::Release);
+        counters::EXECUTING.set(executing as i64);
+    }
+
+    /// Hands the node back to state sync, which continuously syncs until the observer receives
+    /// a verifiable commit decision again.
+    fn fall_back_to_state_sync(&mut self) {
+        if self.is_executing() {
+            info!("Consensus observer hands the node back to state sync");
+            counters::STATE_SYNC_FALLBACKS.inc();
+            self.set_executing(false);
+        }
+        self.clear_pending_blocks();
+    }
+
+    fn epoch_state(&self) -> Arc<EpochState> {
+        self.epoch_state
+            .clone()
+            .expect("Consensus observer hasn't started an epoch")
+    }
+
+    /// The id of the block the next ordered blocks extend.
+    fn last_ordered_block_id(&self) -> HashValue {
+        match self.pending_blocks.values().last() {
+            Some(block) => block.id(),
+            None => committed_block_id(self.root.ledger_info()),
+        }
+    }
+
+    fn clear_pending_blocks(&mut self) {
+        self.pending_blocks.clear();
+        self.payload_manager.clear();
+        counters::NUM_PENDING_BLOCKS.set(0);
+    }
+
+    /// Waits for the reconfiguration notification of the epoch of the blocks following the root,
+    /// and starts executing the blocks of that epoch.
+    pub(crate) async fn wait_for_epoch_start(&mut self) {
+        let next_epoch = self.root.ledger_info().next_block_epoch();
+        while self
+            .epoch_state
+            .as_ref()
+            .map_or(true, |epoch_state| epoch_state.epoch < next_epoch)
+        {
+            let reconfig_notification = self
+                .reconfig_events
+                .next()
+                .await
+                .expect("Reconfig sender dropped, unable to start new epoch");
+            self.process_reconfig_notification(reconfig_notification);
+        }
+    }
+
+    /// Starts the epoch of the given reconfiguration notification, unless it's already started.
+    fn process_reconfig_notification(&mut self, reconfig_notification: ReconfigNotification) {
+        let payload = reconfig_notification.on_chain_configs;
+        if self
+            .epoch_state
+            .as_ref()
+            .map_or(false, |epoch_state| epoch_state.epoch >= payload.epoch())
+        {
+            return;
+        }
+        let validator_set: ValidatorSet = payload
+            .get()
+            .expect("failed to get ValidatorSet from payload");
+        let epoch_state = Arc::new(EpochState {
+            epoch: payload.epoch(),
+            verifier: (&validator_set).into(),
+        });
+        self.execution_client
+            .new_epoch(&epoch_state, self.payload_manager.clone());
+        self.clear_pending_blocks();
+        info!(
+            epoch = epoch_state.epoch,
+            "Consensus observer starts the new epoch"
+        );
+        self.epoch_state = Some(epoch_state);
+    }
+
+    async fn update_root(&mut self, root: LedgerInfoWithSignatures) {
+        counters::LAST_COMMITTED_ROUND.set(root.commit_info().round() as i64);
+        self.root = root;
+        self.wait_for_epoch_start().await;
+    }
+
+    /// Drops the pending blocks and syncs to the given (verified) commit decision.
+    async fn sync_to(&mut self, target: LedgerInfoWithSignatures) -> anyhow::Result<()> {
+        info!(
+            target = %target.commit_info(),
+            "Consensus observer syncs to the commit decision"
+        );
+        counters::STATE_SYNC_TARGETS.inc();
+        self.clear_pending_blocks();
+        let result = self.execution_client.sync_to(target).await;
+        // the root is whatever state sync committed, even if it didn't reach the target
+        let root = self.db_reader.get_latest_ledger_info()?;
+        self.update_root(root).await;
+        result?;
+        Ok(())
+    }
+
+    /// Commits the pending blocks up to the block of the given commit decision.
+    async fn commit(&mut self, commit_proof: LedgerInfoWithSignatures) -> anyhow::Result<()> {
+        let remaining_blocks = self
+            .pending_blocks
+            .split_off(&(commit_proof.commit_info().round() + 1));
+        let blocks: Vec<_> = std::mem::replace(&mut self.pending_blocks, remaining_blocks)
+            .into_values()
+            .collect();
+        counters::NUM_PENDING_BLOCKS.set(self.pending_blocks.len() as i64);
+        self.execution_client
+            .commit(&blocks, commit_proof.clone(), Box::new(|_, _| {}))
+            .await?;
+        self.payload_manager.remove_committed(&blocks);
+        self.update_root(commit_proof).await;
+        Ok(())
+    }
+
+    async fn process_ordered_block(&mut self, ordered_block: OrderedBlock) -> anyhow::Result<()> {
+        // the blocks are only accessed once the message is known to carry some
+        ensure!(
+            !ordered_block.blocks().is_empty(),
+            "{} is empty",
+            ordered_block
+        );
+        if !self.is_executing() {
+            // state sync is in control, the next verified commit decision hands it back
+            return Ok(());
+        }
+        let epoch_state = self.epoch_state();
+        let last_block = ordered_block.last_block();
+        ensure!(
+            last_block.epoch() == epoch_state.epoch,
+            "{} isn't in the current epoch {}",
+            ordered_block,
+            epoch_state.epoch
+        );
+        let root_info = self.root.commit_info();
+        if self.pending_blocks.contains_key(&last_block.round())
+            || (last_block.epoch() == root_info.epoch() && last_block.round() <= root_info.round())
+        {
+            return Ok(());
+        }
+        ordered_block.verify(&epoch_state.verifier)?;
+        // the missing blocks are synced on the next commit decision
+        ensure!(
+            ordered_block.blocks()[0].parent_id() == self.last_ordered_block_id(),
+            "{} doesn't extend the last ordered block",
+            ordered_block
+        );
+        ensure!(
+            self.pending_blocks.len() + ordered_block.blocks().len()
+                <= self.config.max_pending_blocks,
+            "Too many pending blocks to execute {}",
+            ordered_block
+        );
+
+        let (blocks, batch_payloads) = ordered_block.into_parts();
+        self.payload_manager.insert_batches(batch_payloads);
+        for block in blocks {
+            let compute_result = self
+                .execution_client
+                .compute(&block, self.last_ordered_block_id())
+                .await?;
+            self.pending_blocks.insert(
+                block.round(),
+                Arc::new(ExecutedBlock::new(block, compute_result)),
+            );
+        }
+        counters::NUM_PENDING_BLOCKS.set(self.pending_blocks.len() as i64);
+        Ok(())
+    }
+
+    async fn process_commit_decision(
+        &mut self,
+        commit_proof: LedgerInfoWithSignatures,
+    ) -> anyhow::Result<()> {
+        if !self.is_executing() {
+            // state sync commits in the background, catch up with it first
+            let root = self.db_reader.get_latest_ledger_info()?;
+            self.update_root(root).await;
+        }
+        let commit_info = commit_proof.commit_info();
+        let root_info = self.root.commit_info();
+        if (commit_info.epoch(), commit_info.round()) <= (root_info.epoch(), root_info.round()) {
+            return Ok(());
+        }
+        let epoch_state = self.epoch_state();
+        if commit_info.epoch() != epoch_state.epoch {
+            // The validators of a future epoch aren't known yet, so the decision can't be
+            // verified. State sync catches up to that epoch, verifying the epoch changes.
+            info!(
+                commit_info = %commit_info,
+                epoch = epoch_state.epoch,
+                "Commit decision of a future epoch"
+            );
+            self.fall_back_to_state_sync();
+            return Ok(());
+        }
+        commit_proof.verify_signatures(&epoch_state.verifier)?;
+
+        if self.is_executing() {
+            match self.pending_blocks.get(&commit_info.round()) {
+                Some(block) if block.block_info() == *commit_info => {
+                    return self.commit(commit_proof).await;
+                }
+                Some(block) => error!(
+                    "Executed block {} doesn't match the commit decision {}",
+                    block.block_info(),
+                    commit_info
+                ),
+                None => (),
+            }
+        } else {
+            // state sync stops continuously syncing once it reaches the decision
+            info!(
+                commit_info = %commit_info,
+                "Consensus observer takes over from state sync"
+            );
+            self.set_executing(true);
+        }
+        // the blocks up to the commit decision are missing
+        self.sync_to(commit_proof).await
+    }
+
+    async fn process_message(
+        &mut self,
+        peer_id: PeerId,
+        message: ObserverMessage,
+    ) -> anyhow::Result<()> {
+        match &mut self.subscription {
+            Some(subscription) if subscription.peer_id == peer_id => {
+                subscription.last_message_time = Instant::now();
+            }
+            _ => bail!("{} from {} which isn't subscribed", message.name(), peer_id),
+        }
+        counters::RECEIVED_MESSAGES
+            .with_label_values(&[message.name()])
+            .inc();
+        match message {
+            ObserverMessage::OrderedBlock(ordered_block) => {
+                self.process_ordered_block(*ordered_block).await
+            }
+            ObserverMessage::CommitDecision(commit_proof) => {
+                self.process_commit_decision(*commit_proof).await
+            }
+            _ => bail!("Unexpected message {}", message.name()),
+        }
+    }
+
+    pub(crate) async fn process_network_event(
+        &mut self,
+        event: Event<ObserverMessage>,
+    ) -> anyhow::Result<()> {
+        match event {
+            Event::Message(peer_id, message) => self.process_message(peer_id, message).await,
+            Event::NewPeer(metadata) => {
+                if metadata
+                    .application_protocols
+                    .contains(ProtocolId::ConsensusObserverRpc)
+                {
+                    self.publishers.insert(metadata.remote_peer_id);
+                }
+                Ok(())
+            }
+            Event::LostPeer(metadata) => {
+                self.publishers.remove(&metadata.remote_peer_id);
+                let subscribed_peer = self.subscription.as_ref().map(|s| s.peer_id);
+                if subscribed_peer == Some(metadata.remote_peer_id) {
+                    self.subscription = None;
+                }
+                Ok(())
+            }
+            Event::RpcRequest(peer_id, message, _, _) => {
+                bail!("Unexpected rpc request {} from {}", message.name(), peer_id)
+            }
+        }
+    }
+
+    /// Unsubscribes from a silent publisher and subscribes to one of the connected publishers if
+    /// the observer isn't subscribed.
+    pub(crate) async fn check_progress(&mut self) {
+        let timeout = Duration::from_millis(self.config.network_request_timeout_ms);
+        if let Some(subscription) = &self.subscription {
+            if subscription.last_message_time.elapsed()
+                < Duration::from_millis(self.config.subscription_timeout_ms)
+            {
+                return;
+            }
+            let peer_id = subscription.peer_id;
+            warn!(
+                remote_peer = peer_id,
+                "Consensus observer subscription timed out"
+            );
+            self.subscription = None;
+            // best effort, the publisher drops the subscription when it loses the connection
+            let _ = self
+                .network_sender
+                .send_rpc(peer_id, ObserverMessage::UnsubscribeRequest, timeout)
+                .await;
+        }
+
+        let mut publishers: Vec<_> = self.publishers.iter().copied().collect();
+        publishers.shuffle(&mut thread_rng());
+        for peer_id in publishers {
+            match self
+                .network_sender
+                .send_rpc(peer_id, ObserverMessage::SubscribeRequest, timeout)
+                .await
+            {
+                Ok(ObserverMessage::SubscribeResponse(latest_commit)) => {
+                    info!(remote_peer = peer_id, "Consensus observer subscribed");
+                    self.subscription = Some(Subscription {
+                        peer_id,
+                        last_message_time: Instant::now(),
+                    });
+                    // catch up to the publisher before executing the blocks it pushes
+                    if let Err(e) = self.process_commit_decision(*latest_commit).await {
+                        warn!(
+                            remote_peer = peer_id,
+                            error = ?e,
+                            "Failed to catch up to the publisher"
+                        );
+                    }
+                    return;
+                }
+                Ok(response) => {
+                    warn!(
+                        remote_peer = peer_id,
+                        "Unexpected subscribe response {}",
+                        response.name()
+                    );
+                }
+                Err(e) => {
+                    warn!(remote_peer = peer_id, error = ?e, "Failed to subscribe");
+                }
+            }
+        }
+        // no publisher to follow, state sync keeps the node up to date meanwhile
+        self.fall_back_to_state_sync();
+    }
+
+    pub async fn start(mut self, mut network_events: ObserverNetworkEvents) {
+        // state sync keeps the node up to date until the observer subscribes to a publisher
+        self.set_executing(false);
+        self.wait_for_epoch_start().await;
+        info!("Consensus observer starts");
+        let mut interval = tokio::time::interval(Duration::from_millis(
+            self.config.progress_check_interval_ms,
+        ));
+        loop {
+            ::futures::select! {
+                event = network_events.select_next_some() => {
+                    if let Err(e) = self.process_network_event(event).await {
+                        warn!(error = ?e, "Failed to process consensus observer event");
+                    }
+                },
+                reconfig_notification = self.reconfig_events.select_next_some() => {
+                    // the epochs synced by state sync while it's in control
+                    self.process_reconfig_notification(reconfig_notification);
+                },
+                _ = interval.tick().fuse() => {
+                    self.check_progress().await;
+                },
+                complete => break,
+            }
+        }
+        info!("Consensus observer stops");
+    }
+}
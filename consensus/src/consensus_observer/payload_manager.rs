@@ -0,0 +1,94 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::QuorumStoreError, state_replication::PayloadManager};
+use anyhow::anyhow;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use aptos_types::transaction::SignedTransaction;
+use consensus_types::{
+    block::Block,
+    common::{Payload, PayloadFilter},
+    executed_block::ExecutedBlock,
+    proof_of_store::BatchPayload,
+};
+use executor_types::Error as ExecutionError;
+use futures::future::BoxFuture;
+use std::{collections::HashMap, sync::Arc};
+
+/// Resolves the transactions of the blocks executed by a consensus observer. The payloads of
+/// the quorum store batches are pushed along with the ordered blocks and kept until the blocks
+/// referring to them are committed.
+pub struct ObserverPayloadManager {
+    batches: Mutex<HashMap<HashValue, Vec<SignedTransaction>>>,
+}
+
+impl ObserverPayloadManager {
+    pub fn new() -> Self {
+        Self {
+            batches: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Stores the verified payloads of the batches referred to by the ordered blocks.
+    pub fn insert_batches(&self, batch_payloads: Vec<BatchPayload>) {
+        let mut batches = self.batches.lock();
+        for batch_payload in batch_payloads {
+            batches.insert(batch_payload.hash(), batch_payload.into_txns());
+        }
+    }
+
+    /// Removes the batches of the committed blocks, they aren't needed anymore.
+    pub fn remove_committed(&self, blocks: &[Arc<ExecutedBlock>]) {
+        let mut batches = self.batches.lock();
+        for block in blocks {
+            if let Some(Payload::InQuorumStore(proofs)) = block.block().payload() {
+                for proof in proofs {
+                    batches.remove(proof.digest());
+                }
+            }
+        }
+    }
+
+    /// Removes all the batches, e.g. when the pending blocks are dropped to sync.
+    pub fn clear(&self) {
+        self.batches.lock().clear();
+    }
+}
+
+#[async_trait::async_trait]
+impl PayloadManager for ObserverPayloadManager {
+    async fn pull_payload(
+        &self,
+        _max_items: u64,
+        _max_bytes: u64,
+        _exclude: PayloadFilter,
+        _wait_callback: BoxFuture<'static, ()>,
+        _pending_ordering: bool,
+    ) -> Result<Payload, QuorumStoreError> {
+        Err(anyhow!("Consensus observers don't propose blocks").into())
+    }
+
+    async fn get_transactions(
+        &self,
+        block: &Block,
+    ) -> Result<Vec<SignedTransaction>, ExecutionError> {
+        match block.payload() {
+            None => Ok(vec![]),
+            Some(Payload::DirectMempool(txns)) => Ok(txns.clone()),
+            Some(Payload::InQuorumStore(proofs)) => {
+                let batches = self.batches.lock();
+                let mut txns = vec![];
+                for proof in proofs {
+                    let batch_txns = batches.get(proof.digest()).ok_or_else(|| {
+                        ExecutionError::InternalError {
+                            error: format!("Missing the batch of {}", proof),
+                        }
+                    })?;
+                    txns.extend(batch_txns.iter().cloned());
+                }
+                Ok(txns)
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        counters,
+        network::{ObserverMessage, ObserverNetworkEvents, ObserverNetworkSender, OrderedBlock},
+    },
+    error::StateSyncError,
+    state_replication::{PayloadManager, StateComputer, StateComputerCommitCallBackType},
+};
+use anyhow::{anyhow, bail};
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures, PeerId};
+use bytes::Bytes;
+use consensus_types::{
+    block::Block, common::Payload, executed_block::ExecutedBlock, proof_of_store::BatchPayload,
+};
+use executor_types::{Error as ExecutionError, StateComputeResult};
+use futures::{
+    channel::{mpsc, oneshot},
+    StreamExt,
+};
+use network::{
+    protocols::{network::Event, rpc::error::RpcError},
+    ProtocolId,
+};
+use std::{collections::HashSet, sync::Arc};
+use storage_interface::DbReader;
+
+/// The messages consensus asks the publisher to push, in the order they're produced, so that the
+/// subscribers receive the ordered blocks before their commit decision.
+enum PublishRequest {
+    OrderedBlocks(
+        Vec<Arc<ExecutedBlock>>,
+        LedgerInfoWithSignatures,
+        Arc<dyn PayloadManager>,
+    ),
+    CommitDecision(LedgerInfoWithSignatures),
+}
+
+/// Handle used by consensus to push its ordered blocks and commit decisions to the full nodes
+/// subscribed to this validator.
+#[derive(Clone)]
+pub struct ConsensusPublisher {
+    publish_tx: mpsc::UnboundedSender<PublishRequest>,
+}
+
+impl ConsensusPublisher {
+    pub fn new(
+        config: ConsensusObserverConfig,
+        network_sender: ObserverNetworkSender,
+        network_events: ObserverNetworkEvents,
+        db_reader: Arc<dyn DbReader>,
+    ) -> (Self, PublisherTask) {
+        let (publish_tx, publish_rx) = mpsc::unbounded();
+        let task = PublisherTask {
+            max_subscribers: config.max_subscribers,
+            network_sender,
+            network_events,
+            publish_rx,
+            db_reader,
+            subscribers: HashSet::new(),
+        };
+        (Self { publish_tx }, task)
+    }
+
+    /// Publishes the blocks ordered by the given proof, the transactions of their quorum store
+    /// batches are resolved by the payload manager of the epoch.
+    pub fn publish_ordered_blocks(
+        &self,
+        blocks: Vec<Arc<ExecutedBlock>>,
+        ordered_proof: LedgerInfoWithSignatures,
+        payload_manager: Arc<dyn PayloadManager>,
+    ) {
+        self.publish(PublishRequest::OrderedBlocks(
+            blocks,
+            ordered_proof,
+            payload_manager,
+        ));
+    }
+
+    /// Publishes the commit decision of the blocks ordered before.
+    pub fn publish_commit_decision(&self, commit_proof: LedgerInfoWithSignatures) {
+        self.publish(PublishRequest::CommitDecision(commit_proof));
+    }
+
+    fn publish(&self, request: PublishRequest) {
+        if self.publish_tx.unbounded_send(request).is_err() {
+            warn!("Consensus publisher stopped, the message is dropped");
+        }
+    }
+}
+
+/// Serves the subscriptions of the full nodes and pushes the messages published by consensus to
+/// the subscribers.
+pub struct PublisherTask {
+    max_subscribers: usize,
+    network_sender: ObserverNetworkSender,
+    network_events: ObserverNetworkEvents,
+    publish_rx: mpsc::UnboundedReceiver<PublishRequest>,
+    db_reader: Arc<dyn DbReader>,
+    subscribers: HashSet<PeerId>,
+}
+
+impl PublisherTask {
+    async fn create_ordered_block(
+        blocks: Vec<Arc<ExecutedBlock>>,
+        ordered_proof: LedgerInfoWithSignatures,
+        payload_manager: Arc<dyn PayloadManager>,
+    ) -> Result<OrderedBlock, ExecutionError> {
+        let mut batch_payloads = vec![];
+        for block in &blocks {
+            if let Some(Payload::InQuorumStore(proofs)) = block.block().payload() {
+                // the transactions of the batches are concatenated in the order of the proofs
+                let mut txns = payload_manager
+                    .get_transactions(block.block())
+                    .await?
+                    .into_iter();
+                for proof in proofs {
+                    let num_txns = proof.info().num_txns as usize;
+                    batch_payloads.push(BatchPayload::new(txns.by_ref().take(num_txns).collect()));
+                }
+            }
+        }
+        let blocks: Vec<Block> = blocks.iter().map(|block| block.block().clone()).collect();
+        Ok(OrderedBlock::new(blocks, batch_payloads, ordered_proof))
+    }
+
+    async fn process_publish_request(&self, request: PublishRequest) {
+        if self.subscribers.is_empty() {
+            return;
+        }
+        let message = match request {
+            PublishRequest::OrderedBlocks(blocks, ordered_proof, payload_manager) => {
+                match Self::create_ordered_block(blocks, ordered_proof, payload_manager).await {
+                    Ok(ordered_block) => ObserverMessage::OrderedBlock(Box::new(ordered_block)),
+                    Err(e) => {
+                        // the subscribers sync to the commit decision instead
+                        warn!(error = ?e, "Failed to resolve the payloads of the ordered blocks");
+                        return;
+                    }
+                }
+            }
+            PublishRequest::CommitDecision(commit_proof) => {
+                ObserverMessage::CommitDecision(Box::new(commit_proof))
+            }
+        };
+        counters::PUBLISHED_MESSAGES
+            .with_label_values(&[message.name()])
+            .inc();
+        if let Err(e) = self
+            .network_sender
+            .send_to_many(self.subscribers.iter().copied(), message)
+        {
+            warn!(error = ?e, "Failed to publish to the subscribers");
+        }
+    }
+
+    fn process_rpc_request(
+        &mut self,
+        peer_id: PeerId,
+        message: ObserverMessage,
+        protocol: ProtocolId,
+        response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+    ) -> anyhow::Result<()> {
+        let response = match message {
+            ObserverMessage::SubscribeRequest => {
+                if !self.subscribers.contains(&peer_id)
+                    && self.subscribers.len() >= self.max_subscribers
+                {
+                    response_sender
+                        .send(Err(RpcError::ApplicationError(anyhow!(
+                            "Too many subscribers"
+                        ))))
+                        .map_err(|_| anyhow!("Failed to reject the subscription"))?;
+                    bail!("Too many subscribers, rejected the subscription");
+                }
+                let latest_commit = self.db_reader.get_latest_ledger_info()?;
+                if self.subscribers.insert(peer_id) {
+                    info!(remote_peer = peer_id, "New consensus observer subscriber");
+                }
+                ObserverMessage::SubscribeResponse(Box::new(latest_commit))
+            }
+            ObserverMessage::UnsubscribeRequest => {
+                if self.subscribers.remove(&peer_id) {
+                    info!(remote_peer = peer_id, "Consensus observer unsubscribed");
+                }
+                ObserverMessage::UnsubscribeResponse
+            }
+            _ => bail!("Unexpected rpc request {}", message.name()),
+        };
+        counters::NUM_SUBSCRIBERS.set(self.subscribers.len() as i64);
+        let response_bytes = protocol.to_bytes(&response)?;
+        response_sender
+            .send(Ok(response_bytes.into()))
+            .map_err(|_| anyhow!("Failed to send the {} response", response.name()))
+    }
+
+    fn process_network_event(&mut self, event: Event<ObserverMessage>) -> anyhow::Result<()> {
+        match event {
+            Event::RpcRequest(peer_id, message, protocol, response_sender) => {
+                self.process_rpc_request(peer_id, message, protocol, response_sender)
+            }
+            Event::LostPeer(metadata) => {
+                if self.subscribers.remove(&metadata.remote_peer_id) {
+                    counters::NUM_SUBSCRIBERS.set(self.subscribers.len() as i64);
+                }
+                Ok(())
+            }
+            Event::Message(peer_id, message) => {
+                bail!("Unexpected message {} from {}", message.name(), peer_id)
+            }
+            Event::NewPeer(_) => Ok(()),
+        }
+    }
+
+    pub async fn start(mut self) {
+        info!("Consensus publisher starts");
+        loop {
+            ::futures::select! {
+                event = self.network_events.select_next_some() => {
+                    if let Err(e) = self.process_network_event(event) {
+                        warn!(error = ?e, "Failed to process consensus observer event");
+                    }
+                },
+                request = self.publish_rx.select_next_some() => {
+                    self.process_publish_request(request).await;
+                },
+                complete => break,
+            }
+        }
+        info!("Consensus publisher stops");
+    }
+}
+
+/// Publishes the blocks consensus sends to the wrapped state computer to commit, and their
+/// commit decisions once they're committed.
+pub struct PublishingStateComputer {
+    inner: Arc<dyn StateComputer>,
+    publisher: ConsensusPublisher,
+    payload_manager: Arc<dyn PayloadManager>,
+}
+
+impl PublishingStateComputer {
+    pub fn new(
+        inner: Arc<dyn StateComputer>,
+        publisher: ConsensusPublisher,
+        payload_manager: Arc<dyn PayloadManager>,
+    ) -> Self {
+        Self {
+            inner,
+            publisher,
+            payload_manager,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl StateComputer for PublishingStateComputer {
+    async fn compute(
+        &self,
+        block: &Block,
+        parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, ExecutionError> {
+        self.inner.compute(block, parent_block_id).await
+    }
+
+    async fn commit(
+        &self,
+        blocks: &[Arc<ExecutedBlock>],
+        finality_proof: LedgerInfoWithSignatures,
+        callback: StateComputerCommitCallBackType,
+    ) -> Result<(), ExecutionError> {
+        self.publisher.publish_ordered_blocks(
+            blocks.to_vec(),
+            finality_proof.clone(),
+            self.payload_manager.clone(),
+        );
+        let publisher = self.publisher.clone();
+        let callback = Box::new(
+            move |committed_blocks: &[Arc<ExecutedBlock>],
+                  commit_decision: LedgerInfoWithSignatures| {
+                publisher.publish_commit_decision(commit_decision.clone());
+                callback(committed_blocks, commit_decision);
+            },
+        );
+        self.inner.commit(blocks, finality_proof, callback).await
+    }
+
+    async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+        self.inner.sync_to(target).await
+    }
+
+    fn new_epoch(&self, epoch_state: &EpochState, payload_manager: Arc<dyn PayloadManager>) {
+        self.inner.new_epoch(epoch_state, payload_manager)
+    }
+}
@@ -0,0 +1,697 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        network::{ObserverMessage, ObserverNetworkSender, OrderedBlock},
+        observer::ConsensusObserver,
+        payload_manager::ObserverPayloadManager,
+    },
+    error::StateSyncError,
+    state_replication::{PayloadManager, StateComputer, StateComputerCommitCallBackType},
+};
+use aptos_config::config::ConsensusObserverConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_types::{
+    aggregate_signature::{AggregateSignature, PartialSignatures},
+    block_info::{BlockInfo, Round},
+    epoch_state::EpochState,
+    ledger_info::{generate_ledger_info_with_sig, LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::{OnChainConfig, OnChainConfigPayload, ValidatorSet},
+    validator_info::ValidatorInfo,
+    validator_signer::ValidatorSigner,
+    validator_verifier::{random_validator_verifier, ValidatorVerifier},
+    PeerId,
+};
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_types::{
+    block::{
+        block_test_utils::{
+            certificate_for_genesis, placeholder_certificate_for_block, random_txns,
+        },
+        Block,
+    },
+    common::Payload,
+    executed_block::ExecutedBlock,
+    proof_of_store::{Batch, BatchPayload, LogicalTime, ProofOfStore},
+};
+use event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use executor_types::{Error as ExecutionError, StateComputeResult};
+use futures::StreamExt;
+use network::{
+    peer_manager::{ConnectionRequestSender, PeerManagerRequest, PeerManagerRequestSender},
+    protocols::network::{Event, NewNetworkSender},
+    transport::ConnectionMetadata,
+    ProtocolId,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use storage_interface::DbReader;
+
+fn create_batch(signer: &ValidatorSigner, num_txns: usize) -> Batch {
+    Batch::new(
+        1,
+        signer.author(),
+        LogicalTime::new(1, 10),
+        random_txns(num_txns),
+    )
+}
+
+fn create_proof(
+    signers: &[ValidatorSigner],
+    validators: &ValidatorVerifier,
+    batch: &Batch,
+) -> ProofOfStore {
+    let mut signatures = PartialSignatures::empty();
+    for signer in signers {
+        signatures.add_signature(signer.author(), signer.sign(&batch.info()).unwrap());
+    }
+    ProofOfStore::new(
+        batch.info(),
+        validators.aggregate_signatures(&signatures).unwrap(),
+    )
+}
+
+fn batch_payload(batch: &Batch) -> BatchPayload {
+    BatchPayload::new(batch.txns().to_vec())
+}
+
+/// Creates two chained blocks, the second one referring to the given batches, ordered by a
+/// proof signed by all the signers.
+fn create_ordered_block(
+    signers: &[ValidatorSigner],
+    validators: &ValidatorVerifier,
+    batches: &[Batch],
+    batch_payloads: Vec<BatchPayload>,
+) -> OrderedBlock {
+    let genesis = Block::make_genesis_block();
+    let first_block = Block::new_proposal(
+        Payload::DirectMempool(random_txns(2)),
+        1,
+        1,
+        certificate_for_genesis(),
+        &signers[0],
+        vec![],
+    )
+    .unwrap();
+    let proofs = batches
+        .iter()
+        .map(|batch| create_proof(signers, validators, batch))
+        .collect();
+    let second_block = Block::new_proposal(
+        Payload::InQuorumStore(proofs),
+        2,
+        2,
+        placeholder_certificate_for_block(signers, first_block.id(), 1, genesis.id(), 0),
+        &signers[1],
+        vec![],
+    )
+    .unwrap();
+    let ordered_proof = generate_ledger_info_with_sig(
+        signers,
+        LedgerInfo::new(
+            second_block.gen_block_info(HashValue::zero(), 0, None),
+            HashValue::zero(),
+        ),
+    );
+    OrderedBlock::new(
+        vec![first_block, second_block],
+        batch_payloads,
+        ordered_proof,
+    )
+}
+
+#[test]
+fn test_ordered_block_verify() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let batches = vec![create_batch(&signers[0], 3), create_batch(&signers[1], 2)];
+    let batch_payloads: Vec<_> = batches.iter().map(batch_payload).collect();
+
+    let ordered_block =
+        create_ordered_block(&signers, &validators, &batches, batch_payloads.clone());
+    assert!(ordered_block.verify(&validators).is_ok());
+
+    // the batch payloads must match the proofs of store, in order
+    let mut reordered_payloads = batch_payloads.clone();
+    reordered_payloads.reverse();
+    assert!(
+        create_ordered_block(&signers, &validators, &batches, reordered_payloads)
+            .verify(&validators)
+            .is_err()
+    );
+    assert!(create_ordered_block(
+        &signers,
+        &validators,
+        &batches,
+        batch_payloads[..1].to_vec()
+    )
+    .verify(&validators)
+    .is_err());
+
+    // the blocks must be chained and end at the block of the ordered proof
+    let (blocks, _) = ordered_block.clone().into_parts();
+    let reversed_blocks = vec![blocks[1].clone(), blocks[0].clone()];
+    assert!(OrderedBlock::new(
+        reversed_blocks,
+        batch_payloads.clone(),
+        ordered_block.ordered_proof().clone()
+    )
+    .verify(&validators)
+    .is_err());
+    let first_block_only = vec![blocks[0].clone()];
+    assert!(OrderedBlock::new(
+        first_block_only,
+        vec![],
+        ordered_block.ordered_proof().clone()
+    )
+    .verify(&validators)
+    .is_err());
+
+    // the ordered proof must be signed by the validators of the epoch
+    let (_, other_validators) = random_validator_verifier(4, None, true);
+    assert!(ordered_block.verify(&other_validators).is_err());
+}
+
+#[tokio::test]
+async fn test_payload_manager_get_transactions() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let batches = vec![create_batch(&signers[0], 3), create_batch(&signers[1], 2)];
+    let ordered_block = create_ordered_block(
+        &signers,
+        &validators,
+        &batches,
+        batches.iter().map(batch_payload).collect(),
+    );
+    let (blocks, batch_payloads) = ordered_block.into_parts();
+    let payload_manager = ObserverPayloadManager::new();
+
+    // the batches aren't known before they're pushed with the ordered blocks
+    assert!(payload_manager.get_transactions(&blocks[1]).await.is_err());
+
+    payload_manager.insert_batches(batch_payloads);
+    let expected_txns: Vec<_> = batches
+        .iter()
+        .flat_map(|batch| batch.txns().iter().cloned())
+        .collect();
+    assert_eq!(
+        payload_manager.get_transactions(&blocks[1]).await.unwrap(),
+        expected_txns
+    );
+    assert_eq!(
+        payload_manager
+            .get_transactions(&blocks[0])
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+
+    // the batches of the committed blocks are removed
+    let executed_blocks: Vec<_> = blocks
+        .into_iter()
+        .map(|block| Arc::new(ExecutedBlock::new(block, StateComputeResult::new_dummy())))
+        .collect();
+    payload_manager.remove_committed(&executed_blocks);
+    assert!(payload_manager
+        .get_transactions(executed_blocks[1].block())
+        .await
+        .is_err());
+}
+
+/// The latest ledger info of the observed node, as committed by the observer or state sync.
+struct TestDbReader {
+    latest_ledger_info: Mutex<LedgerInfoWithSignatures>,
+}
+
+impl TestDbReader {
+    fn set_latest_ledger_info(&self, ledger_info: LedgerInfoWithSignatures) {
+        *self.latest_ledger_info.lock() = ledger_info;
+    }
+}
+
+impl DbReader for TestDbReader {
+    fn get_latest_ledger_info_option(&self) -> anyhow::Result<Option<LedgerInfoWithSignatures>> {
+        Ok(Some(self.latest_ledger_info.lock().clone()))
+    }
+}
+
+/// Records the blocks executed and committed by the observer, and the targets it syncs to.
+struct TestExecutionClient {
+    db_reader: Arc<TestDbReader>,
+    computed_rounds: Mutex<Vec<Round>>,
+    committed_rounds: Mutex<Vec<Round>>,
+    sync_targets: Mutex<Vec<LedgerInfoWithSignatures>>,
+}
+
+#[async_trait::async_trait]
+impl StateComputer for TestExecutionClient {
+    async fn compute(
+        &self,
+        block: &Block,
+        _parent_block_id: HashValue,
+    ) -> Result<StateComputeResult, ExecutionError> {
+        self.computed_rounds.lock().push(block.round());
+        Ok(StateComputeResult::new_dummy())
+    }
+
+    async fn commit(
+        &self,
+        blocks: &[Arc<ExecutedBlock>],
+        finality_proof: LedgerInfoWithSignatures,
+        callback: StateComputerCommitCallBackType,
+    ) -> Result<(), ExecutionError> {
+        self.committed_rounds
+            .lock()
+            .extend(blocks.iter().map(|block| block.round()));
+        self.db_reader
+            .set_latest_ledger_info(finality_proof.clone());
+        callback(blocks, finality_proof);
+        Ok(())
+    }
+
+    async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError> {
+        self.sync_targets.lock().push(target.clone());
+        self.db_reader.set_latest_ledger_info(target);
+        Ok(())
+    }
+
+    fn new_epoch(&self, _: &EpochState, _: Arc<dyn PayloadManager>) {}
+}
+
+/// Creates the signers of validators other than the ones of `random_validator_verifier`.
+fn create_other_signers() -> Vec<ValidatorSigner> {
+    (0..4)
+        .map(|i| ValidatorSigner::random([i + 10; 32]))
+        .collect()
+}
+
+fn create_validator_set(signers: &[ValidatorSigner]) -> ValidatorSet {
+    ValidatorSet::new(
+        signers
+            .iter()
+            .enumerate()
+            .map(|(index, signer)| {
+                ValidatorInfo::new_with_test_network_keys(
+                    signer.author(),
+                    signer.public_key(),
+                    1,
+                    index as u64,
+                )
+            })
+            .collect(),
+    )
+}
+
+fn create_reconfig_notification(epoch: u64, signers: &[ValidatorSigner]) -> ReconfigNotification {
+    let mut configs = HashMap::new();
+    configs.insert(
+        ValidatorSet::CONFIG_ID,
+        bcs::to_bytes(&create_validator_set(signers)).unwrap(),
+    );
+    ReconfigNotification {
+        version: epoch,
+        on_chain_configs: OnChainConfigPayload::new(epoch, Arc::new(configs)),
+    }
+}
+
+/// Creates the blocks of the given rounds of epoch 1, chained to the given parent (or to the
+/// genesis block if there's none).
+fn create_blocks(
+    signers: &[ValidatorSigner],
+    parent: Option<&Block>,
+    rounds: std::ops::RangeInclusive<Round>,
+) -> Vec<Block> {
+    let genesis = Block::make_genesis_block();
+    let mut blocks: Vec<Block> = vec![];
+    for round in rounds {
+        let parent = blocks.last().or(parent);
+        let quorum_cert = match parent {
+            Some(parent) => placeholder_certificate_for_block(
+                signers,
+                parent.id(),
+                parent.round(),
+                genesis.id(),
+                0,
+            ),
+            None => certificate_for_genesis(),
+        };
+        let block = Block::new_proposal(
+            Payload::DirectMempool(random_txns(1)),
+            round,
+            round,
+            quorum_cert,
+            &signers[0],
+            vec![],
+        )
+        .unwrap();
+        blocks.push(block);
+    }
+    blocks
+}
+
+/// The ordered blocks message of the given blocks, ordered by a proof signed by the signers.
+fn create_ordered_blocks_message(
+    signers: &[ValidatorSigner],
+    blocks: Vec<Block>,
+) -> ObserverMessage {
+    let last_block = blocks.last().unwrap();
+    let ordered_proof = generate_ledger_info_with_sig(
+        signers,
+        LedgerInfo::new(
+            last_block.gen_block_info(HashValue::zero(), 0, None),
+            HashValue::zero(),
+        ),
+    );
+    ObserverMessage::OrderedBlock(Box::new(OrderedBlock::new(blocks, vec![], ordered_proof)))
+}
+
+/// The commit decision of the given block, as executed by the test execution client.
+fn create_commit_decision(signers: &[ValidatorSigner], block: &Block) -> LedgerInfoWithSignatures {
+    let executed_block = ExecutedBlock::new(block.clone(), StateComputeResult::new_dummy());
+    generate_ledger_info_with_sig(
+        signers,
+        LedgerInfo::new(executed_block.block_info(), HashValue::zero()),
+    )
+}
+
+/// A consensus observer of a node at genesis, with a publisher answering its subscriptions with
+/// the latest commit decision set by the test.
+struct TestContext {
+    signers: Vec<ValidatorSigner>,
+    observer: ConsensusObserver,
+    db_reader: Arc<TestDbReader>,
+    execution_client: Arc<TestExecutionClient>,
+    executing: Arc<AtomicBool>,
+    reconfig_sender: aptos_channel::Sender<(), ReconfigNotification>,
+    publisher: PeerId,
+    publisher_latest_commit: Arc<Mutex<LedgerInfoWithSignatures>>,
+}
+
+impl TestContext {
+    async fn new() -> Self {
+        let (signers, _) = random_validator_verifier(4, None, false);
+        let genesis_ledger_info = LedgerInfoWithSignatures::new(
+            LedgerInfo::mock_genesis(None),
+            AggregateSignature::empty(),
+        );
+        let db_reader = Arc::new(TestDbReader {
+            latest_ledger_info: Mutex::new(genesis_ledger_info.clone()),
+        });
+        let execution_client = Arc::new(TestExecutionClient {
+            db_reader: db_reader.clone(),
+            computed_rounds: Mutex::new(vec![]),
+            committed_rounds: Mutex::new(vec![]),
+            sync_targets: Mutex::new(vec![]),
+        });
+
+        // the publisher answers the subscription requests with its latest commit decision
+        let publisher = PeerId::random();
+        let publisher_latest_commit = Arc::new(Mutex::new(genesis_ledger_info));
+        let (network_reqs_tx, mut network_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+        let latest_commit = publisher_latest_commit.clone();
+        tokio::spawn(async move {
+            while let Some(request) = network_reqs_rx.next().await {
+                if let PeerManagerRequest::SendRpc(_, rpc_request) = request {
+                    let response = match rpc_request.protocol_id.from_bytes(&rpc_request.data) {
+                        Ok(ObserverMessage::SubscribeRequest) => {
+                            ObserverMessage::SubscribeResponse(Box::new(
+                                latest_commit.lock().clone(),
+                            ))
+                        }
+                        _ => ObserverMessage::UnsubscribeResponse,
+                    };
+                    let response_bytes = rpc_request.protocol_id.to_bytes(&response).unwrap();
+                    let _ = rpc_request.res_tx.send(Ok(response_bytes.into()));
+                }
+            }
+        });
+        let network_sender = ObserverNetworkSender::new(
+            PeerManagerRequestSender::new(network_reqs_tx),
+            ConnectionRequestSender::new(connection_reqs_tx),
+        );
+
+        let (reconfig_sender, reconfig_receiver) = aptos_channel::new(QueueStyle::KLAST, 1, None);
+        reconfig_sender
+            .push((), create_reconfig_notification(1, &signers))
+            .unwrap();
+        let executing = Arc::new(AtomicBool::new(false));
+        let mut observer = ConsensusObserver::new(
+            ConsensusObserverConfig::default(),
+            network_sender,
+            ReconfigNotificationListener {
+                notification_receiver: reconfig_receiver,
+            },
+            db_reader.clone(),
+            execution_client.clone(),
+            executing.clone(),
+        );
+        observer.wait_for_epoch_start().await;
+
+        Self {
+            signers,
+            observer,
+            db_reader,
+            execution_client,
+            executing,
+            reconfig_sender,
+            publisher,
+            publisher_latest_commit,
+        }
+    }
+
+    fn is_observer_executing(&self) -> bool {
+        self.executing.load(Ordering::Acquire)
+    }
+
+    fn sync_targets(&self) -> Vec<LedgerInfoWithSignatures> {
+        self.execution_client.sync_targets.lock().clone()
+    }
+
+    /// Connects the publisher and subscribes to it, the publisher answering with the given
+    /// latest commit decision.
+    async fn subscribe(&mut self, latest_commit: LedgerInfoWithSignatures) {
+        *self.publisher_latest_commit.lock() = latest_commit;
+        let mut connection_metadata = ConnectionMetadata::mock(self.publisher);
+        connection_metadata.application_protocols =
+            [ProtocolId::ConsensusObserverRpc].iter().collect();
+        self.observer
+            .process_network_event(Event::NewPeer(connection_metadata))
+            .await
+            .unwrap();
+        self.observer.check_progress().await;
+    }
+
+    async fn process_message(&mut self, message: ObserverMessage) -> anyhow::Result<()> {
+        self.observer
+            .process_network_event(Event::Message(self.publisher, message))
+            .await
+    }
+}
+
+#[tokio::test]
+async fn test_observer_takes_over_from_state_sync() {
+    let mut context = TestContext::new().await;
+    assert!(!context.is_observer_executing());
+
+    // the publisher is at the (verified) commit decision of block 2, the observer syncs to it
+    // and executes the following blocks
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    let latest_commit = create_commit_decision(&context.signers, &blocks[1]);
+    context.subscribe(latest_commit.clone()).await;
+    assert!(context.is_observer_executing());
+    assert_eq!(context.sync_targets(), vec![latest_commit.clone()]);
+    assert_eq!(
+        context.db_reader.get_latest_ledger_info().unwrap(),
+        latest_commit
+    );
+}
+
+#[tokio::test]
+async fn test_observer_ignores_unverified_commit_decisions() {
+    let mut context = TestContext::new().await;
+
+    // a commit decision that isn't signed by the validators of the epoch is never synced to
+    let other_signers = create_other_signers();
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    context
+        .subscribe(create_commit_decision(&other_signers, &blocks[1]))
+        .await;
+    assert!(!context.is_observer_executing());
+    assert!(context.sync_targets().is_empty());
+
+    let commit_decision = create_commit_decision(&other_signers, &blocks[1]);
+    assert!(context
+        .process_message(ObserverMessage::CommitDecision(Box::new(commit_decision)))
+        .await
+        .is_err());
+    assert!(!context.is_observer_executing());
+    assert!(context.sync_targets().is_empty());
+}
+
+#[tokio::test]
+async fn test_observer_executes_and_commits_ordered_blocks() {
+    let mut context = TestContext::new().await;
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    context
+        .subscribe(create_commit_decision(&context.signers, &blocks[1]))
+        .await;
+
+    // the blocks extending the root are executed
+    let new_blocks = create_blocks(&context.signers, Some(&blocks[1]), 3..=4);
+    let message = create_ordered_blocks_message(&context.signers, new_blocks.clone());
+    context.process_message(message.clone()).await.unwrap();
+    assert_eq!(*context.execution_client.computed_rounds.lock(), vec![3, 4]);
+
+    // the same blocks aren't executed twice
+    context.process_message(message).await.unwrap();
+    assert_eq!(*context.execution_client.computed_rounds.lock(), vec![3, 4]);
+
+    // the commit decision commits the executed blocks, without syncing
+    let commit_decision = create_commit_decision(&context.signers, &new_blocks[1]);
+    context
+        .process_message(ObserverMessage::CommitDecision(Box::new(
+            commit_decision.clone(),
+        )))
+        .await
+        .unwrap();
+    assert_eq!(
+        *context.execution_client.committed_rounds.lock(),
+        vec![3, 4]
+    );
+    assert_eq!(context.sync_targets().len(), 1);
+    assert_eq!(
+        context.db_reader.get_latest_ledger_info().unwrap(),
+        commit_decision
+    );
+}
+
+#[tokio::test]
+async fn test_observer_syncs_to_missed_blocks() {
+    let mut context = TestContext::new().await;
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    context
+        .subscribe(create_commit_decision(&context.signers, &blocks[1]))
+        .await;
+
+    // the blocks that don't extend the last ordered block (block 3 is missed) aren't executed
+    let new_blocks = create_blocks(&context.signers, Some(&blocks[1]), 3..=5);
+    let message = create_ordered_blocks_message(&context.signers, new_blocks[1..].to_vec());
+    assert!(context.process_message(message).await.is_err());
+    assert!(context.execution_client.computed_rounds.lock().is_empty());
+
+    // the observer syncs to the commit decision of the missed blocks
+    let commit_decision = create_commit_decision(&context.signers, &new_blocks[2]);
+    context
+        .process_message(ObserverMessage::CommitDecision(Box::new(
+            commit_decision.clone(),
+        )))
+        .await
+        .unwrap();
+    assert!(context.execution_client.committed_rounds.lock().is_empty());
+    assert_eq!(context.sync_targets().last(), Some(&commit_decision));
+    assert!(context.is_observer_executing());
+}
+
+#[tokio::test]
+async fn test_observer_rejects_empty_ordered_blocks() {
+    let mut context = TestContext::new().await;
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    context
+        .subscribe(create_commit_decision(&context.signers, &blocks[1]))
+        .await;
+
+    // an ordered block message without blocks is rejected (instead of panicking)
+    let ordered_proof = create_commit_decision(&context.signers, &blocks[1]);
+    let message =
+        ObserverMessage::OrderedBlock(Box::new(OrderedBlock::new(vec![], vec![], ordered_proof)));
+    assert!(context.process_message(message).await.is_err());
+    assert!(context.execution_client.computed_rounds.lock().is_empty());
+}
+
+#[tokio::test]
+async fn test_observer_epoch_change() {
+    let mut context = TestContext::new().await;
+
+    // the publisher is in the next epoch, whose validators aren't known yet: the observer
+    // doesn't sync to its commit decision and leaves the node to state sync
+    let next_signers = create_other_signers();
+    let next_epoch_commit = generate_ledger_info_with_sig(
+        &next_signers,
+        LedgerInfo::new(
+            BlockInfo::new(2, 5, HashValue::random(), HashValue::zero(), 10, 5, None),
+            HashValue::zero(),
+        ),
+    );
+    context.subscribe(next_epoch_commit.clone()).await;
+    assert!(!context.is_observer_executing());
+    assert!(context.sync_targets().is_empty());
+
+    // state sync reaches the end of epoch 1
+    let epoch_ending_ledger_info = LedgerInfoWithSignatures::new(
+        LedgerInfo::new(
+            BlockInfo::new(
+                1,
+                3,
+                HashValue::random(),
+                HashValue::zero(),
+                5,
+                3,
+                Some(EpochState {
+                    epoch: 2,
+                    verifier: (&create_validator_set(&next_signers)).into(),
+                }),
+            ),
+            HashValue::zero(),
+        ),
+        AggregateSignature::empty(),
+    );
+    context
+        .db_reader
+        .set_latest_ledger_info(epoch_ending_ledger_info);
+    context
+        .reconfig_sender
+        .push((), create_reconfig_notification(2, &next_signers))
+        .unwrap();
+
+    // the commit decisions of epoch 2 are now verified, the observer takes over
+    context
+        .process_message(ObserverMessage::CommitDecision(Box::new(
+            next_epoch_commit.clone(),
+        )))
+        .await
+        .unwrap();
+    assert!(context.is_observer_executing());
+    assert_eq!(context.sync_targets(), vec![next_epoch_commit]);
+}
+
+#[tokio::test]
+async fn test_observer_falls_back_without_publisher() {
+    let mut context = TestContext::new().await;
+    let blocks = create_blocks(&context.signers, None, 1..=2);
+    context
+        .subscribe(create_commit_decision(&context.signers, &blocks[1]))
+        .await;
+    assert!(context.is_observer_executing());
+
+    // the publisher disconnects: the node is handed back to state sync
+    context
+        .observer
+        .process_network_event(Event::LostPeer(ConnectionMetadata::mock(context.publisher)))
+        .await
+        .unwrap();
+    context.observer.check_progress().await;
+    assert!(!context.is_observer_executing());
+
+    // the messages of the former publisher are ignored
+    let new_blocks = create_blocks(&context.signers, Some(&blocks[1]), 3..=3);
+    let message = create_ordered_blocks_message(&context.signers, new_blocks);
+    assert!(context.process_message(message).await.is_err());
+    assert!(context.execution_client.computed_rounds.lock().is_empty());
+}
@@ -0,0 +1,5 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+#[cfg(test)]
+mod consensus_observer_test;
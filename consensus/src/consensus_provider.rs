@@ -2,7 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    commit_notifier::QuorumStoreCommitNotifier,
+    commit_notifier::{EmptyCommitNotifier, QuorumStoreCommitNotifier},
+    consensus_observer::{
+        network::{ObserverNetworkEvents, ObserverNetworkSender},
+        observer::ConsensusObserver,
+        publisher::ConsensusPublisher,
+    },
     counters,
     epoch_manager::EpochManager,
     network::NetworkTask,
@@ -17,18 +22,22 @@ use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_vm::AptosVM;
 use consensus_notifications::ConsensusNotificationSender;
+use consensus_types::evidence::EvidenceReader;
 use event_notifications::ReconfigNotificationListener;
 use executor::block_executor::BlockExecutor;
 use futures::channel::mpsc;
 use network::application::storage::PeerMetadataStorage;
 use std::sync::{
-    atomic::{AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
 use storage_interface::DbReaderWriter;
 use tokio::runtime::{self, Runtime};
 
-/// Helper function to start consensus based on configuration and return the runtime
+/// Helper function to start consensus based on configuration and return the runtime.
+/// The ordered blocks are published to the consensus observers if the observer network
+/// handles are given, and the evidence of misbehaving validators consensus records is read
+/// through the evidence reader.
 pub fn start_consensus(
     node_config: &NodeConfig,
     mut network_sender: ConsensusNetworkSender,
@@ -38,18 +47,12 @@ pub fn start_consensus(
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener,
     peer_metadata_storage: Arc<PeerMetadataStorage>,
+    observer_network_handles: Option<(ObserverNetworkSender, ObserverNetworkEvents)>,
+    evidence_reader: EvidenceReader,
 ) -> Runtime {
-    let runtime = runtime::Builder::new_multi_thread()
-        .thread_name_fn(|| {
-            static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
-            let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
-            format!("consensus-{}", id)
-        })
-        .disable_lifo_slot()
-        .enable_all()
-        .build()
-        .expect("Failed to create Tokio runtime!");
+    let runtime = create_runtime("consensus");
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
+    evidence_reader.set_store(storage.clone());
     let txn_notifier = Arc::new(MempoolNotifier::new(
         consensus_to_mempool_sender.clone(),
         node_config.consensus.mempool_executed_txn_timeout_ms,
@@ -58,6 +61,18 @@ pub fn start_consensus(
         node_config.consensus.quorum_store_pull_timeout_ms,
     ));
 
+    let consensus_publisher =
+        observer_network_handles.map(|(observer_network_sender, observer_network_events)| {
+            let (consensus_publisher, publisher_task) = ConsensusPublisher::new(
+                node_config.consensus.observer.clone(),
+                observer_network_sender,
+                observer_network_events,
+                aptos_db.reader.clone(),
+            );
+            runtime.spawn(publisher_task.start());
+            consensus_publisher
+        });
+
     let state_computer = Arc::new(ExecutionProxy::new(
         Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
         txn_notifier,
@@ -83,6 +98,7 @@ pub fn start_consensus(
         storage,
         reconfig_events,
         commit_notifier,
+        consensus_publisher,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
@@ -93,3 +109,57 @@ pub fn start_consensus(
     debug!("Consensus started.");
     runtime
 }
+
+/// Helper function to start the consensus observer of a full node and return the runtime.
+/// `observer_executing` is shared with state sync, which continuously syncs the node while the
+/// observer doesn't execute the blocks.
+pub fn start_consensus_observer(
+    node_config: &NodeConfig,
+    network_sender: ObserverNetworkSender,
+    network_events: ObserverNetworkEvents,
+    state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+    consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
+    aptos_db: DbReaderWriter,
+    reconfig_events: ReconfigNotificationListener,
+    observer_executing: Arc<AtomicBool>,
+) -> Runtime {
+    let runtime = create_runtime("consensus-observer");
+    let txn_notifier = Arc::new(MempoolNotifier::new(
+        consensus_to_mempool_sender,
+        node_config.consensus.mempool_executed_txn_timeout_ms,
+    ));
+    let db_reader = aptos_db.reader.clone();
+    let execution_client = Arc::new(ExecutionProxy::new(
+        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        txn_notifier,
+        state_sync_notifier,
+        Arc::new(EmptyCommitNotifier),
+        runtime.handle(),
+    ));
+
+    let consensus_observer = ConsensusObserver::new(
+        node_config.consensus.observer.clone(),
+        network_sender,
+        reconfig_events,
+        db_reader,
+        execution_client,
+        observer_executing,
+    );
+    runtime.spawn(consensus_observer.start(network_events));
+
+    debug!("Consensus observer started.");
+    runtime
+}
+
+fn create_runtime(thread_name: &'static str) -> Runtime {
+    runtime::Builder::new_multi_thread()
+        .thread_name_fn(move || {
+            static ATOMIC_ID: AtomicUsize = AtomicUsize::new(0);
+            let id = ATOMIC_ID.fetch_add(1, Ordering::SeqCst);
+            format!("{}-{}", thread_name, id)
+        })
+        .disable_lifo_slot()
+        .enable_all()
+        .build()
+        .expect("Failed to create Tokio runtime!")
+}
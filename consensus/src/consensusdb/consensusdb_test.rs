@@ -3,7 +3,12 @@
 
 use super::*;
 use aptos_temppath::TempPath;
-use consensus_types::block::block_test_utils::certificate_for_genesis;
+use aptos_types::{account_address::AccountAddress, validator_signer::ValidatorSigner};
+use consensus_types::{
+    block::block_test_utils::{certificate_for_genesis, random_txns},
+    common::Payload,
+    proof_of_store::LogicalTime,
+};
 
 #[test]
 fn test_put_get() {
@@ -69,3 +74,86 @@ fn test_delete_block_and_qc() {
     assert_eq!(db.get_blocks().unwrap().len(), 0);
     assert_eq!(db.get_quorum_certificates().unwrap().len(), 0);
 }
+
+#[test]
+fn test_put_get_delete_batches() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    assert_eq!(db.get_batches().unwrap().len(), 0);
+
+    let batches: Vec<_> = (0..3)
+        .map(|i| {
+            Batch::new(
+                1,
+                AccountAddress::random(),
+                LogicalTime::new(1, 10 + i),
+                random_txns(5),
+            )
+        })
+        .collect();
+    db.save_batches(batches.clone()).unwrap();
+    let saved = db.get_batches().unwrap();
+    assert_eq!(saved.len(), 3);
+    for batch in &batches {
+        assert_eq!(saved.get(&batch.digest()), Some(batch));
+    }
+
+    db.delete_batches(vec![batches[0].digest(), batches[2].digest()])
+        .unwrap();
+    let saved = db.get_batches().unwrap();
+    assert_eq!(saved.len(), 1);
+    assert_eq!(saved.get(&batches[1].digest()), Some(&batches[1]));
+}
+
+#[test]
+fn test_put_get_evidence() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    assert_eq!(db.get_evidence().unwrap().len(), 0);
+
+    let signers: Vec<_> = (0..3).map(|_| ValidatorSigner::random(None)).collect();
+    let create_evidence = |signer: &ValidatorSigner, round| {
+        let create_proposal = |timestamp_usecs| {
+            Block::new_proposal(
+                Payload::empty(),
+                round,
+                timestamp_usecs,
+                certificate_for_genesis(),
+                signer,
+                vec![],
+            )
+            .unwrap()
+        };
+        Evidence::DoubleProposal {
+            first: create_proposal(1),
+            second: create_proposal(2),
+        }
+    };
+    let evidence = vec![
+        create_evidence(&signers[0], 1),
+        create_evidence(&signers[1], 2),
+    ];
+    for e in &evidence {
+        db.save_evidence(e).unwrap();
+    }
+    // the same evidence is only recorded once
+    db.save_evidence(&evidence[0]).unwrap();
+    // and so is evidence of the same kind for the same author and epoch
+    db.save_evidence(&create_evidence(&signers[0], 3)).unwrap();
+
+    let saved = db.get_evidence().unwrap();
+    assert_eq!(saved.len(), 2);
+    for e in &evidence {
+        assert!(saved.contains(e));
+    }
+
+    // the evidence of the oldest rounds is evicted past the limit
+    let latest_evidence = create_evidence(&signers[2], 3);
+    db.save_evidence_with_limit(&latest_evidence, 2).unwrap();
+    let saved = db.get_evidence().unwrap();
+    assert_eq!(saved.len(), 2);
+    assert!(saved.contains(&evidence[1]));
+    assert!(saved.contains(&latest_evidence));
+}
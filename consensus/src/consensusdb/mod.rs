@@ -7,23 +7,30 @@ mod schema;
 
 use crate::{
     consensusdb::schema::{
+        batch::BatchSchema,
         block::BlockSchema,
+        evidence::EvidenceSchema,
         quorum_certificate::QCSchema,
         single_entry::{SingleEntryKey, SingleEntrySchema},
     },
     error::DbError,
 };
 use anyhow::Result;
-use aptos_crypto::HashValue;
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
-use consensus_types::{block::Block, quorum_cert::QuorumCert};
-use schema::{BLOCK_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
+use consensus_types::{
+    block::Block, evidence::Evidence, proof_of_store::Batch, quorum_cert::QuorumCert,
+};
+use schema::{BATCH_CF_NAME, BLOCK_CF_NAME, EVIDENCE_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME};
 use schemadb::{Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
 use std::{collections::HashMap, iter::Iterator, path::Path, time::Instant};
 
 /// The name of the consensus db file
 pub const CONSENSUS_DB_NAME: &str = "consensus_db";
 
+/// The maximum number of pieces of evidence of misbehaving validators kept.
+pub const MAX_EVIDENCE: usize = 1000;
+
 pub struct ConsensusDB {
     db: DB,
 }
@@ -32,7 +39,9 @@ impl ConsensusDB {
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
         let column_families = vec![
             /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+            BATCH_CF_NAME,
             BLOCK_CF_NAME,
+            EVIDENCE_CF_NAME,
             QC_CF_NAME,
             SINGLE_ENTRY_CF_NAME,
         ];
@@ -128,6 +137,77 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    pub fn save_batches(&self, batches: Vec<Batch>) -> Result<(), DbError> {
+        if batches.is_empty() {
+            return Err(anyhow::anyhow!("Quorum store batches is empty!").into());
+        }
+        let batch = SchemaBatch::new();
+        batches
+            .iter()
+            .try_for_each(|b| batch.put::<BatchSchema>(&b.digest(), b))?;
+        self.commit(batch)
+    }
+
+    pub fn delete_batches(&self, digests: Vec<HashValue>) -> Result<(), DbError> {
+        if digests.is_empty() {
+            return Err(anyhow::anyhow!("Quorum store batch digests is empty!").into());
+        }
+        let batch = SchemaBatch::new();
+        digests
+            .iter()
+            .try_for_each(|digest| batch.delete::<BatchSchema>(digest))?;
+        self.commit(batch)
+    }
+
+    /// Get all quorum store batches.
+    pub fn get_batches(&self) -> Result<HashMap<HashValue, Batch>, DbError> {
+        let mut iter = self.db.iter::<BatchSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        Ok(iter.collect::<Result<HashMap<HashValue, Batch>>>()?)
+    }
+
+    /// Save evidence of a misbehaving validator. One piece of evidence of each kind per author
+    /// and epoch is enough to prove the misbehavior, so later ones are dropped, and at most
+    /// `MAX_EVIDENCE` are kept, evicting the evidence of the oldest rounds first.
+    pub fn save_evidence(&self, evidence: &Evidence) -> Result<(), DbError> {
+        self.save_evidence_with_limit(evidence, MAX_EVIDENCE)
+    }
+
+    fn save_evidence_with_limit(&self, evidence: &Evidence, limit: usize) -> Result<(), DbError> {
+        let mut saved = self.get_evidence_with_hashes()?;
+        if saved.iter().any(|(_hash, e)| {
+            (e.name(), e.author(), e.epoch())
+                == (evidence.name(), evidence.author(), evidence.epoch())
+        }) {
+            return Ok(());
+        }
+        let batch = SchemaBatch::new();
+        if saved.len() >= limit {
+            saved.sort_by_key(|(_hash, e)| (e.epoch(), e.round()));
+            saved
+                .iter()
+                .take(saved.len() + 1 - limit)
+                .try_for_each(|(hash, _evidence)| batch.delete::<EvidenceSchema>(hash))?;
+        }
+        batch.put::<EvidenceSchema>(&evidence.hash(), evidence)?;
+        self.commit(batch)
+    }
+
+    /// Get all the recorded evidence of misbehaving validators.
+    pub fn get_evidence(&self) -> Result<Vec<Evidence>, DbError> {
+        Ok(self
+            .get_evidence_with_hashes()?
+            .into_iter()
+            .map(|(_hash, evidence)| evidence)
+            .collect())
+    }
+
+    fn get_evidence_with_hashes(&self) -> Result<Vec<(HashValue, Evidence)>, DbError> {
+        let mut iter = self.db.iter::<EvidenceSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        Ok(iter.collect::<Result<Vec<(HashValue, Evidence)>>>()?)
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
@@ -0,0 +1,44 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the evidence of misbehaving validators.
+//!
+//! Serialized evidence bytes identified by the evidence hash.
+//! ```text
+//! |<---key---->|<---value--->|
+//! |    hash    |   evidence  |
+//! ```
+
+use super::EVIDENCE_CF_NAME;
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use consensus_types::evidence::Evidence;
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+
+define_schema!(EvidenceSchema, HashValue, Evidence, EVIDENCE_CF_NAME);
+
+impl KeyCodec<EvidenceSchema> for HashValue {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(HashValue::from_slice(data)?)
+    }
+}
+
+impl ValueCodec<EvidenceSchema> for Evidence {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
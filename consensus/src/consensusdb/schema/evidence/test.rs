@@ -0,0 +1,34 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::validator_signer::ValidatorSigner;
+use consensus_types::{
+    block::{block_test_utils::certificate_for_genesis, Block},
+    common::Payload,
+};
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_encode_decode() {
+    let signer = ValidatorSigner::random(None);
+    let create_proposal = |timestamp_usecs| {
+        Block::new_proposal(
+            Payload::empty(),
+            1,
+            timestamp_usecs,
+            certificate_for_genesis(),
+            &signer,
+            vec![],
+        )
+        .unwrap()
+    };
+    let evidence = Evidence::DoubleProposal {
+        first: create_proposal(1),
+        second: create_proposal(2),
+    };
+    assert_encode_decode::<EvidenceSchema>(&evidence.hash(), &evidence);
+}
+
+test_no_panic_decoding!(EvidenceSchema);
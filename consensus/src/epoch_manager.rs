@@ -7,6 +7,7 @@ use crate::{
         BlockStore,
     },
     commit_notifier::CommitNotifier,
+    consensus_observer::publisher::{ConsensusPublisher, PublishingStateComputer},
     counters,
     error::{error_kind, DbError},
     experimental::{
@@ -29,17 +30,23 @@ use crate::{
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
     monitor,
-    network::{IncomingBlockRetrievalRequest, NetworkReceivers, NetworkSender},
+    network::{
+        IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, NetworkReceivers,
+        NetworkSender,
+    },
     network_interface::{ConsensusMsg, ConsensusNetworkSender},
     payload_manager::QuorumStoreClient,
     persistent_liveness_storage::{LedgerRecoveryData, PersistentLivenessStorage, RecoveryData},
-    quorum_store::direct_mempool_quorum_store::DirectMempoolQuorumStore,
+    quorum_store::{
+        batch_store::BatchStore, direct_mempool_quorum_store::DirectMempoolQuorumStore,
+        quorum_store_service::QuorumStore,
+    },
     recovery_manager::RecoveryManager,
     round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
     state_replication::StateComputer,
     util::time_service::TimeService,
 };
-use anyhow::{bail, ensure, Context};
+use anyhow::{anyhow, bail, ensure, Context};
 use aptos_config::config::{ConsensusConfig, NodeConfig};
 use aptos_infallible::{duration_since_epoch, Mutex};
 use aptos_logger::prelude::*;
@@ -52,6 +59,7 @@ use aptos_types::{
         LeaderReputationType, OnChainConfigPayload, OnChainConsensusConfig, ProposerElectionType,
         ValidatorSet,
     },
+    validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
 };
 use channel::{aptos_channel, message_queues::QueueStyle};
@@ -72,7 +80,7 @@ use futures::{
 };
 use itertools::Itertools;
 use network::protocols::network::{ApplicationNetworkSender, Event};
-use safety_rules::SafetyRulesManager;
+use safety_rules::{safety_rules_manager, PersistentSafetyStorage, SafetyRulesManager};
 use std::{
     cmp::Ordering,
     collections::HashMap,
@@ -107,11 +115,16 @@ pub struct EpochManager {
     commit_state_computer: Arc<dyn StateComputer>,
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
+    key_storage: PersistentSafetyStorage,
     reconfig_events: ReconfigNotificationListener,
     commit_notifier: Arc<dyn CommitNotifier>,
+    // publishes the ordered blocks to the subscribed consensus observers
+    consensus_publisher: Option<ConsensusPublisher>,
     // channels to buffer manager
     buffer_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     buffer_manager_reset_tx: Option<UnboundedSender<ResetRequest>>,
+    // channel to quorum store
+    quorum_store_msg_tx: Option<aptos_channel::Sender<AccountAddress, VerifiedEvent>>,
     // channels to round manager
     round_manager_tx: Option<
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
@@ -120,6 +133,8 @@ pub struct EpochManager {
     epoch_state: Option<EpochState>,
     block_retrieval_tx:
         Option<aptos_channel::Sender<AccountAddress, IncomingBlockRetrievalRequest>>,
+    batch_retrieval_tx:
+        Option<aptos_channel::Sender<AccountAddress, IncomingBatchRetrievalRequest>>,
 }
 
 impl EpochManager {
@@ -134,11 +149,13 @@ impl EpochManager {
         storage: Arc<dyn PersistentLivenessStorage>,
         reconfig_events: ReconfigNotificationListener,
         commit_notifier: Arc<dyn CommitNotifier>,
+        consensus_publisher: Option<ConsensusPublisher>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
         let sr_config = &node_config.consensus.safety_rules;
         let safety_rules_manager = SafetyRulesManager::new(sr_config);
+        let key_storage = safety_rules_manager::storage(sr_config);
         Self {
             author,
             config,
@@ -150,14 +167,18 @@ impl EpochManager {
             commit_state_computer,
             storage,
             safety_rules_manager,
+            key_storage,
             reconfig_events,
             commit_notifier,
+            consensus_publisher,
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
+            quorum_store_msg_tx: None,
             round_manager_tx: None,
             round_manager_close_tx: None,
             epoch_state: None,
             block_retrieval_tx: None,
+            batch_retrieval_tx: None,
         }
     }
 
@@ -429,7 +450,7 @@ impl EpochManager {
         Ok(())
     }
 
-    fn spawn_quorum_store(
+    fn spawn_direct_mempool_quorum_store(
         &mut self,
         consensus_to_quorum_store_receiver: Receiver<ConsensusRequest>,
     ) {
@@ -441,6 +462,68 @@ impl EpochManager {
         spawn_named!("Quorum Store", quorum_store.start());
     }
 
+    /// Loads the consensus key of this validator for the epoch, used to sign the batch digests.
+    fn load_consensus_signer(&self, epoch_state: &EpochState) -> anyhow::Result<ValidatorSigner> {
+        let public_key = epoch_state
+            .verifier
+            .get_public_key(&self.author)
+            .ok_or_else(|| anyhow!("{} is not a validator of the epoch", self.author))?;
+        let private_key = self.key_storage.consensus_key_for_version(public_key)?;
+        Ok(ValidatorSigner::new(self.author, private_key))
+    }
+
+    /// Spawns the quorum store of the epoch and its batch retrieval task, returns the batch
+    /// store that resolves the transactions of the quorum store payloads.
+    fn spawn_quorum_store(
+        &mut self,
+        consensus_to_quorum_store_receiver: Receiver<ConsensusRequest>,
+        epoch_state: &EpochState,
+        network_sender: NetworkSender,
+        last_committed_round: Round,
+    ) -> Arc<BatchStore> {
+        let epoch = epoch_state.epoch;
+        let batch_store = Arc::new(BatchStore::new(
+            epoch,
+            self.author,
+            self.storage.clone(),
+            network_sender.clone(),
+            epoch_state.verifier.clone(),
+            &self.config.quorum_store,
+        ));
+        // non-validators still store the batches but don't sign nor create any
+        let signer = match self.load_consensus_signer(epoch_state) {
+            Ok(signer) => Some(Arc::new(signer)),
+            Err(e) => {
+                warn!(epoch = epoch, error = ?e, "Quorum store can't sign batches");
+                None
+            }
+        };
+        let (quorum_store_msg_tx, quorum_store_msg_rx) =
+            aptos_channel::new::<AccountAddress, VerifiedEvent>(
+                QueueStyle::FIFO,
+                self.config.quorum_store.channel_size,
+                Some(&counters::QUORUM_STORE_MSGS),
+            );
+        self.quorum_store_msg_tx = Some(quorum_store_msg_tx);
+
+        let quorum_store = QuorumStore::new(
+            self.author,
+            self.config.quorum_store.clone(),
+            self.config.mempool_txn_pull_timeout_ms,
+            consensus_to_quorum_store_receiver,
+            self.quorum_store_to_mempool_sender.clone(),
+            quorum_store_msg_rx,
+            network_sender,
+            batch_store.clone(),
+            signer,
+            epoch_state.verifier.clone(),
+            last_committed_round,
+        );
+        spawn_named!("Quorum Store", quorum_store.start());
+        self.spawn_batch_retrieval_task(epoch, batch_store.clone());
+        batch_store
+    }
+
     fn spawn_block_retrieval_task(&mut self, epoch: u64, block_store: Arc<BlockStore>) {
         let (request_tx, mut request_rx) = aptos_channel::new(
             QueueStyle::LIFO,
@@ -463,6 +546,28 @@ impl EpochManager {
         tokio::spawn(task);
     }
 
+    fn spawn_batch_retrieval_task(&mut self, epoch: u64, batch_store: Arc<BatchStore>) {
+        let (request_tx, mut request_rx) = aptos_channel::new(
+            QueueStyle::FIFO,
+            self.config.quorum_store.channel_size,
+            Some(&counters::BATCH_RETRIEVAL_TASK_MSGS),
+        );
+        let task = async move {
+            info!(epoch = epoch, "Batch retrieval task starts");
+            while let Some(request) = request_rx.next().await {
+                if let Err(e) = monitor!(
+                    "process_batch_retrieval",
+                    batch_store.process_batch_request(request)
+                ) {
+                    warn!(epoch = epoch, error = ?e, kind = error_kind(&e));
+                }
+            }
+            info!(epoch = epoch, "Batch retrieval task stops");
+        };
+        self.batch_retrieval_tx = Some(request_tx);
+        tokio::spawn(task);
+    }
+
     /// this function spawns the phases and a buffer manager
     /// it sets `self.commit_msg_tx` to a new aptos_channel::Sender and returns an OrderingStateComputer
     fn spawn_decoupled_execution(
@@ -540,6 +645,10 @@ impl EpochManager {
 
         // Shutdown the block retrieval task by dropping the sender
         self.block_retrieval_tx = None;
+
+        // Shutdown the quorum store and its batch retrieval task by dropping the senders
+        self.quorum_store_msg_tx = None;
+        self.batch_retrieval_tx = None;
     }
 
     async fn start_recovery_manager(
@@ -618,16 +727,28 @@ impl EpochManager {
 
         let (consensus_to_quorum_store_sender, consensus_to_quorum_store_receiver) =
             mpsc::channel(self.config.intra_consensus_channel_buffer_size);
-        self.spawn_quorum_store(consensus_to_quorum_store_receiver);
+        let batch_store = if self.config.use_quorum_store {
+            Some(self.spawn_quorum_store(
+                consensus_to_quorum_store_receiver,
+                &epoch_state,
+                network_sender.clone(),
+                recovery_data.root_block().round(),
+            ))
+        } else {
+            self.spawn_direct_mempool_quorum_store(consensus_to_quorum_store_receiver);
+            None
+        };
         let payload_manager = QuorumStoreClient::new(
             consensus_to_quorum_store_sender.clone(),
             self.config.quorum_store_poll_count,
             self.config.quorum_store_pull_timeout_ms,
+            batch_store,
         );
         self.commit_notifier
             .new_epoch(consensus_to_quorum_store_sender);
 
-        self.commit_state_computer.new_epoch(&epoch_state);
+        self.commit_state_computer
+            .new_epoch(&epoch_state, Arc::new(payload_manager.clone()));
         let state_computer = if onchain_config.decoupled_execution() {
             Arc::new(self.spawn_decoupled_execution(
                 safety_rules_container.clone(),
@@ -636,6 +757,14 @@ impl EpochManager {
         } else {
             self.commit_state_computer.clone()
         };
+        let state_computer: Arc<dyn StateComputer> = match &self.consensus_publisher {
+            Some(consensus_publisher) => Arc::new(PublishingStateComputer::new(
+                state_computer,
+                consensus_publisher.clone(),
+                Arc::new(payload_manager.clone()),
+            )),
+            None => state_computer,
+        };
 
         info!(epoch = epoch, "Create BlockStore");
         let block_store = Arc::new(BlockStore::new(
@@ -784,7 +913,10 @@ impl EpochManager {
             | ConsensusMsg::SyncInfo(_)
             | ConsensusMsg::VoteMsg(_)
             | ConsensusMsg::CommitVoteMsg(_)
-            | ConsensusMsg::CommitDecisionMsg(_) => {
+            | ConsensusMsg::CommitDecisionMsg(_)
+            | ConsensusMsg::BatchMsg(_)
+            | ConsensusMsg::SignedDigestMsg(_)
+            | ConsensusMsg::ProofOfStoreMsg(_) => {
                 let event: UnverifiedEvent = msg.into();
                 if event.epoch() == self.epoch() {
                     return Ok(Some(event));
@@ -850,6 +982,15 @@ impl EpochManager {
                     bail!("Commit Phase not started but received Commit Message (CommitVote/CommitDecision)");
                 }
             }
+            quorum_store_event @ (VerifiedEvent::Batch(_)
+            | VerifiedEvent::SignedDigest(_)
+            | VerifiedEvent::ProofOfStore(_)) => {
+                if let Some(sender) = &mut self.quorum_store_msg_tx {
+                    sender.push(peer_id, quorum_store_event)?;
+                } else {
+                    bail!("Quorum Store not started but received Quorum Store Message (Batch/SignedDigest/ProofOfStore)");
+                }
+            }
             round_manager_event => {
                 self.forward_to_round_manager(peer_id, round_manager_event);
             }
@@ -882,6 +1023,21 @@ impl EpochManager {
         }
     }
 
+    fn process_batch_retrieval(
+        &self,
+        peer_id: Author,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        fail_point!("consensus::process::any", |_| {
+            Err(anyhow::anyhow!("Injected error in process_batch_retrieval"))
+        });
+        if let Some(tx) = &self.batch_retrieval_tx {
+            tx.push(peer_id, request)
+        } else {
+            Err(anyhow::anyhow!("Quorum store not started"))
+        }
+    }
+
     fn process_local_timeout(&mut self, round: u64) {
         self.forward_to_round_manager(self.author, VerifiedEvent::LocalTimeout(round));
     }
@@ -910,11 +1066,21 @@ impl EpochManager {
                         error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
                     }
                 },
+                (peer, msg) = network_receivers.quorum_store_messages.select_next_some() => {
+                    if let Err(e) = self.process_message(peer, msg).await {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                },
                 (peer, request) = network_receivers.block_retrieval.select_next_some() => {
                     if let Err(e) = self.process_block_retrieval(peer, request) {
                         error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
                     }
                 },
+                (peer, request) = network_receivers.batch_retrieval.select_next_some() => {
+                    if let Err(e) = self.process_batch_retrieval(peer, request) {
+                        error!(epoch = self.epoch(), error = ?e, kind = error_kind(&e));
+                    }
+                },
                 round = round_timeout_sender_rx.select_next_some() => {
                     self.process_local_timeout(round);
                 },
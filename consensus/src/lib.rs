@@ -41,6 +41,8 @@ mod twins;
 mod txn_notifier;
 mod util;
 
+/// Consensus observer, pushing the blocks ordered by validators to the subscribed full nodes.
+pub mod consensus_observer;
 /// AptosBFT implementation
 pub mod consensus_provider;
 /// Required by the telemetry service
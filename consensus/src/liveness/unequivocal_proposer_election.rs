@@ -3,12 +3,12 @@
 
 use std::cmp::Ordering;
 
-use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use aptos_logger::{error, SecurityEvent};
 use consensus_types::{
     block::Block,
     common::{Author, Round},
+    evidence::Evidence,
 };
 
 use super::proposer_election::ProposerElection;
@@ -16,10 +16,11 @@ use super::proposer_election::ProposerElection;
 // Wrapper around ProposerElection.
 //
 // Provides is_valid_proposal that remembers, and rejects if
-// the same leader proposes multiple blocks.
+// the same leader proposes multiple blocks. The first proposal of the
+// round is kept as evidence of the equivocation.
 pub struct UnequivocalProposerElection {
     proposer_election: Box<dyn ProposerElection + Send + Sync>,
-    already_proposed: Mutex<(Round, HashValue)>,
+    already_proposed: Mutex<(Round, Option<Block>)>,
 }
 
 impl ProposerElection for UnequivocalProposerElection {
@@ -37,7 +38,7 @@ impl UnequivocalProposerElection {
     pub fn new(proposer_election: Box<dyn ProposerElection + Send + Sync>) -> Self {
         Self {
             proposer_election,
-            already_proposed: Mutex::new((0, HashValue::zero())),
+            already_proposed: Mutex::new((0, None)),
         }
     }
 
@@ -66,17 +67,18 @@ impl UnequivocalProposerElection {
             match block.round().cmp(&already_proposed.0) {
                 Ordering::Greater => {
                     already_proposed.0 = block.round();
-                    already_proposed.1 = block.id();
+                    already_proposed.1 = Some(block.clone());
                     true
                 }
                 Ordering::Equal => {
-                    if already_proposed.1 != block.id() {
+                    let already_proposed_id = already_proposed.1.as_ref().map(Block::id);
+                    if already_proposed_id != Some(block.id()) {
                         error!(
                             SecurityEvent::InvalidConsensusProposal,
-                            "Multiple proposals from {} for round {}: {} and {}",
+                            "Multiple proposals from {} for round {}: {:?} and {}",
                             author,
                             block.round(),
-                            already_proposed.1,
+                            already_proposed_id,
                             block.id()
                         );
                         false
@@ -91,4 +93,23 @@ impl UnequivocalProposerElection {
             }
         })
     }
+
+    // Return the evidence of the proposer equivocating if the given proposal was
+    // rejected because its author already proposed another block in this round.
+    pub fn get_double_proposal(&self, block: &Block) -> Option<Evidence> {
+        let already_proposed = self.already_proposed.lock();
+        match &already_proposed.1 {
+            Some(first)
+                if first.round() == block.round()
+                    && first.author() == block.author()
+                    && first.id() != block.id() =>
+            {
+                Some(Evidence::DoubleProposal {
+                    first: first.clone(),
+                    second: block.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
 }
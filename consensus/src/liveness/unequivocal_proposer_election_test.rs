@@ -7,6 +7,7 @@ use aptos_types::validator_signer::ValidatorSigner;
 use consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
     common::{Author, Payload, Round},
+    evidence::Evidence,
 };
 
 use crate::liveness::unequivocal_proposer_election::UnequivocalProposerElection;
@@ -97,6 +98,14 @@ fn test_is_valid_proposal() {
 
     // another proposal from the valid proposer should fail
     assert!(!pe.is_valid_proposal(&bad_duplicate_proposal));
+    assert_eq!(
+        pe.get_double_proposal(&bad_duplicate_proposal),
+        Some(Evidence::DoubleProposal {
+            first: good_proposal.clone(),
+            second: bad_duplicate_proposal.clone(),
+        })
+    );
+    assert_eq!(pe.get_double_proposal(&bad_author_proposal), None);
     // good proposal still passes
     assert!(pe.is_valid_proposal(&good_proposal));
 
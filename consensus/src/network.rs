@@ -20,6 +20,7 @@ use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse, MAX_BLOCKS_PER_REQUEST},
     common::Author,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    proof_of_store::{Batch, BatchRequest, ProofOfStore, SignedDigest},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     vote_msg::VoteMsg,
@@ -38,6 +39,11 @@ use std::{
     time::Duration,
 };
 
+/// Number of quorum store messages buffered per peer before the oldest is dropped.
+const QUORUM_STORE_MSGS_PER_PEER: usize = 100;
+/// Number of batch retrieval requests buffered per peer before the oldest is dropped.
+const BATCH_REQUESTS_PER_PEER: usize = 10;
+
 /// The block retrieval request is used internally for implementing RPC: the callback is executed
 /// for carrying the response
 #[derive(Debug)]
@@ -47,6 +53,15 @@ pub struct IncomingBlockRetrievalRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+/// The batch retrieval request is used internally for implementing RPC: the callback is executed
+/// for carrying the response
+#[derive(Debug)]
+pub struct IncomingBatchRetrievalRequest {
+    pub req: BatchRequest,
+    pub protocol: ProtocolId,
+    pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
+}
+
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
 /// Will be returned by the NetworkTask upon startup.
 pub struct NetworkReceivers {
@@ -55,8 +70,14 @@ pub struct NetworkReceivers {
         (AccountAddress, Discriminant<ConsensusMsg>),
         (AccountAddress, ConsensusMsg),
     >,
+    /// Provide a FIFO buffer for each Author, quorum store messages can't be dropped in favor of
+    /// a more recent one of the same type
+    pub quorum_store_messages:
+        aptos_channel::Receiver<AccountAddress, (AccountAddress, ConsensusMsg)>,
     pub block_retrieval:
         aptos_channel::Receiver<AccountAddress, (AccountAddress, IncomingBlockRetrievalRequest)>,
+    pub batch_retrieval:
+        aptos_channel::Receiver<AccountAddress, (AccountAddress, IncomingBatchRetrievalRequest)>,
 }
 
 /// Implements the actual networking support for all consensus messaging.
@@ -128,6 +149,44 @@ impl NetworkSender {
         Ok(response)
     }
 
+    /// Tries to retrieve the batch of the given digest from the given peer, the batch is returned
+    /// only if its digest matches.
+    pub async fn request_batch(
+        &self,
+        request: BatchRequest,
+        from: Author,
+        timeout: Duration,
+    ) -> anyhow::Result<Batch> {
+        fail_point!("consensus::send::any", |_| {
+            Err(anyhow::anyhow!("Injected error in request_batch"))
+        });
+        fail_point!("consensus::send::batch_retrieval", |_| {
+            Err(anyhow::anyhow!("Injected error in request_batch"))
+        });
+
+        ensure!(from != self.author, "Retrieve batch from self");
+        let msg = ConsensusMsg::BatchRequestMsg(Box::new(request.clone()));
+        counters::CONSENSUS_SENT_MSGS
+            .with_label_values(&[msg.name()])
+            .inc();
+        let response_msg = monitor!(
+            "batch_retrieval",
+            self.network_sender.send_rpc(from, msg, timeout).await
+        )?;
+        let batch = match response_msg {
+            ConsensusMsg::BatchMsg(batch) => *batch,
+            _ => return Err(anyhow!("Invalid response to request")),
+        };
+        ensure!(
+            batch.epoch() == request.epoch() && batch.digest() == request.digest(),
+            "Batch {} doesn't match request {}",
+            batch,
+            request
+        );
+
+        Ok(batch)
+    }
+
     /// Tries to send the given msg to all the participants.
     ///
     /// The future is fulfilled as soon as the message put into the mpsc channel to network
@@ -269,6 +328,25 @@ impl NetworkSender {
         let msg = ConsensusMsg::CommitDecisionMsg(Box::new(CommitDecision::new(ledger_info)));
         self.broadcast(msg).await
     }
+
+    pub async fn broadcast_batch(&mut self, batch: Batch) {
+        fail_point!("consensus::send::broadcast_batch", |_| ());
+        let msg = ConsensusMsg::BatchMsg(Box::new(batch));
+        self.broadcast(msg).await
+    }
+
+    /// Sends the signed digest of a persisted batch back to the author of the batch.
+    pub async fn send_signed_digest(&self, signed_digest: SignedDigest, recipient: Author) {
+        fail_point!("consensus::send::signed_digest", |_| ());
+        let msg = ConsensusMsg::SignedDigestMsg(Box::new(signed_digest));
+        self.send(msg, vec![recipient]).await
+    }
+
+    pub async fn broadcast_proof_of_store(&mut self, proof: ProofOfStore) {
+        fail_point!("consensus::send::broadcast_proof_of_store", |_| ());
+        let msg = ConsensusMsg::ProofOfStoreMsg(Box::new(proof));
+        self.broadcast(msg).await
+    }
 }
 
 pub struct NetworkTask {
@@ -276,8 +354,11 @@ pub struct NetworkTask {
         (AccountAddress, Discriminant<ConsensusMsg>),
         (AccountAddress, ConsensusMsg),
     >,
+    quorum_store_messages_tx: aptos_channel::Sender<AccountAddress, (AccountAddress, ConsensusMsg)>,
     block_retrieval_tx:
         aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingBlockRetrievalRequest)>,
+    batch_retrieval_tx:
+        aptos_channel::Sender<AccountAddress, (AccountAddress, IncomingBatchRetrievalRequest)>,
     all_events: Box<dyn Stream<Item = Event<ConsensusMsg>> + Send + Unpin>,
 }
 
@@ -289,21 +370,35 @@ impl NetworkTask {
     ) -> (NetworkTask, NetworkReceivers) {
         let (consensus_messages_tx, consensus_messages) =
             aptos_channel::new(QueueStyle::LIFO, 1, Some(&counters::CONSENSUS_CHANNEL_MSGS));
+        let (quorum_store_messages_tx, quorum_store_messages) = aptos_channel::new(
+            QueueStyle::FIFO,
+            QUORUM_STORE_MSGS_PER_PEER,
+            Some(&counters::QUORUM_STORE_CHANNEL_MSGS),
+        );
         let (block_retrieval_tx, block_retrieval) = aptos_channel::new(
             QueueStyle::LIFO,
             1,
             Some(&counters::BLOCK_RETRIEVAL_CHANNEL_MSGS),
         );
+        let (batch_retrieval_tx, batch_retrieval) = aptos_channel::new(
+            QueueStyle::FIFO,
+            BATCH_REQUESTS_PER_PEER,
+            Some(&counters::BATCH_RETRIEVAL_CHANNEL_MSGS),
+        );
         let all_events = Box::new(select(network_events, self_receiver));
         (
             NetworkTask {
                 consensus_messages_tx,
+                quorum_store_messages_tx,
                 block_retrieval_tx,
+                batch_retrieval_tx,
                 all_events,
             },
             NetworkReceivers {
                 consensus_messages,
+                quorum_store_messages,
                 block_retrieval,
+                batch_retrieval,
             },
         )
     }
@@ -321,10 +416,17 @@ impl NetworkTask {
                             BlockStage::NETWORK_RECEIVED,
                         );
                     }
-                    if let Err(e) = self
-                        .consensus_messages_tx
-                        .push((peer_id, discriminant(&msg)), (peer_id, msg))
-                    {
+                    let result = match msg {
+                        ConsensusMsg::BatchMsg(_)
+                        | ConsensusMsg::SignedDigestMsg(_)
+                        | ConsensusMsg::ProofOfStoreMsg(_) => {
+                            self.quorum_store_messages_tx.push(peer_id, (peer_id, msg))
+                        }
+                        _ => self
+                            .consensus_messages_tx
+                            .push((peer_id, discriminant(&msg)), (peer_id, msg)),
+                    };
+                    if let Err(e) = result {
                         warn!(
                             remote_peer = peer_id,
                             error = ?e, "Error pushing consensus msg",
@@ -362,6 +464,23 @@ impl NetworkTask {
                             warn!(error = ?e, "aptos channel closed");
                         }
                     }
+                    ConsensusMsg::BatchRequestMsg(request) => {
+                        counters::CONSENSUS_RECEIVED_MSGS
+                            .with_label_values(&["BatchRequestMsg"])
+                            .inc();
+                        debug!(remote_peer = peer_id, "Received {}", request);
+                        let req_with_callback = IncomingBatchRetrievalRequest {
+                            req: *request,
+                            protocol,
+                            response_sender: callback,
+                        };
+                        if let Err(e) = self
+                            .batch_retrieval_tx
+                            .push(peer_id, (peer_id, req_with_callback))
+                        {
+                            warn!(error = ?e, "aptos channel closed");
+                        }
+                    }
                     _ => {
                         warn!(remote_peer = peer_id, "Unexpected msg: {:?}", msg);
                         continue;
@@ -14,6 +14,7 @@ use consensus_types::{
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalResponse},
     epoch_retrieval::EpochRetrievalRequest,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    proof_of_store::{Batch, BatchRequest, ProofOfStore, SignedDigest},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     vote_msg::VoteMsg,
@@ -62,6 +63,16 @@ pub enum ConsensusMsg {
     /// than 2f + 1 signatures on the commit proposal. This part is not on the critical path, but
     /// it can save slow machines to quickly confirm the execution result.
     CommitDecisionMsg(Box<CommitDecision>),
+    /// Batch of transactions broadcast by its author to be stored by the quorum store of the other
+    /// validators, also the response to a BatchRequestMsg.
+    BatchMsg(Box<Batch>),
+    /// SignedDigest is sent back to the author of a batch once the batch is persisted.
+    SignedDigestMsg(Box<SignedDigest>),
+    /// ProofOfStore is broadcast by the author of a batch after collecting no fewer than 2f + 1
+    /// signed digests of the batch, so that any leader can propose it.
+    ProofOfStoreMsg(Box<ProofOfStore>),
+    /// RPC to get a batch that is referenced by a proof of store but missing locally.
+    BatchRequestMsg(Box<BatchRequest>),
 }
 
 /// Network type for consensus
@@ -79,6 +90,10 @@ impl ConsensusMsg {
             ConsensusMsg::VoteMsg(_) => "VoteMsg",
             ConsensusMsg::CommitVoteMsg(_) => "CommitVoteMsg",
             ConsensusMsg::CommitDecisionMsg(_) => "CommitDecisionMsg",
+            ConsensusMsg::BatchMsg(_) => "BatchMsg",
+            ConsensusMsg::SignedDigestMsg(_) => "SignedDigestMsg",
+            ConsensusMsg::ProofOfStoreMsg(_) => "ProofOfStoreMsg",
+            ConsensusMsg::BatchRequestMsg(_) => "BatchRequestMsg",
         }
     }
 }
@@ -16,7 +16,8 @@ use aptos_types::{
 };
 use consensus_types::timeout_2chain::TwoChainTimeoutWithPartialSignatures;
 use consensus_types::{
-    common::Author, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    common::Author, evidence::Evidence, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -33,8 +34,9 @@ pub enum VoteReceptionResult {
     VoteAdded(u128),
     /// The very same vote message has been processed in past.
     DuplicateVote,
-    /// The very same author has already voted for another proposal in this round (equivocation).
-    EquivocateVote,
+    /// The very same author has already voted for another proposal in this round (equivocation),
+    /// carries both votes as evidence.
+    EquivocateVote(Box<Evidence>),
     /// This block has just been certified after adding the vote.
     NewQuorumCertificate(Arc<QuorumCert>),
     /// The vote completes a new TwoChainTimeoutCertificate
@@ -111,7 +113,10 @@ impl PendingVotes {
                     previous_vote = previously_seen_vote
                 );
 
-                return VoteReceptionResult::EquivocateVote;
+                return VoteReceptionResult::EquivocateVote(Box::new(Evidence::DoubleVote {
+                    first: previously_seen_vote.clone(),
+                    second: vote.clone(),
+                }));
             }
         }
 
@@ -275,7 +280,8 @@ mod tests {
         validator_verifier::random_validator_verifier,
     };
     use consensus_types::{
-        block::block_test_utils::certificate_for_genesis, vote::Vote, vote_data::VoteData,
+        block::block_test_utils::certificate_for_genesis, evidence::Evidence, vote::Vote,
+        vote_data::VoteData,
     };
     use itertools::Itertools;
 
@@ -332,7 +338,10 @@ mod tests {
         .unwrap();
         assert_eq!(
             pending_votes.insert_vote(&vote_data_2_author_0, &validator),
-            VoteReceptionResult::EquivocateVote
+            VoteReceptionResult::EquivocateVote(Box::new(Evidence::DoubleVote {
+                first: vote_data_1_author_0.clone(),
+                second: vote_data_2_author_0.clone(),
+            }))
         );
 
         // a different author voting for a different result -> VoteAdded
@@ -11,7 +11,12 @@ use aptos_types::{
     proof::TransactionAccumulatorSummary, transaction::Version,
 };
 use consensus_types::{
-    block::Block, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    block::Block,
+    evidence::{Evidence, EvidenceStore},
+    proof_of_store::Batch,
+    quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate,
+    vote::Vote,
 };
 use std::{cmp::max, collections::HashSet, sync::Arc};
 use storage_interface::DbReader;
@@ -50,6 +55,22 @@ pub trait PersistentLivenessStorage: Send + Sync {
 
     /// Returns a handle of the aptosdb.
     fn aptos_db(&self) -> Arc<dyn DbReader>;
+
+    /// Persist the quorum store batches, so that they can be served to the other validators
+    /// until they expire even across restarts.
+    fn save_batches(&self, batches: Vec<Batch>) -> Result<()>;
+
+    /// Delete the corresponding quorum store batches.
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<()>;
+
+    /// Retrieve all the quorum store batches persisted.
+    fn get_batches(&self) -> Result<Vec<Batch>>;
+
+    /// Persist the evidence of a validator misbehaving.
+    fn save_evidence(&self, evidence: &Evidence) -> Result<()>;
+
+    /// Retrieve all the evidence persisted.
+    fn get_evidence(&self) -> Result<Vec<Evidence>>;
 }
 
 #[derive(Clone)]
@@ -438,4 +459,36 @@ impl PersistentLivenessStorage for StorageWriteProxy {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         self.aptos_db.clone()
     }
+
+    fn save_batches(&self, batches: Vec<Batch>) -> Result<()> {
+        if !batches.is_empty() {
+            self.db.save_batches(batches)?;
+        }
+        Ok(())
+    }
+
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<()> {
+        if !digests.is_empty() {
+            self.db.delete_batches(digests)?;
+        }
+        Ok(())
+    }
+
+    fn get_batches(&self) -> Result<Vec<Batch>> {
+        Ok(self.db.get_batches()?.into_values().collect())
+    }
+
+    fn save_evidence(&self, evidence: &Evidence) -> Result<()> {
+        Ok(self.db.save_evidence(evidence)?)
+    }
+
+    fn get_evidence(&self) -> Result<Vec<Evidence>> {
+        Ok(self.db.get_evidence()?)
+    }
+}
+
+impl EvidenceStore for StorageWriteProxy {
+    fn get_evidence(&self) -> Result<Vec<Evidence>> {
+        Ok(self.db.get_evidence()?)
+    }
 }
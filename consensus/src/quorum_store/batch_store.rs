@@ -0,0 +1,247 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::{IncomingBatchRetrievalRequest, NetworkSender},
+    network_interface::ConsensusMsg,
+    persistent_liveness_storage::PersistentLivenessStorage,
+    quorum_store::counters,
+};
+use anyhow::{bail, ensure};
+use aptos_config::config::QuorumStoreConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_types::{transaction::SignedTransaction, validator_verifier::ValidatorVerifier};
+use consensus_types::{
+    common::Author,
+    proof_of_store::{Batch, BatchRequest, LogicalTime, ProofOfStore},
+};
+use fail::fail_point;
+use rand::{seq::SliceRandom, thread_rng};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// The stored batches, with the number of batches and bytes stored per author.
+#[derive(Default)]
+struct StoredBatches {
+    by_digest: HashMap<HashValue, Batch>,
+    usage_by_author: HashMap<Author, (u64, u64)>,
+}
+
+impl StoredBatches {
+    fn insert(&mut self, batch: Batch) {
+        let usage = self.usage_by_author.entry(batch.author()).or_default();
+        usage.0 += 1;
+        usage.1 += batch.num_bytes();
+        self.by_digest.insert(batch.digest(), batch);
+    }
+
+    fn remove(&mut self, digest: &HashValue) {
+        if let Some(batch) = self.by_digest.remove(digest) {
+            let usage = self.usage_by_author.entry(batch.author()).or_default();
+            usage.0 -= 1;
+            usage.1 -= batch.num_bytes();
+            if usage.0 == 0 {
+                self.usage_by_author.remove(&batch.author());
+            }
+        }
+    }
+
+    fn usage(&self, author: &Author) -> (u64, u64) {
+        self.usage_by_author
+            .get(author)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Stores the batches of the current epoch until they expire, both in memory and in
+/// ConsensusDB, so that the batches this validator signed for can be served to the others.
+pub struct BatchStore {
+    epoch: u64,
+    author: Author,
+    batches: Mutex<StoredBatches>,
+    storage: Arc<dyn PersistentLivenessStorage>,
+    network_sender: NetworkSender,
+    verifier: ValidatorVerifier,
+    request_timeout: Duration,
+    max_batches_per_author: u64,
+    max_bytes_per_author: u64,
+}
+
+impl BatchStore {
+    /// Loads the persisted batches of the given epoch and deletes the ones of the previous
+    /// epochs, they can't be referenced anymore.
+    pub fn new(
+        epoch: u64,
+        author: Author,
+        storage: Arc<dyn PersistentLivenessStorage>,
+        network_sender: NetworkSender,
+        verifier: ValidatorVerifier,
+        config: &QuorumStoreConfig,
+    ) -> Self {
+        let (current, stale): (Vec<_>, Vec<_>) = storage
+            .get_batches()
+            .unwrap_or_else(|e| {
+                error!(error = ?e, "Failed to read quorum store batches");
+                vec![]
+            })
+            .into_iter()
+            .partition(|batch| batch.epoch() == epoch);
+        if let Err(e) = storage.delete_batches(stale.iter().map(Batch::digest).collect()) {
+            error!(error = ?e, "Failed to delete stale quorum store batches");
+        }
+        let mut batches = StoredBatches::default();
+        for batch in current {
+            batches.insert(batch);
+        }
+        Self {
+            epoch,
+            author,
+            batches: Mutex::new(batches),
+            storage,
+            network_sender,
+            verifier,
+            request_timeout: Duration::from_millis(config.batch_request_timeout_ms),
+            max_batches_per_author: config.max_batches_per_author,
+            max_bytes_per_author: config.max_bytes_per_author,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Persists the batch, a digest of it can only be signed after it's saved.
+    pub fn save(&self, batch: Batch) -> anyhow::Result<()> {
+        self.save_impl(batch, false)
+    }
+
+    /// Persists a batch received from its author, unless the author would exceed its quota of
+    /// stored batches or bytes. Batches of a certified proof are saved regardless, with `save`.
+    pub fn save_within_quota(&self, batch: Batch) -> anyhow::Result<()> {
+        self.save_impl(batch, true)
+    }
+
+    fn save_impl(&self, batch: Batch, enforce_quota: bool) -> anyhow::Result<()> {
+        ensure!(
+            batch.epoch() == self.epoch,
+            "Batch of epoch {} in quorum store of epoch {}",
+            batch.epoch(),
+            self.epoch
+        );
+        // the lock is held while persisting, so that concurrent saves can't exceed a quota
+        let mut batches = self.batches.lock();
+        if batches.by_digest.contains_key(&batch.digest()) {
+            return Ok(());
+        }
+        if enforce_quota {
+            let (num_batches, num_bytes) = batches.usage(&batch.author());
+            if num_batches + 1 > self.max_batches_per_author
+                || num_bytes + batch.num_bytes() > self.max_bytes_per_author
+            {
+                counters::BATCHES_OVER_QUOTA_COUNT.inc();
+                bail!(
+                    "{} exceeds the quota of its author, who has {} batches of {} bytes stored",
+                    batch,
+                    num_batches,
+                    num_bytes
+                );
+            }
+        }
+        self.storage.save_batches(vec![batch.clone()])?;
+        batches.insert(batch);
+        counters::NUM_BATCHES_STORED.inc();
+        Ok(())
+    }
+
+    pub fn get(&self, digest: &HashValue) -> Option<Batch> {
+        self.batches.lock().by_digest.get(digest).cloned()
+    }
+
+    /// Removes the batches that expire at or before the given time.
+    pub fn clear_expired(&self, current_time: LogicalTime) {
+        let expired: Vec<_> = {
+            let mut batches = self.batches.lock();
+            let expired: Vec<_> = batches
+                .by_digest
+                .iter()
+                .filter(|(_, batch)| batch.expiration() <= current_time)
+                .map(|(digest, _)| *digest)
+                .collect();
+            for digest in &expired {
+                batches.remove(digest);
+            }
+            expired
+        };
+        counters::NUM_BATCHES_STORED.sub(expired.len() as i64);
+        if let Err(e) = self.storage.delete_batches(expired) {
+            error!(error = ?e, "Failed to delete expired quorum store batches");
+        }
+    }
+
+    /// Returns the transactions of the batches the proofs refer to, in order. The batches that
+    /// aren't available locally are requested from the validators that signed their proof.
+    pub async fn get_transactions(
+        &self,
+        proofs: &[ProofOfStore],
+    ) -> anyhow::Result<Vec<SignedTransaction>> {
+        let mut txns = vec![];
+        for proof in proofs {
+            let batch = match self.get(proof.digest()) {
+                Some(batch) => batch,
+                None => {
+                    let batch = self.fetch(proof).await?;
+                    self.save(batch.clone())?;
+                    batch
+                }
+            };
+            txns.extend(batch.into_txns());
+        }
+        Ok(txns)
+    }
+
+    async fn fetch(&self, proof: &ProofOfStore) -> anyhow::Result<Batch> {
+        fail_point!("consensus::quorum_store::fetch_batch", |_| {
+            Err(anyhow::anyhow!("Injected error in fetch_batch"))
+        });
+        let request = BatchRequest::new(self.epoch, *proof.digest());
+        let mut signers = proof.signers(&self.verifier);
+        signers.retain(|signer| *signer != self.author);
+        signers.shuffle(&mut thread_rng());
+        for signer in signers {
+            counters::BATCH_REQUEST_COUNT.inc();
+            match self
+                .network_sender
+                .request_batch(request.clone(), signer, self.request_timeout)
+                .await
+            {
+                Ok(batch) => return Ok(batch),
+                Err(e) => {
+                    warn!(remote_peer = signer, error = ?e, "Failed to fetch {}", request);
+                }
+            }
+        }
+        bail!("Failed to fetch {} from any signer", request)
+    }
+
+    pub fn process_batch_request(
+        &self,
+        request: IncomingBatchRetrievalRequest,
+    ) -> anyhow::Result<()> {
+        fail_point!("consensus::process_batch_request", |_| {
+            Err(anyhow::anyhow!("Injected error in process_batch_request"))
+        });
+        let batch = match self.get(&request.req.digest()) {
+            Some(batch) if request.req.epoch() == self.epoch => batch,
+            _ => bail!("{} not found", request.req),
+        };
+        let response_bytes = request
+            .protocol
+            .to_bytes(&ConsensusMsg::BatchMsg(Box::new(batch)))?;
+        request
+            .response_sender
+            .send(Ok(response_bytes.into()))
+            .map_err(|_| anyhow::anyhow!("Failed to send batch retrieval response"))
+    }
+}
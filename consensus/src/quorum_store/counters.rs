@@ -1,7 +1,8 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 use aptos_metrics_core::{
-    op_counters::DurationHistogram, register_histogram, register_histogram_vec, HistogramVec,
+    op_counters::DurationHistogram, register_histogram, register_histogram_vec,
+    register_int_counter, register_int_gauge, HistogramVec, IntCounter, IntGauge,
 };
 use once_cell::sync::Lazy;
 use std::time::Duration;
@@ -42,3 +43,57 @@ pub static MAIN_LOOP: Lazy<DurationHistogram> = Lazy::new(|| {
         .unwrap(),
     )
 });
+
+/// Number of batches created by this validator.
+pub static CREATED_BATCHES_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_created_batches_count",
+        "Number of batches created by this validator"
+    )
+    .unwrap()
+});
+
+/// Number of proofs of store aggregated by this validator.
+pub static CREATED_PROOFS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_created_proofs_count",
+        "Number of proofs of store aggregated by this validator"
+    )
+    .unwrap()
+});
+
+/// Count of the batches of peers rejected because their author exceeded its storage quota.
+pub static BATCHES_OVER_QUOTA_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_batches_over_quota_count",
+        "Count of the batches of peers rejected because their author exceeded its storage quota"
+    )
+    .unwrap()
+});
+
+/// Number of batches currently stored.
+pub static NUM_BATCHES_STORED: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_num_batches_stored",
+        "Number of batches currently stored"
+    )
+    .unwrap()
+});
+
+/// Number of proofs of store not yet committed.
+pub static NUM_PROOFS_PENDING: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_num_proofs_pending",
+        "Number of proofs of store not yet committed"
+    )
+    .unwrap()
+});
+
+/// Number of requests sent to fetch a missing batch.
+pub static BATCH_REQUEST_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_batch_request_count",
+        "Number of requests sent to fetch a missing batch"
+    )
+    .unwrap()
+});
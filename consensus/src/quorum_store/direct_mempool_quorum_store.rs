@@ -80,16 +80,19 @@ impl DirectMempoolQuorumStore {
         callback: oneshot::Sender<Result<ConsensusResponse>>,
     ) {
         let get_batch_start_time = Instant::now();
-        let (txns, result) = match payload_filter {
-            PayloadFilter::DirectMempool(exclude_txns) => {
-                match self.pull_internal(max_txns, max_bytes, exclude_txns).await {
-                    Err(e) => {
-                        error!("GetBatch failed {:?}", e);
-                        (vec![], counters::REQUEST_FAIL_LABEL)
-                    }
-                    Ok(txns) => (txns, counters::REQUEST_SUCCESS_LABEL),
-                }
+        let exclude_txns = match payload_filter {
+            PayloadFilter::DirectMempool(exclude_txns) => exclude_txns,
+            PayloadFilter::InQuorumStore(_) => {
+                unreachable!("Unknown payload_filter: {}", payload_filter)
             }
+            PayloadFilter::Empty => Vec::new(),
+        };
+        let (txns, result) = match self.pull_internal(max_txns, max_bytes, exclude_txns).await {
+            Err(e) => {
+                error!("GetBatch failed {:?}", e);
+                (vec![], counters::REQUEST_FAIL_LABEL)
+            }
+            Ok(txns) => (txns, counters::REQUEST_SUCCESS_LABEL),
         };
         counters::quorum_store_service_latency(
             counters::GET_BATCH_LABEL,
@@ -129,7 +132,7 @@ impl DirectMempoolQuorumStore {
                 self.handle_block_request(max_txns, max_bytes, payload_filter, callback)
                     .await;
             }
-            ConsensusRequest::CleanRequest(_, _, callback) => {
+            ConsensusRequest::CleanRequest(_, _, _, callback) => {
                 self.handle_clean_request(callback).await;
             }
         }
@@ -4,6 +4,14 @@
 /// Equivalent to directly fetching blocks from mempool without a quorum store.
 pub mod direct_mempool_quorum_store;
 
+/// Stores the batches until they expire and fetches the missing ones from the other validators.
+pub mod batch_store;
+/// Disseminates batches and certifies their availability with proofs of store.
+pub mod quorum_store_service;
+
+mod proof_builder;
+mod proof_manager;
+
 mod counters;
 #[cfg(test)]
 mod tests;
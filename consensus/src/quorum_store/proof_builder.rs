@@ -0,0 +1,68 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::counters;
+use anyhow::ensure;
+use aptos_crypto::HashValue;
+use aptos_types::{aggregate_signature::PartialSignatures, validator_verifier::ValidatorVerifier};
+use consensus_types::proof_of_store::{LogicalTime, ProofOfStore, SignedDigest, SignedDigestInfo};
+use std::collections::HashMap;
+
+/// Collects the signed digests of the batches authored by this validator, a proof of store is
+/// formed once the signers of a batch reach a quorum of voting power.
+pub struct ProofBuilder {
+    verifier: ValidatorVerifier,
+    pending: HashMap<HashValue, (SignedDigestInfo, PartialSignatures)>,
+}
+
+impl ProofBuilder {
+    pub fn new(verifier: ValidatorVerifier) -> Self {
+        Self {
+            verifier,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// Starts collecting signatures for a batch authored by this validator.
+    pub fn init_proof(&mut self, info: SignedDigestInfo) {
+        self.pending
+            .entry(info.digest)
+            .or_insert_with(|| (info, PartialSignatures::empty()));
+    }
+
+    /// Adds a verified signed digest, returns the proof of store when the quorum is reached.
+    pub fn add_signature(
+        &mut self,
+        signed_digest: SignedDigest,
+    ) -> anyhow::Result<Option<ProofOfStore>> {
+        let digest = signed_digest.digest();
+        let (info, signatures) = match self.pending.get_mut(&digest) {
+            Some(entry) => entry,
+            // the proof is either complete already or the batch expired
+            None => return Ok(None),
+        };
+        ensure!(
+            info == signed_digest.info(),
+            "Signed digest from {} doesn't match the batch info",
+            signed_digest.signer()
+        );
+        signatures.add_signature(signed_digest.signer(), signed_digest.signature().clone());
+        if self
+            .verifier
+            .check_voting_power(signatures.signatures().keys())
+            .is_err()
+        {
+            return Ok(None);
+        }
+        let (info, signatures) = self.pending.remove(&digest).expect("must exist");
+        let multi_signature = self.verifier.aggregate_signatures(&signatures)?;
+        counters::CREATED_PROOFS_COUNT.inc();
+        Ok(Some(ProofOfStore::new(info, multi_signature)))
+    }
+
+    /// Stops collecting signatures for the batches that expire at or before the given time.
+    pub fn clear_expired(&mut self, current_time: LogicalTime) {
+        self.pending
+            .retain(|_, (info, _)| info.expiration > current_time);
+    }
+}
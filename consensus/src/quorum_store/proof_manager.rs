@@ -0,0 +1,87 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::counters;
+use aptos_crypto::HashValue;
+use consensus_types::{
+    common::{Payload, PayloadFilter},
+    proof_of_store::{LogicalTime, ProofOfStore},
+};
+use std::collections::{HashMap, HashSet};
+
+/// Holds the proofs of store received that aren't committed yet, and picks the ones to include
+/// in the proposals of this validator.
+pub struct ProofManager {
+    proofs: HashMap<HashValue, ProofOfStore>,
+    // proofs expiring within this many rounds of the latest committed round aren't proposed, so
+    // that they don't expire before the proposal is voted on
+    expiry_margin_rounds: u64,
+    latest_committed: LogicalTime,
+}
+
+impl ProofManager {
+    pub fn new(latest_committed: LogicalTime, expiry_margin_rounds: u64) -> Self {
+        Self {
+            proofs: HashMap::new(),
+            expiry_margin_rounds,
+            latest_committed,
+        }
+    }
+
+    pub fn insert(&mut self, proof: ProofOfStore) {
+        if proof.expiration() > self.latest_committed {
+            self.proofs.insert(*proof.digest(), proof);
+            counters::NUM_PROOFS_PENDING.set(self.proofs.len() as i64);
+        }
+    }
+
+    /// Returns a payload of the oldest proofs not excluded, within the given limits.
+    pub fn pull_payload(&self, max_txns: u64, max_bytes: u64, filter: PayloadFilter) -> Payload {
+        let excluded = match filter {
+            PayloadFilter::InQuorumStore(digests) => digests,
+            PayloadFilter::Empty => HashSet::new(),
+            PayloadFilter::DirectMempool(_) => {
+                unreachable!("Unknown payload_filter: {}", filter)
+            }
+        };
+        let min_expiration = LogicalTime::new(
+            self.latest_committed.epoch(),
+            self.latest_committed.round() + self.expiry_margin_rounds,
+        );
+        let mut candidates: Vec<_> = self
+            .proofs
+            .values()
+            .filter(|proof| proof.expiration() > min_expiration)
+            .filter(|proof| !excluded.contains(proof.digest()))
+            .collect();
+        candidates.sort_by_key(|proof| (proof.expiration(), *proof.digest()));
+
+        let (mut num_txns, mut num_bytes) = (0, 0);
+        let mut picked = vec![];
+        for proof in candidates {
+            if num_txns + proof.info().num_txns > max_txns
+                || num_bytes + proof.info().num_bytes > max_bytes
+            {
+                break;
+            }
+            num_txns += proof.info().num_txns;
+            num_bytes += proof.info().num_bytes;
+            picked.push(proof.clone());
+        }
+        Payload::InQuorumStore(picked)
+    }
+
+    /// Removes the committed proofs and the ones expired by the commit.
+    pub fn handle_commit(&mut self, committed_time: LogicalTime, digests: &[HashValue]) {
+        if committed_time > self.latest_committed {
+            self.latest_committed = committed_time;
+        }
+        for digest in digests {
+            self.proofs.remove(digest);
+        }
+        let latest_committed = self.latest_committed;
+        self.proofs
+            .retain(|_, proof| proof.expiration() > latest_committed);
+        counters::NUM_PROOFS_PENDING.set(self.proofs.len() as i64);
+    }
+}
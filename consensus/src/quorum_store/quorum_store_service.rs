@@ -0,0 +1,322 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    monitor,
+    network::NetworkSender,
+    quorum_store::{
+        batch_store::BatchStore, counters, proof_builder::ProofBuilder, proof_manager::ProofManager,
+    },
+    round_manager::VerifiedEvent,
+};
+use anyhow::{ensure, Result};
+use aptos_config::config::QuorumStoreConfig;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_mempool::{QuorumStoreRequest, QuorumStoreResponse};
+use aptos_types::{
+    account_address::AccountAddress, transaction::SignedTransaction,
+    validator_signer::ValidatorSigner, validator_verifier::ValidatorVerifier,
+};
+use channel::aptos_channel;
+use consensus_types::{
+    common::{Author, PayloadFilter, Round, TransactionSummary},
+    proof_of_store::{Batch, LogicalTime, ProofOfStore, SignedDigest},
+    request_response::{ConsensusRequest, ConsensusResponse},
+};
+use futures::{
+    channel::{
+        mpsc::{Receiver, Sender},
+        oneshot,
+    },
+    FutureExt, StreamExt,
+};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::time::timeout;
+
+/// Quorum store of an epoch: disseminates batches of mempool transactions to the other
+/// validators, certifies their availability with proofs of store, and serves the proofs to
+/// consensus as proposal payloads.
+pub struct QuorumStore {
+    epoch: u64,
+    author: Author,
+    config: QuorumStoreConfig,
+    mempool_txn_pull_timeout_ms: u64,
+    consensus_receiver: Receiver<ConsensusRequest>,
+    mempool_sender: Sender<QuorumStoreRequest>,
+    network_msg_rx: aptos_channel::Receiver<AccountAddress, VerifiedEvent>,
+    network_sender: NetworkSender,
+    batch_store: Arc<BatchStore>,
+    // only validators of the epoch sign batches
+    signer: Option<Arc<ValidatorSigner>>,
+    proof_builder: ProofBuilder,
+    proof_manager: ProofManager,
+    // batches authored by this validator that are neither committed nor expired, their
+    // transactions are excluded from the new batches
+    in_flight_batches: HashMap<HashValue, (LogicalTime, Vec<TransactionSummary>)>,
+    latest_committed: LogicalTime,
+}
+
+impl QuorumStore {
+    pub fn new(
+        author: Author,
+        config: QuorumStoreConfig,
+        mempool_txn_pull_timeout_ms: u64,
+        consensus_receiver: Receiver<ConsensusRequest>,
+        mempool_sender: Sender<QuorumStoreRequest>,
+        network_msg_rx: aptos_channel::Receiver<AccountAddress, VerifiedEvent>,
+        network_sender: NetworkSender,
+        batch_store: Arc<BatchStore>,
+        signer: Option<Arc<ValidatorSigner>>,
+        verifier: ValidatorVerifier,
+        last_committed_round: Round,
+    ) -> Self {
+        let epoch = batch_store.epoch();
+        let latest_committed = LogicalTime::new(epoch, last_committed_round);
+        let proof_builder = ProofBuilder::new(verifier);
+        let proof_manager = ProofManager::new(latest_committed, config.batch_expiry_round_gap / 2);
+        Self {
+            epoch,
+            author,
+            config,
+            mempool_txn_pull_timeout_ms,
+            consensus_receiver,
+            mempool_sender,
+            network_msg_rx,
+            network_sender,
+            batch_store,
+            signer,
+            proof_builder,
+            proof_manager,
+            in_flight_batches: HashMap::new(),
+            latest_committed,
+        }
+    }
+
+    async fn pull_txns(&self) -> Result<Vec<SignedTransaction>> {
+        let exclude_txns = self
+            .in_flight_batches
+            .values()
+            .flat_map(|(_, summaries)| summaries.iter().cloned())
+            .collect();
+        let (callback, callback_rcv) = oneshot::channel();
+        let msg = QuorumStoreRequest::GetBatchRequest(
+            self.config.max_batch_txns,
+            self.config.max_batch_bytes,
+            exclude_txns,
+            callback,
+        );
+        self.mempool_sender
+            .clone()
+            .try_send(msg)
+            .map_err(anyhow::Error::from)?;
+        // wait for response
+        match monitor!(
+            "pull_txn",
+            timeout(
+                Duration::from_millis(self.mempool_txn_pull_timeout_ms),
+                callback_rcv
+            )
+            .await
+        ) {
+            Err(_) => Err(anyhow::anyhow!(
+                "[quorum_store] did not receive GetBatchResponse on time"
+            )),
+            Ok(resp) => match resp.map_err(anyhow::Error::from)?? {
+                QuorumStoreResponse::GetBatchResponse(txns) => Ok(txns),
+                _ => Err(anyhow::anyhow!(
+                    "[quorum_store] did not receive expected GetBatchResponse"
+                )),
+            },
+        }
+    }
+
+    /// Pulls a batch from mempool and broadcasts it, including to self, the signed digests are
+    /// then sent back to this validator to form the proof.
+    async fn generate_batch(&mut self) -> Result<()> {
+        if self.signer.is_none() {
+            return Ok(());
+        }
+        let txns = self.pull_txns().await?;
+        if txns.is_empty() {
+            return Ok(());
+        }
+        let summaries = txns
+            .iter()
+            .map(|txn| TransactionSummary {
+                sender: txn.sender(),
+                sequence_number: txn.sequence_number(),
+            })
+            .collect();
+        let expiration = LogicalTime::new(
+            self.epoch,
+            self.latest_committed.round() + self.config.batch_expiry_round_gap,
+        );
+        let batch = Batch::new(self.epoch, self.author, expiration, txns);
+        debug!("Created {}", batch);
+        counters::CREATED_BATCHES_COUNT.inc();
+        self.in_flight_batches
+            .insert(batch.digest(), (expiration, summaries));
+        self.proof_builder.init_proof(batch.info());
+        self.network_sender.broadcast_batch(batch).await;
+        Ok(())
+    }
+
+    /// Stores a batch of a peer and sends a signed digest back to its author.
+    async fn process_batch(&mut self, peer_id: Author, batch: Batch) -> Result<()> {
+        batch.verify(peer_id)?;
+        ensure!(
+            batch.expiration() > self.latest_committed,
+            "{} is already expired at {}",
+            batch,
+            self.latest_committed
+        );
+        // peers may be ahead of us, but a batch can't be kept for much longer than a gap
+        ensure!(
+            batch.expiration().round()
+                <= self.latest_committed.round() + 2 * self.config.batch_expiry_round_gap,
+            "{} expires too far in the future",
+            batch
+        );
+        ensure!(
+            batch.num_txns() <= self.config.max_batch_txns
+                && batch.num_bytes() <= self.config.max_batch_bytes,
+            "{} exceeds the batch limits",
+            batch
+        );
+        let info = batch.info();
+        self.batch_store.save_within_quota(batch)?;
+        if let Some(signer) = &self.signer {
+            let signed_digest = SignedDigest::new(self.epoch, info, signer)?;
+            self.network_sender
+                .send_signed_digest(signed_digest, peer_id)
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn process_signed_digest(&mut self, signed_digest: SignedDigest) -> Result<()> {
+        if let Some(proof) = self.proof_builder.add_signature(signed_digest)? {
+            debug!("Created {}", proof);
+            self.network_sender.broadcast_proof_of_store(proof).await;
+        }
+        Ok(())
+    }
+
+    fn process_proof_of_store(&mut self, proof: ProofOfStore) {
+        self.proof_manager.insert(proof);
+    }
+
+    async fn process_network_msg(&mut self, peer_id: Author, event: VerifiedEvent) -> Result<()> {
+        match event {
+            VerifiedEvent::Batch(batch) => self.process_batch(peer_id, *batch).await,
+            VerifiedEvent::SignedDigest(signed_digest) => {
+                self.process_signed_digest(*signed_digest).await
+            }
+            VerifiedEvent::ProofOfStore(proof) => {
+                self.process_proof_of_store(*proof);
+                Ok(())
+            }
+            _ => unreachable!("Unexpected quorum store message {:?}", event),
+        }
+    }
+
+    fn handle_block_request(
+        &self,
+        max_txns: u64,
+        max_bytes: u64,
+        payload_filter: PayloadFilter,
+        callback: oneshot::Sender<Result<ConsensusResponse>>,
+    ) {
+        let get_block_response_start_time = Instant::now();
+        let payload = self
+            .proof_manager
+            .pull_payload(max_txns, max_bytes, payload_filter);
+        let result = match callback.send(Ok(ConsensusResponse::GetBlockResponse(payload))) {
+            Err(_) => {
+                error!("Callback failed");
+                counters::CALLBACK_FAIL_LABEL
+            }
+            Ok(_) => counters::CALLBACK_SUCCESS_LABEL,
+        };
+        counters::quorum_store_service_latency(
+            counters::GET_BLOCK_RESPONSE_LABEL,
+            result,
+            get_block_response_start_time.elapsed(),
+        );
+    }
+
+    fn handle_clean_request(
+        &mut self,
+        epoch: u64,
+        round: Round,
+        digests: Vec<HashValue>,
+        callback: oneshot::Sender<Result<ConsensusResponse>>,
+    ) {
+        let committed_time = LogicalTime::new(epoch, round);
+        if committed_time > self.latest_committed {
+            self.latest_committed = committed_time;
+        }
+        let latest_committed = self.latest_committed;
+        for digest in &digests {
+            self.in_flight_batches.remove(digest);
+        }
+        self.in_flight_batches
+            .retain(|_, (expiration, _)| *expiration > latest_committed);
+        self.proof_manager.handle_commit(latest_committed, &digests);
+        self.proof_builder.clear_expired(latest_committed);
+        self.batch_store.clear_expired(latest_committed);
+
+        if callback
+            .send(Ok(ConsensusResponse::CleanResponse()))
+            .is_err()
+        {
+            error!("Callback failed");
+        }
+    }
+
+    async fn handle_consensus_request(&mut self, req: ConsensusRequest) {
+        match req {
+            ConsensusRequest::GetBlockRequest(max_txns, max_bytes, payload_filter, callback) => {
+                self.handle_block_request(max_txns, max_bytes, payload_filter, callback);
+            }
+            ConsensusRequest::CleanRequest(epoch, round, digests, callback) => {
+                self.handle_clean_request(epoch, round, digests, callback);
+            }
+        }
+    }
+
+    pub async fn start(mut self) {
+        info!(epoch = self.epoch, "Quorum store starts");
+        let mut interval = tokio::time::interval(Duration::from_millis(
+            self.config.batch_generation_poll_interval_ms,
+        ));
+        loop {
+            let _timer = counters::MAIN_LOOP.start_timer();
+            ::futures::select! {
+                msg = self.consensus_receiver.select_next_some() => {
+                    self.handle_consensus_request(msg).await;
+                },
+                msg = self.network_msg_rx.next() => match msg {
+                    Some((peer_id, event)) => {
+                        if let Err(e) = self.process_network_msg(peer_id, event).await {
+                            warn!(epoch = self.epoch, remote_peer = peer_id, error = ?e);
+                        }
+                    }
+                    // the epoch manager dropped the sender, the epoch ended
+                    None => break,
+                },
+                _ = interval.tick().fuse() => {
+                    if let Err(e) = self.generate_batch().await {
+                        warn!(epoch = self.epoch, error = ?e, "Failed to generate batch");
+                    }
+                },
+            }
+        }
+        info!(epoch = self.epoch, "Quorum store stops");
+    }
+}
@@ -3,3 +3,5 @@
 
 #[cfg(test)]
 mod direct_mempool_quorum_store_test;
+#[cfg(test)]
+mod quorum_store_test;
@@ -0,0 +1,313 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::{IncomingBatchRetrievalRequest, NetworkSender},
+    network_interface::{ConsensusMsg, ConsensusNetworkSender},
+    persistent_liveness_storage::PersistentLivenessStorage,
+    quorum_store::{
+        batch_store::BatchStore, proof_builder::ProofBuilder, proof_manager::ProofManager,
+    },
+    test_utils::MockStorage,
+};
+use aptos_config::config::QuorumStoreConfig;
+use aptos_crypto::HashValue;
+use aptos_types::{
+    aggregate_signature::PartialSignatures,
+    validator_signer::ValidatorSigner,
+    validator_verifier::{random_validator_verifier, ValidatorVerifier},
+};
+use channel::{aptos_channel, message_queues::QueueStyle};
+use consensus_types::{
+    block::block_test_utils::random_txns,
+    common::{Payload, PayloadFilter},
+    proof_of_store::{Batch, BatchRequest, LogicalTime, ProofOfStore, SignedDigest},
+};
+use futures::channel::oneshot;
+use network::{
+    peer_manager::{ConnectionRequestSender, PeerManagerRequestSender},
+    protocols::network::NewNetworkSender,
+    ProtocolId,
+};
+use std::{collections::HashSet, sync::Arc};
+
+fn create_batch(signer: &ValidatorSigner, expiration: LogicalTime, num_txns: usize) -> Batch {
+    Batch::new(
+        expiration.epoch(),
+        signer.author(),
+        expiration,
+        random_txns(num_txns),
+    )
+}
+
+fn create_proof(
+    signers: &[ValidatorSigner],
+    validators: &ValidatorVerifier,
+    batch: &Batch,
+) -> ProofOfStore {
+    let mut signatures = PartialSignatures::empty();
+    for signer in signers {
+        signatures.add_signature(signer.author(), signer.sign(&batch.info()).unwrap());
+    }
+    ProofOfStore::new(
+        batch.info(),
+        validators.aggregate_signatures(&signatures).unwrap(),
+    )
+}
+
+fn create_batch_store(
+    signer: &ValidatorSigner,
+    validators: &ValidatorVerifier,
+    storage: Arc<dyn PersistentLivenessStorage>,
+    epoch: u64,
+    config: &QuorumStoreConfig,
+) -> BatchStore {
+    let (network_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+    let (connection_reqs_tx, _) = aptos_channel::new(QueueStyle::FIFO, 8, None);
+    let network_sender = ConsensusNetworkSender::new(
+        PeerManagerRequestSender::new(network_reqs_tx),
+        ConnectionRequestSender::new(connection_reqs_tx),
+    );
+    let (self_sender, _) = channel::new_test(8);
+    BatchStore::new(
+        epoch,
+        signer.author(),
+        storage,
+        NetworkSender::new(
+            signer.author(),
+            network_sender,
+            self_sender,
+            validators.clone(),
+        ),
+        validators.clone(),
+        config,
+    )
+}
+
+fn test_config() -> QuorumStoreConfig {
+    QuorumStoreConfig {
+        batch_request_timeout_ms: 100,
+        ..QuorumStoreConfig::default()
+    }
+}
+
+#[test]
+fn test_proof_builder_quorum() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let batch = create_batch(&signers[0], LogicalTime::new(1, 10), 5);
+    let mut proof_builder = ProofBuilder::new(validators.clone());
+
+    // signatures of unknown batches are ignored
+    let signed_digest = SignedDigest::new(1, batch.info(), &signers[1]).unwrap();
+    assert!(proof_builder
+        .add_signature(signed_digest)
+        .unwrap()
+        .is_none());
+
+    proof_builder.init_proof(batch.info());
+    for signer in &signers[..2] {
+        let signed_digest = SignedDigest::new(1, batch.info(), signer).unwrap();
+        assert!(proof_builder
+            .add_signature(signed_digest)
+            .unwrap()
+            .is_none());
+    }
+    // a signature on a different info is rejected
+    let mut info = batch.info();
+    info.num_txns += 1;
+    let signed_digest = SignedDigest::new(1, info, &signers[2]).unwrap();
+    assert!(proof_builder.add_signature(signed_digest).is_err());
+
+    let signed_digest = SignedDigest::new(1, batch.info(), &signers[2]).unwrap();
+    let proof = proof_builder.add_signature(signed_digest).unwrap().unwrap();
+    assert_eq!(*proof.digest(), batch.digest());
+    assert!(proof.verify(&validators).is_ok());
+    assert_eq!(proof.signers(&validators).len(), 3);
+
+    // the proof is formed only once
+    let signed_digest = SignedDigest::new(1, batch.info(), &signers[3]).unwrap();
+    assert!(proof_builder
+        .add_signature(signed_digest)
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_proof_verify() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let batch = create_batch(&signers[0], LogicalTime::new(1, 10), 5);
+
+    assert!(create_proof(&signers[..3], &validators, &batch)
+        .verify(&validators)
+        .is_ok());
+    assert!(create_proof(&signers[..2], &validators, &batch)
+        .verify(&validators)
+        .is_err());
+
+    let empty_batch = create_batch(&signers[0], LogicalTime::new(1, 10), 0);
+    assert!(create_proof(&signers[..3], &validators, &empty_batch)
+        .verify(&validators)
+        .is_err());
+    assert!(Payload::InQuorumStore(vec![
+        create_proof(&signers[..3], &validators, &batch),
+        create_proof(&signers[1..], &validators, &batch),
+    ])
+    .verify(&validators)
+    .is_err());
+}
+
+#[test]
+fn test_proof_manager_pull_payload() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let mut proof_manager = ProofManager::new(LogicalTime::new(1, 0), 5);
+    let batches: Vec<_> = (0..4)
+        .map(|i| create_batch(&signers[0], LogicalTime::new(1, 10 + i), 10))
+        .collect();
+    for batch in &batches {
+        proof_manager.insert(create_proof(&signers[..3], &validators, batch));
+    }
+    let digests = |payload: Payload| match payload {
+        Payload::InQuorumStore(proofs) => proofs
+            .iter()
+            .map(|proof| *proof.digest())
+            .collect::<Vec<_>>(),
+        _ => panic!("Unexpected payload"),
+    };
+
+    // the proofs expiring first are picked first, within the limits
+    let payload = proof_manager.pull_payload(25, u64::MAX, PayloadFilter::Empty);
+    assert_eq!(
+        digests(payload),
+        vec![batches[0].digest(), batches[1].digest()]
+    );
+
+    // the proofs of pending blocks are excluded
+    let excluded = HashSet::from([batches[0].digest()]);
+    let payload = proof_manager.pull_payload(25, u64::MAX, PayloadFilter::InQuorumStore(excluded));
+    assert_eq!(
+        digests(payload),
+        vec![batches[1].digest(), batches[2].digest()]
+    );
+
+    // the committed proofs are removed, and the ones expiring soon aren't proposed
+    proof_manager.handle_commit(LogicalTime::new(1, 6), &[batches[3].digest()]);
+    let payload = proof_manager.pull_payload(100, u64::MAX, PayloadFilter::Empty);
+    assert_eq!(digests(payload), vec![batches[2].digest()]);
+
+    proof_manager.handle_commit(LogicalTime::new(1, 12), &[]);
+    assert!(proof_manager
+        .pull_payload(100, u64::MAX, PayloadFilter::Empty)
+        .is_empty());
+}
+
+#[test]
+fn test_batch_store_persistence() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let (_, storage) = MockStorage::start_for_testing((&validators).into());
+    let stale_batch = create_batch(&signers[1], LogicalTime::new(1, 10), 5);
+    storage.save_batches(vec![stale_batch.clone()]).unwrap();
+
+    // the batches of the previous epochs are deleted
+    let batch_store =
+        create_batch_store(&signers[0], &validators, storage.clone(), 2, &test_config());
+    assert!(batch_store.get(&stale_batch.digest()).is_none());
+    assert!(storage.get_batches().unwrap().is_empty());
+
+    let batches: Vec<_> = (0..3)
+        .map(|i| create_batch(&signers[1], LogicalTime::new(2, 10 + i), 5))
+        .collect();
+    for batch in &batches {
+        batch_store.save(batch.clone()).unwrap();
+    }
+    assert!(batch_store.save(stale_batch).is_err());
+
+    // the batches of the current epoch are loaded
+    let batch_store =
+        create_batch_store(&signers[0], &validators, storage.clone(), 2, &test_config());
+    for batch in &batches {
+        assert_eq!(batch_store.get(&batch.digest()).as_ref(), Some(batch));
+    }
+
+    batch_store.clear_expired(LogicalTime::new(2, 11));
+    assert!(batch_store.get(&batches[0].digest()).is_none());
+    assert!(batch_store.get(&batches[1].digest()).is_none());
+    assert!(batch_store.get(&batches[2].digest()).is_some());
+    assert_eq!(storage.get_batches().unwrap(), vec![batches[2].clone()]);
+}
+
+#[test]
+fn test_batch_store_quota() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let (_, storage) = MockStorage::start_for_testing((&validators).into());
+    let batches: Vec<_> = (0..3)
+        .map(|i| create_batch(&signers[1], LogicalTime::new(1, 10 + i), 5))
+        .collect();
+    let config = QuorumStoreConfig {
+        max_batches_per_author: 2,
+        max_bytes_per_author: batches.iter().map(Batch::num_bytes).sum(),
+        ..test_config()
+    };
+    let batch_store = create_batch_store(&signers[0], &validators, storage, 1, &config);
+
+    batch_store.save_within_quota(batches[0].clone()).unwrap();
+    batch_store.save_within_quota(batches[1].clone()).unwrap();
+    // saving a stored batch again doesn't count
+    batch_store.save_within_quota(batches[1].clone()).unwrap();
+    assert!(batch_store.save_within_quota(batches[2].clone()).is_err());
+    assert!(batch_store.get(&batches[2].digest()).is_none());
+
+    // the quota is per author, and limits the bytes too
+    let large_batch = create_batch(&signers[2], LogicalTime::new(1, 20), 50);
+    assert!(batch_store.save_within_quota(large_batch.clone()).is_err());
+    let other_batch = create_batch(&signers[2], LogicalTime::new(1, 20), 5);
+    batch_store.save_within_quota(other_batch).unwrap();
+
+    // expired batches don't count anymore
+    batch_store.clear_expired(LogicalTime::new(1, 10));
+    batch_store.save_within_quota(batches[2].clone()).unwrap();
+
+    // batches of certified proofs are saved regardless of the quota
+    batch_store.save(large_batch.clone()).unwrap();
+    assert_eq!(batch_store.get(&large_batch.digest()), Some(large_batch));
+}
+
+#[tokio::test]
+async fn test_batch_store_process_batch_request() {
+    let (signers, validators) = random_validator_verifier(4, None, false);
+    let (_, storage) = MockStorage::start_for_testing((&validators).into());
+    let batch_store = create_batch_store(&signers[0], &validators, storage, 1, &test_config());
+    let batch = create_batch(&signers[1], LogicalTime::new(1, 10), 5);
+    batch_store.save(batch.clone()).unwrap();
+
+    // the local batches are returned without fetching them
+    let proof = create_proof(&signers[1..], &validators, &batch);
+    assert_eq!(
+        batch_store.get_transactions(&[proof]).await.unwrap(),
+        batch.txns()
+    );
+
+    let (tx, rx) = oneshot::channel();
+    batch_store
+        .process_batch_request(IncomingBatchRetrievalRequest {
+            req: BatchRequest::new(1, batch.digest()),
+            protocol: ProtocolId::ConsensusRpcBcs,
+            response_sender: tx,
+        })
+        .unwrap();
+    match rx.await {
+        Ok(Ok(bytes)) => match bcs::from_bytes(&bytes) {
+            Ok(ConsensusMsg::BatchMsg(response)) => assert_eq!(*response, batch),
+            _ => panic!("batch retrieval failure"),
+        },
+        _ => panic!("batch retrieval failure"),
+    }
+
+    let (tx, _rx) = oneshot::channel();
+    assert!(batch_store
+        .process_batch_request(IncomingBatchRetrievalRequest {
+            req: BatchRequest::new(1, HashValue::random()),
+            protocol: ProtocolId::ConsensusRpcBcs,
+            response_sender: tx,
+        })
+        .is_err());
+}
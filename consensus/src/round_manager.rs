@@ -33,8 +33,10 @@ use aptos_types::{
 use channel::aptos_channel;
 use consensus_types::{
     block::Block,
-    common::{Author, Round},
+    common::{Author, Payload, Round},
+    evidence::Evidence,
     experimental::{commit_decision::CommitDecision, commit_vote::CommitVote},
+    proof_of_store::{Batch, LogicalTime, ProofOfStore, SignedDigest},
     proposal_msg::ProposalMsg,
     quorum_cert::QuorumCert,
     sync_info::SyncInfo,
@@ -62,6 +64,9 @@ pub enum UnverifiedEvent {
     SyncInfo(Box<SyncInfo>),
     CommitVote(Box<CommitVote>),
     CommitDecision(Box<CommitDecision>),
+    Batch(Box<Batch>),
+    SignedDigest(Box<SignedDigest>),
+    ProofOfStore(Box<ProofOfStore>),
 }
 
 pub const BACK_PRESSURE_POLLING_INTERVAL_MS: u64 = 10;
@@ -87,6 +92,16 @@ impl UnverifiedEvent {
                 cd.verify(validator)?;
                 VerifiedEvent::CommitDecision(cd)
             }
+            // the author of the batch is checked against the sender by the quorum store
+            UnverifiedEvent::Batch(b) => VerifiedEvent::Batch(b),
+            UnverifiedEvent::SignedDigest(sd) => {
+                sd.verify(validator)?;
+                VerifiedEvent::SignedDigest(sd)
+            }
+            UnverifiedEvent::ProofOfStore(p) => {
+                p.verify(validator)?;
+                VerifiedEvent::ProofOfStore(p)
+            }
         })
     }
 
@@ -97,6 +112,9 @@ impl UnverifiedEvent {
             UnverifiedEvent::SyncInfo(s) => s.epoch(),
             UnverifiedEvent::CommitVote(cv) => cv.epoch(),
             UnverifiedEvent::CommitDecision(cd) => cd.epoch(),
+            UnverifiedEvent::Batch(b) => b.epoch(),
+            UnverifiedEvent::SignedDigest(sd) => sd.epoch(),
+            UnverifiedEvent::ProofOfStore(p) => p.epoch(),
         }
     }
 }
@@ -109,6 +127,9 @@ impl From<ConsensusMsg> for UnverifiedEvent {
             ConsensusMsg::SyncInfo(m) => UnverifiedEvent::SyncInfo(m),
             ConsensusMsg::CommitVoteMsg(m) => UnverifiedEvent::CommitVote(m),
             ConsensusMsg::CommitDecisionMsg(m) => UnverifiedEvent::CommitDecision(m),
+            ConsensusMsg::BatchMsg(m) => UnverifiedEvent::Batch(m),
+            ConsensusMsg::SignedDigestMsg(m) => UnverifiedEvent::SignedDigest(m),
+            ConsensusMsg::ProofOfStoreMsg(m) => UnverifiedEvent::ProofOfStore(m),
             _ => unreachable!("Unexpected conversion"),
         }
     }
@@ -123,6 +144,9 @@ pub enum VerifiedEvent {
     UnverifiedSyncInfo(Box<SyncInfo>),
     CommitVote(Box<CommitVote>),
     CommitDecision(Box<CommitDecision>),
+    Batch(Box<Batch>),
+    SignedDigest(Box<SignedDigest>),
+    ProofOfStore(Box<ProofOfStore>),
     // local messages
     LocalTimeout(Round),
 }
@@ -601,12 +625,35 @@ impl RoundManager {
             self.local_config.max_receiving_block_bytes,
         );
 
-        ensure!(
-            self.proposer_election.is_valid_proposal(&proposal),
-            "[RoundManager] Proposer {} for block {} is not a valid proposer for this round or created duplicate proposal",
-            author,
-            proposal,
-        );
+        if let Some(payload) = proposal.payload() {
+            ensure!(
+                payload.is_empty()
+                    || matches!(payload, Payload::InQuorumStore(_))
+                        == self.local_config.use_quorum_store,
+                "Payload type of block {} doesn't match the quorum store config {}",
+                proposal,
+                self.local_config.use_quorum_store,
+            );
+            if let Payload::InQuorumStore(proofs) = payload {
+                let current_time = LogicalTime::new(proposal.epoch(), proposal.round());
+                ensure!(
+                    proofs.iter().all(|proof| proof.expiration() > current_time),
+                    "Block {} contains expired proofs of store",
+                    proposal,
+                );
+            }
+        }
+
+        if !self.proposer_election.is_valid_proposal(&proposal) {
+            if let Some(evidence) = self.proposer_election.get_double_proposal(&proposal) {
+                self.record_evidence(evidence);
+            }
+            bail!(
+                "[RoundManager] Proposer {} for block {} is not a valid proposer for this round or created duplicate proposal",
+                author,
+                proposal,
+            );
+        }
 
         // Validate that failed_authors list is correctly specified in the block.
         let expected_failed_authors = self.proposal_generator.compute_failed_authors(
@@ -829,10 +876,25 @@ impl RoundManager {
             VoteReceptionResult::VoteAdded(_)
             | VoteReceptionResult::EchoTimeout(_)
             | VoteReceptionResult::DuplicateVote => Ok(()),
+            VoteReceptionResult::EquivocateVote(evidence) => {
+                self.record_evidence(*evidence);
+                bail!("Equivocating vote {}", vote)
+            }
             e => Err(anyhow::anyhow!("{:?}", e)),
         }
     }
 
+    /// Persists the evidence of a validator misbehaving, so that it can be inspected and
+    /// submitted later. The misbehavior itself is logged where it's detected.
+    fn record_evidence(&self, evidence: Evidence) {
+        counters::EQUIVOCATION_EVIDENCE_COUNT
+            .with_label_values(&[evidence.name()])
+            .inc();
+        if let Err(e) = self.storage.save_evidence(&evidence) {
+            error!(error = ?e, "Failed to save evidence {}", evidence);
+        }
+    }
+
     async fn new_qc_aggregated(
         &mut self,
         qc: Arc<QuorumCert>,
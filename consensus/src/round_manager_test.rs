@@ -28,23 +28,29 @@ use aptos_infallible::Mutex;
 use aptos_logger::prelude::info;
 use aptos_secure_storage::Storage;
 use aptos_types::{
+    aggregate_signature::PartialSignatures,
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
     on_chain_config::OnChainConsensusConfig,
     transaction::SignedTransaction,
     validator_signer::ValidatorSigner,
-    validator_verifier::{generate_validator_verifier, random_validator_verifier},
+    validator_verifier::{
+        generate_validator_verifier, random_validator_verifier, ValidatorVerifier,
+    },
     waypoint::Waypoint,
 };
 use channel::{self, aptos_channel, message_queues::QueueStyle};
 use consensus_types::{
     block::{
-        block_test_utils::{certificate_for_genesis, gen_test_certificate},
+        block_test_utils::{
+            certificate_for_genesis, gen_test_certificate, random_payload, random_txns,
+        },
         Block,
     },
     block_retrieval::{BlockRetrievalRequest, BlockRetrievalStatus},
     common::{Author, Payload, Round},
     experimental::commit_decision::CommitDecision,
+    proof_of_store::{Batch, LogicalTime, ProofOfStore},
     proposal_msg::ProposalMsg,
     sync_info::SyncInfo,
     timeout_2chain::{TwoChainTimeout, TwoChainTimeoutWithPartialSignatures},
@@ -1795,3 +1801,115 @@ pub fn forking_retrieval_test() {
         3,
     );
 }
+
+fn create_proof_of_store(
+    signer: &ValidatorSigner,
+    validators: &ValidatorVerifier,
+    expiration: LogicalTime,
+) -> ProofOfStore {
+    let batch = Batch::new(
+        expiration.epoch(),
+        signer.author(),
+        expiration,
+        random_txns(5),
+    );
+    let mut signatures = PartialSignatures::empty();
+    signatures.add_signature(signer.author(), signer.sign(&batch.info()).unwrap());
+    ProofOfStore::new(
+        batch.info(),
+        validators.aggregate_signatures(&signatures).unwrap(),
+    )
+}
+
+#[test]
+/// If the proposal carries valid proofs of store, a vote should be sent
+fn vote_on_proof_of_store_proposal() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1, None);
+    let node = &mut nodes[0];
+    node.round_manager.local_config.use_quorum_store = true;
+    let validators = generate_validator_verifier(&[node.signer.clone()]);
+
+    let genesis_qc = certificate_for_genesis();
+    timed_block_on(&runtime, async {
+        // Start round 1 and clear the message queue
+        node.next_proposal().await;
+
+        let proof = create_proof_of_store(&node.signer, &validators, LogicalTime::new(1, 10));
+        let proposal = Block::new_proposal(
+            Payload::InQuorumStore(vec![proof]),
+            1,
+            1,
+            genesis_qc.clone(),
+            &node.signer,
+            Vec::new(),
+        )
+        .unwrap();
+        proposal.validate_signature(&validators).unwrap();
+        let proposal_id = proposal.id();
+        node.round_manager.process_proposal(proposal).await.unwrap();
+        let vote_msg = node.next_vote().await;
+        assert_eq!(vote_msg.vote().vote_data().proposed().id(), proposal_id);
+    });
+}
+
+#[test]
+/// Proposals with invalid or expired proofs of store, or with a payload that doesn't match the
+/// quorum store config, are rejected
+fn no_vote_on_invalid_proof_of_store_proposal() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1, None);
+    let node = &mut nodes[0];
+    let validators = generate_validator_verifier(&[node.signer.clone()]);
+
+    let signer = node.signer.clone();
+    let genesis_qc = certificate_for_genesis();
+    let create_proposal = |payload: Payload| {
+        Block::new_proposal(payload, 1, 1, genesis_qc.clone(), &signer, Vec::new()).unwrap()
+    };
+
+    // proofs signed by a non-validator don't verify
+    let (other_signers, other_validators) = random_validator_verifier(1, None, false);
+    let invalid_proof = create_proof_of_store(
+        &other_signers[0],
+        &other_validators,
+        LogicalTime::new(1, 10),
+    );
+    assert!(create_proposal(Payload::InQuorumStore(vec![invalid_proof]))
+        .validate_signature(&validators)
+        .is_err());
+
+    let valid_proof = create_proof_of_store(&signer, &validators, LogicalTime::new(1, 10));
+    let expired_proof = create_proof_of_store(&signer, &validators, LogicalTime::new(1, 1));
+    timed_block_on(&runtime, async {
+        // Start round 1 and clear the message queue
+        node.next_proposal().await;
+
+        // quorum store payloads are rejected without a quorum store
+        assert!(node
+            .round_manager
+            .process_proposal(create_proposal(Payload::InQuorumStore(vec![
+                valid_proof.clone()
+            ])))
+            .await
+            .is_err());
+
+        node.round_manager.local_config.use_quorum_store = true;
+        assert!(node
+            .round_manager
+            .process_proposal(create_proposal(random_payload(1)))
+            .await
+            .is_err());
+        assert!(node
+            .round_manager
+            .process_proposal(create_proposal(Payload::InQuorumStore(vec![
+                valid_proof,
+                expired_proof,
+            ])))
+            .await
+            .is_err());
+        node.no_next_msg();
+    });
+}
@@ -7,7 +7,7 @@ use crate::{
     commit_notifier::CommitNotifier,
     counters,
     error::StateSyncError,
-    state_replication::{StateComputer, StateComputerCommitCallBackType},
+    state_replication::{PayloadManager, StateComputer, StateComputerCommitCallBackType},
     txn_notifier::TxnNotifier,
 };
 use anyhow::Result;
@@ -19,7 +19,11 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
 };
 use consensus_notifications::ConsensusNotificationSender;
-use consensus_types::{block::Block, common::Round, executed_block::ExecutedBlock};
+use consensus_types::{
+    block::Block,
+    common::{Payload, Round},
+    executed_block::ExecutedBlock,
+};
 use executor_types::{BlockExecutorTrait, Error as ExecutionError, StateComputeResult};
 use fail::fail_point;
 use futures::{SinkExt, StreamExt};
@@ -32,7 +36,7 @@ type NotificationType = (
     Vec<ContractEvent>,
 );
 
-type CommitType = (u64, Round);
+type CommitType = (u64, Round, Vec<HashValue>);
 
 /// Basic communication with the Execution module;
 /// implements StateComputer traits.
@@ -43,6 +47,7 @@ pub struct ExecutionProxy {
     async_state_sync_notifier: channel::Sender<NotificationType>,
     async_commit_notifier: channel::Sender<CommitType>,
     validators: Mutex<Vec<AccountAddress>>,
+    payload_manager: Mutex<Option<Arc<dyn PayloadManager>>>,
     write_mutex: AsyncMutex<()>,
 }
 
@@ -73,10 +78,11 @@ impl ExecutionProxy {
             channel::new::<CommitType>(10, &counters::PENDING_QUORUM_STORE_COMMIT_NOTIFICATION);
         let notifier = commit_notifier.clone();
         handle.spawn(async move {
-            while let Some((epoch, round)) = commit_rx.next().await {
-                if let Err(e) =
-                    monitor!("notify_commit", notifier.notify_commit(epoch, round).await)
-                {
+            while let Some((epoch, round, batches)) = commit_rx.next().await {
+                if let Err(e) = monitor!(
+                    "notify_commit",
+                    notifier.notify_commit(epoch, round, batches).await
+                ) {
                     error!(error = ?e, "Failed to notify commit notifier");
                 }
             }
@@ -88,9 +94,17 @@ impl ExecutionProxy {
             async_state_sync_notifier: tx,
             async_commit_notifier: commit_tx,
             validators: Mutex::new(vec![]),
+            payload_manager: Mutex::new(None),
             write_mutex: AsyncMutex::new(()),
         }
     }
+
+    fn payload_manager(&self) -> Arc<dyn PayloadManager> {
+        self.payload_manager
+            .lock()
+            .clone()
+            .expect("ExecutionProxy not started for an epoch yet")
+    }
 }
 
 #[async_trait::async_trait]
@@ -116,7 +130,12 @@ impl StateComputer for ExecutionProxy {
 
         // TODO: figure out error handling for the prologue txn
         let executor = self.executor.clone();
-        let transactions_to_execute = block.transactions_to_execute(&self.validators.lock());
+        let txns = monitor!(
+            "get_transactions",
+            self.payload_manager().get_transactions(block).await
+        )?;
+        let transactions_to_execute =
+            block.transactions_to_execute(&self.validators.lock(), txns.clone());
         let compute_result = monitor!(
             "execute_block",
             tokio::task::spawn_blocking(move || {
@@ -130,7 +149,7 @@ impl StateComputer for ExecutionProxy {
         // notify mempool about failed transaction
         if let Err(e) = self
             .txn_notifier
-            .notify_failed_txn(block, &compute_result)
+            .notify_failed_txn(&txns, &compute_result)
             .await
         {
             error!(
@@ -155,10 +174,16 @@ impl StateComputer for ExecutionProxy {
         let skip_clean = blocks.is_empty();
         let mut latest_epoch: u64 = 0;
         let mut latest_round: u64 = 0;
+        let mut committed_batches = Vec::new();
+        let payload_manager = self.payload_manager();
 
         for block in blocks {
             block_ids.push(block.id());
-            txns.extend(block.transactions_to_commit(&self.validators.lock()));
+            let block_txns = payload_manager.get_transactions(block.block()).await?;
+            txns.extend(block.transactions_to_commit(&self.validators.lock(), block_txns));
+            if let Some(Payload::InQuorumStore(proofs)) = block.block().payload() {
+                committed_batches.extend(proofs.iter().map(|proof| *proof.digest()));
+            }
             reconfig_events.extend(block.reconfig_event());
 
             if block.epoch() > latest_epoch {
@@ -199,7 +224,7 @@ impl StateComputer for ExecutionProxy {
         }
         self.async_commit_notifier
             .clone()
-            .send((latest_epoch, latest_round))
+            .send((latest_epoch, latest_round, committed_batches))
             .await
             .expect("Failed to send async commit notification");
         Ok(())
@@ -236,10 +261,11 @@ impl StateComputer for ExecutionProxy {
         })
     }
 
-    fn new_epoch(&self, epoch_state: &EpochState) {
+    fn new_epoch(&self, epoch_state: &EpochState, payload_manager: Arc<dyn PayloadManager>) {
         *self.validators.lock() = epoch_state
             .verifier
             .get_ordered_account_addresses_iter()
             .collect();
+        *self.payload_manager.lock() = Some(payload_manager);
     }
 }
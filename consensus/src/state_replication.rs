@@ -4,7 +4,9 @@
 use crate::error::{QuorumStoreError, StateSyncError};
 use anyhow::Result;
 use aptos_crypto::HashValue;
-use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures};
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures, transaction::SignedTransaction,
+};
 use consensus_types::{
     block::Block,
     common::{Payload, PayloadFilter},
@@ -28,6 +30,21 @@ pub trait PayloadManager: Send + Sync {
         pending_ordering: bool,
     ) -> Result<Payload, QuorumStoreError>;
 
+    /// Returns the user transactions of the block's payload, fetching the batches the payload
+    /// refers to if they're not available locally.
+    async fn get_transactions(
+        &self,
+        block: &Block,
+    ) -> Result<Vec<SignedTransaction>, ExecutionError> {
+        match block.payload() {
+            None => Ok(vec![]),
+            Some(Payload::DirectMempool(txns)) => Ok(txns.clone()),
+            Some(Payload::InQuorumStore(_)) => Err(ExecutionError::InternalError {
+                error: "Quorum store payload without a quorum store".into(),
+            }),
+        }
+    }
+
     fn trace_payloads(&self) {}
 }
 
@@ -61,6 +78,7 @@ pub trait StateComputer: Send + Sync {
     /// can assume there were no modifications to the storage made.
     async fn sync_to(&self, target: LedgerInfoWithSignatures) -> Result<(), StateSyncError>;
 
-    // Reconfigure to execute transactions for a new epoch.
-    fn new_epoch(&self, epoch_state: &EpochState);
+    // Reconfigure to execute transactions for a new epoch, with the payload manager of the epoch
+    // resolving the transactions of the blocks.
+    fn new_epoch(&self, epoch_state: &EpochState, payload_manager: Arc<dyn PayloadManager>);
 }
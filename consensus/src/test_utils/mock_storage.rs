@@ -17,7 +17,8 @@ use aptos_types::{
     on_chain_config::ValidatorSet,
 };
 use consensus_types::{
-    block::Block, quorum_cert::QuorumCert, timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
+    block::Block, evidence::Evidence, proof_of_store::Batch, quorum_cert::QuorumCert,
+    timeout_2chain::TwoChainTimeoutCertificate, vote::Vote,
 };
 use std::{collections::HashMap, sync::Arc};
 use storage_interface::DbReader;
@@ -28,6 +29,8 @@ pub struct MockSharedStorage {
     pub qc: Mutex<HashMap<HashValue, QuorumCert>>,
     pub lis: Mutex<HashMap<u64, LedgerInfoWithSignatures>>,
     pub last_vote: Mutex<Option<Vote>>,
+    pub batches: Mutex<HashMap<HashValue, Batch>>,
+    pub evidence: Mutex<Vec<Evidence>>,
 
     // Liveness state
     pub highest_2chain_timeout_certificate: Mutex<Option<TwoChainTimeoutCertificate>>,
@@ -41,6 +44,8 @@ impl MockSharedStorage {
             qc: Mutex::new(HashMap::new()),
             lis: Mutex::new(HashMap::new()),
             last_vote: Mutex::new(None),
+            batches: Mutex::new(HashMap::new()),
+            evidence: Mutex::new(vec![]),
             highest_2chain_timeout_certificate: Mutex::new(None),
             validator_set,
         }
@@ -234,6 +239,44 @@ impl PersistentLivenessStorage for MockStorage {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         unimplemented!()
     }
+
+    fn save_batches(&self, batches: Vec<Batch>) -> Result<()> {
+        let mut saved = self.shared_storage.batches.lock();
+        for batch in batches {
+            saved.insert(batch.digest(), batch);
+        }
+        Ok(())
+    }
+
+    fn delete_batches(&self, digests: Vec<HashValue>) -> Result<()> {
+        let mut saved = self.shared_storage.batches.lock();
+        for digest in digests {
+            saved.remove(&digest);
+        }
+        Ok(())
+    }
+
+    fn get_batches(&self) -> Result<Vec<Batch>> {
+        Ok(self
+            .shared_storage
+            .batches
+            .lock()
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    fn save_evidence(&self, evidence: &Evidence) -> Result<()> {
+        let mut saved = self.shared_storage.evidence.lock();
+        if !saved.contains(evidence) {
+            saved.push(evidence.clone());
+        }
+        Ok(())
+    }
+
+    fn get_evidence(&self) -> Result<Vec<Evidence>> {
+        Ok(self.shared_storage.evidence.lock().clone())
+    }
 }
 
 /// A storage that ignores any requests, used in the tests that don't care about the storage.
@@ -302,4 +345,24 @@ impl PersistentLivenessStorage for EmptyStorage {
     fn aptos_db(&self) -> Arc<dyn DbReader> {
         unimplemented!()
     }
+
+    fn save_batches(&self, _: Vec<Batch>) -> Result<()> {
+        Ok(())
+    }
+
+    fn delete_batches(&self, _: Vec<HashValue>) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_batches(&self) -> Result<Vec<Batch>> {
+        Ok(vec![])
+    }
+
+    fn save_evidence(&self, _: &Evidence) -> Result<()> {
+        Ok(())
+    }
+
+    fn get_evidence(&self) -> Result<Vec<Evidence>> {
+        Ok(vec![])
+    }
 }
@@ -10,7 +10,10 @@ use crate::{
 use aptos_types::on_chain_config::ProposerElectionType::{
     FixedProposer, RotatingProposer, RoundProposer,
 };
-use consensus_types::{block::Block, common::Round};
+use consensus_types::{
+    block::{block_test_utils::random_txns, Block},
+    common::Round,
+};
 use futures::StreamExt;
 use std::collections::HashMap;
 
@@ -312,3 +315,46 @@ fn twins_commit_test() {
         }
     });
 }
+
+#[test]
+/// This test checks that the transactions of a node's mempool are committed
+/// by every node when the quorum store disseminates them.
+///
+/// Setup:
+///
+/// 4 honest nodes (n0, n1, n2, n3), and 0 twins, with the quorum store enabled.
+///
+/// Test:
+///
+/// Add transactions to the mempool of n0. Check that n3 commits them, which
+/// requires the batch of n0 to be certified and its proof to be proposed.
+///
+/// Run the test:
+/// cargo xtest -p consensus quorum_store_commit_test -- --nocapture
+fn quorum_store_commit_test() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let num_nodes = 4;
+    let num_twins = 0;
+    let mut nodes = SMRNode::start_num_nodes_with_twins_and_quorum_store(
+        num_nodes,
+        num_twins,
+        &mut playground,
+        RotatingProposer(2),
+        None,
+        true,
+    );
+    let txns = random_txns(10);
+    nodes[0].shared_mempool.add_txns(txns.clone()).unwrap();
+    runtime.spawn(playground.start());
+
+    timed_block_on(&runtime, async {
+        loop {
+            let committed_txns = nodes[3].state_sync.next().await.unwrap();
+            if !committed_txns.is_empty() {
+                assert_eq!(committed_txns, txns);
+                break;
+            }
+        }
+    });
+}
@@ -51,9 +51,9 @@ pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    pub shared_mempool: MockSharedMempool,
+    pub state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
     _runtime: Runtime,
-    _shared_mempool: MockSharedMempool,
-    _state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
 }
 
 fn author_from_config(config: &NodeConfig) -> Author {
@@ -85,7 +85,7 @@ impl SMRNode {
         let (state_sync_client, state_sync) = mpsc::unbounded();
         let (ordered_blocks_tx, mut ordered_blocks_events) = mpsc::unbounded::<OrderedBlocks>();
         let shared_mempool = MockSharedMempool::new();
-        let (quorum_store_to_mempool_sender, _) = mpsc::channel(1_024);
+        let quorum_store_to_mempool_sender = shared_mempool.consensus_to_mempool_sender.clone();
         let state_computer = Arc::new(MockStateComputer::new(
             state_sync_client,
             ordered_blocks_tx,
@@ -146,6 +146,7 @@ impl SMRNode {
             storage.clone(),
             reconfig_listener,
             commit_notifier,
+            None,
         );
         let (network_task, network_receiver) = NetworkTask::new(network_events, self_receiver);
 
@@ -171,8 +172,8 @@ impl SMRNode {
             _runtime: runtime,
             commit_cb_receiver,
             storage,
-            _shared_mempool: shared_mempool,
-            _state_sync: state_sync,
+            shared_mempool,
+            state_sync,
         }
     }
 
@@ -183,6 +184,25 @@ impl SMRNode {
         playground: &mut NetworkPlayground,
         proposer_type: ProposerElectionType,
         round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> Vec<Self> {
+        Self::start_num_nodes_with_twins_and_quorum_store(
+            num_nodes,
+            num_twins,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+            false,
+        )
+    }
+
+    /// Starts a given number of nodes and their twins, with or without the quorum store
+    pub fn start_num_nodes_with_twins_and_quorum_store(
+        num_nodes: usize,
+        num_twins: usize,
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+        use_quorum_store: bool,
     ) -> Vec<Self> {
         assert!(num_nodes >= num_twins);
         let ValidatorSwarm {
@@ -256,6 +276,7 @@ impl SMRNode {
             config.base.waypoint = WaypointConfig::FromConfig(waypoint);
             // Disable timeout in twins test to avoid flakiness
             config.consensus.round_initial_timeout_ms = 2_000_000;
+            config.consensus.use_quorum_store = use_quorum_store;
 
             let author = author_from_config(&config);
 
@@ -23,7 +23,7 @@ use crate::error::RestError;
 use anyhow::{anyhow, Result};
 use aptos_api_types::{
     deserialize_from_string,
-    mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE},
+    mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, BCS_VIEW_FUNCTION},
     AptosError, BcsBlock, Block, Bytecode, ExplainVMStatus, GasEstimation, HexEncodedBytes,
     IndexResponse, MoveModuleId, TransactionData, TransactionOnChainData,
     TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent,
@@ -34,11 +34,14 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
-    transaction::SignedTransaction,
+    transaction::{EntryFunction, SignedTransaction},
 };
 use futures::executor::block_on;
 use move_binary_format::CompiledModule;
-use move_core_types::language_storage::{ModuleId, StructTag};
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use reqwest::header::ACCEPT;
 use reqwest::{header::CONTENT_TYPE, Client as ReqwestClient, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
@@ -278,6 +281,28 @@ impl Client {
         })
     }
 
+    /// Retrieves the balance of any coin type by calling the `0x1::coin::balance` view function
+    pub async fn view_coin_balance(
+        &self,
+        address: AccountAddress,
+        coin_type: TypeTag,
+        version: Option<u64>,
+    ) -> AptosResult<Response<u64>> {
+        let function = EntryFunction::new(
+            ModuleId::new(CORE_CODE_ADDRESS, ident_str!("coin").to_owned()),
+            ident_str!("balance").to_owned(),
+            vec![coin_type],
+            vec![bcs::to_bytes(&address)?],
+        );
+        let response = self.view_bcs(&function, version).await?;
+        response.and_then(|values| {
+            let value = values
+                .first()
+                .ok_or_else(|| anyhow!("No balance returned from view function"))?;
+            Ok(bcs::from_bytes(value)?)
+        })
+    }
+
     pub async fn get_index(&self) -> AptosResult<Response<IndexResponse>> {
         self.get(self.build_path("")?).await
     }
@@ -326,6 +351,26 @@ impl Client {
         self.json(response).await
     }
 
+    /// Simulates the transaction, including a breakdown of where its gas was spent in the
+    /// returned transaction's `gas_profile`
+    pub async fn simulate_with_gas_profile(
+        &self,
+        txn: &SignedTransaction,
+    ) -> AptosResult<Response<Vec<UserTransaction>>> {
+        let txn_payload = bcs::to_bytes(txn)?;
+        let url = self.build_path("transactions/simulate?profile_gas=true")?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     pub async fn simulate_bcs(
         &self,
         txn: &SignedTransaction,
@@ -371,6 +416,55 @@ impl Client {
         Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
     }
 
+    /// Executes a view function, returning its return values as JSON
+    pub async fn view(
+        &self,
+        function: &EntryFunction,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Value>>> {
+        let request = bcs::to_bytes(function)?;
+        let url = self.build_path(&Self::view_path(version))?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_VIEW_FUNCTION)
+            .body(request)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
+    /// Executes a view function, returning its BCS encoded return values
+    pub async fn view_bcs(
+        &self,
+        function: &EntryFunction,
+        version: Option<u64>,
+    ) -> AptosResult<Response<Vec<Vec<u8>>>> {
+        let request = bcs::to_bytes(function)?;
+        let url = self.build_path(&Self::view_path(version))?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_VIEW_FUNCTION)
+            .header(ACCEPT, BCS)
+            .body(request)
+            .send()
+            .await?;
+
+        let response = self.check_and_parse_bcs_response(response).await?;
+        Ok(response.and_then(|bytes| bcs::from_bytes(&bytes))?)
+    }
+
+    fn view_path(version: Option<u64>) -> String {
+        match version {
+            Some(version) => format!("view?ledger_version={}", version),
+            None => "view".to_string(),
+        }
+    }
+
     pub async fn submit(
         &self,
         txn: &SignedTransaction,
@@ -0,0 +1,85 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{
+    CliCommand, CliConfig, CliError, CliTypedResult, ConfigSearchMode, ProfileOptions, RestOptions,
+};
+use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+use std::convert::TryFrom;
+
+/// Show the balance of a coin owned by an address
+///
+/// This works with any coin type registered in the account's `0x1::coin::CoinStore<T>`,
+/// defaulting to APT.
+#[derive(Debug, Parser)]
+pub struct Balance {
+    /// Address of the account you want to get the balance of
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Coin type to get the balance of
+    #[clap(long, default_value = "0x1::aptos_coin::AptosCoin")]
+    pub(crate) coin_type: MoveType,
+
+    /// Ledger version to get the balance at
+    ///
+    /// If not provided, the latest ledger version is used
+    #[clap(long)]
+    pub(crate) ledger_version: Option<u64>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// The balance of a single coin type
+#[derive(Clone, Debug, Serialize)]
+pub struct AccountBalance {
+    pub account: AccountAddress,
+    pub coin_type: String,
+    pub balance: u64,
+}
+
+#[async_trait]
+impl CliCommand<AccountBalance> for Balance {
+    fn command_name(&self) -> &'static str {
+        "Balance"
+    }
+
+    async fn execute(self) -> CliTypedResult<AccountBalance> {
+        let account = if let Some(account) = self.account {
+            account
+        } else if let Some(Some(account)) = CliConfig::load_profile(
+            self.profile_options.profile_name(),
+            ConfigSearchMode::CurrentDirAndParents,
+        )?
+        .map(|p| p.account)
+        {
+            account
+        } else {
+            return Err(CliError::CommandArgumentError(
+                "Please provide an account using --account or run aptos init".to_string(),
+            ));
+        };
+
+        let coin_type = TypeTag::try_from(self.coin_type)
+            .map_err(|err| CliError::UnableToParse("--coin-type", err.to_string()))?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        let balance = client
+            .view_coin_balance(account, coin_type.clone(), self.ledger_version)
+            .await?
+            .into_inner();
+
+        Ok(AccountBalance {
+            account,
+            coin_type: coin_type.to_string(),
+            balance,
+        })
+    }
+}
@@ -4,6 +4,7 @@
 use crate::common::types::{CliCommand, CliResult};
 use clap::Subcommand;
 
+pub mod balance;
 pub mod create;
 pub mod create_resource_account;
 pub mod fund;
@@ -17,6 +18,7 @@ pub mod transfer;
 /// account's resources, and transfer resources between accounts.
 #[derive(Debug, Subcommand)]
 pub enum AccountTool {
+    Balance(balance::Balance),
     Create(create::CreateAccount),
     CreateResourceAccount(create_resource_account::CreateResourceAccount),
     FundWithFaucet(fund::FundWithFaucet),
@@ -29,6 +31,7 @@ pub enum AccountTool {
 impl AccountTool {
     pub async fn execute(self) -> CliResult {
         match self {
+            AccountTool::Balance(tool) => tool.execute_serialized().await,
             AccountTool::Create(tool) => tool.execute_serialized().await,
             AccountTool::CreateResourceAccount(tool) => tool.execute_serialized().await,
             AccountTool::FundWithFaucet(tool) => tool.execute_serialized().await,
@@ -1388,6 +1388,28 @@ impl TransactionOptions {
         payload: TransactionPayload,
         gas_price: Option<u64>,
         amount_transfer: Option<u64>,
+    ) -> CliTypedResult<UserTransaction> {
+        self.simulate_transaction_impl(payload, gas_price, amount_transfer, false)
+            .await
+    }
+
+    /// Simulates the transaction instead of submitting it, returning it along with a breakdown
+    /// of where its gas was spent
+    pub async fn profile_gas(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<UserTransaction> {
+        let gas_price = self.gas_options.gas_unit_price;
+        self.simulate_transaction_impl(payload, gas_price, None, true)
+            .await
+    }
+
+    async fn simulate_transaction_impl(
+        &self,
+        payload: TransactionPayload,
+        gas_price: Option<u64>,
+        amount_transfer: Option<u64>,
+        profile_gas: bool,
     ) -> CliTypedResult<UserTransaction> {
         let client = self.rest_client()?;
         let (sender_key, sender_address) = self.get_key_and_address()?;
@@ -1442,7 +1464,14 @@ impl TransactionOptions {
             sender_key.public_key(),
             Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
         );
-        let txns = client.simulate(&signed_transaction).await?.into_inner();
+        let txns = if profile_gas {
+            client
+                .simulate_with_gas_profile(&signed_transaction)
+                .await?
+        } else {
+            client.simulate(&signed_transaction).await?
+        }
+        .into_inner();
         Ok(txns.first().unwrap().clone())
     }
 
@@ -29,9 +29,10 @@ use crate::{
     },
     CliCommand, CliResult,
 };
+use aptos_debugger::{AptosDebugger, ReplayOverrides, ReplayReport};
 use aptos_gas::{AbstractValueSizeGasParameters, NativeGasParameters};
 use aptos_module_verifier::module_init::verify_module_init_function;
-use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_rest_client::aptos_api_types::{MoveType, Transaction};
 use aptos_transactional_test_harness::run_aptos_test;
 use aptos_types::account_address::{create_resource_address, AccountAddress};
 use aptos_types::transaction::{EntryFunction, Script, TransactionArgument, TransactionPayload};
@@ -86,6 +87,8 @@ pub enum MoveTool {
     Document(DocumentPackage),
     TransactionalTest(TransactionalTestOpts),
     CreateResourceAccountAndPublishPackage(CreateResourceAccountAndPublishPackage),
+    View(ViewFunction),
+    Replay(ReplayTransaction),
 }
 
 impl MoveTool {
@@ -107,6 +110,8 @@ impl MoveTool {
             MoveTool::CreateResourceAccountAndPublishPackage(tool) => {
                 tool.execute_serialized_success().await
             }
+            MoveTool::View(tool) => tool.execute_serialized().await,
+            MoveTool::Replay(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -946,6 +951,13 @@ pub struct RunFunction {
     #[clap(long, multiple_values = true)]
     pub(crate) type_args: Vec<MoveType>,
 
+    /// Simulate the transaction instead of submitting it, and write a breakdown of its gas
+    /// usage in the folded stacks format to `gas-profile-<MODULE>-<FUNCTION>.folded`
+    ///
+    /// The output can be rendered as a flamegraph with tools like `inferno-flamegraph`.
+    #[clap(long)]
+    pub(crate) profile_gas: bool,
+
     #[clap(flatten)]
     pub(crate) txn_options: TransactionOptions,
 }
@@ -971,15 +983,173 @@ impl CliCommand<TransactionSummary> for RunFunction {
             type_args.push(type_tag)
         }
 
-        self.txn_options
-            .submit_transaction(TransactionPayload::EntryFunction(EntryFunction::new(
-                self.function_id.module_id,
-                self.function_id.member_id,
-                type_args,
-                args,
-            )))
-            .await
-            .map(TransactionSummary::from)
+        let profile_name = format!(
+            "gas-profile-{}-{}.folded",
+            self.function_id.module_id.name(),
+            self.function_id.member_id
+        );
+        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            self.function_id.module_id,
+            self.function_id.member_id,
+            type_args,
+            args,
+        ));
+
+        if !self.profile_gas {
+            return self
+                .txn_options
+                .submit_transaction(payload)
+                .await
+                .map(TransactionSummary::from);
+        }
+
+        let txn = self.txn_options.profile_gas(payload).await?;
+        let folded_stacks = txn
+            .gas_profile
+            .as_ref()
+            .map(|profile| profile.folded_stacks.join("\n"))
+            .ok_or_else(|| {
+                CliError::UnexpectedError(format!(
+                    "No gas profile returned, the transaction was discarded: {}",
+                    txn.info.vm_status
+                ))
+            })?;
+        let profile_path = PathBuf::from(&profile_name);
+        write_to_file(&profile_path, &profile_name, folded_stacks.as_bytes())?;
+        eprintln!("Gas profile written to {}", profile_path.display());
+
+        Ok(TransactionSummary::from(Transaction::UserTransaction(
+            Box::new(txn),
+        )))
+    }
+}
+
+/// Run a view function
+///
+/// This executes a public Move function against on-chain state without submitting a
+/// transaction, and prints its return values.
+#[derive(Parser)]
+pub struct ViewFunction {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    ///
+    /// Example: `0x1::coin::balance`
+    #[clap(long)]
+    pub(crate) function_id: MemberId,
+
+    /// Arguments combined with their type separated by spaces.
+    ///
+    /// Supported types [u8, u64, u128, bool, hex, string, address, raw]
+    ///
+    /// Example: `address:0x1 bool:true u8:0`
+    #[clap(long, multiple_values = true)]
+    pub(crate) args: Vec<ArgWithType>,
+
+    /// TypeTag arguments separated by spaces.
+    ///
+    /// Example: `u8 u64 u128 bool address vector signer`
+    #[clap(long, multiple_values = true)]
+    pub(crate) type_args: Vec<MoveType>,
+
+    /// Ledger version to run the view function against
+    ///
+    /// If not provided, the latest ledger version is used
+    #[clap(long)]
+    pub(crate) ledger_version: Option<u64>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<serde_json::Value>> for ViewFunction {
+    fn command_name(&self) -> &'static str {
+        "ViewFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<serde_json::Value>> {
+        let args: Vec<Vec<u8>> = self
+            .args
+            .into_iter()
+            .map(|arg_with_type| arg_with_type.arg)
+            .collect();
+        let mut type_args: Vec<TypeTag> = Vec::new();
+
+        // These TypeArgs are used for generics
+        for type_arg in self.type_args.into_iter() {
+            let type_tag = TypeTag::try_from(type_arg)
+                .map_err(|err| CliError::UnableToParse("--type-args", err.to_string()))?;
+            type_args.push(type_tag)
+        }
+
+        let function = EntryFunction::new(
+            self.function_id.module_id,
+            self.function_id.member_id,
+            type_args,
+            args,
+        );
+        let client = self.rest_options.client(&self.profile_options)?;
+        Ok(client
+            .view(&function, self.ledger_version)
+            .await?
+            .into_inner())
+    }
+}
+
+/// Replay a committed transaction
+///
+/// This re-executes a committed transaction against the state right before it, optionally
+/// with a locally built framework and/or a different gas schedule, and reports how the
+/// resulting write set, events, gas and status differ from what was committed.
+#[derive(Parser)]
+pub struct ReplayTransaction {
+    /// Ledger version of the transaction to replay
+    #[clap(long)]
+    pub(crate) txn_version: u64,
+
+    /// Path to a local AptosDB to replay from
+    ///
+    /// If not provided, the REST endpoint is used instead
+    #[clap(long, parse(from_os_str))]
+    pub(crate) db: Option<PathBuf>,
+
+    /// Path to a Move package whose modules replace the on-chain code during the replay
+    ///
+    /// Example: `aptos-move/framework/aptos-framework`
+    #[clap(long, parse(from_os_str))]
+    pub(crate) framework_path: Option<PathBuf>,
+
+    /// Path to a JSON encoded gas schedule that replaces the on-chain gas schedule during
+    /// the replay
+    #[clap(long, parse(from_os_str))]
+    pub(crate) gas_schedule: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<ReplayReport> for ReplayTransaction {
+    fn command_name(&self) -> &'static str {
+        "ReplayTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplayReport> {
+        let debugger = if let Some(db) = self.db {
+            AptosDebugger::db(db)?
+        } else {
+            AptosDebugger::rest_client(self.rest_options.client(&self.profile_options)?)?
+        };
+
+        let overrides =
+            ReplayOverrides::from_files(self.framework_path, self.gas_schedule.as_deref())?;
+
+        Ok(debugger
+            .replay_transaction_with_overrides(self.txn_version, &overrides)
+            .await?)
     }
 }
 
@@ -0,0 +1,298 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{
+    CliCommand, CliError, CliResult, CliTypedResult, TransactionOptions, TransactionSummary,
+};
+use crate::move_tool::{ArgWithType, MemberId};
+use aptos_rest_client::aptos_api_types::MoveType;
+use aptos_types::account_address::{create_resource_address, AccountAddress};
+use aptos_types::transaction::{
+    EntryFunction, Multisig, MultisigTransactionPayload, TransactionPayload,
+};
+use async_trait::async_trait;
+use cached_packages::aptos_stdlib;
+use clap::Parser;
+use move_core_types::language_storage::TypeTag;
+use serde::Serialize;
+
+/// Tool for interacting with multisig accounts
+///
+/// Multisig accounts are on-chain accounts controlled by a set of owners. Owners propose
+/// transactions for the account, approve or reject them, and once enough owners have approved,
+/// any owner can execute the transaction on behalf of the multisig account.
+#[derive(Parser)]
+pub enum MultisigTool {
+    Approve(ApproveTransaction),
+    Create(CreateMultisig),
+    Execute(ExecuteTransaction),
+    Propose(ProposeTransaction),
+    Reject(RejectTransaction),
+    RemoveRejected(RemoveRejectedTransaction),
+}
+
+impl MultisigTool {
+    pub async fn execute(self) -> CliResult {
+        use MultisigTool::*;
+        match self {
+            Approve(tool) => tool.execute_serialized().await,
+            Create(tool) => tool.execute_serialized().await,
+            Execute(tool) => tool.execute_serialized().await,
+            Propose(tool) => tool.execute_serialized().await,
+            Reject(tool) => tool.execute_serialized().await,
+            RemoveRejected(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Create a new multisig account
+///
+/// The sender becomes one of the owners of the multisig account. The account is a resource
+/// account derived from the sender and the seed, so nobody holds a private key for it.
+#[derive(Parser)]
+pub struct CreateMultisig {
+    /// Space separated list of owners besides the sender
+    #[clap(long, multiple_values = true, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) additional_owners: Vec<AccountAddress>,
+
+    /// Number of owner approvals required to execute a transaction
+    #[clap(long)]
+    pub(crate) num_signatures_required: u64,
+
+    /// Seed used in generation of the multisig account address
+    ///
+    /// The seed will be converted to bytes using `BCS`
+    #[clap(long)]
+    pub(crate) seed: String,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+/// A create multisig account output
+#[derive(Clone, Debug, Serialize)]
+pub struct CreateMultisigSummary {
+    pub multisig_account: AccountAddress,
+    #[serde(flatten)]
+    pub transaction_summary: TransactionSummary,
+}
+
+#[async_trait]
+impl CliCommand<CreateMultisigSummary> for CreateMultisig {
+    fn command_name(&self) -> &'static str {
+        "CreateMultisig"
+    }
+
+    async fn execute(self) -> CliTypedResult<CreateMultisigSummary> {
+        let seed = bcs::to_bytes(&self.seed)?;
+        let multisig_account = create_resource_address(self.txn_options.sender_address()?, &seed);
+        self.txn_options
+            .submit_transaction(aptos_stdlib::multisig_account_create(
+                self.additional_owners,
+                self.num_signatures_required,
+                seed,
+            ))
+            .await
+            .map(|transaction| CreateMultisigSummary {
+                multisig_account,
+                transaction_summary: TransactionSummary::from(&transaction),
+            })
+    }
+}
+
+/// Propose a new transaction for a multisig account
+///
+/// The entry function is stored on-chain and executed once enough owners approve it. Proposing
+/// a transaction counts as an approval from the sender.
+#[derive(Parser)]
+pub struct ProposeTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) multisig_address: AccountAddress,
+
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    ///
+    /// Example: `0x1::aptos_account::transfer`
+    #[clap(long)]
+    pub(crate) function_id: MemberId,
+
+    /// Arguments combined with their type separated by spaces.
+    ///
+    /// Supported types [u8, u64, u128, bool, hex, string, address, raw]
+    ///
+    /// Example: `address:0x1 bool:true u8:0`
+    #[clap(long, multiple_values = true)]
+    pub(crate) args: Vec<ArgWithType>,
+
+    /// TypeTag arguments separated by spaces.
+    ///
+    /// Example: `u8 u64 u128 bool address vector signer`
+    #[clap(long, multiple_values = true)]
+    pub(crate) type_args: Vec<MoveType>,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ProposeTransaction {
+    fn command_name(&self) -> &'static str {
+        "ProposeMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let args: Vec<Vec<u8>> = self
+            .args
+            .into_iter()
+            .map(|arg_with_type| arg_with_type.arg)
+            .collect();
+        let mut type_args: Vec<TypeTag> = Vec::new();
+
+        // These TypeArgs are used for generics
+        for type_arg in self.type_args.into_iter() {
+            let type_tag = TypeTag::try_from(type_arg)
+                .map_err(|err| CliError::UnableToParse("--type-args", err.to_string()))?;
+            type_args.push(type_tag)
+        }
+
+        let payload = MultisigTransactionPayload::EntryFunction(EntryFunction::new(
+            self.function_id.module_id,
+            self.function_id.member_id,
+            type_args,
+            args,
+        ));
+        self.txn_options
+            .submit_transaction(aptos_stdlib::multisig_account_create_transaction(
+                self.multisig_address,
+                bcs::to_bytes(&payload)?,
+            ))
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Approve a pending transaction of a multisig account
+#[derive(Parser)]
+pub struct ApproveTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) multisig_address: AccountAddress,
+
+    /// Sequence number of the multisig transaction to approve
+    #[clap(long)]
+    pub(crate) sequence_number: u64,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ApproveTransaction {
+    fn command_name(&self) -> &'static str {
+        "ApproveMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(aptos_stdlib::multisig_account_approve_transaction(
+                self.multisig_address,
+                self.sequence_number,
+            ))
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Reject a pending transaction of a multisig account
+#[derive(Parser)]
+pub struct RejectTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) multisig_address: AccountAddress,
+
+    /// Sequence number of the multisig transaction to reject
+    #[clap(long)]
+    pub(crate) sequence_number: u64,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for RejectTransaction {
+    fn command_name(&self) -> &'static str {
+        "RejectMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(aptos_stdlib::multisig_account_reject_transaction(
+                self.multisig_address,
+                self.sequence_number,
+            ))
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Remove the next transaction of a multisig account without executing it
+///
+/// The transaction must have been rejected by enough owners. The transaction after it becomes
+/// the next one to execute.
+#[derive(Parser)]
+pub struct RemoveRejectedTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) multisig_address: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for RemoveRejectedTransaction {
+    fn command_name(&self) -> &'static str {
+        "RemoveRejectedMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(aptos_stdlib::multisig_account_remove_rejected_transaction(
+                self.multisig_address,
+            ))
+            .await
+            .map(TransactionSummary::from)
+    }
+}
+
+/// Execute the next approved transaction of a multisig account
+///
+/// Multisig transactions are executed in order. The sender must be an owner of the multisig
+/// account, and the transaction must have enough approvals.
+#[derive(Parser)]
+pub struct ExecuteTransaction {
+    /// Address of the multisig account
+    #[clap(long, parse(try_from_str=crate::common::types::load_account_arg))]
+    pub(crate) multisig_address: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ExecuteTransaction {
+    fn command_name(&self) -> &'static str {
+        "ExecuteMultisigTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        self.txn_options
+            .submit_transaction(TransactionPayload::Multisig(Multisig {
+                multisig_address: self.multisig_address,
+                // The payload stored on-chain when the transaction was proposed is executed
+                transaction_payload: None,
+            }))
+            .await
+            .map(TransactionSummary::from)
+    }
+}
@@ -23,6 +23,7 @@ use crate::{
 use aptos_config::config::NodeConfig;
 use aptos_crypto::bls12381::PublicKey;
 use aptos_crypto::{bls12381, x25519, ValidCryptoMaterialStringExt};
+use aptos_debugger::{start_forked_node, AptosDebugger};
 use aptos_faucet::FaucetArgs;
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_rest_client::aptos_api_types::VersionedEvent;
@@ -41,6 +42,8 @@ use async_trait::async_trait;
 use backup_cli::coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt};
 use backup_cli::metadata::cache::MetadataCacheOpt;
 use backup_cli::storage::command_adapter::{config::CommandAdapterConfig, CommandAdapter};
+use backup_cli::storage::s3::{config::S3Config, S3};
+use backup_cli::storage::BackupStorage;
 use backup_cli::utils::{
     ConcurrentDownloadsOpt, GlobalRestoreOpt, ReplayConcurrencyLevelOpt, RocksdbOpt,
 };
@@ -1025,7 +1028,8 @@ const TESTNET_FOLDER: &str = "testnet";
 /// Run local testnet
 ///
 /// This local testnet will run it's own Genesis and run as a single node
-/// network locally.  Optionally, a faucet can be added for minting APT coins.
+/// network locally.  Optionally, a faucet can be added for minting APT coins.  Alternatively,
+/// the node can run on top of a fork of a remote chain.
 #[derive(Parser)]
 pub struct RunLocalTestnet {
     /// An overridable config template for the test node
@@ -1072,10 +1076,70 @@ pub struct RunLocalTestnet {
     #[clap(long)]
     do_not_delegate: bool,
 
+    /// Run an indexer alongside the node
+    ///
+    /// The indexer stores its data in a SQLite database in the test directory.  Requires the CLI
+    /// to be built with the `indexer` feature
+    #[clap(long)]
+    with_indexer: bool,
+
+    /// Run the node on top of a fork of the chain served by the REST API at this URL
+    ///
+    /// Instead of running its own Genesis, the node reads the state of the remote chain as it
+    /// goes, and executes submitted transactions on top of it right away without affecting the
+    /// remote.  Can't be combined with a faucet or an indexer
+    #[clap(long)]
+    fork_url: Option<Url>,
+
+    /// The version of the remote chain to fork at
+    ///
+    /// Defaults to the latest version of the remote chain
+    #[clap(long)]
+    fork_version: Option<u64>,
+
     #[clap(flatten)]
     prompt_options: PromptOptions,
 }
 
+impl RunLocalTestnet {
+    async fn run_fork(self, fork_url: Url) -> CliTypedResult<()> {
+        if self.with_faucet || self.with_indexer {
+            return Err(CliError::CommandArgumentError(
+                "--fork-url can't be combined with --with-faucet or --with-indexer".to_string(),
+            ));
+        }
+
+        let rest_client = Client::new(fork_url);
+        let fork_version = match self.fork_version {
+            Some(version) => version,
+            None => {
+                rest_client
+                    .get_ledger_information()
+                    .await?
+                    .into_inner()
+                    .version
+            }
+        };
+        let fork = AptosDebugger::rest_client(rest_client)?.fork_at_version(fork_version);
+
+        let mut config = match &self.config_path {
+            Some(config_path) => NodeConfig::load(config_path)?,
+            None => NodeConfig::default(),
+        };
+        config.api.address = format!("0.0.0.0:{}", config.api.address.port())
+            .parse()
+            .map_err(|err| CliError::UnexpectedError(format!("Invalid API address {}", err)))?;
+        let _node = start_forked_node(&config, Arc::new(fork)).await?;
+        eprintln!(
+            "Forked the remote chain at version {}\n\tREST API endpoint: http://{}",
+            fork_version, config.api.address
+        );
+
+        // The node runs until the process is stopped.
+        futures::future::pending().await
+    }
+}
+
 #[async_trait]
 impl CliCommand<()> for RunLocalTestnet {
     fn command_name(&self) -> &'static str {
@@ -1083,6 +1147,14 @@ impl CliCommand<()> for RunLocalTestnet {
     }
 
     async fn execute(mut self) -> CliTypedResult<()> {
+        if let Some(fork_url) = self.fork_url.take() {
+            return self.run_fork(fork_url).await;
+        } else if self.fork_version.is_some() {
+            return Err(CliError::CommandArgumentError(
+                "--fork-version requires --fork-url".to_string(),
+            ));
+        }
+
         let rng = self
             .seed
             .map(StdRng::from_seed)
@@ -1107,12 +1179,14 @@ impl CliCommand<()> for RunLocalTestnet {
         // Spawn the node in a separate thread
         let config_path = self.config_path.clone();
         let test_dir_copy = test_dir.clone();
+        let with_indexer = self.with_indexer;
         let node_thread_handle = thread::spawn(move || {
             let result = aptos_node::load_test_environment(
                 config_path,
                 Some(test_dir_copy),
                 false,
                 false,
+                with_indexer,
                 cached_packages::head_release_bundle(),
                 rng,
             );
@@ -1466,10 +1540,18 @@ pub struct BootstrapDbFromBackup {
     /// Config file for the source backup
     ///
     /// This file configures if we should use local files or cloud storage, and how to access
-    /// the backup.
+    /// the backup. Its format depends on `--storage-type`.
     #[clap(long, parse(from_os_str))]
     config_path: PathBuf,
 
+    /// Type of the backup storage: [command-adapter, s3]
+    ///
+    /// `command-adapter` reads shell commands from the config file to access the backup, `s3`
+    /// talks to S3 or a compatible store directly, with credentials from the `AWS_ACCESS_KEY_ID`
+    /// and `AWS_SECRET_ACCESS_KEY` env vars.
+    #[clap(arg_enum, long, default_value = "command-adapter")]
+    storage_type: BackupStorageType,
+
     /// Target database directory
     ///
     /// The directory to create the AptosDB with snapshots and transactions from the backup.
@@ -1487,6 +1569,14 @@ pub struct BootstrapDbFromBackup {
     pub replay_concurrency_level: ReplayConcurrencyLevelOpt,
 }
 
+#[derive(PartialEq, Eq, clap::ArgEnum, Clone, Copy)]
+pub enum BackupStorageType {
+    /// Access the backup with the shell commands in the config file
+    CommandAdapter,
+    /// Access the backup in S3 or a store compatible with its API
+    S3,
+}
+
 #[async_trait]
 impl CliCommand<()> for BootstrapDbFromBackup {
     fn command_name(&self) -> &'static str {
@@ -1510,9 +1600,14 @@ impl CliCommand<()> for BootstrapDbFromBackup {
             replay_concurrency_level: self.replay_concurrency_level,
         }
         .try_into()?;
-        let storage = Arc::new(CommandAdapter::new(
-            CommandAdapterConfig::load_from_file(&self.config_path).await?,
-        ));
+        let storage: Arc<dyn BackupStorage> = match self.storage_type {
+            BackupStorageType::CommandAdapter => Arc::new(CommandAdapter::new(
+                CommandAdapterConfig::load_from_file(&self.config_path).await?,
+            )),
+            BackupStorageType::S3 => {
+                Arc::new(S3::new(S3Config::load_from_file(&self.config_path).await?)?)
+            }
+        };
 
         // hack: get around this error, related to use of `async_trait`:
         //   error: higher-ranked lifetime error
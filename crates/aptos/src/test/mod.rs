@@ -3,6 +3,7 @@
 
 use crate::account::key_rotation::LookupAddress;
 use crate::account::{
+    balance::{AccountBalance, Balance},
     create::{CreateAccount, DEFAULT_FUNDED_COINS},
     fund::FundWithFaucet,
     key_rotation::{RotateKey, RotateSummary},
@@ -23,7 +24,7 @@ use crate::governance::CompileScriptFunction;
 use crate::move_tool::{
     ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
     IncludedArtifactsArgs, InitPackage, MemberId, PublishPackage, RunFunction, RunScript,
-    TestPackage,
+    TestPackage, ViewFunction,
 };
 use crate::node::{
     AnalyzeMode, AnalyzeValidatorPerformance, GetStakePool, InitializeValidator, JoinValidatorSet,
@@ -264,6 +265,24 @@ impl CliTestFramework {
         .await
     }
 
+    pub async fn account_balance(
+        &self,
+        index: usize,
+        coin_type: Option<&str>,
+    ) -> CliTypedResult<AccountBalance> {
+        let coin_type = MoveType::from_str(coin_type.unwrap_or("0x1::aptos_coin::AptosCoin"))
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Balance {
+            rest_options: self.rest_options(),
+            profile_options: Default::default(),
+            account: Some(self.account_id(index)),
+            coin_type,
+            ledger_version: None,
+        }
+        .execute()
+        .await
+    }
+
     pub async fn transfer_coins(
         &self,
         sender_index: usize,
@@ -304,6 +323,7 @@ impl CliTestFramework {
                 IdentifierWrapper::from_str("AptosCoin").unwrap(),
                 vec![],
             ))],
+            profile_gas: false,
             txn_options: self.transaction_options(sender_index, gas_options),
         }
         .execute()
@@ -587,6 +607,7 @@ impl CliTestFramework {
                 ArgWithType::bytes(vec![]),
             ],
             type_args: vec![],
+            profile_gas: false,
             txn_options: self.transaction_options(owner_index, None),
         }
         .execute()
@@ -894,6 +915,41 @@ impl CliTestFramework {
             function_id,
             args: parsed_args,
             type_args: parsed_type_args,
+            profile_gas: false,
+        }
+        .execute()
+        .await
+    }
+
+    pub async fn view_function(
+        &self,
+        function_id: MemberId,
+        args: Vec<&str>,
+        type_args: Vec<&str>,
+    ) -> CliTypedResult<Vec<Value>> {
+        let mut parsed_args = Vec::new();
+        for arg in args {
+            parsed_args.push(
+                ArgWithType::from_str(arg)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            )
+        }
+
+        let mut parsed_type_args = Vec::new();
+        for arg in type_args {
+            parsed_type_args.push(
+                MoveType::from_str(arg)
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            )
+        }
+
+        ViewFunction {
+            rest_options: self.rest_options(),
+            profile_options: Default::default(),
+            function_id,
+            args: parsed_args,
+            type_args: parsed_type_args,
+            ledger_version: None,
         }
         .execute()
         .await
@@ -0,0 +1,524 @@
+-- SQLite has no equivalent of the NUMERIC and JSONB types of Postgres, so those columns are
+-- stored as TEXT. NUMERIC values are encoded so that they compare as numbers, see
+-- src/sql_types/sqlite.rs. Keep this schema in sync with the Postgres migrations.
+
+CREATE TABLE block_metadata_transactions (
+  version BIGINT NOT NULL,
+  block_height BIGINT NOT NULL,
+  id VARCHAR NOT NULL,
+  round BIGINT NOT NULL,
+  epoch BIGINT NOT NULL,
+  previous_block_votes_bitvec TEXT NOT NULL,
+  proposer VARCHAR NOT NULL,
+  failed_proposer_indices TEXT NOT NULL,
+  timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (version)
+);
+
+CREATE TABLE coin_activities (
+  transaction_version BIGINT NOT NULL,
+  event_account_address VARCHAR NOT NULL,
+  event_creation_number BIGINT NOT NULL,
+  event_sequence_number BIGINT NOT NULL,
+  owner_address VARCHAR NOT NULL,
+  coin_type VARCHAR NOT NULL,
+  amount TEXT NOT NULL,
+  activity_type VARCHAR NOT NULL,
+  is_gas_fee BOOLEAN NOT NULL,
+  is_transaction_success BOOLEAN NOT NULL,
+  entry_function_id_str VARCHAR,
+  block_height BIGINT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, event_account_address, event_creation_number, event_sequence_number)
+);
+
+CREATE TABLE coin_balances (
+  transaction_version BIGINT NOT NULL,
+  owner_address VARCHAR NOT NULL,
+  coin_type_hash VARCHAR NOT NULL,
+  coin_type VARCHAR NOT NULL,
+  amount TEXT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, owner_address, coin_type_hash)
+);
+
+CREATE TABLE coin_infos (
+  coin_type_hash VARCHAR NOT NULL,
+  coin_type VARCHAR NOT NULL,
+  transaction_version_created BIGINT NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  symbol VARCHAR NOT NULL,
+  decimals INTEGER NOT NULL,
+  transaction_created_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  supply_aggregator_table_handle VARCHAR,
+  supply_aggregator_table_key TEXT,
+  PRIMARY KEY (coin_type_hash)
+);
+
+CREATE TABLE coin_supply (
+  transaction_version BIGINT NOT NULL,
+  coin_type_hash VARCHAR NOT NULL,
+  coin_type VARCHAR NOT NULL,
+  supply TEXT NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  transaction_epoch BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, coin_type_hash)
+);
+
+CREATE TABLE collection_datas (
+  collection_data_id_hash VARCHAR NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  description TEXT NOT NULL,
+  metadata_uri VARCHAR NOT NULL,
+  supply TEXT NOT NULL,
+  maximum TEXT NOT NULL,
+  maximum_mutable BOOLEAN NOT NULL,
+  uri_mutable BOOLEAN NOT NULL,
+  description_mutable BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  table_handle VARCHAR NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (collection_data_id_hash, transaction_version)
+);
+
+CREATE TABLE current_ans_lookup (
+  domain VARCHAR NOT NULL,
+  subdomain VARCHAR NOT NULL,
+  registered_address VARCHAR,
+  expiration_timestamp TIMESTAMP NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  token_name VARCHAR NOT NULL,
+  PRIMARY KEY (domain, subdomain)
+);
+
+CREATE TABLE current_coin_balances (
+  owner_address VARCHAR NOT NULL,
+  coin_type_hash VARCHAR NOT NULL,
+  coin_type VARCHAR NOT NULL,
+  amount TEXT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (owner_address, coin_type_hash)
+);
+
+CREATE TABLE current_collection_datas (
+  collection_data_id_hash VARCHAR NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  description TEXT NOT NULL,
+  metadata_uri VARCHAR NOT NULL,
+  supply TEXT NOT NULL,
+  maximum TEXT NOT NULL,
+  maximum_mutable BOOLEAN NOT NULL,
+  uri_mutable BOOLEAN NOT NULL,
+  description_mutable BOOLEAN NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  table_handle VARCHAR NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (collection_data_id_hash)
+);
+
+CREATE TABLE current_staking_pool_voter (
+  staking_pool_address VARCHAR NOT NULL,
+  voter_address VARCHAR NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (staking_pool_address)
+);
+
+CREATE TABLE current_token_datas (
+  token_data_id_hash VARCHAR NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  maximum TEXT NOT NULL,
+  supply TEXT NOT NULL,
+  largest_property_version TEXT NOT NULL,
+  metadata_uri VARCHAR NOT NULL,
+  payee_address VARCHAR NOT NULL,
+  royalty_points_numerator TEXT NOT NULL,
+  royalty_points_denominator TEXT NOT NULL,
+  maximum_mutable BOOLEAN NOT NULL,
+  uri_mutable BOOLEAN NOT NULL,
+  description_mutable BOOLEAN NOT NULL,
+  properties_mutable BOOLEAN NOT NULL,
+  royalty_mutable BOOLEAN NOT NULL,
+  default_properties TEXT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  collection_data_id_hash VARCHAR NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  description TEXT NOT NULL,
+  PRIMARY KEY (token_data_id_hash)
+);
+
+CREATE TABLE current_token_ownerships (
+  token_data_id_hash VARCHAR NOT NULL,
+  property_version TEXT NOT NULL,
+  owner_address VARCHAR NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  amount TEXT NOT NULL,
+  token_properties TEXT NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  collection_data_id_hash VARCHAR NOT NULL,
+  table_type TEXT NOT NULL,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (token_data_id_hash, property_version, owner_address)
+);
+
+CREATE TABLE current_token_pending_claims (
+  token_data_id_hash VARCHAR NOT NULL,
+  property_version TEXT NOT NULL,
+  from_address VARCHAR NOT NULL,
+  to_address VARCHAR NOT NULL,
+  collection_data_id_hash VARCHAR NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  amount TEXT NOT NULL,
+  table_handle VARCHAR NOT NULL,
+  last_transaction_version BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  last_transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (token_data_id_hash, property_version, from_address, to_address)
+);
+
+CREATE TABLE events (
+  sequence_number BIGINT NOT NULL,
+  creation_number BIGINT NOT NULL,
+  account_address VARCHAR NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  type TEXT NOT NULL,
+  data TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (account_address, creation_number, sequence_number)
+);
+
+CREATE TABLE indexer_status (
+  db VARCHAR NOT NULL,
+  is_indexer_up BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (db)
+);
+
+CREATE TABLE ledger_infos (
+  chain_id BIGINT NOT NULL,
+  PRIMARY KEY (chain_id)
+);
+
+CREATE TABLE move_modules (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  name TEXT NOT NULL,
+  address VARCHAR NOT NULL,
+  bytecode BLOB,
+  friends TEXT,
+  exposed_functions TEXT,
+  structs TEXT,
+  is_deleted BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+
+CREATE TABLE move_resources (
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  name TEXT NOT NULL,
+  address VARCHAR NOT NULL,
+  type TEXT NOT NULL,
+  module TEXT NOT NULL,
+  generic_type_params TEXT,
+  data TEXT,
+  is_deleted BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+
+CREATE TABLE processor_status (
+  processor VARCHAR NOT NULL,
+  last_success_version BIGINT NOT NULL,
+  last_updated TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (processor)
+);
+
+CREATE TABLE processor_statuses (
+  name VARCHAR NOT NULL,
+  version BIGINT NOT NULL,
+  success BOOLEAN NOT NULL,
+  details TEXT,
+  last_updated TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (name, version)
+);
+
+CREATE TABLE proposal_votes (
+  transaction_version BIGINT NOT NULL,
+  proposal_id BIGINT NOT NULL,
+  voter_address VARCHAR NOT NULL,
+  staking_pool_address VARCHAR NOT NULL,
+  num_votes TEXT NOT NULL,
+  should_pass BOOLEAN NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, proposal_id, voter_address)
+);
+
+CREATE TABLE signatures (
+  transaction_version BIGINT NOT NULL,
+  multi_agent_index BIGINT NOT NULL,
+  multi_sig_index BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  signer VARCHAR NOT NULL,
+  is_sender_primary BOOLEAN NOT NULL,
+  type VARCHAR NOT NULL,
+  public_key VARCHAR NOT NULL,
+  signature VARCHAR NOT NULL,
+  threshold BIGINT NOT NULL,
+  public_key_indices TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, multi_agent_index, multi_sig_index, is_sender_primary)
+);
+
+CREATE TABLE table_items (
+  key TEXT NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  write_set_change_index BIGINT NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  table_handle VARCHAR NOT NULL,
+  decoded_key TEXT NOT NULL,
+  decoded_value TEXT,
+  is_deleted BOOLEAN NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, write_set_change_index)
+);
+
+CREATE TABLE table_metadatas (
+  handle VARCHAR NOT NULL,
+  key_type TEXT NOT NULL,
+  value_type TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (handle)
+);
+
+CREATE TABLE token_activities (
+  transaction_version BIGINT NOT NULL,
+  event_account_address VARCHAR NOT NULL,
+  event_creation_number BIGINT NOT NULL,
+  event_sequence_number BIGINT NOT NULL,
+  collection_data_id_hash VARCHAR NOT NULL,
+  token_data_id_hash VARCHAR NOT NULL,
+  property_version TEXT NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  transfer_type VARCHAR NOT NULL,
+  from_address VARCHAR,
+  to_address VARCHAR,
+  token_amount TEXT NOT NULL,
+  coin_type TEXT,
+  coin_amount TEXT,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (transaction_version, event_account_address, event_creation_number, event_sequence_number)
+);
+
+CREATE TABLE token_datas (
+  token_data_id_hash VARCHAR NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  maximum TEXT NOT NULL,
+  supply TEXT NOT NULL,
+  largest_property_version TEXT NOT NULL,
+  metadata_uri VARCHAR NOT NULL,
+  payee_address VARCHAR NOT NULL,
+  royalty_points_numerator TEXT NOT NULL,
+  royalty_points_denominator TEXT NOT NULL,
+  maximum_mutable BOOLEAN NOT NULL,
+  uri_mutable BOOLEAN NOT NULL,
+  description_mutable BOOLEAN NOT NULL,
+  properties_mutable BOOLEAN NOT NULL,
+  royalty_mutable BOOLEAN NOT NULL,
+  default_properties TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  collection_data_id_hash VARCHAR NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  description TEXT NOT NULL,
+  PRIMARY KEY (token_data_id_hash, transaction_version)
+);
+
+CREATE TABLE token_ownerships (
+  token_data_id_hash VARCHAR NOT NULL,
+  property_version TEXT NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  table_handle VARCHAR NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  owner_address VARCHAR,
+  amount TEXT NOT NULL,
+  table_type TEXT,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  collection_data_id_hash VARCHAR NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (token_data_id_hash, property_version, transaction_version, table_handle)
+);
+
+CREATE TABLE tokens (
+  token_data_id_hash VARCHAR NOT NULL,
+  property_version TEXT NOT NULL,
+  transaction_version BIGINT NOT NULL,
+  creator_address VARCHAR NOT NULL,
+  collection_name VARCHAR NOT NULL,
+  name VARCHAR NOT NULL,
+  token_properties TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  collection_data_id_hash VARCHAR NOT NULL,
+  transaction_timestamp TIMESTAMP NOT NULL,
+  PRIMARY KEY (token_data_id_hash, property_version, transaction_version)
+);
+
+CREATE TABLE transactions (
+  version BIGINT NOT NULL,
+  block_height BIGINT NOT NULL,
+  hash VARCHAR NOT NULL,
+  type VARCHAR NOT NULL,
+  payload TEXT,
+  state_change_hash VARCHAR NOT NULL,
+  event_root_hash VARCHAR NOT NULL,
+  state_checkpoint_hash VARCHAR,
+  gas_used TEXT NOT NULL,
+  success BOOLEAN NOT NULL,
+  vm_status TEXT NOT NULL,
+  accumulator_root_hash VARCHAR NOT NULL,
+  num_events BIGINT NOT NULL,
+  num_write_set_changes BIGINT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  epoch BIGINT NOT NULL,
+  PRIMARY KEY (version)
+);
+
+CREATE TABLE user_transactions (
+  version BIGINT NOT NULL,
+  block_height BIGINT NOT NULL,
+  parent_signature_type VARCHAR NOT NULL,
+  sender VARCHAR NOT NULL,
+  sequence_number BIGINT NOT NULL,
+  max_gas_amount TEXT NOT NULL,
+  expiration_timestamp_secs TIMESTAMP NOT NULL,
+  gas_unit_price TEXT NOT NULL,
+  timestamp TIMESTAMP NOT NULL,
+  entry_function_id_str TEXT NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  epoch BIGINT NOT NULL,
+  PRIMARY KEY (version)
+);
+
+CREATE TABLE write_set_changes (
+  transaction_version BIGINT NOT NULL,
+  "index" BIGINT NOT NULL,
+  hash VARCHAR NOT NULL,
+  transaction_block_height BIGINT NOT NULL,
+  type TEXT NOT NULL,
+  address VARCHAR NOT NULL,
+  inserted_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (transaction_version, "index")
+);
+
+CREATE INDEX txn_insat_index ON transactions (inserted_at);
+CREATE INDEX bmt_insat_index ON block_metadata_transactions (inserted_at);
+CREATE INDEX ut_sender_seq_index ON user_transactions (sender, sequence_number);
+CREATE INDEX ut_insat_index ON user_transactions (inserted_at);
+CREATE INDEX sig_insat_index ON signatures (inserted_at);
+CREATE INDEX ev_addr_type_index ON events (account_address);
+CREATE INDEX ev_insat_index ON events (inserted_at);
+CREATE INDEX wsc_addr_type_ver_index ON write_set_changes (address, transaction_version DESC);
+CREATE INDEX wsc_insat_index ON write_set_changes (inserted_at);
+CREATE INDEX mm_addr_name_ver_index ON move_modules (address, name, transaction_version);
+CREATE INDEX mm_insat_index ON move_modules (inserted_at);
+CREATE INDEX mr_addr_mod_name_ver_index ON move_resources (address, module, name, transaction_version);
+CREATE INDEX mr_insat_index ON move_resources (inserted_at);
+CREATE INDEX ti_hand_ver_key_index ON table_items (table_handle, transaction_version);
+CREATE INDEX ti_insat_index ON table_items (inserted_at);
+CREATE INDEX tm_insat_index ON table_metadatas (inserted_at);
+CREATE INDEX ps_succ_ver_index ON processor_statuses (success, version ASC);
+CREATE INDEX ps_ver_index ON processor_statuses (version ASC);
+CREATE INDEX ps_lastup_index ON processor_statuses (last_updated);
+CREATE INDEX token_crea_cn_name_index ON tokens (creator_address, collection_name, name);
+CREATE INDEX token_insat_index ON tokens (inserted_at);
+CREATE INDEX to_owner_index ON token_ownerships (owner_address);
+CREATE INDEX to_crea_cn_name_index ON token_ownerships (creator_address, collection_name, name);
+CREATE INDEX to_insat_index ON token_ownerships (inserted_at);
+CREATE INDEX td_crea_cn_name_index ON token_datas (creator_address, collection_name, name);
+CREATE INDEX td_insat_index ON token_datas (inserted_at);
+CREATE INDEX cd_crea_cn_index ON collection_datas (creator_address, collection_name);
+CREATE INDEX cd_insat_index ON collection_datas (inserted_at);
+CREATE INDEX curr_to_crea_cn_name_index ON current_token_ownerships (creator_address, collection_name, name);
+CREATE INDEX curr_to_owner_index ON current_token_ownerships (owner_address);
+CREATE INDEX curr_to_insat_index ON current_token_ownerships (inserted_at);
+CREATE INDEX curr_td_crea_cn_name_index ON current_token_datas (creator_address, collection_name, name);
+CREATE INDEX curr_td_insat_index ON current_token_datas (inserted_at);
+CREATE INDEX curr_cd_crea_cn_index ON current_collection_datas (creator_address, collection_name);
+CREATE INDEX curr_cd_insat_index ON current_collection_datas (inserted_at);
+CREATE INDEX curr_to_owner_tt_am_index ON current_token_ownerships (owner_address, table_type, amount);
+CREATE INDEX ta_from_ttyp_index ON token_activities (from_address, transfer_type);
+CREATE INDEX ta_to_ttyp_index ON token_activities (to_address, transfer_type);
+CREATE INDEX ta_addr_coll_name_pv_index ON token_activities (creator_address, collection_name, name, property_version);
+CREATE INDEX ta_tdih_pv_index ON token_activities (token_data_id_hash, property_version);
+CREATE INDEX ta_version_index ON token_activities (transaction_version);
+CREATE INDEX ta_insat_index ON token_activities (inserted_at);
+CREATE INDEX ctpc_th_index ON current_token_pending_claims (table_handle);
+CREATE INDEX ctpc_from_am_index ON current_token_pending_claims (from_address, amount);
+CREATE INDEX ctpc_to_am_index ON current_token_pending_claims (to_address, amount);
+CREATE INDEX ctpc_insat_index ON current_token_pending_claims (inserted_at);
+CREATE INDEX curr_cd_th_index ON current_collection_datas (table_handle);
+CREATE INDEX curr_to_oa_tt_am_ltv_index ON current_token_ownerships (owner_address, table_type, amount, last_transaction_version DESC);
+CREATE INDEX curr_to_oa_tt_ltv_index ON current_token_ownerships (owner_address, table_type, last_transaction_version DESC);
+CREATE INDEX ans_et_index ON current_ans_lookup (expiration_timestamp);
+CREATE INDEX ans_ra_et_index ON current_ans_lookup (registered_address, expiration_timestamp);
+CREATE INDEX ans_d_s_et_index ON current_ans_lookup (domain, subdomain, expiration_timestamp);
+CREATE INDEX ans_insat_index ON current_ans_lookup (inserted_at);
+CREATE INDEX ps_name_succ_ver_index ON processor_statuses (name, success, version ASC);
+CREATE INDEX ci_ct_index ON coin_infos (coin_type);
+CREATE INDEX ci_ca_name_symbol_index ON coin_infos (creator_address, name, symbol);
+CREATE INDEX ci_insat_index ON coin_infos (inserted_at);
+CREATE INDEX cb_tv_oa_ct_index ON coin_balances (transaction_version, owner_address, coin_type);
+CREATE INDEX cb_oa_ct_index ON coin_balances (owner_address, coin_type);
+CREATE INDEX cb_ct_a_index ON coin_balances (coin_type, amount);
+CREATE INDEX cb_insat_index ON coin_balances (inserted_at);
+CREATE INDEX ccb_oa_ct_index ON current_coin_balances (owner_address, coin_type);
+CREATE INDEX ccb_ct_a_index ON current_coin_balances (coin_type, amount);
+CREATE INDEX ccb_insat_index ON current_coin_balances (inserted_at);
+CREATE INDEX ca_oa_ct_at_index ON coin_activities (owner_address, coin_type, activity_type, amount);
+CREATE INDEX ca_oa_igf_index ON coin_activities (owner_address, is_gas_fee);
+CREATE INDEX ca_ct_at_a_index ON coin_activities (coin_type, activity_type, amount);
+CREATE INDEX ca_ct_a_index ON coin_activities (coin_type, amount);
+CREATE INDEX ca_insat_index ON coin_activities (inserted_at);
+CREATE INDEX cs_ct_tv_index ON coin_supply (coin_type, transaction_version desc);
+CREATE INDEX cs_epoch_index ON coin_supply (transaction_epoch);
+CREATE INDEX ut_epoch_index ON user_transactions (epoch);
+CREATE INDEX txn_epoch_index ON transactions (epoch);
+CREATE INDEX ctpv_va_index ON current_staking_pool_voter (voter_address);
+CREATE INDEX ctpv_insat_index ON current_staking_pool_voter (inserted_at);
+CREATE INDEX ans_tn_index ON current_ans_lookup (token_name);
+CREATE INDEX pv_pi_va_index ON proposal_votes (proposal_id, voter_address);
+CREATE INDEX pv_va_index ON proposal_votes (voter_address);
+CREATE INDEX pv_spa_index ON proposal_votes (staking_pool_address);
+CREATE INDEX pv_ia_index ON proposal_votes (inserted_at);
@@ -5,16 +5,113 @@
 #![allow(clippy::extra_unused_lifetimes)]
 use crate::util::remove_null_bytes;
 use diesel::{
+    backend::Backend,
+    connection::{AnsiTransactionManager, SimpleConnection, TransactionManager},
     pg::{Pg, PgConnection},
     query_builder::{AstPass, Query, QueryFragment},
-    r2d2::{ConnectionManager, PoolError, PooledConnection},
-    QueryResult, RunQueryDsl,
+    r2d2::{ConnectionManager, CustomizeConnection, PoolError, PooledConnection, State},
+    sqlite::{Sqlite, SqliteConnection},
+    Connection, QueryResult, RunQueryDsl,
 };
-use std::{cmp::min, sync::Arc};
+use std::{cmp::min, sync::Arc, time::Duration};
 
 pub type PgPool = diesel::r2d2::Pool<ConnectionManager<PgConnection>>;
-pub type PgDbPool = Arc<PgPool>;
 pub type PgPoolConnection = PooledConnection<ConnectionManager<PgConnection>>;
+pub type SqlitePool = diesel::r2d2::Pool<ConnectionManager<SqliteConnection>>;
+pub type SqlitePoolConnection = PooledConnection<ConnectionManager<SqliteConnection>>;
+pub type DbPool = Arc<ConnectionPool>;
+
+/// Connection pool for the database backend selected in `IndexerConfig`
+pub enum ConnectionPool {
+    Postgres(PgPool),
+    Sqlite(SqlitePool),
+}
+
+impl ConnectionPool {
+    pub fn get(&self) -> Result<DbPoolConnection, PoolError> {
+        match self {
+            Self::Postgres(pool) => pool.get().map(DbPoolConnection::Postgres),
+            Self::Sqlite(pool) => pool.get().map(DbPoolConnection::Sqlite),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        match self {
+            Self::Postgres(pool) => pool.state(),
+            Self::Sqlite(pool) => pool.state(),
+        }
+    }
+
+    pub fn connection_timeout(&self) -> Duration {
+        match self {
+            Self::Postgres(pool) => pool.connection_timeout(),
+            Self::Sqlite(pool) => pool.connection_timeout(),
+        }
+    }
+}
+
+/// A connection taken from a `ConnectionPool`
+pub enum DbPoolConnection {
+    Postgres(PgPoolConnection),
+    Sqlite(SqlitePoolConnection),
+}
+
+/// Runs `$body` with `$conn` bound to the backend specific connection of a `DbPoolConnection`.
+/// The body is compiled once per backend, so any diesel query in it must support both.
+#[macro_export]
+macro_rules! with_connection {
+    ($db_conn:expr, |$conn:ident| $body:expr) => {
+        match $db_conn {
+            $crate::database::DbPoolConnection::Postgres($conn) => $body,
+            $crate::database::DbPoolConnection::Sqlite($conn) => $body,
+        }
+    };
+}
+
+impl DbPoolConnection {
+    /// Runs `f` inside a database transaction, which is rolled back if `f` fails. The error of
+    /// `f` is returned even if the rollback fails too, since it is what caused the rollback.
+    pub fn transaction<T, E, F>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self) -> Result<T, E>,
+        E: From<diesel::result::Error>,
+    {
+        with_connection!(self, |conn| AnsiTransactionManager::begin_transaction(
+            &mut **conn
+        ))?;
+        match f(self) {
+            Ok(value) => {
+                with_connection!(self, |conn| AnsiTransactionManager::commit_transaction(
+                    &mut **conn
+                ))?;
+                Ok(value)
+            }
+            Err(err) => {
+                if let Err(rollback_err) = with_connection!(self, |conn| {
+                    AnsiTransactionManager::rollback_transaction(&mut **conn)
+                }) {
+                    aptos_logger::error!("Error rolling back transaction: {:?}", rollback_err);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+/// SQLite only allows one writer at a time, so connections wait for each other instead of
+/// failing right away. WAL mode lets readers proceed while a processor is writing.
+#[derive(Debug)]
+struct SqliteConnectionCustomizer;
+
+impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for SqliteConnectionCustomizer {
+    fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+        conn.batch_execute(
+            "PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL; PRAGMA busy_timeout = 60000;",
+        )
+        .map_err(diesel::r2d2::Error::QueryError)
+    }
+}
+
 #[derive(QueryId)]
 /// Using this will append a where clause at the end of the string upsert function, e.g.
 /// INSERT INTO ... ON CONFLICT DO UPDATE SET ... WHERE "transaction_version" = excluded."transaction_version"
@@ -56,25 +153,56 @@ pub fn clean_data_for_db<T: serde::Serialize + for<'de> serde::Deserialize<'de>>
     }
 }
 
-pub fn new_db_pool(database_url: &str) -> Result<PgDbPool, PoolError> {
+pub fn new_db_pool(database_url: &str) -> Result<DbPool, PoolError> {
     let manager = ConnectionManager::<PgConnection>::new(database_url);
-    PgPool::builder().build(manager).map(Arc::new)
+    PgPool::builder()
+        .build(manager)
+        .map(|pool| Arc::new(ConnectionPool::Postgres(pool)))
+}
+
+/// Creates a pool of connections to the SQLite database at `path`, creating the file if needed
+pub fn new_sqlite_db_pool(path: &str) -> Result<DbPool, PoolError> {
+    let manager = ConnectionManager::<SqliteConnection>::new(path);
+    SqlitePool::builder()
+        .connection_customizer(Box::new(SqliteConnectionCustomizer))
+        .build(manager)
+        .map(|pool| Arc::new(ConnectionPool::Sqlite(pool)))
 }
 
 pub fn execute_with_better_error<
     T: diesel::Table + diesel::QuerySource + diesel::query_builder::QueryId + 'static,
-    U: diesel::query_builder::QueryFragment<diesel::pg::Pg>
+    U: diesel::query_builder::QueryFragment<Pg>
+        + diesel::query_builder::QueryFragment<Sqlite>
         + diesel::query_builder::QueryId
-        + diesel::insertable::CanInsertInSingleQuery<diesel::pg::Pg>,
+        + diesel::insertable::CanInsertInSingleQuery<Pg>
+        + diesel::insertable::CanInsertInSingleQuery<Sqlite>,
 >(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     query: diesel::query_builder::InsertStatement<T, U>,
+    additional_where_clause: Option<&'static str>,
+) -> diesel::QueryResult<usize>
+where
+    <T as diesel::QuerySource>::FromClause:
+        diesel::query_builder::QueryFragment<Pg> + diesel::query_builder::QueryFragment<Sqlite>,
+{
+    with_connection!(conn, |conn| execute_upsert(
+        &mut **conn,
+        query,
+        additional_where_clause
+    ))
+}
+
+fn execute_upsert<C, Q>(
+    conn: &mut C,
+    query: Q,
     mut additional_where_clause: Option<&'static str>,
 ) -> diesel::QueryResult<usize>
 where
-    <T as diesel::QuerySource>::FromClause: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+    C: Connection,
+    Q: QueryFragment<C::Backend> + diesel::query_builder::QueryId,
+    <C::Backend as Backend>::QueryBuilder: Default,
 {
-    let original_query = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+    let original_query = diesel::debug_query::<C::Backend, _>(&query).to_string();
     // This is needed because if we don't insert any row, then diesel makes a call like this
     // SELECT 1 FROM TABLE WHERE 1=0
     if original_query.to_lowercase().contains("where") {
@@ -84,7 +212,7 @@ where
         query,
         where_clause: additional_where_clause,
     };
-    let debug = diesel::debug_query::<diesel::pg::Pg, _>(&final_query).to_string();
+    let debug = diesel::debug_query::<C::Backend, _>(&final_query).to_string();
     aptos_logger::debug!("Executing query: {:?}", debug);
     let res = final_query.execute(conn);
     if let Err(ref e) = res {
@@ -98,13 +226,14 @@ impl<T: Query> Query for UpsertFilterLatestTransactionQuery<T> {
     type SqlType = T::SqlType;
 }
 
-impl<T> RunQueryDsl<PgConnection> for UpsertFilterLatestTransactionQuery<T> {}
+impl<T, C> RunQueryDsl<C> for UpsertFilterLatestTransactionQuery<T> {}
 
-impl<T> QueryFragment<Pg> for UpsertFilterLatestTransactionQuery<T>
+impl<T, DB> QueryFragment<DB> for UpsertFilterLatestTransactionQuery<T>
 where
-    T: QueryFragment<Pg>,
+    DB: Backend,
+    T: QueryFragment<DB>,
 {
-    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, DB>) -> QueryResult<()> {
         self.query.walk_ast(out.reborrow())?;
         if let Some(w) = self.where_clause {
             out.push_sql(w);
@@ -135,4 +264,16 @@ mod test {
             vec![(0, 21845), (21845, 43690), (43690, 65535)]
         );
     }
+
+    #[test]
+    fn test_transaction_keeps_error_when_rollback_fails() {
+        let pool = new_sqlite_db_pool(":memory:").unwrap();
+        let mut conn = pool.get().unwrap();
+        let result: Result<(), diesel::result::Error> = conn.transaction(|conn| {
+            // End the transaction behind the transaction manager's back, so rolling back fails
+            with_connection!(conn, |conn| conn.batch_execute("ROLLBACK")).unwrap();
+            Err(diesel::result::Error::NotFound)
+        });
+        assert!(matches!(result, Err(diesel::result::Error::NotFound)));
+    }
 }
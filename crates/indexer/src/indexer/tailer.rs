@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
-    database::{execute_with_better_error, PgDbPool},
+    database::{execute_with_better_error, DbPool, DbPoolConnection},
     indexer::{
         errors::TransactionProcessingError,
         fetcher::{TransactionFetcher, TransactionFetcherOptions, TransactionFetcherTrait},
@@ -13,15 +13,16 @@ use crate::{
         processor_status::{ProcessorStatusV2, ProcessorStatusV2Query},
     },
     schema::{ledger_infos, processor_status},
+    with_connection,
 };
 use anyhow::{ensure, Context, Result};
 use aptos_api::context::Context as ApiContext;
 use aptos_logger::{debug, info};
 use chrono::ParseError;
 use diesel::{
-    pg::upsert::excluded,
     sql_query,
     sql_types::{BigInt, Text},
+    upsert::excluded,
     ExpressionMethods, RunQueryDsl,
 };
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness};
@@ -29,18 +30,144 @@ use std::{fmt::Debug, sync::Arc};
 use tokio::{sync::Mutex, task::JoinHandle};
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!();
+pub const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations_sqlite");
+
+// These queries get the first version that isn't equal to the next version (versions would be
+// sorted of course). There's also special handling if the gap happens in the beginning.
+// $1/?1 is the processor name, and $2/?2 is the number of versions to look back for gaps.
+const PG_START_VERSION_QUERY: &str = "
+        WITH raw_boundaries AS
+        (
+            SELECT
+                MAX(version) AS MAX_V,
+                MIN(version) AS MIN_V
+            FROM
+                processor_statuses
+            WHERE
+                name = $1
+                AND success = TRUE
+        ),
+        boundaries AS
+        (
+            SELECT
+                MAX(version) AS MAX_V,
+                MIN(version) AS MIN_V
+            FROM
+                processor_statuses, raw_boundaries
+            WHERE
+                name = $1
+                AND success = true
+                and version >= GREATEST(MAX_V - $2, 0)
+        ),
+        gap AS
+        (
+            SELECT
+                MIN(version) + 1 AS maybe_gap
+            FROM
+                (
+                    SELECT
+                        version,
+                        LEAD(version) OVER (
+                    ORDER BY
+                        version ASC) AS next_version
+                    FROM
+                        processor_statuses,
+                        boundaries
+                    WHERE
+                        name = $1
+                        AND success = TRUE
+                        AND version >= GREATEST(MAX_V - $2, 0)
+                ) a
+            WHERE
+                version + 1 <> next_version
+        )
+        SELECT
+            CASE
+                WHEN
+                    MIN_V <> GREATEST(MAX_V - $2, 0)
+                THEN
+                    GREATEST(MAX_V - $2, 0)
+                ELSE
+                    COALESCE(maybe_gap, MAX_V + 1)
+            END
+            AS version
+        FROM
+            gap, boundaries
+        ";
+
+// SQLite has no GREATEST, its multi-argument MAX is the scalar equivalent. Parameters are
+// numbered explicitly, as SQLite would number `$2` by its first occurrence.
+const SQLITE_START_VERSION_QUERY: &str = "
+        WITH raw_boundaries AS
+        (
+            SELECT
+                MAX(version) AS MAX_V,
+                MIN(version) AS MIN_V
+            FROM
+                processor_statuses
+            WHERE
+                name = ?1
+                AND success = TRUE
+        ),
+        boundaries AS
+        (
+            SELECT
+                MAX(version) AS MAX_V,
+                MIN(version) AS MIN_V
+            FROM
+                processor_statuses, raw_boundaries
+            WHERE
+                name = ?1
+                AND success = TRUE
+                AND version >= MAX(MAX_V - ?2, 0)
+        ),
+        gap AS
+        (
+            SELECT
+                MIN(version) + 1 AS maybe_gap
+            FROM
+                (
+                    SELECT
+                        version,
+                        LEAD(version) OVER (
+                    ORDER BY
+                        version ASC) AS next_version
+                    FROM
+                        processor_statuses,
+                        boundaries
+                    WHERE
+                        name = ?1
+                        AND success = TRUE
+                        AND version >= MAX(MAX_V - ?2, 0)
+                ) a
+            WHERE
+                version + 1 <> next_version
+        )
+        SELECT
+            CASE
+                WHEN
+                    MIN_V <> MAX(MAX_V - ?2, 0)
+                THEN
+                    MAX(MAX_V - ?2, 0)
+                ELSE
+                    COALESCE(maybe_gap, MAX_V + 1)
+            END
+            AS version
+        FROM
+            gap, boundaries
+        ";
 
 #[derive(Clone)]
 pub struct Tailer {
     pub transaction_fetcher: Arc<Mutex<dyn TransactionFetcherTrait>>,
     processor: Arc<dyn TransactionProcessor>,
-    connection_pool: PgDbPool,
+    connection_pool: DbPool,
 }
 
 impl Tailer {
     pub fn new(
         context: Arc<ApiContext>,
-        connection_pool: PgDbPool,
+        connection_pool: DbPool,
         processor: Arc<dyn TransactionProcessor>,
         options: TransactionFetcherOptions,
     ) -> Result<Tailer, ParseError> {
@@ -55,12 +182,11 @@ impl Tailer {
     }
 
     pub fn run_migrations(&self) {
-        let _ = &self
+        let mut conn = self
             .connection_pool
             .get()
-            .expect("Could not get connection for migrations")
-            .run_pending_migrations(MIGRATIONS)
-            .expect("migrations failed!");
+            .expect("Could not get connection for migrations");
+        run_migrations(&mut conn);
     }
 
     /// If chain id doesn't exist, save it. Otherwise, make sure that we're indexing the same chain
@@ -213,82 +339,34 @@ impl Tailer {
             .get()
             .expect("DB connection should be available to get starting version");
 
-        // This query gets the first version that isn't equal to the next version (versions would be sorted of course).
-        // There's also special handling if the gap happens in the beginning.
-        let sql = "
-        WITH raw_boundaries AS
-        (
-            SELECT
-                MAX(version) AS MAX_V,
-                MIN(version) AS MIN_V
-            FROM
-                processor_statuses
-            WHERE
-                name = $1
-                AND success = TRUE
-        ),
-        boundaries AS
-        (
-            SELECT
-                MAX(version) AS MAX_V,
-                MIN(version) AS MIN_V
-            FROM
-                processor_statuses, raw_boundaries
-            WHERE
-                name = $1
-                AND success = true
-                and version >= GREATEST(MAX_V - $2, 0)
-        ),
-        gap AS
-        (
-            SELECT
-                MIN(version) + 1 AS maybe_gap
-            FROM
-                (
-                    SELECT
-                        version,
-                        LEAD(version) OVER (
-                    ORDER BY
-                        version ASC) AS next_version
-                    FROM
-                        processor_statuses,
-                        boundaries
-                    WHERE
-                        name = $1
-                        AND success = TRUE
-                        AND version >= GREATEST(MAX_V - $2, 0)
-                ) a
-            WHERE
-                version + 1 <> next_version
-        )
-        SELECT
-            CASE
-                WHEN
-                    MIN_V <> GREATEST(MAX_V - $2, 0)
-                THEN
-                    GREATEST(MAX_V - $2, 0)
-                ELSE
-                    COALESCE(maybe_gap, MAX_V + 1)
-            END
-            AS version
-        FROM
-            gap, boundaries
-        ";
+        let sql = match conn {
+            DbPoolConnection::Postgres(_) => PG_START_VERSION_QUERY,
+            DbPoolConnection::Sqlite(_) => SQLITE_START_VERSION_QUERY,
+        };
         #[derive(Debug, QueryableByName)]
         pub struct Gap {
             #[diesel(sql_type = BigInt)]
             pub version: i64,
         }
-        let mut res: Vec<Option<Gap>> = sql_query(sql)
+        let mut res: Vec<Option<Gap>> = with_connection!(&mut conn, |conn| sql_query(sql)
             .bind::<Text, _>(processor_name)
             // This is the number used to determine how far we look back for gaps. Increasing it may result in slower startup
             .bind::<BigInt, _>(lookback_versions)
-            .get_results(&mut conn)
-            .unwrap();
+            .get_results(conn))
+        .unwrap();
         res.pop().unwrap().map(|g| g.version)
     }
 }
 
+/// Runs the pending migrations of the database backend of `conn`
+pub fn run_migrations(conn: &mut DbPoolConnection) {
+    let _ = match conn {
+        DbPoolConnection::Postgres(conn) => conn.run_pending_migrations(MIGRATIONS),
+        DbPoolConnection::Sqlite(conn) => conn.run_pending_migrations(SQLITE_MIGRATIONS),
+    }
+    .expect("migrations failed!");
+}
+
 pub async fn await_tasks<T: Debug>(tasks: Vec<JoinHandle<T>>) -> Vec<T> {
     let mut results = vec![];
     for task in tasks {
@@ -304,17 +382,20 @@ pub async fn await_tasks<T: Debug>(tasks: Vec<JoinHandle<T>>) -> Vec<T> {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
     use crate::{
-        database::{new_db_pool, PgPoolConnection},
-        models::transactions::TransactionQuery,
+        database::{new_db_pool, new_sqlite_db_pool, DbPoolConnection},
+        models::{processor_statuses::ProcessorStatus, transactions::TransactionQuery},
         processors::default_processor::DefaultTransactionProcessor,
+        schema::processor_statuses,
     };
     use aptos_api_test_context::new_test_context;
     use aptos_api_types::{LedgerInfo as APILedgerInfo, Transaction, U64};
+    use aptos_temppath::TempPath;
     use diesel::RunQueryDsl;
     use serde_json::json;
+    use std::path::Path;
 
     struct FakeFetcher {
         version: u64,
@@ -359,23 +440,31 @@ mod test {
         }
     }
 
-    pub fn wipe_database(conn: &mut PgPoolConnection) {
+    pub fn wipe_database(conn: &mut DbPoolConnection) {
         for command in [
             "DROP SCHEMA public CASCADE",
             "CREATE SCHEMA public",
             "GRANT ALL ON SCHEMA public TO postgres",
             "GRANT ALL ON SCHEMA public TO public",
         ] {
-            diesel::sql_query(command).execute(conn).unwrap();
+            with_connection!(&mut *conn, |conn| diesel::sql_query(command).execute(conn)).unwrap();
         }
     }
 
-    pub fn setup_indexer() -> Result<(PgDbPool, Tailer)> {
+    pub fn setup_indexer() -> Result<(DbPool, Tailer)> {
         let database_url = std::env::var("INDEXER_DATABASE_URL")
             .expect("must set 'INDEXER_DATABASE_URL' to run tests!");
         let conn_pool = new_db_pool(database_url.as_str())?;
         wipe_database(&mut conn_pool.get()?);
+        setup_tailer(conn_pool)
+    }
+
+    pub fn setup_sqlite_indexer(path: &Path) -> Result<(DbPool, Tailer)> {
+        let conn_pool = new_sqlite_db_pool(path.to_str().unwrap())?;
+        setup_tailer(conn_pool)
+    }
 
+    fn setup_tailer(conn_pool: DbPool) -> Result<(DbPool, Tailer)> {
         let test_context = new_test_context("doesnt_matter".to_string(), true);
         let context: Arc<ApiContext> = Arc::new(test_context.context);
         let pg_transaction_processor = DefaultTransactionProcessor::new(conn_pool.clone());
@@ -396,7 +485,51 @@ mod test {
         if crate::should_skip_pg_tests() {
             return;
         }
+        let _pg_test_guard = crate::PG_TEST_LOCK.lock().await;
+        let (conn_pool, tailer) = setup_indexer().unwrap();
+        check_parsing_and_writing(conn_pool, tailer).await;
         let (conn_pool, tailer) = setup_indexer().unwrap();
+        check_chain_id(&tailer).await;
+        check_start_version(conn_pool, tailer);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_parsing_and_writing_sqlite() {
+        let path = TempPath::new();
+        let (conn_pool, tailer) = setup_sqlite_indexer(path.path()).unwrap();
+        check_parsing_and_writing(conn_pool, tailer).await;
+        let path = TempPath::new();
+        let (conn_pool, tailer) = setup_sqlite_indexer(path.path()).unwrap();
+        check_chain_id(&tailer).await;
+        check_start_version(conn_pool, tailer);
+    }
+
+    fn check_start_version(conn_pool: DbPool, tailer: Tailer) {
+        let name = "test_processor";
+        assert_eq!(tailer.get_start_version_long(&name.to_string(), 100), None);
+
+        // Versions 0 to 3 and 5 to 6 were processed
+        let statuses: Vec<_> = ProcessorStatus::from_versions(name, 0, 3, true, None)
+            .into_iter()
+            .chain(ProcessorStatus::from_versions(name, 5, 6, true, None))
+            .collect();
+        with_connection!(&mut conn_pool.get().unwrap(), |conn| {
+            diesel::insert_into(processor_statuses::table)
+                .values(&statuses)
+                .execute(conn)
+        })
+        .unwrap();
+
+        // The gap is found when looking far enough back, otherwise processing continues after
+        // the latest version
+        assert_eq!(
+            tailer.get_start_version_long(&name.to_string(), 100),
+            Some(4)
+        );
+        assert_eq!(tailer.get_start_version_long(&name.to_string(), 1), Some(7));
+    }
+
+    async fn check_parsing_and_writing(conn_pool: DbPool, tailer: Tailer) {
         // An abridged genesis transaction
         let genesis_txn: Transaction = serde_json::from_value(json!(
             {
@@ -906,8 +1039,9 @@ mod test {
             .process_transactions_with_status(txns)
             .await
             .unwrap();
+    }
 
-        let (_conn_pool, tailer) = setup_indexer().unwrap();
+    async fn check_chain_id(tailer: &Tailer) {
         tailer.set_fetcher_version(4).await;
         assert!(tailer.check_or_update_chain_id().await.is_ok());
         assert!(tailer.check_or_update_chain_id().await.is_ok());
@@ -16,8 +16,10 @@ pub mod database;
 pub mod indexer;
 pub mod models;
 pub mod processors;
+pub mod query_server;
 pub mod runtime;
 pub mod schema;
+pub mod sql_types;
 mod util;
 
 /// By default, skips test unless `INDEXER_DATABASE_URL` is set.
@@ -33,3 +35,9 @@ pub fn should_skip_pg_tests() -> bool {
         true
     }
 }
+
+/// Tests against Postgres wipe the database at `INDEXER_DATABASE_URL` before using it, so they
+/// hold this lock to not run concurrently.
+#[cfg(test)]
+pub(crate) static PG_TEST_LOCK: once_cell::sync::Lazy<tokio::sync::Mutex<()>> =
+    once_cell::sync::Lazy::new(|| tokio::sync::Mutex::new(()));
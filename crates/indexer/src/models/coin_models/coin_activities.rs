@@ -12,8 +12,10 @@ use super::{
     coin_utils::{CoinEvent, EventGuidResource},
 };
 use crate::{
+    database::DbPoolConnection,
     schema::coin_activities,
     util::{parse_timestamp, standardize_address, truncate_str},
+    with_connection,
 };
 use aptos_api_types::{
     Event as APIEvent, Transaction as APITransaction, TransactionInfo as APITransactionInfo,
@@ -21,6 +23,7 @@ use aptos_api_types::{
 };
 use aptos_types::APTOS_COIN_TYPE;
 use bigdecimal::BigDecimal;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -60,6 +63,31 @@ pub struct CoinActivity {
     pub transaction_timestamp: chrono::NaiveDateTime,
 }
 
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(
+    transaction_version,
+    event_account_address,
+    event_creation_number,
+    event_sequence_number
+))]
+#[diesel(table_name = coin_activities)]
+pub struct CoinActivityQuery {
+    pub transaction_version: i64,
+    pub event_account_address: String,
+    pub event_creation_number: i64,
+    pub event_sequence_number: i64,
+    pub owner_address: String,
+    pub coin_type: String,
+    pub amount: BigDecimal,
+    pub activity_type: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub block_height: i64,
+    pub transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
 impl CoinActivity {
     /// There are different objects containing different information about balances and coins.
     /// Events: Withdraw and Deposit event containing amounts. There is no coin type so we need to get that from Resources. (from event guid)
@@ -272,3 +300,25 @@ impl CoinActivity {
         }
     }
 }
+
+impl CoinActivityQuery {
+    /// Most recent activities come first
+    pub fn get_by_owner_address(
+        owner_address: &str,
+        offset: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        with_connection!(conn, |conn| coin_activities::table
+            .filter(coin_activities::owner_address.eq(owner_address))
+            .order((
+                coin_activities::transaction_version.desc(),
+                coin_activities::event_account_address.asc(),
+                coin_activities::event_creation_number.asc(),
+                coin_activities::event_sequence_number.asc(),
+            ))
+            .offset(offset)
+            .limit(limit)
+            .load::<Self>(conn))
+    }
+}
@@ -9,9 +9,13 @@ use super::{
     coin_activities::EventToCoinType,
     coin_utils::{CoinInfoType, CoinResource},
 };
-use crate::{schema::coin_balances, schema::current_coin_balances, util::standardize_address};
+use crate::{
+    database::DbPoolConnection, schema::coin_balances, schema::current_coin_balances,
+    util::standardize_address, with_connection,
+};
 use aptos_api_types::WriteResource as APIWriteResource;
 use bigdecimal::BigDecimal;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -40,6 +44,19 @@ pub struct CurrentCoinBalance {
     pub last_transaction_timestamp: chrono::NaiveDateTime,
 }
 
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(owner_address, coin_type_hash))]
+#[diesel(table_name = current_coin_balances)]
+pub struct CurrentCoinBalanceQuery {
+    pub owner_address: String,
+    pub coin_type_hash: String,
+    pub coin_type: String,
+    pub amount: BigDecimal,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+    pub inserted_at: chrono::NaiveDateTime,
+}
+
 impl CoinBalance {
     /// We can find coin info from resources. If the coin info appears multiple times we will only keep the first transaction because it can't be modified.
     pub fn from_write_resource(
@@ -91,3 +108,19 @@ impl CoinBalance {
         }
     }
 }
+
+impl CurrentCoinBalanceQuery {
+    pub fn get_by_owner_address(
+        owner_address: &str,
+        offset: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        with_connection!(conn, |conn| current_coin_balances::table
+            .filter(current_coin_balances::owner_address.eq(owner_address))
+            .order(current_coin_balances::coin_type_hash.asc())
+            .offset(offset)
+            .limit(limit)
+            .load::<Self>(conn))
+    }
+}
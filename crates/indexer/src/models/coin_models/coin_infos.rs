@@ -6,7 +6,7 @@
 #![allow(clippy::unused_unit)]
 
 use super::coin_utils::{CoinInfoType, CoinResource};
-use crate::{database::PgPoolConnection, schema::coin_infos};
+use crate::{database::DbPoolConnection, schema::coin_infos, with_connection};
 use aptos_api_types::WriteResource as APIWriteResource;
 use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
@@ -84,11 +84,11 @@ impl CoinInfo {
 impl CoinInfoQuery {
     pub fn get_by_coin_type(
         coin_type: String,
-        conn: &mut PgPoolConnection,
+        conn: &mut DbPoolConnection,
     ) -> diesel::QueryResult<Option<Self>> {
-        coin_infos::table
+        with_connection!(conn, |conn| coin_infos::table
             .filter(coin_infos::coin_type.eq(coin_type))
             .first::<Self>(conn)
-            .optional()
+            .optional())
     }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::extra_unused_lifetimes)]
-use crate::{database::PgPoolConnection, schema::ledger_infos};
+use crate::{database::DbPoolConnection, schema::ledger_infos, with_connection};
 use diesel::{OptionalExtension, QueryDsl, RunQueryDsl};
 
 #[derive(Debug, Identifiable, Insertable, Queryable)]
@@ -12,10 +12,10 @@ pub struct LedgerInfo {
 }
 
 impl LedgerInfo {
-    pub fn get(conn: &mut PgPoolConnection) -> diesel::QueryResult<Option<Self>> {
-        ledger_infos::table
+    pub fn get(conn: &mut DbPoolConnection) -> diesel::QueryResult<Option<Self>> {
+        with_connection!(conn, |conn| ledger_infos::table
             .select(ledger_infos::all_columns)
             .first::<Self>(conn)
-            .optional()
+            .optional())
     }
 }
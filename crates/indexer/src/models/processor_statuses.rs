@@ -1,7 +1,11 @@
 // Copyright (c) Aptos
 // SPDX-License-Identifier: Apache-2.0
 #![allow(clippy::extra_unused_lifetimes)]
-use crate::{indexer::errors::TransactionProcessingError, schema::processor_statuses};
+use crate::{
+    database::DbPoolConnection, indexer::errors::TransactionProcessingError,
+    schema::processor_statuses, with_connection,
+};
+use diesel::{dsl::max, ExpressionMethods, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
 
 #[derive(AsChangeset, Debug, FieldCount, Insertable, Queryable)]
@@ -53,5 +57,28 @@ impl ProcessorStatus {
     }
 }
 
+/// Latest version successfully processed by a processor. Note that there may be gaps before it.
+#[derive(Debug, Queryable)]
+pub struct ProcessorStatusSummary {
+    pub name: String,
+    pub latest_success_version: Option<i64>,
+    pub last_updated: Option<chrono::NaiveDateTime>,
+}
+
+impl ProcessorStatusSummary {
+    pub fn get_all(conn: &mut DbPoolConnection) -> diesel::QueryResult<Vec<Self>> {
+        with_connection!(conn, |conn| processor_statuses::table
+            .filter(processor_statuses::success.eq(true))
+            .group_by(processor_statuses::name)
+            .select((
+                processor_statuses::name,
+                max(processor_statuses::version),
+                max(processor_statuses::last_updated),
+            ))
+            .order(processor_statuses::name.asc())
+            .load::<Self>(conn))
+    }
+}
+
 // Prevent conflicts with other things named `ProcessorStatus`
 pub type ProcessorStatusModel = ProcessorStatus;
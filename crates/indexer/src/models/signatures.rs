@@ -6,7 +6,7 @@ use crate::{models::transactions::Transaction, schema::signatures, util::standar
 use anyhow::{Context, Result};
 use aptos_api_types::{
     AccountSignature as APIAccountSignature, Ed25519Signature as APIEd25519Signature,
-    MultiAgentSignature as APIMultiAgentSignature,
+    FeePayerSignature as APIFeePayerSignature, MultiAgentSignature as APIMultiAgentSignature,
     MultiEd25519Signature as APIMultiEd25519Signature,
     TransactionSignature as APITransactionSignature,
 };
@@ -74,6 +74,12 @@ impl Signature {
                 transaction_version,
                 transaction_block_height,
             ),
+            APITransactionSignature::FeePayerSignature(sig) => Self::parse_fee_payer_signature(
+                sig,
+                sender,
+                transaction_version,
+                transaction_block_height,
+            ),
         }
     }
 
@@ -86,6 +92,7 @@ impl Signature {
             APITransactionSignature::MultiAgentSignature(_) => {
                 String::from("multi_agent_signature")
             }
+            APITransactionSignature::FeePayerSignature(_) => String::from("fee_payer_signature"),
         }
     }
 
@@ -192,6 +199,35 @@ impl Signature {
         Ok(signatures)
     }
 
+    fn parse_fee_payer_signature(
+        s: &APIFeePayerSignature,
+        sender: &String,
+        transaction_version: i64,
+        transaction_block_height: i64,
+    ) -> Result<Vec<Self>> {
+        let mut signatures = Self::parse_multi_agent_signature(
+            &APIMultiAgentSignature {
+                sender: s.sender.clone(),
+                secondary_signer_addresses: s.secondary_signer_addresses.clone(),
+                secondary_signers: s.secondary_signers.clone(),
+            },
+            sender,
+            transaction_version,
+            transaction_block_height,
+        )?;
+        // The fee payer comes after all the secondary signers
+        signatures.append(&mut Self::parse_multi_agent_signature_helper(
+            &s.fee_payer_signer,
+            sender,
+            transaction_version,
+            transaction_block_height,
+            false,
+            s.secondary_signer_addresses.len() as i64,
+            Some(&s.fee_payer_address.to_string()),
+        ));
+        Ok(signatures)
+    }
+
     fn parse_multi_agent_signature_helper(
         s: &APIAccountSignature,
         sender: &String,
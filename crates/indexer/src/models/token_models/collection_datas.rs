@@ -10,9 +10,10 @@ use super::{
     tokens::TableHandleToOwner,
 };
 use crate::{
-    database::PgPoolConnection,
+    database::DbPoolConnection,
     schema::{collection_datas, current_collection_datas},
     util::standardize_address,
+    with_connection,
 };
 use anyhow::Context;
 use aptos_api_types::WriteTableItem as APIWriteTableItem;
@@ -88,7 +89,7 @@ impl CollectionData {
         txn_version: i64,
         txn_timestamp: chrono::NaiveDateTime,
         table_handle_to_owner: &TableHandleToOwner,
-        conn: &mut PgPoolConnection,
+        conn: &mut DbPoolConnection,
     ) -> anyhow::Result<Option<(Self, CurrentCollectionData)>> {
         let table_item_data = table_item.data.as_ref().unwrap();
 
@@ -160,7 +161,7 @@ impl CollectionData {
     /// cannot change, we can just look in the current_collection_datas table.
     /// Retrying a few times since this collection could've been written in a separate thread.
     pub fn get_collection_creator(
-        conn: &mut PgPoolConnection,
+        conn: &mut DbPoolConnection,
         table_handle: &str,
     ) -> anyhow::Result<String> {
         let mut retried = 0;
@@ -179,11 +180,34 @@ impl CollectionData {
 
 impl CurrentCollectionDataQuery {
     pub fn get_by_table_handle(
-        conn: &mut PgPoolConnection,
+        conn: &mut DbPoolConnection,
         table_handle: &str,
     ) -> diesel::QueryResult<Self> {
-        current_collection_datas::table
+        with_connection!(conn, |conn| current_collection_datas::table
             .filter(current_collection_datas::table_handle.eq(table_handle))
-            .first::<Self>(conn)
+            .first::<Self>(conn))
+    }
+
+    pub fn get_by_creator_address(
+        conn: &mut DbPoolConnection,
+        creator_address: &str,
+        collection_name: Option<&str>,
+        offset: i64,
+        limit: i64,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        with_connection!(conn, |conn| {
+            // Boxed queries are tied to a backend, so this has to be built for each of them
+            let mut query = current_collection_datas::table
+                .filter(current_collection_datas::creator_address.eq(creator_address))
+                .into_boxed();
+            if let Some(collection_name) = collection_name {
+                query = query.filter(current_collection_datas::collection_name.eq(collection_name));
+            }
+            query
+                .order(current_collection_datas::collection_name.asc())
+                .offset(offset)
+                .limit(limit)
+                .load::<Self>(conn)
+        })
     }
 }
@@ -10,10 +10,13 @@ use super::{
     tokens::{TableHandleToOwner, Token},
 };
 use crate::{
+    database::DbPoolConnection,
     schema::{current_token_ownerships, token_ownerships},
     util::standardize_address,
+    with_connection,
 };
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
 use field_count::FieldCount;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -59,6 +62,25 @@ pub struct CurrentTokenOwnership {
     pub last_transaction_timestamp: chrono::NaiveDateTime,
 }
 
+#[derive(Debug, Deserialize, Identifiable, Queryable, Serialize)]
+#[diesel(primary_key(token_data_id_hash, property_version, owner_address))]
+#[diesel(table_name = current_token_ownerships)]
+pub struct CurrentTokenOwnershipQuery {
+    pub token_data_id_hash: String,
+    pub property_version: BigDecimal,
+    pub owner_address: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub name: String,
+    pub amount: BigDecimal,
+    pub token_properties: serde_json::Value,
+    pub last_transaction_version: i64,
+    pub inserted_at: chrono::NaiveDateTime,
+    pub collection_data_id_hash: String,
+    pub table_type: String,
+    pub last_transaction_timestamp: chrono::NaiveDateTime,
+}
+
 impl TokenOwnership {
     /// We only want to track tokens in 0x1::token::TokenStore for now. This is because the table
     /// schema doesn't have table type (i.e. token container) as primary key. TokenStore has token_id
@@ -141,3 +163,24 @@ impl TokenOwnership {
         )))
     }
 }
+
+impl CurrentTokenOwnershipQuery {
+    /// Only returns tokens that the owner currently holds, i.e. with a non zero amount
+    pub fn get_by_owner_address(
+        owner_address: &str,
+        offset: i64,
+        limit: i64,
+        conn: &mut DbPoolConnection,
+    ) -> diesel::QueryResult<Vec<Self>> {
+        with_connection!(conn, |conn| current_token_ownerships::table
+            .filter(current_token_ownerships::owner_address.eq(owner_address))
+            .filter(current_token_ownerships::amount.gt(BigDecimal::zero()))
+            .order((
+                current_token_ownerships::token_data_id_hash.asc(),
+                current_token_ownerships::property_version.asc(),
+            ))
+            .offset(offset)
+            .limit(limit)
+            .load::<Self>(conn))
+    }
+}
@@ -3,7 +3,7 @@
 
 use crate::{
     database::{
-        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+        clean_data_for_db, execute_with_better_error, get_chunks, DbPool, DbPoolConnection,
     },
     indexer::{
         errors::TransactionProcessingError, processing_result::ProcessingResult,
@@ -20,17 +20,17 @@ use crate::{
 use aptos_api_types::Transaction as APITransaction;
 use aptos_types::APTOS_COIN_TYPE;
 use async_trait::async_trait;
-use diesel::{pg::upsert::excluded, result::Error, ExpressionMethods, PgConnection};
+use diesel::{result::Error, upsert::excluded, ExpressionMethods};
 use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "coin_processor";
 pub struct CoinTransactionProcessor {
-    connection_pool: PgDbPool,
+    connection_pool: DbPool,
 }
 
 impl CoinTransactionProcessor {
-    pub fn new(connection_pool: PgDbPool) -> Self {
+    pub fn new(connection_pool: DbPool) -> Self {
         Self { connection_pool }
     }
 }
@@ -47,7 +47,7 @@ impl Debug for CoinTransactionProcessor {
 }
 
 fn insert_to_db_impl(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     coin_activities: &[CoinActivity],
     coin_infos: &[CoinInfo],
     coin_balances: &[CoinBalance],
@@ -63,7 +63,7 @@ fn insert_to_db_impl(
 }
 
 fn insert_to_db(
-    conn: &mut PgPoolConnection,
+    conn: &mut DbPoolConnection,
     name: &'static str,
     start_version: u64,
     end_version: u64,
@@ -79,43 +79,37 @@ fn insert_to_db(
         end_version = end_version,
         "Inserting to db",
     );
-    match conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|pg_conn| {
+    match conn.transaction::<_, Error, _>(|conn| {
+        insert_to_db_impl(
+            conn,
+            &coin_activities,
+            &coin_infos,
+            &coin_balances,
+            &current_coin_balances,
+            &coin_supply,
+        )
+    }) {
+        Ok(_) => Ok(()),
+        Err(_) => conn.transaction::<_, Error, _>(|conn| {
+            let coin_activities = clean_data_for_db(coin_activities, true);
+            let coin_infos = clean_data_for_db(coin_infos, true);
+            let coin_balances = clean_data_for_db(coin_balances, true);
+            let current_coin_balances = clean_data_for_db(current_coin_balances, true);
+
             insert_to_db_impl(
-                pg_conn,
+                conn,
                 &coin_activities,
                 &coin_infos,
                 &coin_balances,
                 &current_coin_balances,
                 &coin_supply,
             )
-        }) {
-        Ok(_) => Ok(()),
-        Err(_) => conn
-            .build_transaction()
-            .read_write()
-            .run::<_, Error, _>(|pg_conn| {
-                let coin_activities = clean_data_for_db(coin_activities, true);
-                let coin_infos = clean_data_for_db(coin_infos, true);
-                let coin_balances = clean_data_for_db(coin_balances, true);
-                let current_coin_balances = clean_data_for_db(current_coin_balances, true);
-
-                insert_to_db_impl(
-                    pg_conn,
-                    &coin_activities,
-                    &coin_infos,
-                    &coin_balances,
-                    &current_coin_balances,
-                    &coin_supply,
-                )
-            }),
+        }),
     }
 }
 
 fn insert_coin_activities(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CoinActivity],
 ) -> Result<(), diesel::result::Error> {
     use schema::coin_activities::dsl::*;
@@ -140,7 +134,7 @@ fn insert_coin_activities(
 }
 
 fn insert_coin_infos(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CoinInfo],
 ) -> Result<(), diesel::result::Error> {
     use schema::coin_infos::dsl::*;
@@ -171,7 +165,7 @@ fn insert_coin_infos(
 }
 
 fn insert_coin_balances(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CoinBalance],
 ) -> Result<(), diesel::result::Error> {
     use schema::coin_balances::dsl::*;
@@ -191,7 +185,7 @@ fn insert_coin_balances(
 }
 
 fn insert_current_coin_balances(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CurrentCoinBalance],
 ) -> Result<(), diesel::result::Error> {
     use schema::current_coin_balances::dsl::*;
@@ -217,7 +211,7 @@ fn insert_current_coin_balances(
 }
 
 fn insert_coin_supply(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CoinSupply],
 ) -> Result<(), diesel::result::Error> {
     use schema::coin_supply::dsl::*;
@@ -315,7 +309,7 @@ impl TransactionProcessor for CoinTransactionProcessor {
         }
     }
 
-    fn connection_pool(&self) -> &PgDbPool {
+    fn connection_pool(&self) -> &DbPool {
         &self.connection_pool
     }
 }
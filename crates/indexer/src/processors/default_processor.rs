@@ -3,7 +3,7 @@
 
 use crate::{
     database::{
-        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+        clean_data_for_db, execute_with_better_error, get_chunks, DbPool, DbPoolConnection,
     },
     indexer::{
         errors::TransactionProcessingError, processing_result::ProcessingResult,
@@ -24,17 +24,17 @@ use crate::{
 };
 use aptos_api_types::Transaction;
 use async_trait::async_trait;
-use diesel::{result::Error, PgConnection};
+use diesel::result::Error;
 use field_count::FieldCount;
 use std::fmt::Debug;
 
 pub const NAME: &str = "default_processor";
 pub struct DefaultTransactionProcessor {
-    connection_pool: PgDbPool,
+    connection_pool: DbPool,
 }
 
 impl DefaultTransactionProcessor {
-    pub fn new(connection_pool: PgDbPool) -> Self {
+    pub fn new(connection_pool: DbPool) -> Self {
         Self { connection_pool }
     }
 }
@@ -51,7 +51,7 @@ impl Debug for DefaultTransactionProcessor {
 }
 
 fn insert_to_db(
-    conn: &mut PgPoolConnection,
+    conn: &mut DbPoolConnection,
     name: &'static str,
     start_version: u64,
     end_version: u64,
@@ -67,46 +67,40 @@ fn insert_to_db(
         end_version = end_version,
         "Inserting to db",
     );
-    match conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|pg_conn| {
-            insert_transactions(pg_conn, &txns)?;
-            insert_user_transactions_w_sigs(pg_conn, &txn_details)?;
-            insert_block_metadata_transactions(pg_conn, &txn_details)?;
-            insert_events(pg_conn, &events)?;
-            insert_write_set_changes(pg_conn, &wscs)?;
-            insert_move_modules(pg_conn, &wsc_details)?;
-            insert_move_resources(pg_conn, &wsc_details)?;
-            insert_table_data(pg_conn, &wsc_details)?;
-            Ok(())
-        }) {
+    match conn.transaction::<_, Error, _>(|conn| {
+        insert_transactions(conn, &txns)?;
+        insert_user_transactions_w_sigs(conn, &txn_details)?;
+        insert_block_metadata_transactions(conn, &txn_details)?;
+        insert_events(conn, &events)?;
+        insert_write_set_changes(conn, &wscs)?;
+        insert_move_modules(conn, &wsc_details)?;
+        insert_move_resources(conn, &wsc_details)?;
+        insert_table_data(conn, &wsc_details)?;
+        Ok(())
+    }) {
         Ok(_) => Ok(()),
-        Err(_) => conn
-            .build_transaction()
-            .read_write()
-            .run::<_, Error, _>(|pg_conn| {
-                let txns = clean_data_for_db(txns, true);
-                let txn_details = clean_data_for_db(txn_details, true);
-                let events = clean_data_for_db(events, true);
-                let wscs = clean_data_for_db(wscs, true);
-                let wsc_details = clean_data_for_db(wsc_details, true);
+        Err(_) => conn.transaction::<_, Error, _>(|conn| {
+            let txns = clean_data_for_db(txns, true);
+            let txn_details = clean_data_for_db(txn_details, true);
+            let events = clean_data_for_db(events, true);
+            let wscs = clean_data_for_db(wscs, true);
+            let wsc_details = clean_data_for_db(wsc_details, true);
 
-                insert_transactions(pg_conn, &txns)?;
-                insert_user_transactions_w_sigs(pg_conn, &txn_details)?;
-                insert_block_metadata_transactions(pg_conn, &txn_details)?;
-                insert_events(pg_conn, &events)?;
-                insert_write_set_changes(pg_conn, &wscs)?;
-                insert_move_modules(pg_conn, &wsc_details)?;
-                insert_move_resources(pg_conn, &wsc_details)?;
-                insert_table_data(pg_conn, &wsc_details)?;
-                Ok(())
-            }),
+            insert_transactions(conn, &txns)?;
+            insert_user_transactions_w_sigs(conn, &txn_details)?;
+            insert_block_metadata_transactions(conn, &txn_details)?;
+            insert_events(conn, &events)?;
+            insert_write_set_changes(conn, &wscs)?;
+            insert_move_modules(conn, &wsc_details)?;
+            insert_move_resources(conn, &wsc_details)?;
+            insert_table_data(conn, &wsc_details)?;
+            Ok(())
+        }),
     }
 }
 
 fn insert_transactions(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     txns: &[TransactionModel],
 ) -> Result<(), diesel::result::Error> {
     use schema::transactions::dsl::*;
@@ -125,7 +119,7 @@ fn insert_transactions(
 }
 
 fn insert_user_transactions_w_sigs(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     txn_details: &[TransactionDetail],
 ) -> Result<(), diesel::result::Error> {
     use schema::{signatures::dsl as sig_schema, user_transactions::dsl as ut_schema};
@@ -171,7 +165,7 @@ fn insert_user_transactions_w_sigs(
 }
 
 fn insert_block_metadata_transactions(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     txn_details: &[TransactionDetail],
 ) -> Result<(), diesel::result::Error> {
     use schema::block_metadata_transactions::dsl::*;
@@ -198,7 +192,10 @@ fn insert_block_metadata_transactions(
     Ok(())
 }
 
-fn insert_events(conn: &mut PgConnection, ev: &[EventModel]) -> Result<(), diesel::result::Error> {
+fn insert_events(
+    conn: &mut DbPoolConnection,
+    ev: &[EventModel],
+) -> Result<(), diesel::result::Error> {
     use schema::events::dsl::*;
 
     let chunks = get_chunks(ev.len(), EventModel::field_count());
@@ -217,7 +214,7 @@ fn insert_events(conn: &mut PgConnection, ev: &[EventModel]) -> Result<(), diese
 }
 
 fn insert_write_set_changes(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     wscs: &[WriteSetChangeModel],
 ) -> Result<(), diesel::result::Error> {
     use schema::write_set_changes::dsl::*;
@@ -238,7 +235,7 @@ fn insert_write_set_changes(
 }
 
 fn insert_move_modules(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     wsc_details: &[WriteSetChangeDetail],
 ) -> Result<(), diesel::result::Error> {
     use schema::move_modules::dsl::*;
@@ -266,7 +263,7 @@ fn insert_move_modules(
 }
 
 fn insert_move_resources(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     wsc_details: &[WriteSetChangeDetail],
 ) -> Result<(), diesel::result::Error> {
     use schema::move_resources::dsl::*;
@@ -295,7 +292,7 @@ fn insert_move_resources(
 
 /// This will insert all table data within each transaction within a block
 fn insert_table_data(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     wsc_details: &[WriteSetChangeDetail],
 ) -> Result<(), diesel::result::Error> {
     use schema::{table_items::dsl as ti, table_metadatas::dsl as tm};
@@ -385,7 +382,7 @@ impl TransactionProcessor for DefaultTransactionProcessor {
         }
     }
 
-    fn connection_pool(&self) -> &PgDbPool {
+    fn connection_pool(&self) -> &DbPool {
         &self.connection_pool
     }
 }
@@ -3,7 +3,7 @@
 
 use crate::{
     database::{
-        clean_data_for_db, execute_with_better_error, get_chunks, PgDbPool, PgPoolConnection,
+        clean_data_for_db, execute_with_better_error, get_chunks, DbPool, DbPoolConnection,
     },
     indexer::{
         errors::TransactionProcessingError, processing_result::ProcessingResult,
@@ -17,17 +17,17 @@ use crate::{
 };
 use aptos_api_types::Transaction as APITransaction;
 use async_trait::async_trait;
-use diesel::{pg::upsert::excluded, result::Error, ExpressionMethods, PgConnection};
+use diesel::{result::Error, upsert::excluded, ExpressionMethods};
 use field_count::FieldCount;
 use std::{collections::HashMap, fmt::Debug};
 
 pub const NAME: &str = "stake_processor";
 pub struct StakeTransactionProcessor {
-    connection_pool: PgDbPool,
+    connection_pool: DbPool,
 }
 
 impl StakeTransactionProcessor {
-    pub fn new(connection_pool: PgDbPool) -> Self {
+    pub fn new(connection_pool: DbPool) -> Self {
         Self { connection_pool }
     }
 }
@@ -44,7 +44,7 @@ impl Debug for StakeTransactionProcessor {
 }
 
 fn insert_to_db_impl(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     current_stake_pool_voters: &[CurrentStakingPoolVoter],
     proposal_votes: &[ProposalVote],
 ) -> Result<(), diesel::result::Error> {
@@ -54,7 +54,7 @@ fn insert_to_db_impl(
 }
 
 fn insert_to_db(
-    conn: &mut PgPoolConnection,
+    conn: &mut DbPoolConnection,
     name: &'static str,
     start_version: u64,
     end_version: u64,
@@ -67,27 +67,21 @@ fn insert_to_db(
         end_version = end_version,
         "Inserting to db",
     );
-    match conn
-        .build_transaction()
-        .read_write()
-        .run::<_, Error, _>(|pg_conn| {
-            insert_to_db_impl(pg_conn, &current_stake_pool_voters, &proposal_votes)
-        }) {
+    match conn.transaction::<_, Error, _>(|conn| {
+        insert_to_db_impl(conn, &current_stake_pool_voters, &proposal_votes)
+    }) {
         Ok(_) => Ok(()),
-        Err(_) => conn
-            .build_transaction()
-            .read_write()
-            .run::<_, Error, _>(|pg_conn| {
-                let current_stake_pool_voters = clean_data_for_db(current_stake_pool_voters, true);
-                let proposal_votes = clean_data_for_db(proposal_votes, true);
-
-                insert_to_db_impl(pg_conn, &current_stake_pool_voters, &proposal_votes)
-            }),
+        Err(_) => conn.transaction::<_, Error, _>(|conn| {
+            let current_stake_pool_voters = clean_data_for_db(current_stake_pool_voters, true);
+            let proposal_votes = clean_data_for_db(proposal_votes, true);
+
+            insert_to_db_impl(conn, &current_stake_pool_voters, &proposal_votes)
+        }),
     }
 }
 
 fn insert_current_stake_pool_voter(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[CurrentStakingPoolVoter],
 ) -> Result<(), diesel::result::Error> {
     use schema::current_staking_pool_voter::dsl::*;
@@ -115,7 +109,7 @@ fn insert_current_stake_pool_voter(
 }
 
 fn insert_proposal_votes(
-    conn: &mut PgConnection,
+    conn: &mut DbPoolConnection,
     item_to_insert: &[ProposalVote],
 ) -> Result<(), diesel::result::Error> {
     use schema::proposal_votes::dsl::*;
@@ -187,7 +181,7 @@ impl TransactionProcessor for StakeTransactionProcessor {
         }
     }
 
-    fn connection_pool(&self) -> &PgDbPool {
+    fn connection_pool(&self) -> &DbPool {
         &self.connection_pool
     }
 }
@@ -0,0 +1,148 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{run_query, Page, QueryServerTags};
+use crate::{
+    database::DbPool,
+    models::coin_models::{
+        coin_activities::CoinActivityQuery, coin_balances::CurrentCoinBalanceQuery,
+    },
+    util::standardize_address,
+};
+use aptos_api_types::Address;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+    Object, OpenApi,
+};
+
+/// Queries over the tables written by the coin processor
+pub struct CoinsApi {
+    pub connection_pool: DbPool,
+}
+
+/// Current balance of a coin held by an account
+#[derive(Clone, Debug, Object)]
+pub struct CoinBalance {
+    pub owner_address: String,
+    pub coin_type: String,
+    /// Amount as a decimal string, since it may not fit in a u64
+    pub amount: String,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: String,
+}
+
+impl From<CurrentCoinBalanceQuery> for CoinBalance {
+    fn from(balance: CurrentCoinBalanceQuery) -> Self {
+        Self {
+            owner_address: balance.owner_address,
+            coin_type: balance.coin_type,
+            amount: balance.amount.to_string(),
+            last_transaction_version: balance.last_transaction_version,
+            last_transaction_timestamp: balance.last_transaction_timestamp.to_string(),
+        }
+    }
+}
+
+/// A change to the coin balance of an account, e.g. a deposit, a withdrawal or a gas fee
+#[derive(Clone, Debug, Object)]
+pub struct CoinActivity {
+    pub transaction_version: i64,
+    pub event_account_address: String,
+    pub event_creation_number: i64,
+    pub event_sequence_number: i64,
+    pub owner_address: String,
+    pub coin_type: String,
+    /// Amount as a decimal string, since it may not fit in a u64
+    pub amount: String,
+    pub activity_type: String,
+    pub is_gas_fee: bool,
+    pub is_transaction_success: bool,
+    pub entry_function_id_str: Option<String>,
+    pub block_height: i64,
+    pub transaction_timestamp: String,
+}
+
+impl From<CoinActivityQuery> for CoinActivity {
+    fn from(activity: CoinActivityQuery) -> Self {
+        Self {
+            transaction_version: activity.transaction_version,
+            event_account_address: activity.event_account_address,
+            event_creation_number: activity.event_creation_number,
+            event_sequence_number: activity.event_sequence_number,
+            owner_address: activity.owner_address,
+            coin_type: activity.coin_type,
+            amount: activity.amount.to_string(),
+            activity_type: activity.activity_type,
+            is_gas_fee: activity.is_gas_fee,
+            is_transaction_success: activity.is_transaction_success,
+            entry_function_id_str: activity.entry_function_id_str,
+            block_height: activity.block_height,
+            transaction_timestamp: activity.transaction_timestamp.to_string(),
+        }
+    }
+}
+
+#[OpenApi]
+impl CoinsApi {
+    /// Get coin balances
+    ///
+    /// Retrieves the current balance of every coin held by the account, ordered by coin type.
+    #[oai(
+        path = "/accounts/:address/coin_balances",
+        method = "get",
+        operation_id = "get_coin_balances",
+        tag = "QueryServerTags::Coins"
+    )]
+    async fn get_coin_balances(
+        &self,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Number of balances to skip, at most 10,000
+        offset: Query<Option<u64>>,
+        /// Max number of balances to retrieve
+        limit: Query<Option<u16>>,
+    ) -> poem::Result<Json<Vec<CoinBalance>>> {
+        let page = Page::new(offset.0, limit.0)?;
+        let owner_address = standardize_address(&address.0.to_string());
+        let balances = run_query(&self.connection_pool, move |conn| {
+            CurrentCoinBalanceQuery::get_by_owner_address(
+                &owner_address,
+                page.offset,
+                page.limit,
+                conn,
+            )
+        })
+        .await?;
+        Ok(Json(balances.into_iter().map(CoinBalance::from).collect()))
+    }
+
+    /// Get coin activities
+    ///
+    /// Retrieves the history of coin balance changes of the account, most recent first.
+    #[oai(
+        path = "/accounts/:address/coin_activities",
+        method = "get",
+        operation_id = "get_coin_activities",
+        tag = "QueryServerTags::Coins"
+    )]
+    async fn get_coin_activities(
+        &self,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Number of activities to skip, at most 10,000
+        offset: Query<Option<u64>>,
+        /// Max number of activities to retrieve
+        limit: Query<Option<u16>>,
+    ) -> poem::Result<Json<Vec<CoinActivity>>> {
+        let page = Page::new(offset.0, limit.0)?;
+        let owner_address = standardize_address(&address.0.to_string());
+        let activities = run_query(&self.connection_pool, move |conn| {
+            CoinActivityQuery::get_by_owner_address(&owner_address, page.offset, page.limit, conn)
+        })
+        .await?;
+        Ok(Json(
+            activities.into_iter().map(CoinActivity::from).collect(),
+        ))
+    }
+}
@@ -0,0 +1,379 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! An optional HTTP server to query the tables written by the processors, so the indexed data
+//! can be read without running a separate query layer on top of the database.
+
+mod coins;
+mod processors;
+mod tokens;
+
+pub use coins::CoinsApi;
+pub use processors::ProcessorsApi;
+pub use tokens::TokensApi;
+
+use crate::database::{DbPool, DbPoolConnection};
+use aptos_logger::{error, info};
+use poem::{
+    http::{header, Method, StatusCode},
+    listener::TcpListener,
+    middleware::Cors,
+    EndpointExt, Route, Server,
+};
+use poem_openapi::{OpenApiService, Tags};
+use std::net::SocketAddr;
+
+const DEFAULT_PAGE_SIZE: u16 = 25;
+const MAX_PAGE_SIZE: u16 = 100;
+/// Large offsets make the database scan and discard every skipped row, so they're rejected
+const MAX_OFFSET: u64 = 10_000;
+
+#[derive(Tags)]
+pub enum QueryServerTags {
+    /// Coin balances and activities
+    Coins,
+
+    /// Status of the processors
+    Processors,
+
+    /// Token ownership and collections
+    Tokens,
+}
+
+/// Generate the top level query service
+pub fn get_api_service(
+    connection_pool: DbPool,
+) -> OpenApiService<(CoinsApi, ProcessorsApi, TokensApi), ()> {
+    let apis = (
+        CoinsApi {
+            connection_pool: connection_pool.clone(),
+        },
+        ProcessorsApi {
+            connection_pool: connection_pool.clone(),
+        },
+        TokensApi { connection_pool },
+    );
+
+    OpenApiService::new(apis, "Aptos Indexer API", env!("CARGO_PKG_VERSION"))
+        .server("/v1")
+        .description("Read only access to the data indexed by the Aptos indexer processors.")
+}
+
+/// Serves the query API at `address` until the server fails
+pub async fn run(address: SocketAddr, connection_pool: DbPool) -> std::io::Result<()> {
+    let api_service = get_api_service(connection_pool);
+    let spec_json = api_service.spec_endpoint();
+    let spec_yaml = api_service.spec_endpoint_yaml();
+
+    let cors = Cors::new()
+        .allow_methods(vec![Method::GET])
+        .allow_headers(vec![header::CONTENT_TYPE, header::ACCEPT]);
+    let route = Route::new()
+        .nest(
+            "/v1",
+            Route::new()
+                .nest("/", api_service)
+                .at("/spec.json", spec_json)
+                .at("/spec.yaml", spec_yaml),
+        )
+        .with(cors);
+
+    info!("Starting indexer query server at {}", address);
+    Server::new(TcpListener::bind(address)).run(route).await
+}
+
+/// A page of results, given as an offset into the results and the number of results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Page {
+    pub offset: i64,
+    pub limit: i64,
+}
+
+impl Page {
+    /// Limits above `MAX_PAGE_SIZE` are capped rather than rejected, offsets above `MAX_OFFSET`
+    /// are rejected
+    pub fn new(offset: Option<u64>, limit: Option<u16>) -> poem::Result<Self> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit == 0 {
+            return Err(poem::Error::from_string(
+                "Given limit value (0) must not be zero",
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+        let offset = offset.unwrap_or(0);
+        if offset > MAX_OFFSET {
+            return Err(poem::Error::from_string(
+                format!(
+                    "Given offset value ({}) must not be larger than {}",
+                    offset, MAX_OFFSET
+                ),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+        Ok(Self {
+            offset: offset as i64,
+            limit: limit.min(MAX_PAGE_SIZE) as i64,
+        })
+    }
+}
+
+/// Logs the error and returns a generic one, so database details aren't exposed to clients
+fn internal_error<E: std::fmt::Debug>(message: &'static str, err: E) -> poem::Error {
+    error!(error =? err, "{}", message);
+    poem::Error::from_string(
+        "Failed to query the indexer database",
+        StatusCode::INTERNAL_SERVER_ERROR,
+    )
+}
+
+/// Runs a query on a blocking thread, since diesel connections are synchronous
+pub(crate) async fn run_query<T, F>(connection_pool: &DbPool, query: F) -> poem::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&mut DbPoolConnection) -> diesel::QueryResult<T> + Send + 'static,
+{
+    let connection_pool = connection_pool.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut conn = connection_pool
+            .get()
+            .map_err(|err| internal_error("Failed to get a database connection", err))?;
+        query(&mut conn).map_err(|err| internal_error("Failed to run database query", err))
+    })
+    .await
+    .map_err(|err| internal_error("Database query task failed", err))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::{new_db_pool, new_sqlite_db_pool},
+        indexer::tailer::{run_migrations, test::wipe_database},
+        models::{
+            coin_models::{coin_activities::CoinActivity, coin_balances::CurrentCoinBalance},
+            processor_statuses::ProcessorStatus,
+            token_models::{
+                collection_datas::CurrentCollectionData, token_ownerships::CurrentTokenOwnership,
+            },
+        },
+        schema::{
+            coin_activities, current_coin_balances, current_collection_datas,
+            current_token_ownerships, processor_statuses,
+        },
+        util::{parse_timestamp, standardize_address},
+        with_connection,
+    };
+    use aptos_temppath::TempPath;
+    use bigdecimal::BigDecimal;
+    use diesel::RunQueryDsl;
+    use poem::{Endpoint, Request};
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_query_server() {
+        if crate::should_skip_pg_tests() {
+            return;
+        }
+        let _pg_test_guard = crate::PG_TEST_LOCK.lock().await;
+        let database_url = std::env::var("INDEXER_DATABASE_URL").unwrap();
+        let conn_pool = new_db_pool(&database_url).unwrap();
+        wipe_database(&mut conn_pool.get().unwrap());
+        check_query_server(conn_pool).await;
+    }
+
+    #[tokio::test]
+    async fn test_query_server_sqlite() {
+        let path = TempPath::new();
+        let conn_pool = new_sqlite_db_pool(path.path().to_str().unwrap()).unwrap();
+        check_query_server(conn_pool).await;
+    }
+
+    /// Writes rows as the processors would and reads them back through the endpoints. Amounts
+    /// and property versions of different lengths check that numerics compare as numbers.
+    async fn check_query_server(conn_pool: DbPool) {
+        let mut conn = conn_pool.get().unwrap();
+        run_migrations(&mut conn);
+        insert_rows(&mut conn);
+        let route = Route::new().nest("/v1", get_api_service(conn_pool));
+
+        let balances = get(&route, "/v1/accounts/0x1/coin_balances").await;
+        assert_eq!(
+            field(&balances, "coin_type"),
+            vec!["0x1::a::A", "0x1::b::B"]
+        );
+        assert_eq!(field(&balances, "amount"), vec!["9", "10"]);
+        let balances = get(&route, "/v1/accounts/0x1/coin_balances?offset=1&limit=1").await;
+        assert_eq!(field(&balances, "coin_type"), vec!["0x1::b::B"]);
+        let resp = route
+            .get_response(request("/v1/accounts/0x1/coin_balances?limit=0"))
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let activities = get(&route, "/v1/accounts/0x1/coin_activities").await;
+        assert_eq!(field(&activities, "transaction_version"), vec![10, 9]);
+        assert_eq!(field(&activities, "amount"), vec!["10", "9"]);
+
+        // The token that was transferred away is no longer held
+        let tokens = get(&route, "/v1/accounts/0x1/tokens").await;
+        assert_eq!(field(&tokens, "name"), vec!["t", "t"]);
+        assert_eq!(field(&tokens, "property_version"), vec!["9", "10"]);
+
+        let collections = get(&route, "/v1/accounts/0x1/collections").await;
+        assert_eq!(field(&collections, "collection_name"), vec!["a", "b"]);
+        assert_eq!(
+            field(&collections, "maximum"),
+            vec![u64::MAX.to_string(), u64::MAX.to_string()]
+        );
+        let collections = get(&route, "/v1/accounts/0x1/collections?collection_name=b").await;
+        assert_eq!(field(&collections, "collection_name"), vec!["b"]);
+
+        let statuses = get(&route, "/v1/processors").await;
+        assert_eq!(field(&statuses, "processor"), vec!["coin_processor"]);
+        assert_eq!(field(&statuses, "latest_success_version"), vec![10]);
+    }
+
+    fn insert_rows(conn: &mut DbPoolConnection) {
+        let owner_address = standardize_address("0x1");
+        let other_address = standardize_address("0x2");
+        let timestamp = parse_timestamp(0, 0);
+
+        let coin_balance = |owner_address: &str, coin_type: &str, amount: u64| CurrentCoinBalance {
+            owner_address: owner_address.to_string(),
+            coin_type_hash: coin_type.to_string(),
+            coin_type: coin_type.to_string(),
+            amount: BigDecimal::from(amount),
+            last_transaction_version: 0,
+            last_transaction_timestamp: timestamp,
+        };
+        let coin_balances = vec![
+            coin_balance(&owner_address, "0x1::b::B", 10),
+            coin_balance(&owner_address, "0x1::a::A", 9),
+            coin_balance(&other_address, "0x1::a::A", 1),
+        ];
+        let coin_activity = |transaction_version: i64, amount: u64| CoinActivity {
+            transaction_version,
+            event_account_address: owner_address.clone(),
+            event_creation_number: 0,
+            event_sequence_number: transaction_version,
+            owner_address: owner_address.clone(),
+            coin_type: "0x1::a::A".to_string(),
+            amount: BigDecimal::from(amount),
+            activity_type: "0x1::coin::DepositEvent".to_string(),
+            is_gas_fee: false,
+            is_transaction_success: true,
+            entry_function_id_str: None,
+            block_height: transaction_version,
+            transaction_timestamp: timestamp,
+        };
+        let coin_activities = vec![coin_activity(9, 9), coin_activity(10, 10)];
+        let token_ownership =
+            |name: &str, property_version: u64, amount: u64| CurrentTokenOwnership {
+                token_data_id_hash: name.to_string(),
+                property_version: BigDecimal::from(property_version),
+                owner_address: owner_address.clone(),
+                creator_address: owner_address.clone(),
+                collection_name: "a".to_string(),
+                name: name.to_string(),
+                amount: BigDecimal::from(amount),
+                token_properties: json!({}),
+                last_transaction_version: 0,
+                collection_data_id_hash: "a".to_string(),
+                table_type: "0x3::token::TokenStore".to_string(),
+                last_transaction_timestamp: timestamp,
+            };
+        let token_ownerships = vec![
+            token_ownership("t", 10, 1),
+            token_ownership("t", 9, 1),
+            token_ownership("u", 0, 0),
+        ];
+        let collection_data = |collection_name: &str| CurrentCollectionData {
+            collection_data_id_hash: collection_name.to_string(),
+            creator_address: owner_address.clone(),
+            collection_name: collection_name.to_string(),
+            description: String::new(),
+            metadata_uri: String::new(),
+            supply: BigDecimal::from(2),
+            maximum: BigDecimal::from(u64::MAX),
+            maximum_mutable: false,
+            uri_mutable: false,
+            description_mutable: false,
+            last_transaction_version: 0,
+            table_handle: collection_name.to_string(),
+            last_transaction_timestamp: timestamp,
+        };
+        let collection_datas = vec![collection_data("b"), collection_data("a")];
+        let statuses = ProcessorStatus::from_versions("coin_processor", 0, 10, true, None);
+
+        with_connection!(conn, |conn| {
+            diesel::insert_into(current_coin_balances::table)
+                .values(&coin_balances)
+                .execute(conn)
+                .unwrap();
+            diesel::insert_into(coin_activities::table)
+                .values(&coin_activities)
+                .execute(conn)
+                .unwrap();
+            diesel::insert_into(current_token_ownerships::table)
+                .values(&token_ownerships)
+                .execute(conn)
+                .unwrap();
+            diesel::insert_into(current_collection_datas::table)
+                .values(&collection_datas)
+                .execute(conn)
+                .unwrap();
+            diesel::insert_into(processor_statuses::table)
+                .values(&statuses)
+                .execute(conn)
+                .unwrap();
+        });
+    }
+
+    fn request(uri: &str) -> Request {
+        Request::builder().uri(uri.parse().unwrap()).finish()
+    }
+
+    async fn get(route: &Route, uri: &str) -> Value {
+        let resp = route.get_response(request(uri)).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        resp.into_body().into_json().await.unwrap()
+    }
+
+    /// Values of the field `name` of each of the returned objects
+    fn field(values: &Value, name: &str) -> Vec<Value> {
+        values
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value[name].clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_page() {
+        assert_eq!(
+            Page::new(None, None).unwrap(),
+            Page {
+                offset: 0,
+                limit: DEFAULT_PAGE_SIZE as i64
+            }
+        );
+        assert_eq!(
+            Page::new(Some(50), Some(10)).unwrap(),
+            Page {
+                offset: 50,
+                limit: 10
+            }
+        );
+        assert_eq!(
+            Page::new(None, Some(MAX_PAGE_SIZE + 1)).unwrap().limit,
+            MAX_PAGE_SIZE as i64
+        );
+        assert!(Page::new(None, Some(0)).is_err());
+        assert_eq!(
+            Page::new(Some(MAX_OFFSET), None).unwrap().offset,
+            MAX_OFFSET as i64
+        );
+        assert!(Page::new(Some(MAX_OFFSET + 1), None).is_err());
+        assert!(Page::new(Some(u64::MAX), None).is_err());
+    }
+}
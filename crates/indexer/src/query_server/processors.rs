@@ -0,0 +1,53 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{run_query, QueryServerTags};
+use crate::{database::DbPool, models::processor_statuses::ProcessorStatusSummary};
+use poem_openapi::{payload::Json, Object, OpenApi};
+
+/// Status of the processors writing to the database
+pub struct ProcessorsApi {
+    pub connection_pool: DbPool,
+}
+
+/// Progress of a processor
+#[derive(Clone, Debug, Object)]
+pub struct ProcessorStatus {
+    pub processor: String,
+    /// Latest version the processor has successfully processed. Earlier versions may still have
+    /// gaps that are filled in when the processor restarts.
+    pub latest_success_version: Option<i64>,
+    pub last_updated: Option<String>,
+}
+
+impl From<ProcessorStatusSummary> for ProcessorStatus {
+    fn from(summary: ProcessorStatusSummary) -> Self {
+        Self {
+            processor: summary.name,
+            latest_success_version: summary.latest_success_version,
+            last_updated: summary
+                .last_updated
+                .map(|last_updated| last_updated.to_string()),
+        }
+    }
+}
+
+#[OpenApi]
+impl ProcessorsApi {
+    /// Get processor status
+    ///
+    /// Retrieves the latest version successfully processed by each processor writing to this
+    /// database. Use it to know how far behind the chain the indexed data is.
+    #[oai(
+        path = "/processors",
+        method = "get",
+        operation_id = "get_processor_status",
+        tag = "QueryServerTags::Processors"
+    )]
+    async fn get_processor_status(&self) -> poem::Result<Json<Vec<ProcessorStatus>>> {
+        let statuses = run_query(&self.connection_pool, ProcessorStatusSummary::get_all).await?;
+        Ok(Json(
+            statuses.into_iter().map(ProcessorStatus::from).collect(),
+        ))
+    }
+}
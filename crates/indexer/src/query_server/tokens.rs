@@ -0,0 +1,172 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{run_query, Page, QueryServerTags};
+use crate::{
+    database::DbPool,
+    models::token_models::{
+        collection_datas::CurrentCollectionDataQuery, token_ownerships::CurrentTokenOwnershipQuery,
+    },
+    util::standardize_address,
+};
+use aptos_api_types::Address;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+    Object, OpenApi,
+};
+
+/// Queries over the tables written by the token processor
+pub struct TokensApi {
+    pub connection_pool: DbPool,
+}
+
+/// A token currently held by an account
+#[derive(Clone, Debug, Object)]
+pub struct TokenOwnership {
+    pub token_data_id_hash: String,
+    pub property_version: String,
+    pub owner_address: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub name: String,
+    /// Amount as a decimal string, since it may not fit in a u64
+    pub amount: String,
+    pub token_properties: serde_json::Value,
+    pub collection_data_id_hash: String,
+    pub table_type: String,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: String,
+}
+
+impl From<CurrentTokenOwnershipQuery> for TokenOwnership {
+    fn from(ownership: CurrentTokenOwnershipQuery) -> Self {
+        Self {
+            token_data_id_hash: ownership.token_data_id_hash,
+            property_version: ownership.property_version.to_string(),
+            owner_address: ownership.owner_address,
+            creator_address: ownership.creator_address,
+            collection_name: ownership.collection_name,
+            name: ownership.name,
+            amount: ownership.amount.to_string(),
+            token_properties: ownership.token_properties,
+            collection_data_id_hash: ownership.collection_data_id_hash,
+            table_type: ownership.table_type,
+            last_transaction_version: ownership.last_transaction_version,
+            last_transaction_timestamp: ownership.last_transaction_timestamp.to_string(),
+        }
+    }
+}
+
+/// Current state of a token collection
+#[derive(Clone, Debug, Object)]
+pub struct CollectionData {
+    pub collection_data_id_hash: String,
+    pub creator_address: String,
+    pub collection_name: String,
+    pub description: String,
+    pub metadata_uri: String,
+    pub supply: String,
+    pub maximum: String,
+    pub maximum_mutable: bool,
+    pub uri_mutable: bool,
+    pub description_mutable: bool,
+    pub table_handle: String,
+    pub last_transaction_version: i64,
+    pub last_transaction_timestamp: String,
+}
+
+impl From<CurrentCollectionDataQuery> for CollectionData {
+    fn from(collection: CurrentCollectionDataQuery) -> Self {
+        Self {
+            collection_data_id_hash: collection.collection_data_id_hash,
+            creator_address: collection.creator_address,
+            collection_name: collection.collection_name,
+            description: collection.description,
+            metadata_uri: collection.metadata_uri,
+            supply: collection.supply.to_string(),
+            maximum: collection.maximum.to_string(),
+            maximum_mutable: collection.maximum_mutable,
+            uri_mutable: collection.uri_mutable,
+            description_mutable: collection.description_mutable,
+            table_handle: collection.table_handle,
+            last_transaction_version: collection.last_transaction_version,
+            last_transaction_timestamp: collection.last_transaction_timestamp.to_string(),
+        }
+    }
+}
+
+#[OpenApi]
+impl TokensApi {
+    /// Get tokens
+    ///
+    /// Retrieves the tokens currently held by the account.
+    #[oai(
+        path = "/accounts/:address/tokens",
+        method = "get",
+        operation_id = "get_tokens",
+        tag = "QueryServerTags::Tokens"
+    )]
+    async fn get_tokens(
+        &self,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Number of tokens to skip, at most 10,000
+        offset: Query<Option<u64>>,
+        /// Max number of tokens to retrieve
+        limit: Query<Option<u16>>,
+    ) -> poem::Result<Json<Vec<TokenOwnership>>> {
+        let page = Page::new(offset.0, limit.0)?;
+        let owner_address = standardize_address(&address.0.to_string());
+        let ownerships = run_query(&self.connection_pool, move |conn| {
+            CurrentTokenOwnershipQuery::get_by_owner_address(
+                &owner_address,
+                page.offset,
+                page.limit,
+                conn,
+            )
+        })
+        .await?;
+        Ok(Json(
+            ownerships.into_iter().map(TokenOwnership::from).collect(),
+        ))
+    }
+
+    /// Get collections
+    ///
+    /// Retrieves the collections created by the account, ordered by name.
+    #[oai(
+        path = "/accounts/:address/collections",
+        method = "get",
+        operation_id = "get_collections",
+        tag = "QueryServerTags::Tokens"
+    )]
+    async fn get_collections(
+        &self,
+        /// Address of the creator with or without a `0x` prefix
+        address: Path<Address>,
+        /// If set, only the collection with this name is returned
+        collection_name: Query<Option<String>>,
+        /// Number of collections to skip, at most 10,000
+        offset: Query<Option<u64>>,
+        /// Max number of collections to retrieve
+        limit: Query<Option<u16>>,
+    ) -> poem::Result<Json<Vec<CollectionData>>> {
+        let page = Page::new(offset.0, limit.0)?;
+        let creator_address = standardize_address(&address.0.to_string());
+        let collection_name = collection_name.0;
+        let collections = run_query(&self.connection_pool, move |conn| {
+            CurrentCollectionDataQuery::get_by_creator_address(
+                conn,
+                &creator_address,
+                collection_name.as_deref(),
+                page.offset,
+                page.limit,
+            )
+        })
+        .await?;
+        Ok(Json(
+            collections.into_iter().map(CollectionData::from).collect(),
+        ))
+    }
+}
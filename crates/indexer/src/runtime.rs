@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    database::new_db_pool,
+    database::{new_db_pool, new_sqlite_db_pool},
     indexer::{
         fetcher::TransactionFetcherOptions, processing_result::ProcessingResult, tailer::Tailer,
         transaction_processor::TransactionProcessor,
@@ -12,6 +12,7 @@ use crate::{
         stake_processor::StakeTransactionProcessor, token_processor::TokenTransactionProcessor,
         Processor,
     },
+    query_server,
 };
 
 use aptos_api::context::Context;
@@ -117,17 +118,29 @@ pub async fn run_forever(config: IndexerConfig, context: Arc<Context>) {
 
     info!(processor_name = processor_name, "Starting indexer...");
 
-    let db_uri = &config.postgres_uri.unwrap();
     info!(
         processor_name = processor_name,
         "Creating connection pool..."
     );
-    let conn_pool = new_db_pool(db_uri).expect("Failed to create connection pool");
+    let conn_pool = match &config.sqlite_path {
+        Some(sqlite_path) => new_sqlite_db_pool(sqlite_path),
+        None => new_db_pool(&config.postgres_uri.unwrap()),
+    }
+    .expect("Failed to create connection pool");
     info!(
         processor_name = processor_name,
         "Created the connection pool... "
     );
 
+    if let Some(query_server_address) = config.query_server_address {
+        let conn_pool = conn_pool.clone();
+        tokio::spawn(async move {
+            if let Err(err) = query_server::run(query_server_address, conn_pool).await {
+                error!("Indexer query server failed: {:?}", err);
+            }
+        });
+    }
+
     info!(processor_name = processor_name, "Instantiating tailer... ");
 
     let processor_enum = Processor::from_string(&processor_name);
@@ -0,0 +1,77 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! SQL types that are stored differently depending on the database backend.
+//! Postgres uses its native `NUMERIC` and `JSONB` types. SQLite has neither a lossless numeric
+//! type nor a JSON type, so both are stored as `TEXT` there, see [`sqlite`].
+#![allow(clippy::extra_unused_lifetimes)]
+use bigdecimal::BigDecimal;
+use diesel::{
+    deserialize::{self, FromSql},
+    expression::AsExpression,
+    internal::derives::as_expression::Bound,
+    pg::{Pg, PgValue},
+    serialize::{self, Output, ToSql},
+    sql_types,
+};
+
+pub mod sqlite;
+
+#[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+#[diesel(postgres_type(oid = 1700, array_oid = 1231))]
+#[diesel(sqlite_type(name = "Text"))]
+pub struct Numeric;
+
+#[derive(Clone, Copy, Debug, Default, QueryId, SqlType)]
+#[diesel(postgres_type(oid = 3802, array_oid = 3807))]
+#[diesel(sqlite_type(name = "Text"))]
+pub struct Jsonb;
+
+/// Implements what `#[derive(AsExpression)]` would for a type we don't own. Nullable columns
+/// are covered by diesel's blanket implementations for `Option`.
+macro_rules! impl_as_expression {
+    ($rust_type:ty, $sql_type:ty) => {
+        impl AsExpression<$sql_type> for $rust_type {
+            type Expression = Bound<$sql_type, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+
+        impl<'a> AsExpression<$sql_type> for &'a $rust_type {
+            type Expression = Bound<$sql_type, Self>;
+
+            fn as_expression(self) -> Self::Expression {
+                Bound::new(self)
+            }
+        }
+    };
+}
+
+impl_as_expression!(BigDecimal, Numeric);
+impl_as_expression!(serde_json::Value, Jsonb);
+
+impl ToSql<Numeric, Pg> for BigDecimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<sql_types::Numeric, Pg>::to_sql(self, out)
+    }
+}
+
+impl FromSql<Numeric, Pg> for BigDecimal {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        FromSql::<sql_types::Numeric, Pg>::from_sql(bytes)
+    }
+}
+
+impl ToSql<Jsonb, Pg> for serde_json::Value {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+        ToSql::<sql_types::Jsonb, Pg>::to_sql(self, out)
+    }
+}
+
+impl FromSql<Jsonb, Pg> for serde_json::Value {
+    fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+        FromSql::<sql_types::Jsonb, Pg>::from_sql(bytes)
+    }
+}
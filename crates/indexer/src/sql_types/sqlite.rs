@@ -0,0 +1,220 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! SQLite representations of [`Numeric`] and [`Jsonb`], both stored as `TEXT`.
+//!
+//! SQLite's `INTEGER` only holds an `i64`, which is too small for u64 amounts, let alone u128
+//! supplies, so numerics are stored as decimal strings that compare and sort as numbers do:
+//! - The integer part is zero padded to [`SQLITE_NUMERIC_DIGITS`] digits, followed by the
+//!   fractional part without trailing zeros, e.g. `0…012.5`.
+//! - Negative values are prefixed with `-`, which sorts before any digit, have every digit
+//!   replaced by its nines' complement so that larger magnitudes sort first, and are terminated
+//!   by `~`, which sorts after any digit or `.`, e.g. `-9…987.4~` for `-12.5`.
+#![allow(clippy::extra_unused_lifetimes)]
+use super::{Jsonb, Numeric};
+use bigdecimal::{BigDecimal, Signed};
+use diesel::{
+    deserialize::{self, FromSql},
+    serialize::{self, IsNull, Output, ToSql},
+    sql_types,
+    sqlite::{Sqlite, SqliteValue},
+};
+use std::str::FromStr;
+
+/// Number of digits the integer part of numerics is padded to, enough for any u128
+const SQLITE_NUMERIC_DIGITS: usize = 40;
+const NEGATIVE_PREFIX: char = '-';
+const NEGATIVE_SUFFIX: char = '~';
+
+/// Replaces every digit by its nines' complement, leaving the decimal point as it is.
+fn nines_complement(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(digit) => char::from_digit(9 - digit, 10).unwrap(),
+            None => c,
+        })
+        .collect()
+}
+
+fn encode_numeric(value: &BigDecimal) -> Result<String, String> {
+    let magnitude = value.abs().to_string();
+    let (integer, fraction) = match magnitude.split_once('.') {
+        Some((integer, fraction)) => (integer, fraction.trim_end_matches('0')),
+        None => (magnitude.as_str(), ""),
+    };
+    if integer.len() > SQLITE_NUMERIC_DIGITS {
+        return Err(format!(
+            "Numerics stored on SQLite have at most {} integer digits: {}",
+            SQLITE_NUMERIC_DIGITS, value
+        ));
+    }
+    let mut encoded = format!("{:0>width$}", integer, width = SQLITE_NUMERIC_DIGITS);
+    if !fraction.is_empty() {
+        encoded.push('.');
+        encoded.push_str(fraction);
+    }
+    if value.is_negative() {
+        encoded = format!(
+            "{}{}{}",
+            NEGATIVE_PREFIX,
+            nines_complement(&encoded),
+            NEGATIVE_SUFFIX
+        );
+    }
+    Ok(encoded)
+}
+
+fn decode_numeric(value: &str) -> Result<BigDecimal, String> {
+    match value.strip_prefix(NEGATIVE_PREFIX) {
+        Some(negative) => {
+            let magnitude = negative
+                .strip_suffix(NEGATIVE_SUFFIX)
+                .ok_or_else(|| format!("Malformed negative numeric on SQLite: {}", value))?;
+            BigDecimal::from_str(&nines_complement(magnitude))
+                .map(|magnitude| -magnitude)
+                .map_err(|err| err.to_string())
+        }
+        None => BigDecimal::from_str(value).map_err(|err| err.to_string()),
+    }
+}
+
+impl ToSql<Numeric, Sqlite> for BigDecimal {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(encode_numeric(self)?);
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Numeric, Sqlite> for BigDecimal {
+    fn from_sql(bytes: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<sql_types::Text, Sqlite>>::from_sql(bytes)?;
+        Ok(decode_numeric(&value)?)
+    }
+}
+
+impl ToSql<Jsonb, Sqlite> for serde_json::Value {
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> serialize::Result {
+        out.set_value(self.to_string());
+        Ok(IsNull::No)
+    }
+}
+
+impl FromSql<Jsonb, Sqlite> for serde_json::Value {
+    fn from_sql(bytes: SqliteValue<'_, '_, '_>) -> deserialize::Result<Self> {
+        let value = <String as FromSql<sql_types::Text, Sqlite>>::from_sql(bytes)?;
+        Ok(serde_json::from_str(&value)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use diesel::{
+        sqlite::SqliteConnection, Connection, ExpressionMethods, IntoSql, QueryDsl, RunQueryDsl,
+    };
+
+    diesel::table! {
+        use crate::sql_types::Numeric;
+
+        numerics (value) {
+            value -> Numeric,
+        }
+    }
+
+    fn numerics_in_order() -> Vec<BigDecimal> {
+        [
+            format!("-{}", u128::MAX).as_str(),
+            "-1000",
+            "-12.5",
+            "-1.5",
+            "-1.25",
+            "-1",
+            "-0.5",
+            "-0.05",
+            "0",
+            "0.05",
+            "0.5",
+            "1",
+            "1.05",
+            "1.25",
+            "1.5",
+            "12.5",
+            "1000",
+            u128::MAX.to_string().as_str(),
+        ]
+        .into_iter()
+        .map(|value| BigDecimal::from_str(value).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn test_sqlite_numeric_round_trip() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        for value in numerics_in_order() {
+            let stored: BigDecimal = diesel::select(value.clone().into_sql::<Numeric>())
+                .get_result(&mut conn)
+                .unwrap();
+            assert_eq!(stored, value);
+        }
+    }
+
+    #[test]
+    fn test_sqlite_numeric_compares_as_number() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        for (lower, higher) in [
+            ("9", "10"),
+            ("-10", "-9"),
+            ("-1.5", "-1.25"),
+            ("1.25", "1.5"),
+        ] {
+            let greater: bool = diesel::select(
+                BigDecimal::from_str(higher)
+                    .unwrap()
+                    .into_sql::<Numeric>()
+                    .gt(BigDecimal::from_str(lower).unwrap().into_sql::<Numeric>()),
+            )
+            .get_result(&mut conn)
+            .unwrap();
+            assert!(greater, "{} should be greater than {}", higher, lower);
+        }
+    }
+
+    #[test]
+    fn test_sqlite_numeric_sorts_as_number() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query("CREATE TABLE numerics (value TEXT PRIMARY KEY NOT NULL)")
+            .execute(&mut conn)
+            .unwrap();
+        let expected = numerics_in_order();
+        // Insert out of order, so that the result isn't just the insertion order.
+        let (first_half, second_half) = expected.split_at(expected.len() / 2);
+        for value in second_half.iter().chain(first_half.iter().rev()) {
+            diesel::insert_into(numerics::table)
+                .values(numerics::value.eq(value))
+                .execute(&mut conn)
+                .unwrap();
+        }
+
+        let sorted: Vec<BigDecimal> = numerics::table
+            .select(numerics::value)
+            .order(numerics::value.asc())
+            .load(&mut conn)
+            .unwrap();
+        assert_eq!(sorted, expected);
+    }
+
+    #[test]
+    fn test_sqlite_numeric_rejects_too_many_digits() {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        for value in [
+            "9".repeat(SQLITE_NUMERIC_DIGITS + 1),
+            format!("-{}", "9".repeat(SQLITE_NUMERIC_DIGITS + 1)),
+        ] {
+            let value = BigDecimal::from_str(&value).unwrap();
+            assert!(diesel::select(value.into_sql::<Numeric>())
+                .get_result::<BigDecimal>(&mut conn)
+                .is_err());
+        }
+    }
+}
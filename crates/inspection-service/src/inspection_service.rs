@@ -4,6 +4,7 @@
 use crate::{gather_metrics, json_encoder::JsonEncoder, NUM_METRICS};
 use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
+use consensus_types::evidence::EvidenceReader;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
@@ -85,6 +86,7 @@ pub fn get_all_metrics() -> HashMap<String, String> {
 async fn serve_requests(
     req: Request<Body>,
     node_config: NodeConfig,
+    evidence_reader: EvidenceReader,
 ) -> Result<Response<Body>, hyper::Error> {
     let mut resp = Response::new(Body::empty());
     match (req.method(), req.uri().path()) {
@@ -100,6 +102,25 @@ async fn serve_requests(
                 *resp.body_mut() = Body::from(DISABLED_ENDPOINT_MESSAGE);
             }
         }
+        // Expose the evidence of misbehaving validators recorded by consensus
+        (&Method::GET, "/consensus_evidence") => {
+            if node_config.inspection_service.expose_consensus_evidence {
+                let encoded_evidence = evidence_reader
+                    .get_evidence()
+                    .and_then(|evidence| serde_json::to_string(&evidence).map_err(Into::into));
+                match encoded_evidence {
+                    Ok(encoded_evidence) => {
+                        *resp.body_mut() = Body::from(encoded_evidence);
+                    }
+                    Err(error) => {
+                        *resp.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                        *resp.body_mut() = Body::from(error.to_string());
+                    }
+                }
+            } else {
+                *resp.body_mut() = Body::from(DISABLED_ENDPOINT_MESSAGE);
+            }
+        }
         // Exposes JSON encoded metrics
         (&Method::GET, "/json_metrics") => {
             let encoder = JsonEncoder;
@@ -139,7 +160,7 @@ async fn serve_requests(
     Ok(resp)
 }
 
-pub fn start_inspection_service(node_config: NodeConfig) {
+pub fn start_inspection_service(node_config: NodeConfig, evidence_reader: EvidenceReader) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
     let service_address = node_config.inspection_service.address.clone();
@@ -160,9 +181,10 @@ pub fn start_inspection_service(node_config: NodeConfig) {
     thread::spawn(move || {
         let make_service = make_service_fn(move |_conn| {
             let node_config = node_config.clone();
+            let evidence_reader = evidence_reader.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
-                    serve_requests(request, node_config.clone())
+                    serve_requests(request, node_config.clone(), evidence_reader.clone())
                 }))
             }
         });
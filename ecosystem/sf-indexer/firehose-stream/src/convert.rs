@@ -2,12 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_api_types::{
-    AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId, Event,
-    GenesisPayload, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
-    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveScriptBytecode,
-    MoveStruct, MoveStructField, MoveStructTag, MoveType, MultiEd25519Signature, ScriptPayload,
-    Transaction, TransactionInfo, TransactionPayload, TransactionSignature, WriteSet,
-    WriteSetChange,
+    AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId,
+    EntryFunctionPayload, Event, GenesisPayload, MoveAbility, MoveFunction,
+    MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
+    MoveModuleId, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType,
+    MultiEd25519Signature, MultisigTransactionPayload, ScriptPayload, Transaction, TransactionInfo,
+    TransactionPayload, TransactionSignature, WriteSet, WriteSetChange,
 };
 use aptos_bitvec::BitVec;
 use aptos_logger::warn;
@@ -146,15 +146,7 @@ pub fn convert_transaction_payload(payload: &TransactionPayload) -> extractor::T
             r#type: extractor::transaction_payload::Type::EntryFunctionPayload as i32,
             payload: Some(
                 extractor::transaction_payload::Payload::EntryFunctionPayload(
-                    extractor::EntryFunctionPayload {
-                        function: Some(convert_entry_function_id(&sfp.function)),
-                        type_arguments: sfp.type_arguments.iter().map(convert_move_type).collect(),
-                        arguments: sfp
-                            .arguments
-                            .iter()
-                            .map(|move_value| move_value.to_string())
-                            .collect(),
-                    },
+                    convert_entry_function_payload(sfp),
                 ),
             ),
         },
@@ -178,6 +170,35 @@ pub fn convert_transaction_payload(payload: &TransactionPayload) -> extractor::T
                 ),
             ),
         },
+        // The extractor protos don't model multisig transactions yet, so only the entry function
+        // being executed on behalf of the multisig account (if provided) is streamed.
+        TransactionPayload::MultisigPayload(mp) => extractor::TransactionPayload {
+            r#type: extractor::transaction_payload::Type::EntryFunctionPayload as i32,
+            payload: mp
+                .transaction_payload
+                .as_ref()
+                .map(|payload| match payload {
+                    MultisigTransactionPayload::EntryFunctionPayload(sfp) => {
+                        extractor::transaction_payload::Payload::EntryFunctionPayload(
+                            convert_entry_function_payload(sfp),
+                        )
+                    }
+                }),
+        },
+    }
+}
+
+pub fn convert_entry_function_payload(
+    sfp: &EntryFunctionPayload,
+) -> extractor::EntryFunctionPayload {
+    extractor::EntryFunctionPayload {
+        function: Some(convert_entry_function_id(&sfp.function)),
+        type_arguments: sfp.type_arguments.iter().map(convert_move_type).collect(),
+        arguments: sfp
+            .arguments
+            .iter()
+            .map(|move_value| move_value.to_string())
+            .collect(),
     }
 }
 
@@ -538,7 +559,8 @@ pub fn convert_transaction_signature(
     let r#type = match signature {
         TransactionSignature::Ed25519Signature(_) => extractor::signature::Type::Ed25519,
         TransactionSignature::MultiEd25519Signature(_) => extractor::signature::Type::MultiEd25519,
-        TransactionSignature::MultiAgentSignature(_) => extractor::signature::Type::MultiAgent,
+        TransactionSignature::MultiAgentSignature(_)
+        | TransactionSignature::FeePayerSignature(_) => extractor::signature::Type::MultiAgent,
     };
 
     let signature = match signature {
@@ -563,6 +585,25 @@ pub fn convert_transaction_signature(
                     .collect(),
             })
         }
+        // The extractor protos don't model fee payers yet, so the fee payer is streamed as the
+        // last secondary signer of a multi agent signature.
+        TransactionSignature::FeePayerSignature(s) => {
+            extractor::signature::Signature::MultiAgent(extractor::MultiAgentSignature {
+                sender: Some(convert_account_signature(&s.sender)),
+                secondary_signer_addresses: s
+                    .secondary_signer_addresses
+                    .iter()
+                    .chain(std::iter::once(&s.fee_payer_address))
+                    .map(|s| s.to_string())
+                    .collect(),
+                secondary_signers: s
+                    .secondary_signers
+                    .iter()
+                    .chain(std::iter::once(&s.fee_payer_signer))
+                    .map(convert_account_signature)
+                    .collect(),
+            })
+        }
     };
 
     Some(extractor::Signature {
@@ -61,6 +61,7 @@ impl PrunerOpt {
                 prune_window: self.ledger_prune_window,
                 batch_size: self.ledger_pruning_batch_size,
                 user_pruning_window_offset: 0,
+                enable_archive: false,
             },
         }
     }
@@ -189,6 +189,11 @@ pub fn update_counters_for_processed_chunk(
                         .with_label_values(&[process_type, "module", state])
                         .inc();
                 }
+                aptos_types::transaction::TransactionPayload::Multisig(_) => {
+                    metrics::APTOS_PROCESSED_USER_TRANSACTIONS_PAYLOAD_TYPE
+                        .with_label_values(&[process_type, "multisig", state])
+                        .inc();
+                }
                 aptos_types::transaction::TransactionPayload::EntryFunction(function) => {
                     metrics::APTOS_PROCESSED_USER_TRANSACTIONS_PAYLOAD_TYPE
                         .with_label_values(&[process_type, "function", state])
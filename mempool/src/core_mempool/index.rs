@@ -65,6 +65,11 @@ impl PriorityIndex {
         self.data.iter().rev()
     }
 
+    /// Iterates over the queue starting from the lowest gas ranking score.
+    pub(crate) fn iter_lowest(&self) -> Iter<OrderedQueueKey> {
+        self.data.iter()
+    }
+
     pub(crate) fn size(&self) -> usize {
         self.data.len()
     }
@@ -0,0 +1,129 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! The mempool journal is an optional on-disk record of the transactions accepted into mempool.
+//! It is replayed on startup so that pending transactions survive a node restart.
+
+mod schema;
+
+pub use schema::JournalEntry;
+
+use crate::{
+    core_mempool::{index::TxnPointer, transaction::TimelineState},
+    counters,
+    logging::{LogEntry, LogSchema},
+};
+use anyhow::Result;
+use aptos_logger::prelude::*;
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use schema::{JournalSchema, JOURNAL_CF_NAME};
+use schemadb::{Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// The name of the mempool journal db file
+pub const MEMPOOL_JOURNAL_NAME: &str = "mempool_journal";
+
+pub struct MempoolJournal {
+    db: DB,
+}
+
+impl MempoolJournal {
+    pub fn new<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        let column_families = vec![
+            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+            JOURNAL_CF_NAME,
+        ];
+
+        let path = db_root_path.as_ref().join(MEMPOOL_JOURNAL_NAME);
+        let instant = Instant::now();
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open(path.clone(), "mempool_journal", column_families, &opts)?;
+
+        info!(
+            "Opened MempoolJournal at {:?} in {} ms",
+            path,
+            instant.elapsed().as_millis()
+        );
+
+        Ok(Self { db })
+    }
+
+    /// Records a transaction that was accepted into mempool.
+    pub(crate) fn insert(
+        &self,
+        txn: &SignedTransaction,
+        timeline_state: TimelineState,
+        expiration_time: Duration,
+    ) {
+        let entry = JournalEntry {
+            txn: txn.clone(),
+            timeline_state,
+            expiration_time,
+        };
+        let result = self
+            .db
+            .put::<JournalSchema>(&(txn.sender(), txn.sequence_number()), &entry);
+        self.log_if_error(result);
+    }
+
+    /// Removes the given transactions from the journal.
+    pub(crate) fn remove(&self, txns: &[TxnPointer]) {
+        if txns.is_empty() {
+            return;
+        }
+        let batch = SchemaBatch::new();
+        let result = txns
+            .iter()
+            .try_for_each(|key| batch.delete::<JournalSchema>(key))
+            .and_then(|_| self.db.write_schemas(batch));
+        self.log_if_error(result);
+    }
+
+    /// Removes all transactions of `sender` with a sequence number lower than
+    /// `sequence_number`, i.e. the ones that can no longer be committed.
+    pub(crate) fn remove_committed(&self, sender: &AccountAddress, sequence_number: u64) {
+        match self.committed_keys(sender, sequence_number) {
+            Ok(keys) => self.remove(&keys),
+            Err(err) => self.log_if_error(Err(err)),
+        }
+    }
+
+    /// Returns all the transactions recorded in the journal.
+    pub(crate) fn get_all(&self) -> Result<Vec<JournalEntry>> {
+        let mut iter = self.db.iter::<JournalSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        iter.map(|item| item.map(|(_, entry)| entry)).collect()
+    }
+
+    fn committed_keys(
+        &self,
+        sender: &AccountAddress,
+        sequence_number: u64,
+    ) -> Result<Vec<TxnPointer>> {
+        let mut iter = self.db.iter::<JournalSchema>(ReadOptions::default())?;
+        iter.seek(&(*sender, 0))?;
+        let mut keys = vec![];
+        for item in iter {
+            let (key, _) = item?;
+            if key.0 != *sender || key.1 >= sequence_number {
+                break;
+            }
+            keys.push(key);
+        }
+        Ok(keys)
+    }
+
+    fn log_if_error(&self, result: Result<()>) {
+        // Failing to update the journal only affects transactions after a restart, so it
+        // shouldn't take down mempool.
+        if let Err(err) = result {
+            error!(LogSchema::new(LogEntry::DBError).error(&err));
+            counters::DB_ERROR.inc();
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the mempool journal.
+//!
+//! Transactions accepted into mempool identified by sender and sequence number.
+//! ```text
+//! |<--------key-------->|<-----value----->|
+//! | sender | seq_number |  journal entry  |
+//! ```
+//!
+//! `seq_number` is serialized in big endian so that the entries of a single sender are sorted
+//! by sequence number.
+
+use crate::core_mempool::transaction::TimelineState;
+use anyhow::{ensure, Result};
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use schemadb::{
+    schema::{KeyCodec, Schema, ValueCodec},
+    ColumnFamilyName,
+};
+use serde::{Deserialize, Serialize};
+use std::{mem::size_of, time::Duration};
+
+pub(super) const JOURNAL_CF_NAME: ColumnFamilyName = "mempool_journal";
+
+/// A transaction recorded in the journal, together with the mempool metadata needed to add it
+/// back on startup.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JournalEntry {
+    pub txn: SignedTransaction,
+    pub timeline_state: TimelineState,
+    // Time since the unix epoch at which the transaction expires from mempool by system TTL.
+    // It is kept when the transaction is restored, so restarts don't extend its lifetime.
+    pub expiration_time: Duration,
+}
+
+#[derive(Debug)]
+pub(crate) struct JournalSchema;
+
+impl Schema for JournalSchema {
+    const COLUMN_FAMILY_NAME: ColumnFamilyName = JOURNAL_CF_NAME;
+    type Key = (AccountAddress, u64);
+    type Value = JournalEntry;
+}
+
+impl KeyCodec<JournalSchema> for (AccountAddress, u64) {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.0.to_vec();
+        encoded.extend_from_slice(&self.1.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == AccountAddress::LENGTH + size_of::<u64>(),
+            "Unexpected data len {}, expected {}.",
+            data.len(),
+            AccountAddress::LENGTH + size_of::<u64>(),
+        );
+        let sender = AccountAddress::from_bytes(&data[..AccountAddress::LENGTH])?;
+        let sequence_number = u64::from_be_bytes(data[AccountAddress::LENGTH..].try_into()?);
+        Ok((sender, sequence_number))
+    }
+}
+
+impl ValueCodec<JournalSchema> for JournalEntry {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
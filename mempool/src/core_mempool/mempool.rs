@@ -4,10 +4,11 @@
 //! Mempool is used to track transactions which have been submitted but not yet
 //! agreed upon.
 use crate::counters::{CONSENSUS_PULLED_LABEL, E2E_LABEL, INSERT_LABEL, LOCAL_LABEL, REMOVE_LABEL};
-use crate::shared_mempool::types::MultiBucketTimelineIndexIds;
+use crate::shared_mempool::types::{MinAcceptedGasPrice, MultiBucketTimelineIndexIds};
 use crate::{
     core_mempool::{
         index::TxnPointer,
+        journal::MempoolJournal,
         transaction::{MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
     },
@@ -25,20 +26,40 @@ use aptos_types::{
 };
 use std::{
     collections::HashSet,
+    sync::Arc,
     time::{Duration, SystemTime},
 };
 
 pub struct Mempool {
     // Stores the metadata of all transactions in mempool (of all states).
     transactions: TransactionStore,
+    // Optional on-disk record of the transactions in mempool, used to restore them on startup.
+    journal: Option<Arc<MempoolJournal>>,
 
     pub system_transaction_timeout: Duration,
 }
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
+        let journal = if config.mempool.journal_enabled {
+            // Mempool works without the journal, transactions just don't survive a restart.
+            match MempoolJournal::new(config.storage.dir()) {
+                Ok(journal) => Some(Arc::new(journal)),
+                Err(err) => {
+                    error!(
+                        LogSchema::new(LogEntry::DBError).error(&err),
+                        "Failed to open the mempool journal, continuing without it"
+                    );
+                    counters::DB_ERROR.inc();
+                    None
+                }
+            }
+        } else {
+            None
+        };
         Mempool {
             transactions: TransactionStore::new(&config.mempool),
+            journal,
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
@@ -63,6 +84,9 @@ impl Mempool {
 
         self.transactions
             .commit_transaction(sender, sequence_number);
+        if let Some(journal) = &self.journal {
+            journal.remove_committed(sender, sequence_number + 1);
+        }
     }
 
     pub(crate) fn reject_transaction(
@@ -87,6 +111,12 @@ impl Mempool {
 
         self.transactions
             .reject_transaction(sender, sequence_number, hash);
+        if let Some(journal) = &self.journal {
+            // The rejection is ignored if the hash doesn't match the transaction in mempool.
+            if self.transactions.get(sender, sequence_number).is_none() {
+                journal.remove(&[(*sender, sequence_number)]);
+            }
+        }
     }
 
     fn log_latency(&self, account: AccountAddress, sequence_number: u64, stage: &'static str) {
@@ -117,6 +147,39 @@ impl Mempool {
         ranking_score: u64,
         sequence_info: AccountSequenceInfo,
         timeline_state: TimelineState,
+    ) -> MempoolStatus {
+        self.insert_txn(txn, ranking_score, sequence_info, timeline_state, None)
+    }
+
+    /// Same as `add_txn`, but for a transaction restored from the journal: it expires by system
+    /// TTL at `expiration_time` instead of `system_transaction_timeout` from now, and isn't
+    /// written to the journal again since it's already there.
+    pub(crate) fn restore_txn(
+        &mut self,
+        txn: SignedTransaction,
+        ranking_score: u64,
+        sequence_info: AccountSequenceInfo,
+        timeline_state: TimelineState,
+        expiration_time: Duration,
+    ) -> MempoolStatus {
+        self.insert_txn(
+            txn,
+            ranking_score,
+            sequence_info,
+            timeline_state,
+            Some(expiration_time),
+        )
+    }
+
+    /// Inserts a new transaction if `expiration_time` is `None`, or restores a journaled one
+    /// otherwise.
+    fn insert_txn(
+        &mut self,
+        txn: SignedTransaction,
+        ranking_score: u64,
+        sequence_info: AccountSequenceInfo,
+        timeline_state: TimelineState,
+        expiration_time: Option<Duration>,
     ) -> MempoolStatus {
         let db_sequence_number = sequence_info.min_seq();
         trace!(
@@ -135,9 +198,15 @@ impl Mempool {
         }
 
         let now = SystemTime::now();
-        let expiration_time =
-            aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout;
+        let is_restored = expiration_time.is_some();
+        let expiration_time = expiration_time.unwrap_or_else(|| {
+            aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout
+        });
 
+        let journaled_txn = match &self.journal {
+            Some(_) if !is_restored => Some(txn.clone()),
+            _ => None,
+        };
         let txn_info = MempoolTransaction::new(
             txn,
             expiration_time,
@@ -148,6 +217,15 @@ impl Mempool {
         );
 
         let status = self.transactions.insert(txn_info);
+        let evicted = self.transactions.take_evicted_txns();
+        if let Some(journal) = &self.journal {
+            journal.remove(&evicted);
+        }
+        if let (Some(journal), Some(txn)) = (&self.journal, journaled_txn) {
+            if status.code == MempoolStatusCode::Accepted {
+                journal.insert(&txn, timeline_state, expiration_time);
+            }
+        }
         counters::core_mempool_txn_ranking_score(
             INSERT_LABEL,
             status.code.to_string().as_str(),
@@ -261,12 +339,38 @@ impl Mempool {
     /// cache and sequence number cache.
     pub(crate) fn gc(&mut self) {
         let now = aptos_infallible::duration_since_epoch();
-        self.transactions.gc_by_system_ttl(now);
+        let removed = self.transactions.gc_by_system_ttl(now);
+        if let Some(journal) = &self.journal {
+            journal.remove(&removed);
+        }
     }
 
     /// Garbage collection based on client-specified expiration time.
     pub(crate) fn gc_by_expiration_time(&mut self, block_time: Duration) {
-        self.transactions.gc_by_expiration_time(block_time);
+        let removed = self.transactions.gc_by_expiration_time(block_time);
+        if let Some(journal) = &self.journal {
+            journal.remove(&removed);
+        }
+    }
+
+    /// Returns the journal, if it is enabled, so that it can be read and updated without
+    /// holding the mempool lock.
+    pub(crate) fn journal(&self) -> Option<Arc<MempoolJournal>> {
+        self.journal.clone()
+    }
+
+    /// Returns the transactions recorded in the journal, if it is enabled.
+    #[cfg(test)]
+    pub(crate) fn journal_entries(&self) -> Vec<super::journal::JournalEntry> {
+        self.journal
+            .as_ref()
+            .map(|journal| journal.get_all().unwrap())
+            .unwrap_or_default()
+    }
+
+    /// Returns the gas unit prices a new transaction is currently accepted at.
+    pub(crate) fn get_min_accepted_gas_price(&self) -> MinAcceptedGasPrice {
+        self.transactions.get_min_accepted_ranking_score()
     }
 
     /// Returns block of transactions and new last_timeline_id.
@@ -4,12 +4,12 @@
 use crate::counters::{
     BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL, E2E_LABEL, LOCAL_LABEL,
 };
-use crate::shared_mempool::types::MultiBucketTimelineIndexIds;
+use crate::shared_mempool::types::{MinAcceptedGasPrice, MultiBucketTimelineIndexIds};
 use crate::{
     core_mempool::{
         index::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
-            PriorityQueueIter, TTLIndex,
+            PriorityQueueIter, TTLIndex, TxnPointer,
         },
         transaction::{MempoolTransaction, TimelineState},
     },
@@ -66,6 +66,9 @@ pub struct TransactionStore {
     // one valid hash.
     hash_index: HashMap<HashValue, (AccountAddress, u64)>,
 
+    // Transactions evicted to make space since they were last taken
+    evicted_txns: Vec<TxnPointer>,
+
     // estimated size in bytes
     size_bytes: usize,
 
@@ -93,6 +96,7 @@ impl TransactionStore {
                 .unwrap(),
             parking_lot_index: ParkingLotIndex::new(),
             hash_index: HashMap::new(),
+            evicted_txns: vec![],
 
             // estimated size in bytes
             size_bytes: 0,
@@ -314,7 +318,8 @@ impl TransactionStore {
     }
 
     /// Checks if Mempool is full.
-    /// If it's full, tries to free some space by evicting transactions from the ParkingLot.
+    /// If it's full, tries to free some space by evicting transactions from the ParkingLot first,
+    /// and then ready transactions that pay a lower gas price than `txn`.
     /// We only evict on attempt to insert a transaction that would be ready for broadcast upon insertion.
     fn check_is_full_after_eviction(
         &mut self,
@@ -324,24 +329,92 @@ impl TransactionStore {
         if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
             // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
             if let Some((address, sequence_number)) = self.parking_lot_index.get_poppable() {
-                if let Some(txn) = self
-                    .transactions
-                    .get_mut(&address)
-                    .and_then(|txns| txns.remove(&sequence_number))
-                {
-                    debug!(
-                        LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
-                            txn.get_sender(),
-                            txn.sequence_info.transaction_sequence_number
-                        ))
-                    );
-                    self.index_remove(&txn);
+                self.evict(&address, sequence_number, counters::EVICT_PARKED_TXN_LABEL);
+            }
+            // if that's not enough, evict cheaper txns of other accounts
+            while self.is_full() {
+                match self.get_evictable_txn(txn.ranking_score, Some(&txn.get_sender())) {
+                    Some((address, sequence_number)) => self.evict(
+                        &address,
+                        sequence_number,
+                        counters::EVICT_LOW_GAS_PRICE_LABEL,
+                    ),
+                    None => break,
                 }
             }
         }
         self.is_full()
     }
 
+    /// Returns the lowest priced ready transaction with a ranking score below `ranking_score`
+    /// that can be evicted without breaking a sequence number chain, i.e. the transaction with
+    /// the highest sequence number of its account. Transactions of `excluded_sender` are skipped,
+    /// since the incoming transaction may depend on them.
+    fn get_evictable_txn(
+        &self,
+        ranking_score: u64,
+        excluded_sender: Option<&AccountAddress>,
+    ) -> Option<TxnPointer> {
+        self.priority_index
+            .iter_lowest()
+            .take_while(|key| key.gas_ranking_score < ranking_score)
+            .find(|key| {
+                Some(&key.address) != excluded_sender
+                    && self
+                        .transactions
+                        .get(&key.address)
+                        .and_then(|txns| txns.keys().next_back())
+                        == Some(&key.sequence_number.transaction_sequence_number)
+            })
+            .map(|key| (key.address, key.sequence_number.transaction_sequence_number))
+    }
+
+    /// Removes a transaction to make space in a full Mempool.
+    fn evict(&mut self, address: &AccountAddress, sequence_number: u64, reason: &'static str) {
+        if let Some(txn) = self
+            .transactions
+            .get_mut(address)
+            .and_then(|txns| txns.remove(&sequence_number))
+        {
+            debug!(
+                LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
+                    txn.get_sender(),
+                    txn.sequence_info.transaction_sequence_number
+                )),
+                reason = reason,
+                ranking_score = txn.ranking_score,
+            );
+            counters::core_mempool_evicted_txn(
+                reason,
+                self.timeline_index.get_bucket(txn.ranking_score),
+            );
+            self.evicted_txns.push((*address, sequence_number));
+            self.index_remove(&txn);
+        }
+    }
+
+    /// Returns the transactions evicted since the last call.
+    pub(crate) fn take_evicted_txns(&mut self) -> Vec<TxnPointer> {
+        std::mem::take(&mut self.evicted_txns)
+    }
+
+    /// Returns the ranking scores a new ready transaction is accepted at: any while Mempool has
+    /// space or non-ready transactions left to evict, otherwise more than the cheapest evictable
+    /// transaction, if there is one.
+    pub(crate) fn get_min_accepted_ranking_score(&self) -> MinAcceptedGasPrice {
+        if !self.is_full() || self.parking_lot_index.size() > 0 {
+            return MinAcceptedGasPrice::Any;
+        }
+        match self
+            .get_evictable_txn(u64::MAX, None)
+            .and_then(|(address, sequence_number)| {
+                self.get_ranking_score(&address, sequence_number)
+            }) {
+            Some(ranking_score) => MinAcceptedGasPrice::AtLeast(ranking_score.saturating_add(1)),
+            None => MinAcceptedGasPrice::NotAccepting,
+        }
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -629,16 +702,17 @@ impl TransactionStore {
     }
 
     /// Garbage collect old transactions.
-    pub(crate) fn gc_by_system_ttl(&mut self, gc_time: Duration) {
-        self.gc(gc_time, true);
+    pub(crate) fn gc_by_system_ttl(&mut self, gc_time: Duration) -> Vec<TxnPointer> {
+        self.gc(gc_time, true)
     }
 
     /// Garbage collect old transactions based on client-specified expiration time.
-    pub(crate) fn gc_by_expiration_time(&mut self, block_time: Duration) {
-        self.gc(block_time, false);
+    pub(crate) fn gc_by_expiration_time(&mut self, block_time: Duration) -> Vec<TxnPointer> {
+        self.gc(block_time, false)
     }
 
-    fn gc(&mut self, now: Duration, by_system_ttl: bool) {
+    /// Returns the transactions that were removed.
+    fn gc(&mut self, now: Duration, by_system_ttl: bool) -> Vec<TxnPointer> {
         let (metric_label, index, log_event) = if by_system_ttl {
             (
                 counters::GC_SYSTEM_TTL_LABEL,
@@ -665,6 +739,7 @@ impl TransactionStore {
             true => TxnsLog::new(),
             false => TxnsLog::new_with_max(10),
         };
+        let mut removed = vec![];
         while let Some(key) = gc_iter.next() {
             if let Some(txns) = self.transactions.get_mut(&key.address) {
                 let park_range_start = Bound::Excluded(key.sequence_number);
@@ -693,6 +768,7 @@ impl TransactionStore {
                     let account = txn.get_sender();
                     let txn_sequence_number = txn.sequence_info.transaction_sequence_number;
                     gc_txns_log.add_with_status(account, txn_sequence_number, status);
+                    removed.push((account, txn_sequence_number));
                     if let Ok(time_delta) = SystemTime::now().duration_since(txn.insertion_time) {
                         counters::CORE_MEMPOOL_GC_LATENCY
                             .with_label_values(&[metric_label, status])
@@ -711,6 +787,7 @@ impl TransactionStore {
             trace!(LogSchema::event_log(LogEntry::GCRemoveTxns, log_event).txns(gc_txns_log));
         }
         self.track_indices();
+        removed
     }
 
     pub(crate) fn iter_queue(&self) -> PriorityQueueIter {
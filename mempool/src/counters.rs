@@ -32,6 +32,10 @@ pub const BROADCAST_BATCHED_LABEL: &str = "broadcast_batched";
 pub const GC_SYSTEM_TTL_LABEL: &str = "system_ttl";
 pub const GC_CLIENT_EXP_LABEL: &str = "client_expiration";
 
+// Core mempool eviction reason labels
+pub const EVICT_PARKED_TXN_LABEL: &str = "parked";
+pub const EVICT_LOW_GAS_PRICE_LABEL: &str = "low_gas_price";
+
 // Core mempool GC txn status label
 pub const GC_ACTIVE_TXN_LABEL: &str = "active";
 pub const GC_PARKED_TXN_LABEL: &str = "parked";
@@ -158,6 +162,22 @@ pub static CORE_MEMPOOL_REMOVED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns evicted from a full core mempool, per timeline bucket
+static CORE_MEMPOOL_EVICTED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_evicted_txns_count",
+        "Number of txns evicted from core mempool to make space for new txns",
+        &["reason", "bucket"]
+    )
+    .unwrap()
+});
+
+pub fn core_mempool_evicted_txn(reason: &'static str, bucket: &str) {
+    CORE_MEMPOOL_EVICTED_TXNS
+        .with_label_values(&[reason, bucket])
+        .inc()
+}
+
 /// Counter tracking number of txns received that are idempotent duplicates
 pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
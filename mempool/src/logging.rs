@@ -172,6 +172,7 @@ pub enum LogEntry {
     DBError,
     UnexpectedNetworkMsg,
     MempoolSnapshot,
+    JournalReplay,
 }
 
 #[derive(Clone, Copy, Serialize)]
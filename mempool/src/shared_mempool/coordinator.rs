@@ -159,6 +159,13 @@ async fn handle_client_request<V>(
                 ))
                 .await;
         }
+        MempoolClientRequest::GetMinAcceptedGasPrice(callback) => {
+            // This is a cheap read of the core mempool, so it is answered without spawning a task.
+            let min_gas_price = smp.mempool.lock().get_min_accepted_gas_price();
+            if callback.send(min_gas_price).is_err() {
+                counters::CLIENT_CALLBACK_FAIL.inc();
+            }
+        }
     }
 }
 
@@ -6,6 +6,7 @@ use crate::{
     network::{MempoolNetworkEvents, MempoolNetworkSender},
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
+        tasks::replay_journal,
         types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
     },
     QuorumStoreRequest,
@@ -70,6 +71,12 @@ pub(crate) fn start_shared_mempool<V>(
         peer_metadata_storage,
     );
 
+    if config.mempool.journal_enabled {
+        // Replaying validates every journaled transaction, so it doesn't hold up startup.
+        let smp = smp.clone();
+        executor.spawn_blocking(move || replay_journal(&smp));
+    }
+
     executor.spawn(coordinator(
         smp,
         executor.clone(),
@@ -32,7 +32,7 @@ use network::application::interface::NetworkInterface;
 use rayon::prelude::*;
 use std::{
     cmp,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -241,6 +241,20 @@ pub(crate) fn process_incoming_transactions<V>(
     transactions: Vec<SignedTransaction>,
     timeline_state: TimelineState,
 ) -> Vec<SubmissionStatusBundle>
+where
+    V: TransactionValidation,
+{
+    process_transactions_with_expiration_times(smp, transactions, timeline_state, &HashMap::new())
+}
+
+/// Same as `process_incoming_transactions`, but the transactions in `expiration_times` are
+/// restored from the journal with the given system TTL expiration instead of getting a new one.
+fn process_transactions_with_expiration_times<V>(
+    smp: &SharedMempool<V>,
+    transactions: Vec<SignedTransaction>,
+    timeline_state: TimelineState,
+    expiration_times: &HashMap<TxnPointer, Duration>,
+) -> Vec<SubmissionStatusBundle>
 where
     V: TransactionValidation,
 {
@@ -317,12 +331,23 @@ where
                 match validation_result.status() {
                     None => {
                         let ranking_score = validation_result.score();
-                        let mempool_status = mempool.add_txn(
-                            transaction.clone(),
-                            ranking_score,
-                            sequence_info,
-                            timeline_state,
-                        );
+                        let mempool_status = match expiration_times
+                            .get(&(transaction.sender(), transaction.sequence_number()))
+                        {
+                            Some(expiration_time) => mempool.restore_txn(
+                                transaction.clone(),
+                                ranking_score,
+                                sequence_info,
+                                timeline_state,
+                                *expiration_time,
+                            ),
+                            None => mempool.add_txn(
+                                transaction.clone(),
+                                ranking_score,
+                                sequence_info,
+                                timeline_state,
+                            ),
+                        };
                         statuses.push((transaction, (mempool_status, None)));
                     }
                     Some(validation_status) => {
@@ -382,6 +407,79 @@ fn log_txn_process_results(results: &[SubmissionStatusBundle], sender: Option<Pe
     }
 }
 
+/// Restores the transactions recorded in the mempool journal after a restart.
+/// Every transaction goes through the regular validation path again, so the ones that were
+/// committed or expired while the node was down are dropped, along with their journal entries.
+/// Restored transactions keep their system TTL expiration, so a restart doesn't extend it.
+/// The journal is only read and updated outside of the mempool lock, and restored transactions
+/// aren't written to it again.
+pub(crate) fn replay_journal<V>(smp: &SharedMempool<V>)
+where
+    V: TransactionValidation,
+{
+    let journal = match smp.mempool.lock().journal() {
+        Some(journal) => journal,
+        None => return,
+    };
+    let entries = match journal.get_all() {
+        Ok(entries) => entries,
+        Err(err) => {
+            error!(LogSchema::new(LogEntry::DBError).error(&err));
+            counters::DB_ERROR.inc();
+            return;
+        }
+    };
+    if entries.is_empty() {
+        return;
+    }
+    let num_entries = entries.len();
+
+    let now = aptos_infallible::duration_since_epoch();
+    let mut dropped = vec![];
+    let mut expiration_times = HashMap::new();
+    let mut transactions_by_state: HashMap<TimelineState, Vec<SignedTransaction>> = HashMap::new();
+    for entry in entries {
+        let txn_pointer = (entry.txn.sender(), entry.txn.sequence_number());
+        if entry.expiration_time <= now {
+            dropped.push(txn_pointer);
+            continue;
+        }
+        expiration_times.insert(txn_pointer, entry.expiration_time);
+        // Timeline ids are reassigned when the transaction becomes ready again.
+        let timeline_state = match entry.timeline_state {
+            TimelineState::Ready(_) => TimelineState::NotReady,
+            state => state,
+        };
+        transactions_by_state
+            .entry(timeline_state)
+            .or_default()
+            .push(entry.txn);
+    }
+
+    for (timeline_state, transactions) in transactions_by_state {
+        let statuses = process_transactions_with_expiration_times(
+            smp,
+            transactions,
+            timeline_state,
+            &expiration_times,
+        );
+        dropped.extend(
+            statuses
+                .iter()
+                .filter(|(_, (status, _))| status.code != MempoolStatusCode::Accepted)
+                .map(|(txn, _)| (txn.sender(), txn.sequence_number())),
+        );
+    }
+    // All the dropped transactions are removed in a single write.
+    journal.remove(&dropped);
+
+    info!(
+        LogSchema::new(LogEntry::JournalReplay),
+        restored = num_entries - dropped.len(),
+        dropped = dropped.len(),
+    );
+}
+
 // ================================= //
 // intra-node communication handlers //
 // ================================= //
@@ -205,9 +205,21 @@ pub type SubmissionStatus = (MempoolStatus, Option<DiscardedVMStatus>);
 
 pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 
+/// The gas unit prices new transactions are currently accepted at.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MinAcceptedGasPrice {
+    /// Mempool has space, or can make space by evicting non-ready transactions, at any price.
+    Any,
+    /// Mempool is full, and a transaction has to pay at least this much to evict another one.
+    AtLeast(u64),
+    /// Mempool is full and has no transaction to evict, so none is accepted at any price.
+    NotAccepting,
+}
+
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetMinAcceptedGasPrice(oneshot::Sender<MinAcceptedGasPrice>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
@@ -4,10 +4,12 @@
 use crate::tests::common::setup_mempool_with_broadcast_buckets;
 use crate::{
     core_mempool::{CoreMempool, MempoolTransaction, TimelineState},
+    shared_mempool::types::MinAcceptedGasPrice,
     tests::common::{add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool, TestTransaction},
 };
 use aptos_config::config::NodeConfig;
 use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
 use aptos_types::mempool_status::MempoolStatusCode;
 use aptos_types::{account_config::AccountSequenceInfo, transaction::SignedTransaction};
 use itertools::Itertools;
@@ -615,6 +617,69 @@ fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     }
 }
 
+#[test]
+fn test_gas_price_eviction() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 3;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(0, 1, 1)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(1, 0, 3)).unwrap();
+
+    // Mempool is full and there's nothing in the parking lot, so a txn has to outbid the
+    // cheapest txn that can be evicted.
+    assert_eq!(
+        pool.get_min_accepted_gas_price(),
+        MinAcceptedGasPrice::AtLeast(2)
+    );
+    assert!(add_txn(&mut pool, TestTransaction::new(2, 0, 1)).is_err());
+
+    // A more expensive txn evicts the last txn of the cheapest account, keeping its chain intact.
+    add_txn(&mut pool, TestTransaction::new(2, 0, 5)).unwrap();
+    let account_txns = pool
+        .get_transaction_store()
+        .get_transactions()
+        .get(&TestTransaction::get_address(0))
+        .unwrap();
+    assert_eq!(account_txns.keys().copied().collect::<Vec<_>>(), vec![0]);
+
+    // Paying the same price as the cheapest txn is not enough.
+    assert_eq!(
+        pool.get_min_accepted_gas_price(),
+        MinAcceptedGasPrice::AtLeast(2)
+    );
+    assert!(add_txn(&mut pool, TestTransaction::new(3, 0, 1)).is_err());
+    add_txn(&mut pool, TestTransaction::new(3, 0, 2)).unwrap();
+    assert!(pool
+        .get_transaction_store()
+        .get_transactions()
+        .get(&TestTransaction::get_address(0))
+        .is_none());
+    assert_eq!(
+        pool.get_min_accepted_gas_price(),
+        MinAcceptedGasPrice::AtLeast(3)
+    );
+
+    // Once there's space, any price is accepted.
+    pool.commit_transaction(&TestTransaction::get_address(1), 0);
+    assert_eq!(pool.get_min_accepted_gas_price(), MinAcceptedGasPrice::Any);
+}
+
+#[test]
+fn test_no_gas_price_accepted_without_evictable_txns() {
+    let mut config = NodeConfig::random();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+    add_txn(&mut pool, TestTransaction::new(0, 0, u64::MAX)).unwrap();
+
+    // Mempool is full and no price outbids the only txn, so nothing can be evicted.
+    assert_eq!(
+        pool.get_min_accepted_gas_price(),
+        MinAcceptedGasPrice::NotAccepting
+    );
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 0, u64::MAX)).is_err());
+}
+
 #[test]
 fn test_gc_ready_transaction() {
     let mut pool = setup_mempool().0;
@@ -785,3 +850,113 @@ fn test_sequence_number_behavior_at_capacity() {
     let batch = pool.get_batch(10, 10240, HashSet::new());
     assert_eq!(batch.len(), 1);
 }
+
+fn setup_mempool_with_journal(dir: &TempPath) -> CoreMempool {
+    let mut config = NodeConfig::random();
+    config.mempool.broadcast_buckets = vec![0];
+    config.mempool.journal_enabled = true;
+    config.storage.dir = dir.path().to_path_buf();
+    CoreMempool::new(&config)
+}
+
+#[test]
+fn test_journal_survives_restart() {
+    let dir = TempPath::new();
+    let mut pool = setup_mempool_with_journal(&dir);
+    let txns = add_txns_to_mempool(
+        &mut pool,
+        vec![
+            TestTransaction::new(0, 0, 1),
+            TestTransaction::new(0, 1, 1),
+            TestTransaction::new(1, 0, 1),
+        ],
+    );
+    pool.commit_transaction(&TestTransaction::get_address(0), 0);
+    drop(pool);
+
+    // Only the transactions that weren't committed are restored.
+    let pool = setup_mempool_with_journal(&dir);
+    let journaled_txns: Vec<_> = pool
+        .journal_entries()
+        .into_iter()
+        .map(|entry| entry.txn)
+        .sorted_by_key(|txn| (txn.sender(), txn.sequence_number()))
+        .collect();
+    let expected_txns: Vec<_> = txns[1..]
+        .iter()
+        .cloned()
+        .sorted_by_key(|txn| (txn.sender(), txn.sequence_number()))
+        .collect();
+    assert_eq!(journaled_txns, expected_txns);
+}
+
+#[test]
+fn test_journal_removes_rejected_and_expired_transactions() {
+    let dir = TempPath::new();
+    let mut pool = setup_mempool_with_journal(&dir);
+
+    let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    let hash = txn.clone().committed_hash();
+    add_signed_txn(&mut pool, txn).unwrap();
+    let txn = TestTransaction::new(2, 0, 1).make_signed_transaction_with_expiration_time(0);
+    add_signed_txn(&mut pool, txn).unwrap();
+    assert_eq!(pool.journal_entries().len(), 2);
+
+    pool.reject_transaction(&TestTransaction::get_address(1), 0, &hash);
+    assert_eq!(pool.journal_entries().len(), 1);
+
+    pool.gc_by_expiration_time(Duration::from_secs(1));
+    assert!(pool.journal_entries().is_empty());
+}
+
+#[test]
+fn test_journal_keeps_expiration_time() {
+    let dir = TempPath::new();
+    let mut pool = setup_mempool_with_journal(&dir);
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    let expiration_time = pool.journal_entries()[0].expiration_time;
+    drop(pool);
+
+    // The restored transaction doesn't get a new system TTL.
+    let mut pool = setup_mempool_with_journal(&dir);
+    let entry = pool.journal_entries().pop().unwrap();
+    assert_eq!(entry.expiration_time, expiration_time);
+    pool.restore_txn(
+        entry.txn.clone(),
+        entry.txn.gas_unit_price(),
+        AccountSequenceInfo::Sequential(0),
+        entry.timeline_state,
+        entry.expiration_time,
+    );
+    assert_eq!(pool.journal_entries()[0].expiration_time, expiration_time);
+
+    // A restored transaction that is past its expiration time is garbage collected.
+    let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    add_signed_txn(&mut pool, txn.clone()).unwrap();
+    drop(pool);
+    let mut pool = setup_mempool_with_journal(&dir);
+    pool.restore_txn(
+        txn.clone(),
+        txn.gas_unit_price(),
+        AccountSequenceInfo::Sequential(0),
+        TimelineState::NotReady,
+        Duration::from_secs(1),
+    );
+    assert_eq!(pool.journal_entries().len(), 2);
+    pool.gc();
+    assert_eq!(pool.journal_entries().len(), 1);
+}
+
+#[test]
+fn test_journal_disabled_when_it_fails_to_open() {
+    // The journal can't be created under a file.
+    let file = TempPath::new();
+    file.create_as_file().unwrap();
+    let mut config = NodeConfig::random();
+    config.mempool.journal_enabled = true;
+    config.storage.dir = file.path().to_path_buf();
+    let mut pool = CoreMempool::new(&config);
+
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    assert!(pool.journal_entries().is_empty());
+}
@@ -58,6 +58,7 @@ impl MockSharedMempool {
             .expect("[mock shared mempool] failed to create runtime");
         let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start(
             runtime.handle(),
+            NodeConfig::random(),
             &DbReaderWriter::new(MockDbReaderWriter),
             MockVMValidator,
         );
@@ -76,10 +77,19 @@ impl MockSharedMempool {
     pub fn new_in_runtime<V: TransactionValidation + 'static>(
         db: &DbReaderWriter,
         validator: V,
+    ) -> Self {
+        Self::new_in_runtime_with_config(NodeConfig::random(), db, validator)
+    }
+
+    /// Same as `new_in_runtime`, but the shared mempool is started with the given config.
+    pub fn new_in_runtime_with_config<V: TransactionValidation + 'static>(
+        config: NodeConfig,
+        db: &DbReaderWriter,
+        validator: V,
     ) -> Self {
         let handle = Handle::current();
         let (ac_client, mempool, quorum_store_sender, mempool_notifier) =
-            Self::start(&handle, db, validator);
+            Self::start(&handle, config, db, validator);
         Self {
             _runtime: None,
             _handle: Some(handle),
@@ -92,6 +102,7 @@ impl MockSharedMempool {
 
     pub fn start<V: TransactionValidation + 'static>(
         handle: &Handle,
+        mut config: NodeConfig,
         db: &DbReaderWriter,
         validator: V,
     ) -> (
@@ -100,7 +111,6 @@ impl MockSharedMempool {
         mpsc::Sender<QuorumStoreRequest>,
         MempoolNotifier,
     ) {
-        let mut config = NodeConfig::random();
         config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
 
         let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
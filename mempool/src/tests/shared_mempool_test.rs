@@ -2,15 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    core_mempool::CoreMempool,
     mocks::MockSharedMempool,
     tests::common::{batch_add_signed_txn, TestTransaction},
     QuorumStoreRequest,
 };
-use aptos_types::transaction::Transaction;
+use anyhow::Result;
+use aptos_config::config::NodeConfig;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    account_config::AccountResource,
+    event::{EventHandle, EventKey},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, Version},
+};
 use consensus_types::common::RejectedTransactionSummary;
 use futures::{channel::oneshot, executor::block_on, sink::SinkExt};
 use mempool_notifications::MempoolNotificationSender;
+use std::time::Duration;
+use storage_interface::{DbReader, DbReaderWriter, DbWriter};
 use tokio::runtime::Builder;
+use vm_validator::mocks::mock_vm_validator::MockVMValidator;
 
 #[test]
 fn test_consensus_events_rejected_txns() {
@@ -99,3 +111,79 @@ fn test_mempool_notify_committed_txns() {
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
+
+/// A DB in which the account of `TestTransaction::get_address(0)` has committed its first
+/// transaction, and no other account has.
+struct OneCommittedTxnDb;
+
+impl DbReader for OneCommittedTxnDb {
+    fn get_latest_state_checkpoint_version(&self) -> Result<Option<Version>> {
+        Ok(Some(1))
+    }
+
+    fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        _: Version,
+    ) -> Result<Option<StateValue>> {
+        // Mempool only reads account resources.
+        let address = TestTransaction::get_address(0);
+        match state_key {
+            StateKey::AccessPath(access_path) if access_path.address == address => (),
+            _ => return Ok(None),
+        }
+        let account_resource = AccountResource::new(
+            1,
+            vec![],
+            EventHandle::new(EventKey::new(0, address), 0),
+            EventHandle::new(EventKey::new(1, address), 0),
+        );
+        Ok(Some(StateValue::from(bcs::to_bytes(&account_resource)?)))
+    }
+}
+
+impl DbWriter for OneCommittedTxnDb {}
+
+#[tokio::test]
+async fn test_journal_replay_drops_committed_and_expired_txns() {
+    let dir = TempPath::new();
+    let mut config = NodeConfig::random();
+    config.mempool.journal_enabled = true;
+    config.storage.dir = dir.path().to_path_buf();
+
+    let committed_txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let expired_txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    let valid_txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    {
+        let mut pool = CoreMempool::new(&config);
+        batch_add_signed_txn(&mut pool, vec![committed_txn, valid_txn.clone()]).unwrap();
+        // Expires by system TTL right away.
+        pool.system_transaction_timeout = Duration::ZERO;
+        batch_add_signed_txn(&mut pool, vec![expired_txn]).unwrap();
+        assert_eq!(pool.journal_entries().len(), 3);
+    }
+
+    // Restart mempool after the first transaction was committed.
+    let smp = MockSharedMempool::new_in_runtime_with_config(
+        config,
+        &DbReaderWriter::new(OneCommittedTxnDb),
+        MockVMValidator,
+    );
+    // The journal replay runs in the background and cleans up the journal last.
+    for _ in 0..100 {
+        if smp.mempool.lock().journal_entries().len() == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let journaled_txns: Vec<_> = smp
+        .mempool
+        .lock()
+        .journal_entries()
+        .into_iter()
+        .map(|entry| entry.txn)
+        .collect();
+    assert_eq!(journaled_txns, vec![valid_txn.clone()]);
+    assert_eq!(smp.get_txns(10), vec![valid_txn]);
+}
@@ -15,7 +15,7 @@ use bounded_executor::BoundedExecutor;
 use futures::stream::StreamExt;
 use peer_monitoring_service_types::{
     ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceRequest,
-    PeerMonitoringServiceResponse, Result, ServerProtocolVersionResponse,
+    PeerMonitoringServiceResponse, PingResponse, Result, ServerProtocolVersionResponse,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
@@ -218,7 +218,7 @@ impl Handler {
     }
 
     fn handle_ping(&self) -> Result<PeerMonitoringServiceResponse, Error> {
-        unimplemented!();
+        Ok(PeerMonitoringServiceResponse::Ping(PingResponse))
     }
 }
 
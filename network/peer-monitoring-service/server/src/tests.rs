@@ -30,7 +30,8 @@ use network::{
 };
 use peer_monitoring_service_types::{
     ConnectedPeersResponse, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
-    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    PeerMonitoringServiceRequest, PeerMonitoringServiceResponse, PingResponse,
+    ServerProtocolVersionResponse,
 };
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -56,6 +57,20 @@ async fn test_get_server_protocol_version() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_ping() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _) = MockClient::new();
+    tokio::spawn(service.start());
+
+    // Process a ping request
+    let request = PeerMonitoringServiceRequest::Ping;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify the response is correct
+    assert_eq!(response, PeerMonitoringServiceResponse::Ping(PingResponse));
+}
+
 #[tokio::test]
 async fn test_get_connected_peers() {
     // Create the peer monitoring client and server
@@ -101,9 +101,7 @@ pub struct KnownPeersResponse {
 
 /// A response for the ping request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
-pub struct PingResponse {
-    pub todo: bool,
-}
+pub struct PingResponse;
 
 /// A response for the server protocol version request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -60,6 +60,8 @@ pub enum ProtocolId {
     PeerMonitoringServiceRpc = 10,
     ConsensusRpcCompressed = 11,
     ConsensusDirectSendCompressed = 12,
+    ConsensusObserverDirectSend = 13,
+    ConsensusObserverRpc = 14,
 }
 
 /// The encoding types for Protocols
@@ -86,6 +88,8 @@ impl ProtocolId {
             PeerMonitoringServiceRpc => "PeerMonitoringServiceRpc",
             ConsensusRpcCompressed => "ConsensusRpcCompressed",
             ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
+            ConsensusObserverDirectSend => "ConsensusObserverDirectSend",
+            ConsensusObserverRpc => "ConsensusObserverRpc",
         }
     }
 
@@ -104,6 +108,8 @@ impl ProtocolId {
             ProtocolId::PeerMonitoringServiceRpc,
             ProtocolId::ConsensusRpcCompressed,
             ProtocolId::ConsensusDirectSendCompressed,
+            ProtocolId::ConsensusObserverDirectSend,
+            ProtocolId::ConsensusObserverRpc,
         ]
     }
 
@@ -5,7 +5,10 @@ use crate::{
     move_types::account_address::AccountAddress,
     types::{
         chain_id::ChainId,
-        transaction::{authenticator::AuthenticationKey, RawTransaction, TransactionPayload},
+        transaction::{
+            authenticator::AuthenticationKey, RawTransaction, RawTransactionWithData,
+            TransactionPayload,
+        },
     },
 };
 use aptos_crypto::ed25519::Ed25519PublicKey;
@@ -85,6 +88,20 @@ impl TransactionBuilder {
             self.chain_id,
         )
     }
+
+    /// Builds the message that the sender, the secondary signers and the fee payer of a fee payer
+    /// transaction each sign, for signers that don't hold a `LocalAccount`.
+    pub fn build_fee_payer(
+        self,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: AccountAddress,
+    ) -> RawTransactionWithData {
+        RawTransactionWithData::new_fee_payer(
+            self.build(),
+            secondary_signer_addresses,
+            fee_payer_address,
+        )
+    }
 }
 
 #[derive(Clone, Debug)]
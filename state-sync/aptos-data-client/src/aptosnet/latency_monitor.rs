@@ -0,0 +1,151 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::aptosnet::{
+    logging::{LogEntry, LogEvent, LogSchema},
+    metrics, AptosNetDataClient,
+};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::StreamExt;
+use network::{
+    application::interface::NetworkInterface, protocols::wire::handshake::v1::ProtocolId,
+};
+use peer_monitoring_service_client::PeerMonitoringServiceClient;
+use peer_monitoring_service_types::{PeerMonitoringServiceRequest, PeerMonitoringServiceResponse};
+use std::{collections::HashSet, sync::Arc, time::Duration};
+use tokio::runtime::Handle;
+
+// Useful constants for the latency monitor
+const LATENCY_PING_LOG_FREQ_SECS: u64 = 10;
+const PING_ERROR: &str = "error";
+const PING_SUCCESS: &str = "success";
+
+/// A monitor that periodically pings the connected peers (using the peer
+/// monitoring service) to measure their latencies. The latencies are used
+/// by the data client to prefer the fastest peers when selecting peers.
+pub struct LatencyMonitor {
+    data_client: AptosNetDataClient, // The data client to update with the peer latencies
+    in_flight_pings: Arc<Mutex<HashSet<PeerNetworkId>>>, // The peers with in-flight pings
+    peer_monitoring_client: PeerMonitoringServiceClient, // The client used to ping the peers
+    ping_interval: Duration,         // The interval between pinging rounds
+    ping_timeout: Duration,          // The timeout of each ping
+    runtime: Option<Handle>,         // An optional runtime on which to spawn the pings
+    time_service: TimeService,       // The service to measure the ping latencies
+}
+
+impl LatencyMonitor {
+    pub fn new(
+        data_client: AptosNetDataClient,
+        peer_monitoring_client: PeerMonitoringServiceClient,
+        runtime: Option<Handle>,
+    ) -> Self {
+        let data_client_config = data_client.data_client_config;
+        let time_service = data_client.time_service.clone();
+        Self {
+            data_client,
+            in_flight_pings: Arc::new(Mutex::new(HashSet::new())),
+            peer_monitoring_client,
+            ping_interval: Duration::from_millis(data_client_config.latency_ping_interval_ms),
+            ping_timeout: Duration::from_millis(data_client_config.latency_ping_timeout_ms),
+            runtime,
+            time_service,
+        }
+    }
+
+    /// Runs the monitor that continuously pings the connected peers
+    pub async fn start_monitor(self) {
+        info!((LogSchema::new(LogEntry::LatencyMonitor).message("Starting the latency monitor!")));
+        let ticker = self.time_service.interval(self.ping_interval);
+        futures::pin_mut!(ticker);
+
+        loop {
+            // Wait for next round before pinging
+            ticker.next().await;
+
+            // Ping all connected peers that don't already have an in-flight ping
+            for peer in self.get_peers_to_ping() {
+                self.ping_peer(peer);
+            }
+        }
+    }
+
+    /// Returns the connected peers that support the peer monitoring
+    /// service and don't have an in-flight ping.
+    fn get_peers_to_ping(&self) -> Vec<PeerNetworkId> {
+        let network_peer_metadata = self.peer_monitoring_client.peer_metadata_storage();
+        let in_flight_pings = self.in_flight_pings.lock();
+        network_peer_metadata
+            .networks()
+            .flat_map(|network_id| {
+                network_peer_metadata
+                    .read_filtered(network_id, |(_, peer_metadata)| {
+                        peer_metadata.is_connected()
+                            && peer_metadata.supports_protocol(ProtocolId::PeerMonitoringServiceRpc)
+                    })
+                    .into_keys()
+            })
+            .filter(|peer| !in_flight_pings.contains(peer))
+            .collect()
+    }
+
+    /// Spawns a task to ping the given peer and update its latency
+    fn ping_peer(&self, peer: PeerNetworkId) {
+        // Mark the ping as in-flight
+        self.in_flight_pings.lock().insert(peer);
+
+        // Create the ping task
+        let data_client = self.data_client.clone();
+        let in_flight_pings = self.in_flight_pings.clone();
+        let peer_monitoring_client = self.peer_monitoring_client.clone();
+        let ping_timeout = self.ping_timeout;
+        let time_service = self.time_service.clone();
+        let ping = async move {
+            // Send the ping and measure the latency
+            let ping_start_time = time_service.now();
+            let result = peer_monitoring_client
+                .send_request(peer, PeerMonitoringServiceRequest::Ping, ping_timeout)
+                .await;
+            let latency = time_service.now().duration_since(ping_start_time);
+
+            // Update the latency of the peer. Failed pings are recorded
+            // with the ping timeout, so that unresponsive peers are avoided.
+            let peer_bucket = data_client.peer_states.read().get_peer_bucket(&peer);
+            match result {
+                Ok(PeerMonitoringServiceResponse::Ping(_)) => {
+                    metrics::LATENCY_PINGS
+                        .with_label_values(&[peer_bucket, PING_SUCCESS])
+                        .inc();
+                    data_client.update_latency(peer, latency);
+                }
+                result => {
+                    metrics::LATENCY_PINGS
+                        .with_label_values(&[peer_bucket, PING_ERROR])
+                        .inc();
+                    sample!(
+                        SampleRate::Duration(Duration::from_secs(LATENCY_PING_LOG_FREQ_SECS)),
+                        warn!(
+                            (LogSchema::new(LogEntry::LatencyMonitor)
+                                .event(LogEvent::PeerPingError)
+                                .message(&format!("Failed to ping peer: {:?}", result))
+                                .peer(&peer))
+                        );
+                    );
+                    data_client.update_latency(peer, ping_timeout);
+                }
+            }
+
+            // Mark the ping as complete
+            in_flight_pings.lock().remove(&peer);
+        };
+
+        // Spawn the ping task
+        if let Some(runtime) = &self.runtime {
+            runtime.spawn(ping);
+        } else {
+            tokio::spawn(ping);
+        }
+    }
+}
@@ -41,6 +41,7 @@ impl<'a> LogSchema<'a> {
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
     DataSummaryPoller,
+    LatencyMonitor,
     PeerStates,
     StorageServiceRequest,
     StorageServiceResponse,
@@ -55,6 +56,7 @@ pub enum LogEvent {
     NoPeersToPoll,
     PeerIgnored,
     PeerNoLongerIgnored,
+    PeerPingError,
     PeerPollingError,
     PeerSelectionError,
     PriorityAndRegularPeers,
@@ -55,6 +55,26 @@ pub static REQUEST_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking the latency pings of peers (by peer type)
+pub static PEER_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_data_client_peer_latencies",
+        "Counters related to the latency pings of peers",
+        &["peer_type"]
+    )
+    .unwrap()
+});
+
+/// Counter for tracking the latency pings sent to peers (by peer type and result)
+pub static LATENCY_PINGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_client_latency_pings",
+        "Counters related to the latency pings sent to peers",
+        &["peer_type", "result"]
+    )
+    .unwrap()
+});
+
 /// Gauge for tracking the number of in-flight polls
 pub static IN_FLIGHT_POLLS: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
@@ -152,6 +172,11 @@ pub fn set_gauge(counter: &Lazy<IntGaugeVec>, label: &str, value: u64) {
     counter.with_label_values(&[label]).set(value as i64);
 }
 
+/// Observes the value for the provided histogram and label
+pub fn observe_value(histogram: &Lazy<HistogramVec>, label: &str, value: f64) {
+    histogram.with_label_values(&[label]).observe(value);
+}
+
 /// Starts the timer for the provided histogram and label values.
 pub fn start_request_timer(
     histogram: &Lazy<HistogramVec>,
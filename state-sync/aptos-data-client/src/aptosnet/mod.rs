@@ -38,19 +38,25 @@ use std::{convert::TryFrom, fmt, sync::Arc, time::Duration};
 use storage_service_client::StorageServiceClient;
 use storage_service_types::requests::{
     DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
-    NewTransactionsWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
-    TransactionOutputsWithProofRequest, TransactionsWithProofRequest,
+    NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+    StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
+    TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+};
+use storage_service_types::responses::{
+    StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof,
 };
-use storage_service_types::responses::{StorageServerSummary, StorageServiceResponse};
 use storage_service_types::Epoch;
 use tokio::{runtime::Handle, task::JoinHandle};
 
+mod latency_monitor;
 mod logging;
 mod metrics;
 mod state;
 #[cfg(test)]
 mod tests;
 
+pub use latency_monitor::LatencyMonitor;
+
 // TODO(joshlind): this code needs to be restructured. There are no clear APIs
 // and little separation between components.
 
@@ -107,6 +113,7 @@ impl AptosNetDataClient {
             data_client_config,
             network_client: network_client.clone(),
             peer_states: Arc::new(RwLock::new(PeerStates::new(
+                data_client_config,
                 base_config,
                 storage_service_config,
                 network_client.get_peer_metadata_storage(),
@@ -138,6 +145,11 @@ impl AptosNetDataClient {
         self.peer_states.write().update_summary(peer, summary)
     }
 
+    /// Update a peer's average latency with a new latency ping.
+    fn update_latency(&self, peer: PeerNetworkId, latency: Duration) {
+        self.peer_states.write().update_latency(peer, latency)
+    }
+
     /// Recompute and update the global data summary cache.
     fn update_global_summary_cache(&self) {
         let aggregate = self.peer_states.read().calculate_aggregate_summary();
@@ -160,10 +172,10 @@ impl AptosNetDataClient {
             self.identify_serviceable(regular_peers, request)
         };
 
-        // Randomly select a peer to handle the request
-        serviceable_peers
-            .choose(&mut rand::thread_rng())
-            .copied()
+        // Select a peer to handle the request (weighted by the quality of the peers)
+        self.peer_states
+            .read()
+            .choose_peer(&serviceable_peers)
             .ok_or_else(|| {
                 Error::DataIsUnavailable(
                     format!("No connected peers are advertising that they can serve this data! Request: {:?}",request),
@@ -456,6 +468,29 @@ impl AptosNetDataClient {
         }
     }
 
+    /// Verifies that the given transaction or output list contains exactly
+    /// one of transactions or outputs. Otherwise, the peer is penalized.
+    fn verify_transactions_or_outputs<T>(
+        response: Response<T>,
+        get_transactions_or_outputs: fn(&T) -> &TransactionOrOutputListWithProof,
+    ) -> Result<Response<T>> {
+        match get_transactions_or_outputs(&response.payload) {
+            (Some(_), None) | (None, Some(_)) => Ok(response),
+            (transactions, outputs) => {
+                let error = Error::InvalidResponse(format!(
+                    "Expected either transactions or outputs! Transactions: {:?}, outputs: {:?}",
+                    transactions.is_some(),
+                    outputs.is_some()
+                ));
+                response
+                    .context
+                    .response_callback
+                    .notify_bad_response(ResponseError::InvalidPayloadDataType);
+                Err(error)
+            }
+        }
+    }
+
     /// Updates the score of the peer who sent the response with the specified id
     fn notify_bad_response(
         &self,
@@ -527,6 +562,30 @@ impl AptosDataClient for AptosNetDataClient {
             .await
     }
 
+    async fn get_new_transactions_or_outputs_with_proof(
+        &self,
+        known_version: Version,
+        known_epoch: Epoch,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> Result<Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>> {
+        let data_request = DataRequest::GetNewTransactionsOrOutputsWithProof(
+            NewTransactionsOrOutputsWithProofRequest {
+                known_version,
+                known_epoch,
+                include_events,
+                max_num_output_reductions: self.data_client_config.max_num_output_reductions,
+            },
+        );
+        let storage_request = StorageServiceRequest::new(data_request, self.use_compression());
+        let response: Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)> = self
+            .send_request_and_decode(storage_request, request_timeout_ms)
+            .await?;
+        Self::verify_transactions_or_outputs(response, |(transactions_or_outputs, _)| {
+            transactions_or_outputs
+        })
+    }
+
     async fn get_number_of_states(
         &self,
         version: Version,
@@ -591,6 +650,31 @@ impl AptosDataClient for AptosNetDataClient {
         self.send_request_and_decode(storage_request, request_timeout_ms)
             .await
     }
+
+    async fn get_transactions_or_outputs_with_proof(
+        &self,
+        proof_version: Version,
+        start_version: Version,
+        end_version: Version,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> Result<Response<TransactionOrOutputListWithProof>> {
+        let data_request =
+            DataRequest::GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest {
+                proof_version,
+                start_version,
+                end_version,
+                include_events,
+                max_num_output_reductions: self.data_client_config.max_num_output_reductions,
+            });
+        let storage_request = StorageServiceRequest::new(data_request, self.use_compression());
+        let response: Response<TransactionOrOutputListWithProof> = self
+            .send_request_and_decode(storage_request, request_timeout_ms)
+            .await?;
+        Self::verify_transactions_or_outputs(response, |transactions_or_outputs| {
+            transactions_or_outputs
+        })
+    }
 }
 
 /// The AptosNet-specific request context needed to update a peer's scoring.
@@ -2,21 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    aptosnet::logging::{LogEntry, LogEvent, LogSchema},
+    aptosnet::{
+        logging::{LogEntry, LogEvent, LogSchema},
+        metrics::{self, observe_value, PRIORITIZED_PEER, REGULAR_PEER},
+    },
     AdvertisedData, GlobalDataSummary, OptimalChunkSizes, ResponseError,
 };
 use aptos_config::{
-    config::{BaseConfig, StorageServiceConfig},
+    config::{AptosDataClientConfig, BaseConfig, StorageServiceConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_logger::prelude::*;
 use itertools::Itertools;
 use netcore::transport::ConnectionOrigin;
 use network::application::storage::PeerMetadataStorage;
+use rand::seq::SliceRandom;
 use std::{
     cmp::min,
     collections::{HashMap, HashSet},
     sync::Arc,
+    time::Duration,
 };
 use storage_service_types::requests::StorageServiceRequest;
 use storage_service_types::responses::StorageServerSummary;
@@ -34,6 +39,13 @@ const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
 
+/// The weight of the latest latency ping in the moving averages of the peer
+/// latencies.
+const MOVING_AVERAGE_WEIGHT: f64 = 0.2;
+/// The minimum selection weight of a serviceable peer (the best peers have a
+/// weight close to 1). This ensures slow peers are still selected sometimes.
+const MIN_SELECTION_WEIGHT: f64 = 0.01;
+
 pub(crate) enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -61,6 +73,9 @@ struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The moving average of the latency pings (in seconds), or `None` if
+    /// we haven't pinged them yet.
+    average_latency_secs: Option<f64>,
 }
 
 impl Default for PeerState {
@@ -68,6 +83,7 @@ impl Default for PeerState {
         Self {
             storage_summary: None,
             score: STARTING_SCORE,
+            average_latency_secs: None,
         }
     }
 }
@@ -100,6 +116,34 @@ impl PeerState {
         };
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
+
+    /// Updates the average latency of the peer with a new latency ping
+    fn update_latency(&mut self, latency_secs: f64) {
+        self.average_latency_secs = Some(moving_average(self.average_latency_secs, latency_secs));
+    }
+
+    /// Returns the selection weight of the peer, given the best latency of the
+    /// peers to select from. Response times are deliberately not taken into
+    /// account: they grow with the amount of data served, so they would favour
+    /// peers that serve small chunks (or large ones, if divided by the size).
+    fn selection_weight(&self, min_latency_secs: f64) -> f64 {
+        let score_weight = self.score / MAX_SCORE;
+        let latency_weight = match self.average_latency_secs {
+            Some(latency_secs) if latency_secs > 0.0 => min_latency_secs / latency_secs,
+            _ => 1.0,
+        };
+        score_weight * latency_weight
+    }
+}
+
+/// Adds the given measurement to the exponential moving average
+fn moving_average(average: Option<f64>, measurement: f64) -> f64 {
+    match average {
+        Some(average) => {
+            MOVING_AVERAGE_WEIGHT * measurement + (1.0 - MOVING_AVERAGE_WEIGHT) * average
+        }
+        None => measurement,
+    }
 }
 
 /// Contains all of the unbanned peers' most recent [`StorageServerSummary`] data
@@ -107,6 +151,7 @@ impl PeerState {
 // TODO(philiphayes): this map needs to be garbage collected
 #[derive(Debug)]
 pub(crate) struct PeerStates {
+    data_client_config: AptosDataClientConfig,
     base_config: BaseConfig,
     storage_service_config: StorageServiceConfig,
     peer_to_state: HashMap<PeerNetworkId, PeerState>,
@@ -117,11 +162,13 @@ pub(crate) struct PeerStates {
 
 impl PeerStates {
     pub fn new(
+        data_client_config: AptosDataClientConfig,
         base_config: BaseConfig,
         storage_service_config: StorageServiceConfig,
         peer_metadata_storage: Arc<PeerMetadataStorage>,
     ) -> Self {
         Self {
+            data_client_config,
             base_config,
             storage_service_config,
             peer_to_state: HashMap::new(),
@@ -190,6 +237,73 @@ impl PeerStates {
         }
     }
 
+    /// Updates the average latency of the peer with a new latency ping
+    pub fn update_latency(&mut self, peer: PeerNetworkId, latency: Duration) {
+        let latency_secs = latency.as_secs_f64();
+        observe_value(
+            &metrics::PEER_LATENCIES,
+            self.get_peer_bucket(&peer),
+            latency_secs,
+        );
+        self.peer_to_state
+            .entry(peer)
+            .or_default()
+            .update_latency(latency_secs);
+    }
+
+    /// Chooses one of the given peers to send a request to. If latency aware peer
+    /// selection is enabled, peers are weighted by their score and latency.
+    /// Otherwise, a peer is chosen uniformly at random.
+    pub fn choose_peer(&self, peers: &[PeerNetworkId]) -> Option<PeerNetworkId> {
+        let mut rng = rand::thread_rng();
+        if !self.data_client_config.latency_aware_peer_selection {
+            return peers.choose(&mut rng).copied();
+        }
+
+        let weighted_peers = self.get_selection_weights(peers);
+        weighted_peers
+            .choose_weighted(&mut rng, |(_, weight)| *weight)
+            .ok()
+            .map(|(peer, _)| *peer)
+    }
+
+    /// Returns the selection weights of the given peers. The latency of each peer
+    /// is weighted relative to the best peer, so that the weights don't
+    /// depend on the network conditions of the node. Peers that haven't been measured
+    /// yet are considered as good as the best peer, so that they get measured quickly.
+    fn get_selection_weights(&self, peers: &[PeerNetworkId]) -> Vec<(PeerNetworkId, f64)> {
+        let peer_states: Vec<_> = peers
+            .iter()
+            .map(|peer| (*peer, self.peer_to_state.get(peer)))
+            .collect();
+
+        // Identify the best latency of the peers
+        let min_latency_secs = peer_states
+            .iter()
+            .filter_map(|(_, state)| state.and_then(|state| state.average_latency_secs))
+            .fold(f64::INFINITY, f64::min);
+
+        // Weight each peer relative to the best peer
+        peer_states
+            .into_iter()
+            .map(|(peer, state)| {
+                let weight = state.map_or(STARTING_SCORE / MAX_SCORE, |state| {
+                    state.selection_weight(min_latency_secs)
+                });
+                (peer, f64::max(weight, MIN_SELECTION_WEIGHT))
+            })
+            .collect()
+    }
+
+    /// Returns the metrics bucket of the given peer
+    pub fn get_peer_bucket(&self, peer: &PeerNetworkId) -> &'static str {
+        if self.is_priority_peer(peer) {
+            PRIORITIZED_PEER
+        } else {
+            REGULAR_PEER
+        }
+    }
+
     /// Returns the number of in-flight priority polls
     pub fn num_in_flight_priority_polls(&self) -> u64 {
         self.in_flight_priority_polls.len() as u64
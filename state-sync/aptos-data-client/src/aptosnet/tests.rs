@@ -13,7 +13,8 @@ use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    transaction::{TransactionListWithProof, Version},
+    proof::TransactionInfoListWithProof,
+    transaction::{Transaction, TransactionListWithProof, TransactionOutputListWithProof, Version},
     PeerId,
 };
 use channel::{aptos_channel, message_queues::QueueStyle};
@@ -42,6 +43,9 @@ use storage_service_types::{
     StorageServiceError, StorageServiceMessage,
 };
 
+/// The number of peer selections to perform when testing weighted selection
+const NUM_PEER_SELECTIONS: u64 = 1000;
+
 fn mock_ledger_info(version: Version) -> LedgerInfoWithSignatures {
     LedgerInfoWithSignatures::new(
         LedgerInfo::new(
@@ -874,6 +878,138 @@ async fn all_peer_request_selection() {
     assert!(peer_for_request == priority_peer_1 || peer_for_request == priority_peer_2);
 }
 
+#[tokio::test]
+async fn latency_aware_peer_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let data_client_config = AptosDataClientConfig {
+        latency_aware_peer_selection: true,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add two regular peers that advertise the same data
+    let fast_peer = mock_network.add_peer(false);
+    let slow_peer = mock_network.add_peer(false);
+    client.update_summary(fast_peer, mock_storage_summary(100));
+    client.update_summary(slow_peer, mock_storage_summary(100));
+
+    // Update the latencies of the peers
+    client.update_latency(fast_peer, Duration::from_millis(10));
+    client.update_latency(slow_peer, Duration::from_millis(1000));
+
+    // Verify the fast peer is selected much more often than the slow peer
+    let storage_request = StorageServiceRequest::new(
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+        }),
+        false,
+    );
+    let num_fast_peer_selections = count_peer_selections(&client, &storage_request, fast_peer);
+    assert!(num_fast_peer_selections > NUM_PEER_SELECTIONS * 9 / 10);
+}
+
+#[tokio::test]
+async fn latency_aware_peer_selection_ignores_throughput() {
+    ::aptos_logger::Logger::init_for_testing();
+    let data_client_config = AptosDataClientConfig {
+        latency_aware_peer_selection: true,
+        ..Default::default()
+    };
+    let (mut mock_network, mock_time, client, _) =
+        MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add two regular peers that advertise the same data and have the same latency
+    let big_chunk_peer = mock_network.add_peer(false);
+    let small_chunk_peer = mock_network.add_peer(false);
+    for peer in [big_chunk_peer, small_chunk_peer] {
+        client.update_summary(peer, mock_storage_summary(200));
+        client.update_latency(peer, Duration::from_millis(100));
+    }
+    client.update_global_summary_cache();
+
+    // Spawn a handler that responds after the same delay for both peers, but with
+    // much more data from one of them (i.e., at a much higher throughput)
+    tokio::spawn(async move {
+        while let Some((peer, _, _, response_sender)) = mock_network.next_request().await {
+            let num_transactions = if peer == big_chunk_peer.peer_id() {
+                1_000
+            } else {
+                1
+            };
+            let transactions =
+                vec![Transaction::StateCheckpoint(HashValue::zero()); num_transactions];
+            let transaction_list = TransactionListWithProof::new(
+                transactions,
+                None,
+                Some(0),
+                TransactionInfoListWithProof::new_empty(),
+            );
+            let data_response = DataResponse::TransactionsWithProof(transaction_list);
+            mock_time.advance_async(Duration::from_millis(100)).await;
+            response_sender.send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+        }
+    });
+
+    // Send a bunch of requests to the peers
+    let request_timeout = client.data_client_config.response_timeout_ms;
+    for _ in 0..20 {
+        client
+            .get_transactions_with_proof(200, 0, 200, false, request_timeout)
+            .await
+            .unwrap();
+    }
+
+    // Verify both peers are still selected equally often
+    let storage_request = StorageServiceRequest::new(
+        DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+            proof_version: 200,
+            start_version: 0,
+            end_version: 200,
+            include_events: false,
+        }),
+        false,
+    );
+    let num_big_chunk_peer_selections =
+        count_peer_selections(&client, &storage_request, big_chunk_peer);
+    assert!(num_big_chunk_peer_selections > NUM_PEER_SELECTIONS / 4);
+    assert!(num_big_chunk_peer_selections < NUM_PEER_SELECTIONS * 3 / 4);
+}
+
+#[tokio::test]
+async fn latency_unaware_peer_selection() {
+    ::aptos_logger::Logger::init_for_testing();
+    let data_client_config = AptosDataClientConfig {
+        latency_aware_peer_selection: false,
+        ..Default::default()
+    };
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add two regular peers that advertise the same data
+    let fast_peer = mock_network.add_peer(false);
+    let slow_peer = mock_network.add_peer(false);
+    client.update_summary(fast_peer, mock_storage_summary(100));
+    client.update_summary(slow_peer, mock_storage_summary(100));
+
+    // Update the latencies of the peers
+    client.update_latency(fast_peer, Duration::from_millis(10));
+    client.update_latency(slow_peer, Duration::from_millis(1000));
+
+    // Verify both peers are selected (the latencies are ignored)
+    let storage_request = StorageServiceRequest::new(
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+        }),
+        false,
+    );
+    let num_fast_peer_selections = count_peer_selections(&client, &storage_request, fast_peer);
+    assert!(num_fast_peer_selections > NUM_PEER_SELECTIONS / 4);
+    assert!(num_fast_peer_selections < NUM_PEER_SELECTIONS * 3 / 4);
+}
+
 #[tokio::test]
 async fn validator_peer_prioritization() {
     ::aptos_logger::Logger::init_for_testing();
@@ -1096,6 +1232,56 @@ async fn bad_peer_is_eventually_banned_callback() {
         .contains(&CompleteDataRange::new(0, 200).unwrap()));
 }
 
+#[tokio::test]
+async fn bad_peer_is_eventually_banned_transactions_or_outputs() {
+    ::aptos_logger::Logger::init_for_testing();
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, None, None);
+
+    let bad_peer = mock_network.add_peer(true);
+
+    // Bypass poller and just add the storage summaries directly.
+    // Bad peer advertises txns and outputs 0 -> 200.
+    client.update_summary(bad_peer, mock_storage_summary(200));
+    client.update_global_summary_cache();
+
+    // Spawn a handler that responds with both transactions and outputs (which is invalid)
+    tokio::spawn(async move {
+        while let Some((_, _, _, response_sender)) = mock_network.next_request().await {
+            let data_response = DataResponse::TransactionsOrOutputsWithProof((
+                Some(TransactionListWithProof::new_empty()),
+                Some(TransactionOutputListWithProof::new_empty()),
+            ));
+            response_sender.send(Ok(StorageServiceResponse::new(data_response, true).unwrap()));
+        }
+    });
+
+    // Send a bunch of requests to the bad peer
+    let request_timeout = client.data_client_config.response_timeout_ms;
+    let mut seen_data_unavailable_err = false;
+    for _ in 0..50 {
+        let result = client
+            .get_transactions_or_outputs_with_proof(200, 200, 200, false, request_timeout)
+            .await;
+
+        // While the score is still decreasing, we should see invalid responses.
+        // Once we see a `DataIsUnavailable` error, we should only see that error.
+        if !seen_data_unavailable_err {
+            match result {
+                Err(Error::InvalidResponse(_)) => {}
+                Err(Error::DataIsUnavailable(_)) => {
+                    seen_data_unavailable_err = true;
+                }
+                _ => panic!("unexpected result: {:?}", result),
+            }
+        } else {
+            assert_matches!(result, Err(Error::DataIsUnavailable(_)));
+        }
+    }
+
+    // Peer should eventually get ignored
+    assert!(seen_data_unavailable_err);
+}
+
 #[tokio::test]
 async fn compression_mismatch_disabled() {
     ::aptos_logger::Logger::init_for_testing();
@@ -1418,6 +1604,17 @@ async fn optimal_chunk_size_calculations() {
     assert_eq!(400, optimal_chunk_sizes.transaction_output_chunk_size);
 }
 
+/// Returns the number of times the given peer is chosen to service the request
+fn count_peer_selections(
+    client: &AptosNetDataClient,
+    request: &StorageServiceRequest,
+    peer: PeerNetworkId,
+) -> u64 {
+    (0..NUM_PEER_SELECTIONS)
+        .filter(|_| client.choose_peer_for_request(request).unwrap() == peer)
+        .count() as u64
+}
+
 /// A helper method that fetches peers to poll depending on the peer priority
 fn fetch_peer_to_poll(
     client: AptosNetDataClient,
@@ -75,6 +75,7 @@ pub trait EventNotificationSender: Send {
 pub struct EventSubscriptionService {
     // Event subscription registry
     event_key_subscriptions: HashMap<EventKey, HashSet<SubscriptionId>>,
+    all_events_subscriptions: HashSet<SubscriptionId>,
     subscription_id_to_event_subscription: HashMap<SubscriptionId, EventSubscription>,
 
     // Reconfig subscription registry
@@ -94,6 +95,7 @@ impl EventSubscriptionService {
     pub fn new(config_registry: &[ConfigID], storage: Arc<RwLock<DbReaderWriter>>) -> Self {
         Self {
             event_key_subscriptions: HashMap::new(),
+            all_events_subscriptions: HashSet::new(),
             subscription_id_to_event_subscription: HashMap::new(),
             reconfig_subscriptions: HashMap::new(),
             config_registry: config_registry.to_vec(),
@@ -117,6 +119,37 @@ impl EventSubscriptionService {
             return Err(Error::CannotSubscribeToZeroEventKeys);
         }
 
+        let (subscription_id, event_notification_listener) = self.create_event_subscription();
+
+        // Update the event key subscriptions to include the new subscription
+        for event_key in event_keys {
+            self.event_key_subscriptions
+                .entry(event_key)
+                .and_modify(|subscriptions| {
+                    subscriptions.insert(subscription_id);
+                })
+                .or_insert_with(|| HashSet::from_iter(vec![subscription_id].iter().cloned()));
+        }
+
+        Ok(event_notification_listener)
+    }
+
+    /// Returns an EventNotificationListener that will be sent a notification
+    /// containing all events committed on-chain, regardless of their keys. This
+    /// is useful for subscribers that do not know the event keys they are
+    /// interested in upfront (e.g., clients streaming events from the API).
+    /// Note: if the notification buffer fills up too quickly, older
+    /// notifications will be dropped. As such, it is the responsibility of the
+    /// subscriber to ensure notifications are processed in a timely manner.
+    pub fn subscribe_to_all_events(&mut self) -> Result<EventNotificationListener, Error> {
+        let (subscription_id, event_notification_listener) = self.create_event_subscription();
+        self.all_events_subscriptions.insert(subscription_id);
+
+        Ok(event_notification_listener)
+    }
+
+    /// Creates and stores a new event subscription, returning its ID and listener
+    fn create_event_subscription(&mut self) -> (SubscriptionId, EventNotificationListener) {
         let (notification_sender, notification_receiver) =
             aptos_channel::new(QueueStyle::KLAST, EVENT_NOTIFICATION_CHANNEL_SIZE, None);
 
@@ -138,19 +171,12 @@ impl EventSubscriptionService {
             );
         }
 
-        // Update the event key subscriptions to include the new subscription
-        for event_key in event_keys {
-            self.event_key_subscriptions
-                .entry(event_key)
-                .and_modify(|subscriptions| {
-                    subscriptions.insert(subscription_id);
-                })
-                .or_insert_with(|| HashSet::from_iter(vec![subscription_id].iter().cloned()));
-        }
-
-        Ok(EventNotificationListener {
-            notification_receiver,
-        })
+        (
+            subscription_id,
+            EventNotificationListener {
+                notification_receiver,
+            },
+        )
     }
 
     /// Returns a ReconfigNotificationListener that can be monitored for
@@ -204,20 +230,25 @@ impl EventSubscriptionService {
             let event_key = event.key();
 
             // Process all subscriptions for the current event
-            if let Some(subscription_ids) = self.event_key_subscriptions.get(event_key) {
-                // Add the event to the subscription's pending event buffer
-                // and store the subscriptions that will need to notified once all
-                // events have been processed.
-                for subscription_id in subscription_ids.iter() {
-                    if let Some(event_subscription) = self
-                        .subscription_id_to_event_subscription
-                        .get_mut(subscription_id)
-                    {
-                        event_subscription.buffer_event(event.clone());
-                        event_subscription_ids_to_notify.insert(*subscription_id);
-                    } else {
-                        return Err(Error::MissingEventSubscription(*subscription_id));
-                    }
+            let subscription_ids = self
+                .event_key_subscriptions
+                .get(event_key)
+                .into_iter()
+                .flatten()
+                .chain(self.all_events_subscriptions.iter());
+
+            // Add the event to the subscription's pending event buffer
+            // and store the subscriptions that will need to notified once all
+            // events have been processed.
+            for subscription_id in subscription_ids {
+                if let Some(event_subscription) = self
+                    .subscription_id_to_event_subscription
+                    .get_mut(subscription_id)
+                {
+                    event_subscription.buffer_event(event.clone());
+                    event_subscription_ids_to_notify.insert(*subscription_id);
+                } else {
+                    return Err(Error::MissingEventSubscription(*subscription_id));
                 }
             }
 
@@ -293,6 +293,48 @@ fn test_event_notification_queuing() {
     verify_no_event_notifications(vec![&mut listener_1, &mut listener_2]);
 }
 
+#[test]
+fn test_all_events_subscribers() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Create several event keys
+    let event_key_1 = create_random_event_key();
+    let event_key_2 = create_random_event_key();
+
+    // Subscribe to all events and to event_key_1 only
+    let mut all_events_listener = event_service.subscribe_to_all_events().unwrap();
+    let mut listener_1 = event_service
+        .subscribe_to_events(vec![event_key_1])
+        .unwrap();
+
+    // Notify the subscription service of new events (with event_key_1 and event_key_2)
+    let version = 10;
+    let event_1 = create_test_event(event_key_1);
+    let event_2 = create_test_event(event_key_2);
+    notify_events(
+        &mut event_service,
+        version,
+        vec![event_1.clone(), event_2.clone()],
+    );
+
+    // Verify the all events listener gets both events and listener 1 only gets its own
+    verify_event_notification_received(
+        vec![&mut all_events_listener],
+        version,
+        vec![event_1.clone(), event_2.clone()],
+    );
+    verify_event_notification_received(vec![&mut listener_1], version, vec![event_1]);
+
+    // Notify the subscription service of a new event (with event_key_2)
+    let version = 11;
+    notify_events(&mut event_service, version, vec![event_2.clone()]);
+
+    // Verify only the all events listener gets the event
+    verify_event_notification_received(vec![&mut all_events_listener], version, vec![event_2]);
+    verify_no_event_notifications(vec![&mut all_events_listener, &mut listener_1]);
+}
+
 #[test]
 fn test_event_subscribers() {
     // Create subscription service and mock database
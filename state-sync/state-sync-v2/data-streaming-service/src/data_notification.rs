@@ -38,11 +38,13 @@ pub enum DataPayload {
 pub enum DataClientRequest {
     EpochEndingLedgerInfos(EpochEndingLedgerInfosRequest),
     NewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest),
+    NewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest),
     NewTransactionsWithProof(NewTransactionsWithProofRequest),
     NumberOfStates(NumberOfStatesRequest),
     StateValuesWithProof(StateValuesWithProofRequest),
     TransactionsWithProof(TransactionsWithProofRequest),
     TransactionOutputsWithProof(TransactionOutputsWithProofRequest),
+    TransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest),
 }
 
 impl DataClientRequest {
@@ -51,11 +53,13 @@ impl DataClientRequest {
         match self {
             Self::EpochEndingLedgerInfos(_) => "epoch_ending_ledger_infos",
             Self::NewTransactionOutputsWithProof(_) => "new_transaction_outputs_with_proof",
+            Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::NewTransactionsWithProof(_) => "new_transactions_with_proof",
             Self::NumberOfStates(_) => "number_of_states",
             Self::StateValuesWithProof(_) => "state_values_with_proof",
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::TransactionOutputsWithProof(_) => "transaction_outputs_with_proof",
+            Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
         }
     }
 }
@@ -90,6 +94,14 @@ pub struct NewTransactionOutputsWithProofRequest {
     pub known_epoch: Epoch,
 }
 
+/// A client request for fetching new transactions or outputs with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewTransactionsOrOutputsWithProofRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub include_events: bool,
+}
+
 /// A client request for fetching the number of states at a version.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NumberOfStatesRequest {
@@ -113,6 +125,15 @@ pub struct TransactionOutputsWithProofRequest {
     pub proof_version: Version,
 }
 
+/// A client request for fetching transactions or outputs with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransactionsOrOutputsWithProofRequest {
+    pub start_version: Version,
+    pub end_version: Version,
+    pub proof_version: Version,
+    pub include_events: bool,
+}
+
 /// A pending client response where data has been requested from the
 /// network and will be available in `client_response` when received.
 pub struct PendingClientResponse {
@@ -6,9 +6,10 @@ use crate::{
     data_notification,
     data_notification::{
         DataClientRequest, DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
-        NewTransactionOutputsWithProofRequest, NewTransactionsWithProofRequest, NotificationId,
-        NumberOfStatesRequest, StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
-        TransactionsWithProofRequest,
+        NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
+        NewTransactionsWithProofRequest, NotificationId, NumberOfStatesRequest,
+        StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
+        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
@@ -728,6 +729,13 @@ fn sanity_check_client_response(
                 ResponsePayload::NewTransactionsWithProof(_)
             )
         }
+        DataClientRequest::NewTransactionsOrOutputsWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::NewTransactionsWithProof(_)
+                    | ResponsePayload::NewTransactionOutputsWithProof(_)
+            )
+        }
         DataClientRequest::NumberOfStates(_) => {
             matches!(
                 data_client_response.payload,
@@ -752,6 +760,13 @@ fn sanity_check_client_response(
                 ResponsePayload::TransactionOutputsWithProof(_)
             )
         }
+        DataClientRequest::TransactionsOrOutputsWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::TransactionsWithProof(_)
+                    | ResponsePayload::TransactionOutputsWithProof(_)
+            )
+        }
     }
 }
 
@@ -808,6 +823,14 @@ fn spawn_request_task<T: AptosDataClient + Send + Clone + 'static>(
                 )
                 .await
             }
+            DataClientRequest::NewTransactionsOrOutputsWithProof(request) => {
+                get_new_transactions_or_outputs_with_proof(
+                    aptos_data_client,
+                    request,
+                    request_timeout_ms,
+                )
+                .await
+            }
             DataClientRequest::NumberOfStates(request) => {
                 get_number_of_states(aptos_data_client, request, request_timeout_ms).await
             }
@@ -821,6 +844,14 @@ fn spawn_request_task<T: AptosDataClient + Send + Clone + 'static>(
             DataClientRequest::TransactionsWithProof(request) => {
                 get_transactions_with_proof(aptos_data_client, request, request_timeout_ms).await
             }
+            DataClientRequest::TransactionsOrOutputsWithProof(request) => {
+                get_transactions_or_outputs_with_proof(
+                    aptos_data_client,
+                    request,
+                    request_timeout_ms,
+                )
+                .await
+            }
         };
 
         // Increment the appropriate counter depending on the response
@@ -903,6 +934,32 @@ async fn get_new_transactions_with_proof<T: AptosDataClient + Send + Clone + 'st
         .map(|response| response.map(ResponsePayload::from))
 }
 
+async fn get_new_transactions_or_outputs_with_proof<T: AptosDataClient + Send + Clone + 'static>(
+    aptos_data_client: T,
+    request: NewTransactionsOrOutputsWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::Error> {
+    let client_response = aptos_data_client.get_new_transactions_or_outputs_with_proof(
+        request.known_version,
+        request.known_epoch,
+        request.include_events,
+        request_timeout_ms,
+    );
+    let (context, ((transactions, outputs), ledger_info)) = client_response.await?.into_parts();
+
+    // Return the payload for the transactions or outputs (whichever was sent)
+    let response_payload = match (transactions, outputs) {
+        (Some(transactions), None) => {
+            ResponsePayload::NewTransactionsWithProof((transactions, ledger_info))
+        }
+        (None, Some(outputs)) => {
+            ResponsePayload::NewTransactionOutputsWithProof((outputs, ledger_info))
+        }
+        _ => return Err(invalid_transactions_or_outputs_response(context)),
+    };
+    Ok(Response::new(context, response_payload))
+}
+
 async fn get_number_of_states<T: AptosDataClient + Send + Clone + 'static>(
     aptos_data_client: T,
     request: NumberOfStatesRequest,
@@ -948,6 +1005,40 @@ async fn get_transactions_with_proof<T: AptosDataClient + Send + Clone + 'static
         .map(|response| response.map(ResponsePayload::from))
 }
 
+async fn get_transactions_or_outputs_with_proof<T: AptosDataClient + Send + Clone + 'static>(
+    aptos_data_client: T,
+    request: TransactionsOrOutputsWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::Error> {
+    let client_response = aptos_data_client.get_transactions_or_outputs_with_proof(
+        request.proof_version,
+        request.start_version,
+        request.end_version,
+        request.include_events,
+        request_timeout_ms,
+    );
+    let (context, (transactions, outputs)) = client_response.await?.into_parts();
+
+    // Return the payload for the transactions or outputs (whichever was sent)
+    let response_payload = match (transactions, outputs) {
+        (Some(transactions), None) => ResponsePayload::TransactionsWithProof(transactions),
+        (None, Some(outputs)) => ResponsePayload::TransactionOutputsWithProof(outputs),
+        _ => return Err(invalid_transactions_or_outputs_response(context)),
+    };
+    Ok(Response::new(context, response_payload))
+}
+
+/// Notifies the data client of a response that does not contain exactly
+/// one of transactions or outputs, and returns the corresponding error.
+fn invalid_transactions_or_outputs_response(context: ResponseContext) -> aptos_data_client::Error {
+    context
+        .response_callback
+        .notify_bad_response(ResponseError::InvalidPayloadDataType);
+    aptos_data_client::Error::InvalidResponse(
+        "Expected either transactions or outputs in the response!".into(),
+    )
+}
+
 /// Returns true iff the given request is a subscription request
 fn is_subscription_request(request: &DataClientRequest) -> bool {
     matches!(request, DataClientRequest::NewTransactionsWithProof(_))
@@ -955,4 +1046,8 @@ fn is_subscription_request(request: &DataClientRequest) -> bool {
             request,
             DataClientRequest::NewTransactionOutputsWithProof(_)
         )
+        || matches!(
+            request,
+            DataClientRequest::NewTransactionsOrOutputsWithProof(_)
+        )
 }
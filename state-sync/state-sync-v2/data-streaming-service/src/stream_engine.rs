@@ -5,13 +5,15 @@ use crate::{
     data_notification::{
         DataClientRequest,
         DataClientRequest::{
-            EpochEndingLedgerInfos, NewTransactionOutputsWithProof, NewTransactionsWithProof,
-            NumberOfStates, StateValuesWithProof, TransactionOutputsWithProof,
+            EpochEndingLedgerInfos, NewTransactionOutputsWithProof,
+            NewTransactionsOrOutputsWithProof, NewTransactionsWithProof, NumberOfStates,
+            StateValuesWithProof, TransactionOutputsWithProof, TransactionsOrOutputsWithProof,
             TransactionsWithProof,
         },
         DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
-        NewTransactionOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        NumberOfStatesRequest, StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
+        NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
+        NewTransactionsWithProofRequest, NumberOfStatesRequest, StateValuesWithProofRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
     error::Error,
@@ -121,6 +123,9 @@ impl StreamEngine {
             StreamRequest::ContinuouslyStreamTransactions(_) => {
                 Ok(ContinuousTransactionStreamEngine::new(stream_request)?.into())
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                Ok(ContinuousTransactionStreamEngine::new(stream_request)?.into())
+            }
             StreamRequest::GetAllStates(request) => Ok(StateStreamEngine::new(request)?.into()),
             StreamRequest::GetAllEpochEndingLedgerInfos(request) => {
                 Ok(EpochEndingStreamEngine::new(request, advertised_data)?.into())
@@ -131,6 +136,9 @@ impl StreamEngine {
             StreamRequest::GetAllTransactions(_) => {
                 Ok(TransactionStreamEngine::new(stream_request)?.into())
             }
+            StreamRequest::GetAllTransactionsOrOutputs(_) => {
+                Ok(TransactionStreamEngine::new(stream_request)?.into())
+            }
             _ => Err(Error::UnsupportedRequestEncountered(format!(
                 "Stream request not supported: {:?}",
                 stream_request
@@ -383,6 +391,21 @@ impl ContinuousTransactionStreamEngine {
                     stream_is_complete: false,
                 })
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                let (next_version, next_epoch) = Self::calculate_next_version_and_epoch(
+                    request.known_version,
+                    request.known_epoch,
+                )?;
+                Ok(ContinuousTransactionStreamEngine {
+                    request: stream_request.clone(),
+                    current_target_ledger_info: None,
+                    end_of_epoch_requested: false,
+                    subscription_requested: false,
+                    next_stream_version_and_epoch: (next_version, next_epoch),
+                    next_request_version_and_epoch: (next_version, next_epoch),
+                    stream_is_complete: false,
+                })
+            }
             request => invalid_stream_request!(request),
         }
     }
@@ -413,6 +436,11 @@ impl ContinuousTransactionStreamEngine {
                     return Ok(Some(target.clone()));
                 }
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                if let Some(target) = &request.target {
+                    return Ok(Some(target.clone()));
+                }
+            }
             request => invalid_stream_request!(request),
         };
 
@@ -539,6 +567,15 @@ impl ContinuousTransactionStreamEngine {
                     },
                 )
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                DataClientRequest::NewTransactionsOrOutputsWithProof(
+                    NewTransactionsOrOutputsWithProofRequest {
+                        known_version,
+                        known_epoch,
+                        include_events: request.include_events,
+                    },
+                )
+            }
             request => invalid_stream_request!(request),
         };
         Ok(data_client_request)
@@ -624,6 +661,13 @@ impl ContinuousTransactionStreamEngine {
                     }
                 }
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                if let Some(target) = &request.target {
+                    if request_end_version == target.ledger_info().version() {
+                        self.stream_is_complete = true;
+                    }
+                }
+            }
             request => invalid_stream_request!(request),
         };
 
@@ -692,6 +736,19 @@ impl ContinuousTransactionStreamEngine {
                     }
                 }
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                for client_request in client_requests {
+                    match client_request {
+                        DataClientRequest::TransactionsOrOutputsWithProof(request) => {
+                            self.update_request_version_and_epoch(
+                                request.end_version,
+                                target_ledger_info,
+                            )?;
+                        }
+                        request => invalid_client_request!(request, self),
+                    }
+                }
+            }
             request => invalid_stream_request!(request),
         }
 
@@ -768,6 +825,14 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                         .optimal_chunk_sizes
                         .transaction_output_chunk_size
                 }
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => cmp::min(
+                    global_data_summary
+                        .optimal_chunk_sizes
+                        .transaction_chunk_size,
+                    global_data_summary
+                        .optimal_chunk_sizes
+                        .transaction_output_chunk_size,
+                ),
                 request => invalid_stream_request!(request),
             };
             let client_requests = create_data_client_requests(
@@ -795,6 +860,20 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
             StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
                 &advertised_data.transaction_outputs
             }
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                // Both transactions and outputs must be available (the
+                // server may choose to respond with either).
+                let (next_request_version, _) = self.next_request_version_and_epoch;
+                return AdvertisedData::contains_range(
+                    next_request_version,
+                    next_request_version,
+                    &advertised_data.transactions,
+                ) && AdvertisedData::contains_range(
+                    next_request_version,
+                    next_request_version,
+                    &advertised_data.transaction_outputs,
+                );
+            }
             request => invalid_stream_request!(request),
         };
 
@@ -846,6 +925,17 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 (LogSchema::new(LogEntry::RequestTimeout)
                     .message("Subscription request for new transaction outputs timed out!"))
             );
+        } else if matches!(
+            self.request,
+            StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_)
+        ) && matches!(
+            client_request,
+            DataClientRequest::NewTransactionsOrOutputsWithProof(_)
+        ) {
+            info!(
+                (LogSchema::new(LogEntry::RequestTimeout)
+                    .message("Subscription request for new transactions or outputs timed out!"))
+            );
         } else {
             return Err(Error::UnexpectedErrorEncountered(format!("Received a subscription request timeout but the request did not match the expected type for the stream! Request: {:?}, Stream: {:?}", client_request, self.request)));
         }
@@ -894,6 +984,17 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 }
                 request => invalid_stream_request!(request),
             },
+            NewTransactionsOrOutputsWithProof(request) => match &self.request {
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                    let data_notification = self.create_notification_for_subscription_data(
+                        request.known_version,
+                        client_response_payload,
+                        notification_id_generator,
+                    )?;
+                    Ok(Some(data_notification))
+                }
+                request => invalid_stream_request!(request),
+            },
             TransactionsWithProof(request) => match &self.request {
                 StreamRequest::ContinuouslyStreamTransactions(_) => {
                     let data_notification = self.create_notification_for_continuous_data(
@@ -918,6 +1019,18 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 }
                 request => invalid_stream_request!(request),
             },
+            TransactionsOrOutputsWithProof(request) => match &self.request {
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                    let data_notification = self.create_notification_for_continuous_data(
+                        request.start_version,
+                        request.end_version,
+                        client_response_payload,
+                        notification_id_generator,
+                    )?;
+                    Ok(Some(data_notification))
+                }
+                request => invalid_stream_request!(request),
+            },
             request => invalid_client_request!(request, self),
         }
     }
@@ -1105,6 +1218,12 @@ impl TransactionStreamEngine {
                 next_request_version: request.start_version,
                 stream_is_complete: false,
             }),
+            StreamRequest::GetAllTransactionsOrOutputs(request) => Ok(TransactionStreamEngine {
+                request: stream_request.clone(),
+                next_stream_version: request.start_version,
+                next_request_version: request.start_version,
+                stream_is_complete: false,
+            }),
             request => invalid_stream_request!(request),
         }
     }
@@ -1166,6 +1285,16 @@ impl TransactionStreamEngine {
                     }
                 }
             }
+            StreamRequest::GetAllTransactionsOrOutputs(_) => {
+                for client_request in client_requests.iter() {
+                    match client_request {
+                        TransactionsOrOutputsWithProof(request) => {
+                            self.update_request_version(request.end_version)?;
+                        }
+                        request => invalid_client_request!(request, self),
+                    }
+                }
+            }
             request => invalid_stream_request!(request),
         }
 
@@ -1192,6 +1321,17 @@ impl DataStreamEngine for TransactionStreamEngine {
                     .optimal_chunk_sizes
                     .transaction_output_chunk_size,
             ),
+            StreamRequest::GetAllTransactionsOrOutputs(request) => (
+                request.end_version,
+                cmp::min(
+                    global_data_summary
+                        .optimal_chunk_sizes
+                        .transaction_chunk_size,
+                    global_data_summary
+                        .optimal_chunk_sizes
+                        .transaction_output_chunk_size,
+                ),
+            ),
             request => invalid_stream_request!(request),
         };
 
@@ -1216,6 +1356,19 @@ impl DataStreamEngine for TransactionStreamEngine {
             StreamRequest::GetAllTransactionOutputs(request) => {
                 (request.end_version, &advertised_data.transaction_outputs)
             }
+            StreamRequest::GetAllTransactionsOrOutputs(request) => {
+                // Both transactions and outputs must be available (the
+                // server may choose to respond with either).
+                return AdvertisedData::contains_range(
+                    self.next_stream_version,
+                    request.end_version,
+                    &advertised_data.transactions,
+                ) && AdvertisedData::contains_range(
+                    self.next_stream_version,
+                    request.end_version,
+                    &advertised_data.transaction_outputs,
+                );
+            }
             request => invalid_stream_request!(request),
         };
         AdvertisedData::contains_range(
@@ -1258,6 +1411,17 @@ impl DataStreamEngine for TransactionStreamEngine {
                 }
                 request => invalid_client_request!(request, self),
             },
+            StreamRequest::GetAllTransactionsOrOutputs(stream_request) => match client_request {
+                TransactionsOrOutputsWithProof(request) => {
+                    let stream_end_version = stream_request.end_version;
+                    self.update_stream_version(
+                        request.start_version,
+                        request.end_version,
+                        stream_end_version,
+                    )?;
+                }
+                request => invalid_client_request!(request, self),
+            },
             request => invalid_stream_request!(request),
         }
 
@@ -1380,6 +1544,16 @@ fn create_data_client_request(
                         },
                     )
                 }
+                StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
+                    DataClientRequest::TransactionsOrOutputsWithProof(
+                        TransactionsOrOutputsWithProofRequest {
+                            start_version: start_index,
+                            end_version: end_index,
+                            proof_version: target_ledger_info_version,
+                            include_events: request.include_events,
+                        },
+                    )
+                }
                 request => invalid_stream_request!(request),
             }
         }
@@ -1405,6 +1579,16 @@ fn create_data_client_request(
                     proof_version: request.proof_version,
                 })
             }
+            StreamRequest::GetAllTransactionsOrOutputs(request) => {
+                DataClientRequest::TransactionsOrOutputsWithProof(
+                    TransactionsOrOutputsWithProofRequest {
+                        start_version: start_index,
+                        end_version: end_index,
+                        proof_version: request.proof_version,
+                        include_events: request.include_events,
+                    },
+                )
+            }
             request => invalid_stream_request!(request),
         },
     }
@@ -68,6 +68,19 @@ pub trait DataStreamingClient {
         include_events: bool,
     ) -> Result<DataStreamListener, Error>;
 
+    /// Fetches all transactions or outputs with proofs from `start_version`
+    /// to `end_version` (inclusive) at the specified `proof_version`. Each
+    /// chunk contains either transactions or outputs (depending on what the
+    /// network returns). If `include_events` is true, events are also
+    /// included in the transaction proofs.
+    async fn get_all_transactions_or_outputs(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        proof_version: Version,
+        include_events: bool,
+    ) -> Result<DataStreamListener, Error>;
+
     /// Continuously streams transaction outputs with proofs as the blockchain
     /// grows. The stream starts at `known_version + 1` (inclusive) and
     /// `known_epoch`, where the `known_epoch` is expected to be the epoch
@@ -105,6 +118,25 @@ pub trait DataStreamingClient {
         target: Option<LedgerInfoWithSignatures>,
     ) -> Result<DataStreamListener, Error>;
 
+    /// Continuously streams transactions or outputs with proofs as the
+    /// blockchain grows. The stream starts at `known_version + 1` (inclusive)
+    /// and `known_epoch`, where the `known_epoch` is expected to be the epoch
+    /// that contains `known_version + 1`, i.e., any epoch change at
+    /// `known_version` must be noted by the client. Each chunk contains either
+    /// transactions or outputs (depending on what the network returns).
+    ///
+    /// If `include_events` is true, events are also included in the proofs.
+    ///
+    /// Note: if a `target` is provided, the stream will terminate once it reaches
+    /// the target. Otherwise, it will continue indefinitely.
+    async fn continuously_stream_transactions_or_outputs(
+        &self,
+        start_version: Version,
+        start_epoch: Epoch,
+        include_events: bool,
+        target: Option<LedgerInfoWithSignatures>,
+    ) -> Result<DataStreamListener, Error>;
+
     /// Terminates the stream with the given stream id and (optionally) provides
     /// feedback about the notification and the termination reason.
     ///
@@ -138,8 +170,10 @@ pub enum StreamRequest {
     GetAllStates(GetAllStatesRequest),
     GetAllTransactions(GetAllTransactionsRequest),
     GetAllTransactionOutputs(GetAllTransactionOutputsRequest),
+    GetAllTransactionsOrOutputs(GetAllTransactionsOrOutputsRequest),
     ContinuouslyStreamTransactions(ContinuouslyStreamTransactionsRequest),
     ContinuouslyStreamTransactionOutputs(ContinuouslyStreamTransactionOutputsRequest),
+    ContinuouslyStreamTransactionsOrOutputs(ContinuouslyStreamTransactionsOrOutputsRequest),
     TerminateStream(TerminateStreamRequest),
 }
 
@@ -151,10 +185,14 @@ impl StreamRequest {
             Self::GetAllStates(_) => "get_all_states",
             Self::GetAllTransactions(_) => "get_all_transactions",
             Self::GetAllTransactionOutputs(_) => "get_all_transaction_outputs",
+            Self::GetAllTransactionsOrOutputs(_) => "get_all_transactions_or_outputs",
             Self::ContinuouslyStreamTransactions(_) => "continuously_stream_transactions",
             Self::ContinuouslyStreamTransactionOutputs(_) => {
                 "continuously_stream_transaction_outputs"
             }
+            Self::ContinuouslyStreamTransactionsOrOutputs(_) => {
+                "continuously_stream_transactions_or_outputs"
+            }
             Self::TerminateStream(_) => "terminate_stream",
         }
     }
@@ -190,6 +228,15 @@ pub struct GetAllTransactionOutputsRequest {
     pub proof_version: Version,
 }
 
+/// A client request for fetching all transactions or outputs with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GetAllTransactionsOrOutputsRequest {
+    pub start_version: Version,
+    pub end_version: Version,
+    pub proof_version: Version,
+    pub include_events: bool,
+}
+
 /// A client request for continuously streaming transactions with proofs
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ContinuouslyStreamTransactionsRequest {
@@ -207,6 +254,15 @@ pub struct ContinuouslyStreamTransactionOutputsRequest {
     pub target: Option<LedgerInfoWithSignatures>,
 }
 
+/// A client request for continuously streaming transactions or outputs with proofs
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContinuouslyStreamTransactionsOrOutputsRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub include_events: bool,
+    pub target: Option<LedgerInfoWithSignatures>,
+}
+
 /// A client request for terminating a stream and providing payload feedback.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TerminateStreamRequest {
@@ -329,6 +385,23 @@ impl DataStreamingClient for StreamingServiceClient {
         self.send_request_and_await_response(client_request).await
     }
 
+    async fn get_all_transactions_or_outputs(
+        &self,
+        start_version: u64,
+        end_version: u64,
+        proof_version: u64,
+        include_events: bool,
+    ) -> Result<DataStreamListener, Error> {
+        let client_request =
+            StreamRequest::GetAllTransactionsOrOutputs(GetAllTransactionsOrOutputsRequest {
+                start_version,
+                end_version,
+                proof_version,
+                include_events,
+            });
+        self.send_request_and_await_response(client_request).await
+    }
+
     async fn continuously_stream_transaction_outputs(
         &self,
         known_version: u64,
@@ -362,6 +435,24 @@ impl DataStreamingClient for StreamingServiceClient {
         self.send_request_and_await_response(client_request).await
     }
 
+    async fn continuously_stream_transactions_or_outputs(
+        &self,
+        known_version: u64,
+        known_epoch: u64,
+        include_events: bool,
+        target: Option<LedgerInfoWithSignatures>,
+    ) -> Result<DataStreamListener, Error> {
+        let client_request = StreamRequest::ContinuouslyStreamTransactionsOrOutputs(
+            ContinuouslyStreamTransactionsOrOutputsRequest {
+                known_version,
+                known_epoch,
+                include_events,
+                target,
+            },
+        );
+        self.send_request_and_await_response(client_request).await
+    }
+
     async fn terminate_stream_with_feedback(
         &self,
         data_stream_id: DataStreamId,
@@ -7,8 +7,9 @@ use crate::{
     error::Error,
     streaming_client::{
         new_streaming_service_client_listener_pair, ContinuouslyStreamTransactionOutputsRequest,
-        ContinuouslyStreamTransactionsRequest, DataStreamingClient,
-        GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest, GetAllTransactionOutputsRequest,
+        ContinuouslyStreamTransactionsOrOutputsRequest, ContinuouslyStreamTransactionsRequest,
+        DataStreamingClient, GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest,
+        GetAllTransactionOutputsRequest, GetAllTransactionsOrOutputsRequest,
         GetAllTransactionsRequest, NotificationAndFeedback, NotificationFeedback, StreamRequest,
         StreamingServiceListener, TerminateStreamRequest,
     },
@@ -137,6 +138,38 @@ fn test_get_all_transaction_outputs() {
     assert_ok!(response);
 }
 
+#[test]
+fn test_get_all_transactions_or_outputs() {
+    // Create a new streaming service client and listener
+    let (streaming_service_client, streaming_service_listener) =
+        new_streaming_service_client_listener_pair();
+
+    // Note the request we expect to receive on the streaming service side
+    let request_start_version = 101;
+    let request_end_version = 200;
+    let request_proof_version = 300;
+    let request_include_events = false;
+    let expected_request =
+        StreamRequest::GetAllTransactionsOrOutputs(GetAllTransactionsOrOutputsRequest {
+            start_version: request_start_version,
+            end_version: request_end_version,
+            proof_version: request_proof_version,
+            include_events: request_include_events,
+        });
+
+    // Spawn a new server thread to handle any transaction or output stream requests
+    let _handler = spawn_service_and_expect_request(streaming_service_listener, expected_request);
+
+    // Send a transaction or output stream request and verify we get a data stream listener
+    let response = block_on(streaming_service_client.get_all_transactions_or_outputs(
+        request_start_version,
+        request_end_version,
+        request_proof_version,
+        request_include_events,
+    ));
+    assert_ok!(response);
+}
+
 #[test]
 fn test_continuously_stream_transactions() {
     // Create a new streaming service client and listener
@@ -201,6 +234,41 @@ fn test_continuously_stream_transaction_outputs() {
     assert_ok!(response);
 }
 
+#[test]
+fn test_continuously_stream_transactions_or_outputs() {
+    // Create a new streaming service client and listener
+    let (streaming_service_client, streaming_service_listener) =
+        new_streaming_service_client_listener_pair();
+
+    // Note the request we expect to receive on the streaming service side
+    let known_version = 101;
+    let known_epoch = 2;
+    let include_events = false;
+    let target = Some(create_ledger_info(1000, 10, true));
+    let expected_request = StreamRequest::ContinuouslyStreamTransactionsOrOutputs(
+        ContinuouslyStreamTransactionsOrOutputsRequest {
+            known_version,
+            known_epoch,
+            include_events,
+            target: target.clone(),
+        },
+    );
+
+    // Spawn a new server thread to handle any continuous transaction or output stream requests
+    let _handler = spawn_service_and_expect_request(streaming_service_listener, expected_request);
+
+    // Send a continuous transaction or output stream request and verify we get a data stream listener
+    let response = block_on(
+        streaming_service_client.continuously_stream_transactions_or_outputs(
+            known_version,
+            known_epoch,
+            include_events,
+            target,
+        ),
+    );
+    assert_ok!(response);
+}
+
 #[test]
 fn test_terminate_stream() {
     // Create a new streaming service client and listener
@@ -618,6 +618,69 @@ async fn test_notifications_continuous_transactions_target() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_notifications_continuous_transactions_or_outputs() {
+    // Create a new streaming client and service
+    let streaming_client = create_streaming_client_and_service();
+
+    // Request a continuous transaction or output stream and get a data stream listener
+    let mut stream_listener = streaming_client
+        .continuously_stream_transactions_or_outputs(
+            MIN_ADVERTISED_TRANSACTION - 1,
+            MIN_ADVERTISED_EPOCH_END,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Read the data notifications from the stream and verify the payloads
+    let mut next_expected_epoch = MIN_ADVERTISED_EPOCH_END;
+    let mut next_expected_version = MIN_ADVERTISED_TRANSACTION;
+    loop {
+        if let Ok(data_notification) = get_data_notification(&mut stream_listener).await {
+            // Extract the ledger info, first version and number of versions
+            let (ledger_info_with_sigs, first_version, num_versions) =
+                match data_notification.data_payload {
+                    DataPayload::ContinuousTransactionsWithProof(
+                        ledger_info_with_sigs,
+                        transactions_with_proof,
+                    ) => (
+                        ledger_info_with_sigs,
+                        transactions_with_proof.first_transaction_version,
+                        transactions_with_proof.transactions.len() as u64,
+                    ),
+                    DataPayload::ContinuousTransactionOutputsWithProof(
+                        ledger_info_with_sigs,
+                        outputs_with_proof,
+                    ) => (
+                        ledger_info_with_sigs,
+                        outputs_with_proof.first_transaction_output_version,
+                        outputs_with_proof.transactions_and_outputs.len() as u64,
+                    ),
+                    data_payload => unexpected_payload_type!(data_payload),
+                };
+
+            // Verify the epoch of the ledger info
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            assert_eq!(ledger_info.epoch(), next_expected_epoch);
+
+            // Verify the start version matches the expected version
+            assert_eq!(Some(next_expected_version), first_version);
+            next_expected_version += num_versions;
+
+            // Update epochs if we've hit the epoch end
+            let last_version = first_version.unwrap() + num_versions - 1;
+            if ledger_info.version() == last_version && ledger_info.ends_epoch() {
+                next_expected_epoch += 1;
+            }
+        } else {
+            assert_eq!(next_expected_epoch, MAX_ADVERTISED_EPOCH_END + 1);
+            return assert_eq!(next_expected_version, MAX_ADVERTISED_TRANSACTION + 1);
+        }
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_notifications_epoch_ending() {
     // Create a new streaming client and service
@@ -859,6 +922,69 @@ async fn test_notifications_subscribe_transactions() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_notifications_subscribe_transactions_or_outputs() {
+    // Create a new streaming client and service
+    let streaming_client = create_streaming_client_and_service_with_data_delay();
+
+    // Request a continuous transaction or output stream and get a data stream listener
+    let mut stream_listener = streaming_client
+        .continuously_stream_transactions_or_outputs(
+            MIN_ADVERTISED_TRANSACTION - 1,
+            MIN_ADVERTISED_EPOCH_END,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Read the data notifications from the stream and verify the payloads
+    let mut next_expected_epoch = MIN_ADVERTISED_EPOCH_END;
+    let mut next_expected_version = MIN_ADVERTISED_TRANSACTION;
+    loop {
+        if let Ok(data_notification) = get_data_notification(&mut stream_listener).await {
+            // Extract the ledger info, first version and number of versions
+            let (ledger_info_with_sigs, first_version, num_versions) =
+                match data_notification.data_payload {
+                    DataPayload::ContinuousTransactionsWithProof(
+                        ledger_info_with_sigs,
+                        transactions_with_proof,
+                    ) => (
+                        ledger_info_with_sigs,
+                        transactions_with_proof.first_transaction_version,
+                        transactions_with_proof.transactions.len() as u64,
+                    ),
+                    DataPayload::ContinuousTransactionOutputsWithProof(
+                        ledger_info_with_sigs,
+                        outputs_with_proof,
+                    ) => (
+                        ledger_info_with_sigs,
+                        outputs_with_proof.first_transaction_output_version,
+                        outputs_with_proof.transactions_and_outputs.len() as u64,
+                    ),
+                    data_payload => unexpected_payload_type!(data_payload),
+                };
+
+            // Verify the epoch of the ledger info
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            assert_eq!(ledger_info.epoch(), next_expected_epoch);
+
+            // Verify the start version matches the expected version
+            assert_eq!(Some(next_expected_version), first_version);
+            next_expected_version += num_versions;
+
+            // Update epochs if we've hit the epoch end
+            let last_version = first_version.unwrap() + num_versions - 1;
+            if ledger_info.version() == last_version && ledger_info.ends_epoch() {
+                next_expected_epoch += 1;
+            }
+        } else {
+            assert_eq!(next_expected_epoch, MAX_REAL_EPOCH_END + 1);
+            return assert_eq!(next_expected_version, MAX_REAL_TRANSACTION + 1);
+        }
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_notifications_transaction_outputs() {
     // Create a new streaming client and service
@@ -1001,6 +1127,51 @@ async fn test_notifications_transactions() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_notifications_transactions_or_outputs() {
+    // Create a new streaming client and service
+    let streaming_client = create_streaming_client_and_service();
+
+    // Request a transaction or output stream and get a data stream listener
+    let mut stream_listener = streaming_client
+        .get_all_transactions_or_outputs(
+            MIN_ADVERTISED_TRANSACTION,
+            MAX_ADVERTISED_TRANSACTION,
+            MAX_ADVERTISED_TRANSACTION,
+            false,
+        )
+        .await
+        .unwrap();
+
+    // Read the data notifications from the stream and verify the payloads
+    let mut next_expected_version = MIN_ADVERTISED_TRANSACTION;
+    loop {
+        let data_notification = get_data_notification(&mut stream_listener).await.unwrap();
+        match data_notification.data_payload {
+            DataPayload::TransactionsWithProof(transactions_with_proof) => {
+                // Verify the transaction start version matches the expected version
+                let first_transaction_version = transactions_with_proof.first_transaction_version;
+                assert_eq!(Some(next_expected_version), first_transaction_version);
+
+                let num_transactions = transactions_with_proof.transactions.len();
+                next_expected_version += num_transactions as u64;
+            }
+            DataPayload::TransactionOutputsWithProof(outputs_with_proof) => {
+                // Verify the output start version matches the expected version
+                let first_output_version = outputs_with_proof.first_transaction_output_version;
+                assert_eq!(Some(next_expected_version), first_output_version);
+
+                let num_outputs = outputs_with_proof.transactions_and_outputs.len();
+                next_expected_version += num_outputs as u64;
+            }
+            DataPayload::EndOfStream => {
+                return assert_eq!(next_expected_version, MAX_ADVERTISED_TRANSACTION + 1)
+            }
+            data_payload => unexpected_payload_type!(data_payload),
+        }
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_notifications_transactions_limited_chunks() {
     // Create a new streaming client and service
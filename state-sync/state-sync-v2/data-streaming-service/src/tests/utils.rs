@@ -38,10 +38,11 @@ use std::sync::Arc;
 use std::{collections::HashMap, thread, time::Duration};
 use storage_service_types::requests::{
     DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
-    NewTransactionsWithProofRequest, StateValuesWithProofRequest,
-    TransactionOutputsWithProofRequest, TransactionsWithProofRequest,
+    NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+    StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
+    TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
 };
-use storage_service_types::responses::CompleteDataRange;
+use storage_service_types::responses::{CompleteDataRange, TransactionOrOutputListWithProof};
 use storage_service_types::Epoch;
 use tokio::time::timeout;
 
@@ -435,6 +436,62 @@ impl AptosDataClient for MockAptosDataClient {
         }
     }
 
+    async fn get_new_transactions_or_outputs_with_proof(
+        &self,
+        known_version: Version,
+        known_epoch: Epoch,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> Result<
+        Response<(TransactionOrOutputListWithProof, LedgerInfoWithSignatures)>,
+        aptos_data_client::Error,
+    > {
+        self.verify_request_timeout(
+            request_timeout_ms,
+            true,
+            DataRequest::GetNewTransactionsOrOutputsWithProof(
+                NewTransactionsOrOutputsWithProofRequest {
+                    known_version,
+                    known_epoch,
+                    include_events,
+                    max_num_output_reductions: self
+                        .aptos_data_client_config
+                        .max_num_output_reductions,
+                },
+            ),
+        );
+
+        // Create a mock data client without timeout verification (to handle the internal requests)
+        let mut aptos_data_client = self.clone();
+        aptos_data_client.skip_timeout_verification = true;
+
+        // Randomly return new transactions or outputs
+        if OsRng.gen::<bool>() {
+            let response = aptos_data_client
+                .get_new_transactions_with_proof(
+                    known_version,
+                    known_epoch,
+                    include_events,
+                    request_timeout_ms,
+                )
+                .await?;
+            Ok(response.map(|(transactions_with_proof, ledger_info)| {
+                ((Some(transactions_with_proof), None), ledger_info)
+            }))
+        } else {
+            let response = aptos_data_client
+                .get_new_transaction_outputs_with_proof(
+                    known_version,
+                    known_epoch,
+                    request_timeout_ms,
+                )
+                .await?;
+            Ok(response.map(|(outputs_with_proof, ledger_info)| {
+                ((None, Some(outputs_with_proof)), ledger_info)
+            }))
+        }
+    }
+
     async fn get_number_of_states(
         &self,
         version: Version,
@@ -513,6 +570,55 @@ impl AptosDataClient for MockAptosDataClient {
         // Return the transaction list with proofs
         Ok(create_data_client_response(transaction_list_with_proof))
     }
+
+    async fn get_transactions_or_outputs_with_proof(
+        &self,
+        proof_version: Version,
+        start_version: Version,
+        end_version: Version,
+        include_events: bool,
+        request_timeout_ms: u64,
+    ) -> Result<Response<TransactionOrOutputListWithProof>, aptos_data_client::Error> {
+        self.verify_request_timeout(
+            request_timeout_ms,
+            false,
+            DataRequest::GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest {
+                proof_version,
+                start_version,
+                end_version,
+                include_events,
+                max_num_output_reductions: self.aptos_data_client_config.max_num_output_reductions,
+            }),
+        );
+
+        // Create a mock data client without timeout verification (to handle the internal requests)
+        let mut aptos_data_client = self.clone();
+        aptos_data_client.skip_timeout_verification = true;
+
+        // Randomly return transactions or outputs
+        if OsRng.gen::<bool>() {
+            let response = aptos_data_client
+                .get_transactions_with_proof(
+                    proof_version,
+                    start_version,
+                    end_version,
+                    include_events,
+                    request_timeout_ms,
+                )
+                .await?;
+            Ok(response.map(|transactions_with_proof| (Some(transactions_with_proof), None)))
+        } else {
+            let response = aptos_data_client
+                .get_transaction_outputs_with_proof(
+                    proof_version,
+                    start_version,
+                    end_version,
+                    request_timeout_ms,
+                )
+                .await?;
+            Ok(response.map(|outputs_with_proof| (None, Some(outputs_with_proof))))
+        }
+    }
 }
 
 #[derive(Debug)]
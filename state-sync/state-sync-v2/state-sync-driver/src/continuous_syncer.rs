@@ -132,6 +132,16 @@ impl<
                     )
                     .await?
             }
+            ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs => {
+                self.streaming_client
+                    .continuously_stream_transactions_or_outputs(
+                        highest_synced_version,
+                        highest_synced_epoch,
+                        false,
+                        sync_request_target,
+                    )
+                    .await?
+            }
         };
         self.speculative_stream_state = Some(SpeculativeStreamState::new(
             highest_epoch_state,
@@ -252,18 +262,12 @@ impl<
             match self.driver_configuration.config.continuous_syncing_mode {
                 ContinuousSyncingMode::ApplyTransactionOutputs => {
                     if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
-                        let num_transaction_outputs = transaction_outputs_with_proof
-                            .transactions_and_outputs
-                            .len();
-                        self.storage_synchronizer
-                            .apply_transaction_outputs(
-                                notification_id,
-                                transaction_outputs_with_proof,
-                                ledger_info_with_signatures.clone(),
-                                None,
-                            )
-                            .await?;
-                        num_transaction_outputs
+                        self.apply_transaction_outputs(
+                            notification_id,
+                            transaction_outputs_with_proof,
+                            ledger_info_with_signatures.clone(),
+                        )
+                        .await?
                     } else {
                         self.reset_active_stream(Some(NotificationAndFeedback::new(
                             notification_id,
@@ -277,16 +281,12 @@ impl<
                 }
                 ContinuousSyncingMode::ExecuteTransactions => {
                     if let Some(transaction_list_with_proof) = transaction_list_with_proof {
-                        let num_transactions = transaction_list_with_proof.transactions.len();
-                        self.storage_synchronizer
-                            .execute_transactions(
-                                notification_id,
-                                transaction_list_with_proof,
-                                ledger_info_with_signatures.clone(),
-                                None,
-                            )
-                            .await?;
-                        num_transactions
+                        self.execute_transactions(
+                            notification_id,
+                            transaction_list_with_proof,
+                            ledger_info_with_signatures.clone(),
+                        )
+                        .await?
                     } else {
                         self.reset_active_stream(Some(NotificationAndFeedback::new(
                             notification_id,
@@ -298,6 +298,32 @@ impl<
                         ));
                     }
                 }
+                ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs => {
+                    if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
+                        self.apply_transaction_outputs(
+                            notification_id,
+                            transaction_outputs_with_proof,
+                            ledger_info_with_signatures.clone(),
+                        )
+                        .await?
+                    } else if let Some(transaction_list_with_proof) = transaction_list_with_proof {
+                        self.execute_transactions(
+                            notification_id,
+                            transaction_list_with_proof,
+                            ledger_info_with_signatures.clone(),
+                        )
+                        .await?
+                    } else {
+                        self.reset_active_stream(Some(NotificationAndFeedback::new(
+                            notification_id,
+                            NotificationFeedback::PayloadTypeIsIncorrect,
+                        )))
+                        .await?;
+                        return Err(Error::InvalidPayload(
+                            "Did not receive transactions or outputs with proof!".into(),
+                        ));
+                    }
+                }
             };
         let synced_version = payload_start_version
             .checked_add(num_transactions_or_outputs as u64)
@@ -310,6 +336,46 @@ impl<
         Ok(())
     }
 
+    /// Applies the given transaction outputs and returns the number of outputs
+    async fn apply_transaction_outputs(
+        &mut self,
+        notification_id: NotificationId,
+        transaction_outputs_with_proof: TransactionOutputListWithProof,
+        ledger_info_with_signatures: LedgerInfoWithSignatures,
+    ) -> Result<usize, Error> {
+        let num_transaction_outputs = transaction_outputs_with_proof
+            .transactions_and_outputs
+            .len();
+        self.storage_synchronizer
+            .apply_transaction_outputs(
+                notification_id,
+                transaction_outputs_with_proof,
+                ledger_info_with_signatures,
+                None,
+            )
+            .await?;
+        Ok(num_transaction_outputs)
+    }
+
+    /// Executes the given transactions and returns the number of transactions
+    async fn execute_transactions(
+        &mut self,
+        notification_id: NotificationId,
+        transaction_list_with_proof: TransactionListWithProof,
+        ledger_info_with_signatures: LedgerInfoWithSignatures,
+    ) -> Result<usize, Error> {
+        let num_transactions = transaction_list_with_proof.transactions.len();
+        self.storage_synchronizer
+            .execute_transactions(
+                notification_id,
+                transaction_list_with_proof,
+                ledger_info_with_signatures,
+                None,
+            )
+            .await?;
+        Ok(num_transactions)
+    }
+
     /// Verifies the first payload version matches the version we wish to sync
     async fn verify_payload_start_version(
         &mut self,
@@ -33,7 +33,13 @@ use data_streaming_service::streaming_client::{
 use event_notifications::EventSubscriptionService;
 use futures::StreamExt;
 use mempool_notifications::MempoolNotificationSender;
-use std::{sync::Arc, time::SystemTime};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::SystemTime,
+};
 use storage_interface::DbReader;
 use tokio::task::yield_now;
 use tokio::time::{interval, Duration};
@@ -53,14 +59,24 @@ pub struct DriverConfiguration {
 
     // The trusted waypoint for the node
     pub waypoint: Waypoint,
+
+    // Set iff a consensus observer runs on this (full) node. The flag is raised
+    // by the observer while it executes the blocks (instead of state sync).
+    pub consensus_observer_executing: Option<Arc<AtomicBool>>,
 }
 
 impl DriverConfiguration {
-    pub fn new(config: StateSyncDriverConfig, role: RoleType, waypoint: Waypoint) -> Self {
+    pub fn new(
+        config: StateSyncDriverConfig,
+        role: RoleType,
+        waypoint: Waypoint,
+        consensus_observer_executing: Option<Arc<AtomicBool>>,
+    ) -> Self {
         Self {
             config,
             role,
             waypoint,
+            consensus_observer_executing,
         }
     }
 }
@@ -200,9 +216,10 @@ impl<
 
     /// Handles a notification sent by consensus
     async fn handle_consensus_notification(&mut self, notification: ConsensusNotification) {
-        // Verify the notification: full nodes shouldn't receive notifications
-        // and consensus should only send notifications after bootstrapping!
-        let result = if self.driver_configuration.role == RoleType::FullNode {
+        // Verify the notification: full nodes shouldn't receive notifications (unless
+        // they run a consensus observer) and consensus should only send notifications
+        // after bootstrapping!
+        let result = if !self.is_consensus_or_observer_enabled() {
             Err(Error::FullNodeConsensusNotification(format!(
                 "Received consensus notification: {:?}",
                 notification
@@ -498,9 +515,30 @@ impl<
         self.driver_configuration.role == RoleType::Validator
     }
 
-    /// Returns true iff consensus is currently executing
+    /// Returns true iff consensus (on validators) or the consensus observer
+    /// (on full nodes) executes the blocks of this node once it's bootstrapped
+    fn is_consensus_or_observer_enabled(&self) -> bool {
+        self.is_validator()
+            || self
+                .driver_configuration
+                .consensus_observer_executing
+                .is_some()
+    }
+
+    /// Returns true iff the consensus observer currently executes the blocks. Otherwise,
+    /// e.g., if the observer has no publisher to follow, the node continuously syncs.
+    fn is_consensus_observer_executing(&self) -> bool {
+        self.driver_configuration
+            .consensus_observer_executing
+            .as_ref()
+            .map_or(false, |executing| executing.load(Ordering::Acquire))
+    }
+
+    /// Returns true iff consensus (or the consensus observer) is currently executing
     fn check_if_consensus_executing(&self) -> bool {
-        self.is_validator() && self.bootstrapper.is_bootstrapped() && !self.active_sync_request()
+        (self.is_validator() || self.is_consensus_observer_executing())
+            && self.bootstrapper.is_bootstrapped()
+            && !self.active_sync_request()
     }
 
     /// Checks if the connection deadline has passed. If so, validators with
@@ -22,7 +22,7 @@ use executor_types::ChunkExecutorTrait;
 use futures::{channel::mpsc, executor::block_on};
 use mempool_notifications::MempoolNotificationSender;
 use std::sync::{
-    atomic::{AtomicUsize, Ordering},
+    atomic::{AtomicBool, AtomicUsize, Ordering},
     Arc,
 };
 use storage_interface::DbReaderWriter;
@@ -52,6 +52,7 @@ impl DriverFactory {
         mut event_subscription_service: EventSubscriptionService,
         aptos_data_client: AptosNetDataClient,
         streaming_service_client: StreamingServiceClient,
+        consensus_observer_executing: Option<Arc<AtomicBool>>,
     ) -> Self {
         // Notify subscribers of the initial on-chain config values
         match (&*storage.reader).fetch_latest_state_checkpoint_version() {
@@ -117,6 +118,7 @@ impl DriverFactory {
             node_config.state_sync.state_sync_driver,
             node_config.base.role,
             waypoint,
+            consensus_observer_executing,
         );
 
         // Create the state sync driver
@@ -414,6 +414,78 @@ async fn test_data_stream_transaction_outputs() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_data_stream_transactions_or_outputs() {
+    // Create test data
+    let notification_id = 1236;
+    let highest_version = 450;
+    let highest_ledger_info = create_random_epoch_ending_ledger_info(highest_version, 1);
+
+    // Create a driver configuration with a genesis waypoint and transaction or output syncing
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::ExecuteOrApplyFromGenesis;
+
+    // Create the mock streaming client
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let mut expectation_sequence = Sequence::new();
+    let (mut notification_sender_1, data_stream_listener_1) = create_data_stream_listener();
+    let (_notification_sender_2, data_stream_listener_2) = create_data_stream_listener();
+    let data_stream_id_1 = data_stream_listener_1.data_stream_id;
+    for data_stream_listener in [data_stream_listener_1, data_stream_listener_2] {
+        mock_streaming_client
+            .expect_get_all_transactions_or_outputs()
+            .times(1)
+            .with(eq(1), eq(highest_version), eq(highest_version), eq(false))
+            .return_once(move |_, _, _, _| Ok(data_stream_listener))
+            .in_sequence(&mut expectation_sequence);
+    }
+    mock_streaming_client
+        .expect_terminate_stream_with_feedback()
+        .with(
+            eq(data_stream_id_1),
+            eq(Some(NotificationAndFeedback::new(
+                notification_id,
+                NotificationFeedback::EmptyPayloadData,
+            ))),
+        )
+        .return_const(Ok(()));
+
+    // Create the bootstrapper
+    let mut bootstrapper = create_bootstrapper(driver_configuration, mock_streaming_client, true);
+
+    // Insert an epoch ending ledger info into the verified states of the bootstrapper
+    manipulate_verified_epoch_states(&mut bootstrapper, true, true, Some(highest_version));
+
+    // Create a global data summary
+    let mut global_data_summary = create_global_summary(1);
+    global_data_summary.advertised_data.synced_ledger_infos = vec![highest_ledger_info.clone()];
+
+    // Drive progress to initialize the transaction or output stream
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+
+    // Send an invalid output along the stream
+    let data_notification = DataNotification {
+        notification_id,
+        data_payload: DataPayload::TransactionOutputsWithProof(
+            TransactionOutputListWithProof::new_empty(),
+        ),
+    };
+    notification_sender_1.send(data_notification).await.unwrap();
+
+    // Drive progress again and ensure we get a verification error
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::VerificationError(_));
+
+    // Drive progress to initialize the transaction or output stream
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+}
+
 #[tokio::test]
 async fn test_fetch_epoch_ending_ledger_infos() {
     // Create a driver configuration with a genesis waypoint and a stream timeout of 1 second
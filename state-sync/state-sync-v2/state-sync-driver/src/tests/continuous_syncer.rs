@@ -283,6 +283,91 @@ async fn test_data_stream_transaction_outputs() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_data_stream_transactions_or_outputs() {
+    // Create test data
+    let current_synced_epoch = 200;
+    let current_synced_version = 15;
+    let notification_id = 1236;
+
+    // Create a driver configuration
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.continuous_syncing_mode =
+        ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs;
+
+    // Create the mock streaming client
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let mut expectation_sequence = Sequence::new();
+    let (mut notification_sender_1, data_stream_listener_1) = create_data_stream_listener();
+    let (_notification_sender_2, data_stream_listener_2) = create_data_stream_listener();
+    let data_stream_id_1 = data_stream_listener_1.data_stream_id;
+    for data_stream_listener in [data_stream_listener_1, data_stream_listener_2] {
+        mock_streaming_client
+            .expect_continuously_stream_transactions_or_outputs()
+            .times(1)
+            .with(
+                eq(current_synced_version),
+                eq(current_synced_epoch),
+                eq(false),
+                eq(None),
+            )
+            .return_once(move |_, _, _, _| Ok(data_stream_listener))
+            .in_sequence(&mut expectation_sequence);
+    }
+    mock_streaming_client
+        .expect_terminate_stream_with_feedback()
+        .with(
+            eq(data_stream_id_1),
+            eq(Some(NotificationAndFeedback::new(
+                notification_id,
+                NotificationFeedback::InvalidPayloadData,
+            ))),
+        )
+        .return_const(Ok(()));
+
+    // Create the continuous syncer
+    let mut continuous_syncer = create_continuous_syncer(
+        driver_configuration,
+        mock_streaming_client,
+        true,
+        current_synced_version,
+        current_synced_epoch,
+    );
+
+    // Drive progress to initialize the transaction or output stream
+    let no_sync_request = Arc::new(Mutex::new(None));
+    continuous_syncer
+        .drive_progress(no_sync_request.clone())
+        .await
+        .unwrap();
+
+    // Send an invalid output along the stream
+    let mut transaction_output_with_proof = TransactionOutputListWithProof::new_empty();
+    transaction_output_with_proof.first_transaction_output_version =
+        Some(current_synced_version - 1);
+    let data_notification = DataNotification {
+        notification_id,
+        data_payload: DataPayload::ContinuousTransactionOutputsWithProof(
+            create_epoch_ending_ledger_info(),
+            transaction_output_with_proof,
+        ),
+    };
+    notification_sender_1.send(data_notification).await.unwrap();
+
+    // Drive progress again and ensure we get a verification error
+    let error = continuous_syncer
+        .drive_progress(no_sync_request.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::VerificationError(_));
+
+    // Drive progress to initialize the transaction or output stream
+    continuous_syncer
+        .drive_progress(no_sync_request.clone())
+        .await
+        .unwrap();
+}
+
 /// Creates a continuous syncer for testing
 fn create_continuous_syncer(
     driver_configuration: DriverConfiguration,
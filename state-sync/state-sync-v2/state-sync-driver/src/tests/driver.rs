@@ -291,6 +291,7 @@ async fn create_driver_for_tests(
         event_subscription_service,
         aptos_data_client,
         streaming_service_client,
+        None,
     );
 
     // The driver will notify reconfiguration subscribers of the initial configs.
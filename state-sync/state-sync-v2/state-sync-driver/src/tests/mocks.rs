@@ -377,6 +377,14 @@ mock! {
             include_events: bool,
         ) -> Result<DataStreamListener, data_streaming_service::error::Error>;
 
+        async fn get_all_transactions_or_outputs(
+            &self,
+            start_version: Version,
+            end_version: Version,
+            proof_version: Version,
+            include_events: bool,
+        ) -> Result<DataStreamListener, data_streaming_service::error::Error>;
+
         async fn continuously_stream_transaction_outputs(
             &self,
             start_version: Version,
@@ -392,6 +400,14 @@ mock! {
             target: Option<LedgerInfoWithSignatures>,
         ) -> Result<DataStreamListener, data_streaming_service::error::Error>;
 
+        async fn continuously_stream_transactions_or_outputs(
+            &self,
+            start_version: Version,
+            start_epoch: Epoch,
+            include_events: bool,
+            target: Option<LedgerInfoWithSignatures>,
+        ) -> Result<DataStreamListener, data_streaming_service::error::Error>;
+
         async fn terminate_stream_with_feedback(
             &self,
             data_stream_id: DataStreamId,
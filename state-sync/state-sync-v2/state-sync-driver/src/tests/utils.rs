@@ -72,6 +72,7 @@ pub fn create_full_node_driver_configuration() -> DriverConfiguration {
         config,
         role,
         waypoint,
+        consensus_observer_executing: None,
     }
 }
 
@@ -34,11 +34,12 @@ use std::{
 use storage_interface::DbReader;
 use storage_service_types::requests::{
     DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest, StorageServiceRequest,
-    TransactionOutputsWithProofRequest, TransactionsWithProofRequest,
+    TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+    TransactionsWithProofRequest,
 };
 use storage_service_types::responses::{
     CompleteDataRange, DataResponse, DataSummary, ProtocolMetadata, ServerProtocolVersion,
-    StorageServerSummary, StorageServiceResponse,
+    StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof,
 };
 use storage_service_types::{Result, StorageServiceError};
 use thiserror::Error;
@@ -157,6 +158,17 @@ impl DataSubscriptionRequest {
                     include_events: request.include_events,
                 })
             }
+            DataRequest::GetNewTransactionsOrOutputsWithProof(request) => {
+                DataRequest::GetTransactionsOrOutputsWithProof(
+                    TransactionsOrOutputsWithProofRequest {
+                        proof_version: target_version,
+                        start_version,
+                        end_version,
+                        include_events: request.include_events,
+                        max_num_output_reductions: request.max_num_output_reductions,
+                    },
+                )
+            }
             request => unreachable!("Unexpected subscription request: {:?}", request),
         };
         let storage_request =
@@ -169,6 +181,7 @@ impl DataSubscriptionRequest {
         match &self.request.data_request {
             DataRequest::GetNewTransactionOutputsWithProof(request) => request.known_version,
             DataRequest::GetNewTransactionsWithProof(request) => request.known_version,
+            DataRequest::GetNewTransactionsOrOutputsWithProof(request) => request.known_version,
             request => unreachable!("Unexpected subscription request: {:?}", request),
         }
     }
@@ -178,6 +191,7 @@ impl DataSubscriptionRequest {
         match &self.request.data_request {
             DataRequest::GetNewTransactionOutputsWithProof(request) => request.known_epoch,
             DataRequest::GetNewTransactionsWithProof(request) => request.known_epoch,
+            DataRequest::GetNewTransactionsOrOutputsWithProof(request) => request.known_epoch,
             request => unreachable!("Unexpected subscription request: {:?}", request),
         }
     }
@@ -190,6 +204,10 @@ impl DataSubscriptionRequest {
                 config.max_transaction_output_chunk_size
             }
             DataRequest::GetNewTransactionsWithProof(_) => config.max_transaction_chunk_size,
+            DataRequest::GetNewTransactionsOrOutputsWithProof(_) => min(
+                config.max_transaction_chunk_size,
+                config.max_transaction_output_chunk_size,
+            ),
             request => unreachable!("Unexpected subscription request: {:?}", request),
         }
     }
@@ -541,6 +559,12 @@ fn notify_peer_of_new_data<T: StorageReaderInterface>(
                             target_ledger_info.clone(),
                         ))
                     }
+                    Ok(DataResponse::TransactionsOrOutputsWithProof(
+                        transactions_or_outputs_with_proof,
+                    )) => DataResponse::NewTransactionsOrOutputsWithProof((
+                        transactions_or_outputs_with_proof,
+                        target_ledger_info.clone(),
+                    )),
                     data_response => {
                         return Err(Error::UnexpectedErrorEncountered(format!(
                             "Failed to get appropriate data response for peer! Got: {:?}",
@@ -813,6 +837,9 @@ impl<T: StorageReaderInterface> Handler<T> {
             DataRequest::GetTransactionsWithProof(request) => {
                 self.get_transactions_with_proof(request)
             }
+            DataRequest::GetTransactionsOrOutputsWithProof(request) => {
+                self.get_transactions_or_outputs_with_proof(request)
+            }
             _ => unreachable!("Received an unexpected request: {:?}", request),
         }?;
         let storage_response = StorageServiceResponse::new(data_response, request.use_compression)?;
@@ -898,6 +925,24 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         Ok(DataResponse::TransactionsWithProof(transactions_with_proof))
     }
+
+    fn get_transactions_or_outputs_with_proof(
+        &self,
+        request: &TransactionsOrOutputsWithProofRequest,
+    ) -> Result<DataResponse, Error> {
+        let transactions_or_outputs_with_proof =
+            self.storage.get_transactions_or_outputs_with_proof(
+                request.proof_version,
+                request.start_version,
+                request.end_version,
+                request.include_events,
+                request.max_num_output_reductions,
+            )?;
+
+        Ok(DataResponse::TransactionsOrOutputsWithProof(
+            transactions_or_outputs_with_proof,
+        ))
+    }
 }
 
 /// The interface into local storage (e.g., the Aptos DB) used by the storage
@@ -943,6 +988,22 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         end_version: u64,
     ) -> Result<TransactionOutputListWithProof, Error>;
 
+    /// Returns a list of transaction outputs or transactions with a proof
+    /// relative to the `proof_version`. The list is expected to start at
+    /// `start_version` and end at `end_version` (inclusive). Transaction
+    /// outputs are preferred, but if the outputs cannot fit into a network
+    /// frame after `max_num_output_reductions` reductions, transactions are
+    /// returned instead. If `include_events` is true, events are returned
+    /// with the transactions.
+    fn get_transactions_or_outputs_with_proof(
+        &self,
+        proof_version: u64,
+        start_version: u64,
+        end_version: u64,
+        include_events: bool,
+        max_num_output_reductions: u64,
+    ) -> Result<TransactionOrOutputListWithProof, Error>;
+
     /// Returns the number of states in the state tree at the specified version.
     fn get_number_of_states(&self, version: u64) -> Result<u64, Error>;
 
@@ -1244,6 +1305,58 @@ impl StorageReaderInterface for StorageReader {
         )))
     }
 
+    fn get_transactions_or_outputs_with_proof(
+        &self,
+        proof_version: u64,
+        start_version: u64,
+        end_version: u64,
+        include_events: bool,
+        max_num_output_reductions: u64,
+    ) -> Result<TransactionOrOutputListWithProof, Error> {
+        // Calculate the number of transaction outputs to fetch
+        let expected_num_outputs = inclusive_range_len(start_version, end_version)?;
+        let max_num_outputs = self.config.max_transaction_output_chunk_size;
+        let mut num_outputs_to_fetch = min(expected_num_outputs, max_num_outputs);
+
+        // Attempt to serve the outputs. Halve the data only as many times as
+        // the fallback limit allows, then fall back to transactions.
+        let mut num_output_reductions = 0;
+        while num_output_reductions <= max_num_output_reductions {
+            let output_list_with_proof = self
+                .storage
+                .get_transaction_outputs(start_version, num_outputs_to_fetch, proof_version)
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let (overflow_frame, num_bytes) = check_overflow_network_frame(
+                &output_list_with_proof,
+                self.config.max_network_chunk_bytes,
+            )?;
+
+            if !overflow_frame {
+                return Ok((None, Some(output_list_with_proof)));
+            } else if num_outputs_to_fetch == 1 {
+                break; // We cannot return less than a single item. Fallback to transactions
+            } else {
+                increment_network_frame_overflow(
+                    DataResponse::TransactionOutputsWithProof(output_list_with_proof).get_label(),
+                );
+                let new_num_outputs_to_fetch = num_outputs_to_fetch / 2;
+                debug!("The request for {:?} outputs was too large (num bytes: {:?}). Current number of data reductions: {:?}",
+                    num_outputs_to_fetch, num_bytes, num_output_reductions);
+                num_outputs_to_fetch = new_num_outputs_to_fetch; // Try again with half the amount of data
+                num_output_reductions += 1;
+            }
+        }
+
+        // Return transactions only
+        let transactions_with_proof = self.get_transactions_with_proof(
+            proof_version,
+            start_version,
+            end_version,
+            include_events,
+        )?;
+        Ok((Some(transactions_with_proof), None))
+    }
+
     fn get_number_of_states(&self, version: u64) -> Result<u64, Error> {
         let number_of_states = self
             .storage
@@ -57,8 +57,9 @@ use storage_interface::{DbReader, ExecutedTrees, Order};
 use storage_service_types::{
     requests::{
         DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
-        NewTransactionsWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
-        TransactionOutputsWithProofRequest, TransactionsWithProofRequest,
+        NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+        StateValuesWithProofRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
+        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{
         CompleteDataRange, DataResponse, DataSummary, ProtocolMetadata, ServerProtocolVersion,
@@ -766,6 +767,75 @@ async fn test_get_new_transaction_outputs_max_chunk() {
     .await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_new_transactions_or_outputs() {
+    // Test small and large chunk sizes
+    let max_output_chunk_size = StorageServiceConfig::default().max_transaction_output_chunk_size;
+    for chunk_size in [1, 100, max_output_chunk_size] {
+        // Create test data
+        let highest_version = 5060;
+        let highest_epoch = 30;
+        let lowest_version = 101;
+        let peer_version = highest_version - chunk_size;
+        let highest_ledger_info = create_test_ledger_info_with_sigs(highest_epoch, highest_version);
+        let output_list_with_proof =
+            create_output_list_with_proof(peer_version + 1, highest_version, highest_version);
+
+        // Create the mock db reader
+        let mut db_reader =
+            create_mock_db_for_subscription(highest_ledger_info.clone(), lowest_version);
+        expect_get_transaction_outputs(
+            &mut db_reader,
+            peer_version + 1,
+            highest_version - peer_version,
+            highest_version,
+            output_list_with_proof.clone(),
+        );
+
+        // Create the storage client and server
+        let (mut mock_client, service, mock_time) = MockClient::new(Some(db_reader), None);
+        tokio::spawn(service.start());
+
+        // Send a request to subscribe to new transactions or outputs
+        let mut response_receiver = get_new_transactions_or_outputs_with_proof(
+            &mut mock_client,
+            peer_version,
+            highest_epoch,
+            false,
+            0,
+        )
+        .await;
+
+        // Verify no subscription response has been received yet
+        assert_none!(response_receiver.try_recv().unwrap());
+
+        // Elapse enough time to force the subscription thread to work
+        wait_for_subscription_service_to_refresh(&mut mock_client, &mock_time).await;
+
+        // Verify a response is received and that it contains the outputs
+        match mock_client
+            .wait_for_response(response_receiver)
+            .await
+            .unwrap()
+            .get_data_response()
+            .unwrap()
+        {
+            DataResponse::NewTransactionsOrOutputsWithProof((
+                (transactions_with_proof, outputs_with_proof),
+                ledger_info,
+            )) => {
+                assert_none!(transactions_with_proof);
+                assert_eq!(outputs_with_proof.unwrap(), output_list_with_proof);
+                assert_eq!(ledger_info, highest_ledger_info);
+            }
+            response => panic!(
+                "Expected new transactions or outputs with proof but got: {:?}",
+                response
+            ),
+        };
+    }
+}
+
 #[tokio::test]
 async fn test_get_number_of_states_at_version() {
     // Create test data
@@ -1182,6 +1252,171 @@ async fn test_get_transaction_outputs_with_proof_invalid() {
     }
 }
 
+#[tokio::test]
+async fn test_get_transactions_or_outputs_with_proof() {
+    // Test small and large chunk requests
+    let max_output_chunk_size = StorageServiceConfig::default().max_transaction_output_chunk_size;
+    for chunk_size in [1, 100, max_output_chunk_size] {
+        // Create test data
+        let start_version = 0;
+        let end_version = start_version + chunk_size - 1;
+        let proof_version = end_version;
+        let output_list_with_proof =
+            create_output_list_with_proof(start_version, end_version, proof_version);
+
+        // Create the mock db reader
+        let mut db_reader = create_mock_db_reader();
+        expect_get_transaction_outputs(
+            &mut db_reader,
+            start_version,
+            chunk_size,
+            proof_version,
+            output_list_with_proof.clone(),
+        );
+
+        // Create the storage client and server
+        let (mut mock_client, service, _) = MockClient::new(Some(db_reader), None);
+        tokio::spawn(service.start());
+
+        // Create a request to fetch transactions or outputs with a proof
+        let response = get_transactions_or_outputs_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            end_version,
+            false,
+            0,
+            true,
+        )
+        .await
+        .unwrap();
+
+        // Verify the response contains the outputs
+        match response.get_data_response().unwrap() {
+            DataResponse::TransactionsOrOutputsWithProof((
+                transactions_with_proof,
+                outputs_with_proof,
+            )) => {
+                assert_none!(transactions_with_proof);
+                assert_eq!(outputs_with_proof.unwrap(), output_list_with_proof);
+            }
+            _ => panic!(
+                "Expected transactions or outputs with proof but got: {:?}",
+                response
+            ),
+        };
+    }
+}
+
+#[tokio::test]
+async fn test_get_transactions_or_outputs_with_proof_fallback() {
+    // Test different numbers of output reductions
+    for max_num_output_reductions in [0, 1, 2] {
+        // Create test data
+        let chunk_size = 100;
+        let min_bytes_per_output = 10 * 1024; // 10 KB
+        let min_bytes_per_transaction = 128;
+        let start_version = 455;
+        let end_version = start_version + chunk_size - 1;
+        let proof_version = 1000000;
+        let transaction_list_with_proof = create_transaction_list_using_sizes(
+            start_version,
+            chunk_size,
+            min_bytes_per_transaction,
+            false,
+        );
+
+        // Create the mock db reader and expect all outputs to overflow
+        let mut db_reader = create_mock_db_reader();
+        let mut expectation_sequence = Sequence::new();
+        let mut num_outputs = chunk_size;
+        for _ in 0..=max_num_output_reductions {
+            let output_list_with_proof =
+                create_output_list_using_sizes(start_version, num_outputs, min_bytes_per_output);
+            db_reader
+                .expect_get_transaction_outputs()
+                .times(1)
+                .with(eq(start_version), eq(num_outputs), eq(proof_version))
+                .in_sequence(&mut expectation_sequence)
+                .returning(move |_, _, _| Ok(output_list_with_proof.clone()));
+            num_outputs /= 2;
+        }
+        expect_get_transactions(
+            &mut db_reader,
+            start_version,
+            chunk_size,
+            proof_version,
+            false,
+            transaction_list_with_proof.clone(),
+        );
+
+        // Create a storage config with a network limit smaller than the outputs
+        let storage_config = StorageServiceConfig {
+            max_network_chunk_bytes: 100 * 1024,
+            ..Default::default()
+        };
+
+        // Create the storage client and server
+        let (mut mock_client, service, _) = MockClient::new(Some(db_reader), Some(storage_config));
+        tokio::spawn(service.start());
+
+        // Create a request to fetch transactions or outputs with a proof
+        let response = get_transactions_or_outputs_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            proof_version,
+            false,
+            max_num_output_reductions,
+            true,
+        )
+        .await
+        .unwrap();
+
+        // Verify the response falls back to transactions
+        match response.get_data_response().unwrap() {
+            DataResponse::TransactionsOrOutputsWithProof((
+                transactions_with_proof,
+                outputs_with_proof,
+            )) => {
+                assert_eq!(
+                    transactions_with_proof.unwrap(),
+                    transaction_list_with_proof
+                );
+                assert_none!(outputs_with_proof);
+            }
+            _ => panic!(
+                "Expected transactions or outputs with proof but got: {:?}",
+                response
+            ),
+        };
+    }
+}
+
+#[tokio::test]
+async fn test_get_transactions_or_outputs_with_proof_invalid() {
+    // Create the storage client and server
+    let (mut mock_client, service, _) = MockClient::new(None, None);
+    tokio::spawn(service.start());
+
+    // Test invalid ranges
+    let start_version = 1000;
+    for end_version in [0, 999] {
+        let response = get_transactions_or_outputs_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            end_version,
+            false,
+            0,
+            true,
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(response, StorageServiceError::InvalidRequest(_));
+    }
+}
+
 #[tokio::test]
 async fn test_get_epoch_ending_ledger_infos() {
     // Test small and large chunk requests
@@ -1758,6 +1993,27 @@ async fn get_outputs_with_proof(
     send_storage_request(mock_client, use_compression, data_request).await
 }
 
+/// Sends a transactions or outputs with proof request and processes the response
+async fn get_transactions_or_outputs_with_proof(
+    mock_client: &mut MockClient,
+    start_version: u64,
+    end_version: u64,
+    proof_version: u64,
+    include_events: bool,
+    max_num_output_reductions: u64,
+    use_compression: bool,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request =
+        DataRequest::GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest {
+            proof_version,
+            start_version,
+            end_version,
+            include_events,
+            max_num_output_reductions,
+        });
+    send_storage_request(mock_client, use_compression, data_request).await
+}
+
 /// Sends a state values with proof request and processes the response
 async fn get_state_values_with_proof(
     mock_client: &mut MockClient,
@@ -1835,6 +2091,26 @@ async fn get_new_transactions_with_proof(
     mock_client.send_request(storage_request).await
 }
 
+/// Creates and sends a request for new transactions or outputs
+async fn get_new_transactions_or_outputs_with_proof(
+    mock_client: &mut MockClient,
+    known_version: u64,
+    known_epoch: u64,
+    include_events: bool,
+    max_num_output_reductions: u64,
+) -> Receiver<Result<bytes::Bytes, network::protocols::network::RpcError>> {
+    let data_request = DataRequest::GetNewTransactionsOrOutputsWithProof(
+        NewTransactionsOrOutputsWithProofRequest {
+            known_version,
+            known_epoch,
+            include_events,
+            max_num_output_reductions,
+        },
+    );
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    mock_client.send_request(storage_request).await
+}
+
 /// Sends the given storage request to the given client
 async fn send_storage_request(
     mock_client: &mut MockClient,
@@ -31,6 +31,8 @@ impl StorageServiceRequest {
 }
 
 /// A single data request.
+/// Note: new variants must be appended to the end of the enum to
+/// preserve the BCS encoding of existing variants.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum DataRequest {
     GetEpochEndingLedgerInfos(EpochEndingLedgerInfoRequest), // Fetches a list of epoch ending ledger infos
@@ -42,6 +44,8 @@ pub enum DataRequest {
     GetStorageServerSummary,             // Fetches a summary of the storage server state
     GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest), // Fetches a list of transaction outputs with a proof
     GetTransactionsWithProof(TransactionsWithProofRequest), // Fetches a list of transactions with a proof
+    GetNewTransactionsOrOutputsWithProof(NewTransactionsOrOutputsWithProofRequest), // Subscribes to new transactions or outputs with a proof
+    GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest), // Fetches a list of transactions or outputs with a proof
 }
 
 impl DataRequest {
@@ -57,6 +61,10 @@ impl DataRequest {
             Self::GetStorageServerSummary => "get_storage_server_summary",
             Self::GetTransactionOutputsWithProof(_) => "get_transaction_outputs_with_proof",
             Self::GetTransactionsWithProof(_) => "get_transactions_with_proof",
+            Self::GetNewTransactionsOrOutputsWithProof(_) => {
+                "get_new_transactions_or_outputs_with_proof"
+            }
+            Self::GetTransactionsOrOutputsWithProof(_) => "get_transactions_or_outputs_with_proof",
         }
     }
 
@@ -67,6 +75,7 @@ impl DataRequest {
     pub fn is_data_subscription_request(&self) -> bool {
         matches!(self, &Self::GetNewTransactionOutputsWithProof(_))
             || matches!(self, &Self::GetNewTransactionsWithProof(_))
+            || matches!(self, &Self::GetNewTransactionsOrOutputsWithProof(_))
     }
 
     pub fn is_protocol_version_request(&self) -> bool {
@@ -98,6 +107,16 @@ pub struct NewTransactionsWithProofRequest {
     pub include_events: bool, // Whether or not to include events in the response
 }
 
+/// A storage service request for fetching a new transaction or output list
+/// beyond the already known version and epoch.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NewTransactionsOrOutputsWithProofRequest {
+    pub known_version: u64,             // The highest known version
+    pub known_epoch: u64,               // The highest known epoch
+    pub include_events: bool,           // Whether or not to include events in the response
+    pub max_num_output_reductions: u64, // The max num of output reductions before fallback
+}
+
 /// A storage service request for fetching a list of state
 /// values at a specified version.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -125,3 +144,15 @@ pub struct TransactionsWithProofRequest {
     pub end_version: u64,     // The ending version of the transaction list (inclusive)
     pub include_events: bool, // Whether or not to include events in the response
 }
+
+/// A storage service request for fetching a transaction list or a transaction
+/// output list with a corresponding proof. The server returns the outputs if
+/// they fit into a network frame, and falls back to the transactions otherwise.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct TransactionsOrOutputsWithProofRequest {
+    pub proof_version: u64,             // The version the proof is relative to
+    pub start_version: u64,             // The starting version of the list
+    pub end_version: u64,               // The ending version of the list (inclusive)
+    pub include_events: bool,           // Whether or not to include events in the response
+    pub max_num_output_reductions: u64, // The max num of output reductions before fallback
+}
@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::requests::DataRequest::{
-    GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof, GetNewTransactionsWithProof,
-    GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-    GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsWithProof,
+    GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
+    GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof, GetNumberOfStatesAtVersion,
+    GetServerProtocolVersion, GetStateValuesWithProof, GetStorageServerSummary,
+    GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof, GetTransactionsWithProof,
 };
 use crate::responses::Error::DegenerateRangeError;
 use crate::{Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL};
@@ -28,6 +29,12 @@ use thiserror::Error;
 /// 5k TPS for a 5 second delay, which should be more than enough.
 pub const OPTIMISTIC_FETCH_VERSION_DELTA: u64 = 25000;
 
+/// A useful type to hold optional transaction data
+pub type TransactionOrOutputListWithProof = (
+    Option<TransactionListWithProof>,
+    Option<TransactionOutputListWithProof>,
+);
+
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
     #[error("Data range cannot be degenerate!")]
@@ -107,6 +114,8 @@ impl StorageServiceResponse {
 }
 
 /// A single data response.
+/// Note: new variants must be appended to the end of the enum to
+/// preserve the BCS encoding of existing variants.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
 pub enum DataResponse {
@@ -119,6 +128,8 @@ pub enum DataResponse {
     StorageServerSummary(StorageServerSummary),
     TransactionOutputsWithProof(TransactionOutputListWithProof),
     TransactionsWithProof(TransactionListWithProof),
+    NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
+    TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
 }
 
 impl DataResponse {
@@ -134,6 +145,8 @@ impl DataResponse {
             Self::StorageServerSummary(_) => "storage_server_summary",
             Self::TransactionOutputsWithProof(_) => "transaction_outputs_with_proof",
             Self::TransactionsWithProof(_) => "transactions_with_proof",
+            Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
+            Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
         }
     }
 }
@@ -200,6 +213,22 @@ impl TryFrom<StorageServiceResponse>
     }
 }
 
+impl TryFrom<StorageServiceResponse>
+    for (TransactionOrOutputListWithProof, LedgerInfoWithSignatures)
+{
+    type Error = crate::responses::Error;
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::NewTransactionsOrOutputsWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected new_transactions_or_outputs_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for (TransactionListWithProof, LedgerInfoWithSignatures) {
     type Error = crate::responses::Error;
     fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
@@ -270,6 +299,20 @@ impl TryFrom<StorageServiceResponse> for TransactionOutputListWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for TransactionOrOutputListWithProof {
+    type Error = crate::responses::Error;
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::TransactionsOrOutputsWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected transactions_or_outputs_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for TransactionListWithProof {
     type Error = crate::responses::Error;
     fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
@@ -322,6 +365,7 @@ impl ProtocolMetadata {
         match &request.data_request {
             GetNewTransactionsWithProof(_)
             | GetNewTransactionOutputsWithProof(_)
+            | GetNewTransactionsOrOutputsWithProof(_)
             | GetNumberOfStatesAtVersion(_)
             | GetServerProtocolVersion
             | GetStorageServerSummary => true,
@@ -361,6 +405,16 @@ impl ProtocolMetadata {
                     self.max_transaction_chunk_size >= chunk_size
                 })
             }),
+            GetTransactionsOrOutputsWithProof(request) => CompleteDataRange::new(
+                request.start_version,
+                request.end_version,
+            )
+            .map_or(false, |range| {
+                range.len().map_or(false, |chunk_size| {
+                    self.max_transaction_chunk_size >= chunk_size
+                        && self.max_transaction_output_chunk_size >= chunk_size
+                })
+            }),
         }
     }
 }
@@ -421,6 +475,9 @@ impl DataSummary {
             GetNewTransactionsWithProof(request) => {
                 self.can_service_optimistic_request(request.known_version)
             }
+            GetNewTransactionsOrOutputsWithProof(request) => {
+                self.can_service_optimistic_request(request.known_version)
+            }
             GetNumberOfStatesAtVersion(version) => self
                 .states
                 .map(|range| range.contains(*version))
@@ -481,6 +538,31 @@ impl DataSummary {
 
                 can_serve_txns && can_create_proof
             }
+            GetTransactionsOrOutputsWithProof(request) => {
+                let desired_range =
+                    match CompleteDataRange::new(request.start_version, request.end_version) {
+                        Ok(desired_range) => desired_range,
+                        Err(_) => return false,
+                    };
+
+                // The server may respond with either transactions or outputs
+                let can_serve_txns = self
+                    .transactions
+                    .map(|range| range.superset_of(&desired_range))
+                    .unwrap_or(false);
+                let can_serve_outputs = self
+                    .transaction_outputs
+                    .map(|range| range.superset_of(&desired_range))
+                    .unwrap_or(false);
+
+                let can_create_proof = self
+                    .synced_ledger_info
+                    .as_ref()
+                    .map(|li| li.ledger_info().version() >= request.proof_version)
+                    .unwrap_or(false);
+
+                can_serve_txns && can_serve_outputs && can_create_proof
+            }
         }
     }
 
@@ -3,18 +3,26 @@
 
 use crate::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
-        TransactionOutputsWithProofRequest, TransactionsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
+        NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+        StateValuesWithProofRequest, TransactionOutputsWithProofRequest,
+        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+    },
+    responses::{
+        CompleteDataRange, DataResponse, DataSummary, ProtocolMetadata, ServerProtocolVersion,
+        StorageServerSummary,
     },
-    responses::{CompleteDataRange, DataSummary, ProtocolMetadata},
     Epoch, StorageServiceRequest,
 };
 use aptos_crypto::hash::HashValue;
 use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
+    epoch_change::EpochChangeProof,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    transaction::Version,
+    proof::SparseMerkleRangeProof,
+    state_store::state_value::StateValueChunkWithProof,
+    transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use claims::{assert_err, assert_ok};
 use proptest::{arbitrary::any, prelude::*};
@@ -136,6 +144,35 @@ fn test_data_summary_can_service_txn_outputs_request() {
     }
 }
 
+#[test]
+fn test_data_summary_can_service_txns_or_outputs_request() {
+    let summary = DataSummary {
+        synced_ledger_info: Some(create_mock_ledger_info(250)),
+        transactions: Some(create_range(50, 200)),
+        transaction_outputs: Some(create_range(100, 250)),
+        ..Default::default()
+    };
+
+    for compression in [true, false] {
+        // both in range and can provide proof => can service
+        assert!(summary.can_service(&txns_or_outputs_request(225, 100, 200, compression)));
+        assert!(summary.can_service(&txns_or_outputs_request(225, 125, 175, compression)));
+        assert!(summary.can_service(&txns_or_outputs_request(250, 200, 200, compression)));
+
+        // can provide proof, but one of the ranges is missing => cannot service
+        assert!(!summary.can_service(&txns_or_outputs_request(225, 50, 200, compression)));
+        assert!(!summary.can_service(&txns_or_outputs_request(225, 100, 250, compression)));
+        assert!(!summary.can_service(&txns_or_outputs_request(225, 99, 201, compression)));
+
+        // both in range, but cannot provide proof => cannot service
+        assert!(!summary.can_service(&txns_or_outputs_request(251, 100, 200, compression)));
+        assert!(!summary.can_service(&txns_or_outputs_request(300, 150, 150, compression)));
+
+        // invalid range
+        assert!(!summary.can_service(&txns_or_outputs_request(225, 175, 125, compression)));
+    }
+}
+
 #[test]
 fn test_data_summary_can_service_state_chunk_request() {
     let summary = DataSummary {
@@ -179,11 +216,120 @@ fn test_protocol_metadata_can_service() {
         assert!(metadata.can_service(&outputs_request(200, 100, 199, compression)));
         assert!(!metadata.can_service(&outputs_request(200, 100, 200, compression)));
 
+        assert!(metadata.can_service(&txns_or_outputs_request(200, 100, 199, compression)));
+        assert!(!metadata.can_service(&txns_or_outputs_request(200, 100, 200, compression)));
+
         assert!(metadata.can_service(&state_values_request(200, 100, 199, compression)));
         assert!(!metadata.can_service(&state_values_request(200, 100, 200, compression)));
     }
 }
 
+#[test]
+fn test_data_request_variant_indices() {
+    // Verify the BCS variant index of each data request. These indices are
+    // part of the wire format and must never change (new variants must be
+    // appended to the end of the enum).
+    let data_requests = vec![
+        DataRequest::GetEpochEndingLedgerInfos(EpochEndingLedgerInfoRequest {
+            start_epoch: 0,
+            expected_end_epoch: 0,
+        }),
+        DataRequest::GetNewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest {
+            known_version: 0,
+            known_epoch: 0,
+        }),
+        DataRequest::GetNewTransactionsWithProof(NewTransactionsWithProofRequest {
+            known_version: 0,
+            known_epoch: 0,
+            include_events: false,
+        }),
+        DataRequest::GetNumberOfStatesAtVersion(0),
+        DataRequest::GetServerProtocolVersion,
+        DataRequest::GetStateValuesWithProof(StateValuesWithProofRequest {
+            version: 0,
+            start_index: 0,
+            end_index: 0,
+        }),
+        DataRequest::GetStorageServerSummary,
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 0,
+            start_version: 0,
+            end_version: 0,
+        }),
+        DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+            proof_version: 0,
+            start_version: 0,
+            end_version: 0,
+            include_events: false,
+        }),
+        DataRequest::GetNewTransactionsOrOutputsWithProof(
+            NewTransactionsOrOutputsWithProofRequest {
+                known_version: 0,
+                known_epoch: 0,
+                include_events: false,
+                max_num_output_reductions: 0,
+            },
+        ),
+        DataRequest::GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest {
+            proof_version: 0,
+            start_version: 0,
+            end_version: 0,
+            include_events: false,
+            max_num_output_reductions: 0,
+        }),
+    ];
+    for (expected_index, data_request) in data_requests.iter().enumerate() {
+        verify_variant_index(data_request, expected_index);
+    }
+}
+
+#[test]
+fn test_data_response_variant_indices() {
+    // Verify the BCS variant index of each data response. These indices are
+    // part of the wire format and must never change (new variants must be
+    // appended to the end of the enum).
+    let ledger_info = create_mock_ledger_info(0);
+    let state_value_chunk_with_proof = StateValueChunkWithProof {
+        first_index: 0,
+        last_index: 0,
+        first_key: HashValue::zero(),
+        last_key: HashValue::zero(),
+        raw_values: vec![],
+        proof: SparseMerkleRangeProof::new(vec![]),
+        root_hash: HashValue::zero(),
+    };
+    let data_responses = vec![
+        DataResponse::EpochEndingLedgerInfos(EpochChangeProof::new(vec![], false)),
+        DataResponse::NewTransactionOutputsWithProof((
+            TransactionOutputListWithProof::new_empty(),
+            ledger_info.clone(),
+        )),
+        DataResponse::NewTransactionsWithProof((
+            TransactionListWithProof::new_empty(),
+            ledger_info.clone(),
+        )),
+        DataResponse::NumberOfStatesAtVersion(0),
+        DataResponse::ServerProtocolVersion(ServerProtocolVersion {
+            protocol_version: 0,
+        }),
+        DataResponse::StateValueChunkWithProof(state_value_chunk_with_proof),
+        DataResponse::StorageServerSummary(StorageServerSummary::default()),
+        DataResponse::TransactionOutputsWithProof(TransactionOutputListWithProof::new_empty()),
+        DataResponse::TransactionsWithProof(TransactionListWithProof::new_empty()),
+        DataResponse::NewTransactionsOrOutputsWithProof((
+            (Some(TransactionListWithProof::new_empty()), None),
+            ledger_info,
+        )),
+        DataResponse::TransactionsOrOutputsWithProof((
+            None,
+            Some(TransactionOutputListWithProof::new_empty()),
+        )),
+    ];
+    for (expected_index, data_response) in data_responses.iter().enumerate() {
+        verify_variant_index(data_response, expected_index);
+    }
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(1000))]
 
@@ -194,6 +340,13 @@ proptest! {
     }
 }
 
+/// Verifies that the given enum value is serialized with the expected BCS
+/// variant index (i.e., the first ULEB128 encoded byte).
+fn verify_variant_index<T: serde::Serialize>(value: &T, expected_index: usize) {
+    let serialized_bytes = bcs::to_bytes(value).unwrap();
+    assert_eq!(serialized_bytes[0] as usize, expected_index);
+}
+
 fn create_mock_ledger_info(version: Version) -> LedgerInfoWithSignatures {
     LedgerInfoWithSignatures::new(
         LedgerInfo::new(
@@ -246,6 +399,23 @@ fn outputs_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+fn txns_or_outputs_request(
+    proof_version: Version,
+    start_version: Version,
+    end_version: Version,
+    use_compression: bool,
+) -> StorageServiceRequest {
+    let data_request =
+        DataRequest::GetTransactionsOrOutputsWithProof(TransactionsOrOutputsWithProofRequest {
+            proof_version,
+            start_version,
+            end_version,
+            include_events: true,
+            max_num_output_reductions: 0,
+        });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 fn state_values_request(
     version: Version,
     start_index: u64,
@@ -111,7 +111,9 @@ fn test_pruner_config() {
                 prune_window: 100,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
+                enable_archive: false,
             },
+            None,
         );
         assert_eq!(ledger_pruner.is_pruner_enabled(), enable);
         assert_eq!(ledger_pruner.get_prune_window(), 100);
@@ -192,6 +194,7 @@ pub fn test_state_merkle_pruning_impl(
                 prune_window: 10,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
+                enable_archive: false,
             },
             state_merkle_pruner_config: StateMerklePrunerConfig {
                 enable: true,
@@ -10,9 +10,10 @@ use crate::{
     },
     state_store::StateStore,
     transaction_store::TransactionStore,
+    MAX_STATE_SNAPSHOT_DELTA_CHANGES,
 };
 use anyhow::{anyhow, ensure, Context, Result};
-use aptos_crypto::hash::HashValue;
+use aptos_crypto::hash::{CryptoHash, HashValue};
 use aptos_types::write_set::WriteSet;
 use aptos_types::{
     contract_event::ContractEvent,
@@ -22,7 +23,8 @@ use aptos_types::{
     transaction::{Transaction, TransactionInfo, Version},
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt, sync::Arc};
+use std::{collections::BTreeMap, fmt, sync::Arc};
+use storage_interface::DbReader;
 
 /// `BackupHandler` provides functionalities for AptosDB data backup.
 #[derive(Clone)]
@@ -142,6 +144,55 @@ impl BackupHandler {
             .get_value_range_proof(rightmost_key, version)
     }
 
+    /// Gets an iterator which yields all state keys written to by the transactions in
+    /// (`base_version`, `version`], in the order of the hashed keys, together with their values at
+    /// `version` (`None` if deleted). Applied on top of the state at `base_version`, these result
+    /// in the state at `version`.
+    ///
+    /// The changed keys are collected in memory to sort them, so this fails once more than
+    /// `MAX_STATE_SNAPSHOT_DELTA_CHANGES` keys changed, in which case a full state snapshot should
+    /// be taken instead.
+    pub fn get_state_value_changes_iter(
+        &self,
+        base_version: Version,
+        version: Version,
+    ) -> Result<impl Iterator<Item = Result<(StateKey, Option<StateValue>)>> + '_> {
+        ensure!(
+            version > base_version,
+            "Bad state value changes range: ({}, {}]",
+            base_version,
+            version,
+        );
+        let mut changed_keys = BTreeMap::new();
+        for write_set_res in self
+            .transaction_store
+            .get_write_set_iter(base_version + 1, (version - base_version) as usize)?
+        {
+            for (state_key, _write_op) in write_set_res?.iter() {
+                changed_keys.insert(state_key.hash(), state_key.clone());
+            }
+            ensure!(
+                changed_keys.len() <= MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+                "More than {} state keys changed in ({}, {}], take a full state snapshot instead.",
+                MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+                base_version,
+                version,
+            );
+        }
+
+        Ok(changed_keys
+            .into_values()
+            .enumerate()
+            .map(move |(idx, state_key)| {
+                BACKUP_STATE_SNAPSHOT_VERSION.set(version as i64);
+                BACKUP_STATE_SNAPSHOT_LEAF_IDX.set(idx as i64);
+                let value = self
+                    .state_store
+                    .get_state_value_by_version(&state_key, version)?;
+                Ok((state_key, value))
+            }))
+    }
+
     /// Gets the epoch, committed version, and synced version of the DB.
     pub fn get_db_state(&self) -> Result<Option<DbState>> {
         Ok(self
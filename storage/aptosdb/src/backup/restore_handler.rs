@@ -17,7 +17,7 @@ use aptos_types::{
     transaction::{Transaction, TransactionInfo, Version},
 };
 use schemadb::DB;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use storage_interface::DbReader;
 
 /// Provides functionalities for AptosDB data restore.
@@ -64,6 +64,21 @@ impl RestoreHandler {
         )
     }
 
+    pub fn save_state_snapshot_delta(
+        &self,
+        base_version: Version,
+        version: Version,
+        expected_root_hash: HashValue,
+        changes: &HashMap<StateKey, Option<StateValue>>,
+    ) -> Result<()> {
+        self.state_store.apply_state_snapshot_delta(
+            base_version,
+            version,
+            expected_root_hash,
+            changes,
+        )
+    }
+
     pub fn reset_state_store(&self) {
         self.state_store.reset();
     }
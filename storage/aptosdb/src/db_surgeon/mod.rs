@@ -0,0 +1,476 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file defines `DbSurgeon`, which performs offline repairs and inspection on an AptosDB that
+//! no node is running on: truncating the DB back to a version, dumping column families in human
+//! readable form and exporting state snapshots to portable files.
+
+use crate::{
+    pruner::pruner_manager::PrunerManager,
+    schema::{
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        epoch_by_version::EpochByVersionSchema,
+        event::EventSchema,
+        event_accumulator::EventAccumulatorSchema,
+        event_by_key::EventByKeySchema,
+        event_by_version::EventByVersionSchema,
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+        ledger_info::LedgerInfoSchema,
+        stale_node_index::StaleNodeIndexSchema,
+        stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
+        stale_state_value_index::StaleStateValueIndexSchema,
+        state_value::StateValueSchema,
+        transaction::TransactionSchema,
+        transaction_accumulator::TransactionAccumulatorSchema,
+        transaction_by_account::TransactionByAccountSchema,
+        transaction_by_hash::TransactionByHashSchema,
+        transaction_info::TransactionInfoSchema,
+        version_data::VersionDataSchema,
+        write_set::WriteSetSchema,
+        DB_METADATA_CF_NAME, EPOCH_BY_VERSION_CF_NAME, EVENT_ACCUMULATOR_CF_NAME,
+        EVENT_BY_KEY_CF_NAME, EVENT_BY_VERSION_CF_NAME, EVENT_CF_NAME,
+        JELLYFISH_MERKLE_NODE_CF_NAME, LEDGER_INFO_CF_NAME, STALE_NODE_INDEX_CF_NAME,
+        STALE_NODE_INDEX_CROSS_EPOCH_CF_NAME, STALE_STATE_VALUE_INDEX_CF_NAME, STATE_VALUE_CF_NAME,
+        TRANSACTION_ACCUMULATOR_CF_NAME, TRANSACTION_BY_ACCOUNT_CF_NAME,
+        TRANSACTION_BY_HASH_CF_NAME, TRANSACTION_CF_NAME, TRANSACTION_INFO_CF_NAME,
+        VERSION_DATA_CF_NAME, WRITE_SET_CF_NAME,
+    },
+    AptosDB, LEDGER_DB_NAME, STATE_MERKLE_DB_NAME,
+};
+use anyhow::{bail, ensure, format_err, Result};
+use aptos_config::config::{
+    RocksdbConfigs, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    proof::position::Position,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use aptosdb_indexer::{Indexer, INDEX_DB_NAME};
+use itertools::zip_eq;
+use schemadb::{
+    schema::{Schema, SeekKeyCodec},
+    ReadOptions, SchemaBatch, DB,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Number of versions whose ledger data is deleted in one write batch when truncating.
+const TRUNCATION_BATCH_SIZE: Version = 10_000;
+/// Number of keys deleted in one write batch when truncating a column family by range.
+const MAX_DELETIONS_PER_BATCH: usize = 100_000;
+
+/// Header of an exported state snapshot file. It is followed by `num_items` records of BCS encoded
+/// `(StateKey, StateValue)`. The header and every record are prefixed with their length as a big
+/// endian u32.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotHeader {
+    pub version: Version,
+    pub root_hash: HashValue,
+    pub num_items: u64,
+}
+
+/// `DbSurgeon` operates on an AptosDB directory exclusively, so the node using it must be stopped.
+pub struct DbSurgeon {
+    db: AptosDB,
+    indexer: Option<Indexer>,
+}
+
+impl DbSurgeon {
+    pub fn open<P: AsRef<Path> + Clone>(
+        db_root_path: P,
+        rocksdb_configs: RocksdbConfigs,
+    ) -> Result<Self> {
+        // The indexer is opened separately, to avoid AptosDB catching it up with the ledger.
+        let indexer = if db_root_path.as_ref().join(INDEX_DB_NAME).exists() {
+            Some(Indexer::open(
+                &db_root_path,
+                rocksdb_configs.index_db_config,
+            )?)
+        } else {
+            None
+        };
+        let db = AptosDB::open(
+            db_root_path,
+            false, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            rocksdb_configs,
+            false, /* enable_indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+
+        Ok(Self { db, indexer })
+    }
+
+    /// Deletes everything committed after `target_version` from the ledger db, the state merkle db
+    /// and the index db, as if the DB had never gone past it. Refuses to truncate into pruned
+    /// history, i.e. if the ledger pruner or the state merkle pruners have moved past the latest
+    /// state snapshot at or before `target_version`, or if a state snapshot restore is in progress
+    /// after it.
+    ///
+    /// The in-memory state of the DB is stale afterwards, which is why this consumes the surgeon.
+    /// Truncation is done from the latest version downwards and can be rerun if interrupted.
+    pub fn truncate(self, target_version: Version) -> Result<()> {
+        let latest_version = self
+            .db
+            .ledger_store
+            .get_latest_transaction_info_option()?
+            .map(|(version, _)| version)
+            .ok_or_else(|| format_err!("The DB is empty."))?;
+        ensure!(
+            target_version <= latest_version,
+            "Target version {} is after the latest version {}.",
+            target_version,
+            latest_version,
+        );
+        self.check_truncatable(target_version)?;
+        info!(
+            target_version = target_version,
+            latest_version = latest_version,
+            "Truncating AptosDB."
+        );
+
+        let next_version = target_version + 1;
+        self.truncate_state_merkle_db(next_version)?;
+        self.truncate_ledger_db(next_version, latest_version + 1)?;
+        self.truncate_pruner_progress(target_version)?;
+        if let Some(indexer) = &self.indexer {
+            indexer.truncate(target_version)?;
+        }
+
+        info!(target_version = target_version, "Truncated AptosDB.");
+        Ok(())
+    }
+
+    /// Writes up to `limit` entries of the column family `cf_name` in the database `db_name` (one of
+    /// `ledger_db`, `state_merkle_db` and `index_indexer_db`) to `out` in human readable form, and returns
+    /// the number of entries written.
+    pub fn dump(
+        &self,
+        db_name: &str,
+        cf_name: &str,
+        limit: usize,
+        out: &mut dyn Write,
+    ) -> Result<usize> {
+        match db_name {
+            LEDGER_DB_NAME => self.dump_ledger_db(cf_name, limit, out),
+            STATE_MERKLE_DB_NAME => self.dump_state_merkle_db(cf_name, limit, out),
+            INDEX_DB_NAME => match &self.indexer {
+                Some(indexer) => indexer.dump(cf_name, limit, out),
+                None => bail!("The index db doesn't exist."),
+            },
+            _ => bail!("Unknown db {}.", db_name),
+        }
+    }
+
+    /// Exports the state snapshot at `version` to a file at `path`, in the format described by
+    /// `StateSnapshotHeader`, and returns its header.
+    pub fn export_state_snapshot(
+        &self,
+        version: Version,
+        path: &Path,
+    ) -> Result<StateSnapshotHeader> {
+        let state_store = &self.db.state_store;
+        let header = StateSnapshotHeader {
+            version,
+            root_hash: state_store.get_root_hash(version)?,
+            num_items: state_store.get_value_count(version)? as u64,
+        };
+
+        let mut file = BufWriter::new(File::create(path)?);
+        write_record(&mut file, &bcs::to_bytes(&header)?)?;
+        let mut num_items = 0;
+        for item in state_store.get_state_key_and_value_iter(version, HashValue::zero())? {
+            write_record(&mut file, &bcs::to_bytes(&item?)?)?;
+            num_items += 1;
+        }
+        ensure!(
+            num_items == header.num_items,
+            "Exported {} state items, expected {}.",
+            num_items,
+            header.num_items,
+        );
+        file.into_inner()?.sync_all()?;
+
+        info!(
+            version = version,
+            num_items = num_items,
+            path = path,
+            "Exported state snapshot."
+        );
+        Ok(header)
+    }
+
+    /// Checks that the DB can be truncated to `target_version` without losing data the truncated
+    /// DB needs. Once these checks pass, every pruner progress is at or before `target_version`,
+    /// which `truncate_pruner_progress` makes sure of in `db_metadata` as well. When they fail,
+    /// the pruners have already deleted data the truncated DB would need, i.e. ledger history up
+    /// to `target_version` or nodes of the state snapshot the buffered state is rebuilt from. That
+    /// data can't be recovered, so no value of the progress would make the truncated DB
+    /// consistent, and refusing is the only safe option. The DB can be restored from a backup
+    /// instead.
+    fn check_truncatable(&self, target_version: Version) -> Result<()> {
+        let ledger_min_readable_version = self.db.ledger_pruner.get_min_readable_version();
+        ensure!(
+            target_version >= ledger_min_readable_version,
+            "Ledger history before version {} is pruned, can't truncate to {}.",
+            ledger_min_readable_version,
+            target_version,
+        );
+
+        // The buffered state is rebuilt from the latest snapshot on open, so that snapshot has to
+        // be complete.
+        let snapshot_version = self
+            .db
+            .state_store
+            .state_db
+            .state_merkle_db
+            .get_state_snapshot_version_before(target_version + 1)?
+            .ok_or_else(|| {
+                format_err!("No state snapshot at or before version {}.", target_version)
+            })?;
+        for key in [
+            DbMetadataKey::StateMerklePrunerProgress,
+            DbMetadataKey::EpochEndingStateMerklePrunerProgress,
+        ] {
+            let pruner_progress = self
+                .db
+                .state_merkle_db
+                .get::<DbMetadataSchema>(&key)?
+                .map_or(0, |v| v.expect_version());
+            ensure!(
+                snapshot_version >= pruner_progress,
+                "{:?} is {}, the state snapshot at version {} might be incomplete.",
+                key,
+                pruner_progress,
+                snapshot_version,
+            );
+        }
+
+        let mut iter = self
+            .db
+            .ledger_db
+            .iter::<DbMetadataSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            if let (DbMetadataKey::StateSnapshotRestoreProgress(version), _) = item? {
+                ensure!(
+                    version <= target_version,
+                    "A state snapshot restore at version {} is in progress.",
+                    version,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rewrites the pruner progress in `db_metadata` of the ledger db and the state merkle db to
+    /// `target_version` where it is past it, so that the pruners never resume from a version the
+    /// truncated DB doesn't have.
+    fn truncate_pruner_progress(&self, target_version: Version) -> Result<()> {
+        for (db, key) in [
+            (&self.db.ledger_db, DbMetadataKey::LedgerPrunerProgress),
+            (
+                &self.db.state_merkle_db,
+                DbMetadataKey::StateMerklePrunerProgress,
+            ),
+            (
+                &self.db.state_merkle_db,
+                DbMetadataKey::EpochEndingStateMerklePrunerProgress,
+            ),
+        ] {
+            let pruner_progress = db
+                .get::<DbMetadataSchema>(&key)?
+                .map(|v| v.expect_version());
+            if pruner_progress.map_or(false, |v| v > target_version) {
+                db.put::<DbMetadataSchema>(&key, &DbMetadataValue::Version(target_version))?;
+                info!(
+                    key = ?key,
+                    target_version = target_version,
+                    "Rewrote pruner progress."
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the state merkle tree nodes created at or after `next_version` and the stale node
+    /// indices marking nodes stale since then, since those nodes are live again.
+    fn truncate_state_merkle_db(&self, next_version: Version) -> Result<()> {
+        let db = &self.db.state_merkle_db;
+        delete_from::<JellyfishMerkleNodeSchema, _>(db, &(next_version, 0u8))?;
+        delete_from::<StaleNodeIndexSchema, _>(db, &next_version)?;
+        delete_from::<StaleNodeIndexCrossEpochSchema, _>(db, &next_version)?;
+        Ok(())
+    }
+
+    /// Deletes the ledger data of the versions in [next_version, end_version).
+    fn truncate_ledger_db(&self, next_version: Version, end_version: Version) -> Result<()> {
+        let db = &self.db.ledger_db;
+
+        // Column families keyed by version first, which can be truncated by range.
+        delete_from::<EpochByVersionSchema, _>(db, &next_version)?;
+        delete_from::<EventAccumulatorSchema, _>(
+            db,
+            &(next_version, Position::from_inorder_index(0)),
+        )?;
+        delete_from::<StaleStateValueIndexSchema, _>(db, &next_version)?;
+        delete_from::<VersionDataSchema, _>(db, &next_version)?;
+        let mut batch = SchemaBatch::new();
+        let mut iter = db.iter::<LedgerInfoSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (epoch, ledger_info_with_sigs) = item?;
+            if ledger_info_with_sigs.ledger_info().version() >= next_version {
+                batch.delete::<LedgerInfoSchema>(&epoch)?;
+            }
+        }
+        db.write_schemas(batch)?;
+
+        // The rest is found by looking at the transactions, so those go last, in the same batch.
+        let mut end = end_version;
+        while end > next_version {
+            let begin = std::cmp::max(next_version, end.saturating_sub(TRUNCATION_BATCH_SIZE));
+            self.truncate_ledger_versions(begin, end)?;
+            info!(begin = begin, end = end, "Truncated ledger versions.");
+            end = begin;
+        }
+
+        Ok(())
+    }
+
+    fn truncate_ledger_versions(&self, begin: Version, end: Version) -> Result<()> {
+        let num_txns = (end - begin) as usize;
+        let txns = self
+            .db
+            .transaction_store
+            .get_transaction_iter(begin, num_txns)?
+            .collect::<Result<Vec<_>>>()?;
+        let write_sets = self.db.transaction_store.get_write_sets(begin, end)?;
+
+        let mut batch = SchemaBatch::new();
+        for (version, write_set) in zip_eq(begin..end, &write_sets) {
+            for (state_key, _write_op) in write_set.iter() {
+                batch.delete::<StateValueSchema>(&(state_key.clone(), version))?;
+            }
+            // Exactly the accumulator nodes frozen by appending this leaf.
+            let mut position = Position::from_leaf_index(version);
+            batch.delete::<TransactionAccumulatorSchema>(&position)?;
+            while position.is_right_child() {
+                position = position.parent();
+                batch.delete::<TransactionAccumulatorSchema>(&position)?;
+            }
+        }
+        self.db.event_store.prune_events(begin, end, &mut batch)?;
+        let transaction_store = &self.db.transaction_store;
+        transaction_store.prune_transaction_by_hash(&txns, &mut batch)?;
+        transaction_store.prune_transaction_by_account(&txns, &mut batch)?;
+        transaction_store.prune_write_set(begin, end, &mut batch)?;
+        transaction_store.prune_transaction_schema(begin, end, &mut batch)?;
+        transaction_store.prune_transaction_info_schema(begin, end, &mut batch)?;
+
+        self.db.ledger_db.write_schemas(batch)
+    }
+
+    fn dump_ledger_db(&self, cf_name: &str, limit: usize, out: &mut dyn Write) -> Result<usize> {
+        let db = &self.db.ledger_db;
+        match cf_name {
+            DB_METADATA_CF_NAME => db.dump::<DbMetadataSchema>(limit, out),
+            EPOCH_BY_VERSION_CF_NAME => db.dump::<EpochByVersionSchema>(limit, out),
+            EVENT_ACCUMULATOR_CF_NAME => db.dump::<EventAccumulatorSchema>(limit, out),
+            EVENT_BY_KEY_CF_NAME => db.dump::<EventByKeySchema>(limit, out),
+            EVENT_BY_VERSION_CF_NAME => db.dump::<EventByVersionSchema>(limit, out),
+            EVENT_CF_NAME => db.dump::<EventSchema>(limit, out),
+            LEDGER_INFO_CF_NAME => db.dump::<LedgerInfoSchema>(limit, out),
+            STALE_STATE_VALUE_INDEX_CF_NAME => db.dump::<StaleStateValueIndexSchema>(limit, out),
+            STATE_VALUE_CF_NAME => db.dump::<StateValueSchema>(limit, out),
+            TRANSACTION_CF_NAME => db.dump::<TransactionSchema>(limit, out),
+            TRANSACTION_ACCUMULATOR_CF_NAME => db.dump::<TransactionAccumulatorSchema>(limit, out),
+            TRANSACTION_BY_ACCOUNT_CF_NAME => db.dump::<TransactionByAccountSchema>(limit, out),
+            TRANSACTION_BY_HASH_CF_NAME => db.dump::<TransactionByHashSchema>(limit, out),
+            TRANSACTION_INFO_CF_NAME => db.dump::<TransactionInfoSchema>(limit, out),
+            VERSION_DATA_CF_NAME => db.dump::<VersionDataSchema>(limit, out),
+            WRITE_SET_CF_NAME => db.dump::<WriteSetSchema>(limit, out),
+            _ => bail!("Unknown column family {} in the ledger db.", cf_name),
+        }
+    }
+
+    fn dump_state_merkle_db(
+        &self,
+        cf_name: &str,
+        limit: usize,
+        out: &mut dyn Write,
+    ) -> Result<usize> {
+        let db = &self.db.state_merkle_db;
+        match cf_name {
+            DB_METADATA_CF_NAME => db.dump::<DbMetadataSchema>(limit, out),
+            JELLYFISH_MERKLE_NODE_CF_NAME => db.dump::<JellyfishMerkleNodeSchema>(limit, out),
+            STALE_NODE_INDEX_CF_NAME => db.dump::<StaleNodeIndexSchema>(limit, out),
+            STALE_NODE_INDEX_CROSS_EPOCH_CF_NAME => {
+                db.dump::<StaleNodeIndexCrossEpochSchema>(limit, out)
+            }
+            _ => bail!("Unknown column family {} in the state merkle db.", cf_name),
+        }
+    }
+}
+
+/// Reads a state snapshot file written by `DbSurgeon::export_state_snapshot`.
+pub fn read_exported_state_snapshot(
+    path: &Path,
+) -> Result<(StateSnapshotHeader, Vec<(StateKey, StateValue)>)> {
+    let mut file = BufReader::new(File::open(path)?);
+    let header: StateSnapshotHeader = bcs::from_bytes(&read_record(&mut file)?)?;
+    let items = (0..header.num_items)
+        .map(|_| Ok(bcs::from_bytes(&read_record(&mut file)?)?))
+        .collect::<Result<Vec<_>>>()?;
+    ensure!(
+        file.fill_buf()?.is_empty(),
+        "Trailing data after {} state items.",
+        header.num_items,
+    );
+    Ok((header, items))
+}
+
+/// Deletes all entries of schema `S` from `seek_key` on.
+fn delete_from<S: Schema, K: SeekKeyCodec<S>>(db: &DB, seek_key: &K) -> Result<usize> {
+    let mut iter = db.iter::<S>(ReadOptions::default())?;
+    iter.seek(seek_key)?;
+    let mut batch = SchemaBatch::new();
+    let mut num_deleted = 0;
+    for item in iter {
+        let (key, _value) = item?;
+        batch.delete::<S>(&key)?;
+        num_deleted += 1;
+        if num_deleted % MAX_DELETIONS_PER_BATCH == 0 {
+            db.write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+        }
+    }
+    db.write_schemas(batch)?;
+    Ok(num_deleted)
+}
+
+fn write_record(out: &mut impl Write, record: &[u8]) -> Result<()> {
+    out.write_all(&(record.len() as u32).to_be_bytes())?;
+    out.write_all(record)?;
+    Ok(())
+}
+
+fn read_record(input: &mut impl Read) -> Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    input.read_exact(&mut len)?;
+    let mut record = vec![0u8; u32::from_be_bytes(len) as usize];
+    input.read_exact(&mut record)?;
+    Ok(record)
+}
+
+#[cfg(test)]
+mod test;
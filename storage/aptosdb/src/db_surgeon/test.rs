@@ -0,0 +1,140 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::test_helper::{arb_blocks_to_commit, update_in_memory_state};
+use aptos_temppath::TempPath;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::TransactionToCommit};
+use proptest::prelude::*;
+use storage_interface::DbReader;
+
+fn verify_truncate(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+    truncate_after_block: usize,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test_with_indexer(&tmp_dir);
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    let mut target_version = 0;
+    for (idx, (txns_to_commit, ledger_info_with_sigs)) in input.iter().enumerate() {
+        update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+        if idx == truncate_after_block % input.len() {
+            target_version = next_ver - 1;
+        }
+    }
+    let latest_version = next_ver - 1;
+    let expected_txns = db
+        .get_transactions(0, target_version + 1, target_version, true)
+        .unwrap();
+    drop(db);
+
+    let surgeon = DbSurgeon::open(&tmp_dir, RocksdbConfigs::default()).unwrap();
+    let mut dumped = Vec::new();
+    assert_eq!(
+        surgeon
+            .dump(LEDGER_DB_NAME, TRANSACTION_CF_NAME, usize::MAX, &mut dumped)
+            .unwrap(),
+        next_ver as usize
+    );
+    assert_eq!(dumped.split(|b| *b == b'\n').count(), next_ver as usize + 1);
+    assert!(surgeon
+        .dump(
+            LEDGER_DB_NAME,
+            JELLYFISH_MERKLE_NODE_CF_NAME,
+            1,
+            &mut dumped
+        )
+        .is_err());
+
+    let snapshot_path = tmp_dir.path().join("state_snapshot");
+    let header = surgeon
+        .export_state_snapshot(target_version, &snapshot_path)
+        .unwrap();
+    surgeon.truncate(target_version).unwrap();
+
+    let db = AptosDB::new_for_test_with_indexer(&tmp_dir);
+    assert_eq!(
+        db.get_latest_transaction_info_option().unwrap().unwrap().0,
+        target_version
+    );
+    if target_version < latest_version {
+        assert!(db
+            .get_transaction_by_version(target_version + 1, latest_version, false)
+            .is_err());
+    }
+    if let Some(ledger_info) = db.ledger_store.get_latest_ledger_info_option() {
+        assert!(ledger_info.ledger_info().version() <= target_version);
+    }
+    assert!(db
+        .state_store
+        .get_all_jmt_nodes()
+        .unwrap()
+        .iter()
+        .all(|node_key| node_key.version() <= target_version));
+
+    let txns = db
+        .get_transactions(0, target_version + 1, target_version, true)
+        .unwrap();
+    assert_eq!(txns.transactions, expected_txns.transactions);
+    assert_eq!(txns.events, expected_txns.events);
+    assert_eq!(
+        txns.proof.transaction_infos,
+        expected_txns.proof.transaction_infos
+    );
+
+    let verifier = db.get_db_verifier();
+    assert_eq!(
+        verifier.verify_ledger(0, target_version + 1).unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        verifier.find_dangling_indices(0, Version::MAX).unwrap(),
+        vec![]
+    );
+    assert_eq!(
+        verifier
+            .verify_state_snapshots(0, target_version + 1, 1)
+            .unwrap(),
+        vec![]
+    );
+
+    let (read_header, items) = read_exported_state_snapshot(&snapshot_path).unwrap();
+    assert_eq!(read_header, header);
+    assert_eq!(
+        Some((target_version, header.root_hash)),
+        db.get_state_snapshot_before(target_version + 1).unwrap()
+    );
+    assert_eq!(items.len() as u64, header.num_items);
+    for (key, value) in items {
+        assert_eq!(
+            db.get_state_value_by_version(&key, target_version).unwrap(),
+            Some(value)
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_truncate(input in arb_blocks_to_commit(), truncate_after_block in 0usize..10) {
+        verify_truncate(input, truncate_after_block);
+    }
+}
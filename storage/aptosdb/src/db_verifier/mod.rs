@@ -0,0 +1,586 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file defines `DbVerifier`, which checks the consistency of a live AptosDB in place: the
+//! transaction accumulator against transaction infos, event accumulators, the secondary indices
+//! of transactions and the state merkle trees. Every inconsistency found is reported with the exact
+//! key it was found at.
+
+use crate::{
+    event_store::EventStore,
+    ledger_store::LedgerStore,
+    metrics::{VERIFIER_INCONSISTENCIES, VERIFIER_VERSION},
+    schema::{
+        event_accumulator::EventAccumulatorSchema,
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema, transaction::TransactionSchema,
+        transaction_accumulator::TransactionAccumulatorSchema,
+        transaction_by_account::TransactionByAccountSchema,
+        transaction_by_hash::TransactionByHashSchema, transaction_info::TransactionInfoSchema,
+        EVENT_ACCUMULATOR_CF_NAME, JELLYFISH_MERKLE_NODE_CF_NAME, TRANSACTION_ACCUMULATOR_CF_NAME,
+        TRANSACTION_BY_ACCOUNT_CF_NAME, TRANSACTION_BY_HASH_CF_NAME, TRANSACTION_CF_NAME,
+        TRANSACTION_INFO_CF_NAME,
+    },
+    state_store::StateStore,
+};
+use accumulator::{HashReader, MerkleAccumulator};
+use anyhow::{ensure, format_err, Result};
+use aptos_crypto::{
+    hash::{CryptoHash, EventAccumulatorHasher, TransactionAccumulatorHasher},
+    HashValue,
+};
+use aptos_jellyfish_merkle::{
+    node_type::{Node, NodeKey},
+    TreeReader,
+};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    proof::{accumulator::InMemoryAccumulator, position::Position, MerkleTreeInternalNode},
+    transaction::{Transaction, Version},
+};
+use schemadb::{ColumnFamilyName, ReadOptions, DB};
+use std::{
+    sync::{
+        mpsc::{self, RecvTimeoutError, Sender},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use thiserror::Error;
+
+/// Number of versions the background verifier checks at a time.
+const BACKGROUND_BATCH_SIZE: Version = 1000;
+
+/// An inconsistency found in the DB.
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum Inconsistency {
+    #[error("{cf_name}: no entry at {key}.")]
+    MissingEntry {
+        cf_name: ColumnFamilyName,
+        key: String,
+    },
+    #[error("{cf_name}: hash at {key} is {actual:x}, expected {expected:x}.")]
+    HashMismatch {
+        cf_name: ColumnFamilyName,
+        key: String,
+        expected: HashValue,
+        actual: HashValue,
+    },
+    #[error("{cf_name}: entry at {key} points to version {actual:?}, expected {expected}.")]
+    IndexMismatch {
+        cf_name: ColumnFamilyName,
+        key: String,
+        expected: Version,
+        actual: Option<Version>,
+    },
+    #[error("{cf_name}: entry at {key} points to version {version}, which doesn't match it.")]
+    DanglingIndex {
+        cf_name: ColumnFamilyName,
+        key: String,
+        version: Version,
+    },
+    #[error("Root hash of the {tree} at version {version} is {actual:x}, expected {expected:x}.")]
+    RootHashMismatch {
+        tree: &'static str,
+        version: Version,
+        expected: HashValue,
+        actual: HashValue,
+    },
+    #[error("Failed to read the {tree} at version {version}: {error}")]
+    Unreadable {
+        tree: &'static str,
+        version: Version,
+        error: String,
+    },
+}
+
+impl Inconsistency {
+    fn missing(cf_name: ColumnFamilyName, key: impl ToString) -> Self {
+        Self::MissingEntry {
+            cf_name,
+            key: key.to_string(),
+        }
+    }
+}
+
+/// `DbVerifier` provides functionalities to verify AptosDB in place.
+#[derive(Clone)]
+pub struct DbVerifier {
+    ledger_db: Arc<DB>,
+    ledger_store: Arc<LedgerStore>,
+    event_store: Arc<EventStore>,
+    state_store: Arc<StateStore>,
+}
+
+impl DbVerifier {
+    pub(crate) fn new(
+        ledger_db: Arc<DB>,
+        ledger_store: Arc<LedgerStore>,
+        event_store: Arc<EventStore>,
+        state_store: Arc<StateStore>,
+    ) -> Self {
+        Self {
+            ledger_db,
+            ledger_store,
+            event_store,
+            state_store,
+        }
+    }
+
+    /// Verifies the versions in [start_version, end_version): the transaction accumulator leaves
+    /// and the nodes they freeze against the transaction infos, the transactions and their events
+    /// against the hashes in the transaction infos, and the presence of the transactions in the
+    /// `transaction_by_hash` and `transaction_by_account` indices. If the latest ledger info is in
+    /// range, the transaction accumulator root hash is checked against it as well.
+    pub fn verify_ledger(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Vec<Inconsistency>> {
+        let mut inconsistencies = Vec::new();
+        for version in start_version..end_version {
+            self.verify_version(version, &mut inconsistencies)?;
+        }
+
+        if let Some(ledger_info) = self.ledger_store.get_latest_ledger_info_option() {
+            let ledger_info = ledger_info.ledger_info();
+            let version = ledger_info.version();
+            if (start_version..end_version).contains(&version) {
+                match self.ledger_store.get_root_hash(version) {
+                    Ok(root_hash) if root_hash != ledger_info.transaction_accumulator_hash() => {
+                        inconsistencies.push(Inconsistency::RootHashMismatch {
+                            tree: "transaction accumulator",
+                            version,
+                            expected: ledger_info.transaction_accumulator_hash(),
+                            actual: root_hash,
+                        })
+                    }
+                    Ok(_) => (),
+                    Err(error) => inconsistencies.push(Inconsistency::Unreadable {
+                        tree: "transaction accumulator",
+                        version,
+                        error: error.to_string(),
+                    }),
+                }
+            }
+        }
+
+        Ok(inconsistencies)
+    }
+
+    /// Scans the whole `transaction_by_hash` and `transaction_by_account` indices for entries
+    /// pointing to a version in [start_version, end_version) that doesn't hold the transaction
+    /// they are keyed by.
+    pub fn find_dangling_indices(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Vec<Inconsistency>> {
+        let mut inconsistencies = Vec::new();
+        let range = start_version..end_version;
+
+        let mut iter = self
+            .ledger_db
+            .iter::<TransactionByHashSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let (hash, version) = item?;
+            if !range.contains(&version) {
+                continue;
+            }
+            match self.ledger_db.get::<TransactionSchema>(&version)? {
+                Some(txn) if txn.hash() == hash => (),
+                _ => inconsistencies.push(Inconsistency::DanglingIndex {
+                    cf_name: TRANSACTION_BY_HASH_CF_NAME,
+                    key: hash.to_hex(),
+                    version,
+                }),
+            }
+        }
+
+        let mut iter = self
+            .ledger_db
+            .iter::<TransactionByAccountSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let ((sender, sequence_number), version) = item?;
+            if !range.contains(&version) {
+                continue;
+            }
+            match self.ledger_db.get::<TransactionSchema>(&version)? {
+                Some(Transaction::UserTransaction(txn))
+                    if txn.sender() == sender && txn.sequence_number() == sequence_number => {}
+                _ => inconsistencies.push(Inconsistency::DanglingIndex {
+                    cf_name: TRANSACTION_BY_ACCOUNT_CF_NAME,
+                    key: format!("({}, {})", sender, sequence_number),
+                    version,
+                }),
+            }
+        }
+
+        Ok(inconsistencies)
+    }
+
+    /// Recomputes the state merkle tree root hash from the leaves up for every
+    /// `sample_interval`-th state snapshot in [start_version, end_version), checking every node
+    /// against the hash its parent keeps for it, and the root against the transaction info.
+    pub fn verify_state_snapshots(
+        &self,
+        start_version: Version,
+        end_version: Version,
+        sample_interval: usize,
+    ) -> Result<Vec<Inconsistency>> {
+        ensure!(sample_interval > 0, "Sample interval must be positive.");
+        let mut inconsistencies = Vec::new();
+        let state_merkle_db = &self.state_store.state_db.state_merkle_db;
+
+        // Snapshot roots are the nodes with an empty nibble path, which sort first among the
+        // nodes of their version, so seeking to the root of a version lands on the root of the
+        // next snapshot, skipping versions without one.
+        let mut iter = state_merkle_db.iter::<JellyfishMerkleNodeSchema>(ReadOptions::default())?;
+        let mut num_snapshots = 0;
+        let mut next_version = start_version;
+        while next_version < end_version {
+            iter.seek(&NodeKey::new_empty_path(next_version))?;
+            let root_key = match iter.next().transpose()? {
+                Some((node_key, _node)) => node_key,
+                None => break,
+            };
+            let version = root_key.version();
+            if version >= end_version {
+                break;
+            }
+            next_version = version + 1;
+            if !root_key.nibble_path().is_empty() {
+                // A version with nodes but no root, e.g. one that is partially pruned.
+                continue;
+            }
+            num_snapshots += 1;
+            if (num_snapshots - 1) % sample_interval != 0 {
+                continue;
+            }
+            let expected = match self
+                .ledger_db
+                .get::<TransactionInfoSchema>(&version)?
+                .and_then(|txn_info| txn_info.state_checkpoint_hash())
+            {
+                Some(hash) => hash,
+                // The transaction info is pruned.
+                None => continue,
+            };
+
+            info!(version = version, "Verifying state snapshot.");
+            if let Some(actual) = self.verify_state_node(root_key, &mut inconsistencies)? {
+                if actual != expected {
+                    inconsistencies.push(Inconsistency::RootHashMismatch {
+                        tree: "state merkle tree",
+                        version,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        Ok(inconsistencies)
+    }
+
+    /// Starts a thread which keeps verifying the ledger as it grows, from the current latest
+    /// version on, checking for new versions every `interval`. Inconsistencies are logged and
+    /// counted in the `aptos_storage_verifier_inconsistencies` metric. The thread is stopped when
+    /// the returned `BackgroundVerifier` is dropped.
+    pub fn spawn_background_verifier(self, interval: Duration) -> BackgroundVerifier {
+        let mut next_version = self
+            .ledger_store
+            .get_latest_ledger_info_option()
+            .map_or(0, |li| li.ledger_info().version() + 1);
+        let (quit_sender, quit_receiver) = mpsc::channel::<()>();
+        // Waits for `interval`, returns whether the verifier should quit instead.
+        let wait = move || {
+            !matches!(
+                quit_receiver.recv_timeout(interval),
+                Err(RecvTimeoutError::Timeout)
+            )
+        };
+
+        let worker_thread = thread::Builder::new()
+            .name("aptosdb_verifier".into())
+            .spawn(move || loop {
+                let ledger_next_version = self
+                    .ledger_store
+                    .get_latest_ledger_info_option()
+                    .map_or(0, |li| li.ledger_info().version() + 1);
+                if next_version >= ledger_next_version {
+                    if wait() {
+                        return;
+                    }
+                    continue;
+                }
+
+                let end_version =
+                    std::cmp::min(ledger_next_version, next_version + BACKGROUND_BATCH_SIZE);
+                match self.verify_ledger(next_version, end_version) {
+                    Ok(inconsistencies) => {
+                        for inconsistency in &inconsistencies {
+                            error!(inconsistency = %inconsistency, "AptosDB inconsistency found.");
+                        }
+                        VERIFIER_INCONSISTENCIES.inc_by(inconsistencies.len() as u64);
+                        VERIFIER_VERSION.set(end_version as i64 - 1);
+                        next_version = end_version;
+                    }
+                    Err(error) => {
+                        warn!(error = ?error, "Failed to verify AptosDB.");
+                        if wait() {
+                            return;
+                        }
+                    }
+                }
+            })
+            .expect("Creating verifier thread should succeed.");
+
+        BackgroundVerifier {
+            quit_sender: Some(quit_sender),
+            worker_thread: Some(worker_thread),
+        }
+    }
+
+    fn verify_version(
+        &self,
+        version: Version,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> Result<()> {
+        let txn_info = match self.ledger_db.get::<TransactionInfoSchema>(&version)? {
+            Some(txn_info) => txn_info,
+            None => {
+                inconsistencies.push(Inconsistency::missing(TRANSACTION_INFO_CF_NAME, version));
+                return Ok(());
+            }
+        };
+
+        self.verify_transaction_accumulator(version, txn_info.hash(), inconsistencies)?;
+
+        match self.ledger_db.get::<TransactionSchema>(&version)? {
+            Some(txn) => {
+                let hash = txn.hash();
+                if hash != txn_info.transaction_hash() {
+                    inconsistencies.push(Inconsistency::HashMismatch {
+                        cf_name: TRANSACTION_CF_NAME,
+                        key: version.to_string(),
+                        expected: txn_info.transaction_hash(),
+                        actual: hash,
+                    });
+                }
+                let indexed = self.ledger_db.get::<TransactionByHashSchema>(&hash)?;
+                if indexed != Some(version) {
+                    inconsistencies.push(Inconsistency::IndexMismatch {
+                        cf_name: TRANSACTION_BY_HASH_CF_NAME,
+                        key: hash.to_hex(),
+                        expected: version,
+                        actual: indexed,
+                    });
+                }
+                if let Transaction::UserTransaction(txn) = txn {
+                    let key = (txn.sender(), txn.sequence_number());
+                    let indexed = self.ledger_db.get::<TransactionByAccountSchema>(&key)?;
+                    if indexed != Some(version) {
+                        inconsistencies.push(Inconsistency::IndexMismatch {
+                            cf_name: TRANSACTION_BY_ACCOUNT_CF_NAME,
+                            key: format!("({}, {})", key.0, key.1),
+                            expected: version,
+                            actual: indexed,
+                        });
+                    }
+                }
+            }
+            None => inconsistencies.push(Inconsistency::missing(TRANSACTION_CF_NAME, version)),
+        }
+
+        let events = self.event_store.get_events_by_version(version)?;
+        let event_hashes: Vec<_> = events.iter().map(CryptoHash::hash).collect();
+        let events_root_hash =
+            InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash();
+        if events_root_hash != txn_info.event_root_hash() {
+            inconsistencies.push(Inconsistency::RootHashMismatch {
+                tree: "events",
+                version,
+                expected: txn_info.event_root_hash(),
+                actual: events_root_hash,
+            });
+        }
+        let reader = EventAccumulatorReader {
+            db: &self.ledger_db,
+            version,
+        };
+        match MerkleAccumulator::<_, EventAccumulatorHasher>::get_root_hash(
+            &reader,
+            event_hashes.len() as u64,
+        ) {
+            Ok(root_hash) if root_hash != txn_info.event_root_hash() => {
+                inconsistencies.push(Inconsistency::RootHashMismatch {
+                    tree: "event accumulator",
+                    version,
+                    expected: txn_info.event_root_hash(),
+                    actual: root_hash,
+                })
+            }
+            Ok(_) => (),
+            Err(error) => inconsistencies.push(Inconsistency::Unreadable {
+                tree: "event accumulator",
+                version,
+                error: error.to_string(),
+            }),
+        }
+
+        Ok(())
+    }
+
+    /// Checks the accumulator leaf of `version`, and the internal nodes that get frozen by
+    /// appending it. Each frozen node is checked exactly once over a range of versions.
+    fn verify_transaction_accumulator(
+        &self,
+        version: Version,
+        txn_info_hash: HashValue,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> Result<()> {
+        let mut position = Position::from_leaf_index(version);
+        let mut hash =
+            match self.check_accumulator_node(position, txn_info_hash, inconsistencies)? {
+                Some(hash) => hash,
+                None => return Ok(()),
+            };
+
+        while position.is_right_child() {
+            // A missing left sibling was reported when verifying the version it was frozen by, or
+            // it's out of range.
+            let left_hash = match self
+                .ledger_db
+                .get::<TransactionAccumulatorSchema>(&position.sibling())?
+            {
+                Some(left_hash) => left_hash,
+                None => break,
+            };
+            position = position.parent();
+            let expected =
+                MerkleTreeInternalNode::<TransactionAccumulatorHasher>::new(left_hash, hash).hash();
+            hash = match self.check_accumulator_node(position, expected, inconsistencies)? {
+                Some(hash) => hash,
+                None => break,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hash stored at `position`, after checking it against `expected`.
+    fn check_accumulator_node(
+        &self,
+        position: Position,
+        expected: HashValue,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> Result<Option<HashValue>> {
+        let actual = self
+            .ledger_db
+            .get::<TransactionAccumulatorSchema>(&position)?;
+        match actual {
+            Some(actual) if actual != expected => {
+                inconsistencies.push(Inconsistency::HashMismatch {
+                    cf_name: TRANSACTION_ACCUMULATOR_CF_NAME,
+                    key: position.to_string(),
+                    expected,
+                    actual,
+                })
+            }
+            Some(_) => (),
+            None => inconsistencies.push(Inconsistency::missing(
+                TRANSACTION_ACCUMULATOR_CF_NAME,
+                position,
+            )),
+        }
+        Ok(actual)
+    }
+
+    /// Returns the hash of the subtree at `node_key` recomputed from its leaves, or `None` if the
+    /// node is missing.
+    fn verify_state_node(
+        &self,
+        node_key: NodeKey,
+        inconsistencies: &mut Vec<Inconsistency>,
+    ) -> Result<Option<HashValue>> {
+        let node = match self
+            .state_store
+            .state_db
+            .state_merkle_db
+            .get_node_option(&node_key)?
+        {
+            Some(node) => node,
+            None => {
+                inconsistencies.push(Inconsistency::missing(
+                    JELLYFISH_MERKLE_NODE_CF_NAME,
+                    format!("{:?}", node_key),
+                ));
+                return Ok(None);
+            }
+        };
+
+        if let Node::Internal(internal_node) = &node {
+            for (nibble, child) in internal_node.children_sorted() {
+                let child_key = node_key.gen_child_node_key(child.version, *nibble);
+                match self.verify_state_node(child_key.clone(), inconsistencies)? {
+                    Some(actual) if actual != child.hash => {
+                        inconsistencies.push(Inconsistency::HashMismatch {
+                            cf_name: JELLYFISH_MERKLE_NODE_CF_NAME,
+                            key: format!("{:?}", child_key),
+                            expected: child.hash,
+                            actual,
+                        })
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(Some(node.hash()))
+    }
+}
+
+/// Handle of the thread started by `DbVerifier::spawn_background_verifier`, which stops the thread
+/// when dropped. The thread finishes the batch of versions it is verifying first.
+pub struct BackgroundVerifier {
+    /// Dropped to tell the worker thread to quit.
+    quit_sender: Option<Sender<()>>,
+    /// Joined upon destruction.
+    worker_thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for BackgroundVerifier {
+    fn drop(&mut self) {
+        self.quit_sender.take();
+        self.worker_thread
+            .take()
+            .expect("Verifier thread must exist.")
+            .join()
+            .expect("Verifier thread should join peacefully.");
+    }
+}
+
+struct EventAccumulatorReader<'a> {
+    db: &'a DB,
+    version: Version,
+}
+
+impl<'a> HashReader for EventAccumulatorReader<'a> {
+    fn get(&self, position: Position) -> Result<HashValue> {
+        self.db
+            .get::<EventAccumulatorSchema>(&(self.version, position))?
+            .ok_or_else(|| {
+                format_err!(
+                    "{}: no entry at ({}, {}).",
+                    EVENT_ACCUMULATOR_CF_NAME,
+                    self.version,
+                    position
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,95 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    test_helper::{arb_blocks_to_commit, update_in_memory_state},
+    AptosDB,
+};
+use aptos_temppath::TempPath;
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::TransactionToCommit};
+use proptest::prelude::*;
+
+fn verify_db_verifier(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+
+    let verifier = db.get_db_verifier();
+    assert_eq!(verifier.verify_ledger(0, next_ver).unwrap(), vec![]);
+    assert_eq!(verifier.find_dangling_indices(0, next_ver).unwrap(), vec![]);
+    assert_eq!(
+        verifier.verify_state_snapshots(0, next_ver, 1).unwrap(),
+        vec![]
+    );
+
+    // Corrupt the DB.
+    let bogus_hash = HashValue::random();
+    let txn_hash = db
+        .ledger_db
+        .get::<TransactionSchema>(&0)
+        .unwrap()
+        .unwrap()
+        .hash();
+    let leaf = Position::from_leaf_index(0);
+    db.ledger_db
+        .put::<TransactionAccumulatorSchema>(&leaf, &bogus_hash)
+        .unwrap();
+    db.ledger_db
+        .delete::<TransactionByHashSchema>(&txn_hash)
+        .unwrap();
+    db.ledger_db
+        .put::<TransactionByHashSchema>(&bogus_hash, &0)
+        .unwrap();
+
+    let inconsistencies = verifier.verify_ledger(0, next_ver).unwrap();
+    assert!(inconsistencies.contains(&Inconsistency::HashMismatch {
+        cf_name: TRANSACTION_ACCUMULATOR_CF_NAME,
+        key: leaf.to_string(),
+        expected: db.ledger_store.get_transaction_info(0).unwrap().hash(),
+        actual: bogus_hash,
+    }));
+    assert!(inconsistencies.contains(&Inconsistency::IndexMismatch {
+        cf_name: TRANSACTION_BY_HASH_CF_NAME,
+        key: txn_hash.to_hex(),
+        expected: 0,
+        actual: None,
+    }));
+    assert_eq!(
+        verifier.find_dangling_indices(0, next_ver).unwrap(),
+        vec![Inconsistency::DanglingIndex {
+            cf_name: TRANSACTION_BY_HASH_CF_NAME,
+            key: bogus_hash.to_hex(),
+            version: 0,
+        }]
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_db_verifier(input in arb_blocks_to_commit()) {
+        verify_db_verifier(input);
+    }
+}
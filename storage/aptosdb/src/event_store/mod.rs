@@ -9,6 +9,7 @@ use super::AptosDB;
 use crate::utils::iterators::EventsByVersionIter;
 use crate::{
     errors::AptosDbError,
+    ledger_archive::{read_archived_transaction, LedgerArchive},
     schema::{
         event::EventSchema, event_accumulator::EventAccumulatorSchema,
         event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
@@ -40,11 +41,20 @@ use std::{
 #[derive(Debug)]
 pub struct EventStore {
     db: Arc<DB>,
+    /// Where the events pruned from the db are read from, if set.
+    ledger_archive: Option<Arc<LedgerArchive>>,
 }
 
 impl EventStore {
     pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        Self::new_with_ledger_archive(db, None)
+    }
+
+    pub(crate) fn new_with_ledger_archive(
+        db: Arc<DB>,
+        ledger_archive: Option<Arc<LedgerArchive>>,
+    ) -> Self {
+        Self { db, ledger_archive }
     }
 
     /// Get all of the events given a transaction version.
@@ -62,6 +72,13 @@ impl EventStore {
             }
             events.push(event);
         }
+        if events.is_empty() {
+            if let Some(archived_events) =
+                read_archived_transaction(&self.ledger_archive, version, |txn| txn.events.clone())?
+            {
+                return Ok(archived_events);
+            }
+        }
 
         Ok(events)
     }
@@ -88,11 +105,14 @@ impl EventStore {
         version: Version,
         index: u64,
     ) -> Result<ContractEvent> {
-        self.db
-            .get::<EventSchema>(&(version, index))?
-            .ok_or_else(|| {
-                AptosDbError::NotFound(format!("Event {} of Txn {}", index, version)).into()
-            })
+        if let Some(event) = self.db.get::<EventSchema>(&(version, index))? {
+            return Ok(event);
+        }
+        read_archived_transaction(&self.ledger_archive, version, |txn| {
+            txn.events.get(index as usize).cloned()
+        })?
+        .flatten()
+        .ok_or_else(|| AptosDbError::NotFound(format!("Event {} of Txn {}", index, version)).into())
     }
 
     pub fn get_txn_ver_by_seq_num(&self, event_key: &EventKey, seq_num: u64) -> Result<u64> {
@@ -459,16 +479,40 @@ impl EventStore {
         start: Version,
         end: Version,
         db_batch: &mut SchemaBatch,
+    ) -> anyhow::Result<()> {
+        self.prune_events_impl(start, end, true /* prune_indices */, db_batch)
+    }
+
+    /// Prune the events in the range of version in [begin, end), but not their indices by key and
+    /// by version, which keep pointing to the events moved into the ledger archive.
+    pub fn prune_archived_events(
+        &self,
+        start: Version,
+        end: Version,
+        db_batch: &mut SchemaBatch,
+    ) -> anyhow::Result<()> {
+        self.prune_events_impl(start, end, false /* prune_indices */, db_batch)
+    }
+
+    fn prune_events_impl(
+        &self,
+        start: Version,
+        end: Version,
+        prune_indices: bool,
+        db_batch: &mut SchemaBatch,
     ) -> anyhow::Result<()> {
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
             for (current_index, event) in (events?).into_iter().enumerate() {
-                db_batch.delete::<EventByVersionSchema>(&(
-                    *event.key(),
-                    current_version as u64,
-                    event.sequence_number(),
-                ))?;
-                db_batch.delete::<EventByKeySchema>(&(*event.key(), event.sequence_number()))?;
+                if prune_indices {
+                    db_batch.delete::<EventByVersionSchema>(&(
+                        *event.key(),
+                        current_version as u64,
+                        event.sequence_number(),
+                    ))?;
+                    db_batch
+                        .delete::<EventByKeySchema>(&(*event.key(), event.sequence_number()))?;
+                }
                 db_batch.delete::<EventSchema>(&(current_version as u64, current_index as u64))?;
             }
             current_version += 1;
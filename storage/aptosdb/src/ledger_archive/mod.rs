@@ -0,0 +1,316 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This file defines the ledger archive, which keeps the ledger history pruned from the ledger db
+//! (transactions, transaction infos, write sets and events) in compressed, immutable segment
+//! files, so that it can still be served to readers from cheap disk.
+//!
+//! Every batch handled by the ledger pruner becomes one segment file, named after the version range
+//! it covers. Segments are written to a temporary file and renamed into place only after they are
+//! durable, so a segment file that exists is always complete.
+//!
+//! Along with the transactions, a segment keeps the transaction accumulator nodes pruned with
+//! them, so that proofs can still be built for archived transactions. The indices of transactions
+//! by hash and by account, and of events by key, aren't pruned when archiving: they stay in the
+//! ledger db and point into the archive. The ledger, transaction and event stores fall back to
+//! the archive when the data they're asked for is pruned.
+
+use crate::{EventStore, LedgerStore, TransactionStore};
+use accumulator::HashReader;
+use anyhow::{ensure, format_err, Result};
+use aptos_compression::{compress, decompress, metrics::CompressionClient};
+use aptos_crypto::HashValue;
+use aptos_infallible::{Mutex, RwLock};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    contract_event::ContractEvent,
+    proof::position::Position,
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+use itertools::izip;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsStr,
+    fs,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+pub const LEDGER_ARCHIVE_DIR_NAME: &str = "ledger_archive";
+
+const SEGMENT_FILE_EXTENSION: &str = "seg";
+const TEMP_FILE_EXTENSION: &str = "tmp";
+/// Upper bound of a segment file in bytes, both before and after compression.
+const MAX_SEGMENT_BYTES: usize = 1 << 30;
+/// Number of decompressed segments kept in memory to serve consecutive reads.
+const NUM_CACHED_SEGMENTS: usize = 16;
+
+/// Everything the ledger db knows about a transaction, as kept in a segment file.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct ArchivedTransaction {
+    pub transaction: Transaction,
+    pub info: TransactionInfo,
+    pub write_set: WriteSet,
+    pub events: Vec<ContractEvent>,
+}
+
+/// The content of a segment file.
+#[derive(Debug, Deserialize, Serialize)]
+struct Segment {
+    transactions: Vec<ArchivedTransaction>,
+    /// The transaction accumulator nodes pruned with the transactions, by in-order index of their
+    /// position, in ascending order.
+    accumulator_nodes: Vec<(u64, HashValue)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct LedgerArchive {
+    dir: PathBuf,
+    /// Maps the first version of each segment to the version following its last.
+    segments: RwLock<BTreeMap<Version, Version>>,
+    cache: Mutex<LruCache<Version, Arc<Segment>>>,
+}
+
+impl LedgerArchive {
+    pub fn open<P: AsRef<Path>>(db_root_path: P) -> Result<Self> {
+        let dir = db_root_path.as_ref().join(LEDGER_ARCHIVE_DIR_NAME);
+        fs::create_dir_all(&dir)?;
+
+        let mut segments = BTreeMap::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            match path.extension().and_then(OsStr::to_str) {
+                Some(SEGMENT_FILE_EXTENSION) => {
+                    let (begin, end) = parse_segment_file_name(&path)?;
+                    segments.insert(begin, end);
+                }
+                // Left behind by an interrupted write, the batch will be archived again.
+                Some(TEMP_FILE_EXTENSION) => fs::remove_file(&path)?,
+                _ => (),
+            }
+        }
+        let mut prev_end = 0;
+        for (begin, end) in &segments {
+            ensure!(
+                prev_end <= *begin,
+                "Overlapping ledger archive segments ending at {} and starting at {}.",
+                prev_end,
+                begin,
+            );
+            prev_end = *end;
+        }
+        info!(
+            dir = dir,
+            num_segments = segments.len(),
+            next_version = prev_end,
+            "Opened ledger archive."
+        );
+
+        Ok(Self {
+            dir,
+            segments: RwLock::new(segments),
+            cache: Mutex::new(LruCache::new(NUM_CACHED_SEGMENTS)),
+        })
+    }
+
+    /// Returns the version following the last archived transaction, if anything is archived.
+    pub fn next_version(&self) -> Option<Version> {
+        self.segments.read().values().next_back().copied()
+    }
+
+    /// Archives the transactions in [begin, end) into a new segment. Must be called before they
+    /// are pruned from the ledger db.
+    pub fn archive(
+        &self,
+        begin: Version,
+        end: Version,
+        transaction_store: &TransactionStore,
+        ledger_store: &LedgerStore,
+        event_store: &EventStore,
+    ) -> Result<()> {
+        // If the node crashed after archiving a batch but before pruning it, the pruner will hand
+        // it over again.
+        let begin = std::cmp::max(begin, self.next_version().unwrap_or(0));
+        if begin >= end {
+            return Ok(());
+        }
+
+        let num_txns = (end - begin) as usize;
+        let txns = transaction_store
+            .get_transaction_iter(begin, num_txns)?
+            .collect::<Result<Vec<_>>>()?;
+        let txn_infos = ledger_store
+            .get_transaction_info_iter(begin, num_txns)?
+            .collect::<Result<Vec<_>>>()?;
+        let write_sets = transaction_store.get_write_sets(begin, end)?;
+        let events = event_store
+            .get_events_by_version_iter(begin, num_txns)?
+            .collect::<Result<Vec<_>>>()?;
+        let transactions = izip!(txns, txn_infos, write_sets, events)
+            .map(
+                |(transaction, info, write_set, events)| ArchivedTransaction {
+                    transaction,
+                    info,
+                    write_set,
+                    events,
+                },
+            )
+            .collect::<Vec<_>>();
+        ensure!(
+            transactions.len() == num_txns,
+            "Expected {} transactions to archive in [{}, {}), got {}.",
+            num_txns,
+            begin,
+            end,
+            transactions.len(),
+        );
+        let mut accumulator_nodes = transaction_store
+            .get_transaction_accumulator_positions_to_prune(begin, end)
+            .into_iter()
+            .map(|position| Ok((position.to_inorder_index(), ledger_store.get(position)?)))
+            .collect::<Result<Vec<_>>>()?;
+        accumulator_nodes.sort_unstable_by_key(|(index, _hash)| *index);
+        let segment = Segment {
+            transactions,
+            accumulator_nodes,
+        };
+
+        let compressed = compress(
+            bcs::to_bytes(&segment)?,
+            CompressionClient::DbArchive,
+            MAX_SEGMENT_BYTES,
+        )?;
+        let path = self.segment_path(begin, end);
+        let temp_path = path.with_extension(TEMP_FILE_EXTENSION);
+        let mut file = File::create(&temp_path)?;
+        file.write_all(&compressed)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, &path)?;
+        File::open(&self.dir)?.sync_all()?;
+
+        self.segments.write().insert(begin, end);
+        Ok(())
+    }
+
+    /// Returns the first version of the ledger history readable from the ledger db, whose oldest
+    /// version is `min_readable_version`, together with the archive. Archived transactions
+    /// before a gap in the archive aren't part of that history.
+    pub fn get_first_readable_version(&self, min_readable_version: Version) -> Version {
+        let mut first_version = min_readable_version;
+        for (begin, end) in self.segments.read().range(..min_readable_version).rev() {
+            if *end < first_version {
+                break;
+            }
+            first_version = *begin;
+        }
+        first_version
+    }
+
+    /// Applies `read` to the archived transaction at `version`, or returns `None` if it's not
+    /// archived.
+    pub fn read_transaction<T>(
+        &self,
+        version: Version,
+        read: impl FnOnce(&ArchivedTransaction) -> T,
+    ) -> Result<Option<T>> {
+        let (begin, end) = match self.get_segment_range(version) {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+        let segment = self.load_segment(begin, end)?;
+        Ok(Some(read(
+            &segment.transactions[(version - begin) as usize],
+        )))
+    }
+
+    /// Returns the hash of the archived transaction accumulator node at `position`, or `None`
+    /// if it's not archived.
+    pub fn get_accumulator_node(&self, position: Position) -> Result<Option<HashValue>> {
+        // A node is pruned together with the last leaf under its parent (see
+        // `TransactionStore::get_transaction_accumulator_positions_to_prune`), and the leaf of
+        // version `v` is at in-order index `2 * v`.
+        let version = position.parent().right_most_child().to_inorder_index() / 2;
+        let (begin, end) = match self.get_segment_range(version) {
+            Some(range) => range,
+            None => return Ok(None),
+        };
+        let segment = self.load_segment(begin, end)?;
+        let index = position.to_inorder_index();
+        Ok(segment
+            .accumulator_nodes
+            .binary_search_by_key(&index, |(index, _hash)| *index)
+            .ok()
+            .map(|i| segment.accumulator_nodes[i].1))
+    }
+
+    /// Returns the range of the segment containing `version`.
+    fn get_segment_range(&self, version: Version) -> Option<(Version, Version)> {
+        self.segments
+            .read()
+            .range(..=version)
+            .next_back()
+            .filter(|(_begin, end)| version < **end)
+            .map(|(begin, end)| (*begin, *end))
+    }
+
+    fn load_segment(&self, begin: Version, end: Version) -> Result<Arc<Segment>> {
+        if let Some(segment) = self.cache.lock().get(&begin) {
+            return Ok(Arc::clone(segment));
+        }
+
+        let compressed = fs::read(self.segment_path(begin, end))?;
+        let raw = decompress(&compressed, CompressionClient::DbArchive, MAX_SEGMENT_BYTES)?;
+        let segment: Segment = bcs::from_bytes(&raw)?;
+        ensure!(
+            segment.transactions.len() as u64 == end - begin,
+            "Ledger archive segment [{}, {}) holds {} transactions.",
+            begin,
+            end,
+            segment.transactions.len(),
+        );
+
+        let segment = Arc::new(segment);
+        self.cache.lock().put(begin, Arc::clone(&segment));
+        Ok(segment)
+    }
+
+    fn segment_path(&self, begin: Version, end: Version) -> PathBuf {
+        self.dir
+            .join(format!("{:020}-{:020}", begin, end))
+            .with_extension(SEGMENT_FILE_EXTENSION)
+    }
+}
+
+fn parse_segment_file_name(path: &Path) -> Result<(Version, Version)> {
+    let parse = || {
+        let (begin, end) = path.file_stem()?.to_str()?.split_once('-')?;
+        let (begin, end) = (begin.parse().ok()?, end.parse().ok()?);
+        if begin < end {
+            Some((begin, end))
+        } else {
+            None
+        }
+    };
+    parse().ok_or_else(|| format_err!("Invalid ledger archive segment file {:?}.", path))
+}
+
+#[cfg(test)]
+mod test;
+
+/// Applies `read` to the archived transaction at `version`, if there's an archive and the
+/// transaction is in it.
+pub(crate) fn read_archived_transaction<T>(
+    ledger_archive: &Option<Arc<LedgerArchive>>,
+    version: Version,
+    read: impl FnOnce(&ArchivedTransaction) -> T,
+) -> Result<Option<T>> {
+    match ledger_archive {
+        Some(ledger_archive) => ledger_archive.read_transaction(version, read),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    pruner::pruner_manager::PrunerManager,
+    test_helper::{arb_blocks_to_commit, update_in_memory_state},
+    AptosDB,
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{TransactionToCommit, Version},
+};
+use proptest::prelude::*;
+use storage_interface::{DbReader, Order};
+
+fn verify_archived_reads(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+    step_size: usize,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test_with_ledger_archive(&tmp_dir);
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+    let ledger_version = next_ver - 1;
+    let txns_to_commit = input
+        .iter()
+        .flat_map(|(txns_to_commit, _)| txns_to_commit.iter())
+        .collect::<Vec<_>>();
+
+    // Everything but the latest version gets pruned, so the requested ranges span both the
+    // archive and the ledger db.
+    let read_all = |db: &AptosDB| {
+        let txns = db
+            .get_transactions(0, ledger_version + 1, ledger_version, true)
+            .unwrap();
+        let outputs = db
+            .get_transaction_outputs(0, ledger_version + 1, ledger_version)
+            .unwrap();
+        let txns_with_proof = (0..=ledger_version)
+            .map(|version| {
+                (
+                    db.get_transaction_by_version(version, ledger_version, true)
+                        .unwrap(),
+                    db.get_accumulator_root_hash(version).unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let user_txns = txns_to_commit
+            .iter()
+            .filter_map(|txn_to_commit| {
+                let txn = txn_to_commit.transaction().as_signed_user_txn().ok()?;
+                Some((
+                    db.get_transaction_by_hash(
+                        txn_to_commit.transaction().hash(),
+                        ledger_version,
+                        true,
+                    )
+                    .unwrap(),
+                    db.get_account_transactions(
+                        txn.sender(),
+                        txn.sequence_number(),
+                        1,
+                        true, /* include_events */
+                        ledger_version,
+                    )
+                    .unwrap(),
+                ))
+            })
+            .collect::<Vec<_>>();
+        let events = txns_to_commit
+            .iter()
+            .flat_map(|txn_to_commit| txn_to_commit.events().iter())
+            .map(|event| {
+                db.get_events_by_event_key(
+                    event.key(),
+                    event.sequence_number(),
+                    Order::Ascending,
+                    1,
+                    ledger_version,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        (txns, outputs, txns_with_proof, user_txns, events)
+    };
+    let expected = read_all(&db);
+
+    // Prune in steps, each of which ends up in its own segment.
+    for target_version in (step_size as Version..ledger_version)
+        .step_by(step_size)
+        .chain(std::iter::once(ledger_version))
+    {
+        db.prune_ledger_for_test(target_version).unwrap();
+    }
+    assert_eq!(db.ledger_pruner.get_min_readable_version(), ledger_version);
+
+    let check = |db: &AptosDB| {
+        assert_eq!(db.get_first_txn_version().unwrap(), Some(0));
+        assert_eq!(db.get_first_viable_txn_version().unwrap(), 0);
+        assert_eq!(read_all(db), expected);
+    };
+    check(&db);
+
+    // Segments survive a restart.
+    drop(db);
+    let db = AptosDB::new_for_test_with_ledger_archive(&tmp_dir);
+    check(&db);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_archived_reads(input in arb_blocks_to_commit(), step_size in 1usize..10) {
+        verify_archived_reads(input, step_size);
+    }
+}
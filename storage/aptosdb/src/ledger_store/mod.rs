@@ -7,6 +7,7 @@
 use crate::utils::iterators::{EpochEndingLedgerInfoIter, ExpectContinuousVersions};
 use crate::{
     errors::AptosDbError,
+    ledger_archive::{read_archived_transaction, LedgerArchive},
     schema::{
         epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema,
         transaction_accumulator::TransactionAccumulatorSchema,
@@ -37,6 +38,10 @@ use std::{ops::Deref, sync::Arc};
 pub struct LedgerStore {
     db: Arc<DB>,
 
+    /// Where the transaction infos and transaction accumulator nodes pruned from the db are read
+    /// from, if set.
+    ledger_archive: Option<Arc<LedgerArchive>>,
+
     /// We almost always need the latest ledger info and signatures to serve read requests, so we
     /// cache it in memory in order to avoid reading DB and deserializing the object frequently. It
     /// should be updated every time new ledger info and signatures are persisted.
@@ -45,6 +50,13 @@ pub struct LedgerStore {
 
 impl LedgerStore {
     pub fn new(db: Arc<DB>) -> Self {
+        Self::new_with_ledger_archive(db, None)
+    }
+
+    pub(crate) fn new_with_ledger_archive(
+        db: Arc<DB>,
+        ledger_archive: Option<Arc<LedgerArchive>>,
+    ) -> Self {
         // Upon restart, read the latest ledger info and signatures and cache them in memory.
         let ledger_info = {
             let mut iter = db
@@ -59,6 +71,7 @@ impl LedgerStore {
 
         Self {
             db,
+            ledger_archive,
             latest_ledger_info: ArcSwap::from(Arc::new(ledger_info)),
         }
     }
@@ -162,8 +175,10 @@ impl LedgerStore {
 
     /// Get transaction info given `version`
     pub fn get_transaction_info(&self, version: Version) -> Result<TransactionInfo> {
-        self.db
-            .get::<TransactionInfoSchema>(&version)?
+        if let Some(txn_info) = self.db.get::<TransactionInfoSchema>(&version)? {
+            return Ok(txn_info);
+        }
+        read_archived_transaction(&self.ledger_archive, version, |txn| txn.info.clone())?
             .ok_or_else(|| format_err!("No TransactionInfo at version {}", version))
     }
 
@@ -318,9 +333,14 @@ pub(crate) type Accumulator = MerkleAccumulator<LedgerStore, TransactionAccumula
 
 impl HashReader for LedgerStore {
     fn get(&self, position: Position) -> Result<HashValue> {
-        self.db
-            .get::<TransactionAccumulatorSchema>(&position)?
-            .ok_or_else(|| format_err!("{} does not exist.", position))
+        if let Some(hash) = self.db.get::<TransactionAccumulatorSchema>(&position)? {
+            return Ok(hash);
+        }
+        match &self.ledger_archive {
+            Some(ledger_archive) => ledger_archive.get_accumulator_node(position)?,
+            None => None,
+        }
+        .ok_or_else(|| format_err!("{} does not exist.", position))
     }
 }
 
@@ -14,6 +14,8 @@
 pub mod test_helper;
 
 pub mod backup;
+pub mod db_surgeon;
+pub mod db_verifier;
 pub mod errors;
 pub mod metrics;
 pub mod schema;
@@ -21,6 +23,7 @@ pub mod state_restore;
 
 mod db_options;
 mod event_store;
+mod ledger_archive;
 mod ledger_store;
 mod lru_node_cache;
 mod pruner;
@@ -41,8 +44,10 @@ use crate::{
         gen_ledger_cfds, gen_state_merkle_cfds, ledger_db_column_families,
         state_merkle_db_column_families,
     },
+    db_verifier::DbVerifier,
     errors::AptosDbError,
     event_store::EventStore,
+    ledger_archive::LedgerArchive,
     ledger_store::LedgerStore,
     metrics::{
         API_LATENCY_SECONDS, COMMITTED_TXNS, LATEST_TXN_VERSION, LEDGER_VERSION, NEXT_BLOCK_EPOCH,
@@ -93,8 +98,12 @@ use aptos_types::{
     },
 };
 use aptos_vm::data_cache::AsMoveResolver;
-use aptosdb_indexer::Indexer;
-use itertools::zip_eq;
+use aptosdb_indexer::{Indexer, TransactionToIndex};
+use itertools::{izip, zip_eq};
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use schemadb::{SchemaBatch, DB};
@@ -122,6 +131,11 @@ use storage_interface::{
 pub const LEDGER_DB_NAME: &str = "ledger_db";
 pub const STATE_MERKLE_DB_NAME: &str = "state_merkle_db";
 
+/// Max number of state keys an incremental state snapshot can change. Building and applying a
+/// state snapshot delta holds all changed keys and values in memory, and they must all be in the
+/// tree update of a single version, so larger deltas are refused in favor of a full snapshot.
+pub const MAX_STATE_SNAPSHOT_DELTA_CHANGES: usize = 1_000_000;
+
 // TODO: Either implement an iteration API to allow a very old client to loop through a long history
 // or guarantee that there is always a recent enough waypoint and client knows to boot from there.
 const MAX_NUM_EPOCH_ENDING_LEDGER_INFO: usize = 100;
@@ -252,6 +266,7 @@ pub struct AptosDB {
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
     indexer: Option<Indexer>,
+    ledger_archive: Option<Arc<LedgerArchive>>,
 }
 
 impl AptosDB {
@@ -262,6 +277,7 @@ impl AptosDB {
         buffered_state_target_items: usize,
         max_nodes_per_lru_cache_shard: usize,
         hack_for_tests: bool,
+        ledger_archive: Option<Arc<LedgerArchive>>,
     ) -> Self {
         let arc_ledger_rocksdb = Arc::new(ledger_rocksdb);
         let arc_state_merkle_rocksdb = Arc::new(state_merkle_rocksdb);
@@ -286,15 +302,25 @@ impl AptosDB {
             Arc::clone(&arc_ledger_rocksdb),
             Arc::clone(&state_store),
             pruner_config.ledger_pruner_config,
+            ledger_archive.clone(),
         );
 
         AptosDB {
             ledger_db: Arc::clone(&arc_ledger_rocksdb),
             state_merkle_db: Arc::clone(&arc_state_merkle_rocksdb),
-            event_store: Arc::new(EventStore::new(Arc::clone(&arc_ledger_rocksdb))),
-            ledger_store: Arc::new(LedgerStore::new(Arc::clone(&arc_ledger_rocksdb))),
+            event_store: Arc::new(EventStore::new_with_ledger_archive(
+                Arc::clone(&arc_ledger_rocksdb),
+                ledger_archive.clone(),
+            )),
+            ledger_store: Arc::new(LedgerStore::new_with_ledger_archive(
+                Arc::clone(&arc_ledger_rocksdb),
+                ledger_archive.clone(),
+            )),
             state_store,
-            transaction_store: Arc::new(TransactionStore::new(Arc::clone(&arc_ledger_rocksdb))),
+            transaction_store: Arc::new(TransactionStore::new_with_ledger_archive(
+                Arc::clone(&arc_ledger_rocksdb),
+                ledger_archive.clone(),
+            )),
             ledger_pruner,
             _rocksdb_property_reporter: RocksdbPropertyReporter::new(
                 Arc::clone(&arc_ledger_rocksdb),
@@ -302,6 +328,7 @@ impl AptosDB {
             ),
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
+            ledger_archive,
         }
     }
 
@@ -355,6 +382,12 @@ impl AptosDB {
             )
         };
 
+        let ledger_archive = if pruner_config.ledger_pruner_config.enable_archive {
+            Some(Arc::new(LedgerArchive::open(&db_root_path)?))
+        } else {
+            None
+        };
+
         let mut myself = Self::new_with_dbs(
             ledger_db,
             state_merkle_db,
@@ -362,6 +395,7 @@ impl AptosDB {
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             readonly,
+            ledger_archive,
         );
 
         if !readonly && enable_indexer {
@@ -405,11 +439,31 @@ impl AptosDB {
             while next_version < ledger_next_version {
                 info!(next_version = next_version, "AptosDB Indexer catching up. ",);
                 let end_version = std::cmp::min(ledger_next_version, next_version + BATCH_SIZE);
+                let num_txns = (end_version - next_version) as usize;
+                let txns = self
+                    .transaction_store
+                    .get_transaction_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
+                let txn_infos = self
+                    .ledger_store
+                    .get_transaction_info_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
                 let write_sets = self
                     .transaction_store
                     .get_write_sets(next_version, end_version)?;
-                let write_sets_ref: Vec<_> = write_sets.iter().collect();
-                indexer.index_with_annotator(&annotator, next_version, &write_sets_ref)?;
+                let events = self
+                    .event_store
+                    .get_events_by_version_iter(next_version, num_txns)?
+                    .collect::<Result<Vec<_>>>()?;
+                let txns_to_index: Vec<_> = izip!(&txns, &txn_infos, &write_sets, &events)
+                    .map(|(txn, txn_info, write_set, events)| TransactionToIndex {
+                        transaction: txn,
+                        status: txn_info.status(),
+                        write_set,
+                        events,
+                    })
+                    .collect();
+                indexer.index_with_annotator(&annotator, next_version, &txns_to_index)?;
 
                 next_version = end_version;
             }
@@ -455,6 +509,7 @@ impl AptosDB {
             BUFFERED_STATE_TARGET_ITEMS,
             0,
             true,
+            None,
         ))
     }
 
@@ -535,6 +590,40 @@ impl AptosDB {
         )
     }
 
+    /// This opens db in non-readonly mode, with the ledger pruner archiving everything it prunes.
+    /// The pruner only runs when triggered by `prune_ledger_for_test`.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn new_for_test_with_ledger_archive<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
+        Self::open(
+            db_root_path,
+            false, /* readonly */
+            PrunerConfig {
+                ledger_pruner_config: aptos_config::config::LedgerPrunerConfig {
+                    enable: true,
+                    prune_window: 0,
+                    // Large enough for the pruner not to kick in by itself.
+                    batch_size: 1_000_000,
+                    user_pruning_window_offset: 0,
+                    enable_archive: true,
+                },
+                ..NO_OP_STORAGE_PRUNER_CONFIG
+            },
+            RocksdbConfigs::default(),
+            false, /* enable_indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .expect("Unable to open AptosDB")
+    }
+
+    /// Prunes the ledger history up to (excluding) `target_version` and waits for the pruner.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn prune_ledger_for_test(&self, target_version: Version) -> Result<()> {
+        self.ledger_pruner
+            .set_pruner_target_db_version(target_version);
+        self.ledger_pruner.wait_for_pruner()
+    }
+
     /// This gets the current buffered_state in StateStore.
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn buffered_state(&self) -> &Mutex<BufferedState> {
@@ -638,6 +727,16 @@ impl AptosDB {
         })
     }
 
+    /// Gets an instance of `DbVerifier` for verifying the DB in place.
+    pub fn get_db_verifier(&self) -> DbVerifier {
+        DbVerifier::new(
+            Arc::clone(&self.ledger_db),
+            Arc::clone(&self.ledger_store),
+            Arc::clone(&self.event_store),
+            Arc::clone(&self.state_store),
+        )
+    }
+
     // ================================== Backup APIs ===================================
 
     /// Gets an instance of `BackupHandler` for data backup purpose.
@@ -814,7 +913,31 @@ impl AptosDB {
         }
     }
 
+    /// Returns the first version of the ledger history (transactions, events, ...) that can be
+    /// read, from the ledger db or from the ledger archive.
+    fn get_first_readable_ledger_version(&self) -> Version {
+        let min_readable_version = self.ledger_pruner.get_min_readable_version();
+        match &self.ledger_archive {
+            Some(ledger_archive) => ledger_archive.get_first_readable_version(min_readable_version),
+            None => min_readable_version,
+        }
+    }
+
     fn error_if_ledger_pruned(&self, data_type: &str, version: Version) -> Result<()> {
+        let min_readable_version = self.get_first_readable_ledger_version();
+        ensure!(
+            version >= min_readable_version,
+            "{} at version {} is pruned, min available version is {}.",
+            data_type,
+            version,
+            min_readable_version
+        );
+        Ok(())
+    }
+
+    /// The state values (and the state storage usage) pruned by the ledger pruner aren't
+    /// archived, unlike the rest of the ledger history.
+    fn error_if_state_value_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.ledger_pruner.get_min_readable_version();
         ensure!(
             version >= min_readable_version,
@@ -875,7 +998,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + '_>> {
         gauged_api("get_prefixed_state_value_iterator", || {
-            self.error_if_ledger_pruned("State", version)?;
+            self.error_if_state_value_pruned("State", version)?;
 
             Ok(Box::new(
                 self.state_store
@@ -1058,13 +1181,17 @@ impl DbReader for AptosDB {
     /// Get the first version that txn starts existent.
     fn get_first_txn_version(&self) -> Result<Option<Version>> {
         gauged_api("get_first_txn_version", || {
-            Ok(Some(self.ledger_pruner.get_min_readable_version()))
+            Ok(Some(self.get_first_readable_ledger_version()))
         })
     }
 
     /// Get the first version that will likely not be pruned soon
     fn get_first_viable_txn_version(&self) -> Result<Version> {
         gauged_api("get_first_viable_txn_version", || {
+            // Archived transactions are never deleted.
+            if self.ledger_archive.is_some() {
+                return Ok(self.get_first_readable_ledger_version());
+            }
             Ok(self.ledger_pruner.get_min_viable_version())
         })
     }
@@ -1072,7 +1199,7 @@ impl DbReader for AptosDB {
     /// Get the first version that write set starts existent.
     fn get_first_write_set_version(&self) -> Result<Option<Version>> {
         gauged_api("get_first_write_set_version", || {
-            Ok(Some(self.ledger_pruner.get_min_readable_version()))
+            Ok(Some(self.get_first_readable_ledger_version()))
         })
     }
 
@@ -1146,6 +1273,69 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        gauged_api("get_events_by_type", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            self.error_if_ledger_pruned("Event", start_version)?;
+            let indexer = match &self.indexer {
+                Some(indexer) => indexer,
+                None => bail!("Indexer not enabled."),
+            };
+
+            indexer
+                .lookup_events_by_type(type_tag, start_version, start_index, limit, ledger_version)?
+                .into_iter()
+                .map(|(ver, idx)| {
+                    let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+                    ensure!(
+                        event.type_tag() == type_tag,
+                        "Index broken, expected type:{}, actual:{}",
+                        type_tag,
+                        event.type_tag()
+                    );
+                    Ok((idx, EventWithVersion::new(ver, event)))
+                })
+                .collect()
+        })
+    }
+
+    fn get_transaction_versions_by_function(
+        &self,
+        module_id: &ModuleId,
+        function: &IdentStr,
+        success: Option<bool>,
+        sender: Option<AccountAddress>,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        gauged_api("get_transaction_versions_by_function", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            self.error_if_ledger_pruned("Transaction", start_version)?;
+            let indexer = match &self.indexer {
+                Some(indexer) => indexer,
+                None => bail!("Indexer not enabled."),
+            };
+
+            indexer.lookup_transactions_by_function(
+                module_id,
+                function,
+                success,
+                sender,
+                start_version,
+                limit,
+                ledger_version,
+            )
+        })
+    }
+
     /// Gets ledger info at specified version and ensures it's an epoch ending.
     fn get_epoch_ending_ledger_info(&self, version: u64) -> Result<LedgerInfoWithSignatures> {
         gauged_api("get_epoch_ending_ledger_info", || {
@@ -1193,7 +1383,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<Option<StateValue>> {
         gauged_api("get_state_value_by_version", || {
-            self.error_if_ledger_pruned("State", version)?;
+            self.error_if_state_value_pruned("State", version)?;
 
             self.state_store
                 .get_state_value_by_version(state_store_key, version)
@@ -1465,7 +1655,7 @@ impl DbReader for AptosDB {
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         gauged_api("get_state_storage_usage", || {
             if let Some(v) = version {
-                self.error_if_ledger_pruned("state storage usage", v)?;
+                self.error_if_state_value_pruned("state storage usage", v)?;
             }
             self.state_store.get_usage(version)
         })
@@ -1635,8 +1825,16 @@ impl DbWriter for AptosDB {
                 let _timer = OTHER_TIMERS_SECONDS
                     .with_label_values(&["indexer_index"])
                     .start_timer();
-                let write_sets: Vec<_> = txns_to_commit.iter().map(|txn| txn.write_set()).collect();
-                indexer.index(self.state_store.clone(), first_version, &write_sets)?;
+                let txns_to_index: Vec<_> = txns_to_commit
+                    .iter()
+                    .map(|txn| TransactionToIndex {
+                        transaction: txn.transaction(),
+                        status: txn.transaction_info().status(),
+                        write_set: txn.write_set(),
+                        events: txn.events(),
+                    })
+                    .collect();
+                indexer.index(self.state_store.clone(), first_version, &txns_to_index)?;
             }
 
             // Once everything is successfully persisted, update the latest in-memory ledger info.
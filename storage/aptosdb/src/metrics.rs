@@ -204,3 +204,19 @@ pub(crate) static BACKUP_STATE_SNAPSHOT_LEAF_IDX: Lazy<IntGauge> = Lazy::new(||
     )
     .unwrap()
 });
+
+pub(crate) static VERIFIER_VERSION: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_verifier_version",
+        "Last version checked by the background DB verifier."
+    )
+    .unwrap()
+});
+
+pub(crate) static VERIFIER_INCONSISTENCIES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_storage_verifier_inconsistencies",
+        "Number of inconsistencies found by the background DB verifier."
+    )
+    .unwrap()
+});
@@ -7,6 +7,9 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub struct EventStorePruner {
     event_store: Arc<EventStore>,
+    /// Whether to keep the indices of events by key and by version, which keep pointing to the
+    /// events moved into the ledger archive.
+    keep_indices: bool,
 }
 
 impl DBSubPruner for EventStorePruner {
@@ -16,14 +19,25 @@ impl DBSubPruner for EventStorePruner {
         min_readable_version: u64,
         target_version: u64,
     ) -> anyhow::Result<()> {
-        self.event_store
-            .prune_events(min_readable_version, target_version, db_batch)?;
+        if self.keep_indices {
+            self.event_store.prune_archived_events(
+                min_readable_version,
+                target_version,
+                db_batch,
+            )?;
+        } else {
+            self.event_store
+                .prune_events(min_readable_version, target_version, db_batch)?;
+        }
         Ok(())
     }
 }
 
 impl EventStorePruner {
-    pub(in crate::pruner) fn new(event_store: Arc<EventStore>) -> Self {
-        EventStorePruner { event_store }
+    pub(in crate::pruner) fn new(event_store: Arc<EventStore>, keep_indices: bool) -> Self {
+        EventStorePruner {
+            event_store,
+            keep_indices,
+        }
     }
 }
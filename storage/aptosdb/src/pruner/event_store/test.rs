@@ -75,7 +75,9 @@ fn verify_event_store_pruner(events: Vec<Vec<ContractEvent>>) {
             prune_window: 0,
             batch_size: 1,
             user_pruning_window_offset: 0,
+            enable_archive: false,
         },
+        None,
     );
     // start pruning events batches of size 2 and verify transactions have been pruned from DB
     for i in (0..=num_versions).step_by(2) {
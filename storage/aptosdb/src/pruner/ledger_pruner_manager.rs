@@ -6,6 +6,7 @@ use crate::metrics::{PRUNER_BATCH_SIZE, PRUNER_WINDOW};
 use aptos_config::config::LedgerPrunerConfig;
 use aptos_infallible::Mutex;
 
+use crate::ledger_archive::LedgerArchive;
 use crate::pruner::db_pruner::DBPruner;
 use crate::pruner::ledger_pruner_worker::LedgerPrunerWorker;
 use crate::pruner::ledger_store::ledger_store_pruner::LedgerPruner;
@@ -104,8 +105,10 @@ impl LedgerPrunerManager {
         ledger_rocksdb: Arc<DB>,
         state_store: Arc<StateStore>,
         ledger_pruner_config: LedgerPrunerConfig,
+        ledger_archive: Option<Arc<LedgerArchive>>,
     ) -> Self {
-        let ledger_pruner = pruner_utils::create_ledger_pruner(ledger_rocksdb, state_store);
+        let ledger_pruner =
+            pruner_utils::create_ledger_pruner(ledger_rocksdb, state_store, ledger_archive);
 
         if ledger_pruner_config.enable {
             PRUNER_WINDOW
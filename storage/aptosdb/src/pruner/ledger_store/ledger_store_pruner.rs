@@ -3,6 +3,7 @@
 
 use crate::{
     db_metadata::DbMetadataSchema,
+    ledger_archive::LedgerArchive,
     metrics::PRUNER_LEAST_READABLE_VERSION,
     pruner::{
         db_pruner::DBPruner,
@@ -18,7 +19,7 @@ use crate::{
         db_metadata::{DbMetadataKey, DbMetadataValue},
         transaction::TransactionSchema,
     },
-    EventStore, StateStore, TransactionStore,
+    EventStore, LedgerStore, StateStore, TransactionStore,
 };
 use aptos_logger::warn;
 use aptos_types::transaction::{AtomicVersion, Version};
@@ -38,6 +39,11 @@ pub(crate) struct LedgerPruner {
     state_value_pruner: Arc<dyn DBSubPruner + Send + Sync>,
     event_store_pruner: Arc<dyn DBSubPruner + Send + Sync>,
     write_set_pruner: Arc<dyn DBSubPruner + Send + Sync>,
+    /// If set, everything about to be pruned is moved into the archive first.
+    ledger_archive: Option<Arc<LedgerArchive>>,
+    transaction_store: Arc<TransactionStore>,
+    ledger_store: LedgerStore,
+    event_store: Arc<EventStore>,
 }
 
 impl DBPruner for LedgerPruner {
@@ -131,17 +137,25 @@ impl LedgerPruner {
         transaction_store: Arc<TransactionStore>,
         event_store: Arc<EventStore>,
         state_store: Arc<StateStore>,
+        ledger_archive: Option<Arc<LedgerArchive>>,
     ) -> Self {
+        // The indices are kept for the reads of the archived data.
+        let keep_indices = ledger_archive.is_some();
         let pruner = LedgerPruner {
-            db,
+            db: Arc::clone(&db),
             target_version: AtomicVersion::new(0),
             min_readable_version: AtomicVersion::new(0),
             transaction_store_pruner: Arc::new(TransactionStorePruner::new(
                 transaction_store.clone(),
+                keep_indices,
             )),
             state_value_pruner: Arc::new(StateValuePruner::new(state_store)),
-            event_store_pruner: Arc::new(EventStorePruner::new(event_store)),
-            write_set_pruner: Arc::new(WriteSetPruner::new(transaction_store)),
+            event_store_pruner: Arc::new(EventStorePruner::new(event_store.clone(), keep_indices)),
+            write_set_pruner: Arc::new(WriteSetPruner::new(transaction_store.clone())),
+            ledger_archive,
+            transaction_store,
+            ledger_store: LedgerStore::new(db),
+            event_store,
         };
         pruner.initialize();
         pruner
@@ -156,7 +170,7 @@ impl LedgerPruner {
         let target_version = 1; // The genesis version is 0. Delete [0,1) (exclusive)
         let max_version = 1; // We should only be pruning a single version
 
-        let ledger_pruner = pruner_utils::create_ledger_pruner(ledger_db, state_store);
+        let ledger_pruner = pruner_utils::create_ledger_pruner(ledger_db, state_store, None);
         ledger_pruner.set_target_version(target_version);
         ledger_pruner.prune_inner(max_version, db_batch)?;
 
@@ -174,6 +188,15 @@ impl LedgerPruner {
         // more than max_version in one go.
         let current_target_version = self.get_current_batch_target(max_versions as Version);
 
+        if let Some(ledger_archive) = &self.ledger_archive {
+            ledger_archive.archive(
+                min_readable_version,
+                current_target_version,
+                &self.transaction_store,
+                &self.ledger_store,
+                &self.event_store,
+            )?;
+        }
         self.transaction_store_pruner.prune(
             db_batch,
             min_readable_version,
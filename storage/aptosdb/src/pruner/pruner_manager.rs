@@ -33,13 +33,13 @@ pub trait PrunerManager: Debug + Sync {
 
     /// (For tests only.) Notifies the worker thread and waits for it to finish its job by polling
     /// an internal counter.
-    #[cfg(test)]
+    #[cfg(any(test, feature = "fuzzing"))]
     fn wake_and_wait_pruner(&self, latest_version: Version) -> anyhow::Result<()> {
         self.maybe_set_pruner_target_db_version(latest_version);
         self.wait_for_pruner()
     }
 
-    #[cfg(test)]
+    #[cfg(any(test, feature = "fuzzing"))]
     fn wait_for_pruner(&self) -> anyhow::Result<()> {
         use std::{
             thread::sleep,
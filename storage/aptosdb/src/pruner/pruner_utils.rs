@@ -4,6 +4,7 @@
 //! This module provides common utilities for the DB pruner.
 
 use crate::{
+    ledger_archive::LedgerArchive,
     pruner::{ledger_store::ledger_store_pruner::LedgerPruner, state_store::StateMerklePruner},
     EventStore, StateStore, TransactionStore,
 };
@@ -28,11 +29,13 @@ where
 pub(crate) fn create_ledger_pruner(
     ledger_db: Arc<DB>,
     state_store: Arc<StateStore>,
+    ledger_archive: Option<Arc<LedgerArchive>>,
 ) -> Arc<LedgerPruner> {
     Arc::new(LedgerPruner::new(
         Arc::clone(&ledger_db),
         Arc::new(TransactionStore::new(Arc::clone(&ledger_db))),
         Arc::new(EventStore::new(Arc::clone(&ledger_db))),
         state_store,
+        ledger_archive,
     ))
 }
@@ -392,7 +392,9 @@ fn verify_state_value_pruner(inputs: Vec<Vec<(StateKey, Option<StateValue>)>>) {
             prune_window: 0,
             batch_size: 1,
             user_pruning_window_offset: 0,
+            enable_archive: false,
         },
+        None,
     );
     for batch in inputs {
         update_store(store, batch.clone().into_iter(), version);
@@ -60,7 +60,9 @@ fn verify_write_set_pruner(write_sets: Vec<WriteSet>) {
             prune_window: 0,
             batch_size: 1,
             user_pruning_window_offset: 0,
+            enable_archive: false,
         },
+        None,
     );
 
     // write sets
@@ -121,7 +123,9 @@ fn verify_txn_store_pruner(
                 prune_window: 0,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
+                enable_archive: false,
             },
+            None,
         );
         pruner
             .wake_and_wait_pruner(i as u64 /* latest_version */)
@@ -8,6 +8,9 @@ use std::sync::Arc;
 #[derive(Debug)]
 pub struct TransactionStorePruner {
     transaction_store: Arc<TransactionStore>,
+    /// Whether to keep the indices of transactions by hash and by account, which keep pointing
+    /// to the transactions moved into the ledger archive.
+    keep_indices: bool,
 }
 
 impl DBSubPruner for TransactionStorePruner {
@@ -20,12 +23,14 @@ impl DBSubPruner for TransactionStorePruner {
         // Current target version  might be less than the target version to ensure we don't prune
         // more than max_version in one go.
 
-        let candidate_transactions =
-            self.get_pruning_candidate_transactions(min_readable_version, target_version)?;
-        self.transaction_store
-            .prune_transaction_by_hash(&candidate_transactions, db_batch)?;
-        self.transaction_store
-            .prune_transaction_by_account(&candidate_transactions, db_batch)?;
+        if !self.keep_indices {
+            let candidate_transactions =
+                self.get_pruning_candidate_transactions(min_readable_version, target_version)?;
+            self.transaction_store
+                .prune_transaction_by_hash(&candidate_transactions, db_batch)?;
+            self.transaction_store
+                .prune_transaction_by_account(&candidate_transactions, db_batch)?;
+        }
         self.transaction_store.prune_transaction_schema(
             min_readable_version,
             target_version,
@@ -46,8 +51,14 @@ impl DBSubPruner for TransactionStorePruner {
 }
 
 impl TransactionStorePruner {
-    pub(in crate::pruner) fn new(transaction_store: Arc<TransactionStore>) -> Self {
-        TransactionStorePruner { transaction_store }
+    pub(in crate::pruner) fn new(
+        transaction_store: Arc<TransactionStore>,
+        keep_indices: bool,
+    ) -> Self {
+        TransactionStorePruner {
+            transaction_store,
+            keep_indices,
+        }
     }
 
     fn get_pruning_candidate_transactions(
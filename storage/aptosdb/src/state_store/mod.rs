@@ -15,7 +15,7 @@ use crate::{
     state_store::buffered_state::BufferedState,
     version_data::VersionDataSchema,
     AptosDbError, LedgerStore, StaleNodeIndexCrossEpochSchema, StaleNodeIndexSchema,
-    StatePrunerManager, TransactionStore, OTHER_TIMERS_SECONDS,
+    StatePrunerManager, TransactionStore, MAX_STATE_SNAPSHOT_DELTA_CHANGES, OTHER_TIMERS_SECONDS,
 };
 use anyhow::{ensure, format_err, Result};
 use aptos_crypto::{
@@ -45,7 +45,7 @@ use std::{
     sync::Arc,
 };
 use storage_interface::{
-    cached_state_view::CachedStateView, state_delta::StateDelta,
+    cached_state_view::CachedStateView, jmt_update_refs, jmt_updates, state_delta::StateDelta,
     sync_proof_fetcher::SyncProofFetcher, DbReader, StateSnapshotReceiver,
 };
 
@@ -63,6 +63,9 @@ const MAX_WRITE_SETS_AFTER_SNAPSHOT: LeafCount = buffered_state::TARGET_SNAPSHOT
     * (buffered_state::ASYNC_COMMIT_CHANNEL_BUFFER_SIZE + 2 + 1/*  Rendezvous channel */)
     * 2;
 
+// Number of state values written to the ledger DB at once when applying a state snapshot delta.
+const STATE_SNAPSHOT_DELTA_WRITE_BATCH_SIZE: usize = 10_000;
+
 static IO_POOL: Lazy<rayon::ThreadPool> = Lazy::new(|| {
     rayon::ThreadPoolBuilder::new()
         .num_threads(32)
@@ -601,6 +604,94 @@ impl StateStore {
         Ok(hash)
     }
 
+    /// Creates the state snapshot at `version` by applying the changes between two snapshots on
+    /// top of the snapshot at `base_version`, which must already exist in the DB. This is how
+    /// incremental state snapshot backups are restored: the changes are trusted only if the
+    /// resulting root hash matches `expected_root_hash`, otherwise nothing is written.
+    ///
+    /// The tree update of a version can't be split, so all changes are passed in at once and at
+    /// most `MAX_STATE_SNAPSHOT_DELTA_CHANGES` are accepted. The values are written in batches of
+    /// `STATE_SNAPSHOT_DELTA_WRITE_BATCH_SIZE`, and the tree goes in last, after all of them: the
+    /// state at `version` only exists once the tree does. Values left at `version` by a crash in
+    /// between are rewritten identically by applying the delta again, since everything written is
+    /// derived from the state at `base_version` and `changes`.
+    pub fn apply_state_snapshot_delta(
+        &self,
+        base_version: Version,
+        version: Version,
+        expected_root_hash: HashValue,
+        changes: &HashMap<StateKey, Option<StateValue>>,
+    ) -> Result<()> {
+        ensure!(
+            version > base_version,
+            "State snapshot delta must move forward, base version: {}, version: {}",
+            base_version,
+            version,
+        );
+        ensure!(
+            changes.len() <= MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+            "State snapshot delta changes {} keys, more than the max of {}.",
+            changes.len(),
+            MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+        );
+        let jmt_updates = jmt_updates(changes);
+        let (merkle_batch, root_hash) = self.state_merkle_db.merklize_value_set(
+            jmt_update_refs(&jmt_updates),
+            None, /* node_hashes */
+            version,
+            Some(base_version),
+            self.get_previous_epoch_ending(version)?.map(|(v, _e)| v),
+        )?;
+        ensure!(
+            root_hash == expected_root_hash,
+            "Root hash mismatch after applying state snapshot delta. expected: {}, actual: {}",
+            expected_root_hash,
+            root_hash,
+        );
+
+        let mut batch = SchemaBatch::new();
+        let mut num_pending = 0;
+        let mut usage = self.get_usage(Some(base_version))?;
+        for (key, value) in changes {
+            if let Some((old_version, old_value)) =
+                self.get_state_value_with_version_by_version(key, base_version)?
+            {
+                usage.remove_item(key.size() + old_value.size());
+                batch.put::<StaleStateValueIndexSchema>(
+                    &StaleStateValueIndex {
+                        stale_since_version: version,
+                        version: old_version,
+                        state_key: key.clone(),
+                    },
+                    &(),
+                )?;
+            }
+            match value {
+                Some(value) => usage.add_item(key.size() + value.size()),
+                None => batch.put::<StaleStateValueIndexSchema>(
+                    &StaleStateValueIndex {
+                        stale_since_version: version,
+                        version,
+                        state_key: key.clone(),
+                    },
+                    &(),
+                )?,
+            }
+            batch.put::<StateValueSchema>(&(key.clone(), version), value)?;
+            num_pending += 1;
+            if num_pending == STATE_SNAPSHOT_DELTA_WRITE_BATCH_SIZE {
+                self.ledger_db
+                    .write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+                num_pending = 0;
+            }
+        }
+        batch.put::<VersionDataSchema>(&version, &usage.into())?;
+        self.ledger_db.write_schemas(batch)?;
+
+        // The tree goes in last, so it never points to missing values.
+        self.state_merkle_db.write_schemas(merkle_batch)
+    }
+
     pub fn get_root_hash(&self, version: Version) -> Result<HashValue> {
         self.state_merkle_db.get_root_hash(version)
     }
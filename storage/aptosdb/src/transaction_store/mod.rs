@@ -8,6 +8,7 @@ use crate::utils::iterators::AccountTransactionVersionIter;
 use crate::utils::iterators::ExpectContinuousVersions;
 use crate::{
     errors::AptosDbError,
+    ledger_archive::{read_archived_transaction, LedgerArchive},
     schema::{
         transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
@@ -31,11 +32,20 @@ mod test;
 #[derive(Clone, Debug)]
 pub struct TransactionStore {
     db: Arc<DB>,
+    /// Where the transactions and write sets pruned from the db are read from, if set.
+    ledger_archive: Option<Arc<LedgerArchive>>,
 }
 
 impl TransactionStore {
     pub fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        Self::new_with_ledger_archive(db, None)
+    }
+
+    pub(crate) fn new_with_ledger_archive(
+        db: Arc<DB>,
+        ledger_archive: Option<Arc<LedgerArchive>>,
+    ) -> Self {
+        Self { db, ledger_archive }
     }
 
     /// Gets the version of a transaction by the sender `address` and `sequence_number`.
@@ -98,8 +108,10 @@ impl TransactionStore {
 
     /// Get signed transaction given `version`
     pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
-        self.db
-            .get::<TransactionSchema>(&version)?
+        if let Some(transaction) = self.db.get::<TransactionSchema>(&version)? {
+            return Ok(transaction);
+        }
+        read_archived_transaction(&self.ledger_archive, version, |txn| txn.transaction.clone())?
             .ok_or_else(|| AptosDbError::NotFound(format!("Txn {}", version)).into())
     }
 
@@ -146,9 +158,13 @@ impl TransactionStore {
 
     /// Get executed transaction vm output given `version`
     pub fn get_write_set(&self, version: Version) -> Result<WriteSet> {
-        self.db.get::<WriteSetSchema>(&version)?.ok_or_else(|| {
-            AptosDbError::NotFound(format!("WriteSet at version {}", version)).into()
-        })
+        if let Some(write_set) = self.db.get::<WriteSetSchema>(&version)? {
+            return Ok(write_set);
+        }
+        read_archived_transaction(&self.ledger_archive, version, |txn| txn.write_set.clone())?
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!("WriteSet at version {}", version)).into()
+            })
     }
 
     /// Get write sets in `[begin_version, end_version)` half-open range.
@@ -269,6 +285,21 @@ impl TransactionStore {
         end: Version,
         db_batch: &mut SchemaBatch,
     ) -> Result<()> {
+        for position in self.get_transaction_accumulator_positions_to_prune(begin, end) {
+            db_batch.delete::<TransactionAccumulatorSchema>(&position)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the positions of the transaction accumulator nodes pruned together with the
+    /// transactions in [begin, end), i.e. the children of the nodes whose last leaf is in the
+    /// range (see `prune_transaction_accumulator`).
+    pub fn get_transaction_accumulator_positions_to_prune(
+        &self,
+        begin: Version,
+        end: Version,
+    ) -> Vec<Position> {
+        let mut positions = vec![];
         for version_to_delete in begin..end {
             // The even version will be pruned in the iteration of version + 1.
             if version_to_delete % 2 == 0 {
@@ -284,12 +315,12 @@ impl TransactionStore {
 
             let mut current = first_ancestor_that_is_a_left_child;
             while !current.is_leaf() {
-                db_batch.delete::<TransactionAccumulatorSchema>(&current.left_child())?;
-                db_batch.delete::<TransactionAccumulatorSchema>(&current.right_child())?;
+                positions.push(current.left_child());
+                positions.push(current.right_child());
                 current = current.right_child();
             }
         }
-        Ok(())
+        positions
     }
 
     /// Finds the first ancestor that is a child of its parent.
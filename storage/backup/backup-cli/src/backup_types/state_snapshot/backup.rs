@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    backup_types::state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotChunk},
+    backup_types::state_snapshot::manifest::{
+        StateSnapshotBackup, StateSnapshotChunk, StateSnapshotDeltaBackup, StateSnapshotDeltaChunk,
+    },
     metadata::Metadata,
     storage::{BackupHandleRef, BackupStorage, FileHandle, ShellSafeName},
     utils::{
@@ -10,7 +12,7 @@ use crate::{
         should_cut_chunk, storage_ext::BackupStorageExt, GlobalBackupOpt,
     },
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
 use aptos_types::{
@@ -70,7 +72,7 @@ impl StateSnapshotBackupController {
     }
 
     async fn run_impl(mut self) -> Result<FileHandle> {
-        self.version = Some(self.get_version_for_epoch_ending(self.epoch).await?);
+        self.version = Some(get_version_for_epoch_ending(&self.client, self.epoch).await?);
         let backup_handle = self
             .storage
             .create_backup_with_random_suffix(&self.backup_name())
@@ -174,21 +176,6 @@ impl StateSnapshotBackupController {
         Ok(key.hash())
     }
 
-    async fn get_version_for_epoch_ending(&self, epoch: u64) -> Result<u64> {
-        let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(
-            self.client
-                .get_epoch_ending_ledger_infos(epoch, epoch + 1)
-                .await?
-                .read_record_bytes()
-                .await?
-                .ok_or_else(|| {
-                    anyhow!("Failed to get epoch ending ledger info for epoch {}", epoch)
-                })?
-                .as_ref(),
-        )?;
-        Ok(ledger_info.ledger_info().version())
-    }
-
     async fn write_chunk(
         &self,
         backup_handle: &BackupHandleRef,
@@ -273,3 +260,226 @@ impl StateSnapshotBackupController {
         Ok(manifest_handle)
     }
 }
+
+#[derive(Parser)]
+pub struct StateSnapshotDeltaBackupOpt {
+    #[clap(
+        long = "state-snapshot-epoch",
+        help = "Epoch at the end of which a state snapshot is to be taken."
+    )]
+    pub epoch: u64,
+    #[clap(
+        long = "base-state-snapshot-epoch",
+        help = "Epoch at the end of which the state snapshot this one is relative to was taken. \
+        Only the state values changed since then are backed up, so restoring it requires that \
+        snapshot as well."
+    )]
+    pub base_epoch: u64,
+}
+
+/// Backs up the state values changed between two epoch endings, instead of all state values like
+/// `StateSnapshotBackupController` does.
+pub struct StateSnapshotDeltaBackupController {
+    epoch: u64,
+    base_epoch: u64,
+    max_chunk_size: usize,
+    client: Arc<BackupServiceClient>,
+    storage: Arc<dyn BackupStorage>,
+}
+
+impl StateSnapshotDeltaBackupController {
+    pub fn new(
+        opt: StateSnapshotDeltaBackupOpt,
+        global_opt: GlobalBackupOpt,
+        client: Arc<BackupServiceClient>,
+        storage: Arc<dyn BackupStorage>,
+    ) -> Self {
+        Self {
+            epoch: opt.epoch,
+            base_epoch: opt.base_epoch,
+            max_chunk_size: global_opt.max_chunk_size,
+            client,
+            storage,
+        }
+    }
+
+    pub async fn run(self) -> Result<FileHandle> {
+        info!(
+            "Incremental state snapshot backup started, for epoch {} based on epoch {}.",
+            self.epoch, self.base_epoch,
+        );
+        let ret = self
+            .run_impl()
+            .await
+            .map_err(|e| anyhow!("Incremental state snapshot backup failed: {}", e))?;
+        info!(
+            "Incremental state snapshot backup succeeded. Manifest: {}",
+            ret
+        );
+        Ok(ret)
+    }
+
+    async fn run_impl(self) -> Result<FileHandle> {
+        ensure!(
+            self.base_epoch < self.epoch,
+            "Base epoch {} must be older than epoch {}.",
+            self.base_epoch,
+            self.epoch,
+        );
+        let base_version = get_version_for_epoch_ending(&self.client, self.base_epoch).await?;
+        let version = get_version_for_epoch_ending(&self.client, self.epoch).await?;
+        let backup_handle = self
+            .storage
+            .create_backup_with_random_suffix(&format!(
+                "state_delta_epoch_{}_ver_{}-{}",
+                self.epoch, base_version, version
+            ))
+            .await?;
+
+        let mut chunks = vec![];
+        let mut chunk_bytes = vec![];
+        let mut chunk_first_key = None;
+        let mut chunk_last_key = HashValue::zero();
+        let mut first_idx = 0;
+        let mut num_changes: usize = 0;
+
+        let mut changes_file = self
+            .client
+            .get_state_value_changes(base_version, version)
+            .await?;
+        while let Some(record_bytes) = changes_file.read_record_bytes().await? {
+            if should_cut_chunk(&chunk_bytes, &record_bytes, self.max_chunk_size) {
+                chunks.push(
+                    self.write_chunk(
+                        &backup_handle,
+                        &chunk_bytes,
+                        first_idx,
+                        chunk_first_key.take().expect("Chunk is not empty."),
+                        chunk_last_key,
+                    )
+                    .await?,
+                );
+                chunk_bytes = vec![];
+                first_idx = num_changes;
+            }
+
+            let key = Self::parse_key(&record_bytes)?;
+            chunk_first_key.get_or_insert(key);
+            chunk_last_key = key;
+            num_changes += 1;
+            chunk_bytes.extend(&(record_bytes.len() as u32).to_be_bytes());
+            chunk_bytes.extend(&record_bytes);
+        }
+        if let Some(first_key) = chunk_first_key {
+            chunks.push(
+                self.write_chunk(
+                    &backup_handle,
+                    &chunk_bytes,
+                    first_idx,
+                    first_key,
+                    chunk_last_key,
+                )
+                .await?,
+            );
+        }
+        info!(num_changes = num_changes, "State value changes written.");
+
+        self.write_manifest(&backup_handle, base_version, version, chunks)
+            .await
+    }
+
+    fn parse_key(record: &Bytes) -> Result<HashValue> {
+        let (key, _): (StateKey, Option<StateValue>) = bcs::from_bytes(record)?;
+        Ok(key.hash())
+    }
+
+    async fn write_chunk(
+        &self,
+        backup_handle: &BackupHandleRef,
+        chunk_bytes: &[u8],
+        first_idx: usize,
+        first_key: HashValue,
+        last_key: HashValue,
+    ) -> Result<StateSnapshotDeltaChunk> {
+        let (chunk_handle, mut chunk_file) = self
+            .storage
+            .create_for_write(
+                backup_handle,
+                &StateSnapshotBackupController::chunk_name(first_idx),
+            )
+            .await?;
+        chunk_file.write_all(chunk_bytes).await?;
+        chunk_file.shutdown().await?;
+
+        Ok(StateSnapshotDeltaChunk {
+            first_key,
+            last_key,
+            blobs: chunk_handle,
+        })
+    }
+
+    async fn write_manifest(
+        &self,
+        backup_handle: &BackupHandleRef,
+        base_version: Version,
+        version: Version,
+        chunks: Vec<StateSnapshotDeltaChunk>,
+    ) -> Result<FileHandle> {
+        let proof_bytes = self.client.get_state_root_proof(version).await?;
+        let (txn_info, _): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+            bcs::from_bytes(&proof_bytes)?;
+
+        let (proof_handle, mut proof_file) = self
+            .storage
+            .create_for_write(backup_handle, StateSnapshotBackupController::proof_name())
+            .await?;
+        proof_file.write_all(&proof_bytes).await?;
+        proof_file.shutdown().await?;
+
+        let manifest = StateSnapshotDeltaBackup {
+            base_version,
+            version,
+            epoch: self.epoch,
+            root_hash: txn_info.transaction_info().ensure_state_checkpoint_hash()?,
+            chunks,
+            proof: proof_handle,
+        };
+
+        let (manifest_handle, mut manifest_file) = self
+            .storage
+            .create_for_write(
+                backup_handle,
+                StateSnapshotBackupController::manifest_name(),
+            )
+            .await?;
+        manifest_file
+            .write_all(&serde_json::to_vec(&manifest)?)
+            .await?;
+        manifest_file.shutdown().await?;
+
+        let metadata = Metadata::new_state_snapshot_delta_backup(
+            base_version,
+            self.epoch,
+            version,
+            manifest_handle.clone(),
+        );
+        self.storage
+            .save_metadata_line(&metadata.name(), &metadata.to_text_line()?)
+            .await?;
+
+        Ok(manifest_handle)
+    }
+}
+
+async fn get_version_for_epoch_ending(client: &BackupServiceClient, epoch: u64) -> Result<u64> {
+    let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(
+        client
+            .get_epoch_ending_ledger_infos(epoch, epoch + 1)
+            .await?
+            .read_record_bytes()
+            .await?
+            .ok_or_else(|| anyhow!("Failed to get epoch ending ledger info for epoch {}", epoch))?
+            .as_ref(),
+    )?;
+    Ok(ledger_info.ledger_info().version())
+}
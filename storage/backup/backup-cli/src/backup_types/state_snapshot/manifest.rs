@@ -49,3 +49,39 @@ pub struct StateSnapshotBackup {
     /// limits the requirement on such `EpochStateBackup` to no older than the same epoch.
     pub proof: FileHandle,
 }
+
+/// A chunk of an incremental state snapshot manifest, representing the changed state values in
+/// the key range [`first_key`, `last_key`] (right side inclusive).
+#[derive(Deserialize, Serialize)]
+pub struct StateSnapshotDeltaChunk {
+    /// key of the first changed state value in this chunk.
+    pub first_key: HashValue,
+    /// key of the last changed state value in this chunk.
+    pub last_key: HashValue,
+    /// Repeated `len(record) + record` where `record` is BCS serialized tuple
+    /// `(key, Option<state_value>)`, `None` meaning the value is deleted at `version`.
+    pub blobs: FileHandle,
+}
+
+/// Incremental state snapshot backup manifest, representing the state values changed between the
+/// state snapshot at `base_version` and that at `version`. Applying it on top of the state at
+/// `base_version`, which is either a full state snapshot or another incremental one, results in
+/// the complete state view at `version`.
+#[derive(Deserialize, Serialize)]
+pub struct StateSnapshotDeltaBackup {
+    /// Version of the state snapshot the changes are relative to.
+    pub base_version: Version,
+    /// Version at which this state snapshot is taken.
+    pub version: Version,
+    /// Epoch in which this state snapshot is taken.
+    pub epoch: u64,
+    /// Hash of the state tree root at `version`.
+    pub root_hash: HashValue,
+    /// All changed state values in chunks, ordered by key.
+    pub chunks: Vec<StateSnapshotDeltaChunk>,
+    /// BCS serialized
+    /// `Tuple(TransactionInfoWithProof, LedgerInfoWithSignatures)`, same as
+    /// `StateSnapshotBackup::proof`. The individual chunks carry no proofs, the changes are
+    /// verified by checking the root hash of the tree after applying them.
+    pub proof: FileHandle,
+}
@@ -5,7 +5,8 @@ use crate::metrics::OTHER_TIMERS_SECONDS;
 use crate::utils::stream::StreamX;
 use crate::{
     backup_types::{
-        epoch_ending::restore::EpochHistory, state_snapshot::manifest::StateSnapshotBackup,
+        epoch_ending::restore::EpochHistory,
+        state_snapshot::manifest::{StateSnapshotBackup, StateSnapshotDeltaBackup},
     },
     metrics::{
         restore::{
@@ -23,6 +24,7 @@ use crate::{
     },
 };
 use anyhow::{anyhow, ensure, Result};
+use aptos_crypto::hash::CryptoHash;
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::{
@@ -31,9 +33,10 @@ use aptos_types::{
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::Version,
 };
+use aptosdb::MAX_STATE_SNAPSHOT_DELTA_CHANGES;
 use clap::Parser;
 use futures::{stream, TryStreamExt};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use storage_interface::StateSnapshotReceiver;
 use tokio::time::Instant;
 
@@ -222,3 +225,163 @@ impl StateSnapshotRestoreController {
         Ok(chunk)
     }
 }
+
+#[derive(Parser)]
+pub struct StateSnapshotDeltaRestoreOpt {
+    #[clap(long = "state-delta-manifest")]
+    pub manifest_handle: FileHandle,
+}
+
+/// Restores an incremental state snapshot on top of the state snapshot it's based on, which must
+/// have been restored already.
+pub struct StateSnapshotDeltaRestoreController {
+    storage: Arc<dyn BackupStorage>,
+    run_mode: Arc<RestoreRunMode>,
+    manifest_handle: FileHandle,
+    /// Global "target_version" for the entire restore process, if the delta is newer than this,
+    /// nothing will be done, otherwise, this has no effect.
+    target_version: Version,
+    epoch_history: Option<Arc<EpochHistory>>,
+    concurrent_downloads: usize,
+}
+
+impl StateSnapshotDeltaRestoreController {
+    pub fn new(
+        opt: StateSnapshotDeltaRestoreOpt,
+        global_opt: GlobalRestoreOptions,
+        storage: Arc<dyn BackupStorage>,
+        epoch_history: Option<Arc<EpochHistory>>,
+    ) -> Self {
+        Self {
+            storage,
+            run_mode: global_opt.run_mode,
+            manifest_handle: opt.manifest_handle,
+            target_version: global_opt.target_version,
+            epoch_history,
+            concurrent_downloads: global_opt.concurrent_downloads,
+        }
+    }
+
+    pub async fn run(self) -> Result<()> {
+        let name = self.name();
+        let start = Instant::now();
+        info!("{} started. Manifest: {}", name, self.manifest_handle);
+        self.run_impl()
+            .await
+            .map_err(|e| anyhow!("{} failed: {}", name, e))?;
+        info!(time = start.elapsed().as_secs(), "{} succeeded.", name);
+        Ok(())
+    }
+}
+
+impl StateSnapshotDeltaRestoreController {
+    fn name(&self) -> String {
+        format!("state snapshot delta {}", self.run_mode.name())
+    }
+
+    /// Unlike a full state snapshot, which is restored chunk by chunk, a delta is applied in one
+    /// tree update, so all its changes are loaded into memory first. Deltas with more than
+    /// `MAX_STATE_SNAPSHOT_DELTA_CHANGES` changes are refused before they are fully loaded.
+    async fn run_impl(self) -> Result<()> {
+        let manifest: StateSnapshotDeltaBackup =
+            self.storage.load_json_file(&self.manifest_handle).await?;
+        if manifest.version > self.target_version {
+            warn!(
+                "Trying to restore state snapshot delta to version {}, which is newer than the target version {}, skipping.",
+                manifest.version,
+                self.target_version,
+            );
+            return Ok(());
+        }
+
+        let (txn_info_with_proof, li): (TransactionInfoWithProof, LedgerInfoWithSignatures) =
+            self.storage.load_bcs_file(&manifest.proof).await?;
+        txn_info_with_proof.verify(li.ledger_info(), manifest.version)?;
+        let state_root_hash = txn_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()?;
+        ensure!(
+            state_root_hash == manifest.root_hash,
+            "Root hash mismatch with that in proof. root hash: {}, expected: {}",
+            manifest.root_hash,
+            state_root_hash,
+        );
+        if let Some(epoch_history) = self.epoch_history.as_ref() {
+            epoch_history.verify_ledger_info(&li)?;
+        }
+
+        let storage = self.storage.clone();
+        let futs_iter = manifest.chunks.into_iter().map(|chunk| {
+            let storage = storage.clone();
+            async move {
+                tokio::spawn(async move {
+                    let changes = Self::read_state_value_changes(&storage, chunk.blobs).await?;
+                    ensure!(
+                        changes.first().map(|(key, _)| key.hash()) == Some(chunk.first_key)
+                            && changes.last().map(|(key, _)| key.hash()) == Some(chunk.last_key),
+                        "State value changes don't match the key range in the manifest.",
+                    );
+                    Result::<_>::Ok(changes)
+                })
+                .await?
+            }
+        });
+        let con = self.concurrent_downloads;
+        let mut futs_stream = stream::iter(futs_iter).buffered_x(con * 2, con);
+        let mut changes = HashMap::new();
+        let mut prev_key = None;
+        while let Some(chunk) = futs_stream.try_next().await? {
+            for (key, value) in chunk {
+                let key_hash = key.hash();
+                ensure!(
+                    prev_key.map_or(true, |prev| prev < key_hash),
+                    "State value changes are not sorted by key hash.",
+                );
+                prev_key = Some(key_hash);
+                changes.insert(key, value);
+            }
+            ensure!(
+                changes.len() <= MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+                "State snapshot delta has more than {} changes.",
+                MAX_STATE_SNAPSHOT_DELTA_CHANGES,
+            );
+        }
+        info!(
+            base_version = manifest.base_version,
+            version = manifest.version,
+            num_changes = changes.len(),
+            "State value changes loaded."
+        );
+
+        let _timer = OTHER_TIMERS_SECONDS
+            .with_label_values(&["save_state_snapshot_delta"])
+            .start_timer();
+        let run_mode = self.run_mode.clone();
+        tokio::task::spawn_blocking(move || {
+            run_mode.save_state_snapshot_delta(
+                manifest.base_version,
+                manifest.version,
+                manifest.root_hash,
+                &changes,
+            )
+        })
+        .await??;
+        self.run_mode.finish();
+        Ok(())
+    }
+
+    async fn read_state_value_changes(
+        storage: &Arc<dyn BackupStorage>,
+        file_handle: FileHandle,
+    ) -> Result<Vec<(StateKey, Option<StateValue>)>> {
+        let mut file = storage.open_for_read(&file_handle).await?;
+
+        let mut chunk = vec![];
+
+        while let Some(record_bytes) = file.read_record_bytes().await? {
+            chunk.push(bcs::from_bytes(&record_bytes)?);
+        }
+
+        Ok(chunk)
+    }
+}
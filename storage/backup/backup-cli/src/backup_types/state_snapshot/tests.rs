@@ -4,17 +4,26 @@
 use crate::utils::ReplayConcurrencyLevelOpt;
 use crate::{
     backup_types::state_snapshot::{
-        backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
-        restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        backup::{
+            StateSnapshotBackupController, StateSnapshotBackupOpt,
+            StateSnapshotDeltaBackupController, StateSnapshotDeltaBackupOpt,
+        },
+        restore::{
+            StateSnapshotDeltaRestoreController, StateSnapshotDeltaRestoreOpt,
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt,
+        },
     },
     storage::{local_fs::LocalFs, BackupStorage},
     utils::{
         backup_service_client::BackupServiceClient,
         test_utils::{start_local_backup_service, tmp_db_with_random_content},
-        ConcurrentDownloadsOpt, GlobalBackupOpt, GlobalRestoreOpt, RocksdbOpt, TrustedWaypointOpt,
+        ConcurrentDownloadsOpt, GlobalBackupOpt, GlobalRestoreOpt, GlobalRestoreOptions,
+        RocksdbOpt, TrustedWaypointOpt,
     },
 };
+use aptos_crypto::HashValue;
 use aptos_temppath::TempPath;
+use aptos_types::transaction::Version;
 use aptosdb::AptosDB;
 use std::{convert::TryInto, sync::Arc};
 use storage_interface::DbReader;
@@ -106,3 +115,127 @@ fn end_to_end() {
 
     rt.shutdown_timeout(Duration::from_secs(1));
 }
+
+fn epoch_ending_version_and_root_hash(db: &AptosDB, epoch: u64) -> (Version, HashValue) {
+    let version = db
+        .get_epoch_ending_ledger_infos(epoch, epoch + 1)
+        .unwrap()
+        .ledger_info_with_sigs
+        .pop()
+        .unwrap()
+        .ledger_info()
+        .version();
+    let state_root_hash = db
+        .get_transactions(version, 1, version, false)
+        .unwrap()
+        .proof
+        .transaction_infos
+        .pop()
+        .unwrap()
+        .state_checkpoint_hash()
+        .unwrap();
+    (version, state_root_hash)
+}
+
+#[test]
+fn end_to_end_delta() {
+    let (_src_db_dir, src_db, _blocks) = tmp_db_with_random_content();
+    let tgt_db_dir = TempPath::new();
+    tgt_db_dir.create_as_dir().unwrap();
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let store: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir.path().to_path_buf()));
+
+    let epoch = src_db
+        .get_latest_ledger_info()
+        .unwrap()
+        .ledger_info()
+        .next_block_epoch()
+        - 1;
+    if epoch == 0 {
+        // Nothing to take a delta against.
+        return;
+    }
+    let base_epoch = epoch / 2;
+    let (base_version, _) = epoch_ending_version_and_root_hash(&src_db, base_epoch);
+    let (version, state_root_hash) = epoch_ending_version_and_root_hash(&src_db, epoch);
+
+    let (rt, port) = start_local_backup_service(src_db);
+    let client = Arc::new(BackupServiceClient::new(format!(
+        "http://localhost:{}",
+        port
+    )));
+    let global_backup_opt = GlobalBackupOpt {
+        max_chunk_size: 500,
+    };
+    let base_manifest_handle = rt
+        .block_on(
+            StateSnapshotBackupController::new(
+                StateSnapshotBackupOpt { epoch: base_epoch },
+                global_backup_opt.clone(),
+                Arc::clone(&client),
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+    let delta_manifest_handle = rt
+        .block_on(
+            StateSnapshotDeltaBackupController::new(
+                StateSnapshotDeltaBackupOpt { epoch, base_epoch },
+                global_backup_opt,
+                client,
+                Arc::clone(&store),
+            )
+            .run(),
+        )
+        .unwrap();
+
+    let global_restore_opt: GlobalRestoreOptions = GlobalRestoreOpt {
+        dry_run: false,
+        db_dir: Some(tgt_db_dir.path().to_path_buf()),
+        target_version: None, // max
+        trusted_waypoints: TrustedWaypointOpt::default(),
+        rocksdb_opt: RocksdbOpt::default(),
+        concurrent_downloads: ConcurrentDownloadsOpt::default(),
+        replay_concurrency_level: ReplayConcurrencyLevelOpt::default(),
+    }
+    .try_into()
+    .unwrap();
+    rt.block_on(
+        StateSnapshotRestoreController::new(
+            StateSnapshotRestoreOpt {
+                manifest_handle: base_manifest_handle,
+                version: base_version,
+            },
+            global_restore_opt.clone(),
+            Arc::clone(&store),
+            None, /* epoch_history */
+        )
+        .run(),
+    )
+    .unwrap();
+    rt.block_on(
+        StateSnapshotDeltaRestoreController::new(
+            StateSnapshotDeltaRestoreOpt {
+                manifest_handle: delta_manifest_handle,
+            },
+            global_restore_opt,
+            store,
+            None, /* epoch_history */
+        )
+        .run(),
+    )
+    .unwrap();
+
+    let tgt_db = AptosDB::new_readonly_for_test(&tgt_db_dir);
+    assert_eq!(
+        tgt_db
+            .get_state_snapshot_before(version + 1)
+            .unwrap()
+            .unwrap(),
+        (version, state_root_hash)
+    );
+
+    rt.shutdown_timeout(Duration::from_secs(1));
+}
@@ -11,7 +11,10 @@ use aptos_push_metrics::MetricsPusher;
 use backup_cli::{
     backup_types::{
         epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
-        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        state_snapshot::backup::{
+            StateSnapshotBackupController, StateSnapshotBackupOpt,
+            StateSnapshotDeltaBackupController, StateSnapshotDeltaBackupOpt,
+        },
         transaction::backup::{TransactionBackupController, TransactionBackupOpt},
     },
     coordinators::backup::{BackupCoordinator, BackupCoordinatorOpt},
@@ -101,6 +104,12 @@ enum BackupType {
         #[clap(subcommand)]
         storage: StorageOpt,
     },
+    StateSnapshotDelta {
+        #[clap(flatten)]
+        opt: StateSnapshotDeltaBackupOpt,
+        #[clap(subcommand)]
+        storage: StorageOpt,
+    },
     Transaction {
         #[clap(flatten)]
         opt: TransactionBackupOpt,
@@ -193,6 +202,16 @@ async fn main_impl() -> Result<()> {
                         .run()
                         .await?;
                     }
+                    BackupType::StateSnapshotDelta { opt, storage } => {
+                        StateSnapshotDeltaBackupController::new(
+                            opt,
+                            global_opt,
+                            client,
+                            storage.init_storage().await?,
+                        )
+                        .run()
+                        .await?;
+                    }
                     BackupType::Transaction { opt, storage } => {
                         TransactionBackupController::new(
                             opt,
@@ -7,7 +7,10 @@ use aptos_push_metrics::MetricsPusher;
 use backup_cli::{
     backup_types::{
         epoch_ending::restore::{EpochEndingRestoreController, EpochEndingRestoreOpt},
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotDeltaRestoreController, StateSnapshotDeltaRestoreOpt,
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt,
+        },
         transaction::restore::{TransactionRestoreController, TransactionRestoreOpt},
     },
     coordinators::restore::{RestoreCoordinator, RestoreCoordinatorOpt},
@@ -40,6 +43,12 @@ enum RestoreType {
         #[clap(subcommand)]
         storage: StorageOpt,
     },
+    StateSnapshotDelta {
+        #[clap(flatten)]
+        opt: StateSnapshotDeltaRestoreOpt,
+        #[clap(subcommand)]
+        storage: StorageOpt,
+    },
     Transaction {
         #[clap(flatten)]
         opt: TransactionRestoreOpt,
@@ -86,6 +95,16 @@ async fn main_impl() -> Result<()> {
             .run()
             .await?;
         }
+        RestoreType::StateSnapshotDelta { opt, storage } => {
+            StateSnapshotDeltaRestoreController::new(
+                opt,
+                global_opt,
+                storage.init_storage().await?,
+                None, /* epoch_history */
+            )
+            .run()
+            .await?;
+        }
         RestoreType::Transaction { opt, storage } => {
             TransactionRestoreController::new(
                 opt,
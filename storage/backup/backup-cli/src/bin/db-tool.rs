@@ -0,0 +1,182 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use aptos_config::config::{
+    BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_logger::{prelude::*, Level, Logger};
+use aptos_types::transaction::Version;
+use aptosdb::{db_surgeon::DbSurgeon, AptosDB};
+use backup_cli::utils::RocksdbOpt;
+use clap::Parser;
+use std::{io::stdout, path::PathBuf};
+use storage_interface::DbReader;
+
+#[derive(Parser)]
+enum Command {
+    #[clap(about = "Verify the consistency of an AptosDB in place.")]
+    Verify(VerifyOpt),
+    #[clap(about = "Delete everything committed after a version from an AptosDB.")]
+    Truncate(TruncateOpt),
+    #[clap(about = "Print the entries of a column family of an AptosDB.")]
+    Dump(DumpOpt),
+    #[clap(about = "Export the state snapshot at a version of an AptosDB to a file.")]
+    ExportStateSnapshot(ExportStateSnapshotOpt),
+}
+
+#[derive(Parser)]
+struct VerifyOpt {
+    #[clap(long = "db-dir", parse(from_os_str))]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(
+        long,
+        help = "The first transaction version to verify. [Defaults to the oldest version in the DB]"
+    )]
+    start_version: Option<Version>,
+    #[clap(
+        long,
+        help = "The version following the last transaction version to verify. [Defaults to the \
+        version following the latest version in the DB]"
+    )]
+    end_version: Option<Version>,
+    #[clap(
+        long,
+        default_value = "1000",
+        help = "Recompute the state merkle tree root hash of every N-th state snapshot in range."
+    )]
+    state_sample_interval: usize,
+    #[clap(
+        long,
+        help = "Skip scanning the index column families in full for entries pointing to versions \
+        in range that don't exist."
+    )]
+    skip_dangling_index_scan: bool,
+}
+
+#[derive(Parser)]
+struct TruncateOpt {
+    #[clap(long = "db-dir", parse(from_os_str))]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, help = "The last transaction version to keep.")]
+    target_version: Version,
+}
+
+#[derive(Parser)]
+struct DumpOpt {
+    #[clap(long = "db-dir", parse(from_os_str))]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, help = "One of ledger_db, state_merkle_db and index_indexer_db.")]
+    db_name: String,
+    #[clap(long)]
+    cf_name: String,
+    #[clap(
+        long,
+        default_value = "100",
+        help = "Maximum number of entries to print."
+    )]
+    limit: usize,
+}
+
+#[derive(Parser)]
+struct ExportStateSnapshotOpt {
+    #[clap(long = "db-dir", parse(from_os_str))]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(
+        long,
+        help = "Version of the state snapshot, which must exist in the DB."
+    )]
+    version: Version,
+    #[clap(long, parse(from_os_str))]
+    output_file: PathBuf,
+}
+
+fn main() -> Result<()> {
+    main_impl().map_err(|e| {
+        error!("main_impl() failed: {}", e);
+        e
+    })
+}
+
+fn main_impl() -> Result<()> {
+    Logger::new().level(Level::Info).init();
+
+    match Command::from_args() {
+        Command::Verify(opt) => verify(opt),
+        Command::Truncate(opt) => {
+            DbSurgeon::open(opt.db_dir, opt.rocksdb_opt.into())?.truncate(opt.target_version)
+        }
+        Command::Dump(opt) => {
+            DbSurgeon::open(opt.db_dir, opt.rocksdb_opt.into())?.dump(
+                &opt.db_name,
+                &opt.cf_name,
+                opt.limit,
+                &mut stdout(),
+            )?;
+            Ok(())
+        }
+        Command::ExportStateSnapshot(opt) => {
+            let header = DbSurgeon::open(opt.db_dir, opt.rocksdb_opt.into())?
+                .export_state_snapshot(opt.version, &opt.output_file)?;
+            println!("{:?}", header);
+            Ok(())
+        }
+    }
+}
+
+fn verify(opt: VerifyOpt) -> Result<()> {
+    let db = AptosDB::open(
+        opt.db_dir,
+        true,                        /* read_only */
+        NO_OP_STORAGE_PRUNER_CONFIG, /* pruner config */
+        opt.rocksdb_opt.into(),
+        false,
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )?;
+    let start_version = match opt.start_version {
+        Some(version) => version,
+        None => db.get_first_txn_version()?.unwrap_or(0),
+    };
+    let end_version = match opt.end_version {
+        Some(version) => version,
+        None => db.get_latest_version()? + 1,
+    };
+    info!(
+        start_version = start_version,
+        end_version = end_version,
+        "Verifying DB."
+    );
+
+    let verifier = db.get_db_verifier();
+    let mut inconsistencies = verifier.verify_ledger(start_version, end_version)?;
+    inconsistencies.extend(verifier.verify_state_snapshots(
+        start_version,
+        end_version,
+        opt.state_sample_interval,
+    )?);
+    if !opt.skip_dangling_index_scan {
+        inconsistencies.extend(verifier.find_dangling_indices(start_version, end_version)?);
+    }
+
+    for inconsistency in &inconsistencies {
+        println!("{}", inconsistency);
+    }
+    if !inconsistencies.is_empty() {
+        bail!("Found {} inconsistencies.", inconsistencies.len());
+    }
+    println!(
+        "No inconsistencies found in [{}, {}).",
+        start_version, end_version
+    );
+    Ok(())
+}
@@ -4,7 +4,10 @@
 use crate::{
     backup_types::{
         epoch_ending::backup::{EpochEndingBackupController, EpochEndingBackupOpt},
-        state_snapshot::backup::{StateSnapshotBackupController, StateSnapshotBackupOpt},
+        state_snapshot::backup::{
+            StateSnapshotBackupController, StateSnapshotBackupOpt,
+            StateSnapshotDeltaBackupController, StateSnapshotDeltaBackupOpt,
+        },
         transaction::backup::{TransactionBackupController, TransactionBackupOpt},
     },
     metadata,
@@ -50,6 +53,17 @@ pub struct BackupCoordinatorOpt {
         is already at 19, then snapshot at 15 will be taken instead of at 10 (not at 18)."
     )]
     pub state_snapshot_interval_epochs: usize,
+    // Defaulting to 0 to keep taking only full state snapshots unless asked otherwise.
+    #[clap(
+        long,
+        default_value = "0",
+        help = "Number of incremental state snapshots to take between two full ones. An \
+        incremental state snapshot only contains the state values changed since the previous \
+        snapshot, which is much smaller than a full one, but restoring it requires the full \
+        snapshot and all incremental ones in between to be restored. 0 disables incremental \
+        state snapshots."
+    )]
+    pub incremental_state_snapshots: usize,
     // Defaulting to 1M, which converts to a 20 minutes delay of a transaction showing up in a backup,
     // from a 1K TPS chain, and a few minutes replay time.
     #[clap(
@@ -83,6 +97,7 @@ pub struct BackupCoordinator {
     global_opt: GlobalBackupOpt,
     metadata_cache_opt: MetadataCacheOpt,
     state_snapshot_interval_epochs: usize,
+    incremental_state_snapshots: usize,
     transaction_batch_size: usize,
     concurrent_downloads: usize,
 }
@@ -101,6 +116,7 @@ impl BackupCoordinator {
             global_opt,
             metadata_cache_opt: opt.metadata_cache_opt,
             state_snapshot_interval_epochs: opt.state_snapshot_interval_epochs,
+            incremental_state_snapshots: opt.incremental_state_snapshots,
             transaction_batch_size: opt.transaction_batch_size,
             concurrent_downloads: opt.concurrent_downloads.get(),
         }
@@ -108,13 +124,16 @@ impl BackupCoordinator {
 
     pub async fn run(&self) -> Result<()> {
         // Connect to both the local node and the backup storage.
-        let backup_state = metadata::cache::sync_and_load(
+        let metadata_view = metadata::cache::sync_and_load(
             &self.metadata_cache_opt,
             Arc::clone(&self.storage),
             self.concurrent_downloads,
         )
-        .await?
-        .get_storage_state()?;
+        .await?;
+        let backup_state = metadata_view.get_storage_state()?;
+        let num_state_snapshot_deltas = metadata_view
+            .select_state_snapshot_chain(Version::MAX)?
+            .map_or(0, |chain| chain.deltas.len());
 
         // On new DbState retrieved:
         // `watch_db_state` informs `backup_epoch_endings` via channel 1,
@@ -140,7 +159,10 @@ impl BackupCoordinator {
             .boxed_local();
         let backup_state_snapshots = self
             .backup_work_stream(
-                backup_state.latest_state_snapshot_epoch,
+                (
+                    backup_state.latest_state_snapshot_epoch,
+                    num_state_snapshot_deltas,
+                ),
                 &rx2,
                 Self::backup_state_snapshot,
             )
@@ -231,9 +253,9 @@ impl BackupCoordinator {
 
     async fn backup_state_snapshot(
         &self,
-        last_snapshot_epoch_in_backup: Option<Version>,
+        (last_snapshot_epoch_in_backup, num_deltas_since_full): (Option<u64>, usize),
         db_state: DbState,
-    ) -> Result<Option<Version>> {
+    ) -> Result<(Option<u64>, usize)> {
         if let Some(epoch) = last_snapshot_epoch_in_backup {
             STATE_SNAPSHOT_EPOCH.set(epoch as i64);
         }
@@ -246,7 +268,31 @@ impl BackupCoordinator {
         // <= becuse db_state.epoch is still open
         if db_state.epoch <= epoch {
             // wait for the next db_state update
-            return Ok(last_snapshot_epoch_in_backup);
+            return Ok((last_snapshot_epoch_in_backup, num_deltas_since_full));
+        }
+
+        if let Some(base_epoch) = last_snapshot_epoch_in_backup {
+            if num_deltas_since_full < self.incremental_state_snapshots {
+                match StateSnapshotDeltaBackupController::new(
+                    StateSnapshotDeltaBackupOpt { epoch, base_epoch },
+                    self.global_opt.clone(),
+                    Arc::clone(&self.client),
+                    Arc::clone(&self.storage),
+                )
+                .run()
+                .await
+                {
+                    Ok(_) => return Ok((Some(epoch), num_deltas_since_full + 1)),
+                    // e.g. too many state keys changed since the base snapshot, see
+                    // `MAX_STATE_SNAPSHOT_DELTA_CHANGES`.
+                    Err(e) => warn!(
+                        epoch = epoch,
+                        base_epoch = base_epoch,
+                        "Incremental state snapshot failed, taking a full one instead: {}",
+                        e
+                    ),
+                }
+            }
         }
 
         StateSnapshotBackupController::new(
@@ -258,7 +304,7 @@ impl BackupCoordinator {
         .run()
         .await?;
 
-        Ok(Some(epoch))
+        Ok((Some(epoch), 0))
     }
 
     async fn backup_transactions(
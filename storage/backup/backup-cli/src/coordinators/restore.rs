@@ -4,7 +4,10 @@
 use crate::{
     backup_types::{
         epoch_ending::restore::EpochHistoryRestoreController,
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotDeltaRestoreController, StateSnapshotDeltaRestoreOpt,
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt,
+        },
         transaction::restore::TransactionRestoreBatchController,
     },
     metadata,
@@ -125,22 +128,25 @@ impl RestoreCoordinator {
             return Ok(());
         }
 
-        let state_snapshot_backup =
+        let max_txn_ver = metadata_view
+            .max_transaction_version()?
+            .ok_or_else(|| anyhow!("No transaction backup found."))?;
+        let state_snapshot_chain =
             if let Some(version) = self.global_opt.run_mode.get_in_progress_state_snapshot()? {
                 info!(
                     version = version,
                     "Found in progress state snapshot restore",
                 );
-                metadata_view.expect_state_snapshot(version)?
+                metadata_view.expect_state_snapshot_chain(
+                    version,
+                    std::cmp::min(self.target_version(), max_txn_ver),
+                )?
             } else {
-                let max_txn_ver = metadata_view
-                    .max_transaction_version()?
-                    .ok_or_else(|| anyhow!("No transaction backup found."))?;
                 metadata_view
-                    .select_state_snapshot(std::cmp::min(self.target_version(), max_txn_ver))?
+                    .select_state_snapshot_chain(std::cmp::min(self.target_version(), max_txn_ver))?
                     .ok_or_else(|| anyhow!("No usable state snapshot."))?
             };
-        let version = state_snapshot_backup.version;
+        let version = state_snapshot_chain.version();
         let epoch_ending_backups = metadata_view.select_epoch_ending_backups(version)?;
         let transaction_backup = metadata_view
             .select_transaction_backups(version, version)?
@@ -168,8 +174,8 @@ impl RestoreCoordinator {
 
         StateSnapshotRestoreController::new(
             StateSnapshotRestoreOpt {
-                manifest_handle: state_snapshot_backup.manifest,
-                version,
+                manifest_handle: state_snapshot_chain.base.manifest,
+                version: state_snapshot_chain.base.version,
             },
             self.global_opt.clone(),
             Arc::clone(&self.storage),
@@ -177,6 +183,18 @@ impl RestoreCoordinator {
         )
         .run()
         .await?;
+        for delta in state_snapshot_chain.deltas {
+            StateSnapshotDeltaRestoreController::new(
+                StateSnapshotDeltaRestoreOpt {
+                    manifest_handle: delta.manifest,
+                },
+                self.global_opt.clone(),
+                Arc::clone(&self.storage),
+                epoch_history.clone(),
+            )
+            .run()
+            .await?;
+        }
 
         let txn_manifests = vec![transaction_backup.manifest];
         TransactionRestoreBatchController::new(
@@ -4,7 +4,10 @@
 use crate::{
     backup_types::{
         epoch_ending::restore::EpochHistoryRestoreController,
-        state_snapshot::restore::{StateSnapshotRestoreController, StateSnapshotRestoreOpt},
+        state_snapshot::restore::{
+            StateSnapshotDeltaRestoreController, StateSnapshotDeltaRestoreOpt,
+            StateSnapshotRestoreController, StateSnapshotRestoreOpt,
+        },
         transaction::restore::TransactionRestoreBatchController,
     },
     metadata,
@@ -70,7 +73,7 @@ impl VerifyCoordinator {
         )
         .await?;
         let ver_max = Version::max_value();
-        let state_snapshot = metadata_view.select_state_snapshot(ver_max)?;
+        let state_snapshot_chain = metadata_view.select_state_snapshot_chain(ver_max)?;
         let transactions = metadata_view.select_transaction_backups(0, ver_max)?;
         let epoch_endings = metadata_view.select_epoch_ending_backups(ver_max)?;
 
@@ -95,11 +98,11 @@ impl VerifyCoordinator {
             .await?,
         );
 
-        if let Some(backup) = state_snapshot {
+        if let Some(chain) = state_snapshot_chain {
             StateSnapshotRestoreController::new(
                 StateSnapshotRestoreOpt {
-                    manifest_handle: backup.manifest,
-                    version: backup.version,
+                    manifest_handle: chain.base.manifest,
+                    version: chain.base.version,
                 },
                 global_opt.clone(),
                 Arc::clone(&self.storage),
@@ -107,6 +110,18 @@ impl VerifyCoordinator {
             )
             .run()
             .await?;
+            for delta in chain.deltas {
+                StateSnapshotDeltaRestoreController::new(
+                    StateSnapshotDeltaRestoreOpt {
+                        manifest_handle: delta.manifest,
+                    },
+                    global_opt.clone(),
+                    Arc::clone(&self.storage),
+                    Some(Arc::clone(&epoch_history)),
+                )
+                .run()
+                .await?;
+            }
         }
 
         let txn_manifests = transactions.into_iter().map(|b| b.manifest).collect();
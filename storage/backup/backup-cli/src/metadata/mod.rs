@@ -16,6 +16,7 @@ use std::convert::TryInto;
 pub(crate) enum Metadata {
     EpochEndingBackup(EpochEndingBackupMeta),
     StateSnapshotBackup(StateSnapshotBackupMeta),
+    StateSnapshotDeltaBackup(StateSnapshotDeltaBackupMeta),
     TransactionBackup(TransactionBackupMeta),
     Identity(IdentityMeta),
 }
@@ -45,6 +46,20 @@ impl Metadata {
         })
     }
 
+    pub fn new_state_snapshot_delta_backup(
+        base_version: Version,
+        epoch: u64,
+        version: Version,
+        manifest: FileHandle,
+    ) -> Self {
+        Self::StateSnapshotDeltaBackup(StateSnapshotDeltaBackupMeta {
+            base_version,
+            epoch,
+            version,
+            manifest,
+        })
+    }
+
     pub fn new_transaction_backup(
         first_version: Version,
         last_version: Version,
@@ -69,6 +84,10 @@ impl Metadata {
                 format!("epoch_ending_{}-{}.meta", e.first_epoch, e.last_epoch)
             }
             Self::StateSnapshotBackup(s) => format!("state_snapshot_ver_{}.meta", s.version),
+            Self::StateSnapshotDeltaBackup(d) => format!(
+                "state_snapshot_delta_ver_{}-{}.meta",
+                d.base_version, d.version
+            ),
             Self::TransactionBackup(t) => {
                 format!("transaction_{}-{}.meta", t.first_version, t.last_version,)
             }
@@ -99,6 +118,14 @@ pub struct StateSnapshotBackupMeta {
     pub manifest: FileHandle,
 }
 
+#[derive(Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd)]
+pub struct StateSnapshotDeltaBackupMeta {
+    pub base_version: Version,
+    pub epoch: u64,
+    pub version: Version,
+    pub manifest: FileHandle,
+}
+
 #[derive(Clone, Deserialize, Serialize, Eq, PartialEq, Ord, PartialOrd)]
 pub struct TransactionBackupMeta {
     pub first_version: Version,
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::metadata::{
-    EpochEndingBackupMeta, IdentityMeta, Metadata, StateSnapshotBackupMeta, TransactionBackupMeta,
+    EpochEndingBackupMeta, IdentityMeta, Metadata, StateSnapshotBackupMeta,
+    StateSnapshotDeltaBackupMeta, TransactionBackupMeta,
 };
 use anyhow::{anyhow, ensure, Result};
 use aptos_types::transaction::Version;
@@ -12,6 +13,7 @@ use std::{fmt, str::FromStr};
 pub struct MetadataView {
     epoch_ending_backups: Vec<EpochEndingBackupMeta>,
     state_snapshot_backups: Vec<StateSnapshotBackupMeta>,
+    state_snapshot_delta_backups: Vec<StateSnapshotDeltaBackupMeta>,
     transaction_backups: Vec<TransactionBackupMeta>,
     _identity: Option<IdentityMeta>,
 }
@@ -20,10 +22,10 @@ impl MetadataView {
     pub fn get_storage_state(&self) -> Result<BackupStorageState> {
         let latest_epoch_ending_epoch =
             self.epoch_ending_backups.iter().map(|e| e.last_epoch).max();
-        let latest_state_snapshot = self.select_state_snapshot(Version::MAX)?;
+        let latest_state_snapshot = self.select_state_snapshot_chain(Version::MAX)?;
         let (latest_state_snapshot_epoch, latest_state_snapshot_version) =
             match latest_state_snapshot {
-                Some(snapshot) => (Some(snapshot.epoch), Some(snapshot.version)),
+                Some(chain) => (Some(chain.epoch()), Some(chain.version())),
                 None => (None, None),
             };
         let latest_transaction_version = self
@@ -61,6 +63,77 @@ impl MetadataView {
             .ok_or_else(|| anyhow!("State snapshot not found at version {}", version))
     }
 
+    /// Selects the newest state snapshot no newer than `target_version` that can be restored,
+    /// either a full one or a chain of incremental ones on top of a full one.
+    pub fn select_state_snapshot_chain(
+        &self,
+        target_version: Version,
+    ) -> Result<Option<StateSnapshotChain>> {
+        Ok(self
+            .state_snapshot_backups
+            .iter()
+            .map(|m| m.version)
+            .chain(self.state_snapshot_delta_backups.iter().map(|d| d.version))
+            .filter(|version| *version <= target_version)
+            .sorted()
+            .dedup()
+            .rev()
+            .find_map(|version| self.find_state_snapshot_chain(version, None)))
+    }
+
+    /// Like `select_state_snapshot_chain()`, but the chain must start from the full state
+    /// snapshot at `base_version`, for example because it's already partially restored.
+    pub fn expect_state_snapshot_chain(
+        &self,
+        base_version: Version,
+        target_version: Version,
+    ) -> Result<StateSnapshotChain> {
+        self.state_snapshot_delta_backups
+            .iter()
+            .map(|d| d.version)
+            .filter(|version| *version > base_version && *version <= target_version)
+            .sorted()
+            .dedup()
+            .rev()
+            .chain(std::iter::once(base_version))
+            .find_map(|version| self.find_state_snapshot_chain(version, Some(base_version)))
+            .ok_or_else(|| anyhow!("State snapshot not found at version {}", base_version))
+    }
+
+    /// Finds a chain of state snapshots resulting in the state at `version`, preferring full
+    /// snapshots over incremental ones.
+    fn find_state_snapshot_chain(
+        &self,
+        version: Version,
+        base_version: Option<Version>,
+    ) -> Option<StateSnapshotChain> {
+        if base_version.map_or(false, |base_version| version < base_version) {
+            return None;
+        }
+        if base_version.map_or(true, |base_version| base_version == version) {
+            if let Some(base) = self
+                .state_snapshot_backups
+                .iter()
+                .find(|m| m.version == version)
+            {
+                return Some(StateSnapshotChain {
+                    base: base.clone(),
+                    deltas: Vec::new(),
+                });
+            }
+        }
+        self.state_snapshot_delta_backups
+            .iter()
+            .filter(|d| d.version == version && d.base_version < version)
+            .sorted()
+            .rev()
+            .find_map(|delta| {
+                let mut chain = self.find_state_snapshot_chain(delta.base_version, base_version)?;
+                chain.deltas.push(delta.clone());
+                Some(chain)
+            })
+    }
+
     pub fn select_transaction_backups(
         &self,
         start_version: Version,
@@ -132,6 +205,7 @@ impl From<Vec<Metadata>> for MetadataView {
     fn from(metadata_vec: Vec<Metadata>) -> Self {
         let mut epoch_ending_backups = Vec::new();
         let mut state_snapshot_backups = Vec::new();
+        let mut state_snapshot_delta_backups = Vec::new();
         let mut transaction_backups = Vec::new();
         let mut identity = None;
 
@@ -139,6 +213,7 @@ impl From<Vec<Metadata>> for MetadataView {
             match meta {
                 Metadata::EpochEndingBackup(e) => epoch_ending_backups.push(e),
                 Metadata::StateSnapshotBackup(s) => state_snapshot_backups.push(s),
+                Metadata::StateSnapshotDeltaBackup(d) => state_snapshot_delta_backups.push(d),
                 Metadata::TransactionBackup(t) => transaction_backups.push(t),
                 Metadata::Identity(i) => identity = Some(i),
             }
@@ -147,12 +222,34 @@ impl From<Vec<Metadata>> for MetadataView {
         Self {
             epoch_ending_backups,
             state_snapshot_backups,
+            state_snapshot_delta_backups,
             transaction_backups,
             _identity: identity,
         }
     }
 }
 
+/// A full state snapshot, followed by incremental ones each based on the previous one, which
+/// together restore the state at the version of the last one.
+pub struct StateSnapshotChain {
+    pub base: StateSnapshotBackupMeta,
+    pub deltas: Vec<StateSnapshotDeltaBackupMeta>,
+}
+
+impl StateSnapshotChain {
+    pub fn version(&self) -> Version {
+        self.deltas
+            .last()
+            .map_or(self.base.version, |delta| delta.version)
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.deltas
+            .last()
+            .map_or(self.base.epoch, |delta| delta.epoch)
+    }
+}
+
 pub struct BackupStorageState {
     pub latest_epoch_ending_epoch: Option<u64>,
     pub latest_state_snapshot_epoch: Option<u64>,
@@ -3,6 +3,7 @@
 
 pub mod command_adapter;
 pub mod local_fs;
+pub mod s3;
 
 #[cfg(test)]
 mod test_util;
@@ -12,6 +13,7 @@ mod tests;
 use crate::storage::{
     command_adapter::{CommandAdapter, CommandAdapterOpt},
     local_fs::{LocalFs, LocalFsOpt},
+    s3::{S3Opt, S3},
 };
 use anyhow::{ensure, Result};
 use async_trait::async_trait;
@@ -180,6 +182,13 @@ pub enum StorageOpt {
     https://github.com/aptos-labs/aptos-core/tree/main/storage/backup/backup-cli/src/storage/command_adapter/sample_configs/"
     )]
     CommandAdapter(CommandAdapterOpt),
+    #[clap(
+        about = "Select the S3 backup storage type, which talks to S3 or a store compatible with \
+    its API, like MinIO, directly over HTTP, without the need for shell commands. See a sample \
+    config here: \
+    https://github.com/aptos-labs/aptos-core/tree/main/storage/backup/backup-cli/src/storage/s3/sample_configs/"
+    )]
+    S3(S3Opt),
 }
 
 impl StorageOpt {
@@ -187,6 +196,7 @@ impl StorageOpt {
         Ok(match self {
             StorageOpt::LocalFs(opt) => Arc::new(LocalFs::new_with_opt(opt)),
             StorageOpt::CommandAdapter(opt) => Arc::new(CommandAdapter::new_with_opt(opt).await?),
+            StorageOpt::S3(opt) => Arc::new(S3::new_with_opt(opt).await?),
         })
     }
 }
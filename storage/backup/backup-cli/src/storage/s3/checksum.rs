@@ -0,0 +1,185 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! Verifies the content read from S3 against the checksum it was uploaded with, so corrupted
+//! objects are noticed instead of being restored from.
+
+use crate::storage::s3::client::{CHECKSUM_HEADER, PART_SIZE_HEADER};
+use anyhow::{anyhow, ensure, Result};
+use pin_project::pin_project;
+use reqwest::header::HeaderMap;
+use sha2::{Digest, Sha256};
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The checksum of an object, see `CHECKSUM_HEADER`.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Checksum {
+    /// SHA-256 of the object
+    Object(Vec<u8>),
+    /// SHA-256 of the SHA-256s of the parts of a multipart upload
+    Parts {
+        digest: Vec<u8>,
+        num_parts: usize,
+        part_size: usize,
+    },
+}
+
+impl Checksum {
+    /// Returns `None` if the object was uploaded without a checksum, or the store doesn't
+    /// support them.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>> {
+        let header = |name: &str| -> Result<Option<&str>> {
+            headers
+                .get(name)
+                .map(|value| value.to_str())
+                .transpose()
+                .map_err(|_| anyhow!("Header {} is not valid UTF-8.", name))
+        };
+        let checksum = match header(CHECKSUM_HEADER)? {
+            Some(checksum) => checksum,
+            None => return Ok(None),
+        };
+        Ok(Some(match checksum.split_once('-') {
+            None => Self::Object(base64::decode(checksum)?),
+            Some((digest, num_parts)) => {
+                let part_size = header(PART_SIZE_HEADER)?.ok_or_else(|| {
+                    anyhow!("Multipart checksum {} without a part size.", checksum)
+                })?;
+                let part_size: usize = part_size.parse()?;
+                ensure!(part_size > 0, "Part size must not be zero.");
+                Self::Parts {
+                    digest: base64::decode(digest)?,
+                    num_parts: num_parts.parse()?,
+                    part_size,
+                }
+            }
+        }))
+    }
+}
+
+/// Computes the checksum of the content fed to it, in the form of the expected `Checksum`.
+struct Verifier {
+    expected: Checksum,
+    hasher: Sha256,
+    /// Bytes left in the current part, if the checksum is over parts
+    part_remaining: usize,
+    part_digests: Vec<u8>,
+    num_parts: usize,
+}
+
+impl Verifier {
+    fn new(expected: Checksum) -> Self {
+        let part_remaining = match &expected {
+            Checksum::Object(_) => usize::MAX,
+            Checksum::Parts { part_size, .. } => *part_size,
+        };
+        Self {
+            expected,
+            hasher: Sha256::new(),
+            part_remaining,
+            part_digests: Vec::new(),
+            num_parts: 0,
+        }
+    }
+
+    fn update(&mut self, mut data: &[u8]) {
+        if let Checksum::Object(_) = self.expected {
+            self.hasher.update(data);
+            return;
+        }
+        while !data.is_empty() {
+            let len = std::cmp::min(self.part_remaining, data.len());
+            self.hasher.update(&data[..len]);
+            self.part_remaining -= len;
+            data = &data[len..];
+            if self.part_remaining == 0 {
+                self.finish_part();
+            }
+        }
+    }
+
+    fn finish_part(&mut self) {
+        if let Checksum::Parts { part_size, .. } = self.expected {
+            self.part_digests
+                .extend_from_slice(&self.hasher.finalize_reset());
+            self.num_parts += 1;
+            self.part_remaining = part_size;
+        }
+    }
+
+    /// Whether the content fed matches the expected checksum
+    fn verify(mut self) -> bool {
+        let actual = match self.expected {
+            Checksum::Object(_) => Checksum::Object(self.hasher.finalize().to_vec()),
+            Checksum::Parts { part_size, .. } => {
+                // The last part is usually shorter.
+                if self.part_remaining != part_size {
+                    self.finish_part();
+                }
+                Checksum::Parts {
+                    digest: Sha256::digest(&self.part_digests).to_vec(),
+                    num_parts: self.num_parts,
+                    part_size,
+                }
+            }
+        };
+        actual == self.expected
+    }
+}
+
+/// An `AsyncRead` that fails with `InvalidData` at the end of the content if it doesn't match
+/// the checksum.
+#[pin_project]
+pub(super) struct VerifyingReader<R> {
+    #[pin]
+    inner: R,
+    key: String,
+    verifier: Option<Verifier>,
+}
+
+impl<R> VerifyingReader<R> {
+    pub fn new(inner: R, key: String, checksum: Checksum) -> Self {
+        Self {
+            inner,
+            key,
+            verifier: Some(Verifier::new(checksum)),
+        }
+    }
+}
+
+impl<R: AsyncRead> AsyncRead for VerifyingReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.project();
+        if buf.remaining() == 0 {
+            return this.inner.poll_read(cx, buf);
+        }
+        let filled = buf.filled().len();
+        futures::ready!(this.inner.poll_read(cx, buf))?;
+        let read = &buf.filled()[filled..];
+        if !read.is_empty() {
+            if let Some(verifier) = this.verifier.as_mut() {
+                verifier.update(read);
+            }
+        } else if let Some(verifier) = this.verifier.take() {
+            // Reached the end of the content.
+            if !verifier.verify() {
+                return Poll::Ready(Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "Content of S3 object {} doesn't match its checksum.",
+                        this.key
+                    ),
+                )));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,480 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal client for the subset of the S3 HTTP API used by the backup storage, signing
+//! requests with AWS Signature Version 4.
+
+use crate::storage::s3::config::S3Config;
+use anyhow::{anyhow, ensure, Result};
+use aptos_logger::warn;
+use bytes::Bytes;
+use hmac::{Hmac, Mac, NewMac};
+use once_cell::sync::Lazy;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use regex::Regex;
+use reqwest::{Method, Response, StatusCode, Url};
+use sha2::{Digest, Sha256};
+use std::time::Duration;
+
+/// Characters S3 expects to be percent encoded, i.e. everything but the unreserved characters.
+const URI_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+const SERVICE: &str = "s3";
+const SIGNING_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Base64 encoded SHA-256 of an object or part, which S3 verifies on upload and returns on
+/// download. For multipart uploads it's the SHA-256 of the SHA-256s of the parts, followed by
+/// `-<number of parts>`.
+pub(crate) const CHECKSUM_HEADER: &str = "x-amz-checksum-sha256";
+/// User metadata holding the part size of multipart uploads, needed to verify their checksum.
+pub(crate) const PART_SIZE_HEADER: &str = "x-amz-meta-part-size";
+
+#[derive(Clone)]
+pub(crate) struct Credentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl Credentials {
+    const ACCESS_KEY_ID_ENV: &'static str = "AWS_ACCESS_KEY_ID";
+    const SECRET_ACCESS_KEY_ENV: &'static str = "AWS_SECRET_ACCESS_KEY";
+    const SESSION_TOKEN_ENV: &'static str = "AWS_SESSION_TOKEN";
+
+    /// Credentials are taken from the environment, so they don't end up in config files.
+    pub fn from_env() -> Result<Self> {
+        let var = |key: &str| {
+            std::env::var(key).map_err(|_| anyhow!("Env var {} must be set for S3 storage.", key))
+        };
+        Ok(Self {
+            access_key_id: var(Self::ACCESS_KEY_ID_ENV)?,
+            secret_access_key: var(Self::SECRET_ACCESS_KEY_ENV)?,
+            session_token: std::env::var(Self::SESSION_TOKEN_ENV).ok(),
+        })
+    }
+}
+
+/// A part of a multipart upload, as needed to complete the upload.
+pub(crate) struct UploadedPart {
+    pub etag: String,
+    pub checksum: String,
+}
+
+pub(crate) struct S3Client {
+    http: reqwest::Client,
+    endpoint: Url,
+    region: String,
+    bucket: String,
+    credentials: Credentials,
+    max_retries: usize,
+}
+
+impl S3Client {
+    pub fn new(config: &S3Config, credentials: Credentials) -> Result<Self> {
+        let endpoint = Url::parse(&config.endpoint)?;
+        ensure!(
+            endpoint.host_str().is_some(),
+            "S3 endpoint has no host: {}",
+            config.endpoint
+        );
+        Ok(Self {
+            http: reqwest::Client::new(),
+            endpoint,
+            region: config.region.clone(),
+            bucket: config.bucket.clone(),
+            credentials,
+            max_retries: config.max_retries,
+        })
+    }
+
+    pub async fn put_object(&self, key: &str, body: Bytes) -> Result<()> {
+        let headers = vec![(CHECKSUM_HEADER, checksum(&body))];
+        self.send(Method::PUT, key, vec![], headers, body).await?;
+        Ok(())
+    }
+
+    /// The response carries the checksum of the object in `CHECKSUM_HEADER`, if it was uploaded
+    /// with one.
+    pub async fn get_object(&self, key: &str) -> Result<Response> {
+        let headers = vec![("x-amz-checksum-mode", "ENABLED".to_string())];
+        self.send(Method::GET, key, vec![], headers, Bytes::new())
+            .await
+    }
+
+    /// Returns the upload id to pass to the other multipart upload requests.
+    pub async fn create_multipart_upload(&self, key: &str, part_size: usize) -> Result<String> {
+        let response = self
+            .send(
+                Method::POST,
+                key,
+                vec![("uploads", String::new())],
+                vec![
+                    ("x-amz-checksum-algorithm", "SHA256".to_string()),
+                    (PART_SIZE_HEADER, part_size.to_string()),
+                ],
+                Bytes::new(),
+            )
+            .await?;
+        let body = response.text().await?;
+        xml_values(&body, "UploadId")
+            .pop()
+            .ok_or_else(|| anyhow!("No UploadId in CreateMultipartUpload response: {}", body))
+    }
+
+    pub async fn upload_part(
+        &self,
+        key: &str,
+        upload_id: &str,
+        part_number: usize,
+        body: Bytes,
+    ) -> Result<UploadedPart> {
+        let checksum = checksum(&body);
+        let response = self
+            .send(
+                Method::PUT,
+                key,
+                vec![
+                    ("partNumber", part_number.to_string()),
+                    ("uploadId", upload_id.to_string()),
+                ],
+                vec![(CHECKSUM_HEADER, checksum.clone())],
+                body,
+            )
+            .await?;
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .ok_or_else(|| anyhow!("No ETag in UploadPart response."))?
+            .to_str()?
+            .to_string();
+        Ok(UploadedPart { etag, checksum })
+    }
+
+    pub async fn complete_multipart_upload(
+        &self,
+        key: &str,
+        upload_id: &str,
+        parts: &[UploadedPart],
+    ) -> Result<()> {
+        let parts: String = parts
+            .iter()
+            .enumerate()
+            .map(|(idx, part)| {
+                format!(
+                    "<Part><PartNumber>{}</PartNumber><ETag>{}</ETag><ChecksumSHA256>{}</ChecksumSHA256></Part>",
+                    idx + 1,
+                    part.etag,
+                    part.checksum
+                )
+            })
+            .collect();
+        let body = format!(
+            "<CompleteMultipartUpload>{}</CompleteMultipartUpload>",
+            parts
+        );
+        let response = self
+            .send(
+                Method::POST,
+                key,
+                vec![("uploadId", upload_id.to_string())],
+                vec![],
+                Bytes::from(body),
+            )
+            .await?;
+        // S3 can report a failure to complete the upload in the body of a 200 response.
+        let body = response.text().await?;
+        ensure!(
+            !body.contains("<Error>"),
+            "Failed to complete multipart upload of {}: {}",
+            key,
+            body
+        );
+        Ok(())
+    }
+
+    pub async fn abort_multipart_upload(&self, key: &str, upload_id: &str) -> Result<()> {
+        self.send(
+            Method::DELETE,
+            key,
+            vec![("uploadId", upload_id.to_string())],
+            vec![],
+            Bytes::new(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Lists the keys of all objects starting with `prefix`.
+    pub async fn list_objects(&self, prefix: &str) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let mut query = vec![
+                ("list-type", "2".to_string()),
+                ("prefix", prefix.to_string()),
+            ];
+            if let Some(token) = continuation_token.take() {
+                query.push(("continuation-token", token));
+            }
+            let body = self
+                .send(Method::GET, "", query, vec![], Bytes::new())
+                .await?
+                .text()
+                .await?;
+            keys.extend(xml_values(&body, "Key"));
+            continuation_token = xml_values(&body, "NextContinuationToken").pop();
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(keys)
+    }
+
+    /// Sends a request, retrying on network errors, throttling and server errors.
+    /// Any other unsuccessful response is returned as an error. `headers` are sent and signed in
+    /// addition to the ones every request has, their names must be lower case.
+    async fn send(
+        &self,
+        method: Method,
+        key: &str,
+        query: Vec<(&str, String)>,
+        headers: Vec<(&str, String)>,
+        body: Bytes,
+    ) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let err = match self
+                .send_once(method.clone(), key, &query, &headers, body.clone())
+                .await
+            {
+                Ok(response) if response.status().is_success() => return Ok(response),
+                Ok(response) => {
+                    let status = response.status();
+                    let err = anyhow!(
+                        "S3 {} request for key {:?} failed with status {}: {}",
+                        method,
+                        key,
+                        status,
+                        response.text().await.unwrap_or_default(),
+                    );
+                    if !is_retryable(status) {
+                        return Err(err);
+                    }
+                    err
+                }
+                Err(err) => err,
+            };
+            if attempt >= self.max_retries {
+                return Err(err);
+            }
+            let backoff = std::cmp::min(Duration::from_millis(100 << attempt.min(7)), MAX_BACKOFF);
+            warn!(
+                error = %err,
+                attempt = attempt,
+                backoff_ms = backoff.as_millis() as u64,
+                "S3 request failed, retrying."
+            );
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+        }
+    }
+
+    async fn send_once(
+        &self,
+        method: Method,
+        key: &str,
+        query: &[(&str, String)],
+        extra_headers: &[(&str, String)],
+        body: Bytes,
+    ) -> Result<Response> {
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let canonical_uri = self.canonical_uri(key);
+        let canonical_query = canonical_query_string(query);
+        let payload_hash = hex::encode(Sha256::digest(&body));
+
+        let mut headers = vec![
+            ("host".to_string(), self.host()),
+            ("x-amz-content-sha256".to_string(), payload_hash.clone()),
+            ("x-amz-date".to_string(), amz_date.clone()),
+        ];
+        headers.extend(
+            extra_headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone())),
+        );
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token".to_string(), session_token.clone()));
+        }
+        let authorization = authorization_header(
+            &self.credentials,
+            &self.region,
+            &amz_date,
+            method.as_str(),
+            &canonical_uri,
+            &canonical_query,
+            &headers,
+            &payload_hash,
+        );
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&canonical_uri);
+        url.set_query(if canonical_query.is_empty() {
+            None
+        } else {
+            Some(&canonical_query)
+        });
+        let mut request = self
+            .http
+            .request(method, url)
+            .header(reqwest::header::AUTHORIZATION, authorization);
+        for (name, value) in headers {
+            // reqwest sets the host header itself, from the url
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        Ok(request.body(body).send().await?)
+    }
+
+    /// Path style addressing, i.e. "/bucket/key", which works with both AWS and self hosted
+    /// S3 compatible stores.
+    fn canonical_uri(&self, key: &str) -> String {
+        let mut uri = format!("/{}", uri_encode(&self.bucket));
+        if !key.is_empty() {
+            for segment in key.split('/') {
+                uri.push('/');
+                uri.push_str(&uri_encode(segment));
+            }
+        }
+        uri
+    }
+
+    fn host(&self) -> String {
+        let host = self.endpoint.host_str().expect("Checked in constructor.");
+        match self.endpoint.port() {
+            Some(port) => format!("{}:{}", host, port),
+            None => host.to_string(),
+        }
+    }
+}
+
+/// The value of `CHECKSUM_HEADER` for `content`.
+pub(crate) fn checksum(content: &[u8]) -> String {
+    base64::encode(Sha256::digest(content))
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+fn uri_encode(value: &str) -> String {
+    utf8_percent_encode(value, URI_ENCODE_SET).to_string()
+}
+
+fn canonical_query_string(query: &[(&str, String)]) -> String {
+    let mut params: Vec<_> = query
+        .iter()
+        .map(|(key, value)| (uri_encode(key), uri_encode(value)))
+        .collect();
+    params.sort();
+    params
+        .into_iter()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_varkey(key).expect("HMAC accepts keys of any size.");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Builds the `Authorization` header of a request. `headers` must hold all headers to sign with
+/// lower case names, including `host`, `x-amz-content-sha256` and `x-amz-date`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn authorization_header(
+    credentials: &Credentials,
+    region: &str,
+    amz_date: &str,
+    method: &str,
+    canonical_uri: &str,
+    canonical_query: &str,
+    headers: &[(String, String)],
+    payload_hash: &str,
+) -> String {
+    let mut headers = headers.to_vec();
+    headers.sort();
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = [
+        method,
+        canonical_uri,
+        canonical_query,
+        &canonical_headers,
+        &signed_headers,
+        payload_hash,
+    ]
+    .join("\n");
+
+    let date = &amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, region, SERVICE);
+    let string_to_sign = [
+        SIGNING_ALGORITHM,
+        amz_date,
+        &scope,
+        &hex::encode(Sha256::digest(canonical_request.as_bytes())),
+    ]
+    .join("\n");
+
+    let signing_key = [region, SERVICE, "aws4_request"].iter().fold(
+        hmac_sha256(
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            date,
+        ),
+        |key, data| hmac_sha256(&key, data),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, &string_to_sign));
+
+    format!(
+        "{} Credential={}/{}, SignedHeaders={}, Signature={}",
+        SIGNING_ALGORITHM, credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+/// Extracts the text of all elements named `tag`. The responses we parse are flat and simple
+/// enough that a full XML parser isn't needed.
+pub(crate) fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    static ESCAPES: Lazy<Vec<(&str, &str)>> = Lazy::new(|| {
+        vec![
+            ("&lt;", "<"),
+            ("&gt;", ">"),
+            ("&quot;", "\""),
+            ("&apos;", "'"),
+            ("&amp;", "&"),
+        ]
+    });
+    let re = Regex::new(&format!("<{tag}>(.*?)</{tag}>", tag = tag)).expect("Valid regex.");
+    re.captures_iter(xml)
+        .map(|captures| {
+            ESCAPES
+                .iter()
+                .fold(captures[1].to_string(), |value, (escaped, raw)| {
+                    value.replace(escaped, raw)
+                })
+        })
+        .collect()
+}
@@ -0,0 +1,76 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::utils::error_notes::ErrorNotes;
+use anyhow::{ensure, Result};
+use serde::Deserialize;
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Config {
+    /// Base URL of the S3 compatible service, like "https://s3.us-west-2.amazonaws.com" or
+    /// "http://localhost:9000" for a local MinIO. Buckets are addressed path style, i.e.
+    /// "{endpoint}/{bucket}/{key}".
+    pub endpoint: String,
+    /// Region the requests are signed for. Self hosted stores usually accept the default.
+    #[serde(default = "S3Config::default_region")]
+    pub region: String,
+    pub bucket: String,
+    /// Prefix of all keys in the bucket, so multiple backups can share a bucket. A trailing "/"
+    /// is added if missing.
+    #[serde(default)]
+    pub prefix: String,
+    /// Files larger than this are uploaded in parts of this size, S3 requires at least 5MB.
+    #[serde(default = "S3Config::default_part_size_mb")]
+    pub part_size_mb: usize,
+    /// Number of times a failed request is retried, with exponential backoff.
+    #[serde(default = "S3Config::default_max_retries")]
+    pub max_retries: usize,
+}
+
+impl S3Config {
+    const MIN_PART_SIZE_MB: usize = 5;
+    const MAX_PART_SIZE_MB: usize = 5 * 1024;
+
+    fn default_region() -> String {
+        "us-east-1".to_string()
+    }
+
+    fn default_part_size_mb() -> usize {
+        16
+    }
+
+    fn default_max_retries() -> usize {
+        5
+    }
+
+    pub async fn load_from_file(path: &Path) -> Result<Self> {
+        let path_str = path.to_str().unwrap_or_default();
+        let mut file = tokio::fs::File::open(path).await.err_notes(path_str)?;
+        let mut content = Vec::new();
+        file.read_to_end(&mut content).await.err_notes(path_str)?;
+
+        Self::validated(serde_yaml::from_slice(&content)?)
+    }
+
+    pub fn load_from_str(content: &str) -> Result<Self> {
+        Self::validated(serde_yaml::from_str(content)?)
+    }
+
+    fn validated(mut config: Self) -> Result<Self> {
+        ensure!(!config.bucket.is_empty(), "S3 bucket must not be empty.");
+        ensure!(
+            (Self::MIN_PART_SIZE_MB..=Self::MAX_PART_SIZE_MB).contains(&config.part_size_mb),
+            "S3 part_size_mb must be between {} and {}, got {}.",
+            Self::MIN_PART_SIZE_MB,
+            Self::MAX_PART_SIZE_MB,
+            config.part_size_mb,
+        );
+        if !config.prefix.is_empty() && !config.prefix.ends_with('/') {
+            config.prefix.push('/');
+        }
+        Ok(config)
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+mod checksum;
+mod client;
+pub mod config;
+mod upload;
+
+#[cfg(test)]
+mod tests;
+
+use crate::storage::{
+    s3::{
+        checksum::{Checksum, VerifyingReader},
+        client::{Credentials, S3Client},
+        config::S3Config,
+        upload::S3Upload,
+    },
+    BackupHandle, BackupHandleRef, BackupStorage, FileHandle, FileHandleRef, ShellSafeName,
+    TextLine,
+};
+use anyhow::Result;
+use aptos_logger::warn;
+use async_trait::async_trait;
+use bytes::Bytes;
+use clap::Parser;
+use futures::TryStreamExt;
+use std::{path::PathBuf, sync::Arc};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::io::StreamReader;
+
+#[derive(Parser)]
+pub struct S3Opt {
+    #[clap(
+        long = "config",
+        help = "Config file for the S3 backup store. Credentials are read from the \
+        AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY and optional AWS_SESSION_TOKEN env vars."
+    )]
+    config: PathBuf,
+}
+
+/// A BackupStorage that talks to S3, or any store compatible with its API like MinIO, directly
+/// over HTTP. Files larger than the configured part size are uploaded in parts, failed requests
+/// are retried, and the SHA-256 of every request body is sent along for the store to verify.
+/// Files are uploaded with a checksum, which is verified when they're read back.
+/// see `S3Config`.
+pub struct S3 {
+    client: Arc<S3Client>,
+    prefix: String,
+    part_size: usize,
+}
+
+impl S3 {
+    const METADATA_DIR: &'static str = "metadata";
+
+    pub fn new(config: S3Config) -> Result<Self> {
+        Self::new_with_credentials(config, Credentials::from_env()?)
+    }
+
+    pub async fn new_with_opt(opt: S3Opt) -> Result<Self> {
+        let config = S3Config::load_from_file(&opt.config).await?;
+
+        Self::new(config)
+    }
+
+    fn new_with_credentials(config: S3Config, credentials: Credentials) -> Result<Self> {
+        Ok(Self {
+            client: Arc::new(S3Client::new(&config, credentials)?),
+            prefix: config.prefix,
+            part_size: config.part_size_mb * 1024 * 1024,
+        })
+    }
+
+    fn key(&self, file_handle: &FileHandleRef) -> String {
+        format!("{}{}", self.prefix, file_handle)
+    }
+
+    fn metadata_prefix(&self) -> String {
+        self.key(&format!("{}/", Self::METADATA_DIR))
+    }
+}
+
+#[async_trait]
+impl BackupStorage for S3 {
+    async fn create_backup(&self, name: &ShellSafeName) -> Result<BackupHandle> {
+        // There are no directories to create, the backup name just becomes part of the keys.
+        Ok(name.to_string())
+    }
+
+    async fn create_for_write(
+        &self,
+        backup_handle: &BackupHandleRef,
+        name: &ShellSafeName,
+    ) -> Result<(FileHandle, Box<dyn AsyncWrite + Send + Unpin>)> {
+        let file_handle = format!("{}/{}", backup_handle, name.as_ref());
+        let upload = S3Upload::new(self.client.clone(), self.key(&file_handle), self.part_size);
+        Ok((file_handle, Box::new(upload)))
+    }
+
+    async fn open_for_read(
+        &self,
+        file_handle: &FileHandleRef,
+    ) -> Result<Box<dyn AsyncRead + Send + Unpin>> {
+        let key = self.key(file_handle);
+        let response = self.client.get_object(&key).await?;
+        let checksum = Checksum::from_headers(response.headers())?;
+        let stream = response
+            .bytes_stream()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e));
+        let reader = StreamReader::new(stream);
+        Ok(match checksum {
+            Some(checksum) => Box::new(VerifyingReader::new(reader, key, checksum)),
+            None => {
+                warn!(
+                    key = key,
+                    "S3 object has no checksum, can't verify its content."
+                );
+                Box::new(reader)
+            }
+        })
+    }
+
+    async fn save_metadata_line(&self, name: &ShellSafeName, content: &TextLine) -> Result<()> {
+        let key = format!("{}{}", self.metadata_prefix(), name.as_ref());
+        self.client
+            .put_object(&key, Bytes::copy_from_slice(content.as_ref().as_bytes()))
+            .await
+    }
+
+    async fn list_metadata_files(&self) -> Result<Vec<FileHandle>> {
+        Ok(self
+            .client
+            .list_objects(&self.metadata_prefix())
+            .await?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&self.prefix).map(str::to_string))
+            .collect())
+    }
+}
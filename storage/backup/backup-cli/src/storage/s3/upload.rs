@@ -0,0 +1,157 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage::s3::client::S3Client;
+use anyhow::{anyhow, Result};
+use aptos_logger::prelude::*;
+use futures::{
+    task::{Context, Poll},
+    FutureExt,
+};
+use std::{pin::Pin, sync::Arc};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, DuplexStream},
+    sync::oneshot,
+    task::JoinHandle,
+};
+
+/// Size of the in memory pipe between the writer and the upload task.
+const PIPE_SIZE: usize = 1024 * 1024;
+
+/// An `AsyncWrite` that uploads what's written to an S3 object, in parts if it's larger than
+/// `part_size`. The object only becomes visible once the writer is shut down successfully, if
+/// the writer is dropped before that the upload is abandoned.
+pub(super) struct S3Upload {
+    pipe: DuplexStream,
+    commit_tx: Option<oneshot::Sender<()>>,
+    upload: Option<JoinHandle<Result<()>>>,
+}
+
+impl S3Upload {
+    pub fn new(client: Arc<S3Client>, key: String, part_size: usize) -> Self {
+        let (pipe, reader) = tokio::io::duplex(PIPE_SIZE);
+        let (commit_tx, commit_rx) = oneshot::channel();
+        let upload = tokio::spawn(async move {
+            let res = upload(&client, &key, part_size, reader, commit_rx).await;
+            if let Err(err) = &res {
+                error!(key = key, error = ?err, "S3 upload failed.");
+            }
+            res
+        });
+
+        Self {
+            pipe,
+            commit_tx: Some(commit_tx),
+            upload: Some(upload),
+        }
+    }
+}
+
+impl AsyncWrite for S3Upload {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.pipe).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.pipe).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        futures::ready!(Pin::new(&mut self.pipe).poll_shutdown(cx))?;
+        if let Some(commit_tx) = self.commit_tx.take() {
+            // The upload task only goes away early on error, which is reported below.
+            let _ = commit_tx.send(());
+        }
+        let upload = match self.upload.as_mut() {
+            Some(upload) => upload,
+            None => return Poll::Ready(Ok(())),
+        };
+        let res = futures::ready!(upload.poll_unpin(cx));
+        self.upload = None;
+        Poll::Ready(
+            res.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+        )
+    }
+}
+
+async fn upload(
+    client: &S3Client,
+    key: &str,
+    part_size: usize,
+    mut reader: impl AsyncRead + Unpin,
+    commit_rx: oneshot::Receiver<()>,
+) -> Result<()> {
+    let first_part = read_part(&mut reader, part_size).await?;
+    if first_part.len() < part_size {
+        // Small enough for a single request.
+        wait_for_commit(commit_rx, key).await?;
+        return client.put_object(key, first_part.into()).await;
+    }
+
+    let upload_id = client.create_multipart_upload(key, part_size).await?;
+    let res = upload_parts(
+        client, key, &upload_id, part_size, first_part, reader, commit_rx,
+    )
+    .await;
+    if res.is_err() {
+        // Otherwise the uploaded parts are kept, and charged for, until the upload is completed
+        // or aborted.
+        if let Err(err) = client.abort_multipart_upload(key, &upload_id).await {
+            warn!(
+                key = key,
+                upload_id = upload_id,
+                error = ?err,
+                "Failed to abort S3 multipart upload."
+            );
+        }
+    }
+    res
+}
+
+async fn upload_parts(
+    client: &S3Client,
+    key: &str,
+    upload_id: &str,
+    part_size: usize,
+    first_part: Vec<u8>,
+    mut reader: impl AsyncRead + Unpin,
+    commit_rx: oneshot::Receiver<()>,
+) -> Result<()> {
+    let mut parts = Vec::new();
+    let mut part = first_part;
+    while !part.is_empty() {
+        parts.push(
+            client
+                .upload_part(key, upload_id, parts.len() + 1, part.into())
+                .await?,
+        );
+        part = read_part(&mut reader, part_size).await?;
+    }
+    wait_for_commit(commit_rx, key).await?;
+    client
+        .complete_multipart_upload(key, upload_id, &parts)
+        .await
+}
+
+/// Reads until `part_size` bytes are read or the end of the stream is reached.
+async fn read_part(reader: &mut (impl AsyncRead + Unpin), part_size: usize) -> Result<Vec<u8>> {
+    let mut part = Vec::with_capacity(part_size);
+    reader.take(part_size as u64).read_to_end(&mut part).await?;
+    Ok(part)
+}
+
+/// The writer reaching the end of the stream doesn't mean the content is complete, it can also
+/// be dropped half way.
+async fn wait_for_commit(commit_rx: oneshot::Receiver<()>, key: &str) -> Result<()> {
+    commit_rx.await.map_err(|_| {
+        anyhow!(
+            "Writer dropped before shutdown, abandoning upload of {}.",
+            key
+        )
+    })
+}
@@ -77,6 +77,15 @@ impl BackupServiceClient {
         self.get(&format!("state_snapshot/{}", version)).await
     }
 
+    pub async fn get_state_value_changes(
+        &self,
+        base_version: Version,
+        version: Version,
+    ) -> Result<impl AsyncRead> {
+        self.get(&format!("state_value_changes/{}/{}", base_version, version))
+            .await
+    }
+
     pub async fn get_state_root_proof(&self, version: Version) -> Result<Vec<u8>> {
         let mut buf = Vec::new();
         self.get(&format!("state_root_proof/{}", version))
@@ -202,6 +202,31 @@ impl RestoreRunMode {
         }
     }
 
+    pub fn save_state_snapshot_delta(
+        &self,
+        base_version: Version,
+        version: Version,
+        expected_root_hash: HashValue,
+        changes: &HashMap<StateKey, Option<StateValue>>,
+    ) -> Result<()> {
+        match self {
+            Self::Restore { restore_handler } => restore_handler.save_state_snapshot_delta(
+                base_version,
+                version,
+                expected_root_hash,
+                changes,
+            ),
+            Self::Verify => {
+                info!(
+                    "This is a dry run. The state snapshot delta to version {} is not applied, \
+                    the resulting root hash is not verified.",
+                    version,
+                );
+                Ok(())
+            }
+        }
+    }
+
     pub fn finish(&self) {
         match self {
             Self::Restore { restore_handler } => {
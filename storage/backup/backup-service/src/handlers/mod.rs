@@ -15,6 +15,7 @@ use warp::{filters::BoxedFilter, reply::Reply, Filter};
 static DB_STATE: &str = "db_state";
 static STATE_RANGE_PROOF: &str = "state_range_proof";
 static STATE_SNAPSHOT: &str = "state_snapshot";
+static STATE_VALUE_CHANGES: &str = "state_value_changes";
 static STATE_ROOT_PROOF: &str = "state_root_proof";
 static EPOCH_ENDING_LEDGER_INFOS: &str = "epoch_ending_ledger_infos";
 static TRANSACTIONS: &str = "transactions";
@@ -50,6 +51,22 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         })
         .recover(handle_rejection);
 
+    // GET state_value_changes/<base_version>/<version>
+    let bh = backup_handler.clone();
+    let state_value_changes = warp::path!(Version / Version)
+        .map(move |base_version, version| {
+            // use async move block to group `bh` and the iterator into the same lifetime, since the
+            // latter references the former.
+            reply_with_async_channel_writer(&bh, STATE_VALUE_CHANGES, |bh, sender| async move {
+                send_size_prefixed_bcs_bytes(
+                    bh.get_state_value_changes_iter(base_version, version),
+                    sender,
+                )
+                .await
+            })
+        })
+        .recover(handle_rejection);
+
     // GET state_root_proof/<version>
     let bh = backup_handler.clone();
     let state_root_proof = warp::path!(Version)
@@ -112,6 +129,7 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .and(warp::path(DB_STATE).and(db_state))
         .or(warp::path(STATE_RANGE_PROOF).and(state_range_proof))
         .or(warp::path(STATE_SNAPSHOT).and(state_snapshot))
+        .or(warp::path(STATE_VALUE_CHANGES).and(state_value_changes))
         .or(warp::path(STATE_ROOT_PROOF).and(state_root_proof))
         .or(warp::path(EPOCH_ENDING_LEDGER_INFOS).and(epoch_ending_ledger_infos))
         .or(warp::path(TRANSACTIONS).and(transactions))
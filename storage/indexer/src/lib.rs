@@ -4,12 +4,19 @@
 mod db;
 mod metadata;
 mod schema;
+#[cfg(test)]
+mod test;
+
+pub use crate::db::INDEX_DB_NAME;
 
 use crate::{
-    db::INDEX_DB_NAME,
     metadata::{MetadataKey, MetadataValue},
     schema::{
-        column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        column_families, event_by_type::EventByTypeSchema, indexer_metadata::IndexerMetadataSchema,
+        table_info::TableInfoSchema, transaction_by_function::TransactionByFunctionSchema,
+        transaction_by_function_and_sender::TransactionByFunctionAndSenderSchema,
+        EVENT_BY_TYPE_CF_NAME, INDEXER_METADATA_CF_NAME, TABLE_INFO_CF_NAME,
+        TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME, TRANSACTION_BY_FUNCTION_CF_NAME,
     },
 };
 use anyhow::{bail, ensure, Result};
@@ -19,27 +26,43 @@ use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_types::{
     access_path::Path,
     account_address::AccountAddress,
+    contract_event::ContractEvent,
     state_store::{
         state_key::StateKey,
         table::{TableHandle, TableInfo},
     },
-    transaction::{AtomicVersion, Version},
+    transaction::{
+        AtomicVersion, ExecutionStatus, MultisigTransactionPayload, Transaction,
+        TransactionPayload, Version,
+    },
     write_set::{WriteOp, WriteSet},
 };
 use aptos_vm::data_cache::{AsMoveResolver, StorageAdapter};
 use move_core_types::{
     identifier::IdentStr,
-    language_storage::{StructTag, TypeTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
 };
 use move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
-use schemadb::{SchemaBatch, DB};
+use schemadb::{schema::Schema, ReadOptions, SchemaBatch, DB};
 use std::{
     collections::HashMap,
     convert::TryInto,
+    io::Write,
     sync::{atomic::Ordering, Arc},
 };
 use storage_interface::{state_view::DbStateView, DbReader};
 
+/// Number of keys deleted in one write batch when truncating.
+const MAX_DELETIONS_PER_BATCH: usize = 100_000;
+
+/// The parts of a committed transaction that are indexed.
+pub struct TransactionToIndex<'a> {
+    pub transaction: &'a Transaction,
+    pub status: &'a ExecutionStatus,
+    pub write_set: &'a WriteSet,
+    pub events: &'a [ContractEvent],
+}
+
 #[derive(Debug)]
 pub struct Indexer {
     db: DB,
@@ -64,6 +87,15 @@ impl Indexer {
             .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
             .map_or(0, |v| v.expect_version());
 
+        // Indices added after the db was created only cover the versions indexed from then on.
+        let mut batch = SchemaBatch::new();
+        for key in &MetadataKey::START_VERSION_KEYS {
+            if db.get::<IndexerMetadataSchema>(key)?.is_none() {
+                batch.put::<IndexerMetadataSchema>(key, &MetadataValue::Version(next_version))?;
+            }
+        }
+        db.write_schemas(batch)?;
+
         Ok(Self {
             db,
             next_version: AtomicVersion::new(next_version),
@@ -74,23 +106,23 @@ impl Indexer {
         &self,
         db_reader: Arc<dyn DbReader>,
         first_version: Version,
-        write_sets: &[&WriteSet],
+        txns: &[TransactionToIndex],
     ) -> Result<()> {
-        let last_version = first_version + write_sets.len() as Version;
+        let last_version = first_version + txns.len() as Version;
         let state_view = DbStateView {
             db: db_reader,
             version: Some(last_version),
         };
         let resolver = state_view.as_move_resolver();
         let annotator = MoveValueAnnotator::new(&resolver);
-        self.index_with_annotator(&annotator, first_version, write_sets)
+        self.index_with_annotator(&annotator, first_version, txns)
     }
 
     pub fn index_with_annotator(
         &self,
         annotator: &MoveValueAnnotator<StorageAdapter<DbStateView>>,
         first_version: Version,
-        write_sets: &[&WriteSet],
+        txns: &[TransactionToIndex],
     ) -> Result<()> {
         let next_version = self.next_version();
         ensure!(
@@ -99,26 +131,51 @@ impl Indexer {
             next_version,
             first_version,
         );
-        let end_version = first_version + write_sets.len() as Version;
+        let end_version = first_version + txns.len() as Version;
         if end_version <= next_version {
             warn!(
                 "Seeing old transactions. Expecting version: {}, got {} transactions starting from version {}.",
                 next_version,
-                write_sets.len(),
+                txns.len(),
                 first_version,
             );
             return Ok(());
         }
 
         let mut table_info_parser = TableInfoParser::new(self, annotator);
-        for write_set in write_sets {
-            for (state_key, write_op) in write_set.iter() {
+        for txn in txns {
+            for (state_key, write_op) in txn.write_set.iter() {
                 table_info_parser.parse_write_op(state_key, write_op)?;
             }
         }
 
         let mut batch = SchemaBatch::new();
         table_info_parser.finish(&mut batch)?;
+        for (version, txn) in (first_version..).zip(txns) {
+            for (index, event) in txn.events.iter().enumerate() {
+                batch.put::<EventByTypeSchema>(
+                    &(event.type_tag().clone(), version, index as u64),
+                    &(),
+                )?;
+            }
+            if let Some((sender, module_id, function)) = entry_function_call(txn.transaction) {
+                let success = txn.status.is_success();
+                batch.put::<TransactionByFunctionSchema>(
+                    &(module_id.clone(), function.to_owned(), success, version),
+                    &(),
+                )?;
+                batch.put::<TransactionByFunctionAndSenderSchema>(
+                    &(
+                        module_id.clone(),
+                        function.to_owned(),
+                        sender,
+                        success,
+                        version,
+                    ),
+                    &(),
+                )?;
+            }
+        }
         batch.put::<IndexerMetadataSchema>(
             &MetadataKey::LatestVersion,
             &MetadataValue::Version(end_version - 1),
@@ -136,6 +193,292 @@ impl Indexer {
     pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.db.get::<TableInfoSchema>(&handle)
     }
+
+    /// Given `type_tag`, returns up to `limit` events of that type at or after the event at
+    /// index `start_index` of the transaction at `start_version`, identified by transaction
+    /// version and index among all events emitted by the same transaction. Result won't contain
+    /// records with a transaction version > `ledger_version` and is in ascending order. Fails if
+    /// `start_version` precedes the start of the index.
+    pub fn lookup_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<
+        Vec<(
+            Version, // transaction version it belongs to
+            u64,     // index among events for the same transaction
+        )>,
+    > {
+        self.ensure_indexed(
+            &MetadataKey::EventByTypeStartVersion,
+            "Events by type",
+            start_version,
+        )?;
+
+        let mut iter = self.db.iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag.clone(), start_version, start_index))?;
+
+        let mut result: Vec<(Version, u64)> = Vec::new();
+        for res in iter {
+            if result.len() as u64 >= limit {
+                break;
+            }
+            let ((tag, ver, idx), ()) = res?;
+            if tag != *type_tag || ver > ledger_version {
+                break;
+            }
+            result.push((ver, idx));
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the versions of user transactions calling the entry function `module_id::function`
+    /// at or after `start_version`, in ascending order. Results can be restricted to successful or
+    /// failed transactions and to a single sender. Result won't contain versions >
+    /// `ledger_version`. Fails if `start_version` precedes the start of the index.
+    pub fn lookup_transactions_by_function(
+        &self,
+        module_id: &ModuleId,
+        function: &IdentStr,
+        success: Option<bool>,
+        sender: Option<AccountAddress>,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        let start_version_key = match sender {
+            Some(_) => MetadataKey::TransactionByFunctionAndSenderStartVersion,
+            None => MetadataKey::TransactionByFunctionStartVersion,
+        };
+        self.ensure_indexed(
+            &start_version_key,
+            "Transactions by function",
+            start_version,
+        )?;
+
+        let statuses = match success {
+            Some(success) => vec![success],
+            None => vec![false, true],
+        };
+
+        // Successful and failed transactions are indexed separately, so take up to `limit` of
+        // each and merge them.
+        let mut result = Vec::new();
+        for success in statuses {
+            let versions = match sender {
+                Some(sender) => self.lookup_versions::<TransactionByFunctionAndSenderSchema>(
+                    &(
+                        module_id.clone(),
+                        function.to_owned(),
+                        sender,
+                        success,
+                        start_version,
+                    ),
+                    limit,
+                    ledger_version,
+                    |(module, func, txn_sender, status, ver)| {
+                        (module == module_id
+                            && func.as_ident_str() == function
+                            && *txn_sender == sender
+                            && *status == success)
+                            .then_some(*ver)
+                    },
+                )?,
+                None => self.lookup_versions::<TransactionByFunctionSchema>(
+                    &(
+                        module_id.clone(),
+                        function.to_owned(),
+                        success,
+                        start_version,
+                    ),
+                    limit,
+                    ledger_version,
+                    |(module, func, status, ver)| {
+                        (module == module_id
+                            && func.as_ident_str() == function
+                            && *status == success)
+                            .then_some(*ver)
+                    },
+                )?,
+            };
+            result.extend(versions);
+        }
+        result.sort_unstable();
+        result.truncate(limit as usize);
+
+        Ok(result)
+    }
+
+    /// Seeks `S` to `seek_key` and returns up to `limit` versions, as extracted by `version_of`,
+    /// until `version_of` returns `None` or a version > `ledger_version`.
+    fn lookup_versions<S: Schema>(
+        &self,
+        seek_key: &S::Key,
+        limit: u64,
+        ledger_version: Version,
+        version_of: impl Fn(&S::Key) -> Option<Version>,
+    ) -> Result<Vec<Version>> {
+        let mut iter = self.db.iter::<S>(ReadOptions::default())?;
+        iter.seek(seek_key)?;
+
+        let mut versions = Vec::new();
+        for res in iter {
+            if versions.len() as u64 >= limit {
+                break;
+            }
+            let (key, _value) = res?;
+            match version_of(&key) {
+                Some(version) if version <= ledger_version => versions.push(version),
+                _ => break,
+            }
+        }
+        Ok(versions)
+    }
+
+    /// Fails if the index whose start version is stored under `start_version_key` doesn't cover
+    /// `version`, because the index was added after that version had been committed.
+    fn ensure_indexed(
+        &self,
+        start_version_key: &MetadataKey,
+        index_name: &str,
+        version: Version,
+    ) -> Result<()> {
+        let index_start_version = self
+            .db
+            .get::<IndexerMetadataSchema>(start_version_key)?
+            .map_or(0, |v| v.expect_version());
+        ensure!(
+            version >= index_start_version,
+            "{} are only indexed from version {}, requested version {}.",
+            index_name,
+            index_start_version,
+            version,
+        );
+        Ok(())
+    }
+
+    /// Deletes everything indexed for versions after `target_version`, so that indexing resumes
+    /// from there. Table infos are kept, since they never change once created.
+    pub fn truncate(&self, target_version: Version) -> Result<()> {
+        let next_version = target_version + 1;
+        delete_after::<EventByTypeSchema>(
+            &self.db,
+            target_version,
+            |key| key.1,
+            |key| (key.0.clone(), next_version, 0),
+        )?;
+        delete_after::<TransactionByFunctionSchema>(
+            &self.db,
+            target_version,
+            |key| key.3,
+            |key| (key.0.clone(), key.1.clone(), key.2, next_version),
+        )?;
+        delete_after::<TransactionByFunctionAndSenderSchema>(
+            &self.db,
+            target_version,
+            |key| key.4,
+            |key| (key.0.clone(), key.1.clone(), key.2, key.3, next_version),
+        )?;
+
+        let mut batch = SchemaBatch::new();
+        // Versions after `target_version` get indexed again.
+        for key in &MetadataKey::START_VERSION_KEYS {
+            let start_version = self
+                .db
+                .get::<IndexerMetadataSchema>(key)?
+                .map(|v| v.expect_version());
+            if start_version.map_or(false, |v| v > next_version) {
+                batch.put::<IndexerMetadataSchema>(key, &MetadataValue::Version(next_version))?;
+            }
+        }
+
+        let latest_version = self
+            .db
+            .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
+            .map(|v| v.expect_version());
+        if latest_version.map_or(false, |v| v > target_version) {
+            batch.put::<IndexerMetadataSchema>(
+                &MetadataKey::LatestVersion,
+                &MetadataValue::Version(target_version),
+            )?;
+        }
+        self.db.write_schemas(batch)?;
+        self.next_version.fetch_min(next_version, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// Writes up to `limit` entries of the column family `cf_name` to `out` in human readable form
+    /// and returns the number of entries written.
+    pub fn dump(&self, cf_name: &str, limit: usize, out: &mut dyn Write) -> Result<usize> {
+        match cf_name {
+            EVENT_BY_TYPE_CF_NAME => self.db.dump::<EventByTypeSchema>(limit, out),
+            INDEXER_METADATA_CF_NAME => self.db.dump::<IndexerMetadataSchema>(limit, out),
+            TABLE_INFO_CF_NAME => self.db.dump::<TableInfoSchema>(limit, out),
+            TRANSACTION_BY_FUNCTION_CF_NAME => {
+                self.db.dump::<TransactionByFunctionSchema>(limit, out)
+            }
+            TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME => {
+                self.db
+                    .dump::<TransactionByFunctionAndSenderSchema>(limit, out)
+            }
+            _ => bail!("Unknown column family {} in the index db.", cf_name),
+        }
+    }
+}
+
+/// Deletes all entries of schema `S` indexed for versions after `target_version`. Entries are
+/// grouped by a key prefix and ordered by version within a group, so the entries of a group up to
+/// `target_version` are skipped by seeking to the first key of the group after it, as given by
+/// `first_truncated_key`.
+fn delete_after<S: Schema>(
+    db: &DB,
+    target_version: Version,
+    version_of: impl Fn(&S::Key) -> Version,
+    first_truncated_key: impl Fn(&S::Key) -> S::Key,
+) -> Result<usize> {
+    let mut iter = db.iter::<S>(ReadOptions::default())?;
+    iter.seek_to_first();
+    let mut batch = SchemaBatch::new();
+    let mut num_deleted = 0;
+    while let Some((key, _value)) = iter.next().transpose()? {
+        if version_of(&key) <= target_version {
+            iter.seek(&first_truncated_key(&key))?;
+            continue;
+        }
+        batch.delete::<S>(&key)?;
+        num_deleted += 1;
+        if num_deleted % MAX_DELETIONS_PER_BATCH == 0 {
+            db.write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+        }
+    }
+    db.write_schemas(batch)?;
+    Ok(num_deleted)
+}
+
+/// Returns the sender and the entry function called by a user transaction, if any.
+fn entry_function_call(txn: &Transaction) -> Option<(AccountAddress, &ModuleId, &IdentStr)> {
+    let signed_txn = match txn {
+        Transaction::UserTransaction(signed_txn) => signed_txn,
+        _ => return None,
+    };
+    let entry_function = match signed_txn.payload() {
+        TransactionPayload::EntryFunction(entry_function) => entry_function,
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => entry_function,
+            None => return None,
+        },
+        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => return None,
+    };
+    Some((
+        signed_txn.sender(),
+        entry_function.module(),
+        entry_function.function(),
+    ))
 }
 
 struct TableInfoParser<'a> {
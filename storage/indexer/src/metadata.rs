@@ -22,4 +22,20 @@ impl MetadataValue {
 #[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
 pub(crate) enum MetadataKey {
     LatestVersion,
+    /// The first version indexed by `TransactionByFunctionSchema`.
+    TransactionByFunctionStartVersion,
+    /// The first version indexed by `TransactionByFunctionAndSenderSchema`.
+    TransactionByFunctionAndSenderStartVersion,
+    /// The first version indexed by `EventByTypeSchema`.
+    EventByTypeStartVersion,
+}
+
+impl MetadataKey {
+    /// Keys of the start versions of the indices, which are recorded when an index is first
+    /// opened. Versions committed before that aren't indexed.
+    pub const START_VERSION_KEYS: [MetadataKey; 3] = [
+        MetadataKey::TransactionByFunctionStartVersion,
+        MetadataKey::TransactionByFunctionAndSenderStartVersion,
+        MetadataKey::EventByTypeStartVersion,
+    ];
 }
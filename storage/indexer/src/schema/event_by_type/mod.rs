@@ -0,0 +1,67 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an event index via which a ContractEvent (
+//! represented by a <txn_version, event_idx> tuple so that it can be fetched from the ledger DB)
+//! can be found by its type.
+//!
+//! ```text
+//! |<-------------key------------->|
+//! | type_tag | txn_version | idx  |
+//! ```
+//!
+//! `type_tag` is BCS encoded, which is prefix free, so all events of a type are stored adjacently
+//! in version order.
+
+use crate::schema::{ensure_slice_len_gt, EVENT_BY_TYPE_CF_NAME};
+use anyhow::Result;
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use move_core_types::language_storage::TypeTag;
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(EventByTypeSchema, Key, (), EVENT_BY_TYPE_CF_NAME);
+
+type Index = u64;
+type Key = (TypeTag, Version, Index);
+
+impl KeyCodec<EventByTypeSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref type_tag, version, index) = *self;
+
+        let mut encoded = bcs::to_bytes(type_tag)?;
+        encoded.write_u64::<BigEndian>(version)?;
+        encoded.write_u64::<BigEndian>(index)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const SUFFIX_SIZE: usize = size_of::<Version>() + size_of::<Index>();
+        ensure_slice_len_gt(data, SUFFIX_SIZE)?;
+
+        let type_tag_len = data.len() - SUFFIX_SIZE;
+        let type_tag = bcs::from_bytes(&data[..type_tag_len])?;
+        let version = (&data[type_tag_len..]).read_u64::<BigEndian>()?;
+        let index = (&data[type_tag_len + size_of::<Version>()..]).read_u64::<BigEndian>()?;
+
+        Ok((type_tag, version, index))
+    }
+}
+
+impl ValueCodec<EventByTypeSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(_data: &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,19 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        type_tag in any::<TypeTag>(),
+        version in any::<Version>(),
+        index in any::<u64>(),
+    ) {
+        assert_encode_decode::<EventByTypeSchema>(&(type_tag, version, index), &());
+    }
+}
+
+test_no_panic_decoding!(EventByTypeSchema);
@@ -6,19 +6,40 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod event_by_type;
 pub(crate) mod indexer_metadata;
 pub(crate) mod table_info;
+pub(crate) mod transaction_by_function;
+pub(crate) mod transaction_by_function_and_sender;
 
+use anyhow::{ensure, Result};
 use schemadb::ColumnFamilyName;
 
 pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
+pub const EVENT_BY_TYPE_CF_NAME: ColumnFamilyName = "event_by_type";
 pub const INDEXER_METADATA_CF_NAME: ColumnFamilyName = "indexer_metadata";
 pub const TABLE_INFO_CF_NAME: ColumnFamilyName = "table_info";
+pub const TRANSACTION_BY_FUNCTION_CF_NAME: ColumnFamilyName = "transaction_by_function";
+pub const TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME: ColumnFamilyName =
+    "transaction_by_function_and_sender";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         INDEXER_METADATA_CF_NAME,
         TABLE_INFO_CF_NAME,
+        TRANSACTION_BY_FUNCTION_CF_NAME,
+        TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME,
     ]
 }
+
+fn ensure_slice_len_gt(data: &[u8], len: usize) -> Result<()> {
+    ensure!(
+        data.len() > len,
+        "Unexpected data len {}, expected to be greater than {}.",
+        data.len(),
+        len,
+    );
+    Ok(())
+}
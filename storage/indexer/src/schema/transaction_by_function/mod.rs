@@ -0,0 +1,76 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for a transaction index via which the versions of
+//! user transactions calling an entry function can be found, separately for successful and failed
+//! transactions.
+//!
+//! ```text
+//! |<---------------------key------------------->|
+//! | module_id | function | success | txn_version |
+//! ```
+//!
+//! `module_id` and `function` are BCS encoded, which is prefix free, so all transactions calling a
+//! function with the same status are stored adjacently in version order.
+
+use crate::schema::{ensure_slice_len_gt, TRANSACTION_BY_FUNCTION_CF_NAME};
+use anyhow::{bail, Result};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(
+    TransactionByFunctionSchema,
+    Key,
+    (),
+    TRANSACTION_BY_FUNCTION_CF_NAME
+);
+
+type Success = bool;
+type Key = (ModuleId, Identifier, Success, Version);
+
+impl KeyCodec<TransactionByFunctionSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref module_id, ref function, success, version) = *self;
+
+        let mut encoded = bcs::to_bytes(&(module_id, function))?;
+        encoded.write_u8(success as u8)?;
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const SUFFIX_SIZE: usize = size_of::<u8>() + size_of::<Version>();
+        ensure_slice_len_gt(data, SUFFIX_SIZE)?;
+
+        let function_len = data.len() - SUFFIX_SIZE;
+        let (module_id, function) = bcs::from_bytes(&data[..function_len])?;
+        let success = match data[function_len] {
+            0 => false,
+            1 => true,
+            byte => bail!("Invalid success flag {}.", byte),
+        };
+        let version = (&data[function_len + size_of::<u8>()..]).read_u64::<BigEndian>()?;
+
+        Ok((module_id, function, success, version))
+    }
+}
+
+impl ValueCodec<TransactionByFunctionSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(_data: &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,23 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use proptest::prelude::*;
+use schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        module_id in any::<ModuleId>(),
+        function in any::<Identifier>(),
+        success in any::<bool>(),
+        version in any::<Version>(),
+    ) {
+        assert_encode_decode::<TransactionByFunctionSchema>(
+            &(module_id, function, success, version),
+            &(),
+        );
+    }
+}
+
+test_no_panic_decoding!(TransactionByFunctionSchema);
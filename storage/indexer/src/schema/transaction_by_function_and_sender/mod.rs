@@ -0,0 +1,79 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for a transaction index via which the versions of
+//! user transactions a sender sent to call an entry function can be found, separately for
+//! successful and failed transactions.
+//!
+//! ```text
+//! |<--------------------------key---------------------------->|
+//! | module_id | function | sender | success | txn_version |
+//! ```
+//!
+//! `module_id` and `function` are BCS encoded, which is prefix free, so all transactions calling a
+//! function from the same sender with the same status are stored adjacently in version order.
+
+use crate::schema::{ensure_slice_len_gt, TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME};
+use anyhow::{bail, Result};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use std::mem::size_of;
+
+define_schema!(
+    TransactionByFunctionAndSenderSchema,
+    Key,
+    (),
+    TRANSACTION_BY_FUNCTION_AND_SENDER_CF_NAME
+);
+
+type Success = bool;
+type Key = (ModuleId, Identifier, AccountAddress, Success, Version);
+
+impl KeyCodec<TransactionByFunctionAndSenderSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref module_id, ref function, sender, success, version) = *self;
+
+        let mut encoded = bcs::to_bytes(&(module_id, function))?;
+        encoded.extend_from_slice(sender.as_ref());
+        encoded.write_u8(success as u8)?;
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const SUFFIX_SIZE: usize = AccountAddress::LENGTH + size_of::<u8>() + size_of::<Version>();
+        ensure_slice_len_gt(data, SUFFIX_SIZE)?;
+
+        let function_len = data.len() - SUFFIX_SIZE;
+        let (module_id, function) = bcs::from_bytes(&data[..function_len])?;
+        let sender_end = function_len + AccountAddress::LENGTH;
+        let sender = AccountAddress::try_from(&data[function_len..sender_end])?;
+        let success = match data[sender_end] {
+            0 => false,
+            1 => true,
+            byte => bail!("Invalid success flag {}.", byte),
+        };
+        let version = (&data[sender_end + size_of::<u8>()..]).read_u64::<BigEndian>()?;
+
+        Ok((module_id, function, sender, success, version))
+    }
+}
+
+impl ValueCodec<TransactionByFunctionAndSenderSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(_data: &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
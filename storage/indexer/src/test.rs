@@ -0,0 +1,123 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_temppath::TempPath;
+use move_core_types::ident_str;
+
+fn lookup_transfers(
+    indexer: &Indexer,
+    sender: Option<AccountAddress>,
+    start_version: Version,
+) -> Result<()> {
+    indexer
+        .lookup_transactions_by_function(
+            &ModuleId::new(AccountAddress::ONE, ident_str!("coin").to_owned()),
+            ident_str!("transfer"),
+            None, /* success */
+            sender,
+            start_version,
+            10,  /* limit */
+            100, /* ledger_version */
+        )
+        .map(|versions| assert!(versions.is_empty()))
+}
+
+fn deposit_event_type() -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: AccountAddress::ONE,
+        module: ident_str!("coin").to_owned(),
+        name: ident_str!("DepositEvent").to_owned(),
+        type_params: vec![],
+    }))
+}
+
+fn lookup_deposits(indexer: &Indexer, start_version: Version) -> Result<()> {
+    indexer
+        .lookup_events_by_type(
+            &deposit_event_type(),
+            start_version,
+            0,   /* start_index */
+            10,  /* limit */
+            100, /* ledger_version */
+        )
+        .map(|events| assert!(events.is_empty()))
+}
+
+#[test]
+fn test_index_start_version() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let indexer = Indexer::open(&tmp_dir, RocksdbConfig::default()).unwrap();
+    lookup_transfers(&indexer, None, 0).unwrap();
+    lookup_transfers(&indexer, Some(AccountAddress::ONE), 0).unwrap();
+    lookup_deposits(&indexer, 0).unwrap();
+
+    // Make it look like a db indexed up to version 10 before the indices existed.
+    let mut batch = SchemaBatch::new();
+    for key in &MetadataKey::START_VERSION_KEYS {
+        batch.delete::<IndexerMetadataSchema>(key).unwrap();
+    }
+    batch
+        .put::<IndexerMetadataSchema>(&MetadataKey::LatestVersion, &MetadataValue::Version(10))
+        .unwrap();
+    indexer.db.write_schemas(batch).unwrap();
+    drop(indexer);
+
+    let indexer = Indexer::open(&tmp_dir, RocksdbConfig::default()).unwrap();
+    assert!(lookup_transfers(&indexer, None, 9).is_err());
+    assert!(lookup_transfers(&indexer, Some(AccountAddress::ONE), 9).is_err());
+    assert!(lookup_deposits(&indexer, 9).is_err());
+    lookup_transfers(&indexer, None, 10).unwrap();
+    lookup_transfers(&indexer, Some(AccountAddress::ONE), 10).unwrap();
+    lookup_deposits(&indexer, 10).unwrap();
+
+    // Truncated versions get indexed again.
+    indexer.truncate(5).unwrap();
+    lookup_transfers(&indexer, None, 6).unwrap();
+    lookup_transfers(&indexer, Some(AccountAddress::ONE), 6).unwrap();
+    lookup_deposits(&indexer, 6).unwrap();
+    assert!(lookup_transfers(&indexer, None, 5).is_err());
+}
+
+#[test]
+fn test_lookup_events_by_type_limit() {
+    let tmp_dir = TempPath::new();
+    tmp_dir.create_as_dir().unwrap();
+    let indexer = Indexer::open(&tmp_dir, RocksdbConfig::default()).unwrap();
+    let type_tag = deposit_event_type();
+
+    let mut batch = SchemaBatch::new();
+    for key in [(5, 0), (5, 1), (5, 2), (6, 0), (8, 3)] {
+        batch
+            .put::<EventByTypeSchema>(&(type_tag.clone(), key.0, key.1), &())
+            .unwrap();
+    }
+    indexer.db.write_schemas(batch).unwrap();
+
+    // Events of a transaction are split across pages.
+    assert_eq!(
+        indexer
+            .lookup_events_by_type(&type_tag, 0, 0, 2, 8)
+            .unwrap(),
+        vec![(5, 0), (5, 1)]
+    );
+    assert_eq!(
+        indexer
+            .lookup_events_by_type(&type_tag, 5, 2, 2, 8)
+            .unwrap(),
+        vec![(5, 2), (6, 0)]
+    );
+    assert_eq!(
+        indexer
+            .lookup_events_by_type(&type_tag, 6, 1, 2, 8)
+            .unwrap(),
+        vec![(8, 3)]
+    );
+    assert_eq!(
+        indexer
+            .lookup_events_by_type(&type_tag, 6, 1, 2, 7)
+            .unwrap(),
+        vec![]
+    );
+}
@@ -30,7 +30,7 @@ use crate::{
 use anyhow::{format_err, Result};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
-use std::{collections::HashMap, iter::Iterator, path::Path};
+use std::{collections::HashMap, io::Write, iter::Iterator, path::Path};
 
 use iterator::{ScanDirection, SchemaIterator};
 /// Type alias to `rocksdb::ReadOptions`. See [`rocksdb doc`](https://github.com/pingcap/rust-rocksdb/blob/master/src/rocksdb_options.rs)
@@ -219,6 +219,20 @@ impl DB {
         self.iter_with_direction::<S>(opts, ScanDirection::Backward)
     }
 
+    /// Writes up to `limit` entries of a certain schema to `out` in human readable form, in key
+    /// order, and returns the number of entries written.
+    pub fn dump<S: Schema>(&self, limit: usize, out: &mut dyn Write) -> Result<usize> {
+        let mut iter = self.iter::<S>(ReadOptions::default())?;
+        iter.seek_to_first();
+        let mut num_entries = 0;
+        for item in iter.take(limit) {
+            let (key, value) = item?;
+            writeln!(out, "{:?} => {:?}", key, value)?;
+            num_entries += 1;
+        }
+        Ok(num_entries)
+    }
+
     /// Writes a group of records wrapped in a [`SchemaBatch`].
     pub fn write_schemas(&self, batch: SchemaBatch) -> Result<()> {
         let _timer = APTOS_SCHEMADB_BATCH_COMMIT_LATENCY_SECONDS
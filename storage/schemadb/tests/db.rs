@@ -188,6 +188,29 @@ fn gen_expected_values(values: &[(u32, u32)]) -> Vec<(TestField, TestField)> {
         .collect()
 }
 
+#[test]
+fn test_dump() {
+    let db = TestDB::new();
+
+    db.put::<TestSchema1>(&TestField(1), &TestField(2)).unwrap();
+    db.put::<TestSchema1>(&TestField(0), &TestField(1)).unwrap();
+    db.put::<TestSchema2>(&TestField(3), &TestField(4)).unwrap();
+
+    let mut dumped = Vec::new();
+    assert_eq!(db.dump::<TestSchema1>(usize::MAX, &mut dumped).unwrap(), 2);
+    assert_eq!(
+        String::from_utf8(dumped).unwrap(),
+        "TestField(0) => TestField(1)\nTestField(1) => TestField(2)\n"
+    );
+
+    let mut dumped = Vec::new();
+    assert_eq!(db.dump::<TestSchema1>(1, &mut dumped).unwrap(), 1);
+    assert_eq!(
+        String::from_utf8(dumped).unwrap(),
+        "TestField(0) => TestField(1)\n"
+    );
+}
+
 #[test]
 fn test_single_schema_batch() {
     let db = TestDB::new();
@@ -32,6 +32,10 @@ use aptos_types::{
         TransactionOutputListWithProof, TransactionToCommit, TransactionWithProof, Version,
     },
 };
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
@@ -206,6 +210,38 @@ pub trait DbReader: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns up to `limit` events of the given type, starting at the event at index
+    /// `start_index` among the events emitted by the transaction at `start_version`, in order.
+    /// Each event comes with its index among the events of its transaction. Requires the internal
+    /// indexer, and fails for versions committed before the indexer started indexing them.
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        unimplemented!()
+    }
+
+    /// Returns the versions of user transactions calling the entry function `module_id::function`
+    /// at or after `start_version`, in ascending order, optionally only successful or failed ones
+    /// and only ones sent by `sender`. Requires the internal indexer, and fails for versions
+    /// committed before the indexer started indexing them.
+    fn get_transaction_versions_by_function(
+        &self,
+        module_id: &ModuleId,
+        function: &IdentStr,
+        success: Option<bool>,
+        sender: Option<AccountAddress>,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        unimplemented!()
+    }
+
     /// See [AptosDB::get_block_timestamp].
     ///
     /// [AptosDB::get_block_timestamp]:
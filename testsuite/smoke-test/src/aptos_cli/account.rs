@@ -18,6 +18,10 @@ async fn test_account_flow() {
         .await;
     cli.assert_account_balance_now(1, DEFAULT_FUNDED_COINS)
         .await;
+    assert_eq!(
+        cli.account_balance(0, None).await.unwrap().balance,
+        DEFAULT_FUNDED_COINS
+    );
 
     let transfer_amount = 100;
     let response = cli
@@ -135,6 +135,16 @@ async fn test_move_publish_flow() {
         .await
         .is_ok());
 
+    // The message can be read back through a view function
+    let function_id = MemberId::from_str(&format!("{}::message::get_message", account)).unwrap();
+    let account_arg = format!("address:{}", account);
+    assert_eq!(
+        cli.view_function(function_id, vec![account_arg.as_str()], vec![])
+            .await
+            .unwrap(),
+        vec![serde_json::Value::String("hello_world".to_string())]
+    );
+
     // Now download the package. It will be stored in a directory PACKAGE_NAME inside move_dir.
     let _ = match cli
         .download_package(0, PACKAGE_NAME.to_owned(), cli.move_dir())
@@ -0,0 +1,89 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    smoke_test_environment::SwarmBuilder,
+    test_utils::{assert_balance, create_and_fund_account, transfer_and_reconfig, transfer_coins},
+};
+use aptos_config::config::NodeConfig;
+use forge::{NodeExt, Swarm, SwarmExt};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+const MAX_CATCH_UP_SECS: u64 = 120;
+
+#[tokio::test]
+async fn test_vfn_observes_validator() {
+    // Create a validator publishing its ordered blocks and commit decisions
+    let mut swarm = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .with_init_config(Arc::new(|_, config, _| {
+            config.consensus.observer.publisher_enabled = true;
+        }))
+        .build()
+        .await;
+
+    // Create a VFN observing the validator
+    let validator_peer_id = swarm.validators().next().unwrap().peer_id();
+    let mut vfn_config = NodeConfig::default_for_validator_full_node();
+    vfn_config.consensus.observer.observer_enabled = true;
+    let vfn_peer_id = swarm
+        .add_validator_fullnode(
+            &swarm.versions().max().unwrap(),
+            vfn_config,
+            validator_peer_id,
+        )
+        .unwrap();
+    let vfn = swarm.full_node_mut(vfn_peer_id).unwrap();
+    vfn.wait_until_healthy(Instant::now() + Duration::from_secs(MAX_CATCH_UP_SECS))
+        .await
+        .unwrap();
+
+    // Execute transactions (and epoch changes) on the validator
+    let validator_client = swarm.validator(validator_peer_id).unwrap().rest_client();
+    let vfn_client = swarm.full_node(vfn_peer_id).unwrap().rest_client();
+    let transaction_factory = swarm.chain_info().transaction_factory();
+    let mut account_0 = create_and_fund_account(&mut swarm, 100).await;
+    let mut account_1 = create_and_fund_account(&mut swarm, 100).await;
+    transfer_and_reconfig(
+        &validator_client,
+        &transaction_factory,
+        swarm.chain_info().root_account,
+        &mut account_0,
+        &account_1,
+        5,
+    )
+    .await;
+    transfer_coins(
+        &validator_client,
+        &transaction_factory,
+        &mut account_1,
+        &account_0,
+        10,
+    )
+    .await;
+
+    // Verify the VFN catches up and executes the blocks of the validator itself
+    swarm
+        .wait_for_all_nodes_to_catchup(Duration::from_secs(MAX_CATCH_UP_SECS))
+        .await
+        .unwrap();
+    assert_balance(&vfn_client, &account_0, 105).await;
+    assert_balance(&vfn_client, &account_1, 95).await;
+    let vfn = swarm.full_node(vfn_peer_id).unwrap();
+    assert_eq!(
+        vfn.get_metric_i64("aptos_consensus_observer_executing")
+            .await
+            .unwrap(),
+        Some(1)
+    );
+    assert!(
+        vfn.get_metric_i64("aptos_consensus_observer_state_sync_targets")
+            .await
+            .unwrap()
+            .unwrap_or_default()
+            > 0
+    );
+}
@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod consensus_fault_tolerance;
+mod consensus_observer;
 mod consensusdb_recovery;
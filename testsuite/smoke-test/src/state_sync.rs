@@ -139,6 +139,27 @@ async fn test_full_node_bootstrap_transactions() {
     test_full_node_sync(vfn_peer_id, &mut swarm, true).await;
 }
 
+#[tokio::test]
+async fn test_full_node_bootstrap_transactions_or_outputs() {
+    // Create a validator swarm of 1 validator node
+    let mut swarm = new_local_swarm_with_aptos(1).await;
+
+    // Create a fullnode config that uses transactions or outputs to sync
+    let mut vfn_config = NodeConfig::default_for_validator_full_node();
+    vfn_config.state_sync.state_sync_driver.bootstrapping_mode =
+        BootstrappingMode::ExecuteOrApplyFromGenesis;
+    vfn_config
+        .state_sync
+        .state_sync_driver
+        .continuous_syncing_mode = ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs;
+
+    // Create the fullnode
+    let vfn_peer_id = create_full_node(vfn_config, &mut swarm).await;
+
+    // Test the ability of the fullnode to sync
+    test_full_node_sync(vfn_peer_id, &mut swarm, true).await;
+}
+
 #[tokio::test]
 async fn test_full_node_continuous_sync_outputs() {
     // Create a validator swarm of 1 validator node
@@ -177,6 +198,25 @@ async fn test_full_node_continuous_sync_transactions() {
     test_full_node_sync(vfn_peer_id, &mut swarm, false).await;
 }
 
+#[tokio::test]
+async fn test_full_node_continuous_sync_transactions_or_outputs() {
+    // Create a validator swarm of 1 validator node
+    let mut swarm = new_local_swarm_with_aptos(1).await;
+
+    // Create a fullnode config that uses transactions or outputs to sync
+    let mut vfn_config = NodeConfig::default_for_validator_full_node();
+    vfn_config
+        .state_sync
+        .state_sync_driver
+        .continuous_syncing_mode = ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs;
+
+    // Create the fullnode
+    let vfn_peer_id = create_full_node(vfn_config, &mut swarm).await;
+
+    // Test the ability of the fullnode to sync
+    test_full_node_sync(vfn_peer_id, &mut swarm, false).await;
+}
+
 /// Creates a new full node using the given config and swarm
 async fn create_full_node(full_node_config: NodeConfig, swarm: &mut LocalSwarm) -> PeerId {
     let validator_peer_id = swarm.validators().next().unwrap().peer_id();
@@ -416,6 +456,26 @@ async fn test_validator_bootstrap_transactions() {
     test_validator_sync(&mut swarm, 1).await;
 }
 
+#[tokio::test]
+async fn test_validator_bootstrap_transactions_or_outputs_network_limit() {
+    // Create a swarm of 4 validators using transaction or output syncing and a network limit
+    // that forces the storage service to fall back to transactions for large chunks.
+    let mut swarm = SwarmBuilder::new_local(4)
+        .with_aptos()
+        .with_init_config(Arc::new(|_, config, _| {
+            config.state_sync.state_sync_driver.bootstrapping_mode =
+                BootstrappingMode::ExecuteOrApplyFromGenesis;
+            config.state_sync.state_sync_driver.continuous_syncing_mode =
+                ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs;
+            config.state_sync.storage_service.max_network_chunk_bytes = 10 * 1024;
+        }))
+        .build()
+        .await;
+
+    // Test the ability of the validators to sync
+    test_validator_sync(&mut swarm, 1).await;
+}
+
 #[ignore] // We ignore this test because it takes a long time. But, it works, so it shouldn't be removed.
 #[tokio::test]
 async fn test_validator_bootstrap_transactions_network_limit() {
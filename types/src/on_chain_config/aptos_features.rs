@@ -10,6 +10,8 @@ use serde::{Deserialize, Serialize};
 pub enum FeatureFlag {
     CODE_DEPENDENCY_CHECK = 1,
     TREAT_FRIEND_AS_PRIVATE = 2,
+    MULTISIG_ACCOUNTS = 5,
+    FEE_PAYER = 6,
 }
 
 /// Representation of features on chain as a bitset.
@@ -215,6 +215,46 @@ pub fn get_test_unchecked_multi_agent_txn(
     )
 }
 
+pub fn get_test_unchecked_fee_payer_txn(
+    sender: AccountAddress,
+    sequence_number: u64,
+    sender_private_key: &Ed25519PrivateKey,
+    sender_public_key: Ed25519PublicKey,
+    fee_payer_address: AccountAddress,
+    fee_payer_private_key: &Ed25519PrivateKey,
+    fee_payer_public_key: Ed25519PublicKey,
+    script: Option<Script>,
+) -> SignedTransaction {
+    let expiration_time = expiration_time(10);
+    let raw_txn = RawTransaction::new(
+        sender,
+        sequence_number,
+        TransactionPayload::Script(
+            script.unwrap_or_else(|| Script::new(EMPTY_SCRIPT.to_vec(), vec![], Vec::new())),
+        ),
+        MAX_GAS_AMOUNT,
+        TEST_GAS_PRICE,
+        expiration_time,
+        ChainId::test(),
+    );
+    let message = RawTransactionWithData::new_fee_payer(raw_txn.clone(), vec![], fee_payer_address);
+
+    let sender_signature = sender_private_key.sign(&message).unwrap();
+    let sender_authenticator = AccountAuthenticator::ed25519(sender_public_key, sender_signature);
+    let fee_payer_signature = fee_payer_private_key.sign(&message).unwrap();
+    let fee_payer_authenticator =
+        AccountAuthenticator::ed25519(fee_payer_public_key, fee_payer_signature);
+
+    SignedTransaction::new_fee_payer(
+        raw_txn,
+        sender_authenticator,
+        vec![],
+        vec![],
+        fee_payer_address,
+        fee_payer_authenticator,
+    )
+}
+
 pub fn get_test_txn_with_chain_id(
     sender: AccountAddress,
     sequence_number: u64,
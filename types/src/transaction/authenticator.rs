@@ -57,6 +57,15 @@ pub enum TransactionAuthenticator {
         secondary_signer_addresses: Vec<AccountAddress>,
         secondary_signers: Vec<AccountAuthenticator>,
     },
+    /// Multi-agent transaction whose gas is paid by a separate fee payer account. The fee payer
+    /// signs the transaction but doesn't take part in its execution.
+    FeePayer {
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    },
 }
 
 impl TransactionAuthenticator {
@@ -92,6 +101,23 @@ impl TransactionAuthenticator {
         }
     }
 
+    /// Create a fee payer authenticator
+    pub fn fee_payer(
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        Self::FeePayer {
+            sender,
+            secondary_signer_addresses,
+            secondary_signers,
+            fee_payer_address,
+            fee_payer_signer,
+        }
+    }
+
     /// Return Ok if all AccountAuthenticator's public keys match their signatures, Err otherwise
     pub fn verify(&self, raw_txn: &RawTransaction) -> Result<()> {
         let num_sigs: usize = self.sender().number_of_signatures()
@@ -99,7 +125,10 @@ impl TransactionAuthenticator {
                 .secondary_signers()
                 .iter()
                 .map(|auth| auth.number_of_signatures())
-                .sum::<usize>();
+                .sum::<usize>()
+            + self
+                .fee_payer_signer()
+                .map_or(0, |auth| auth.number_of_signatures());
         if num_sigs > MAX_NUM_OF_SIGS {
             return Err(Error::new(AuthenticationError::MaxSignaturesExceeded));
         }
@@ -127,6 +156,24 @@ impl TransactionAuthenticator {
                 }
                 Ok(())
             }
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let message = RawTransactionWithData::new_fee_payer(
+                    raw_txn.clone(),
+                    secondary_signer_addresses.clone(),
+                    *fee_payer_address,
+                );
+                sender.verify(&message)?;
+                for signer in secondary_signers {
+                    signer.verify(&message)?;
+                }
+                fee_payer_signer.verify(&message)
+            }
         }
     }
 
@@ -143,7 +190,7 @@ impl TransactionAuthenticator {
                 public_key,
                 signature,
             } => AccountAuthenticator::multi_ed25519(public_key.clone(), signature.clone()),
-            Self::MultiAgent { sender, .. } => sender.clone(),
+            Self::MultiAgent { sender, .. } | Self::FeePayer { sender, .. } => sender.clone(),
         }
     }
 
@@ -158,6 +205,10 @@ impl TransactionAuthenticator {
                 sender: _,
                 secondary_signer_addresses,
                 ..
+            }
+            | Self::FeePayer {
+                secondary_signer_addresses,
+                ..
             } => secondary_signer_addresses.to_vec(),
         }
     }
@@ -173,9 +224,31 @@ impl TransactionAuthenticator {
                 sender: _,
                 secondary_signer_addresses: _,
                 secondary_signers,
+            }
+            | Self::FeePayer {
+                secondary_signers, ..
             } => secondary_signers.to_vec(),
         }
     }
+
+    /// The account paying for gas, if it isn't the sender
+    pub fn fee_payer_address(&self) -> Option<AccountAddress> {
+        match self {
+            Self::FeePayer {
+                fee_payer_address, ..
+            } => Some(*fee_payer_address),
+            _ => None,
+        }
+    }
+
+    pub fn fee_payer_signer(&self) -> Option<AccountAuthenticator> {
+        match self {
+            Self::FeePayer {
+                fee_payer_signer, ..
+            } => Some(fee_payer_signer.clone()),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -224,6 +297,33 @@ impl fmt::Display for TransactionAuthenticator {
                     sender, sec_addrs, sec_signers,
                 )
             }
+            Self::FeePayer {
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            } => {
+                let mut sec_addrs: String = "".to_string();
+                for sec_addr in secondary_signer_addresses {
+                    sec_addrs = format!("{}\n\t\t\t{:#?},", sec_addrs, sec_addr);
+                }
+                let mut sec_signers: String = "".to_string();
+                for sec_signer in secondary_signers {
+                    sec_signers = format!("{}\n\t\t\t{:#?},", sec_signers, sec_signer);
+                }
+                write!(
+                    f,
+                    "TransactionAuthenticator[\n\
+                        \tscheme: FeePayer, \n\
+                        \tsender: {}\n\
+                        \tsecondary signer addresses: {}\n\
+                        \tsecondary signers: {}\n\
+                        \tfee payer address: {}\n\
+                        \tfee payer signer: {}]",
+                    sender, sec_addrs, sec_signers, fee_payer_address, fee_payer_signer,
+                )
+            }
         }
     }
 }
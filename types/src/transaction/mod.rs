@@ -37,11 +37,13 @@ use std::{
 pub mod authenticator;
 mod change_set;
 mod module;
+mod multisig;
 mod script;
 mod transaction_argument;
 
 pub use change_set::ChangeSet;
 pub use module::{Module, ModuleBundle};
+pub use multisig::{Multisig, MultisigTransactionPayload};
 pub use script::{
     ArgumentABI, EntryABI, EntryFunction, EntryFunctionABI, Script, TransactionScriptABI,
     TypeArgumentABI,
@@ -266,6 +268,63 @@ impl RawTransaction {
         ))
     }
 
+    /// Signs the given fee payer `RawTransaction`, which is a multi-agent transaction whose gas is
+    /// paid by `fee_payer_address` instead of the sender. The fee payer signs the transaction
+    /// along with the sender and the secondary signers.
+    ///
+    /// The order and length of the secondary keys provided here have to match the order and
+    /// length of the `secondary_signers`.
+    pub fn sign_fee_payer(
+        self,
+        sender_private_key: &Ed25519PrivateKey,
+        secondary_signers: Vec<AccountAddress>,
+        secondary_private_keys: Vec<&Ed25519PrivateKey>,
+        fee_payer_address: AccountAddress,
+        fee_payer_private_key: &Ed25519PrivateKey,
+    ) -> Result<SignatureCheckedTransaction> {
+        let message = RawTransactionWithData::new_fee_payer(
+            self.clone(),
+            secondary_signers.clone(),
+            fee_payer_address,
+        );
+        let sender_signature = sender_private_key.sign(&message)?;
+        let sender_authenticator = AccountAuthenticator::ed25519(
+            Ed25519PublicKey::from(sender_private_key),
+            sender_signature,
+        );
+
+        if secondary_private_keys.len() != secondary_signers.len() {
+            return Err(format_err!(
+                "number of secondary private keys and number of secondary signers don't match"
+            ));
+        }
+        let mut secondary_authenticators = vec![];
+        for priv_key in secondary_private_keys {
+            let signature = priv_key.sign(&message)?;
+            secondary_authenticators.push(AccountAuthenticator::ed25519(
+                Ed25519PublicKey::from(priv_key),
+                signature,
+            ));
+        }
+
+        let fee_payer_signature = fee_payer_private_key.sign(&message)?;
+        let fee_payer_authenticator = AccountAuthenticator::ed25519(
+            Ed25519PublicKey::from(fee_payer_private_key),
+            fee_payer_signature,
+        );
+
+        Ok(SignatureCheckedTransaction(
+            SignedTransaction::new_fee_payer(
+                self,
+                sender_authenticator,
+                secondary_signers,
+                secondary_authenticators,
+                fee_payer_address,
+                fee_payer_authenticator,
+            ),
+        ))
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     pub fn multi_sign_for_testing(
         self,
@@ -293,6 +352,10 @@ impl RawTransaction {
                 script_fn.args().to_vec(),
             ),
             TransactionPayload::ModuleBundle(_) => ("module publishing".to_string(), vec![]),
+            TransactionPayload::Multisig(multisig) => (
+                format!("multisig transaction for {}", multisig.multisig_address),
+                vec![],
+            ),
         };
         let mut f_args: String = "".to_string();
         for arg in args {
@@ -341,6 +404,11 @@ pub enum RawTransactionWithData {
         raw_txn: RawTransaction,
         secondary_signer_addresses: Vec<AccountAddress>,
     },
+    MultiAgentWithFeePayer {
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: AccountAddress,
+    },
 }
 
 impl RawTransactionWithData {
@@ -353,6 +421,18 @@ impl RawTransactionWithData {
             secondary_signer_addresses,
         }
     }
+
+    pub fn new_fee_payer(
+        raw_txn: RawTransaction,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        fee_payer_address: AccountAddress,
+    ) -> Self {
+        Self::MultiAgentWithFeePayer {
+            raw_txn,
+            secondary_signer_addresses,
+            fee_payer_address,
+        }
+    }
 }
 
 /// Different kinds of transactions.
@@ -364,6 +444,9 @@ pub enum TransactionPayload {
     ModuleBundle(ModuleBundle),
     /// A transaction that executes an existing entry function published on-chain.
     EntryFunction(EntryFunction),
+    /// A multisig transaction that allows an owner of a multisig account to execute a
+    /// pre-approved transaction as the multisig account.
+    Multisig(Multisig),
 }
 
 impl TransactionPayload {
@@ -511,6 +594,27 @@ impl SignedTransaction {
         }
     }
 
+    pub fn new_fee_payer(
+        raw_txn: RawTransaction,
+        sender: AccountAuthenticator,
+        secondary_signer_addresses: Vec<AccountAddress>,
+        secondary_signers: Vec<AccountAuthenticator>,
+        fee_payer_address: AccountAddress,
+        fee_payer_signer: AccountAuthenticator,
+    ) -> Self {
+        SignedTransaction {
+            raw_txn,
+            authenticator: TransactionAuthenticator::fee_payer(
+                sender,
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer_address,
+                fee_payer_signer,
+            ),
+            size: OnceCell::new(),
+        }
+    }
+
     pub fn new_with_authenticator(
         raw_txn: RawTransaction,
         authenticator: TransactionAuthenticator,
@@ -582,6 +686,7 @@ impl SignedTransaction {
     pub fn contains_duplicate_signers(&self) -> bool {
         let mut all_signer_addresses = self.authenticator.secondary_signer_addreses();
         all_signer_addresses.push(self.sender());
+        all_signer_addresses.extend(self.authenticator.fee_payer_address());
         let mut s = BTreeSet::new();
         all_signer_addresses.iter().any(|a| !s.insert(*a))
     }
@@ -600,7 +705,7 @@ impl SignedTransaction {
     pub fn is_multi_agent(&self) -> bool {
         matches!(
             self.authenticator,
-            TransactionAuthenticator::MultiAgent { .. }
+            TransactionAuthenticator::MultiAgent { .. } | TransactionAuthenticator::FeePayer { .. }
         )
     }
 
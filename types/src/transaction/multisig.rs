@@ -0,0 +1,23 @@
+// Copyright (c) Aptos
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::transaction::EntryFunction;
+use move_core_types::account_address::AccountAddress;
+use serde::{Deserialize, Serialize};
+
+/// A multisig transaction that allows an owner of a multisig account to execute a pre-approved
+/// transaction as the multisig account.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Multisig {
+    pub multisig_address: AccountAddress,
+
+    // Transaction payload is optional if already stored on chain.
+    pub transaction_payload: Option<MultisigTransactionPayload>,
+}
+
+/// The payload a multisig account executes once enough of its owners have approved it.
+/// It is stored on chain in its BCS encoded form.
+#[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
+pub enum MultisigTransactionPayload {
+    EntryFunction(EntryFunction),
+}
@@ -320,6 +320,52 @@ fn test_validate_account_doesnt_exist() {
     );
 }
 
+#[test]
+fn test_validate_fee_payer_doesnt_exist() {
+    let vm_validator = TestValidator::new();
+
+    let address = account_config::aptos_test_root_address();
+    let random_account_addr = account_address::AccountAddress::random();
+    let transaction = transaction_test_helpers::get_test_unchecked_fee_payer_txn(
+        address,
+        1,
+        &vm_genesis::GENESIS_KEYPAIR.0,
+        vm_genesis::GENESIS_KEYPAIR.1.clone(),
+        random_account_addr,
+        &vm_genesis::GENESIS_KEYPAIR.0,
+        vm_genesis::GENESIS_KEYPAIR.1.clone(),
+        None,
+    );
+    let ret = vm_validator.validate_transaction(transaction).unwrap();
+    assert_eq!(
+        ret.status().unwrap(),
+        StatusCode::SENDING_ACCOUNT_DOES_NOT_EXIST
+    );
+}
+
+#[test]
+fn test_validate_fee_payer_invalid_auth_key() {
+    let vm_validator = TestValidator::new();
+
+    let mut rng = ::rand::rngs::StdRng::from_seed([1u8; 32]);
+    let other_private_key = Ed25519PrivateKey::generate(&mut rng);
+    // The fee payer signs with a key that doesn't match its authentication key
+
+    let address = account_config::aptos_test_root_address();
+    let transaction = transaction_test_helpers::get_test_unchecked_fee_payer_txn(
+        address,
+        1,
+        &vm_genesis::GENESIS_KEYPAIR.0,
+        vm_genesis::GENESIS_KEYPAIR.1.clone(),
+        AccountAddress::ONE,
+        &other_private_key,
+        other_private_key.public_key(),
+        None,
+    );
+    let ret = vm_validator.validate_transaction(transaction).unwrap();
+    assert_eq!(ret.status().unwrap(), StatusCode::INVALID_AUTH_KEY);
+}
+
 #[test]
 fn test_validate_sequence_number_too_new() {
     let vm_validator = TestValidator::new();